@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-  collections::{BTreeMap, HashMap},
+  collections::{BTreeMap, BTreeSet, HashMap},
   env, fs,
   path::{Path, PathBuf},
 };
@@ -11,8 +11,9 @@ use std::{
 use anyhow::{Context, Result};
 use tauri_utils::{
   acl::{
-    capability::Capability, manifest::Manifest, schema::CAPABILITIES_SCHEMA_FOLDER_PATH,
-    ACL_MANIFESTS_FILE_NAME, APP_ACL_KEY, CAPABILITIES_FILE_NAME,
+    build::PermissionIdentifierFormatter,
+    capability::Capability, manifest::Manifest, ACL_MANIFESTS_FILE_NAME, APP_ACL_KEY,
+    CAPABILITIES_FILE_NAME,
   },
   platform::Target,
   write_if_changed,
@@ -29,8 +30,9 @@ use crate::Attributes;
 #[derive(Debug, Default, Clone)]
 pub struct InlinedPlugin {
   commands: &'static [&'static str],
-  permissions_path_pattern: Option<&'static str>,
+  permissions_path_pattern: Option<PathBuf>,
   default: Option<DefaultPermissionRule>,
+  permission_identifier_format: Option<PermissionIdentifierFormatter>,
 }
 
 /// Variants of a generated default permission that can be used on an [`InlinedPlugin`].
@@ -41,7 +43,8 @@ pub enum DefaultPermissionRule {
   /// Allow the given list of permissions.
   ///
   /// Note that the list refers to permissions instead of command names,
-  /// so for example a command called `execute` would need to be allowed as `allow-execute`.
+  /// so for example a command called `execute` would need to be allowed as `allow-execute`,
+  /// or using the identifier set by [`InlinedPlugin::permission_identifier_format`].
   Allow(Vec<String>),
 }
 
@@ -62,8 +65,16 @@ impl InlinedPlugin {
   /// **Note:** You must emit [rerun-if-changed] instructions for the plugin permissions directory.
   ///
   /// By default it is `./permissions/$plugin-name/**/*`
-  pub fn permissions_path_pattern(mut self, pattern: &'static str) -> Self {
-    self.permissions_path_pattern.replace(pattern);
+  pub fn permissions_path_pattern(self, pattern: &'static str) -> Self {
+    self.permissions_path_pattern_owned(pattern)
+  }
+
+  /// Sets a glob pattern that is used to find the permissions of this inlined plugin, computed at
+  /// build time, for instance from the `CARGO_MANIFEST_DIR` environment variable.
+  ///
+  /// See [`Self::permissions_path_pattern`] for more information.
+  pub fn permissions_path_pattern_owned(mut self, pattern: impl Into<PathBuf>) -> Self {
+    self.permissions_path_pattern.replace(pattern.into());
     self
   }
 
@@ -74,6 +85,28 @@ impl InlinedPlugin {
     self.default.replace(default);
     self
   }
+
+  /// Sets the function used to name the permissions autogenerated for [`Self::commands`].
+  ///
+  /// The function receives the command name and whether the permission allows (`true`) or denies (`false`) it,
+  /// and must return a valid permission identifier: lowercase ASCII alphanumeric characters separated by single
+  /// hyphens, underscores or dots, e.g. `allow-read-file` or `cmd.read_file.allow`.
+  ///
+  /// By default the permissions are named `allow-$command` and `deny-$command`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// tauri_build::InlinedPlugin::new()
+  ///   .commands(&["read_file"])
+  ///   .permission_identifier_format(|command, allow| {
+  ///     format!("cmd-{}-{}", command.replace('_', "-"), if allow { "allow" } else { "deny" })
+  ///   });
+  /// ```
+  pub fn permission_identifier_format(mut self, format: PermissionIdentifierFormatter) -> Self {
+    self.permission_identifier_format.replace(format);
+    self
+  }
 }
 
 /// Tauri application permission manifest.
@@ -82,10 +115,11 @@ impl InlinedPlugin {
 /// To change the glob pattern that is used to find permissions, use [`Self::permissions_path_pattern`].
 ///
 /// To autogenerate permissions for each of the app commands, see [`Self::commands`].
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub struct AppManifest {
   commands: &'static [&'static str],
-  permissions_path_pattern: Option<&'static str>,
+  permissions_path_pattern: Option<PathBuf>,
+  permission_identifier_format: Option<PermissionIdentifierFormatter>,
 }
 
 impl AppManifest {
@@ -105,15 +139,33 @@ impl AppManifest {
   /// **Note:** You must emit [rerun-if-changed] instructions for the permissions directory.
   ///
   /// By default it is `./permissions/**/*` ignoring any [`InlinedPlugin`].
-  pub fn permissions_path_pattern(mut self, pattern: &'static str) -> Self {
-    self.permissions_path_pattern.replace(pattern);
+  pub fn permissions_path_pattern(self, pattern: &'static str) -> Self {
+    self.permissions_path_pattern_owned(pattern)
+  }
+
+  /// Sets a glob pattern that is used to find the permissions of the app, computed at build time,
+  /// for instance from the `CARGO_MANIFEST_DIR` environment variable.
+  ///
+  /// See [`Self::permissions_path_pattern`] for more information.
+  pub fn permissions_path_pattern_owned(mut self, pattern: impl Into<PathBuf>) -> Self {
+    self.permissions_path_pattern.replace(pattern.into());
+    self
+  }
+
+  /// Sets the function used to name the permissions autogenerated for [`Self::commands`].
+  ///
+  /// See [`InlinedPlugin::permission_identifier_format`] for more information.
+  pub fn permission_identifier_format(mut self, format: PermissionIdentifierFormatter) -> Self {
+    self.permission_identifier_format.replace(format);
     self
   }
 }
 
 /// Saves capabilities in a file inside the project, mainly to be read by tauri-cli.
-fn save_capabilities(capabilities: &BTreeMap<String, Capability>) -> Result<PathBuf> {
-  let dir = Path::new(CAPABILITIES_SCHEMA_FOLDER_PATH);
+fn save_capabilities(
+  dir: &Path,
+  capabilities: &BTreeMap<String, Capability>,
+) -> Result<PathBuf> {
   fs::create_dir_all(dir)?;
 
   let path = dir.join(CAPABILITIES_FILE_NAME);
@@ -124,8 +176,10 @@ fn save_capabilities(capabilities: &BTreeMap<String, Capability>) -> Result<Path
 }
 
 /// Saves ACL manifests in a file inside the project, mainly to be read by tauri-cli.
-fn save_acl_manifests(acl_manifests: &BTreeMap<String, Manifest>) -> Result<PathBuf> {
-  let dir = Path::new(CAPABILITIES_SCHEMA_FOLDER_PATH);
+fn save_acl_manifests(
+  dir: &Path,
+  acl_manifests: &BTreeMap<String, Manifest>,
+) -> Result<PathBuf> {
   fs::create_dir_all(dir)?;
 
   let path = dir.join(ACL_MANIFESTS_FILE_NAME);
@@ -168,11 +222,14 @@ fn inline_plugins(
     let mut permission_files = if plugin.commands.is_empty() {
       Vec::new()
     } else {
-      let autogenerated = tauri_utils::acl::build::autogenerate_command_permissions(
+      let autogenerated = tauri_utils::acl::build::autogenerate_command_permissions_with_format(
         &plugin_out_dir,
         plugin.commands,
         "",
         false,
+        plugin
+          .permission_identifier_format
+          .unwrap_or(tauri_utils::acl::build::default_permission_identifier),
       );
 
       let default_permissions = plugin.default.map(|default| match default {
@@ -208,9 +265,9 @@ permissions = [{default_permissions}]
       )?
     };
 
-    if let Some(pattern) = plugin.permissions_path_pattern {
+    if let Some(pattern) = &plugin.permissions_path_pattern {
       permission_files.extend(tauri_utils::acl::build::define_permissions(
-        pattern,
+        &pattern.to_string_lossy(),
         name,
         &plugin_out_dir,
         |_| true,
@@ -245,7 +302,7 @@ permissions = [{default_permissions}]
 
 fn app_manifest_permissions(
   out_dir: &Path,
-  manifest: AppManifest,
+  manifest: &AppManifest,
   inlined_plugins: &HashMap<&'static str, InlinedPlugin>,
 ) -> Result<Manifest> {
   let app_out_dir = out_dir.join("app-manifest");
@@ -256,11 +313,14 @@ fn app_manifest_permissions(
     Vec::new()
   } else {
     let autogenerated_path = Path::new("./permissions/autogenerated");
-    tauri_utils::acl::build::autogenerate_command_permissions(
+    tauri_utils::acl::build::autogenerate_command_permissions_with_format(
       autogenerated_path,
       manifest.commands,
       "",
       false,
+      manifest
+        .permission_identifier_format
+        .unwrap_or(tauri_utils::acl::build::default_permission_identifier),
     );
     tauri_utils::acl::build::define_permissions(
       &autogenerated_path.join("*").to_string_lossy(),
@@ -270,9 +330,9 @@ fn app_manifest_permissions(
     )?
   };
 
-  if let Some(pattern) = manifest.permissions_path_pattern {
+  if let Some(pattern) = &manifest.permissions_path_pattern {
     permission_files.extend(tauri_utils::acl::build::define_permissions(
-      pattern,
+      &pattern.to_string_lossy(),
       pkg_name,
       &app_out_dir,
       |_| true,
@@ -314,6 +374,123 @@ fn app_manifest_permissions(
   ))
 }
 
+/// Escapes a markdown table cell, so a `|` or a line break doesn't end the cell or the row.
+fn table_cell(text: &str) -> String {
+  text
+    .trim()
+    .replace('|', "\\|")
+    .replace("\r\n", "<br>")
+    .replace(['\r', '\n'], "<br>")
+}
+
+/// Renders the permission documentation of a manifest as a markdown table.
+fn permission_docs(name: &str, manifest: &Manifest) -> String {
+  fn collect_default(manifest: &Manifest, permissions: &[String], defaults: &mut BTreeSet<String>) {
+    for permission in permissions {
+      if defaults.insert(permission.clone()) {
+        if let Some(set) = manifest.permission_sets.get(permission) {
+          collect_default(manifest, &set.permissions, defaults);
+        }
+      }
+    }
+  }
+
+  fn commands_list(commands: &[String]) -> String {
+    commands
+      .iter()
+      .map(|c| format!("`{c}`"))
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  let prefix = if name == APP_ACL_KEY {
+    "".to_string()
+  } else {
+    format!("{name}:")
+  };
+
+  let mut defaults = BTreeSet::new();
+  if let Some(default) = &manifest.default_permission {
+    collect_default(manifest, &default.permissions, &mut defaults);
+  }
+
+  let mut docs = String::new();
+
+  if let Some(default) = &manifest.default_permission {
+    docs.push_str("## Default Permission\n\n");
+    if !default.description.is_empty() {
+      docs.push_str(&default.description);
+      docs.push_str("\n\n");
+    }
+    for permission in &default.permissions {
+      docs.push_str(&format!("- `{permission}`\n"));
+    }
+    docs.push('\n');
+  }
+
+  docs.push_str("## Permission Table\n\n");
+  docs.push_str("| Identifier | Description | Commands Allowed | Commands Denied | Default |\n");
+  docs.push_str("| --- | --- | --- | --- | --- |\n");
+
+  for set in manifest.permission_sets.values() {
+    docs.push_str(&format!(
+      "| `{prefix}{}` | {} | {} | | {} |\n",
+      set.identifier,
+      table_cell(&set.description),
+      set
+        .permissions
+        .iter()
+        .map(|p| format!("`{p}`"))
+        .collect::<Vec<_>>()
+        .join(", "),
+      if defaults.contains(&set.identifier) {
+        "yes"
+      } else {
+        "no"
+      },
+    ));
+  }
+
+  for permission in manifest.permissions.values() {
+    docs.push_str(&format!(
+      "| `{prefix}{}` | {} | {} | {} | {} |\n",
+      permission.identifier,
+      table_cell(permission.description.as_deref().unwrap_or_default()),
+      commands_list(&permission.commands.allow),
+      commands_list(&permission.commands.deny),
+      if defaults.contains(&permission.identifier) {
+        "yes"
+      } else {
+        "no"
+      },
+    ));
+  }
+
+  docs
+}
+
+/// Writes the permission documentation of the app manifest and inlined plugins to the given directory.
+fn save_permission_docs(
+  dir: &Path,
+  acl_manifests: &BTreeMap<String, Manifest>,
+  inlined_plugins: &HashMap<&'static str, InlinedPlugin>,
+) -> Result<()> {
+  fs::create_dir_all(dir)?;
+
+  for (name, manifest) in acl_manifests {
+    if name != APP_ACL_KEY && !inlined_plugins.contains_key(name.as_str()) {
+      continue;
+    }
+
+    let file_name = if name == APP_ACL_KEY { "app" } else { name };
+    let path = dir.join(format!("{file_name}.md"));
+    write_if_changed(&path, permission_docs(name, manifest))
+      .with_context(|| format!("failed to write permission docs to {}", path.display()))?;
+  }
+
+  Ok(())
+}
+
 fn validate_capabilities(
   acl_manifests: &BTreeMap<String, Manifest>,
   capabilities: &BTreeMap<String, Capability>,
@@ -377,12 +554,17 @@ fn validate_capabilities(
   Ok(())
 }
 
-pub fn build(out_dir: &Path, target: Target, attributes: &Attributes) -> super::Result<()> {
+pub fn build(
+  out_dir: &Path,
+  schemas_dir: &Path,
+  target: Target,
+  attributes: &Attributes,
+) -> super::Result<()> {
   let mut acl_manifests = read_plugins_manifests()?;
 
   let app_manifest = app_manifest_permissions(
     out_dir,
-    attributes.app_manifest,
+    &attributes.app_manifest,
     &attributes.inlined_plugins,
   )?;
   if app_manifest.default_permission.is_some()
@@ -394,10 +576,14 @@ pub fn build(out_dir: &Path, target: Target, attributes: &Attributes) -> super::
 
   acl_manifests.extend(inline_plugins(out_dir, attributes.inlined_plugins.clone())?);
 
-  let acl_manifests_path = save_acl_manifests(&acl_manifests)?;
+  let acl_manifests_path = save_acl_manifests(schemas_dir, &acl_manifests)?;
   fs::copy(acl_manifests_path, out_dir.join(ACL_MANIFESTS_FILE_NAME))?;
 
-  tauri_utils::acl::schema::generate_capability_schema(&acl_manifests, target)?;
+  if let Some(docs_path) = &attributes.permission_docs_path {
+    save_permission_docs(docs_path, &acl_manifests, &attributes.inlined_plugins)?;
+  }
+
+  tauri_utils::acl::schema::generate_capability_schema_in(&acl_manifests, target, schemas_dir)?;
 
   let capabilities = if let Some(pattern) = attributes.capabilities_path_pattern {
     tauri_utils::acl::build::parse_capabilities(pattern)?
@@ -407,10 +593,23 @@ pub fn build(out_dir: &Path, target: Target, attributes: &Attributes) -> super::
   };
   validate_capabilities(&acl_manifests, &capabilities)?;
 
-  let capabilities_path = save_capabilities(&capabilities)?;
+  let capabilities_path = save_capabilities(schemas_dir, &capabilities)?;
   fs::copy(capabilities_path, out_dir.join(CAPABILITIES_FILE_NAME))?;
 
   tauri_utils::plugin::save_global_api_scripts_paths(out_dir);
 
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::table_cell;
+
+  #[test]
+  fn table_cells_are_escaped() {
+    assert_eq!(
+      table_cell("Allows a | b.\r\nDenies c.\n"),
+      "Allows a \\| b.<br>Denies c."
+    );
+  }
+}
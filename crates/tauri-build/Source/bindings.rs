@@ -0,0 +1,1182 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! TypeScript bindings of the application commands, see [`crate::Attributes::typescript_bindings`].
+//!
+//! The bindings are rendered from the crate sources: every function annotated with `#[command]` becomes
+//! an async function calling `invoke`, and the structs and enums deriving `Serialize` or `Deserialize`
+//! used by the commands become TypeScript types, honoring their `serde` attributes.
+
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::Write,
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use cargo_toml::Manifest;
+use heck::{
+  ToKebabCase, ToLowerCamelCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+  ToUpperCamelCase,
+};
+use syn::{
+  ext::IdentExt, meta::ParseNestedMeta, Attribute, Expr, ExprLit, Field, Fields, FieldsNamed,
+  FnArg, GenericArgument, Item, ItemEnum, ItemFn, ItemStruct, Lit, LitStr, Meta, Pat,
+  PathArguments, ReturnType, Token, Type,
+};
+use tauri_utils::write_if_changed;
+
+/// Argument types resolved by Tauri instead of being read from the invoke payload.
+const INJECTED_TYPES: &[&str] = &[
+  "AppHandle",
+  "CancellationToken",
+  "CommandScope",
+  "GlobalScope",
+  "Progress",
+  "RawBytes",
+  "Request",
+  "RequestBodyStream",
+  "State",
+  "Webview",
+  "WebviewWindow",
+  "Window",
+  "WindowState",
+];
+
+/// Words that can't be used as parameter names.
+const RESERVED_WORDS: &[&str] = &[
+  "arguments",
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "eval",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "implements",
+  "import",
+  "in",
+  "instanceof",
+  "interface",
+  "let",
+  "new",
+  "null",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "static",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+];
+
+/// Renders the bindings of the commands defined in the crate sources and writes them to `path` if they changed.
+pub fn generate(
+  crate_dir: &Path,
+  manifest: &Manifest<cargo_toml::Value>,
+  path: &Path,
+) -> crate::Result<()> {
+  let mut sources = Sources::default();
+
+  let dirs = source_dirs(crate_dir, manifest);
+  for dir in &dirs {
+    println!("cargo:rerun-if-changed={}", dir.display());
+  }
+
+  let files = dirs
+    .iter()
+    .flat_map(|dir| rust_files(dir))
+    .collect::<BTreeSet<_>>();
+  for file in files {
+    let content =
+      fs::read_to_string(&file).with_context(|| format!("failed to read {}", file.display()))?;
+    // files that don't parse are reported by the compiler
+    if let Ok(file) = syn::parse_file(&content) {
+      sources.collect(&file.items);
+    }
+  }
+
+  let (bindings, warnings) = render(&sources);
+  for warning in warnings {
+    println!("cargo:warning={warning}");
+  }
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  write_if_changed(path, bindings)
+    .with_context(|| format!("failed to write TypeScript bindings to {}", path.display()))?;
+
+  Ok(())
+}
+
+/// The directories containing the library and binary targets of the crate.
+fn source_dirs(crate_dir: &Path, manifest: &Manifest<cargo_toml::Value>) -> BTreeSet<PathBuf> {
+  let lib = manifest
+    .lib
+    .as_ref()
+    .and_then(|lib| lib.path.clone())
+    .unwrap_or_else(|| "src/lib.rs".into());
+  let bins = manifest.bin.iter().filter_map(|bin| bin.path.clone());
+
+  [lib, "src/main.rs".into()]
+    .into_iter()
+    .chain(bins)
+    .filter_map(|path| crate_dir.join(path).parent().map(Path::to_path_buf))
+    .filter(|dir| dir.is_dir())
+    .collect()
+}
+
+fn rust_files(dir: &Path) -> impl Iterator<Item = PathBuf> {
+  walkdir::WalkDir::new(dir)
+    .into_iter()
+    .filter_entry(|entry| {
+      entry.depth() == 0 || !entry.file_name().to_string_lossy().starts_with('.')
+    })
+    .filter_map(Result::ok)
+    .filter(|entry| {
+      entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "rs")
+    })
+    .map(walkdir::DirEntry::into_path)
+}
+
+/// The commands and serializable types found in the crate sources.
+#[derive(Default)]
+struct Sources {
+  commands: Vec<Command>,
+  types: BTreeMap<String, TypeDefinition>,
+}
+
+impl Sources {
+  fn collect(&mut self, items: &[Item]) {
+    for item in items {
+      match item {
+        Item::Fn(function) => {
+          if let Some(command) = Command::parse(function) {
+            self.commands.push(command);
+          }
+        }
+        Item::Mod(module) => {
+          if let Some((_, items)) = &module.content {
+            self.collect(items);
+          }
+        }
+        Item::Struct(item) if derives_serde(&item.attrs) => {
+          self
+            .types
+            .entry(item.ident.to_string())
+            .or_insert_with(|| TypeDefinition::Struct(item.clone()));
+        }
+        Item::Enum(item) if derives_serde(&item.attrs) => {
+          self
+            .types
+            .entry(item.ident.to_string())
+            .or_insert_with(|| TypeDefinition::Enum(item.clone()));
+        }
+        _ => {}
+      }
+    }
+  }
+}
+
+enum TypeDefinition {
+  Struct(ItemStruct),
+  Enum(ItemEnum),
+}
+
+impl TypeDefinition {
+  fn attrs(&self) -> &[Attribute] {
+    match self {
+      Self::Struct(item) => &item.attrs,
+      Self::Enum(item) => &item.attrs,
+    }
+  }
+
+  fn generics(&self) -> Vec<String> {
+    let generics = match self {
+      Self::Struct(item) => &item.generics,
+      Self::Enum(item) => &item.generics,
+    };
+    generics
+      .type_params()
+      .map(|param| param.ident.to_string())
+      .collect()
+  }
+}
+
+struct Command {
+  name: String,
+  docs: Vec<String>,
+  deprecated: Option<String>,
+  args: Vec<CommandArg>,
+  output: Output,
+}
+
+struct CommandArg {
+  key: String,
+  ty: Type,
+  /// The argument is an `Option<T>` or has a `#[arg(default)]` value.
+  optional: bool,
+}
+
+enum Output {
+  Void,
+  Bytes,
+  Type(Box<Type>),
+}
+
+impl Command {
+  /// Reads the command defined by `function`, `None` if it isn't annotated with `#[command]`.
+  fn parse(function: &ItemFn) -> Option<Self> {
+    let attr = function.attrs.iter().find(|attr| {
+      attr
+        .path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "command")
+    })?;
+
+    let mut argument_case = "camelCase".to_string();
+    let mut raw_response = false;
+    let mut deprecated = deprecated(&function.attrs);
+    if let Meta::List(_) = attr.meta {
+      // invalid attributes are reported by the macro
+      attr
+        .parse_nested_meta(|meta| {
+          if meta.path.is_ident("rename_all") {
+            argument_case = meta.value()?.parse::<LitStr>()?.value();
+          } else if meta.path.is_ident("deprecated") {
+            deprecated.replace(meta.value()?.parse::<LitStr>()?.value());
+          } else if meta.path.is_ident("raw_response") {
+            raw_response = true;
+          } else {
+            skip_value(&meta)?;
+          }
+          Ok(())
+        })
+        .ok()?;
+    }
+
+    let mut args = Vec::new();
+    for arg in &function.sig.inputs {
+      let FnArg::Typed(arg) = arg else {
+        continue;
+      };
+
+      let mut rename = None;
+      let mut default = false;
+      let mut injected = is_injected(&arg.ty);
+      for attr in arg.attrs.iter().filter(|attr| attr.path().is_ident("arg")) {
+        attr
+          .parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+              rename.replace(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+              default = true;
+              skip_value(&meta)?;
+            } else if meta.path.is_ident("window") || meta.path.is_ident("from_request") {
+              injected = true;
+              skip_value(&meta)?;
+            } else {
+              skip_value(&meta)?;
+            }
+            Ok(())
+          })
+          .ok()?;
+      }
+
+      if injected {
+        continue;
+      }
+
+      let key = match rename {
+        Some(rename) => rename,
+        None => {
+          let key = match arg.pat.as_ref() {
+            Pat::Ident(pat) => pat.ident.unraw().to_string(),
+            Pat::Struct(pat) => pat.path.segments.last()?.ident.to_string(),
+            Pat::TupleStruct(pat) => pat.path.segments.last()?.ident.to_string(),
+            _ => String::new(),
+          };
+          match argument_case.as_str() {
+            "snake_case" => key.to_snake_case(),
+            "kebab-case" => key.to_kebab_case(),
+            _ => key.to_lower_camel_case(),
+          }
+        }
+      };
+
+      args.push(CommandArg {
+        key,
+        optional: default || option_type(&arg.ty).is_some(),
+        ty: (*arg.ty).clone(),
+      });
+    }
+
+    let output = match &function.sig.output {
+      _ if raw_response => Output::Bytes,
+      ReturnType::Default => Output::Void,
+      ReturnType::Type(_, ty) => match ok_type(ty) {
+        Type::Tuple(tuple) if tuple.elems.is_empty() => Output::Void,
+        ty => Output::Type(Box::new(ty.clone())),
+      },
+    };
+
+    Some(Self {
+      name: function.sig.ident.unraw().to_string(),
+      docs: doc_lines(&function.attrs),
+      deprecated,
+      args,
+      output,
+    })
+  }
+}
+
+/// The `serde` attributes of a container, variant or field.
+#[derive(Default)]
+struct SerdeAttributes {
+  rename: Option<String>,
+  rename_all: Option<String>,
+  rename_all_fields: Option<String>,
+  tag: Option<String>,
+  content: Option<String>,
+  untagged: bool,
+  transparent: bool,
+  skip: bool,
+  /// The value can be missing when deserializing or skipped when serializing.
+  optional: bool,
+  flatten: bool,
+  /// The value has a custom serialization, its type is unknown.
+  custom: bool,
+}
+
+impl SerdeAttributes {
+  fn parse(attrs: &[Attribute]) -> Self {
+    let mut attributes = Self::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+      // invalid attributes are reported by the derive macro
+      let _ = attr.parse_nested_meta(|meta| {
+        let name = meta
+          .path
+          .get_ident()
+          .map(ToString::to_string)
+          .unwrap_or_default();
+        match name.as_str() {
+          "rename" => attributes.rename = serialized_name(&meta)?,
+          "rename_all" => attributes.rename_all = serialized_name(&meta)?,
+          "rename_all_fields" => attributes.rename_all_fields = serialized_name(&meta)?,
+          "tag" => attributes.tag = Some(meta.value()?.parse::<LitStr>()?.value()),
+          "content" => attributes.content = Some(meta.value()?.parse::<LitStr>()?.value()),
+          "untagged" => attributes.untagged = true,
+          "transparent" => attributes.transparent = true,
+          "flatten" => attributes.flatten = true,
+          "skip" => attributes.skip = true,
+          "default" | "skip_serializing" | "skip_deserializing" | "skip_serializing_if" => {
+            attributes.optional = true;
+            skip_value(&meta)?;
+          }
+          "with" | "serialize_with" | "deserialize_with" => {
+            attributes.custom = true;
+            skip_value(&meta)?;
+          }
+          _ => skip_value(&meta)?,
+        }
+        Ok(())
+      });
+    }
+
+    attributes
+  }
+}
+
+/// Reads `name = "value"` or the `serialize` name of `name(serialize = "value", deserialize = "other")`.
+fn serialized_name(meta: &ParseNestedMeta) -> syn::Result<Option<String>> {
+  if meta.input.peek(Token![=]) {
+    return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+  }
+
+  let mut name = None;
+  meta.parse_nested_meta(|inner| {
+    let value = inner.value()?.parse::<LitStr>()?.value();
+    if inner.path.is_ident("serialize") {
+      name.replace(value);
+    }
+    Ok(())
+  })?;
+  Ok(name)
+}
+
+/// Consumes the value of an attribute we have no use for.
+fn skip_value(meta: &ParseNestedMeta) -> syn::Result<()> {
+  if meta.input.peek(Token![=]) {
+    meta.value()?.parse::<Expr>()?;
+  } else if meta.input.peek(syn::token::Paren) {
+    meta.parse_nested_meta(|inner| skip_value(&inner))?;
+  }
+  Ok(())
+}
+
+fn derives_serde(attrs: &[Attribute]) -> bool {
+  attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("derive"))
+    .filter_map(|attr| {
+      attr
+        .parse_args_with(syn::punctuated::Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        .ok()
+    })
+    .flatten()
+    .any(|path| {
+      path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Serialize" || segment.ident == "Deserialize")
+    })
+}
+
+/// The message of a `#[deprecated]` attribute.
+fn deprecated(attrs: &[Attribute]) -> Option<String> {
+  let attr = attrs
+    .iter()
+    .find(|attr| attr.path().is_ident("deprecated"))?;
+  let mut note = String::new();
+  match &attr.meta {
+    Meta::NameValue(meta) => {
+      if let Expr::Lit(ExprLit {
+        lit: Lit::Str(s), ..
+      }) = &meta.value
+      {
+        note = s.value();
+      }
+    }
+    Meta::List(_) => {
+      let _ = attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("note") {
+          note = meta.value()?.parse::<LitStr>()?.value();
+        } else {
+          skip_value(&meta)?;
+        }
+        Ok(())
+      });
+    }
+    Meta::Path(_) => {}
+  }
+  Some(note)
+}
+
+/// The doc comment lines, without the leading space.
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+  let docs = attrs
+    .iter()
+    .filter_map(|attr| match &attr.meta {
+      Meta::NameValue(doc) if doc.path.is_ident("doc") => match &doc.value {
+        Expr::Lit(ExprLit {
+          lit: Lit::Str(s), ..
+        }) => Some(s.value()),
+        _ => None,
+      },
+      _ => None,
+    })
+    .flat_map(|doc| {
+      doc
+        .split('\n')
+        .map(|line| {
+          line
+            .strip_prefix(' ')
+            .unwrap_or(line)
+            .trim_end()
+            .to_string()
+        })
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>()
+    .join("\n");
+
+  docs.trim().lines().map(ToString::to_string).collect()
+}
+
+/// The last segment of a type path and its type arguments.
+fn path_segment(ty: &Type) -> Option<(String, Vec<&Type>)> {
+  let Type::Path(path) = ty else {
+    return None;
+  };
+  let segment = path.path.segments.last()?;
+  let args = match &segment.arguments {
+    PathArguments::AngleBracketed(args) => args
+      .args
+      .iter()
+      .filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+      })
+      .collect(),
+    _ => Vec::new(),
+  };
+  Some((segment.ident.to_string(), args))
+}
+
+fn is_injected(ty: &Type) -> bool {
+  let ty = match ty {
+    Type::Reference(reference) => &reference.elem,
+    ty => ty,
+  };
+  path_segment(ty).is_some_and(|(name, _)| INJECTED_TYPES.contains(&name.as_str()))
+}
+
+fn option_type(ty: &Type) -> Option<&Type> {
+  match path_segment(ty) {
+    Some((name, args)) if name == "Option" && args.len() == 1 => Some(args[0]),
+    _ => None,
+  }
+}
+
+/// The `T` of a `Result<T, E>`, or the type itself.
+fn ok_type(ty: &Type) -> &Type {
+  match path_segment(ty) {
+    Some((name, args)) if name == "Result" && !args.is_empty() => args[0],
+    _ => ty,
+  }
+}
+
+/// Renders a type the way it is usually written.
+fn rust_type(ty: &Type) -> String {
+  match ty {
+    Type::Reference(reference) => format!("&{}", rust_type(&reference.elem)),
+    Type::Paren(paren) => rust_type(&paren.elem),
+    Type::Group(group) => rust_type(&group.elem),
+    Type::Slice(slice) => format!("[{}]", rust_type(&slice.elem)),
+    Type::Array(array) => format!("[{}; _]", rust_type(&array.elem)),
+    Type::Tuple(tuple) => format!(
+      "({})",
+      tuple
+        .elems
+        .iter()
+        .map(rust_type)
+        .collect::<Vec<_>>()
+        .join(", ")
+    ),
+    Type::Path(path) => path
+      .path
+      .segments
+      .iter()
+      .map(|segment| match &segment.arguments {
+        PathArguments::AngleBracketed(args) => format!(
+          "{}<{}>",
+          segment.ident,
+          args
+            .args
+            .iter()
+            .map(|arg| match arg {
+              GenericArgument::Type(ty) => rust_type(ty),
+              GenericArgument::Lifetime(lifetime) => lifetime.to_string(),
+              _ => "_".into(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+        ),
+        _ => segment.ident.to_string(),
+      })
+      .collect::<Vec<_>>()
+      .join("::"),
+    _ => "_".into(),
+  }
+}
+
+/// Maps Rust types to TypeScript types, collecting the named types to render.
+struct Renderer<'a> {
+  types: &'a BTreeMap<String, TypeDefinition>,
+  referenced: BTreeSet<String>,
+  /// The generic parameters of the type definition being rendered.
+  generics: Vec<String>,
+  /// The types that couldn't be mapped since the last call to [`Self::resolve`].
+  unresolved: Vec<String>,
+  uses_channel: bool,
+}
+
+impl<'a> Renderer<'a> {
+  fn new(types: &'a BTreeMap<String, TypeDefinition>) -> Self {
+    Self {
+      types,
+      referenced: BTreeSet::new(),
+      generics: Vec::new(),
+      unresolved: Vec::new(),
+      uses_channel: false,
+    }
+  }
+
+  /// The TypeScript type of `ty`, with a warning for every part of it typed as `unknown`.
+  fn resolve(&mut self, ty: &Type, context: &str, warnings: &mut Vec<String>) -> String {
+    let ty = self.ts_type(ty);
+    for unresolved in self.unresolved.drain(..) {
+      warnings.push(format!(
+        "{context} uses `{unresolved}`, which is not a known serializable type, using `unknown` in the TypeScript bindings"
+      ));
+    }
+    ty
+  }
+
+  fn ts_type(&mut self, ty: &Type) -> String {
+    match ty {
+      Type::Reference(reference) => self.ts_type(&reference.elem),
+      Type::Paren(paren) => self.ts_type(&paren.elem),
+      Type::Group(group) => self.ts_type(&group.elem),
+      Type::Tuple(tuple) if tuple.elems.is_empty() => "null".into(),
+      Type::Tuple(tuple) => format!(
+        "[{}]",
+        tuple
+          .elems
+          .iter()
+          .map(|ty| self.ts_type(ty))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      Type::Slice(slice) => array_of(self.ts_type(&slice.elem)),
+      Type::Array(array) => array_of(self.ts_type(&array.elem)),
+      Type::Path(path) if path.qself.is_none() => self.path_type(ty),
+      ty => self.unresolved(ty),
+    }
+  }
+
+  fn path_type(&mut self, ty: &Type) -> String {
+    let Some((name, args)) = path_segment(ty) else {
+      return self.unresolved(ty);
+    };
+    let types = self.types;
+
+    match (name.as_str(), args.as_slice()) {
+      (name, []) if self.generics.iter().any(|param| param == name) => name.into(),
+      ("String" | "str" | "char" | "PathBuf" | "Path" | "OsString" | "Url" | "Uuid", []) => {
+        "string".into()
+      }
+      (
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32" | "i64" | "i128"
+        | "isize" | "f32" | "f64",
+        [],
+      ) => "number".into(),
+      ("bool", []) => "boolean".into(),
+      ("Value" | "JsonValue", []) => "unknown".into(),
+      ("Response", []) => "ArrayBuffer".into(),
+      ("Box" | "Arc" | "Rc" | "Cow", [inner]) => self.ts_type(inner),
+      ("Option", [inner]) => format!("{} | null", self.ts_type(inner)),
+      ("Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "IndexSet", [inner]) => {
+        array_of(self.ts_type(inner))
+      }
+      ("HashMap" | "BTreeMap" | "IndexMap", [_, value]) => {
+        format!("Record<string, {}>", self.ts_type(value))
+      }
+      ("Channel", []) => {
+        self.uses_channel = true;
+        "Channel<unknown>".into()
+      }
+      ("Channel", [inner]) => {
+        self.uses_channel = true;
+        format!("Channel<{}>", self.ts_type(inner))
+      }
+      (name, args) => match types.get(name) {
+        Some(definition) if definition.generics().len() == args.len() => {
+          self.referenced.insert(name.to_string());
+          if args.is_empty() {
+            name.to_string()
+          } else {
+            let args = args
+              .iter()
+              .map(|ty| self.ts_type(ty))
+              .collect::<Vec<_>>()
+              .join(", ");
+            format!("{name}<{args}>")
+          }
+        }
+        _ => self.unresolved(ty),
+      },
+    }
+  }
+
+  fn unresolved(&mut self, ty: &Type) -> String {
+    self.unresolved.push(rust_type(ty));
+    "unknown".into()
+  }
+
+  fn definition(&mut self, definition: &TypeDefinition) -> String {
+    let attributes = SerdeAttributes::parse(definition.attrs());
+    match definition {
+      TypeDefinition::Struct(item) => self.fields_type(&item.fields, &attributes, true),
+      TypeDefinition::Enum(item) => self.enum_type(item, &attributes),
+    }
+  }
+
+  fn field_type(&mut self, field: &Field, attributes: &SerdeAttributes) -> String {
+    if attributes.custom {
+      "unknown".into()
+    } else {
+      self.ts_type(&field.ty)
+    }
+  }
+
+  /// The type of a struct or enum variant with the given `serde` attributes.
+  fn fields_type(
+    &mut self,
+    fields: &Fields,
+    attributes: &SerdeAttributes,
+    multiline: bool,
+  ) -> String {
+    match fields {
+      Fields::Unit => "null".into(),
+      Fields::Unnamed(fields) => {
+        let mut types = fields
+          .unnamed
+          .iter()
+          .filter_map(|field| {
+            let attributes = SerdeAttributes::parse(&field.attrs);
+            (!attributes.skip).then(|| self.field_type(field, &attributes))
+          })
+          .collect::<Vec<_>>();
+        if types.len() == 1 {
+          types.remove(0)
+        } else {
+          format!("[{}]", types.join(", "))
+        }
+      }
+      Fields::Named(fields) if attributes.transparent => fields
+        .named
+        .iter()
+        .find_map(|field| {
+          let attributes = SerdeAttributes::parse(&field.attrs);
+          (!attributes.skip).then(|| self.field_type(field, &attributes))
+        })
+        .unwrap_or_else(|| "null".into()),
+      Fields::Named(fields) => {
+        let (members, flattened) = self.members(fields, attributes);
+        object(members, flattened, multiline)
+      }
+    }
+  }
+
+  /// The properties of a struct and the types of its flattened fields.
+  fn members(
+    &mut self,
+    fields: &FieldsNamed,
+    container: &SerdeAttributes,
+  ) -> (Vec<String>, Vec<String>) {
+    let mut members = Vec::new();
+    let mut flattened = Vec::new();
+
+    for field in &fields.named {
+      let attributes = SerdeAttributes::parse(&field.attrs);
+      if attributes.skip {
+        continue;
+      }
+
+      let ty = self.field_type(field, &attributes);
+      if attributes.flatten {
+        flattened.push(ty);
+        continue;
+      }
+
+      let name = attributes.rename.clone().unwrap_or_else(|| {
+        let ident = field.ident.as_ref().map(|ident| ident.unraw().to_string());
+        rename(&ident.unwrap_or_default(), container.rename_all.as_deref())
+      });
+      let optional = attributes.optional || container.optional || option_type(&field.ty).is_some();
+
+      members.push(format!(
+        "{}{}: {ty}",
+        property(&name),
+        if optional { "?" } else { "" }
+      ));
+    }
+
+    (members, flattened)
+  }
+
+  fn enum_type(&mut self, item: &ItemEnum, container: &SerdeAttributes) -> String {
+    let mut variants = Vec::new();
+
+    for variant in &item.variants {
+      let attributes = SerdeAttributes::parse(&variant.attrs);
+      if attributes.skip {
+        continue;
+      }
+
+      let name = attributes.rename.clone().unwrap_or_else(|| {
+        rename(
+          &variant.ident.unraw().to_string(),
+          container.rename_all.as_deref(),
+        )
+      });
+      let literal = string_literal(&name);
+      // the `rename_all` of a variant applies to its fields
+      let field_attributes = SerdeAttributes {
+        rename_all: attributes
+          .rename_all
+          .clone()
+          .or_else(|| container.rename_all_fields.clone()),
+        ..Default::default()
+      };
+
+      let ty = if container.untagged || attributes.untagged {
+        self.fields_type(&variant.fields, &field_attributes, false)
+      } else {
+        match (&container.tag, &container.content, &variant.fields) {
+          (None, _, Fields::Unit) => literal,
+          (None, _, variant_fields) => format!(
+            "{{ {}: {} }}",
+            property(&name),
+            self.fields_type(variant_fields, &field_attributes, false)
+          ),
+          (Some(tag), _, Fields::Unit) => format!("{{ {}: {literal} }}", property(tag)),
+          (Some(tag), Some(content), variant_fields) => format!(
+            "{{ {}: {literal}; {}: {} }}",
+            property(tag),
+            property(content),
+            self.fields_type(variant_fields, &field_attributes, false)
+          ),
+          (Some(tag), None, Fields::Named(named)) => {
+            let (mut members, flattened) = self.members(named, &field_attributes);
+            members.insert(0, format!("{}: {literal}", property(tag)));
+            object(members, flattened, false)
+          }
+          (Some(tag), None, variant_fields) => format!(
+            "{{ {}: {literal} }} & {}",
+            property(tag),
+            parenthesize(self.fields_type(variant_fields, &field_attributes, false))
+          ),
+        }
+      };
+
+      variants.push(ty);
+    }
+
+    if variants.is_empty() {
+      "never".into()
+    } else {
+      variants.join(" | ")
+    }
+  }
+}
+
+fn render(sources: &Sources) -> (String, Vec<String>) {
+  let mut renderer = Renderer::new(&sources.types);
+  let mut warnings = Vec::new();
+  let mut module = String::new();
+
+  let mut commands = sources.commands.iter().collect::<Vec<_>>();
+  commands.sort_by(|a, b| a.name.cmp(&b.name));
+  commands.dedup_by(|command, previous| {
+    let duplicate = command.name == previous.name;
+    if duplicate {
+      warnings.push(format!(
+        "command `{}` is defined more than once, only the first definition is used in the TypeScript bindings",
+        command.name
+      ));
+    }
+    duplicate
+  });
+
+  for command in commands {
+    let mut params = Vec::new();
+    let mut args = Vec::new();
+
+    // TypeScript doesn't allow required parameters after optional ones
+    let required = command
+      .args
+      .iter()
+      .rposition(|arg| !arg.optional)
+      .map_or(0, |index| index + 1);
+
+    for (index, arg) in command.args.iter().enumerate() {
+      let ty = renderer.resolve(
+        &arg.ty,
+        &format!("command `{}`: argument `{}`", command.name, arg.key),
+        &mut warnings,
+      );
+
+      let ident = match identifier(&arg.key) {
+        ident if ident.is_empty() => format!("arg{index}"),
+        ident => ident,
+      };
+      params.push(match (arg.optional, index < required) {
+        (true, false) => format!("{ident}?: {ty}"),
+        (true, true) => format!("{ident}: {ty} | undefined"),
+        (false, _) => format!("{ident}: {ty}"),
+      });
+      args.push(if ident == arg.key {
+        ident
+      } else {
+        format!("{}: {ident}", string_literal(&arg.key))
+      });
+    }
+
+    let return_type = match &command.output {
+      Output::Void => "void".to_string(),
+      Output::Bytes => "ArrayBuffer".to_string(),
+      Output::Type(ty) => renderer.resolve(
+        ty,
+        &format!("command `{}`: the return type", command.name),
+        &mut warnings,
+      ),
+    };
+
+    let mut doc_lines = command.docs.clone();
+    if let Some(note) = &command.deprecated {
+      doc_lines.push(format!("@deprecated {note}").trim_end().to_string());
+    }
+
+    module.push('\n');
+    doc_comment(&mut module, &doc_lines);
+    let _ = writeln!(
+      module,
+      "export async function {}({}): Promise<{return_type}> {{",
+      command.name,
+      params.join(", ")
+    );
+    if args.is_empty() {
+      let _ = writeln!(module, "\treturn invoke(\"{}\");", command.name);
+    } else {
+      let _ = writeln!(
+        module,
+        "\treturn invoke(\"{}\", {{ {} }});",
+        command.name,
+        args.join(", ")
+      );
+    }
+    module.push_str("}\n");
+  }
+
+  // rendering a type can reference new ones
+  let mut definitions = BTreeMap::new();
+  while let Some(name) = renderer
+    .referenced
+    .iter()
+    .find(|name| !definitions.contains_key(*name))
+    .cloned()
+  {
+    let definition = &sources.types[&name];
+    renderer.generics = definition.generics();
+    let ty = renderer.definition(definition);
+    for unresolved in renderer.unresolved.drain(..) {
+      warnings.push(format!(
+        "type `{name}` uses `{unresolved}`, which is not a known serializable type, using `unknown` in the TypeScript bindings"
+      ));
+    }
+    let params = if renderer.generics.is_empty() {
+      String::new()
+    } else {
+      format!("<{}>", renderer.generics.join(", "))
+    };
+    definitions.insert(name, (definition, params, ty));
+  }
+
+  for (name, (definition, params, ty)) in definitions {
+    module.push('\n');
+    doc_comment(&mut module, &doc_lines(definition.attrs()));
+    let _ = writeln!(module, "export type {name}{params} = {ty};");
+  }
+
+  let imports = if renderer.uses_channel {
+    "import { Channel, invoke } from \"@tauri-apps/api/core\";"
+  } else {
+    "import { invoke } from \"@tauri-apps/api/core\";"
+  };
+
+  (
+    format!("// This file is generated by tauri, do not edit it.\n\n{imports}\n{module}"),
+    warnings,
+  )
+}
+
+fn doc_comment(module: &mut String, lines: &[String]) {
+  if lines.is_empty() {
+    return;
+  }
+
+  module.push_str("/**\n");
+  for line in lines {
+    if line.is_empty() {
+      module.push_str(" *\n");
+    } else {
+      let _ = writeln!(module, " * {}", line.replace("*/", "*\\/"));
+    }
+  }
+  module.push_str(" */\n");
+}
+
+/// An object type with the given properties, intersected with the flattened types.
+fn object(members: Vec<String>, flattened: Vec<String>, multiline: bool) -> String {
+  let object = if members.is_empty() {
+    (flattened.is_empty()).then(|| "Record<string, never>".to_string())
+  } else if multiline {
+    let mut object = String::from("{\n");
+    for member in members {
+      let _ = writeln!(object, "\t{member};");
+    }
+    object.push('}');
+    Some(object)
+  } else {
+    Some(format!("{{ {} }}", members.join("; ")))
+  };
+
+  object
+    .into_iter()
+    .chain(flattened.into_iter().map(parenthesize))
+    .collect::<Vec<_>>()
+    .join(" & ")
+}
+
+/// Applies a `serde` `rename_all` rule.
+fn rename(name: &str, rule: Option<&str>) -> String {
+  match rule {
+    Some("lowercase") => name.to_ascii_lowercase(),
+    Some("UPPERCASE") => name.to_ascii_uppercase(),
+    Some("PascalCase") => name.to_upper_camel_case(),
+    Some("camelCase") => name.to_lower_camel_case(),
+    Some("snake_case") => name.to_snake_case(),
+    Some("SCREAMING_SNAKE_CASE") => name.to_shouty_snake_case(),
+    Some("kebab-case") => name.to_kebab_case(),
+    Some("SCREAMING-KEBAB-CASE") => name.to_shouty_kebab_case(),
+    _ => name.to_string(),
+  }
+}
+
+fn array_of(inner: String) -> String {
+  if inner.contains(" | ") || inner.contains(" & ") {
+    format!("({inner})[]")
+  } else {
+    format!("{inner}[]")
+  }
+}
+
+fn parenthesize(ty: String) -> String {
+  if ty.contains(" | ") {
+    format!("({ty})")
+  } else {
+    ty
+  }
+}
+
+fn string_literal(value: &str) -> String {
+  serde_json::Value::String(value.into()).to_string()
+}
+
+/// An object property, quoted if it isn't a valid identifier.
+fn property(name: &str) -> String {
+  if !name.is_empty() && identifier(name) == name {
+    name.to_string()
+  } else {
+    string_literal(name)
+  }
+}
+
+/// Turns an argument key into a valid TypeScript identifier.
+fn identifier(key: &str) -> String {
+  let ident = key.replace(
+    |c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '$',
+    "_",
+  );
+
+  if ident.starts_with(|c: char| c.is_ascii_digit()) || RESERVED_WORDS.contains(&ident.as_str()) {
+    format!("_{ident}")
+  } else {
+    ident
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{render, source_dirs, Sources};
+  use std::path::Path;
+
+  fn fixture() -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("test/fixture/bindings")
+  }
+
+  fn sources() -> Sources {
+    let content = std::fs::read_to_string(fixture().join("Source/commands.rs")).unwrap();
+    let file = syn::parse_file(&content).unwrap();
+    let mut sources = Sources::default();
+    sources.collect(&file.items);
+    sources
+  }
+
+  #[test]
+  fn renders_the_fixture() {
+    let (bindings, warnings) = render(&sources());
+
+    assert_eq!(
+      bindings,
+      std::fs::read_to_string(fixture().join("bindings.ts")).unwrap()
+    );
+    assert_eq!(
+      warnings,
+      [
+        "command `login`: argument `session` uses `Session`, which is not a known serializable type, using `unknown` in the TypeScript bindings",
+        "command `search`: argument `filter` uses `regex::Regex`, which is not a known serializable type, using `unknown` in the TypeScript bindings",
+      ]
+    );
+  }
+
+  #[test]
+  fn optional_arguments_are_options_or_defaults() {
+    let sources = sources();
+    let command = sources
+      .commands
+      .iter()
+      .find(|command| command.name == "list_users")
+      .unwrap();
+
+    let optional = command
+      .args
+      .iter()
+      .map(|arg| (arg.key.as_str(), arg.optional))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      optional,
+      [
+        ("page_size", true),
+        ("include_inactive", true),
+        ("query", false)
+      ]
+    );
+  }
+
+  #[test]
+  fn scans_the_target_directories() {
+    let manifest = cargo_toml::Manifest::from_str(
+      "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n\n[lib]\npath = \"Source/lib.rs\"\n",
+    )
+    .unwrap();
+
+    let dirs = source_dirs(&fixture(), &manifest);
+    assert_eq!(
+      dirs.into_iter().collect::<Vec<_>>(),
+      [fixture().join("Source")]
+    );
+  }
+}
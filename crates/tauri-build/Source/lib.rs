@@ -26,6 +26,7 @@ use std::{
 };
 
 mod acl;
+mod bindings;
 #[cfg(feature = "codegen")]
 mod codegen;
 mod manifest;
@@ -350,6 +351,9 @@ pub struct Attributes {
   codegen: Option<codegen::context::CodegenContext>,
   inlined_plugins: HashMap<&'static str, InlinedPlugin>,
   app_manifest: AppManifest,
+  permission_docs_path: Option<PathBuf>,
+  schemas_out_dir: Option<PathBuf>,
+  typescript_bindings: Option<PathBuf>,
 }
 
 impl Attributes {
@@ -403,6 +407,46 @@ impl Attributes {
     self
   }
 
+  /// Generates a markdown permission reference for the application and each inlined plugin in the given directory.
+  ///
+  /// A `$name.md` file is written for each inlined plugin and an `app.md` file for the [`AppManifest`] permissions,
+  /// listing the permission identifiers, descriptions, allowed and denied commands and whether they are part of the default permission.
+  /// Files are only written when their content changes so they can be committed to the repository.
+  #[must_use]
+  pub fn generate_permission_docs(mut self, path: impl Into<PathBuf>) -> Self {
+    self.permission_docs_path.replace(path.into());
+    self
+  }
+
+  /// Sets the directory where the capability schemas, ACL manifests and resolved capabilities are written.
+  ///
+  /// The `TAURI_SCHEMAS_OUT_DIR` environment variable takes precedence over this value, which takes precedence over
+  /// the `build > schemasDir` configuration value. Defaults to `gen/schemas`. Relative paths are resolved against the
+  /// crate directory.
+  ///
+  /// The Tauri CLI commands reading the generated files, such as `tauri permission ls`, cannot see this value.
+  /// Use the `build > schemasDir` configuration value or the environment variable if they need to find the files.
+  #[must_use]
+  pub fn schemas_out_dir(mut self, path: impl Into<PathBuf>) -> Self {
+    self.schemas_out_dir.replace(path.into());
+    self
+  }
+
+  /// Generates a TypeScript module at the given path with one async function per command, calling `invoke`,
+  /// and a type for every struct and enum the commands use.
+  ///
+  /// The module is rendered from the crate sources when the build script runs: every function annotated
+  /// with `#[command]` in the library and binary target directories is included, and the structs and enums
+  /// deriving `Serialize` or `Deserialize` are mapped to TypeScript types following their `serde` attributes.
+  /// `Option<T>` arguments and arguments with a `#[arg(default)]` value are optional.
+  /// Types that can't be mapped are typed as `unknown` and a cargo warning names the command.
+  /// Relative paths are resolved against the crate directory, and the file is only written when its content changes.
+  #[must_use]
+  pub fn typescript_bindings(mut self, path: impl Into<PathBuf>) -> Self {
+    self.typescript_bindings.replace(path.into());
+    self
+  }
+
   #[cfg(feature = "codegen")]
   #[cfg_attr(docsrs, doc(cfg(feature = "codegen")))]
   #[must_use]
@@ -513,7 +557,22 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
 
   manifest::check(&config, &mut manifest)?;
 
-  acl::build(&out_dir, target, &attributes)?;
+  println!(
+    "cargo:rerun-if-env-changed={}",
+    tauri_utils::acl::SCHEMAS_OUT_DIR_ENV
+  );
+  let current_dir = env::current_dir()?;
+  let schemas_dir = tauri_utils::acl::schemas_out_dir(
+    &current_dir,
+    attributes.schemas_out_dir.as_deref(),
+    config.build.schemas_dir.as_deref(),
+  );
+
+  acl::build(&out_dir, &schemas_dir, target, &attributes)?;
+
+  if let Some(path) = &attributes.typescript_bindings {
+    bindings::generate(&current_dir, &manifest, &current_dir.join(path))?;
+  }
 
   println!("cargo:rustc-env=TAURI_ENV_TARGET_TRIPLE={target_triple}");
   // when running codegen in this build script, we need to access the env var directly
@@ -0,0 +1,143 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::{ipc::Channel, AppHandle, State, WebviewWindow};
+
+pub struct Db {
+  users: HashMap<u64, User>,
+}
+
+pub struct Session;
+
+/// A user of the application.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+  pub id: u64,
+  pub display_name: String,
+  pub email: Option<String>,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  #[serde(skip)]
+  pub password_hash: String,
+  pub role: Role,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+  Admin,
+  Member,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+pub enum DownloadEvent {
+  #[serde(rename_all = "camelCase")]
+  Started {
+    url: String,
+    content_length: Option<u64>,
+  },
+  Progress(u64),
+  Finished,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Lookup {
+  Found(User),
+  Missing,
+}
+
+#[derive(Serialize)]
+pub struct Page<T> {
+  pub items: Vec<T>,
+  pub next: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Error {
+  NotFound { id: u64 },
+  Unauthorized,
+}
+
+/// Returns the user with the given id.
+///
+/// Fails if the user does not exist.
+#[tauri::command]
+pub async fn get_user(db: State<'_, Db>, id: u64) -> Result<User, Error> {
+  db.users.get(&id).cloned().ok_or(Error::NotFound { id })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub fn list_users(
+  db: State<'_, Db>,
+  page_size: Option<u32>,
+  #[arg(default)] include_inactive: bool,
+  query: String,
+) -> Page<User> {
+  let items = db
+    .users
+    .values()
+    .filter(|user| include_inactive || user.display_name.contains(&query))
+    .take(page_size.unwrap_or(50) as usize)
+    .cloned()
+    .collect();
+  Page { items, next: None }
+}
+
+#[tauri::command]
+pub fn download(app: AppHandle, url: String, on_event: Channel<DownloadEvent>) {
+  let _ = app;
+  let _ = on_event.send(DownloadEvent::Started {
+    url,
+    content_length: None,
+  });
+  let _ = on_event.send(DownloadEvent::Finished);
+}
+
+#[tauri::command(deprecated = "use `get_user` instead")]
+pub fn find_user(db: State<'_, Db>, name: &str) -> Lookup {
+  db.users
+    .values()
+    .find(|user| user.display_name == name)
+    .cloned()
+    .map(Lookup::Found)
+    .unwrap_or(Lookup::Missing)
+}
+
+#[tauri::command(raw_response)]
+pub fn avatar(#[arg(rename = "userId")] id: u64) -> tauri::ipc::Response {
+  tauri::ipc::Response::new(id.to_le_bytes().to_vec())
+}
+
+#[tauri::command]
+pub fn login(session: Session) -> bool {
+  let _ = session;
+  true
+}
+
+#[tauri::command]
+pub fn search(filter: regex::Regex, limit: usize) -> Vec<(String, u32)> {
+  let _ = (filter, limit);
+  Vec::new()
+}
+
+pub mod settings {
+  use std::collections::BTreeMap;
+
+  #[tauri::command]
+  pub fn settings(
+    #[arg(window = "main")] main: super::WebviewWindow,
+    r#type: String,
+    default: Option<serde_json::Value>,
+  ) -> BTreeMap<String, serde_json::Value> {
+    let _ = (main, r#type, default);
+    BTreeMap::new()
+  }
+}
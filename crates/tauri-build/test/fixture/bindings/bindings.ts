@@ -0,0 +1,65 @@
+// This file is generated by tauri, do not edit it.
+
+import { Channel, invoke } from "@tauri-apps/api/core";
+
+export async function avatar(userId: number): Promise<ArrayBuffer> {
+	return invoke("avatar", { userId });
+}
+
+export async function download(url: string, onEvent: Channel<DownloadEvent>): Promise<void> {
+	return invoke("download", { url, onEvent });
+}
+
+/**
+ * @deprecated use `get_user` instead
+ */
+export async function find_user(name: string): Promise<Lookup> {
+	return invoke("find_user", { name });
+}
+
+/**
+ * Returns the user with the given id.
+ *
+ * Fails if the user does not exist.
+ */
+export async function get_user(id: number): Promise<User> {
+	return invoke("get_user", { id });
+}
+
+export async function list_users(page_size: number | null | undefined, include_inactive: boolean | undefined, query: string): Promise<Page<User>> {
+	return invoke("list_users", { page_size, include_inactive, query });
+}
+
+export async function login(session: unknown): Promise<boolean> {
+	return invoke("login", { session });
+}
+
+export async function search(filter: unknown, limit: number): Promise<[string, number][]> {
+	return invoke("search", { filter, limit });
+}
+
+export async function settings(type: string, _default?: unknown | null): Promise<Record<string, unknown>> {
+	return invoke("settings", { type, "default": _default });
+}
+
+export type DownloadEvent = { event: "started"; data: { url: string; contentLength?: number | null } } | { event: "progress"; data: number } | { event: "finished" };
+
+export type Lookup = User | null;
+
+export type Page<T> = {
+	items: T[];
+	next?: string | null;
+};
+
+export type Role = "admin" | "member";
+
+/**
+ * A user of the application.
+ */
+export type User = {
+	id: number;
+	displayName: string;
+	email?: string | null;
+	tags?: string[];
+	role: Role;
+};
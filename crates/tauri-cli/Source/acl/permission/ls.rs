@@ -6,7 +6,10 @@ use std::{collections::BTreeMap, fs::read_to_string};
 
 use clap::Parser;
 use colored::Colorize;
-use tauri_utils::acl::{APP_ACL_KEY, manifest::Manifest};
+use tauri_utils::{
+	acl::{ACL_MANIFESTS_FILE_NAME, APP_ACL_KEY, manifest::Manifest, schemas_out_dir},
+	platform::Target,
+};
 
 use crate::{Result, helpers::app_paths::tauri_dir};
 
@@ -23,7 +26,14 @@ pub struct Options {
 pub fn command(options:Options) -> Result<()> {
 	crate::helpers::app_paths::resolve();
 
-	let acl_manifests_path = tauri_dir().join("gen").join("schemas").join("acl-manifests.json");
+	let configured_schemas_dir = crate::helpers::config::get(Target::current(), None)
+		.ok()
+		.and_then(|config| {
+			config.lock().unwrap().as_ref().and_then(|config| config.build.schemas_dir.clone())
+		});
+
+	let acl_manifests_path =
+		schemas_out_dir(tauri_dir(), None, configured_schemas_dir.as_deref()).join(ACL_MANIFESTS_FILE_NAME);
 
 	if acl_manifests_path.exists() {
 		let plugin_manifest_json = read_to_string(&acl_manifests_path)?;
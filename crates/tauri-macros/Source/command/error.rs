@@ -0,0 +1,419 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+	Attribute,
+	Data,
+	DeriveInput,
+	Field,
+	Fields,
+	LitStr,
+	Path,
+	Type,
+	Variant,
+	spanned::Spanned,
+};
+
+/// The `#[command_error(...)]` attributes of the enum.
+struct ContainerAttributes {
+	root:TokenStream,
+	rename_all:Option<fn(&str) -> String>,
+}
+
+/// The `#[command_error(...)]` attributes of a variant.
+#[derive(Default)]
+struct VariantAttributes {
+	kind:Option<String>,
+}
+
+/// The `#[command_error(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldAttributes {
+	skip:bool,
+	flatten:bool,
+	from:bool,
+}
+
+fn command_error_attrs(attrs:&[Attribute]) -> impl Iterator<Item = &Attribute> {
+	attrs.iter().filter(|attr| attr.path().is_ident("command_error"))
+}
+
+impl ContainerAttributes {
+	fn parse(attrs:&[Attribute]) -> syn::Result<Self> {
+		let mut container = Self { root:quote!(::tauri), rename_all:None };
+
+		for attr in command_error_attrs(attrs) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("root") {
+					let root:LitStr = meta.value()?.parse()?;
+
+					// errors point at the literal, e.g. for `root = "my-crate"`
+					let root:Path = root.parse()?;
+
+					container.root = quote!(#root);
+
+					Ok(())
+				} else if meta.path.is_ident("rename_all") {
+					let case:LitStr = meta.value()?.parse()?;
+
+					container.rename_all = Some(match case.value().as_str() {
+						"camelCase" => <str as ToLowerCamelCase>::to_lower_camel_case,
+						"snake_case" => <str as ToSnakeCase>::to_snake_case,
+						"kebab-case" => <str as ToKebabCase>::to_kebab_case,
+						"SCREAMING_SNAKE_CASE" => <str as ToShoutySnakeCase>::to_shouty_snake_case,
+						_ => {
+							return Err(syn::Error::new(
+								case.span(),
+								"expected \"camelCase\", \"snake_case\", \"kebab-case\" or \
+								 \"SCREAMING_SNAKE_CASE\"",
+							));
+						},
+					});
+
+					Ok(())
+				} else {
+					Err(meta.error("unsupported attribute, expected `root` or `rename_all`"))
+				}
+			})?;
+		}
+
+		Ok(container)
+	}
+}
+
+impl VariantAttributes {
+	fn parse(attrs:&[Attribute]) -> syn::Result<Self> {
+		let mut variant = Self::default();
+
+		for attr in command_error_attrs(attrs) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("kind") {
+					let kind:LitStr = meta.value()?.parse()?;
+
+					variant.kind = Some(kind.value());
+
+					Ok(())
+				} else {
+					Err(meta.error("unsupported variant attribute, expected `kind`"))
+				}
+			})?;
+		}
+
+		Ok(variant)
+	}
+}
+
+impl FieldAttributes {
+	fn parse(attrs:&[Attribute]) -> syn::Result<Self> {
+		let mut field = Self::default();
+
+		for attr in command_error_attrs(attrs) {
+			attr.parse_nested_meta(|meta| {
+				if meta.path.is_ident("skip") {
+					field.skip = true;
+				} else if meta.path.is_ident("flatten") {
+					field.flatten = true;
+				} else if meta.path.is_ident("from") {
+					field.from = true;
+				} else {
+					return Err(meta.error(
+						"unsupported field attribute, expected `skip`, `flatten` or `from`",
+					));
+				}
+
+				Ok(())
+			})?;
+		}
+
+		if field.skip && field.flatten {
+			return Err(syn::Error::new(
+				attrs[0].span(),
+				"`skip` and `flatten` cannot be used on the same field",
+			));
+		}
+
+		Ok(field)
+	}
+}
+
+/// Implements `Serialize` for an error enum, and `From` for the fields marked
+/// with `#[command_error(from)]`.
+pub fn derive(input:DeriveInput) -> syn::Result<TokenStream> {
+	let Data::Enum(data) = &input.data else {
+		return Err(syn::Error::new(
+			input.ident.span(),
+			"`CommandError` can only be derived for enums",
+		));
+	};
+
+	let container = ContainerAttributes::parse(&input.attrs)?;
+
+	let root = &container.root;
+
+	let name = &input.ident;
+
+	let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+	let mut arms = Vec::new();
+
+	let mut from_impls = Vec::new();
+
+	for variant in &data.variants {
+		let attributes = VariantAttributes::parse(&variant.attrs)?;
+
+		let kind = attributes.kind.unwrap_or_else(|| {
+			let kind = variant.ident.to_string();
+
+			container.rename_all.map(|rename| rename(&kind)).unwrap_or(kind)
+		});
+
+		arms.push(variant_arm(root, variant, &kind)?);
+
+		if let Some((field, ty)) = from_field(variant)? {
+			let ident = &variant.ident;
+
+			let construct = match field {
+				Some(field) => quote!(Self::#ident { #field: error }),
+				None => quote!(Self::#ident(error)),
+			};
+
+			from_impls.push(quote! {
+				#[automatically_derived]
+				impl #impl_generics ::core::convert::From<#ty> for #name #ty_generics #where_clause {
+					fn from(error: #ty) -> Self {
+						#construct
+					}
+				}
+			});
+		}
+	}
+
+	let serde = quote!(#root::ipc::private::serde);
+
+	let json = quote!(#root::ipc::private::serde_json);
+
+	Ok(quote! {
+		#[automatically_derived]
+		impl #impl_generics #serde::Serialize for #name #ty_generics #where_clause {
+			fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+			where
+				S: #serde::Serializer,
+			{
+				use #serde::ser::{Error as _, SerializeMap as _};
+
+				let (kind, data): (&str, #json::Value) = match self {
+					#(#arms)*
+				};
+
+				let mut map = serializer.serialize_map(::core::option::Option::Some(3))?;
+				map.serialize_entry("kind", kind)?;
+				map.serialize_entry("message", &::std::string::ToString::to_string(self))?;
+				map.serialize_entry("data", &data)?;
+				map.end()
+			}
+		}
+
+		#(#from_impls)*
+	})
+}
+
+/// The match arm resolving the kind and data of a variant.
+fn variant_arm(root:&TokenStream, variant:&Variant, kind:&str) -> syn::Result<TokenStream> {
+	let json = quote!(#root::ipc::private::serde_json);
+
+	let ident = &variant.ident;
+
+	let to_value =
+		|binding:&Ident| quote!(#json::to_value(#binding).map_err(S::Error::custom)?);
+
+	match &variant.fields {
+		Fields::Unit => Ok(quote!(Self::#ident => (#kind, #json::Value::Null),)),
+		Fields::Unnamed(fields) => {
+			let mut bindings = Vec::new();
+
+			let mut values = Vec::new();
+
+			for (index, field) in fields.unnamed.iter().enumerate() {
+				let attributes = FieldAttributes::parse(&field.attrs)?;
+
+				if attributes.flatten {
+					return Err(syn::Error::new(
+						field.span(),
+						"`flatten` can only be used on named fields",
+					));
+				}
+
+				if attributes.skip {
+					bindings.push(quote!(_));
+				} else {
+					let binding = format_ident!("__field{}", index);
+
+					values.push(to_value(&binding));
+
+					bindings.push(quote!(#binding));
+				}
+			}
+
+			// a single field is sent as is, multiple fields as an array
+			let data = match values.len() {
+				0 => quote!(#json::Value::Null),
+				1 => values.remove(0),
+				_ => quote!(#json::Value::Array(::std::vec![#(#values),*])),
+			};
+
+			Ok(quote!(Self::#ident(#(#bindings),*) => (#kind, #data),))
+		},
+		Fields::Named(fields) => {
+			let mut bindings = Vec::new();
+
+			let mut inserts = Vec::new();
+
+			for field in &fields.named {
+				let attributes = FieldAttributes::parse(&field.attrs)?;
+
+				if attributes.skip {
+					continue;
+				}
+
+				let field_ident = field.ident.as_ref().expect("named field without ident");
+
+				let key = field_ident.to_string();
+
+				let value = to_value(field_ident);
+
+				inserts.push(if attributes.flatten {
+					quote! {
+						match #value {
+							#json::Value::Object(entries) => data.extend(entries),
+							#json::Value::Null => {},
+							_ => {
+								return ::core::result::Result::Err(S::Error::custom(concat!(
+									"the flattened field `",
+									#key,
+									"` must serialize to an object"
+								)));
+							},
+						}
+					}
+				} else {
+					quote!(data.insert(::std::string::String::from(#key), #value);)
+				});
+
+				bindings.push(field_ident);
+			}
+
+			let data = if inserts.is_empty() {
+				quote!(#json::Value::Null)
+			} else {
+				quote!({
+					let mut data = #json::Map::new();
+					#(#inserts)*
+					#json::Value::Object(data)
+				})
+			};
+
+			Ok(quote!(Self::#ident { #(#bindings,)* .. } => (#kind, #data),))
+		},
+	}
+}
+
+/// The field marked with `#[command_error(from)]`, with its name if the
+/// variant has named fields.
+fn from_field(variant:&Variant) -> syn::Result<Option<(Option<&Ident>, &Type)>> {
+	let fields:Vec<&Field> = variant.fields.iter().collect();
+
+	for field in &fields {
+		if FieldAttributes::parse(&field.attrs)?.from {
+			if fields.len() != 1 {
+				return Err(syn::Error::new(
+					field.span(),
+					"`from` can only be used on variants with a single field",
+				));
+			}
+
+			return Ok(Some((field.ident.as_ref(), &field.ty)));
+		}
+	}
+
+	Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+	use syn::DeriveInput;
+
+	/// The compile error of deriving `CommandError` for `input`.
+	fn error(input:&str) -> String {
+		super::derive(syn::parse_str::<DeriveInput>(input).unwrap()).unwrap_err().to_string()
+	}
+
+	#[test]
+	fn derives_for_enums() {
+		assert!(
+			super::derive(
+				syn::parse_str::<DeriveInput>(
+					"#[command_error(rename_all = \"kebab-case\")] enum Error { NotFound { \
+					 #[command_error(flatten)] details: Details }, #[command_error(kind = \"io\")] \
+					 Io(#[command_error(from, skip)] std::io::Error) }"
+				)
+				.unwrap()
+			)
+			.is_ok()
+		);
+	}
+
+	#[test]
+	fn accepts_root_paths() {
+		for root in ["crate", "tauri", "::tauri", "my_app::reexports::tauri"] {
+			let input = format!("#[command_error(root = \"{root}\")] enum Error {{ Io }}");
+
+			let tokens = super::derive(syn::parse_str::<DeriveInput>(&input).unwrap()).unwrap();
+
+			let path = syn::parse_str::<syn::Path>(root).unwrap();
+
+			assert!(
+				tokens.to_string().contains(&quote::quote!(#path::ipc::private::serde).to_string()),
+				"{root} is not the root of the generated code"
+			);
+		}
+	}
+
+	#[test]
+	fn rejects_invalid_input() {
+		assert_eq!(error("#[command_error(root = \"my-app\")] enum Error { Io }"), "unexpected token");
+
+		assert_eq!(
+			error("struct Error { message: String }"),
+			"`CommandError` can only be derived for enums"
+		);
+
+		assert_eq!(
+			error("#[command_error(rename_all = \"Title Case\")] enum Error { Io }"),
+			"expected \"camelCase\", \"snake_case\", \"kebab-case\" or \"SCREAMING_SNAKE_CASE\""
+		);
+
+		assert_eq!(
+			error("enum Error { Io(#[command_error(flatten)] Details) }"),
+			"`flatten` can only be used on named fields"
+		);
+
+		assert_eq!(
+			error("enum Error { Io { #[command_error(skip, flatten)] details: Details } }"),
+			"`skip` and `flatten` cannot be used on the same field"
+		);
+
+		assert_eq!(
+			error("enum Error { Io(#[command_error(from)] std::io::Error, String) }"),
+			"`from` can only be used on variants with a single field"
+		);
+
+		assert_eq!(
+			error("enum Error { #[command_error(code = \"E_IO\")] Io }"),
+			"unsupported variant attribute, expected `kind`"
+		);
+	}
+}
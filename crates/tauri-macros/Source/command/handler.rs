@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use proc_macro2::TokenStream as TokenStream2;
 use quote::format_ident;
 use syn::{
 	Attribute,
@@ -31,6 +32,7 @@ pub struct Handler {
 	command_defs:Vec<CommandDef>,
 	commands:Vec<Ident>,
 	wrappers:Vec<Path>,
+	metas:Vec<TokenStream2>,
 }
 
 impl Parse for Handler {
@@ -38,33 +40,125 @@ impl Parse for Handler {
 		let command_defs = input.parse_terminated(CommandDef::parse, Token![,])?;
 
 		// parse the command names and wrappers from the passed paths
-		let (commands, wrappers) = command_defs
+		let (commands, wrappers):(Vec<Ident>, Vec<Path>) = command_defs
 			.iter()
 			.map(|command_def| {
 				let mut wrapper = command_def.path.clone();
 
-				let last = super::path_to_command(&mut wrapper);
-
 				// the name of the actual command function
-				let command = last.ident.clone();
+				let command = super::path_to_command(&mut wrapper).ident.clone();
 
-				// set the path to the command function wrapper
-				last.ident = super::format_command_wrapper(&command);
+				// the wrapper is in the module named like the command, which is reachable
+				// wherever the command function is, including its re-exports
+				wrapper.segments.push(super::command_wrapper_reexport().into());
 
 				(command, wrapper)
 			})
 			.unzip();
 
+		let metas = command_defs
+			.iter()
+			.zip(&wrappers)
+			.map(|(command_def, wrapper)| {
+				let path = &command_def.path;
+
+				quote::quote!(#wrapper!(@meta #path))
+			})
+			.collect();
+
+		// the same command can only be listed twice if it is behind mutually exclusive attributes
+		let mut seen:Vec<&Ident> = Vec::new();
+
+		for (command, def) in commands.iter().zip(command_defs.iter()) {
+			if def.attrs.is_empty() {
+				if seen.contains(&command) {
+					return Err(syn::Error::new(
+						command.span(),
+						format!("command `{command}` is listed more than once"),
+					));
+				}
+
+				seen.push(command);
+			}
+		}
+
 		Ok(Self {
 			command_defs:command_defs.into_iter().collect(), // remove punctuation separators
 			commands,
 			wrappers,
+			metas,
 		})
 	}
 }
 
-impl From<Handler> for proc_macro::TokenStream {
-	fn from(Handler { command_defs, commands, wrappers }:Handler) -> Self {
+impl Handler {
+	/// Generates a `tauri::ipc::HandlerCollection` for the commands instead of a
+	/// bare handler closure.
+	pub fn into_collection(self) -> TokenStream2 {
+		let commands = self.commands.clone();
+
+		let attrs:Vec<Vec<Attribute>> = self.command_defs.iter().map(|def| def.attrs.clone()).collect();
+
+		let metas = self.metas.clone();
+
+		let wrapper = self.unconditional_wrapper().cloned();
+
+		let maybe_register = self.register_metadata();
+
+		let handler = self.into_handler();
+
+		let maybe_metadata = if cfg!(feature = "command-metadata") {
+			quote::quote!(.with_metadata(&[#(#(#attrs)* #metas),*]))
+		} else {
+			TokenStream2::default()
+		};
+
+		let names = quote::quote!(&[#(#(#attrs)* stringify!(#commands)),*]);
+
+		// the wrapper resolves the crate root the command was defined with, e.g. `crate` inside of tauri
+		let collection = match wrapper {
+			Some(wrapper) => quote::quote!(#wrapper!(@collection ::core::module_path!(), #names, #handler)),
+			None => {
+				quote::quote!(::tauri::ipc::HandlerCollection::new(::core::module_path!(), #names, #handler))
+			},
+		};
+
+		quote::quote!({
+		  #maybe_register
+		  #collection #maybe_metadata
+		})
+	}
+
+	/// Records the metadata of the commands when the handler is created, see
+	/// `tauri::ipc::command_metadata`.
+	fn register_metadata(&self) -> TokenStream2 {
+		if !cfg!(feature = "command-metadata") {
+			return TokenStream2::default();
+		}
+
+		let Some(wrapper) = self.unconditional_wrapper() else {
+			return TokenStream2::default();
+		};
+
+		let attrs:Vec<Vec<Attribute>> = self.command_defs.iter().map(|def| def.attrs.clone()).collect();
+
+		let metas = &self.metas;
+
+		quote::quote!(#wrapper!(@register &[#(#(#attrs)* #metas),*]);)
+	}
+
+	/// The wrapper of a command that is always compiled, which can name the
+	/// `tauri` crate with the root the command was defined with.
+	fn unconditional_wrapper(&self) -> Option<&Path> {
+		self.command_defs
+			.iter()
+			.zip(&self.wrappers)
+			.find_map(|(def, wrapper)| def.attrs.is_empty().then_some(wrapper))
+	}
+
+	fn into_handler(self) -> TokenStream2 {
+		let Handler { command_defs, commands, wrappers, .. } = self;
+
 		let cmd = format_ident!("__tauri_cmd__");
 
 		let invoke = format_ident!("__tauri_invoke__");
@@ -81,6 +175,83 @@ impl From<Handler> for proc_macro::TokenStream {
 			},
 		  }
 		})
-		.into()
+	}
+}
+
+impl From<Handler> for proc_macro::TokenStream {
+	fn from(handler:Handler) -> Self { handler.into_tokens().into() }
+}
+
+impl Handler {
+	/// Generates the handler closure of `generate_handler!`.
+	fn into_tokens(self) -> TokenStream2 {
+		let maybe_register = self.register_metadata();
+
+		let handler = self.into_handler();
+
+		quote::quote!({
+		  #maybe_register
+		  #handler
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Handler;
+
+	#[test]
+	fn rejects_duplicate_commands() {
+		let error = syn::parse_str::<Handler>("files::save, settings::save").err().unwrap();
+
+		assert_eq!(error.to_string(), "command `save` is listed more than once");
+	}
+
+	#[test]
+	fn accepts_duplicate_commands_behind_attributes() {
+		assert!(syn::parse_str::<Handler>("#[cfg(unix)] unix::save, #[cfg(windows)] windows::save").is_ok());
+	}
+
+	#[test]
+	fn resolves_wrappers_at_the_command_paths() {
+		let handler = syn::parse_str::<Handler>("api::save, renamed, commands::format").unwrap();
+
+		let wrappers =
+			handler.wrappers.iter().map(|wrapper| quote::quote!(#wrapper).to_string()).collect::<Vec<_>>();
+
+		assert_eq!(wrappers, ["api :: save :: __cmd__", "renamed :: __cmd__", "commands :: format :: __cmd__"]);
+	}
+
+	#[test]
+	fn plain_handlers_register_the_metadata() {
+		let handler = syn::parse_str::<Handler>("#[cfg(unix)] unix::open, files::save")
+			.unwrap()
+			.into_tokens()
+			.to_string();
+
+		if cfg!(feature = "command-metadata") {
+			assert!(handler.contains("files :: save :: __cmd__ ! (@ register"), "{handler}");
+			assert!(handler.contains("unix :: open :: __cmd__ ! (@ meta unix :: open)"), "{handler}");
+		} else {
+			assert!(!handler.contains("@ register"), "{handler}");
+		}
+	}
+
+	#[test]
+	fn collections_are_created_by_a_command_wrapper() {
+		let collection = syn::parse_str::<Handler>("#[cfg(unix)] unix::open, files::save")
+			.unwrap()
+			.into_collection()
+			.to_string();
+
+		assert!(collection.contains("files :: save :: __cmd__ ! (@ collection"), "{collection}");
+
+		// without an unconditional command, the `tauri` crate is named directly
+		let collection = syn::parse_str::<Handler>("#[cfg(unix)] unix::open")
+			.unwrap()
+			.into_collection()
+			.to_string();
+
+		assert!(collection.contains(":: tauri :: ipc :: HandlerCollection :: new"), "{collection}");
 	}
 }
@@ -7,12 +7,20 @@ use syn::{Path, PathSegment};
 
 pub use self::{handler::Handler, wrapper::wrapper};
 
+pub mod error;
 mod handler;
 mod wrapper;
 
 /// The autogenerated wrapper ident.
 fn format_command_wrapper(function:&Ident) -> Ident { quote::format_ident!("__cmd__{}", function) }
 
+/// The name of the wrapper inside of the module named like the command, which
+/// `pub use` re-exports along with the command function.
+fn command_wrapper_reexport() -> Ident { quote::format_ident!("__cmd__") }
+
+/// The autogenerated command metadata ident.
+fn format_command_meta(function:&Ident) -> Ident { quote::format_ident!("__CMD_META__{}", function) }
+
 /// This function will panic if the passed [`syn::Path`] does not have any
 /// segments.
 fn path_to_command(path:&mut Path) -> &mut PathSegment {
@@ -4,7 +4,7 @@
 
 use std::env::var;
 
-use heck::{ToLowerCamelCase, ToSnakeCase};
+use heck::{ToKebabCase, ToLowerCamelCase, ToSnakeCase};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote, quote_spanned};
@@ -14,7 +14,7 @@ use syn::{
   parse_macro_input,
   punctuated::Punctuated,
   spanned::Spanned,
-  Expr, ExprLit, FnArg, ItemFn, Lit, Meta, Pat, Token, Visibility,
+  Attribute, Expr, ExprLit, FnArg, ItemFn, Lit, LitStr, Meta, Pat, Path, Token, Visibility,
 };
 
 enum WrapperAttributeKind {
@@ -36,16 +36,40 @@ impl Parse for WrapperAttributeKind {
 
 struct WrapperAttributes {
   root: TokenStream2,
+  /// The root path usable outside of the generated `macro_rules!`, where `$crate` isn't available.
+  meta_root: TokenStream2,
   execution_context: ExecutionContext,
+  /// Span of the `exec` attribute, if any.
+  exec_span: Option<Span>,
   argument_case: ArgumentCase,
+  /// `arg_case = "any"`, also read the arguments from their alternate snake_case or camelCase keys.
+  any_case: bool,
+  /// Send the return value without serializing it, see `tauri::ipc::IntoRawResponse`.
+  raw_response: bool,
+  /// Functions run before the arguments are deserialized, in declaration order.
+  guards: Vec<Path>,
+  /// Function converting the error of the returned `Result` into an `InvokeError`.
+  error: Option<Path>,
+  /// `deprecated = "note"`, warns the first time the command is invoked.
+  deprecated: Option<String>,
+  /// `timeout = "30s"`, in milliseconds, cancels the invoke once it elapses.
+  timeout: Option<u64>,
 }
 
 impl Parse for WrapperAttributes {
   fn parse(input: ParseStream) -> syn::Result<Self> {
     let mut wrapper_attributes = WrapperAttributes {
       root: quote!(::tauri),
+      meta_root: quote!(::tauri),
       execution_context: ExecutionContext::Blocking,
+      exec_span: None,
       argument_case: ArgumentCase::Camel,
+      any_case: false,
+      raw_response: false,
+      guards: Vec::new(),
+      error: None,
+      deprecated: None,
+      timeout: None,
     };
 
     let attrs = Punctuated::<WrapperAttributeKind, Token![,]>::parse_terminated(input)?;
@@ -64,14 +88,82 @@ impl Parse for WrapperAttributes {
               wrapper_attributes.argument_case = match s.value().as_str() {
                 "snake_case" => ArgumentCase::Snake,
                 "camelCase" => ArgumentCase::Camel,
+                "kebab-case" => ArgumentCase::Kebab,
                 _ => {
                   return Err(syn::Error::new(
                     s.span(),
-                    "expected \"camelCase\" or \"snake_case\"",
+                    "expected \"camelCase\", \"snake_case\" or \"kebab-case\"",
                   ))
                 }
               };
             }
+          } else if v.path.is_ident("arg_case") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = v.value
+            {
+              if s.value() != "any" {
+                return Err(syn::Error::new(s.span(), "expected \"any\""));
+              }
+              wrapper_attributes.any_case = true;
+            }
+          } else if v.path.is_ident("guard") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = v.value
+            {
+              wrapper_attributes.guards.push(s.parse()?);
+            }
+          } else if v.path.is_ident("error") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = v.value
+            {
+              wrapper_attributes.error.replace(s.parse()?);
+            }
+          } else if v.path.is_ident("deprecated") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = v.value
+            {
+              wrapper_attributes.deprecated.replace(s.value());
+            }
+          } else if v.path.is_ident("timeout") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = &v.value
+            {
+              wrapper_attributes.timeout.replace(parse_timeout(s)?);
+            } else {
+              return Err(syn::Error::new_spanned(
+                &v.value,
+                "expected a duration string such as \"500ms\", \"30s\" or \"2m\"",
+              ));
+            }
+          } else if v.path.is_ident("exec") {
+            if let Expr::Lit(ExprLit {
+              lit: Lit::Str(s),
+              attrs: _,
+            }) = v.value
+            {
+              wrapper_attributes.execution_context = match s.value().as_str() {
+                "async" => ExecutionContext::Async,
+                "blocking" => ExecutionContext::BlockingPool,
+                "main" => ExecutionContext::Main,
+                _ => {
+                  return Err(syn::Error::new(
+                    s.span(),
+                    "expected \"async\", \"blocking\" or \"main\"",
+                  ))
+                }
+              };
+              wrapper_attributes.exec_span = Some(s.span());
+            }
           } else if v.path.is_ident("root") {
             if let Expr::Lit(ExprLit {
               lit: Lit::Str(s),
@@ -80,19 +172,22 @@ impl Parse for WrapperAttributes {
             {
               let lit = s.value();
 
-              wrapper_attributes.root = if lit == "crate" {
-                quote!($crate)
+              (wrapper_attributes.root, wrapper_attributes.meta_root) = if lit == "crate" {
+                (quote!($crate), quote!(crate))
               } else {
                 let ident = Ident::new(&lit, Span::call_site());
-                quote!(#ident)
+                (quote!(#ident), quote!(#ident))
               };
             }
           }
         }
+        WrapperAttributeKind::Meta(Meta::Path(p)) if p.is_ident("raw_response") => {
+          wrapper_attributes.raw_response = true;
+        }
         WrapperAttributeKind::Meta(Meta::Path(_)) => {
           return Err(syn::Error::new(
             input.span(),
-            "unexpected input, expected one of `rename_all`, `root`, `async`",
+            "unexpected input, expected one of `rename_all`, `root`, `async`, `raw_response`, `guard`, `timeout`",
           ));
         }
         WrapperAttributeKind::Async => {
@@ -105,10 +200,47 @@ impl Parse for WrapperAttributes {
   }
 }
 
+/// Parses a timeout such as `"500ms"`, `"30s"` or `"2m"` into milliseconds.
+fn parse_timeout(timeout: &LitStr) -> syn::Result<u64> {
+  let value = timeout.value();
+
+  let (amount, unit) = value
+    .find(|c: char| !c.is_ascii_digit())
+    .map(|index| value.split_at(index))
+    .unwrap_or((&value, ""));
+
+  let multiplier = match unit.trim() {
+    "ms" => 1,
+    "s" => 1000,
+    "m" => 60 * 1000,
+    _ => {
+      return Err(syn::Error::new(
+        timeout.span(),
+        "expected a duration such as \"500ms\", \"30s\" or \"2m\"",
+      ))
+    }
+  };
+
+  let amount = amount
+    .parse::<u64>()
+    .ok()
+    .filter(|amount| *amount > 0)
+    .ok_or_else(|| syn::Error::new(timeout.span(), "expected a positive integer duration"))?;
+
+  amount
+    .checked_mul(multiplier)
+    .ok_or_else(|| syn::Error::new(timeout.span(), "the duration is too long"))
+}
+
 /// The execution context of the command.
 enum ExecutionContext {
   Async,
+  /// Runs on the thread selected by `tauri::Builder::default_command_execution`.
   Blocking,
+  /// `exec = "blocking"`, runs on the blocking thread pool of the async runtime.
+  BlockingPool,
+  /// `exec = "main"`, runs on the main thread.
+  Main,
 }
 
 /// The case of each argument name.
@@ -116,6 +248,7 @@ enum ExecutionContext {
 enum ArgumentCase {
   Snake,
   Camel,
+  Kebab,
 }
 
 /// The bindings we attach to `tauri::Invoke`.
@@ -123,16 +256,26 @@ struct Invoke {
   message: Ident,
   resolver: Ident,
   acl: Ident,
+  stream: Ident,
 }
 
 /// Create a new [`Wrapper`] from the function and the generated code parsed from the function.
 pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
   let mut attrs = parse_macro_input!(attributes as WrapperAttributes);
-  let function = parse_macro_input!(item as ItemFn);
+  let mut function = parse_macro_input!(item as ItemFn);
   let wrapper = super::format_command_wrapper(&function.sig.ident);
   let visibility = &function.vis;
 
   if function.sig.asyncness.is_some() {
+    if let (Some(span), ExecutionContext::BlockingPool | ExecutionContext::Main) =
+      (attrs.exec_span, &attrs.execution_context)
+    {
+      return quote_spanned! {
+        span => compile_error!("async commands can only use `exec = \"async\"`");
+      }
+      .into();
+    }
+
     attrs.execution_context = ExecutionContext::Async;
   }
 
@@ -146,6 +289,7 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
     message: format_ident!("__tauri_message__"),
     resolver: format_ident!("__tauri_resolver__"),
     acl: format_ident!("__tauri_acl__"),
+    stream: format_ident!("__tauri_stream__"),
   };
 
   // Tauri currently doesn't support async commands that take a reference as input and don't return
@@ -223,14 +367,61 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
   let body = match attrs.execution_context {
     ExecutionContext::Async => body_async(&plugin_name, &function, &invoke, &attrs)
       .unwrap_or_else(syn::Error::into_compile_error),
-    ExecutionContext::Blocking => body_blocking(&plugin_name, &function, &invoke, &attrs)
-      .unwrap_or_else(syn::Error::into_compile_error),
+    ExecutionContext::Blocking | ExecutionContext::BlockingPool | ExecutionContext::Main => {
+      body_blocking(&plugin_name, &function, &invoke, &attrs)
+        .map(|body| dispatch_blocking(body, &invoke, &attrs))
+        .unwrap_or_else(syn::Error::into_compile_error)
+    }
+  };
+
+  // the metadata is both a constant next to the function and an arm of the wrapper, which is how
+  // `generate_handler!` reaches it
+  let (maybe_meta, maybe_meta_arm) = if cfg!(feature = "command-metadata") {
+    let command = &function.sig.ident;
+    let meta = super::format_command_meta(command);
+    let meta_root = &attrs.meta_root;
+    let root = &attrs.root;
+    match (
+      command_meta(&function, &attrs, meta_root),
+      command_meta(&function, &attrs, root),
+    ) {
+      (Ok(value), Ok(arm_value)) => (
+        quote!(
+          #[doc(hidden)]
+          #[allow(non_upper_case_globals)]
+          #visibility const #meta: #meta_root::ipc::CommandMeta = #value;
+        ),
+        quote!(
+          (@meta $path:path) => {{
+            const META: #root::ipc::CommandMeta = #arm_value;
+            &META
+          }};
+
+          // used by `generate_handler!`, which can't name the `tauri` crate itself
+          (@register $metadata:expr) => {{
+            static REGISTERED: ::std::sync::Once = ::std::sync::Once::new();
+            REGISTERED.call_once(|| #root::ipc::private::register_command_metadata($metadata));
+          }};
+        ),
+      ),
+      (Err(error), _) | (_, Err(error)) => (error.into_compile_error(), TokenStream2::default()),
+    }
+  } else {
+    (TokenStream2::default(), TokenStream2::default())
   };
 
+  // `#[arg(...)]` is only meaningful to the wrapper, the compiler would reject it on the function
+  for input in &mut function.sig.inputs {
+    if let FnArg::Typed(arg) = input {
+      arg.attrs.retain(|attr| !attr.path().is_ident("arg"));
+    }
+  }
+
   let Invoke {
     message,
     resolver,
     acl,
+    stream,
   } = invoke;
 
   let root = attrs.root;
@@ -239,6 +430,8 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
     ExecutionContext::Async if function.sig.asyncness.is_none() => "sync_threadpool",
     ExecutionContext::Async => "async",
     ExecutionContext::Blocking => "sync",
+    ExecutionContext::BlockingPool => "sync_blocking",
+    ExecutionContext::Main => "sync_main",
   };
 
   let loc = function.span().start();
@@ -261,32 +454,71 @@ pub fn wrapper(attributes: TokenStream, item: TokenStream) -> TokenStream {
     quote!()
   };
 
+  let maybe_deprecation = attrs.deprecated.as_ref().map(|note| {
+    let command = &function.sig.ident;
+    quote!({
+      static WARNED: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+      #root::ipc::private::warn_deprecated(&WARNED, stringify!(#command), #note, &#message);
+    })
+  });
+
+  let command = &function.sig.ident;
+  let reexport = super::command_wrapper_reexport();
+  // `#[macro_export]` macros can be re-exported publicly, the others are only visible in the crate
+  let reexport_visibility = if maybe_macro_export.is_empty() {
+    quote!(pub(super))
+  } else {
+    quote!(pub)
+  };
+
   // Rely on rust 2018 edition to allow importing a macro from a path.
   quote!(
     #async_command_check
 
     #function
 
-    #maybe_macro_export
+    #maybe_meta
+
+    // the wrapper lives in a module named like the command, so `pub use` re-exports it along with
+    // the command function
     #[doc(hidden)]
-    macro_rules! #wrapper {
-        // double braces because the item is expected to be a block expression
-        ($path:path, $invoke:ident) => {{
-          #[allow(unused_imports)]
-          use #root::ipc::private::*;
-          // prevent warnings when the body is a `compile_error!` or if the command has no arguments
-          #[allow(unused_variables)]
-          let #root::ipc::Invoke { message: #message, resolver: #resolver, acl: #acl } = $invoke;
-
-          #maybe_span
-
-          #body
-      }};
+    #[allow(clippy::module_inception)]
+    #visibility mod #command {
+      #maybe_macro_export
+      #[doc(hidden)]
+      macro_rules! #wrapper {
+          // used by `generate_handler_collection!`, which can't name the `tauri` crate itself
+          (@collection $module:expr, $commands:expr, $handler:expr) => {
+            #root::ipc::HandlerCollection::new($module, $commands, $handler)
+          };
+
+          #maybe_meta_arm
+
+          // double braces because the item is expected to be a block expression
+          ($path:path, $invoke:ident) => {{
+            #[allow(unused_imports)]
+            use #root::ipc::private::*;
+            // prevent warnings when the body is a `compile_error!` or if the command has no arguments
+            #[allow(unused_variables)]
+            let #root::ipc::Invoke { message: #message, resolver: #resolver, acl: #acl } = $invoke;
+            #[allow(unused_variables)]
+            let #stream = #root::ipc::StreamContext::from_message(&#message);
+
+            #maybe_deprecation
+
+            #maybe_span
+
+            #body
+        }};
+      }
+
+      #[allow(unused_imports)]
+      #reexport_visibility use #wrapper as #reexport;
     }
 
-    // allow the macro to be resolved with the same path as the command function
+    // allow the macro to be resolved next to the command function too, e.g. through glob imports
     #[allow(unused_imports)]
-    #visibility use #wrapper;
+    #visibility use #command::#reexport as #wrapper;
   )
   .into()
 }
@@ -306,29 +538,69 @@ fn body_async(
     message,
     resolver,
     acl,
+    stream,
   } = invoke;
+  let root = &attributes.root;
+  let result_conversions = result_conversions(function, attributes, true);
+  let command = &function.sig.ident;
+  let guards = attributes.guards.iter().map(|guard| {
+    quote!({
+      let context = #root::ipc::CommandContext::new(#plugin_name, stringify!(#command), &#message);
+      let outcome = #guard(&context);
+      let kind = (&outcome).guard_kind();
+      kind.future(outcome).await?;
+    })
+  });
+  let guards = quote!(#(#guards)*);
+  // the asynchronously initialized states are awaited before the arguments are extracted
+  let states = managed_state_types(function).into_iter().map(|ty| {
+    quote!((&&#root::ipc::private::StateArg::<#ty>::new()).state_ready(&#message).await?;)
+  });
+  let guards = quote!(#guards #(#states)*);
+  // the invoke is rejected as soon as the timeout elapses, the token is taken before the message
+  // is moved into the task
+  let (maybe_cancel_after, maybe_timeout) = match attributes.timeout {
+    Some(millis) => (
+      quote!(let cancellation = cancel_after(&#message, #millis);),
+      quote!(let task = until_cancelled(cancellation, task);),
+    ),
+    None => (TokenStream2::default(), TokenStream2::default()),
+  };
   parse_args(plugin_name, function, message, acl, attributes).map(|args| {
     #[cfg(feature = "tracing")]
     quote! {
       use tracing::Instrument;
 
       let span = tracing::debug_span!("ipc::request::run");
-      #resolver.respond_async_serialized(async move {
+      #maybe_cancel_after
+      let task = async move {
+        #guards
         let result = $path(#(#args?),*);
+        #result_conversions
+        let kind = (&result).stream_kind();
+        let result = kind.attach(result, &#stream);
         let kind = (&result).async_kind();
         kind.future(result).await
-      }
-      .instrument(span));
+      };
+      #maybe_timeout
+      #resolver.respond_async(task.instrument(span));
       return true;
     }
 
     #[cfg(not(feature = "tracing"))]
     quote! {
-      #resolver.respond_async_serialized(async move {
+      #maybe_cancel_after
+      let task = async move {
+        #guards
         let result = $path(#(#args?),*);
+        #result_conversions
+        let kind = (&result).stream_kind();
+        let result = kind.attach(result, &#stream);
         let kind = (&result).async_kind();
         kind.future(result).await
-      });
+      };
+      #maybe_timeout
+      #resolver.respond_async(task);
       return true;
     }
   })
@@ -349,6 +621,7 @@ fn body_blocking(
     message,
     resolver,
     acl,
+    stream,
   } = invoke;
   let args = parse_args(plugin_name, function, message, acl, attributes)?;
 
@@ -364,15 +637,110 @@ fn body_blocking(
     quote!()
   };
 
+  let root = &attributes.root;
+  let result_conversions = result_conversions(function, attributes, false);
+
+  let command = &function.sig.ident;
+  let guards = attributes.guards.iter().map(|guard| {
+    quote!({
+      let context = #root::ipc::CommandContext::new(#plugin_name, stringify!(#command), &#message);
+      if let Err(err) = check_guard(#guard(&context)) {
+        #resolver.invoke_error(err);
+        return true;
+      }
+    })
+  });
+
+  // blocking commands can only observe the timeout through their `CancellationToken`
+  let maybe_timeout = attributes.timeout.map(|millis| {
+    quote!(cancel_after(&#message, #millis);)
+  });
+
   Ok(quote! {
     #maybe_span
+    #maybe_timeout
+    #(#guards)*
     let result = $path(#(match #args #match_body),*);
+    #result_conversions
+    let kind = (&result).stream_kind();
+    let result = kind.attach(result, &#stream);
     let kind = (&result).blocking_kind();
     kind.block(result, #resolver);
     return true;
   })
 }
 
+/// Runs the blocking body on the thread selected by the execution context.
+fn dispatch_blocking(
+  body: TokenStream2,
+  invoke: &Invoke,
+  attributes: &WrapperAttributes,
+) -> TokenStream2 {
+  let Invoke {
+    message, resolver, ..
+  } = invoke;
+  let root = &attributes.root;
+
+  match attributes.execution_context {
+    ExecutionContext::BlockingPool => quote! {
+      #root::async_runtime::spawn_blocking(move || -> bool { #body });
+      return true;
+    },
+    ExecutionContext::Main => quote! {
+      let webview = #message.webview();
+      let resolver = #resolver.clone();
+      if let Err(err) = webview.run_on_main_thread(move || {
+        (move || -> bool { #body })();
+      }) {
+        resolver.invoke_error(err.into());
+      }
+      return true;
+    },
+    _ => quote! {
+      if default_command_execution(&#message) == #root::ipc::CommandExecution::Blocking {
+        #root::async_runtime::spawn_blocking(move || -> bool { #body });
+        return true;
+      }
+      #body
+    },
+  }
+}
+
+/// Statements applied to the command return value before it is dispatched to a response kind:
+/// the `error` conversion and the `raw_response` mode.
+fn result_conversions(
+  function: &ItemFn,
+  attributes: &WrapperAttributes,
+  is_async_body: bool,
+) -> TokenStream2 {
+  let root = &attributes.root;
+
+  if attributes.error.is_none() && !attributes.raw_response {
+    return TokenStream2::default();
+  }
+
+  // both conversions need the output of async functions
+  let maybe_await = if is_async_body && function.sig.asyncness.is_some() {
+    quote!(let result = result.await;)
+  } else {
+    quote!()
+  };
+
+  let maybe_error = attributes.error.as_ref().map(|error| {
+    quote_spanned!(error.span() => let result = ::core::result::Result::map_err(result, #error);)
+  });
+
+  let maybe_raw_response = attributes.raw_response.then(|| {
+    quote!(let result = #root::ipc::IntoRawResponse::into_raw_response(result);)
+  });
+
+  quote! {
+    #maybe_await
+    #maybe_error
+    #maybe_raw_response
+  }
+}
+
 /// Parse all arguments for the command wrapper to use from the signature of the command function.
 fn parse_args(
   plugin_name: &TokenStream2,
@@ -381,6 +749,15 @@ fn parse_args(
   acl: &Ident,
   attributes: &WrapperAttributes,
 ) -> syn::Result<Vec<TokenStream2>> {
+  // the bytes payload can only be handed over to a single argument
+  let mut raw_bytes_args = function.sig.inputs.iter().filter(|arg| is_raw_bytes_arg(arg));
+  if let (Some(_), Some(second)) = (raw_bytes_args.next(), raw_bytes_args.next()) {
+    return Err(syn::Error::new(
+      second.span(),
+      "a command can only have one `RawBytes` argument",
+    ));
+  }
+
   function
     .sig
     .inputs
@@ -398,6 +775,91 @@ fn parse_args(
     .collect()
 }
 
+/// Whether the argument is typed `RawBytes`, which borrows the bytes payload of the invoke.
+fn is_raw_bytes_arg(arg: &FnArg) -> bool {
+  match arg {
+    FnArg::Typed(arg) => match &*arg.ty {
+      syn::Type::Path(path) => path
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "RawBytes"),
+      _ => false,
+    },
+    FnArg::Receiver(_) => false,
+  }
+}
+
+/// The types of the arguments named `State`, with their lifetimes elided.
+///
+/// Whether they are a `tauri::State` is only known to the compiler, see
+/// `tauri::ipc::private::StateArg`. The types using the generic parameters of the function are
+/// skipped, since they are not in scope in the generated handler.
+fn managed_state_types(function: &ItemFn) -> Vec<TokenStream2> {
+  let generics = function
+    .sig
+    .generics
+    .type_params()
+    .map(|param| param.ident.to_string())
+    .collect::<Vec<_>>();
+
+  fn uses_generics(tokens: TokenStream2, generics: &[String]) -> bool {
+    tokens.into_iter().any(|token| match token {
+      proc_macro2::TokenTree::Ident(ident) => generics.contains(&ident.to_string()),
+      proc_macro2::TokenTree::Group(group) => uses_generics(group.stream(), generics),
+      _ => false,
+    })
+  }
+
+  // the named lifetimes of the function are not in scope in the generated handler either
+  fn elide_lifetimes(tokens: TokenStream2) -> TokenStream2 {
+    let mut lifetime = false;
+    tokens
+      .into_iter()
+      .map(|token| {
+        let token = match token {
+          proc_macro2::TokenTree::Ident(ident) if lifetime && ident != "static" => {
+            proc_macro2::TokenTree::Ident(Ident::new("_", ident.span()))
+          }
+          proc_macro2::TokenTree::Group(group) => {
+            let mut elided =
+              proc_macro2::Group::new(group.delimiter(), elide_lifetimes(group.stream()));
+            elided.set_span(group.span());
+            proc_macro2::TokenTree::Group(elided)
+          }
+          token => token,
+        };
+        lifetime = matches!(&token, proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'');
+        token
+      })
+      .collect()
+  }
+
+  function
+    .sig
+    .inputs
+    .iter()
+    .filter_map(|arg| match arg {
+      FnArg::Typed(arg) => match &*arg.ty {
+        syn::Type::Path(path)
+          if path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "State") =>
+        {
+          Some(&arg.ty)
+        }
+        _ => None,
+      },
+      FnArg::Receiver(_) => None,
+    })
+    .map(|ty| quote!(#ty))
+    .filter(|ty| !uses_generics(ty.clone(), &generics))
+    .map(elide_lifetimes)
+    .collect()
+}
+
 /// Transform a [`FnArg`] into a command argument.
 fn parse_arg(
   plugin_name: &TokenStream2,
@@ -407,9 +869,60 @@ fn parse_arg(
   acl: &Ident,
   attributes: &WrapperAttributes,
 ) -> syn::Result<TokenStream2> {
+  let (key, arg_attributes) = parse_arg_key(arg, attributes)?;
+
+  let root = &attributes.root;
+
+  let mut item = quote!(#root::ipc::CommandItem {
+    plugin: #plugin_name,
+    name: stringify!(#command),
+    key: #key,
+    message: &#message,
+    acl: &#acl,
+  });
+
+  // explicitly renamed arguments only accept their own key
+  if attributes.any_case && arg_attributes.rename.is_none() {
+    let alternate = match attributes.argument_case {
+      ArgumentCase::Snake => key.to_lower_camel_case(),
+      ArgumentCase::Camel | ArgumentCase::Kebab => key.to_snake_case(),
+    };
+    if alternate != key {
+      item = quote!(#root::ipc::private::any_case_item(#item, #alternate));
+    }
+  }
+
+  if let Some(span) = arg_attributes.from_request {
+    return Ok(quote_spanned!(span => #root::ipc::private::from_request_arg(#item)));
+  }
+
+  match (arg_attributes.default, arg_attributes.window) {
+    (Some((span, _)), Some(_)) => Err(syn::Error::new(
+      span,
+      "`default` and `window` cannot be used on the same argument",
+    )),
+    (None, Some(label)) => Ok(
+      quote_spanned!(label.span() => #root::ipc::private::labeled_arg(
+        #item,
+        #label
+      )),
+    ),
+    (Some((span, default)), None) => Ok(quote_spanned!(span => #root::ipc::private::default_arg(
+      #item,
+      || #default
+    ))),
+    (None, None) => Ok(quote!(#root::ipc::CommandArg::from_command(#item))),
+  }
+}
+
+/// Resolve the key used to read the argument from the invoke payload.
+fn parse_arg_key(
+  arg: &FnArg,
+  attributes: &WrapperAttributes,
+) -> syn::Result<(String, ArgAttributes)> {
   // we have no use for self arguments
-  let mut arg = match arg {
-    FnArg::Typed(arg) => arg.pat.as_ref().clone(),
+  let (mut arg, arg_attributes) = match arg {
+    FnArg::Typed(arg) => (arg.pat.as_ref().clone(), ArgAttributes::parse(&arg.attrs)?),
     FnArg::Receiver(arg) => {
       return Err(syn::Error::new(
         arg.span(),
@@ -440,26 +953,167 @@ fn parse_arg(
     ));
   }
 
-  match attributes.argument_case {
-    ArgumentCase::Camel => {
-      key = key.to_lower_camel_case();
-    }
-    ArgumentCase::Snake => {
-      key = key.to_snake_case();
+  if let Some(rename) = &arg_attributes.rename {
+    key = rename.clone();
+  } else {
+    match attributes.argument_case {
+      ArgumentCase::Camel => {
+        key = key.to_lower_camel_case();
+      }
+      ArgumentCase::Snake => {
+        key = key.to_snake_case();
+      }
+      ArgumentCase::Kebab => {
+        key = key.to_kebab_case();
+      }
     }
   }
 
-  let root = &attributes.root;
+  Ok((key, arg_attributes))
+}
+
+/// Generates the `CommandMeta` describing the command, naming the `tauri` crate with `root`.
+fn command_meta(
+  function: &ItemFn,
+  attributes: &WrapperAttributes,
+  root: &TokenStream2,
+) -> syn::Result<TokenStream2> {
+  let name = &function.sig.ident;
+
+  let args = function
+    .sig
+    .inputs
+    .iter()
+    .map(|arg| {
+      let (key, _) = parse_arg_key(arg, attributes)?;
+      let (name, ty) = match arg {
+        FnArg::Typed(arg) => match &*arg.pat {
+          Pat::Ident(pat) => (pat.ident.unraw().to_string(), &arg.ty),
+          pat => (type_name(pat), &arg.ty),
+        },
+        // already rejected by `parse_arg_key`
+        FnArg::Receiver(_) => unreachable!(),
+      };
+      let ty = type_name(ty);
+      Ok(quote!(#root::ipc::CommandArgMeta { name: #name, key: #key, ty: #ty }))
+    })
+    .collect::<syn::Result<Vec<_>>>()?;
+
+  let return_type = match &function.sig.output {
+    syn::ReturnType::Default => "()".to_string(),
+    syn::ReturnType::Type(_, ty) => type_name(ty),
+  };
+
+  let docs = doc_comment(function);
+
+  let is_async = matches!(attributes.execution_context, ExecutionContext::Async);
+  let deprecated = match &attributes.deprecated {
+    Some(note) => quote!(::core::option::Option::Some(#note)),
+    None => quote!(::core::option::Option::None),
+  };
 
-  Ok(quote!(#root::ipc::CommandArg::from_command(
-    #root::ipc::CommandItem {
-      plugin: #plugin_name,
-      name: stringify!(#command),
-      key: #key,
-      message: &#message,
-      acl: &#acl,
+  Ok(quote!(#root::ipc::CommandMeta {
+    name: stringify!(#name),
+    args: &[#(#args),*],
+    return_type: #return_type,
+    docs: #docs,
+    is_async: #is_async,
+    deprecated: #deprecated,
+  }))
+}
+
+/// The doc comment of the command function, without the leading spaces.
+fn doc_comment(function: &ItemFn) -> String {
+  function
+    .attrs
+    .iter()
+    .filter_map(|attr| match &attr.meta {
+      Meta::NameValue(doc) if doc.path.is_ident("doc") => match &doc.value {
+        Expr::Lit(ExprLit {
+          lit: Lit::Str(s), ..
+        }) => Some(s.value()),
+        _ => None,
+      },
+      _ => None,
+    })
+    .map(|line| line.strip_prefix(' ').map(ToString::to_string).unwrap_or(line))
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Renders a type or pattern the way it is usually written.
+fn type_name(tokens: &impl quote::ToTokens) -> String {
+  tokens
+    .to_token_stream()
+    .to_string()
+    .replace(" :: ", "::")
+    .replace(" <", "<")
+    .replace("< ", "<")
+    .replace(" >", ">")
+    .replace(" ,", ",")
+    .replace("& ", "&")
+}
+
+/// The `#[arg(...)]` attributes of a command argument.
+#[derive(Default)]
+struct ArgAttributes {
+  /// `#[arg(default)]` or `#[arg(default = <expr>)]`.
+  default: Option<(Span, TokenStream2)>,
+  /// `#[arg(rename = "name")]`.
+  rename: Option<String>,
+  /// `#[arg(window = "label")]`.
+  window: Option<LitStr>,
+  /// `#[arg(from_request)]`.
+  from_request: Option<Span>,
+}
+
+impl ArgAttributes {
+  fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+    let mut arg_attributes = Self::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("arg")) {
+      attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("default") {
+          let value = if meta.input.peek(Token![=]) {
+            let expr: Expr = meta.value()?.parse()?;
+            quote!(#expr)
+          } else {
+            quote!(::core::default::Default::default())
+          };
+          arg_attributes.default.replace((attr.span(), value));
+          Ok(())
+        } else if meta.path.is_ident("rename") {
+          let name: LitStr = meta.value()?.parse()?;
+          if name.value().is_empty() {
+            return Err(syn::Error::new(name.span(), "argument name cannot be empty"));
+          }
+          arg_attributes.rename.replace(name.value());
+          Ok(())
+        } else if meta.path.is_ident("window") {
+          arg_attributes.window.replace(meta.value()?.parse()?);
+          Ok(())
+        } else if meta.path.is_ident("from_request") {
+          arg_attributes.from_request.replace(attr.span());
+          Ok(())
+        } else {
+          Err(meta.error(
+            "unsupported argument attribute, expected `default`, `rename`, `window` or `from_request`",
+          ))
+        }
+      })?;
     }
-  )))
+
+    if let Some(span) = arg_attributes.from_request {
+      if arg_attributes.default.is_some() || arg_attributes.window.is_some() {
+        return Err(syn::Error::new(
+          span,
+          "`from_request` cannot be combined with `default` or `window`",
+        ));
+      }
+    }
+
+    Ok(arg_attributes)
+  }
 }
 
 fn is_rustc_at_least(major: u32, minor: u32) -> bool {
@@ -500,3 +1154,183 @@ fn cross_command(bin: &str) -> std::process::Command {
   let cmd = std::process::Command::new(bin);
   cmd
 }
+
+#[cfg(test)]
+mod tests {
+  use quote::{format_ident, quote};
+  use syn::{FnArg, ItemFn};
+
+  use super::{
+    managed_state_types, parse_arg, parse_arg_key, parse_args, parse_timeout, WrapperAttributes,
+  };
+
+  /// The key of `arg` on a command declared with `#[command(<attributes>)]`.
+  fn key(attributes: &str, arg: &str) -> syn::Result<String> {
+    let attributes = syn::parse_str::<WrapperAttributes>(attributes)?;
+    let arg = syn::parse_str::<FnArg>(arg)?;
+    parse_arg_key(&arg, &attributes).map(|(key, _)| key)
+  }
+
+  #[test]
+  fn keys_follow_rename_all() {
+    assert_eq!(key("", "file_path: String").unwrap(), "filePath");
+    assert_eq!(
+      key(r#"rename_all = "camelCase""#, "file_path: String").unwrap(),
+      "filePath"
+    );
+    assert_eq!(
+      key(r#"rename_all = "snake_case""#, "filePath: String").unwrap(),
+      "file_path"
+    );
+    assert_eq!(
+      key(r#"rename_all = "kebab-case""#, "file_path: String").unwrap(),
+      "file-path"
+    );
+    assert_eq!(
+      key(r#"rename_all = "kebab-case""#, "r#type: String").unwrap(),
+      "type"
+    );
+  }
+
+  #[test]
+  fn renamed_arguments_ignore_rename_all() {
+    assert_eq!(
+      key(
+        r#"rename_all = "kebab-case""#,
+        r#"#[arg(rename = "customName")] file_path: String"#
+      )
+      .unwrap(),
+      "customName"
+    );
+    assert_eq!(
+      key(
+        r#"rename_all = "snake_case""#,
+        r#"#[arg(rename = "file-path")] file_path: String"#
+      )
+      .unwrap(),
+      "file-path"
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_renames() {
+    assert_eq!(
+      key("", r#"#[arg(rename = "")] file_path: String"#)
+        .unwrap_err()
+        .to_string(),
+      "argument name cannot be empty"
+    );
+    assert_eq!(
+      key(r#"rename_all = "PascalCase""#, "file_path: String")
+        .unwrap_err()
+        .to_string(),
+      r#"expected "camelCase", "snake_case" or "kebab-case""#
+    );
+  }
+
+  #[test]
+  fn accepts_a_single_raw_bytes_argument() {
+    let attributes = syn::parse_str::<WrapperAttributes>("").unwrap();
+    let ident = format_ident!("message");
+    let parse = |function: &str| {
+      let function = syn::parse_str::<ItemFn>(function).unwrap();
+      parse_args(&quote!(None), &function, &ident, &ident, &attributes)
+    };
+
+    assert!(parse("fn upload(name: String, data: RawBytes<'_>) {}").is_ok());
+    assert_eq!(
+      parse("fn upload(data: RawBytes<'_>, more: tauri::ipc::RawBytes<'_>) {}")
+        .err()
+        .unwrap()
+        .to_string(),
+      "a command can only have one `RawBytes` argument"
+    );
+  }
+
+  #[test]
+  fn any_case_reads_the_alternate_key() {
+    let ident = format_ident!("message");
+    let item = |attributes: &str, arg: &str| {
+      let attributes = syn::parse_str::<WrapperAttributes>(attributes).unwrap();
+      let arg = syn::parse_str::<FnArg>(arg).unwrap();
+      parse_arg(
+        &quote!(None),
+        &format_ident!("read"),
+        &arg,
+        &ident,
+        &ident,
+        &attributes,
+      )
+      .unwrap()
+      .to_string()
+    };
+
+    let camel = item(r#"arg_case = "any""#, "file_path: String");
+    assert!(camel.contains("any_case_item"));
+    assert!(camel.contains(r#"key : "filePath""#));
+    assert!(camel.contains(r#""file_path""#));
+
+    let snake = item(
+      r#"rename_all = "snake_case", arg_case = "any""#,
+      "file_path: String",
+    );
+    assert!(snake.contains(r#""filePath""#));
+
+    // single words have no alternate spelling
+    assert!(!item(r#"arg_case = "any""#, "path: String").contains("any_case_item"));
+
+    // explicitly renamed arguments only accept their own key
+    assert!(!item(
+      r#"arg_case = "any""#,
+      r#"#[arg(rename = "source")] file_path: String"#
+    )
+    .contains("any_case_item"));
+  }
+
+  #[test]
+  fn awaits_the_state_arguments() {
+    let function = syn::parse_str::<ItemFn>(
+      "async fn command<'a, R: Runtime>(db: State<'a, Db>, local: app::State<Config>, \
+       window: State<'static, R>, name: String) {}",
+    )
+    .unwrap();
+
+    let types = managed_state_types(&function)
+      .into_iter()
+      .map(|ty| ty.to_string())
+      .collect::<Vec<_>>();
+
+    // `app::State` is resolved by the compiler, the types using generics are skipped
+    assert_eq!(types, ["State < '_ , Db >", "app :: State < Config >"]);
+  }
+
+  #[test]
+  fn timeouts_are_parsed_in_milliseconds() {
+    let timeout = |value: &str| {
+      parse_timeout(&syn::LitStr::new(
+        value,
+        proc_macro2::Span::call_site(),
+      ))
+    };
+
+    assert_eq!(timeout("500ms").unwrap(), 500);
+    assert_eq!(timeout("30s").unwrap(), 30_000);
+    assert_eq!(timeout("2m").unwrap(), 120_000);
+
+    for invalid in ["", "30", "30h", "-1s", "1.5s", "0s"] {
+      assert!(timeout(invalid).is_err(), "{invalid} was accepted");
+    }
+
+    // overflows instead of panicking the macro
+    assert!(timeout(&format!("{}m", u64::MAX / 1000)).is_err());
+  }
+
+  #[test]
+  fn timeouts_must_be_strings() {
+    assert!(syn::parse_str::<WrapperAttributes>(r#"timeout = "30s""#).is_ok());
+
+    let error = syn::parse_str::<WrapperAttributes>("timeout = 30").err().unwrap();
+
+    assert!(error.to_string().contains("expected a duration string"));
+  }
+}
@@ -14,7 +14,7 @@ use std::path::PathBuf;
 
 use proc_macro::TokenStream;
 use quote::{ToTokens, quote};
-use syn::{LitStr, parse_macro_input, parse2};
+use syn::{DeriveInput, LitStr, parse_macro_input, parse2};
 use tauri_codegen::image::CachedIcon;
 
 use crate::context::ContextItems;
@@ -30,6 +30,103 @@ mod context;
 /// Mark a function as a command handler. It creates a wrapper function with the
 /// necessary glue code.
 ///
+/// # Argument defaults
+/// Arguments deserialized from the invoke payload can be marked with
+/// `#[arg(default)]` to use [`Default::default`] when the key is missing, or
+/// `#[arg(default = <expr>)]` to use the given expression instead:
+///
+/// ```rust,ignore
+/// #[tauri::command]
+/// fn search(query:String, #[arg(default = 20)] limit:u32, #[arg(default)] offset:u32) {}
+/// ```
+///
+/// # Argument names
+/// Arguments are read from the camelCase keys of the invoke payload by
+/// default. Use `#[command(rename_all = "snake_case")]` (or `"camelCase"`,
+/// `"kebab-case"`) to change the convention for every argument, and
+/// `#[arg(rename = "name")]` to set the key of a single argument:
+///
+/// ```rust,ignore
+/// #[tauri::command(rename_all = "snake_case")]
+/// fn open(file_path:String, #[arg(rename = "readOnly")] read_only:bool) {}
+/// ```
+///
+/// While migrating a frontend between conventions,
+/// `#[command(arg_case = "any")]` also accepts the snake_case spelling of
+/// camelCase keys and vice versa. The declared spelling wins when both are
+/// present, and the use of the other one is logged at the debug level.
+///
+/// # Other windows
+/// A `tauri::WebviewWindow` argument marked with `#[arg(window = "label")]`
+/// receives the window with that label instead of the calling one, and the
+/// invoke is rejected if no such window exists. With the `unstable` feature,
+/// `tauri::Window` and `tauri::Webview` arguments are supported as well:
+///
+/// ```rust,ignore
+/// #[tauri::command]
+/// fn reload_main<R:tauri::Runtime>(
+///   caller:tauri::WebviewWindow<R>,
+///   #[arg(window = "main")] main:tauri::WebviewWindow<R>,
+/// ) -> tauri::Result<()> {
+///   main.eval("location.reload()")
+/// }
+/// ```
+///
+/// # Custom extractors
+/// Arguments marked with `#[arg(from_request)]` are extracted with their
+/// `tauri::ipc::FromCommandRequest` implementation instead of being
+/// deserialized from the invoke payload. Extraction errors name the extractor
+/// type, the argument and the command.
+///
+/// # Execution
+/// Non-async commands run on the main thread unless
+/// `tauri::Builder::default_command_execution` says otherwise. Use
+/// `#[command(exec = "blocking")]` to run the command on the blocking thread
+/// pool, `#[command(exec = "main")]` to always run it on the main thread, or
+/// `#[command(exec = "async")]` (same as `#[command(async)]`) to run it on the
+/// async runtime. Async functions only accept `exec = "async"`.
+///
+/// # Guards
+/// `#[command(guard = "crate::guards::require_login")]` runs the given function
+/// before the arguments are deserialized, rejecting the invoke if it returns an
+/// error. The attribute can be repeated, guards run in declaration order. See
+/// `tauri::ipc::CommandContext` for the guard signature; async commands also
+/// accept async guards. Guard paths are resolved where `generate_handler!` is
+/// called, so prefer absolute paths.
+///
+/// # Error conversion
+/// `#[command(error = "crate::ipc_error::to_invoke_error")]` applies the given
+/// `fn(E) -> tauri::ipc::InvokeError` to the error of the returned `Result`,
+/// so the error type doesn't need to implement `Serialize` or
+/// `Into<tauri::ipc::InvokeError>`.
+///
+/// # Raw responses
+/// With `#[command(raw_response)]` the return value is sent to the webview
+/// without serialization. The function must return a type implementing
+/// `tauri::ipc::IntoRawResponse`, such as `tauri::ipc::Response`, `Vec<u8>` or
+/// a `(Vec<u8>, content_type)` tuple, optionally wrapped in a `Result`.
+///
+/// # Deprecation
+/// `#[command(deprecated = "use save_document instead")]` keeps the command
+/// working but logs a warning with the note and the label of the calling
+/// webview the first time it is invoked. The warning goes through `tracing`
+/// when its feature is enabled, otherwise through `log`, falling back to
+/// stderr in debug builds when no logger is set. The note is also included in
+/// the command metadata and the generated TypeScript bindings.
+///
+/// # Timeout
+/// `#[command(timeout = "30s")]` triggers the `tauri::ipc::CancellationToken`
+/// of the invoke once the duration elapses (`ms`, `s` and `m` units are
+/// accepted). Async commands are also rejected with a `cancelled` error kind at
+/// that point, while blocking commands keep running until they observe the
+/// token.
+///
+/// # Generated items
+/// Next to the function, the macro generates a hidden module named like the
+/// command holding the wrapper `generate_handler!` calls, so re-exporting the
+/// command with `pub use` re-exports its wrapper too. As a consequence, no
+/// other module or type can be named like a command in the same module.
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
@@ -39,6 +136,56 @@ pub fn command(attributes:TokenStream, item:TokenStream) -> TokenStream {
 	command::wrapper(attributes, item)
 }
 
+/// Implements `Serialize` for an error enum so it can be returned by commands.
+///
+/// Every variant is serialized as
+/// `{ "kind": "VariantName", "message": "<Display output>", "data": <payload> }`,
+/// so the enum must also implement [`std::fmt::Display`], usually through
+/// `thiserror`. The payload is:
+///
+/// - `null` for unit variants;
+/// - the field for single field tuple variants;
+/// - an array of the fields for other tuple variants;
+/// - an object keyed by the field names for struct variants.
+///
+/// Since the enum implements `Serialize`, it also converts into
+/// `tauri::ipc::InvokeError`.
+///
+/// # Attributes
+///
+/// - `#[command_error(rename_all = "...")]` on the enum renames every kind, one
+///   of `"camelCase"`, `"snake_case"`, `"kebab-case"` or
+///   `"SCREAMING_SNAKE_CASE"`.
+/// - `#[command_error(kind = "...")]` on a variant sets its kind.
+/// - `#[command_error(skip)]` on a field leaves it out of the payload. A
+///   payload without fields is `null`.
+/// - `#[command_error(flatten)]` on a named field merges the entries of the
+///   object it serializes to into the payload.
+/// - `#[command_error(from)]` on the field of a single field variant
+///   implements `From<FieldType>` for the enum.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Debug, thiserror::Error, tauri::CommandError)]
+/// #[command_error(rename_all = "snake_case")]
+/// enum Error {
+///   #[error(transparent)]
+///   Io(#[command_error(from, skip)] std::io::Error),
+///   #[error("{path} was not found")]
+///   NotFound { path:String },
+/// }
+///
+/// // Error::NotFound { path: "a.txt".into() } is serialized as
+/// // { "kind": "not_found", "message": "a.txt was not found", "data": { "path": "a.txt" } }
+/// ```
+#[proc_macro_derive(CommandError, attributes(command_error))]
+pub fn command_error(input:TokenStream) -> TokenStream {
+	command::error::derive(parse_macro_input!(input as DeriveInput))
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
 #[proc_macro_attribute]
 pub fn mobile_entry_point(attributes:TokenStream, item:TokenStream) -> TokenStream {
 	mobile::entry_point(attributes, item)
@@ -62,6 +209,17 @@ pub fn mobile_entry_point(attributes:TokenStream, item:TokenStream) -> TokenStre
 ///   let _handler = generate_handler![command_one, command_two];
 /// }
 /// ```
+///
+/// Commands can be listed with any path they are reachable from, including
+/// `pub use` and glob re-exports, since re-exporting a command also re-exports
+/// the hidden module holding its wrapper. A command re-exported with
+/// `pub use ... as` is registered under its new name:
+///
+/// ```rust,ignore
+/// pub use crate::features::files::save;
+/// pub use crate::features::files::open as open_file;
+/// ```
+///
 /// # Stability
 /// The output of this macro is managed internally by Tauri,
 /// and should not be accessed directly on normal applications.
@@ -71,6 +229,28 @@ pub fn generate_handler(item:TokenStream) -> TokenStream {
 	parse_macro_input!(item as command::Handler).into()
 }
 
+/// Accepts a list of command functions. Creates a
+/// `tauri::ipc::HandlerCollection` that can be merged with the collections of
+/// other modules using `tauri::compose_handlers!`.
+///
+/// # Examples
+/// ```rust,ignore
+/// use tauri_macros::{command, generate_handler_collection};
+/// #[command]
+/// fn command_one() {}
+/// pub fn handlers<R: tauri::Runtime>() -> tauri::ipc::HandlerCollection<R> {
+///   generate_handler_collection![command_one]
+/// }
+/// ```
+/// # Stability
+/// The output of this macro is managed internally by Tauri,
+/// and should not be accessed directly on normal applications.
+/// It may have breaking changes in the future.
+#[proc_macro]
+pub fn generate_handler_collection(item:TokenStream) -> TokenStream {
+	parse_macro_input!(item as command::Handler).into_collection().into()
+}
+
 /// Reads a Tauri config file and generates a `::tauri::Context` based on the
 /// content.
 ///
@@ -0,0 +1,328 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Window aspect ratio.
+//!
+//! tao can not lock the aspect ratio of a window, so it is done on the
+//! platform window here.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: the window rectangle is adjusted while the window is resized
+//!   by the user, from the `WM_SIZING` message.
+//! - **Linux**: uses the aspect ratio geometry hint. GTK replaces all the
+//!   geometry hints at once, so the minimum and maximum sizes set by tao are
+//!   set again with it.
+//! - **macOS**: uses the content aspect ratio of the `NSWindow`.
+//! - **iOS / Android**: Unsupported.
+
+use tao::{dpi::PhysicalSize, window::Window};
+use tauri_runtime::window::WindowSizeConstraints;
+
+/// The inner size a window of the given `size` is resized to so that it
+/// satisfies `constraints`, its height following its width first when an
+/// `aspect_ratio` is given.
+pub fn constrained_size(
+	size:PhysicalSize<u32>,
+	constraints:&tao::window::WindowSizeConstraints,
+	aspect_ratio:Option<f64>,
+	scale_factor:f64,
+) -> PhysicalSize<u32> {
+	let mut desired = size.cast::<f64>();
+
+	if let Some(ratio) = aspect_ratio.filter(|ratio| *ratio > 0.0) {
+		desired.height = desired.width / ratio;
+	}
+
+	constraints.clamp(desired.into(), scale_factor).to_physical::<u32>(scale_factor)
+}
+
+#[cfg(windows)]
+const ASPECT_RATIO_SUBCLASS_ID:usize =
+	(windows::Win32::UI::WindowsAndMessaging::WM_USER + 3) as usize;
+
+/// Locks the inner size of `window` to `aspect_ratio`, or unlocks it when
+/// `None`.
+#[cfg(windows)]
+pub fn set(window:&Window, _constraints:&WindowSizeConstraints, aspect_ratio:Option<f64>) {
+	use tao::platform::windows::WindowExtWindows;
+	use windows::Win32::{
+		Foundation::HWND,
+		UI::Shell::{RemoveWindowSubclass, SetWindowSubclass},
+	};
+
+	let hwnd = HWND(window.hwnd() as _);
+
+	unsafe {
+		match aspect_ratio.filter(|ratio| *ratio > 0.0) {
+			// the ratio is stored in the reference data of the subclass, which is
+			// replaced when the subclass is set again
+			Some(ratio) => {
+				let _ = SetWindowSubclass(
+					hwnd,
+					Some(aspect_ratio_proc),
+					ASPECT_RATIO_SUBCLASS_ID,
+					(ratio as f32).to_bits() as usize,
+				);
+			},
+			None => {
+				let _ =
+					RemoveWindowSubclass(hwnd, Some(aspect_ratio_proc), ASPECT_RATIO_SUBCLASS_ID);
+			},
+		}
+	}
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn aspect_ratio_proc(
+	hwnd:windows::Win32::Foundation::HWND,
+	msg:u32,
+	wparam:windows::Win32::Foundation::WPARAM,
+	lparam:windows::Win32::Foundation::LPARAM,
+	_:usize,
+	ratio:usize,
+) -> windows::Win32::Foundation::LRESULT {
+	use windows::Win32::{
+		Foundation::{LRESULT, RECT},
+		UI::{
+			Shell::DefSubclassProc,
+			WindowsAndMessaging::{
+				GetClientRect,
+				GetWindowRect,
+				WM_SIZING,
+				WMSZ_BOTTOM,
+				WMSZ_TOP,
+				WMSZ_TOPLEFT,
+				WMSZ_TOPRIGHT,
+			},
+		},
+	};
+
+	if msg != WM_SIZING {
+		return DefSubclassProc(hwnd, msg, wparam, lparam);
+	}
+
+	let ratio = f32::from_bits(ratio as u32) as f64;
+
+	let rect = &mut *(lparam.0 as *mut RECT);
+
+	// the ratio applies to the client area, without the frame
+	let mut window_rect = RECT::default();
+
+	let mut client_rect = RECT::default();
+
+	if GetWindowRect(hwnd, &mut window_rect).is_err()
+		|| GetClientRect(hwnd, &mut client_rect).is_err()
+	{
+		return DefSubclassProc(hwnd, msg, wparam, lparam);
+	}
+
+	let frame_width =
+		(window_rect.right - window_rect.left) - (client_rect.right - client_rect.left);
+
+	let frame_height =
+		(window_rect.bottom - window_rect.top) - (client_rect.bottom - client_rect.top);
+
+	let width = (rect.right - rect.left - frame_width).max(0);
+
+	let height = (rect.bottom - rect.top - frame_height).max(0);
+
+	match wparam.0 as u32 {
+		// the width follows the height when dragging the top or bottom edge
+		WMSZ_TOP | WMSZ_BOTTOM => {
+			rect.right = rect.left + (height as f64 * ratio).round() as i32 + frame_width;
+		},
+
+		// the height follows the width otherwise, growing from the dragged edge
+		edge => {
+			let height = (width as f64 / ratio).round() as i32 + frame_height;
+
+			if matches!(edge, WMSZ_TOPLEFT | WMSZ_TOPRIGHT) {
+				rect.top = rect.bottom - height;
+			} else {
+				rect.bottom = rect.top + height;
+			}
+		},
+	}
+
+	LRESULT(1)
+}
+
+/// Locks the inner size of `window` to `aspect_ratio`, or unlocks it when
+/// `None`.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn set(window:&Window, constraints:&WindowSizeConstraints, aspect_ratio:Option<f64>) {
+	use gtk::{
+		gdk::{Geometry, Gravity, WindowHints},
+		prelude::*,
+	};
+	use tao::platform::unix::WindowExtUnix;
+
+	let scale_factor = window.scale_factor();
+
+	// GTK geometry hints are in logical pixels
+	let logical = |unit:Option<tao::dpi::PixelUnit>| {
+		unit.map(|unit| unit.to_logical::<f64>(scale_factor).0.round() as i32)
+	};
+
+	let (min_width, min_height) = (logical(constraints.min_width), logical(constraints.min_height));
+
+	let (max_width, max_height) = (logical(constraints.max_width), logical(constraints.max_height));
+
+	let mut hints = WindowHints::empty();
+
+	if min_width.is_some() || min_height.is_some() {
+		hints |= WindowHints::MIN_SIZE;
+	}
+
+	if max_width.is_some() || max_height.is_some() {
+		hints |= WindowHints::MAX_SIZE;
+	}
+
+	let ratio = aspect_ratio.filter(|ratio| *ratio > 0.0);
+
+	if ratio.is_some() {
+		hints |= WindowHints::ASPECT;
+	}
+
+	let geometry = Geometry::new(
+		min_width.unwrap_or(0),
+		min_height.unwrap_or(0),
+		max_width.unwrap_or(i32::MAX),
+		max_height.unwrap_or(i32::MAX),
+		0,
+		0,
+		0,
+		0,
+		ratio.unwrap_or_default(),
+		ratio.unwrap_or_default(),
+		Gravity::NorthWest,
+	);
+
+	window.gtk_window().set_geometry_hints(None::<&gtk::Widget>, Some(&geometry), hints);
+}
+
+/// Locks the inner size of `window` to `aspect_ratio`, or unlocks it when
+/// `None`.
+#[cfg(target_os = "macos")]
+pub fn set(window:&Window, _constraints:&WindowSizeConstraints, aspect_ratio:Option<f64>) {
+	use objc2_app_kit::NSWindow;
+	use objc2_foundation::NSSize;
+	use tao::platform::macos::WindowExtMacOS;
+
+	let ns_window:&NSWindow = unsafe { &*window.ns_window().cast() };
+
+	match aspect_ratio.filter(|ratio| *ratio > 0.0) {
+		Some(ratio) => ns_window.setContentAspectRatio(NSSize::new(ratio, 1.0)),
+		// the aspect ratio and the resize increments exclude each other, so
+		// setting the default increments removes the aspect ratio
+		None => ns_window.setContentResizeIncrements(NSSize::new(1.0, 1.0)),
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn set(_window:&Window, _constraints:&WindowSizeConstraints, _aspect_ratio:Option<f64>) {}
+
+#[cfg(test)]
+mod tests {
+	use tao::{
+		dpi::{LogicalUnit, PhysicalSize, PhysicalUnit},
+		window::WindowSizeConstraints,
+	};
+
+	use super::constrained_size;
+
+	fn physical(width:u32, height:u32) -> PhysicalSize<u32> { PhysicalSize::new(width, height) }
+
+	#[test]
+	fn sizes_are_clamped_to_the_constraints() {
+		let constraints = WindowSizeConstraints {
+			min_width:Some(PhysicalUnit::new(400).into()),
+			min_height:Some(PhysicalUnit::new(300).into()),
+			max_width:Some(PhysicalUnit::new(800).into()),
+			max_height:Some(PhysicalUnit::new(600).into()),
+		};
+
+		assert_eq!(
+			constrained_size(physical(200, 100), &constraints, None, 1.0),
+			physical(400, 300)
+		);
+		assert_eq!(
+			constrained_size(physical(500, 400), &constraints, None, 1.0),
+			physical(500, 400)
+		);
+		assert_eq!(
+			constrained_size(physical(1000, 700), &constraints, None, 1.0),
+			physical(800, 600)
+		);
+		assert_eq!(
+			constrained_size(physical(1000, 100), &constraints, None, 1.0),
+			physical(800, 300)
+		);
+	}
+
+	#[test]
+	fn logical_constraints_are_scaled() {
+		let constraints = WindowSizeConstraints {
+			min_width:Some(LogicalUnit::new(300.0).into()),
+			..Default::default()
+		};
+
+		assert_eq!(
+			constrained_size(physical(400, 400), &constraints, None, 2.0),
+			physical(600, 400)
+		);
+		assert_eq!(
+			constrained_size(physical(400, 400), &constraints, None, 1.0),
+			physical(400, 400)
+		);
+	}
+
+	#[test]
+	fn locked_height_follows_the_width() {
+		let constraints = WindowSizeConstraints::default();
+
+		assert_eq!(
+			constrained_size(physical(800, 600), &constraints, Some(2.0), 1.0),
+			physical(800, 400)
+		);
+		assert_eq!(
+			constrained_size(physical(800, 600), &constraints, None, 1.0),
+			physical(800, 600)
+		);
+
+		// a ratio that is not positive doesn't lock the size
+		assert_eq!(
+			constrained_size(physical(800, 600), &constraints, Some(0.0), 1.0),
+			physical(800, 600)
+		);
+	}
+
+	#[test]
+	fn constraints_win_over_the_aspect_ratio() {
+		let constraints = WindowSizeConstraints {
+			min_height:Some(PhysicalUnit::new(300).into()),
+			..Default::default()
+		};
+
+		assert_eq!(
+			constrained_size(physical(800, 600), &constraints, Some(4.0), 1.0),
+			physical(800, 300)
+		);
+	}
+}
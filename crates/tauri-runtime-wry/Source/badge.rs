@@ -0,0 +1,344 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Application badge.
+//!
+//! tao only sets the badge through a window and has no badge on Windows, so
+//! the application badge is set with the platform APIs directly and the
+//! Windows badge is drawn as the taskbar overlay icon.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: the badge is a red circle drawn with GDI, with counts above
+//!   99 shown as `99+`. It replaces the overlay icon set by the user until it
+//!   is removed.
+//! - **Linux**: the count is sent with the `com.canonical.Unity.LauncherEntry`
+//!   D-Bus signal, shown by KDE and GNOME with the Dash to Dock extension.
+//! - **macOS**: the badge is the label of the dock tile.
+
+/// The label shown for the badge `count`, `None` and `0` removing the badge.
+#[cfg(any(windows, target_os = "macos", test))]
+pub fn count_label(count:Option<u64>) -> Option<String> {
+	count.filter(|count| *count != 0).map(|count| {
+		if cfg!(windows) && count > 99 { "99+".into() } else { count.to_string() }
+	})
+}
+
+/// The badge `count` of a window, whose negative values remove the badge.
+#[cfg(any(windows, target_os = "macos", test))]
+pub fn window_count(count:Option<i64>) -> Option<u64> {
+	count.and_then(|count| u64::try_from(count).ok())
+}
+
+#[cfg(windows)]
+thread_local! {
+	// the app badge, drawn on the windows without a badge of their own
+	static APP_BADGE:std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Sets the app badge label, shown on the windows without their own badge
+/// once they [apply](Overlay::apply) it.
+#[cfg(windows)]
+pub fn set_app_label(label:Option<String>) { APP_BADGE.with(|badge| badge.replace(label)); }
+
+/// The taskbar overlay of a window.
+///
+/// The badge is drawn instead of the overlay icon set by the user, which is
+/// shown again once the badge is removed.
+#[cfg(windows)]
+#[derive(Default)]
+pub struct Overlay {
+	/// The overlay icon set by the user.
+	pub icon:Option<tao::window::Icon>,
+	/// The badge label of the window, taking precedence over the app badge.
+	pub label:Option<String>,
+}
+
+#[cfg(windows)]
+impl Overlay {
+	/// Shows the badge of the window, or the overlay icon set by the user when
+	/// neither the window nor the app has a badge.
+	pub fn apply(&self, window:&tao::window::Window) {
+		use tao::platform::windows::WindowExtWindows;
+
+		let label = self.label.clone().or_else(|| APP_BADGE.with(|badge| badge.borrow().clone()));
+
+		match label.and_then(|label| overlay_icon(&label, window.scale_factor())) {
+			Some(icon) => window.set_overlay_icon(Some(&icon)),
+			None => window.set_overlay_icon(self.icon.as_ref()),
+		}
+	}
+}
+
+/// Draws the taskbar overlay icon showing `label`.
+#[cfg(windows)]
+fn overlay_icon(label:&str, scale_factor:f64) -> Option<tao::window::Icon> {
+	use windows::{
+		Win32::{
+			Foundation::{COLORREF, HANDLE, RECT},
+			Graphics::Gdi::{
+				ANTIALIASED_QUALITY,
+				BI_RGB,
+				BITMAPINFO,
+				BITMAPINFOHEADER,
+				CLIP_DEFAULT_PRECIS,
+				CreateCompatibleDC,
+				CreateDIBSection,
+				CreateFontW,
+				DEFAULT_CHARSET,
+				DIB_RGB_COLORS,
+				DT_CENTER,
+				DT_NOPREFIX,
+				DT_SINGLELINE,
+				DT_VCENTER,
+				DeleteDC,
+				DeleteObject,
+				DrawTextW,
+				FW_BOLD,
+				GdiFlush,
+				HDC,
+				OUT_DEFAULT_PRECIS,
+				SelectObject,
+				SetBkMode,
+				SetTextColor,
+				TRANSPARENT,
+			},
+		},
+		core::w,
+	};
+
+	const BADGE_COLOR:[u8; 3] = [0xc4, 0x2b, 0x1c];
+
+	if label.is_empty() {
+		return None;
+	}
+
+	// the overlay icon is a small icon, 16x16 at 100% scaling
+	let size = (16.0 * scale_factor).round().max(16.0) as i32;
+
+	let font_height = size as f64
+		* match label.chars().count() {
+			1 => 0.8,
+			2 => 0.7,
+			_ => 0.55,
+		};
+
+	let mut text:Vec<u16> = label.encode_utf16().collect();
+
+	// GDI draws the text without alpha, so it is drawn in white on black and its
+	// brightness is used as coverage
+	let coverage = unsafe {
+		let hdc = CreateCompatibleDC(HDC::default());
+
+		let info = BITMAPINFO {
+			bmiHeader:BITMAPINFOHEADER {
+				biSize:std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+				biWidth:size,
+				// top-down rows
+				biHeight:-size,
+				biPlanes:1,
+				biBitCount:32,
+				biCompression:BI_RGB.0,
+				..Default::default()
+			},
+			..Default::default()
+		};
+
+		let mut bits = std::ptr::null_mut();
+
+		let bitmap = CreateDIBSection(hdc, &info, DIB_RGB_COLORS, &mut bits, HANDLE::default(), 0);
+
+		let coverage = bitmap.ok().map(|bitmap| {
+			let font = CreateFontW(
+				-(font_height.round() as i32),
+				0,
+				0,
+				0,
+				FW_BOLD.0 as i32,
+				0,
+				0,
+				0,
+				DEFAULT_CHARSET,
+				OUT_DEFAULT_PRECIS,
+				CLIP_DEFAULT_PRECIS,
+				ANTIALIASED_QUALITY,
+				0,
+				w!("Segoe UI"),
+			);
+
+			let previous_bitmap = SelectObject(hdc, bitmap);
+
+			let previous_font = SelectObject(hdc, font);
+
+			SetTextColor(hdc, COLORREF(0x00ff_ffff));
+
+			SetBkMode(hdc, TRANSPARENT);
+
+			let mut rect = RECT { left:0, top:0, right:size, bottom:size };
+
+			DrawTextW(
+				hdc,
+				&mut text,
+				&mut rect,
+				DT_CENTER | DT_VCENTER | DT_SINGLELINE | DT_NOPREFIX,
+			);
+
+			let _ = GdiFlush();
+
+			// BGRA pixels, the text being white every channel is the coverage
+			let pixels = std::slice::from_raw_parts(bits.cast::<u8>(), (size * size * 4) as usize);
+
+			let coverage = pixels.chunks_exact(4)
+				.map(|pixel| pixel[1])
+				.collect::<Vec<u8>>();
+
+			SelectObject(hdc, previous_font);
+
+			SelectObject(hdc, previous_bitmap);
+
+			let _ = DeleteObject(font);
+
+			let _ = DeleteObject(bitmap);
+
+			coverage
+		});
+
+		let _ = DeleteDC(hdc);
+
+		coverage
+	};
+
+	let Some(coverage) = coverage else {
+		log::error!("failed to draw the badge overlay icon");
+
+		return None;
+	};
+
+	let radius = size as f64 / 2.0;
+
+	let mut rgba = Vec::with_capacity(coverage.len() * 4);
+
+	for (index, text) in coverage.into_iter().enumerate() {
+		let x = (index as i32 % size) as f64 + 0.5 - radius;
+
+		let y = (index as i32 / size) as f64 + 0.5 - radius;
+
+		// antialiased circle edge
+		let circle = (radius - (x * x + y * y).sqrt() + 0.5).clamp(0.0, 1.0);
+
+		let text = text as f64 / 255.0;
+
+		for channel in BADGE_COLOR {
+			rgba.push((channel as f64 + (255.0 - channel as f64) * text).round() as u8);
+		}
+
+		rgba.push((circle.max(text) * 255.0).round() as u8);
+	}
+
+	match tao::window::Icon::from_rgba(rgba, size as u32, size as u32) {
+		Ok(icon) => Some(icon),
+		Err(e) => {
+			log::error!("failed to create the badge overlay icon: {e}");
+
+			None
+		},
+	}
+}
+
+/// Sets the label of the dock tile, `None` removing the badge.
+#[cfg(target_os = "macos")]
+pub fn set_dock_label(label:Option<String>) {
+	use objc2_app_kit::NSApplication;
+	use objc2_foundation::{MainThreadMarker, NSString};
+
+	let Some(mtm) = MainThreadMarker::new() else {
+		return;
+	};
+
+	let label = label.map(|label| NSString::from_str(&label));
+
+	unsafe {
+		NSApplication::sharedApplication(mtm).dockTile().setBadgeLabel(label.as_deref());
+	}
+}
+
+/// Sends the badge `count` to the launcher entry of `desktop_filename`, `None`
+/// or `0` removing the badge.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn set_launcher_count(count:Option<u64>, desktop_filename:Option<String>) {
+	use std::collections::HashMap;
+
+	use gtk::{gio, glib::ToVariant};
+
+	let Some(desktop_filename) = desktop_filename else {
+		return;
+	};
+
+	let connection = match gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE) {
+		Ok(connection) => connection,
+		Err(e) => {
+			log::debug!("no D-Bus session bus to show the badge, ignoring it: {e}");
+
+			return;
+		},
+	};
+
+	// the launcher entry count is signed
+	let count = count.map_or(0, |count| i64::try_from(count).unwrap_or(i64::MAX));
+
+	let mut properties = HashMap::new();
+	properties.insert("count", count.to_variant());
+	// the count must be hidden explicitly or the launcher keeps showing the last one
+	properties.insert("count-visible", (count != 0).to_variant());
+
+	let parameters = (format!("application://{desktop_filename}"), properties).to_variant();
+
+	if let Err(e) = connection.emit_signal(
+		None,
+		"/",
+		"com.canonical.Unity.LauncherEntry",
+		"Update",
+		Some(&parameters),
+	) {
+		log::error!("failed to send the badge to the launcher: {e}");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{count_label, window_count};
+
+	#[test]
+	fn zero_counts_remove_the_badge() {
+		assert_eq!(count_label(None), None);
+		assert_eq!(count_label(Some(0)), None);
+	}
+
+	#[test]
+	fn counts_are_labels() {
+		assert_eq!(count_label(Some(1)).as_deref(), Some("1"));
+		assert_eq!(count_label(Some(99)).as_deref(), Some("99"));
+	}
+
+	#[test]
+	fn large_counts_are_capped_on_windows() {
+		let expected = if cfg!(windows) { "99+" } else { "100" };
+
+		assert_eq!(count_label(Some(100)).as_deref(), Some(expected));
+	}
+
+	#[test]
+	fn negative_window_counts_remove_the_badge() {
+		assert_eq!(window_count(Some(-1)), None);
+		assert_eq!(count_label(window_count(Some(-1))), None);
+		assert_eq!(window_count(Some(3)), Some(3));
+		assert_eq!(window_count(None), None);
+	}
+}
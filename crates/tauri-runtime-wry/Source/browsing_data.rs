@@ -0,0 +1,767 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview browsing data removal and cookie management.
+//!
+//! wry can only clear all the browsing data without reporting when it is done
+//! and can neither add nor delete cookies, so the data store of the platform
+//! webview is used directly.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2Profile2::ClearBrowsingData`, which
+//!   requires WebView2 Runtime 1.0.1245 or later, and the cookie manager.
+//! - **Linux**: uses the `WebKitWebsiteDataManager` of the webview.
+//! - **macOS**: uses the `WKWebsiteDataStore` of the webview.
+//! - **iOS / Android**: Unsupported.
+
+use std::sync::{Arc, Mutex};
+
+use tauri_runtime::{
+	Error,
+	Result,
+	webview::{Cookie, DataTypes},
+};
+use url::Url;
+
+pub type BrowsingDataHandler = Box<dyn FnOnce(Result<()>) + Send>;
+
+/// Sets the domain of `cookie` to the host of `url` when it has none, making it
+/// a host-only cookie of that page like a cookie set without a `Domain`.
+pub fn with_default_domain(mut cookie:Cookie<'static>, url:Option<&Url>) -> Cookie<'static> {
+	if cookie.domain().is_none() {
+		if let Some(host) = url.and_then(Url::host_str) {
+			cookie.set_domain(host.to_owned());
+		}
+	}
+
+	cookie
+}
+
+/// Adds every cookie of `cookies`, `handler` being called once all of them
+/// were stored or with the first failure.
+pub fn set_cookies(
+	webview:&wry::WebView,
+	cookies:Vec<Cookie<'static>>,
+	handler:BrowsingDataHandler,
+) {
+	if cookies.is_empty() {
+		handler(Ok(()));
+
+		return;
+	}
+
+	let remaining = Arc::new(Mutex::new((cookies.len(), Some(handler))));
+
+	for cookie in cookies {
+		let remaining = remaining.clone();
+
+		set_cookie(
+			webview,
+			cookie,
+			Box::new(move |result| {
+				let mut remaining = remaining.lock().unwrap();
+
+				remaining.0 -= 1;
+
+				if result.is_err() || remaining.0 == 0 {
+					if let Some(handler) = remaining.1.take() {
+						handler(result);
+					}
+				}
+			}),
+		);
+	}
+}
+
+/// The domain of `cookie`, required by every platform webview.
+#[cfg(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn cookie_domain(cookie:&Cookie) -> Result<String> {
+	cookie
+		.domain()
+		.map(Into::into)
+		.ok_or_else(|| {
+			Error::BrowsingData(format!(
+				"the cookie {} has no domain and the webview URL has no host",
+				cookie.name()
+			))
+		})
+}
+
+/// The expiration of `cookie` in seconds since the Unix epoch, `None` for a
+/// session cookie.
+#[cfg(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn cookie_expires(cookie:&Cookie) -> Option<i64> {
+	// the max age takes precedence over the expiration date
+	match cookie.max_age() {
+		Some(max_age) => {
+			let now = std::time::SystemTime::now()
+				.duration_since(std::time::UNIX_EPOCH)
+				.map_or(0, |now| now.as_secs() as i64);
+
+			Some(now + max_age.whole_seconds())
+		},
+		None => cookie.expires_datetime().map(|expires| expires.unix_timestamp()),
+	}
+}
+
+#[cfg(windows)]
+pub fn clear(webview:&wry::WebView, types:DataTypes, handler:BrowsingDataHandler) {
+	use std::{cell::Cell, rc::Rc};
+
+	use webview2_com::{
+		ClearBrowsingDataCompletedHandler,
+		Microsoft::Web::WebView2::Win32::{
+			COREWEBVIEW2_BROWSING_DATA_KINDS,
+			COREWEBVIEW2_BROWSING_DATA_KINDS_COOKIES,
+			COREWEBVIEW2_BROWSING_DATA_KINDS_DISK_CACHE,
+			COREWEBVIEW2_BROWSING_DATA_KINDS_INDEXED_DB,
+			COREWEBVIEW2_BROWSING_DATA_KINDS_LOCAL_STORAGE,
+			ICoreWebView2_13,
+			ICoreWebView2Profile2,
+		},
+	};
+	use windows::core::Interface;
+	use wry::WebViewExtWindows;
+
+	let mut kinds = COREWEBVIEW2_BROWSING_DATA_KINDS(0);
+
+	for (data_type, kind) in [
+		(DataTypes::COOKIES, COREWEBVIEW2_BROWSING_DATA_KINDS_COOKIES),
+		(DataTypes::LOCAL_STORAGE, COREWEBVIEW2_BROWSING_DATA_KINDS_LOCAL_STORAGE),
+		(DataTypes::INDEXED_DB, COREWEBVIEW2_BROWSING_DATA_KINDS_INDEXED_DB),
+		(DataTypes::CACHE, COREWEBVIEW2_BROWSING_DATA_KINDS_DISK_CACHE),
+	] {
+		if types.contains(data_type) {
+			kinds.0 |= kind.0;
+		}
+	}
+
+	let error = |e:windows::core::Error| Error::BrowsingData(e.to_string());
+
+	// shared with the completion handler, whichever runs first reports the result
+	let handler = Rc::new(Cell::new(Some(handler)));
+
+	let handler_ = handler.clone();
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			core_webview
+				.cast::<ICoreWebView2_13>()?
+				.Profile()?
+				.cast::<ICoreWebView2Profile2>()?
+				.ClearBrowsingData(
+					kinds,
+					&ClearBrowsingDataCompletedHandler::create(Box::new(move |result| {
+						if let Some(handler) = handler_.take() {
+							handler(result.map_err(error));
+						}
+
+						Ok(())
+					})),
+				)
+		})
+	};
+
+	if let Err(e) = result {
+		if let Some(handler) = handler.take() {
+			handler(Err(error(e)));
+		}
+	}
+}
+
+#[cfg(windows)]
+pub fn delete_cookie(webview:&wry::WebView, name:String, url:Url, handler:BrowsingDataHandler) {
+	use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2_2;
+	use windows::core::{HSTRING, Interface};
+	use wry::WebViewExtWindows;
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			core_webview
+				.cast::<ICoreWebView2_2>()?
+				.CookieManager()?
+				.DeleteCookies(&HSTRING::from(name), &HSTRING::from(url.as_str()))
+		})
+	};
+
+	handler(result.map_err(|e| Error::BrowsingData(e.to_string())));
+}
+
+#[cfg(windows)]
+pub fn set_cookie(webview:&wry::WebView, cookie:Cookie<'static>, handler:BrowsingDataHandler) {
+	use tauri_runtime::webview::SameSite;
+	use webview2_com::Microsoft::Web::WebView2::Win32::{
+		COREWEBVIEW2_COOKIE_SAME_SITE_KIND_LAX,
+		COREWEBVIEW2_COOKIE_SAME_SITE_KIND_NONE,
+		COREWEBVIEW2_COOKIE_SAME_SITE_KIND_STRICT,
+		ICoreWebView2_2,
+	};
+	use windows::{
+		Win32::Foundation::BOOL,
+		core::{HSTRING, Interface},
+	};
+	use wry::WebViewExtWindows;
+
+	let domain = match cookie_domain(&cookie) {
+		Ok(domain) => domain,
+		Err(e) => {
+			handler(Err(e));
+
+			return;
+		},
+	};
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			let manager = core_webview.cast::<ICoreWebView2_2>()?.CookieManager()?;
+
+			let native = manager.CreateCookie(
+				&HSTRING::from(cookie.name()),
+				&HSTRING::from(cookie.value()),
+				&HSTRING::from(domain),
+				&HSTRING::from(cookie.path().unwrap_or("/")),
+			)?;
+
+			if let Some(expires) = cookie_expires(&cookie) {
+				native.SetExpires(expires as f64)?;
+			}
+
+			native.SetIsHttpOnly(BOOL::from(cookie.http_only().unwrap_or_default()))?;
+
+			native.SetIsSecure(BOOL::from(cookie.secure().unwrap_or_default()))?;
+
+			if let Some(same_site) = cookie.same_site() {
+				native.SetSameSite(match same_site {
+					SameSite::Strict => COREWEBVIEW2_COOKIE_SAME_SITE_KIND_STRICT,
+					SameSite::Lax => COREWEBVIEW2_COOKIE_SAME_SITE_KIND_LAX,
+					SameSite::None => COREWEBVIEW2_COOKIE_SAME_SITE_KIND_NONE,
+				})?;
+			}
+
+			manager.AddOrUpdateCookie(&native)
+		})
+	};
+
+	handler(result.map_err(|e| Error::BrowsingData(e.to_string())));
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn clear(webview:&wry::WebView, types:DataTypes, handler:BrowsingDataHandler) {
+	use gtk::{gio, glib};
+	use webkit2gtk::{WebViewExt, WebsiteDataManagerExt, WebsiteDataTypes};
+	use wry::WebViewExtUnix;
+
+	let Some(manager) = webview.webview().website_data_manager() else {
+		handler(Err(Error::BrowsingData("the webview has no website data manager".into())));
+
+		return;
+	};
+
+	let mut kinds = WebsiteDataTypes::empty();
+
+	for (data_type, kind) in [
+		(DataTypes::COOKIES, WebsiteDataTypes::COOKIES),
+		(
+			DataTypes::LOCAL_STORAGE,
+			WebsiteDataTypes::LOCAL_STORAGE | WebsiteDataTypes::SESSION_STORAGE,
+		),
+		(DataTypes::INDEXED_DB, WebsiteDataTypes::INDEXEDDB_DATABASES),
+		(DataTypes::CACHE, WebsiteDataTypes::DISK_CACHE | WebsiteDataTypes::MEMORY_CACHE),
+	] {
+		if types.contains(data_type) {
+			kinds |= kind;
+		}
+	}
+
+	// a zero timespan removes the data regardless of when it was modified
+	manager.clear(kinds, glib::TimeSpan(0), gio::Cancellable::NONE, move |result| {
+		handler(result.map_err(|e| Error::BrowsingData(e.to_string())));
+	});
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn delete_cookie(webview:&wry::WebView, name:String, url:Url, handler:BrowsingDataHandler) {
+	use std::{cell::Cell, rc::Rc};
+
+	use gtk::gio;
+	use webkit2gtk::{CookieManagerExt, WebViewExt, WebsiteDataManagerExt};
+	use wry::WebViewExtUnix;
+
+	let Some(cookie_manager) =
+		webview.webview().website_data_manager().and_then(|manager| manager.cookie_manager())
+	else {
+		handler(Err(Error::BrowsingData("the webview has no cookie manager".into())));
+
+		return;
+	};
+
+	let cookie_manager_ = cookie_manager.clone();
+
+	cookie_manager.cookies(url.as_str(), gio::Cancellable::NONE, move |result| {
+		let cookies = match result {
+			Ok(cookies) => cookies,
+			Err(e) => {
+				handler(Err(Error::BrowsingData(e.to_string())));
+
+				return;
+			},
+		};
+
+		let cookies = cookies
+			.into_iter()
+			.filter_map(|mut cookie| {
+				(cookie.name().as_deref() == Some(name.as_str())).then_some(cookie)
+			})
+			.collect::<Vec<_>>();
+
+		if cookies.is_empty() {
+			handler(Ok(()));
+
+			return;
+		}
+
+		// the handler is called once every deletion completed or on the first failure
+		let remaining = Rc::new(Cell::new(cookies.len()));
+
+		let handler = Rc::new(Cell::new(Some(handler)));
+
+		for mut cookie in cookies {
+			let remaining = remaining.clone();
+
+			let handler = handler.clone();
+
+			cookie_manager_.delete_cookie(&mut cookie, gio::Cancellable::NONE, move |result| {
+				remaining.set(remaining.get() - 1);
+
+				match result {
+					Err(e) => {
+						if let Some(handler) = handler.take() {
+							handler(Err(Error::BrowsingData(e.to_string())));
+						}
+					},
+					Ok(()) if remaining.get() == 0 => {
+						if let Some(handler) = handler.take() {
+							handler(Ok(()));
+						}
+					},
+					Ok(()) => {},
+				}
+			});
+		}
+	});
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn set_cookie(webview:&wry::WebView, cookie:Cookie<'static>, handler:BrowsingDataHandler) {
+	use gtk::{gio, glib};
+	use soup3::SameSitePolicy;
+	use tauri_runtime::webview::SameSite;
+	use webkit2gtk::{CookieManagerExt, WebViewExt, WebsiteDataManagerExt};
+	use wry::WebViewExtUnix;
+
+	let Some(cookie_manager) =
+		webview.webview().website_data_manager().and_then(|manager| manager.cookie_manager())
+	else {
+		handler(Err(Error::BrowsingData("the webview has no cookie manager".into())));
+
+		return;
+	};
+
+	let domain = match cookie_domain(&cookie) {
+		Ok(domain) => domain,
+		Err(e) => {
+			handler(Err(e));
+
+			return;
+		},
+	};
+
+	// a negative max age makes a session cookie, the expiration is set below
+	let mut native = soup3::Cookie::new(
+		cookie.name(),
+		cookie.value(),
+		&domain,
+		cookie.path().unwrap_or("/"),
+		-1,
+	);
+
+	if let Some(expires) =
+		cookie_expires(&cookie).and_then(|expires| glib::DateTime::from_unix_utc(expires).ok())
+	{
+		native.set_expires(&expires);
+	}
+
+	native.set_http_only(cookie.http_only().unwrap_or_default());
+
+	native.set_secure(cookie.secure().unwrap_or_default());
+
+	if let Some(same_site) = cookie.same_site() {
+		native.set_same_site_policy(match same_site {
+			SameSite::Strict => SameSitePolicy::Strict,
+			SameSite::Lax => SameSitePolicy::Lax,
+			SameSite::None => SameSitePolicy::None,
+		});
+	}
+
+	cookie_manager.add_cookie(&mut native, gio::Cancellable::NONE, move |result| {
+		handler(result.map_err(|e| Error::BrowsingData(e.to_string())));
+	});
+}
+
+#[cfg(target_os = "macos")]
+pub fn clear(webview:&wry::WebView, types:DataTypes, handler:BrowsingDataHandler) {
+	use std::cell::Cell;
+
+	use block2::RcBlock;
+	use objc2::rc::Retained;
+	use objc2_foundation::{NSDate, NSSet, NSString};
+	use objc2_web_kit::{
+		WKWebView,
+		WKWebsiteDataTypeCookies,
+		WKWebsiteDataTypeDiskCache,
+		WKWebsiteDataTypeIndexedDBDatabases,
+		WKWebsiteDataTypeLocalStorage,
+		WKWebsiteDataTypeMemoryCache,
+		WKWebsiteDataTypeSessionStorage,
+	};
+	use wry::WebViewExtMacOS;
+
+	let mut kinds:Vec<&NSString> = Vec::new();
+
+	unsafe {
+		if types.contains(DataTypes::COOKIES) {
+			kinds.push(WKWebsiteDataTypeCookies);
+		}
+
+		if types.contains(DataTypes::LOCAL_STORAGE) {
+			kinds.extend([WKWebsiteDataTypeLocalStorage, WKWebsiteDataTypeSessionStorage]);
+		}
+
+		if types.contains(DataTypes::INDEXED_DB) {
+			kinds.push(WKWebsiteDataTypeIndexedDBDatabases);
+		}
+
+		if types.contains(DataTypes::CACHE) {
+			kinds.extend([WKWebsiteDataTypeDiskCache, WKWebsiteDataTypeMemoryCache]);
+		}
+	}
+
+	let handler = Cell::new(Some(handler));
+
+	let block = RcBlock::new(move || {
+		if let Some(handler) = handler.take() {
+			handler(Ok(()));
+		}
+	});
+
+	unsafe {
+		let webview = Retained::cast::<WKWebView>(webview.webview());
+
+		let store = webview.configuration().websiteDataStore();
+
+		store.removeDataOfTypes_modifiedSince_completionHandler(
+			&NSSet::from_slice(&kinds),
+			&NSDate::distantPast(),
+			&block,
+		);
+	}
+}
+
+#[cfg(target_os = "macos")]
+pub fn delete_cookie(webview:&wry::WebView, name:String, url:Url, handler:BrowsingDataHandler) {
+	use std::{cell::Cell, ptr::NonNull, rc::Rc};
+
+	use block2::RcBlock;
+	use objc2::{Message, rc::Retained};
+	use objc2_foundation::{NSArray, NSHTTPCookie};
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	let host = url.host_str().unwrap_or_default().to_owned();
+
+	let path = url.path().to_owned();
+
+	// the cookies sent to `url`, see RFC 6265 domain and path matching
+	let is_sent = move |cookie:&NSHTTPCookie| {
+		let (domain, cookie_path) =
+			unsafe { (cookie.domain().to_string(), cookie.path().to_string()) };
+
+		let domain = domain.trim_start_matches('.');
+
+		(host == domain || host.ends_with(&format!(".{domain}"))) && path.starts_with(&cookie_path)
+	};
+
+	let store = unsafe {
+		Retained::cast::<WKWebView>(webview.webview())
+			.configuration()
+			.websiteDataStore()
+			.httpCookieStore()
+	};
+
+	let store_ = store.clone();
+
+	let handler = Rc::new(Cell::new(Some(handler)));
+
+	let block = RcBlock::new(move |cookies:NonNull<NSArray<NSHTTPCookie>>| {
+		let cookies = unsafe { cookies.as_ref() }
+			.iter()
+			.filter(|cookie| unsafe { cookie.name() }.to_string() == name && is_sent(cookie))
+			.map(|cookie| cookie.retain())
+			.collect::<Vec<_>>();
+
+		if cookies.is_empty() {
+			if let Some(handler) = handler.take() {
+				handler(Ok(()));
+			}
+
+			return;
+		}
+
+		// the handler is called once every deletion completed
+		let remaining = Rc::new(Cell::new(cookies.len()));
+
+		for cookie in cookies {
+			let remaining = remaining.clone();
+
+			let handler = handler.clone();
+
+			let completion = RcBlock::new(move || {
+				remaining.set(remaining.get() - 1);
+
+				if remaining.get() == 0 {
+					if let Some(handler) = handler.take() {
+						handler(Ok(()));
+					}
+				}
+			});
+
+			unsafe { store_.deleteCookie_completionHandler(&cookie, Some(&completion)) };
+		}
+	});
+
+	unsafe { store.getAllCookies(&block) };
+}
+
+#[cfg(target_os = "macos")]
+pub fn set_cookie(webview:&wry::WebView, cookie:Cookie<'static>, handler:BrowsingDataHandler) {
+	use std::cell::Cell;
+
+	use block2::RcBlock;
+	use objc2::{Message, rc::Retained, runtime::NSObject};
+	use objc2_foundation::{
+		NSDate,
+		NSDictionary,
+		NSHTTPCookie,
+		NSHTTPCookieDomain,
+		NSHTTPCookieExpires,
+		NSHTTPCookieName,
+		NSHTTPCookiePath,
+		NSHTTPCookieSameSiteLax,
+		NSHTTPCookieSameSitePolicy,
+		NSHTTPCookieSameSiteStrict,
+		NSHTTPCookieSecure,
+		NSHTTPCookieValue,
+		NSString,
+	};
+	use objc2_web_kit::WKWebView;
+	use tauri_runtime::webview::SameSite;
+	use wry::WebViewExtMacOS;
+
+	let domain = match cookie_domain(&cookie) {
+		Ok(domain) => domain,
+		Err(e) => {
+			handler(Err(e));
+
+			return;
+		},
+	};
+
+	let string = |value:&str| Retained::into_super(NSString::from_str(value));
+
+	// NSHTTPCookie has no property key for it but reads the attribute name
+	let http_only = NSString::from_str("HttpOnly");
+
+	let native = unsafe {
+		let mut keys:Vec<&NSString> =
+			vec![NSHTTPCookieName, NSHTTPCookieValue, NSHTTPCookieDomain, NSHTTPCookiePath];
+
+		let mut values:Vec<Retained<NSObject>> = vec![
+			string(cookie.name()),
+			string(cookie.value()),
+			string(&domain),
+			string(cookie.path().unwrap_or("/")),
+		];
+
+		if let Some(expires) = cookie_expires(&cookie) {
+			keys.push(NSHTTPCookieExpires);
+
+			values
+				.push(Retained::into_super(NSDate::dateWithTimeIntervalSince1970(expires as f64)));
+		}
+
+		if cookie.secure().unwrap_or_default() {
+			keys.push(NSHTTPCookieSecure);
+
+			values.push(string("TRUE"));
+		}
+
+		if cookie.http_only().unwrap_or_default() {
+			keys.push(&http_only);
+
+			values.push(string("TRUE"));
+		}
+
+		match cookie.same_site() {
+			Some(SameSite::Strict) => {
+				keys.push(NSHTTPCookieSameSitePolicy);
+
+				values.push(Retained::into_super(NSHTTPCookieSameSiteStrict.retain()));
+			},
+			Some(SameSite::Lax) => {
+				keys.push(NSHTTPCookieSameSitePolicy);
+
+				values.push(Retained::into_super(NSHTTPCookieSameSiteLax.retain()));
+			},
+			// no policy is SameSite=None
+			Some(SameSite::None) | None => {},
+		}
+
+		NSHTTPCookie::cookieWithProperties(&NSDictionary::from_vec(&keys, values))
+	};
+
+	let Some(native) = native else {
+		handler(Err(Error::BrowsingData(format!("the cookie {} is invalid", cookie.name()))));
+
+		return;
+	};
+
+	let handler = Cell::new(Some(handler));
+
+	let block = RcBlock::new(move || {
+		if let Some(handler) = handler.take() {
+			handler(Ok(()));
+		}
+	});
+
+	unsafe {
+		Retained::cast::<WKWebView>(webview.webview())
+			.configuration()
+			.websiteDataStore()
+			.httpCookieStore()
+			.setCookie_completionHandler(&native, Some(&block));
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn clear(_webview:&wry::WebView, _types:DataTypes, handler:BrowsingDataHandler) {
+	handler(Err(Error::BrowsingData("unsupported on this platform".into())));
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn delete_cookie(
+	_webview:&wry::WebView,
+	_name:String,
+	_url:Url,
+	handler:BrowsingDataHandler,
+) {
+	handler(Err(Error::BrowsingData("unsupported on this platform".into())));
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn set_cookie(_webview:&wry::WebView, _cookie:Cookie<'static>, handler:BrowsingDataHandler) {
+	handler(Err(Error::BrowsingData("unsupported on this platform".into())));
+}
+
+#[cfg(test)]
+mod tests {
+	use tauri_runtime::webview::Cookie;
+	use url::Url;
+
+	use super::with_default_domain;
+
+	#[test]
+	fn cookies_without_domain_use_the_url_host() {
+		let url = Url::parse("https://auth.tauri.app/login").unwrap();
+
+		let cookie = with_default_domain(Cookie::new("session", "abc"), Some(&url));
+
+		assert_eq!(cookie.domain(), Some("auth.tauri.app"));
+	}
+
+	#[test]
+	fn cookie_domains_are_kept() {
+		let url = Url::parse("https://auth.tauri.app/login").unwrap();
+
+		let cookie = Cookie::build(("session", "abc")).domain("tauri.app").build();
+
+		assert_eq!(with_default_domain(cookie, Some(&url)).domain(), Some("tauri.app"));
+	}
+
+	#[test]
+	fn cookies_stay_without_domain_without_host() {
+		let url = Url::parse("data:text/html,<p>tauri</p>").unwrap();
+
+		assert_eq!(with_default_domain(Cookie::new("session", "abc"), Some(&url)).domain(), None);
+		assert_eq!(with_default_domain(Cookie::new("session", "abc"), None).domain(), None);
+	}
+}
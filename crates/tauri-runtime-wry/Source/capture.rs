@@ -0,0 +1,483 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview contents capture.
+//!
+//! wry does not expose the snapshot APIs of the platform webviews, so the
+//! bitmap is read from the platform webview directly and converted to RGBA.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2::CapturePreview`, which renders the
+//!   webview even when the window is occluded but not when it is minimized.
+//! - **Linux**: uses `webkit_web_view_get_snapshot`, which renders the webview
+//!   even when the window is occluded.
+//! - **macOS**: uses `WKWebView::takeSnapshotWithConfiguration`.
+//! - **iOS / Android**: Unsupported.
+
+use tauri_runtime::{Error, Icon, Rect, Result};
+
+pub type CaptureHandler = Box<dyn FnOnce(Result<Icon<'static>>) + Send>;
+
+/// Captures `webview`, which is `width` logical pixels wide, and calls
+/// `handler` with the bitmap cropped to `region`.
+pub fn capture(
+	webview:&wry::WebView,
+	region:Option<Rect>,
+	scale_factor:f64,
+	width:f64,
+	handler:CaptureHandler,
+) {
+	capture_bitmap(webview, move |bitmap| {
+		handler(bitmap.and_then(|bitmap| crop(bitmap, region, scale_factor, width)))
+	});
+}
+
+/// Crops `bitmap` to `region`, converted to bitmap pixels from the logical
+/// `width` of the webview since the bitmap may not use the window scale factor.
+fn crop(
+	bitmap:Icon<'static>,
+	region:Option<Rect>,
+	scale_factor:f64,
+	width:f64,
+) -> Result<Icon<'static>> {
+	let Some(region) = region else {
+		return Ok(bitmap);
+	};
+
+	let ratio = if width > 0.0 { f64::from(bitmap.width) / width } else { scale_factor };
+
+	let position = region.position.to_logical::<f64>(scale_factor);
+
+	let size = region.size.to_logical::<f64>(scale_factor);
+
+	let x = ((position.x * ratio).round().max(0.0) as u32).min(bitmap.width);
+
+	let y = ((position.y * ratio).round().max(0.0) as u32).min(bitmap.height);
+
+	let width = ((size.width * ratio).round().max(0.0) as u32).min(bitmap.width - x);
+
+	let height = ((size.height * ratio).round().max(0.0) as u32).min(bitmap.height - y);
+
+	if width == 0 || height == 0 {
+		return Err(Error::FailedToCaptureWebview("the region is outside of the webview".into()));
+	}
+
+	let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+
+	for row in y..y + height {
+		let start = (row as usize * bitmap.width as usize + x as usize) * 4;
+
+		rgba.extend_from_slice(&bitmap.rgba[start..start + width as usize * 4]);
+	}
+
+	Ok(Icon { rgba:rgba.into(), width, height })
+}
+
+/// Converts a premultiplied alpha pixel to a straight alpha one.
+#[cfg(any(
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn unpremultiply([r, g, b, a]:[u8; 4]) -> [u8; 4] {
+	if a == 0 || a == 255 {
+		return [r, g, b, a];
+	}
+
+	let channel = |c:u8| ((u32::from(c) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8;
+
+	[channel(r), channel(g), channel(b), a]
+}
+
+#[cfg(windows)]
+fn capture_bitmap(webview:&wry::WebView, handler:impl FnOnce(Result<Icon<'static>>) + 'static) {
+	use std::{cell::Cell, rc::Rc};
+
+	use webview2_com::{
+		CapturePreviewCompletedHandler,
+		Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+	};
+	use windows::Win32::{
+		Foundation::HGLOBAL,
+		Graphics::Imaging::{
+			CLSID_WICImagingFactory,
+			GUID_WICPixelFormat32bppRGBA,
+			IWICImagingFactory,
+			IWICPalette,
+			WICBitmapDitherTypeNone,
+			WICBitmapPaletteTypeCustom,
+			WICDecodeMetadataCacheOnDemand,
+		},
+		System::Com::{
+			CLSCTX_INPROC_SERVER,
+			CoCreateInstance,
+			IStream,
+			STREAM_SEEK_SET,
+			StructuredStorage::CreateStreamOnHGlobal,
+		},
+	};
+	use wry::WebViewExtWindows;
+
+	/// Decodes the PNG written to `stream` with the Windows Imaging Component.
+	fn decode_png(stream:&IStream) -> windows::core::Result<Icon<'static>> {
+		unsafe {
+			stream.Seek(0, STREAM_SEEK_SET, None)?;
+
+			let factory:IWICImagingFactory =
+				CoCreateInstance(&CLSID_WICImagingFactory, None, CLSCTX_INPROC_SERVER)?;
+
+			let frame = factory
+				.CreateDecoderFromStream(stream, std::ptr::null(), WICDecodeMetadataCacheOnDemand)?
+				.GetFrame(0)?;
+
+			let converter = factory.CreateFormatConverter()?;
+
+			converter.Initialize(
+				&frame,
+				&GUID_WICPixelFormat32bppRGBA,
+				WICBitmapDitherTypeNone,
+				None::<&IWICPalette>,
+				0.0,
+				WICBitmapPaletteTypeCustom,
+			)?;
+
+			let (mut width, mut height) = (0, 0);
+
+			converter.GetSize(&mut width, &mut height)?;
+
+			let mut rgba = vec![0; width as usize * height as usize * 4];
+
+			converter.CopyPixels(std::ptr::null(), width * 4, &mut rgba)?;
+
+			Ok(Icon { rgba:rgba.into(), width, height })
+		}
+	}
+
+	let error = |e:windows::core::Error| Error::FailedToCaptureWebview(e.to_string());
+
+	// shared with the completion handler, whichever runs first reports the result
+	let handler = Rc::new(Cell::new(Some(handler)));
+
+	let handler_ = handler.clone();
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			let stream = CreateStreamOnHGlobal(HGLOBAL::default(), true)?;
+
+			let stream_ = stream.clone();
+
+			core_webview.CapturePreview(
+				COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG,
+				&stream,
+				&CapturePreviewCompletedHandler::create(Box::new(move |result| {
+					if let Some(handler) = handler_.take() {
+						handler(
+							result
+								.and_then(|()| decode_png(&stream_))
+								.map_err(error),
+						);
+					}
+
+					Ok(())
+				})),
+			)
+		})
+	};
+
+	if let Err(e) = result {
+		if let Some(handler) = handler.take() {
+			handler(Err(error(e)));
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn capture_bitmap(webview:&wry::WebView, handler:impl FnOnce(Result<Icon<'static>>) + 'static) {
+	use gtk::{cairo, gio};
+	use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	fn surface_to_rgba(surface:cairo::Surface) -> Result<Icon<'static>> {
+		let error = |message:&str| Error::FailedToCaptureWebview(message.into());
+
+		let surface = cairo::ImageSurface::try_from(surface)
+			.map_err(|_| error("the snapshot is not an image surface"))?;
+
+		let has_alpha = match surface.format() {
+			cairo::Format::ARgb32 => true,
+			cairo::Format::Rgb24 => false,
+			_ => return Err(error("unexpected snapshot surface format")),
+		};
+
+		surface.flush();
+
+		let width = surface.width() as usize;
+
+		let height = surface.height() as usize;
+
+		let stride = surface.stride() as usize;
+
+		let mut rgba = Vec::with_capacity(width * height * 4);
+
+		surface
+			.with_data(|data| {
+				for row in data.chunks(stride).take(height) {
+					for pixel in row[..width * 4].chunks_exact(4) {
+						// pixels are native endian 32 bits values with premultiplied alpha
+						let value = u32::from_ne_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]);
+
+						let [a, r, g, b] = value.to_be_bytes();
+
+						let a = if has_alpha { a } else { 255 };
+
+						rgba.extend_from_slice(&unpremultiply([r, g, b, a]));
+					}
+				}
+			})
+			.map_err(|_| error("failed to read the snapshot surface"))?;
+
+		Ok(Icon { rgba:rgba.into(), width:width as u32, height:height as u32 })
+	}
+
+	webview.webview().snapshot(
+		SnapshotRegion::Visible,
+		SnapshotOptions::NONE,
+		None::<&gio::Cancellable>,
+		move |surface| {
+			handler(
+				surface
+					.map_err(|e| Error::FailedToCaptureWebview(e.to_string()))
+					.and_then(surface_to_rgba),
+			)
+		},
+	);
+}
+
+#[cfg(target_os = "macos")]
+fn capture_bitmap(webview:&wry::WebView, handler:impl FnOnce(Result<Icon<'static>>) + 'static) {
+	use std::cell::Cell;
+
+	use block2::RcBlock;
+	use objc2::{ClassType, rc::Retained};
+	use objc2_app_kit::{NSBitmapFormat, NSBitmapImageRep, NSImage};
+	use objc2_foundation::NSError;
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	fn image_to_rgba(image:&NSImage) -> Result<Icon<'static>> {
+		let error = |message:&str| Error::FailedToCaptureWebview(message.into());
+
+		let rep = unsafe {
+			image
+				.TIFFRepresentation()
+				.and_then(|tiff| NSBitmapImageRep::initWithData(NSBitmapImageRep::alloc(), &tiff))
+		}
+		.ok_or_else(|| error("the snapshot has no bitmap"))?;
+
+		let (width, height, bytes_per_row, bytes_per_pixel, samples, format, data) = unsafe {
+			if rep.bitsPerSample() != 8 || rep.isPlanar() {
+				return Err(error("unexpected snapshot bitmap format"));
+			}
+
+			(
+				rep.pixelsWide() as usize,
+				rep.pixelsHigh() as usize,
+				rep.bytesPerRow() as usize,
+				rep.bitsPerPixel() as usize / 8,
+				rep.samplesPerPixel() as usize,
+				rep.bitmapFormat(),
+				rep.bitmapData(),
+			)
+		};
+
+		if data.is_null() || !(samples == 3 || samples == 4) || bytes_per_pixel < samples {
+			return Err(error("unexpected snapshot bitmap format"));
+		}
+
+		let alpha_first = format.0 & NSBitmapFormat::AlphaFirst.0 != 0;
+
+		let premultiplied =
+			format.0 & NSBitmapFormat::AlphaNonpremultiplied.0 == 0;
+
+		let data = unsafe { std::slice::from_raw_parts(data, bytes_per_row * height) };
+
+		let mut rgba = Vec::with_capacity(width * height * 4);
+
+		for row in data.chunks(bytes_per_row) {
+			for pixel in row[..width * bytes_per_pixel].chunks_exact(bytes_per_pixel) {
+				let pixel = match (samples, alpha_first) {
+					(3, _) => [pixel[0], pixel[1], pixel[2], 255],
+					(_, true) => [pixel[1], pixel[2], pixel[3], pixel[0]],
+					(_, false) => [pixel[0], pixel[1], pixel[2], pixel[3]],
+				};
+
+				rgba.extend_from_slice(&if premultiplied { unpremultiply(pixel) } else { pixel });
+			}
+		}
+
+		Ok(Icon { rgba:rgba.into(), width:width as u32, height:height as u32 })
+	}
+
+	let handler = Cell::new(Some(handler));
+
+	let block = RcBlock::new(move |image:*mut NSImage, error:*mut NSError| {
+		let Some(handler) = handler.take() else {
+			return;
+		};
+
+		let result = match unsafe { image.as_ref() } {
+			Some(image) => image_to_rgba(image),
+			None => {
+				Err(Error::FailedToCaptureWebview(
+					unsafe { error.as_ref() }
+						.map(|error| error.localizedDescription().to_string())
+						.unwrap_or_else(|| "the snapshot failed".into()),
+				))
+			},
+		};
+
+		handler(result);
+	});
+
+	let webview = unsafe { Retained::cast::<WKWebView>(webview.webview()) };
+
+	unsafe { webview.takeSnapshotWithConfiguration_completionHandler(None, &block) };
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn capture_bitmap(_webview:&wry::WebView, handler:impl FnOnce(Result<Icon<'static>>) + 'static) {
+	handler(Err(Error::FailedToCaptureWebview("unsupported on this platform".into())));
+}
+
+#[cfg(test)]
+mod tests {
+	use tauri_runtime::{
+		Icon,
+		Rect,
+		dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+	};
+
+	use super::crop;
+
+	/// A `width` x `height` bitmap whose pixels hold their index.
+	fn bitmap(width:u32, height:u32) -> Icon<'static> {
+		let rgba = (0..width * height).flat_map(|i| [i as u8; 4]).collect::<Vec<_>>();
+
+		Icon { rgba:rgba.into(), width, height }
+	}
+
+	fn pixels(bitmap:&Icon<'_>) -> Vec<u8> { bitmap.rgba.chunks(4).map(|pixel| pixel[0]).collect() }
+
+	#[test]
+	fn captures_without_a_region_are_not_cropped() {
+		let cropped = crop(bitmap(4, 2), None, 2.0, 2.0).unwrap();
+
+		assert_eq!((cropped.width, cropped.height), (4, 2));
+
+		assert_eq!(pixels(&cropped), (0..8).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn regions_are_scaled_to_the_bitmap() {
+		// a 4x2 logical webview captured at twice its size
+		let region = Rect {
+			position:LogicalPosition::new(1.0, 0.0).into(),
+			size:LogicalSize::new(2.0, 1.0).into(),
+		};
+
+		let cropped = crop(bitmap(8, 4), Some(region), 1.0, 4.0).unwrap();
+
+		assert_eq!((cropped.width, cropped.height), (4, 2));
+
+		assert_eq!(pixels(&cropped), [2, 3, 4, 5, 10, 11, 12, 13]);
+
+		// physical regions go through the window scale factor first
+		let region = Rect {
+			position:PhysicalPosition::new(2, 0).into(),
+			size:PhysicalSize::new(4, 2).into(),
+		};
+
+		let cropped = crop(bitmap(8, 4), Some(region), 2.0, 4.0).unwrap();
+
+		assert_eq!(pixels(&cropped), [2, 3, 4, 5, 10, 11, 12, 13]);
+	}
+
+	#[test]
+	fn regions_are_clamped_to_the_bitmap() {
+		let region = Rect {
+			position:LogicalPosition::new(2.0, 1.0).into(),
+			size:LogicalSize::new(10.0, 10.0).into(),
+		};
+
+		let cropped = crop(bitmap(4, 2), Some(region), 1.0, 4.0).unwrap();
+
+		assert_eq!((cropped.width, cropped.height), (2, 1));
+
+		assert_eq!(pixels(&cropped), [6, 7]);
+	}
+
+	#[test]
+	fn regions_outside_of_the_webview_fail() {
+		let region = Rect {
+			position:LogicalPosition::new(4.0, 0.0).into(),
+			size:LogicalSize::new(1.0, 1.0).into(),
+		};
+
+		assert!(crop(bitmap(4, 2), Some(region), 1.0, 4.0).is_err());
+	}
+
+	#[test]
+	fn an_unknown_width_falls_back_to_the_scale_factor() {
+		let region = Rect {
+			position:LogicalPosition::new(1.0, 1.0).into(),
+			size:LogicalSize::new(1.0, 1.0).into(),
+		};
+
+		let cropped = crop(bitmap(4, 4), Some(region), 2.0, 0.0).unwrap();
+
+		assert_eq!((cropped.width, cropped.height), (2, 2));
+
+		assert_eq!(pixels(&cropped), [10, 11, 14, 15]);
+	}
+
+	#[cfg(any(
+		target_os = "macos",
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	#[test]
+	fn pixels_are_unpremultiplied() {
+		use super::unpremultiply;
+
+		assert_eq!(unpremultiply([0, 0, 0, 0]), [0, 0, 0, 0]);
+
+		assert_eq!(unpremultiply([10, 20, 30, 255]), [10, 20, 30, 255]);
+
+		assert_eq!(unpremultiply([64, 32, 0, 128]), [128, 64, 0, 128]);
+
+		// channels brighter than the alpha are clamped
+		assert_eq!(unpremultiply([200, 0, 0, 100]), [255, 0, 0, 100]);
+	}
+}
@@ -0,0 +1,511 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Region-based click-through.
+//!
+//! tao can only ignore the cursor events of the whole window, so the cursor
+//! events outside of the interactive regions are ignored here.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows / macOS**: the cursor is hit tested against the regions on
+//!   every mouse event, before the event is routed to a window, and the whole
+//!   window ignores the cursor events while it is outside of them. Hit testing
+//!   the window itself is not enough, the webview being hit tested by another
+//!   process on Windows and the windows behind not receiving the events a view
+//!   declines on macOS. The events are read with a low-level mouse hook on
+//!   Windows and with `NSEvent` monitors on macOS.
+//! - **Linux**: the regions are the input shape of the window.
+//! - **iOS / Android**: Unsupported.
+
+use std::{
+	cell::{Cell, RefCell},
+	rc::Rc,
+	sync::Arc,
+};
+
+use tao::window::Window;
+use tauri_runtime::Rect;
+
+/// The interactive regions of a window, the cursor events outside of them
+/// being ignored until it is dropped.
+pub struct CursorRegion {
+	state:Rc<State>,
+	#[cfg(target_os = "macos")]
+	monitors:Vec<objc2::rc::Retained<objc2::runtime::AnyObject>>,
+}
+
+/// Sets the interactive regions of `window`, `None` restoring the cursor
+/// events of the whole window.
+///
+/// `ignoring` is the value of the last
+/// [`set_ignore_cursor_events`](Window::set_ignore_cursor_events) call, which
+/// takes precedence over the regions.
+pub fn set(
+	window:&Arc<Window>,
+	current:&mut Option<CursorRegion>,
+	regions:Option<Vec<Rect>>,
+	ignoring:bool,
+) {
+	match (current.as_ref(), regions) {
+		(Some(region), Some(regions)) => region.update(regions),
+		(None, Some(regions)) => *current = CursorRegion::new(window.clone(), regions, ignoring),
+		(_, None) => *current = None,
+	}
+}
+
+/// Ignores the cursor events of the whole window when `ignore` is `true`,
+/// keeping the regions of `current` otherwise.
+pub fn set_ignore(window:&Window, current:Option<&CursorRegion>, ignore:bool) {
+	match current {
+		Some(region) => region.set_ignore(ignore),
+		None => {
+			let _ = window.set_ignore_cursor_events(ignore);
+		},
+	}
+}
+
+/// Whether the `(x, y)` physical position in the window is in one of the
+/// `regions`.
+#[cfg(any(windows, target_os = "macos", test))]
+fn contains(regions:&[Rect], scale_factor:f64, x:f64, y:f64) -> bool {
+	regions.iter().any(|rect| {
+		let position = rect.position.to_physical::<f64>(scale_factor);
+
+		let size = rect.size.to_physical::<f64>(scale_factor);
+
+		x >= position.x
+			&& y >= position.y
+			&& x < position.x + size.width
+			&& y < position.y + size.height
+	})
+}
+
+struct State {
+	window:Arc<Window>,
+	regions:RefCell<Vec<Rect>>,
+	// the value set with `set_ignore_cursor_events`
+	user_ignoring:Cell<bool>,
+	// whether the cursor was outside of the regions on the last mouse event
+	#[cfg(any(windows, target_os = "macos"))]
+	outside:Cell<bool>,
+	// whether the window currently ignores the cursor events
+	#[cfg(any(windows, target_os = "macos"))]
+	ignoring:Cell<bool>,
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+impl State {
+	fn new(window:Arc<Window>, regions:Vec<Rect>, ignoring:bool) -> Rc<Self> {
+		Rc::new(Self {
+			window,
+			regions:regions.into(),
+			user_ignoring:ignoring.into(),
+			outside:false.into(),
+			ignoring:ignoring.into(),
+		})
+	}
+
+	/// Hit tests the `cursor` physical screen position against the regions.
+	fn hit_test(&self, cursor:tao::dpi::PhysicalPosition<f64>) {
+		let Ok(origin) = self.window.inner_position() else {
+			return;
+		};
+
+		let inside = contains(
+			&self.regions.borrow(),
+			self.window.scale_factor(),
+			cursor.x - origin.x as f64,
+			cursor.y - origin.y as f64,
+		);
+
+		self.outside.set(!inside);
+
+		self.apply();
+	}
+
+	/// Hit tests the current cursor position, for the changes made between two
+	/// mouse events.
+	fn hit_test_cursor(&self) {
+		if let Ok(cursor) = self.window.cursor_position() {
+			self.hit_test(cursor);
+		}
+	}
+
+	fn apply(&self) {
+		let ignore = self.user_ignoring.get() || self.outside.get();
+
+		if self.ignoring.replace(ignore) != ignore {
+			let _ = self.window.set_ignore_cursor_events(ignore);
+		}
+	}
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+impl CursorRegion {
+	fn update(&self, regions:Vec<Rect>) {
+		*self.state.regions.borrow_mut() = regions;
+
+		self.state.hit_test_cursor();
+	}
+
+	fn set_ignore(&self, ignore:bool) {
+		self.state.user_ignoring.set(ignore);
+
+		self.state.apply();
+	}
+
+	/// Gives the window back the cursor events, unless the user ignores them.
+	fn restore(&self) {
+		let ignore = self.state.user_ignoring.get();
+
+		if self.state.ignoring.get() != ignore {
+			let _ = self.state.window.set_ignore_cursor_events(ignore);
+		}
+	}
+}
+
+#[cfg(windows)]
+#[derive(Default)]
+struct Hook {
+	handle:Option<windows::Win32::UI::WindowsAndMessaging::HHOOK>,
+	states:Vec<Rc<State>>,
+}
+
+#[cfg(windows)]
+thread_local! {
+	// the hook procedure only receives the mouse event, and a single hook
+	// serves every window of the event loop thread
+	static HOOK:RefCell<Hook> = RefCell::default();
+}
+
+#[cfg(windows)]
+impl CursorRegion {
+	fn new(window:Arc<Window>, regions:Vec<Rect>, ignoring:bool) -> Option<Self> {
+		use windows::Win32::{
+			Foundation::HINSTANCE,
+			System::LibraryLoader::GetModuleHandleW,
+			UI::WindowsAndMessaging::{SetWindowsHookExW, WH_MOUSE_LL},
+		};
+
+		let state = State::new(window, regions, ignoring);
+
+		let installed = HOOK.with(|hook| {
+			let mut hook = hook.borrow_mut();
+
+			if hook.handle.is_none() {
+				let handle = unsafe {
+					GetModuleHandleW(None).and_then(|module| {
+						SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_proc), HINSTANCE::from(module), 0)
+					})
+				};
+
+				match handle {
+					Ok(handle) => hook.handle = Some(handle),
+					Err(e) => {
+						log::error!("failed to hook the mouse events for the cursor regions: {e}");
+
+						return false;
+					},
+				}
+			}
+
+			hook.states.push(state.clone());
+
+			true
+		});
+
+		if !installed {
+			return None;
+		}
+
+		state.hit_test_cursor();
+
+		Some(Self { state })
+	}
+}
+
+#[cfg(windows)]
+impl Drop for CursorRegion {
+	fn drop(&mut self) {
+		use windows::Win32::UI::WindowsAndMessaging::UnhookWindowsHookEx;
+
+		HOOK.with(|hook| {
+			let mut hook = hook.borrow_mut();
+
+			hook.states.retain(|state| !Rc::ptr_eq(state, &self.state));
+
+			if hook.states.is_empty() {
+				if let Some(handle) = hook.handle.take() {
+					let _ = unsafe { UnhookWindowsHookEx(handle) };
+				}
+			}
+		});
+
+		self.restore();
+	}
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn mouse_proc(
+	code:i32,
+	wparam:windows::Win32::Foundation::WPARAM,
+	lparam:windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+	use windows::Win32::UI::WindowsAndMessaging::{
+		CallNextHookEx,
+		HC_ACTION,
+		HHOOK,
+		MSLLHOOKSTRUCT,
+	};
+
+	if code == HC_ACTION as i32 {
+		// the event is routed once the hook returns, so the window ignores it or
+		// not according to the position of the event itself
+		let point = unsafe { (*(lparam.0 as *const MSLLHOOKSTRUCT)).pt };
+
+		let cursor = tao::dpi::PhysicalPosition::new(point.x as f64, point.y as f64);
+
+		// cloned so the hit tests can run while a region is dropped
+		let states = HOOK.with(|hook| hook.borrow().states.clone());
+
+		for state in states {
+			state.hit_test(cursor);
+		}
+	}
+
+	unsafe { CallNextHookEx(HHOOK::default(), code, wparam, lparam) }
+}
+
+#[cfg(target_os = "macos")]
+impl CursorRegion {
+	fn new(window:Arc<Window>, regions:Vec<Rect>, ignoring:bool) -> Option<Self> {
+		use std::ptr::NonNull;
+
+		use block2::RcBlock;
+		use objc2_app_kit::{NSEvent, NSEventMask};
+
+		let state = State::new(window, regions, ignoring);
+
+		let mask = NSEventMask::MouseMoved
+			| NSEventMask::LeftMouseDragged
+			| NSEventMask::RightMouseDragged
+			| NSEventMask::OtherMouseDragged;
+
+		// the global monitor receives the events while the window ignores them,
+		// the local one while it does not
+		let state_ = state.clone();
+
+		let global = RcBlock::new(move |_:NonNull<NSEvent>| state_.hit_test_cursor());
+
+		let state_ = state.clone();
+
+		let local = RcBlock::new(move |event:NonNull<NSEvent>| {
+			state_.hit_test_cursor();
+
+			event.as_ptr()
+		});
+
+		let monitors = unsafe {
+			[
+				NSEvent::addGlobalMonitorForEventsMatchingMask_handler(mask, &global),
+				NSEvent::addLocalMonitorForEventsMatchingMask_handler(mask, &local),
+			]
+		};
+
+		let region = Self { state, monitors:monitors.into_iter().flatten().collect() };
+
+		// dropping the region removes the monitor that was added
+		if region.monitors.len() != 2 {
+			log::error!("failed to monitor the mouse events for the cursor regions");
+
+			return None;
+		}
+
+		region.state.hit_test_cursor();
+
+		Some(region)
+	}
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for CursorRegion {
+	fn drop(&mut self) {
+		use objc2_app_kit::NSEvent;
+
+		for monitor in &self.monitors {
+			unsafe { NSEvent::removeMonitor(monitor) };
+		}
+
+		self.restore();
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+impl CursorRegion {
+	fn new(window:Arc<Window>, regions:Vec<Rect>, ignoring:bool) -> Option<Self> {
+		let state =
+			Rc::new(State { window, regions:regions.into(), user_ignoring:ignoring.into() });
+
+		let region = Self { state };
+
+		region.apply();
+
+		Some(region)
+	}
+
+	fn update(&self, regions:Vec<Rect>) {
+		*self.state.regions.borrow_mut() = regions;
+
+		self.apply();
+	}
+
+	fn set_ignore(&self, ignore:bool) {
+		self.state.user_ignoring.set(ignore);
+
+		self.apply();
+	}
+
+	/// Sets the input shape to the regions, or to nothing while the user
+	/// ignores the cursor events like tao does.
+	fn apply(&self) {
+		use gtk::{cairo, prelude::WidgetExt};
+		use tao::platform::unix::WindowExtUnix;
+
+		let window = &self.state.window;
+
+		// GTK uses logical coordinates
+		let scale_factor = window.scale_factor();
+
+		let rectangles = if self.state.user_ignoring.get() {
+			Vec::new()
+		} else {
+			self
+				.state
+				.regions
+				.borrow()
+				.iter()
+				.map(|rect| {
+					let position = rect.position.to_logical::<f64>(scale_factor);
+
+					let size = rect.size.to_logical::<f64>(scale_factor);
+
+					cairo::RectangleInt::new(
+						position.x.round() as i32,
+						position.y.round() as i32,
+						size.width.round() as i32,
+						size.height.round() as i32,
+					)
+				})
+				.collect::<Vec<_>>()
+		};
+
+		window
+			.gtk_window()
+			.input_shape_combine_region(Some(&cairo::Region::create_rectangles(&rectangles)));
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+impl Drop for CursorRegion {
+	fn drop(&mut self) {
+		let _ = self.state.window.set_ignore_cursor_events(self.state.user_ignoring.get());
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+impl CursorRegion {
+	fn new(window:Arc<Window>, regions:Vec<Rect>, ignoring:bool) -> Option<Self> {
+		let state =
+			Rc::new(State { window, regions:regions.into(), user_ignoring:ignoring.into() });
+
+		Some(Self { state })
+	}
+
+	fn update(&self, _regions:Vec<Rect>) {}
+
+	fn set_ignore(&self, ignore:bool) {
+		self.state.user_ignoring.set(ignore);
+
+		let _ = self.state.window.set_ignore_cursor_events(ignore);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use tauri_runtime::{
+		Rect,
+		dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+	};
+
+	use super::contains;
+
+	fn rect(x:f64, y:f64, width:f64, height:f64) -> Rect {
+		Rect {
+			position:LogicalPosition::new(x, y).into(),
+			size:LogicalSize::new(width, height).into(),
+		}
+	}
+
+	#[test]
+	fn positions_in_a_region_are_interactive() {
+		let regions = [rect(10.0, 10.0, 100.0, 50.0), rect(200.0, 0.0, 20.0, 20.0)];
+
+		assert!(contains(&regions, 1.0, 10.0, 10.0));
+		assert!(contains(&regions, 1.0, 109.5, 59.5));
+		assert!(contains(&regions, 1.0, 210.0, 5.0));
+	}
+
+	#[test]
+	fn positions_outside_of_the_regions_pass_through() {
+		let regions = [rect(10.0, 10.0, 100.0, 50.0)];
+
+		assert!(!contains(&regions, 1.0, 9.9, 10.0));
+		// the right and bottom edges are outside
+		assert!(!contains(&regions, 1.0, 110.0, 30.0));
+		assert!(!contains(&regions, 1.0, 50.0, 60.0));
+		assert!(!contains(&[], 1.0, 0.0, 0.0));
+	}
+
+	#[test]
+	fn logical_regions_are_scaled() {
+		let regions = [rect(10.0, 10.0, 100.0, 50.0)];
+
+		assert!(contains(&regions, 2.0, 20.0, 20.0));
+		assert!(contains(&regions, 2.0, 219.0, 119.0));
+		assert!(!contains(&regions, 2.0, 19.0, 20.0));
+		assert!(!contains(&regions, 2.0, 220.0, 20.0));
+	}
+
+	#[test]
+	fn physical_regions_are_not_scaled() {
+		let regions = [Rect {
+			position:PhysicalPosition::new(10, 10).into(),
+			size:PhysicalSize::new(100, 50).into(),
+		}];
+
+		assert!(contains(&regions, 2.0, 109.0, 59.0));
+		assert!(!contains(&regions, 2.0, 110.0, 30.0));
+	}
+}
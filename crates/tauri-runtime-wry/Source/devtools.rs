@@ -0,0 +1,728 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Devtools docking and open state.
+//!
+//! wry can neither dock the devtools nor report when they are opened or closed
+//! by the user, so the web inspector of the platform webview is used directly.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: the devtools are always opened in a separate window. Its
+//!   showing and destruction are followed with a WinEvent hook on the browser
+//!   process, and it is given to the webview that opened it from the API, or
+//!   else to the webview of the foreground window. It is closed with
+//!   `WM_CLOSE`.
+//! - **Linux**: the open state follows the `attach`, `open-window` and `closed`
+//!   signals of the inspector. The devtools can't be docked to the right side.
+//! - **macOS**: the open state follows the private
+//!   `_webView:didAttachLocalInspector:` and `_webView:willCloseLocalInspector:`
+//!   methods of the UI delegate, set on a delegate forwarding everything else
+//!   to the one of wry. The devtools can't be docked to the right side.
+//! - **iOS / Android**: Unsupported.
+
+#![cfg(any(debug_assertions, feature = "devtools"))]
+
+use std::{cell::Cell, rc::Rc};
+
+use tauri_runtime::webview::{DevtoolsDock, DevtoolsOptions};
+
+/// Whether the devtools can be opened as set in `dock` on this platform.
+pub fn is_dock_supported(dock:DevtoolsDock) -> bool {
+	if cfg!(windows) {
+		dock == DevtoolsDock::Detached
+	} else if cfg!(any(
+		target_os = "macos",
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	)) {
+		// the inspector is always attached to the bottom side
+		dock != DevtoolsDock::Right
+	} else {
+		false
+	}
+}
+
+/// The open state of the devtools of a webview.
+pub struct DevtoolsState {
+	open:Cell<bool>,
+	on_change:Box<dyn Fn(bool)>,
+	#[cfg(windows)]
+	native:Native,
+}
+
+/// The webview and the devtools window the WinEvent hook matches.
+#[cfg(windows)]
+#[derive(Default)]
+struct Native {
+	/// The browser process of the webview, showing the devtools window.
+	process_id:Cell<u32>,
+	/// The root window of the webview.
+	root:Cell<windows::Win32::Foundation::HWND>,
+	/// Whether the devtools were opened from the API and their window isn't
+	/// shown yet.
+	pending:Cell<bool>,
+	window:Cell<Option<windows::Win32::Foundation::HWND>>,
+}
+
+impl DevtoolsState {
+	/// Tracks the devtools of a webview, calling `on_change` when they are
+	/// opened or closed.
+	pub fn new(on_change:impl Fn(bool) + 'static) -> Self {
+		Self {
+			open:Cell::new(false),
+			on_change:Box::new(on_change),
+			#[cfg(windows)]
+			native:Default::default(),
+		}
+	}
+
+	/// Whether the devtools are open.
+	pub fn is_open(&self) -> bool { self.open.get() }
+
+	fn update(&self, open:bool) {
+		if self.open.replace(open) != open {
+			(self.on_change)(open);
+		}
+	}
+
+	/// Opens the devtools of `webview`, where the platform opens them by
+	/// default if there are no `options`.
+	pub fn open(&self, webview:&wry::WebView, options:Option<DevtoolsOptions>) {
+		#[cfg(windows)]
+		if self.native.window.get().is_none() {
+			self.native.pending.set(true);
+		}
+
+		match options {
+			Some(options) => open(webview, options),
+			None => webview.open_devtools(),
+		}
+
+		self.update(true);
+	}
+
+	/// Closes the devtools of `webview`.
+	pub fn close(&self, webview:&wry::WebView) {
+		close(self, webview);
+
+		self.update(false);
+	}
+}
+
+/// A webview a devtools window shown by a browser process may belong to.
+#[cfg(any(windows, test))]
+struct Candidate {
+	process_id:u32,
+	/// Whether the devtools were opened from the API and their window isn't
+	/// shown yet.
+	pending:bool,
+	/// Whether the webview is in the foreground window.
+	foreground:bool,
+	/// Whether the webview already has a devtools window.
+	has_devtools:bool,
+}
+
+/// The index of the candidate a devtools window shown by `process_id` belongs
+/// to: the one that opened it from the API, or else the one in the foreground
+/// window, or else the only webview of the process.
+#[cfg(any(windows, test))]
+fn devtools_owner(candidates:&[Candidate], process_id:u32) -> Option<usize> {
+	let of_process = || {
+		candidates
+			.iter()
+			.enumerate()
+			.filter(move |(_, candidate)| {
+				candidate.process_id == process_id && !candidate.has_devtools
+			})
+	};
+
+	of_process()
+		.find(|(_, candidate)| candidate.pending)
+		.or_else(|| of_process().find(|(_, candidate)| candidate.foreground))
+		.or_else(|| {
+			let mut candidates = of_process();
+
+			candidates.next().filter(|_| candidates.next().is_none())
+		})
+		.map(|(index, _)| index)
+}
+
+#[cfg(windows)]
+#[derive(Default)]
+struct Hook {
+	/// The hooks of the browser processes, by process id.
+	handles:std::collections::HashMap<u32, windows::Win32::UI::Accessibility::HWINEVENTHOOK>,
+	states:Vec<std::rc::Weak<DevtoolsState>>,
+}
+
+#[cfg(windows)]
+thread_local! {
+	// the out of context events are received on the event loop thread
+	static HOOK:std::cell::RefCell<Hook> = std::cell::RefCell::default();
+}
+
+/// Follows the devtools window of `webview` with a WinEvent hook on its
+/// browser process.
+#[cfg(windows)]
+pub fn attach_change_handler(webview:&wry::WebView, state:&Rc<DevtoolsState>) {
+	use windows::Win32::UI::{
+		Accessibility::SetWinEventHook,
+		WindowsAndMessaging::{
+			EVENT_OBJECT_DESTROY,
+			EVENT_OBJECT_HIDE,
+			GA_ROOT,
+			GetAncestor,
+			WINEVENT_OUTOFCONTEXT,
+		},
+	};
+	use wry::WebViewExtWindows;
+
+	let mut process_id = 0;
+
+	if let Err(e) = unsafe { webview.webview().BrowserProcessId(&mut process_id) } {
+		log::error!("failed to get the browser process of the webview: {e}");
+
+		return;
+	}
+
+	if let Ok(parent) = unsafe { webview.controller().ParentWindow() } {
+		state.native.root.set(unsafe { GetAncestor(parent, GA_ROOT) });
+	}
+
+	state.native.process_id.set(process_id);
+
+	HOOK.with(|hook| {
+		let mut hook = hook.borrow_mut();
+
+		if !hook.handles.contains_key(&process_id) {
+			// the show event is in the range too
+			let handle = unsafe {
+				SetWinEventHook(
+					EVENT_OBJECT_DESTROY,
+					EVENT_OBJECT_HIDE,
+					None,
+					Some(event_proc),
+					process_id,
+					0,
+					WINEVENT_OUTOFCONTEXT,
+				)
+			};
+
+			if handle.is_invalid() {
+				log::error!("failed to hook the devtools window events");
+
+				return;
+			}
+
+			hook.handles.insert(process_id, handle);
+		}
+
+		hook.states.push(Rc::downgrade(state));
+	});
+}
+
+#[cfg(windows)]
+impl Drop for DevtoolsState {
+	fn drop(&mut self) {
+		use windows::Win32::UI::Accessibility::UnhookWinEvent;
+
+		// the thread local is gone when the event loop thread exits
+		let _ = HOOK.try_with(|hook| {
+			let mut hook = hook.borrow_mut();
+
+			hook.states.retain(|state| state.strong_count() > 0);
+
+			let processes = hook
+				.states
+				.iter()
+				.filter_map(std::rc::Weak::upgrade)
+				.map(|state| state.native.process_id.get())
+				.collect::<std::collections::HashSet<_>>();
+
+			hook.handles.retain(|process_id, handle| {
+				let used = processes.contains(process_id);
+
+				if !used {
+					let _ = unsafe { UnhookWinEvent(*handle) };
+				}
+
+				used
+			});
+		});
+	}
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn event_proc(
+	_hook:windows::Win32::UI::Accessibility::HWINEVENTHOOK,
+	event:u32,
+	hwnd:windows::Win32::Foundation::HWND,
+	object:i32,
+	child:i32,
+	_thread:u32,
+	_time:u32,
+) {
+	use windows::Win32::UI::WindowsAndMessaging::{
+		CHILDID_SELF,
+		EVENT_OBJECT_SHOW,
+		GA_ROOT,
+		GetAncestor,
+		GetForegroundWindow,
+		GetWindowTextW,
+		GetWindowThreadProcessId,
+		OBJID_WINDOW,
+	};
+
+	if object != OBJID_WINDOW.0 || child != CHILDID_SELF as i32 {
+		return;
+	}
+
+	// upgraded so the change handlers can run while a state is dropped
+	let states = HOOK.with(|hook| {
+		hook.borrow().states.iter().filter_map(std::rc::Weak::upgrade).collect::<Vec<_>>()
+	});
+
+	let owner = states.iter().find(|state| state.native.window.get() == Some(hwnd));
+
+	if event != EVENT_OBJECT_SHOW {
+		// hidden or destroyed
+		if let Some(state) = owner {
+			state.native.window.set(None);
+
+			state.update(false);
+		}
+
+		return;
+	}
+
+	if owner.is_some() || unsafe { GetAncestor(hwnd, GA_ROOT) } != hwnd {
+		return;
+	}
+
+	let mut title = [0u16; 16];
+
+	let len = unsafe { GetWindowTextW(hwnd, &mut title) }.max(0) as usize;
+
+	if !String::from_utf16_lossy(&title[..len]).starts_with("DevTools") {
+		return;
+	}
+
+	let mut process_id = 0;
+
+	unsafe { GetWindowThreadProcessId(hwnd, Some(&mut process_id)) };
+
+	let foreground = unsafe { GetForegroundWindow() };
+
+	let candidates = states
+		.iter()
+		.map(|state| {
+			Candidate {
+				process_id:state.native.process_id.get(),
+				pending:state.native.pending.get(),
+				foreground:state.native.root.get() == foreground,
+				has_devtools:state.native.window.get().is_some(),
+			}
+		})
+		.collect::<Vec<_>>();
+
+	if let Some(state) = devtools_owner(&candidates, process_id).map(|index| &states[index]) {
+		state.native.pending.set(false);
+
+		state.native.window.set(Some(hwnd));
+
+		state.update(true);
+	}
+}
+
+#[cfg(windows)]
+fn open(webview:&wry::WebView, _options:DevtoolsOptions) { webview.open_devtools(); }
+
+#[cfg(windows)]
+fn close(state:&DevtoolsState, _webview:&wry::WebView) {
+	use windows::Win32::{
+		Foundation::{LPARAM, WPARAM},
+		UI::WindowsAndMessaging::{PostMessageW, WM_CLOSE},
+	};
+
+	state.native.pending.set(false);
+
+	if let Some(hwnd) = state.native.window.take() {
+		if let Err(e) = unsafe { PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)) } {
+			log::error!("failed to close the devtools window: {e}");
+		}
+	}
+}
+
+/// Follows the open state of the devtools from the inspector signals.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_change_handler(webview:&wry::WebView, state:&Rc<DevtoolsState>) {
+	use webkit2gtk::{WebInspectorExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	let Some(inspector) = webview.webview().inspector() else {
+		log::error!("the webview has no inspector to listen to");
+
+		return;
+	};
+
+	let update = |open:bool| {
+		let state = Rc::downgrade(state);
+
+		move |_:&webkit2gtk::WebInspector| {
+			if let Some(state) = state.upgrade() {
+				state.update(open);
+			}
+
+			false
+		}
+	};
+
+	inspector.connect_attach(update(true));
+
+	inspector.connect_open_window(update(true));
+
+	let closed = update(false);
+
+	inspector.connect_closed(move |inspector| {
+		closed(inspector);
+	});
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn open(webview:&wry::WebView, options:DevtoolsOptions) {
+	use webkit2gtk::{WebInspectorExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	let Some(inspector) = webview.webview().inspector() else {
+		return;
+	};
+
+	inspector.show();
+
+	// the right side is rejected by `is_dock_supported`
+	match options.dock {
+		DevtoolsDock::Detached => inspector.detach(),
+		DevtoolsDock::Right | DevtoolsDock::Bottom => inspector.attach(),
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn close(_state:&DevtoolsState, webview:&wry::WebView) { webview.close_devtools(); }
+
+/// Follows the open state of the devtools from the private inspector methods
+/// of the UI delegate.
+#[cfg(target_os = "macos")]
+pub fn attach_change_handler(webview:&wry::WebView, state:&Rc<DevtoolsState>) {
+	use objc2::{msg_send, msg_send_id, rc::Retained, runtime::AnyObject};
+	use objc2_foundation::MainThreadMarker;
+	use wry::WebViewExtMacOS;
+
+	let Some(mtm) = MainThreadMarker::new() else {
+		return;
+	};
+
+	let wk_webview = webview.webview();
+
+	let delegate:Option<Retained<AnyObject>> = unsafe { msg_send_id![&*wk_webview, UIDelegate] };
+
+	let devtools_delegate = macos::DevtoolsDelegate::new(mtm, Rc::downgrade(state), delegate);
+
+	unsafe {
+		let _:() = msg_send![&*wk_webview, setUIDelegate: &*devtools_delegate];
+	}
+
+	// the UI delegate is a weak reference, the webview keeps it alive
+	macos::retain_with(&wk_webview, devtools_delegate);
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::rc::Weak;
+
+	use objc2::{
+		ClassType,
+		DeclaredClass,
+		declare_class,
+		msg_send,
+		msg_send_id,
+		mutability::MainThreadOnly,
+		rc::Retained,
+		runtime::{AnyObject, NSObject, NSObjectProtocol, Sel},
+	};
+	use objc2_foundation::MainThreadMarker;
+	use objc2_web_kit::WKWebView;
+
+	use super::DevtoolsState;
+
+	pub struct Ivars {
+		state:Weak<DevtoolsState>,
+		/// The UI delegate of wry.
+		delegate:Option<Retained<AnyObject>>,
+	}
+
+	declare_class!(
+		pub struct DevtoolsDelegate;
+
+		unsafe impl ClassType for DevtoolsDelegate {
+			type Super = NSObject;
+			type Mutability = MainThreadOnly;
+			const NAME:&'static str = "TauriDevtoolsDelegate";
+		}
+
+		impl DeclaredClass for DevtoolsDelegate {
+			type Ivars = Ivars;
+		}
+
+		unsafe impl DevtoolsDelegate {
+			#[method(respondsToSelector:)]
+			fn responds_to_selector(&self, selector:Sel) -> bool {
+				let responds:bool = unsafe { msg_send![super(self), respondsToSelector: selector] };
+
+				responds
+					|| self.ivars().delegate.as_deref().is_some_and(|delegate| unsafe {
+						msg_send![delegate, respondsToSelector: selector]
+					})
+			}
+
+			#[method(forwardingTargetForSelector:)]
+			fn forwarding_target_for_selector(&self, _selector:Sel) -> *mut AnyObject {
+				self.ivars().delegate.as_ref().map_or(std::ptr::null_mut(), |delegate| {
+					Retained::as_ptr(delegate) as *mut AnyObject
+				})
+			}
+
+			#[method(_webView:didAttachLocalInspector:)]
+			fn did_attach_local_inspector(&self, _webview:&AnyObject, _inspector:&AnyObject) {
+				self.update(true);
+			}
+
+			#[method(_webView:willCloseLocalInspector:)]
+			fn will_close_local_inspector(&self, _webview:&AnyObject, _inspector:&AnyObject) {
+				self.update(false);
+			}
+		}
+
+		unsafe impl NSObjectProtocol for DevtoolsDelegate {}
+	);
+
+	impl DevtoolsDelegate {
+		pub fn new(
+			mtm:MainThreadMarker,
+			state:Weak<DevtoolsState>,
+			delegate:Option<Retained<AnyObject>>,
+		) -> Retained<Self> {
+			let this = mtm.alloc::<Self>().set_ivars(Ivars { state, delegate });
+
+			unsafe { msg_send_id![super(this), init] }
+		}
+
+		fn update(&self, open:bool) {
+			if let Some(state) = self.ivars().state.upgrade() {
+				state.update(open);
+			}
+		}
+	}
+
+	/// Keeps `delegate` alive as long as `webview`.
+	pub fn retain_with(webview:&WKWebView, delegate:Retained<DevtoolsDelegate>) {
+		use objc2::ffi::{OBJC_ASSOCIATION_RETAIN_NONATOMIC, objc_setAssociatedObject};
+
+		static KEY:u8 = 0;
+
+		unsafe {
+			objc_setAssociatedObject(
+				webview as *const WKWebView as *mut _,
+				&KEY as *const u8 as *const _,
+				Retained::as_ptr(&delegate) as *mut _,
+				OBJC_ASSOCIATION_RETAIN_NONATOMIC,
+			);
+		}
+	}
+}
+
+#[cfg(target_os = "macos")]
+fn open(webview:&wry::WebView, options:DevtoolsOptions) {
+	use objc2::{msg_send, runtime::AnyObject};
+	use wry::WebViewExtMacOS;
+
+	webview.open_devtools();
+
+	let wk_webview = webview.webview();
+
+	// `_WKInspector` is a private API
+	unsafe {
+		let inspector:*mut AnyObject = msg_send![&*wk_webview, _inspector];
+
+		if inspector.is_null() {
+			return;
+		}
+
+		// the right side is rejected by `is_dock_supported`
+		match options.dock {
+			DevtoolsDock::Detached => {
+				let _:() = msg_send![inspector, detach];
+			},
+			DevtoolsDock::Right | DevtoolsDock::Bottom => {
+				let _:() = msg_send![inspector, attach];
+			},
+		}
+	}
+}
+
+#[cfg(target_os = "macos")]
+fn close(_state:&DevtoolsState, webview:&wry::WebView) { webview.close_devtools(); }
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_change_handler(_webview:&wry::WebView, _state:&Rc<DevtoolsState>) {}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn open(webview:&wry::WebView, _options:DevtoolsOptions) { webview.open_devtools(); }
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn close(_state:&DevtoolsState, webview:&wry::WebView) { webview.close_devtools(); }
+
+#[cfg(test)]
+mod tests {
+	use std::{cell::RefCell, rc::Rc};
+
+	use tauri_runtime::webview::DevtoolsDock;
+
+	use super::{Candidate, DevtoolsState, devtools_owner, is_dock_supported};
+
+	fn candidate(process_id:u32) -> Candidate {
+		Candidate { process_id, pending:false, foreground:false, has_devtools:false }
+	}
+
+	#[test]
+	fn changes_are_reported_once() {
+		let reported = Rc::new(RefCell::new(Vec::new()));
+
+		let reported_ = reported.clone();
+
+		let state = DevtoolsState::new(move |open| reported_.borrow_mut().push(open));
+
+		// the platform reports the devtools opened from the API back
+		state.update(true);
+
+		state.update(true);
+
+		assert!(state.is_open());
+
+		state.update(false);
+
+		state.update(false);
+
+		assert!(!state.is_open());
+
+		assert_eq!(*reported.borrow(), [true, false]);
+	}
+
+	#[test]
+	fn the_devtools_are_docked_where_supported() {
+		#[cfg(windows)]
+		assert_eq!(
+			[DevtoolsDock::Detached, DevtoolsDock::Right, DevtoolsDock::Bottom]
+				.map(is_dock_supported),
+			[true, false, false]
+		);
+
+		#[cfg(any(
+			target_os = "macos",
+			target_os = "linux",
+			target_os = "dragonfly",
+			target_os = "freebsd",
+			target_os = "netbsd",
+			target_os = "openbsd"
+		))]
+		assert_eq!(
+			[DevtoolsDock::Detached, DevtoolsDock::Right, DevtoolsDock::Bottom]
+				.map(is_dock_supported),
+			[true, false, true]
+		);
+	}
+
+	#[test]
+	fn devtools_windows_go_to_the_webview_that_opened_them() {
+		let candidates = [
+			Candidate { foreground:true, ..candidate(1) },
+			Candidate { pending:true, ..candidate(1) },
+			candidate(2),
+		];
+
+		assert_eq!(devtools_owner(&candidates, 1), Some(1));
+
+		// opened by the user in the foreground window
+		let candidates =
+			[candidate(1), Candidate { foreground:true, ..candidate(1) }, candidate(2)];
+
+		assert_eq!(devtools_owner(&candidates, 1), Some(1));
+	}
+
+	#[test]
+	fn devtools_windows_of_other_processes_are_ignored() {
+		let candidates = [Candidate { pending:true, ..candidate(1) }, candidate(2)];
+
+		// the only webview of the process
+		assert_eq!(devtools_owner(&candidates, 2), Some(1));
+
+		assert_eq!(devtools_owner(&candidates, 3), None);
+
+		// the webviews of a process can't be told apart
+		let candidates = [candidate(1), candidate(1)];
+
+		assert_eq!(devtools_owner(&candidates, 1), None);
+
+		// a webview has a single devtools window
+		let candidates =
+			[Candidate { has_devtools:true, ..candidate(1) }, candidate(1)];
+
+		assert_eq!(devtools_owner(&candidates, 1), Some(1));
+	}
+}
@@ -0,0 +1,109 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Confirmation dialogs.
+//!
+//! The dialogs are modal to their window and run a nested event loop until
+//! they are answered.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `MessageBoxW`.
+//! - **Linux**: uses a `GtkMessageDialog`.
+//! - **macOS**: uses an `NSAlert`, which is modal to the whole application.
+//! - **iOS / Android**: Unsupported.
+
+use tao::window::Window;
+use tauri_runtime::Result;
+
+/// Asks to confirm `message` over `window`, returning whether it was
+/// confirmed.
+#[cfg(windows)]
+pub fn confirm(window:&Window, title:&str, message:&str) -> Result<bool> {
+	use tao::platform::windows::WindowExtWindows;
+	use windows::{
+		Win32::{
+			Foundation::HWND,
+			UI::WindowsAndMessaging::{IDOK, MB_ICONWARNING, MB_OKCANCEL, MessageBoxW},
+		},
+		core::HSTRING,
+	};
+
+	let result = unsafe {
+		MessageBoxW(
+			HWND(window.hwnd() as _),
+			&HSTRING::from(message),
+			&HSTRING::from(title),
+			MB_OKCANCEL | MB_ICONWARNING,
+		)
+	};
+
+	Ok(result == IDOK)
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn confirm(window:&Window, title:&str, message:&str) -> Result<bool> {
+	use gtk::{ButtonsType, DialogFlags, MessageDialog, MessageType, ResponseType, prelude::*};
+	use tao::platform::unix::WindowExtUnix;
+
+	let dialog = MessageDialog::new(
+		Some(window.gtk_window()),
+		DialogFlags::MODAL | DialogFlags::DESTROY_WITH_PARENT,
+		MessageType::Warning,
+		ButtonsType::OkCancel,
+		message,
+	);
+
+	dialog.set_title(title);
+
+	let response = dialog.run();
+
+	dialog.close();
+
+	Ok(response == ResponseType::Ok)
+}
+
+#[cfg(target_os = "macos")]
+pub fn confirm(_window:&Window, title:&str, message:&str) -> Result<bool> {
+	use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSAlertStyle};
+	use objc2_foundation::{MainThreadMarker, NSString};
+
+	let mtm = MainThreadMarker::new()
+		.ok_or_else(|| tauri_runtime::Error::Dialog("not on the main thread".into()))?;
+
+	unsafe {
+		let alert = NSAlert::new(mtm);
+
+		alert.setAlertStyle(NSAlertStyle::Warning);
+
+		alert.setMessageText(&NSString::from_str(title));
+
+		alert.setInformativeText(&NSString::from_str(message));
+
+		alert.addButtonWithTitle(&NSString::from_str("OK"));
+
+		alert.addButtonWithTitle(&NSString::from_str("Cancel"));
+
+		Ok(alert.runModal() == NSAlertFirstButtonReturn)
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn confirm(_window:&Window, _title:&str, _message:&str) -> Result<bool> {
+	Err(tauri_runtime::Error::Dialog("unsupported on this platform".into()))
+}
@@ -0,0 +1,530 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Drag and drop event filtering.
+//!
+//! wry reports every cursor move of a drag, so the `Over` events are throttled
+//! to the frame rate here, and the drags rejected by the webview filter are not
+//! reported at all.
+//!
+//! wry always accepts the drags it reports, so the "no drop" cursor feedback of
+//! a rejected drag is set on top of its native drop handling.
+
+use std::{
+	cell::Cell,
+	path::{Path, PathBuf},
+	rc::Rc,
+	time::{Duration, Instant},
+};
+
+use tauri_runtime::window::DragDropEvent;
+use tauri_utils::mime_type::MimeType;
+
+/// The minimum delay between two reported `Over` events.
+const OVER_INTERVAL:Duration = Duration::from_millis(16);
+
+/// The type reported for the dragged directories.
+const DIRECTORY_TYPE:&str = "inode/directory";
+
+pub type DragDropFilter = Box<dyn Fn(&DragDropEvent) -> bool + Send>;
+
+/// The state of the drag operation over a webview.
+pub struct DragDropState {
+	filter:Option<DragDropFilter>,
+	rejected:Rc<Cell<bool>>,
+	last_over:Cell<Option<Instant>>,
+}
+
+impl DragDropState {
+	pub fn new(filter:Option<DragDropFilter>) -> Self {
+		Self { filter, rejected:Default::default(), last_over:Cell::new(None) }
+	}
+
+	/// Whether the current drag was rejected, shared with the native feedback,
+	/// or `None` when there is no filter to reject it.
+	pub fn rejected(&self) -> Option<Rc<Cell<bool>>> {
+		self.filter.as_ref().map(|_| self.rejected.clone())
+	}
+
+	/// Whether `event` is reported, running the filter when a drag enters.
+	pub fn accept(&self, event:&DragDropEvent) -> bool {
+		self.accept_at(event, Instant::now())
+	}
+
+	fn accept_at(&self, event:&DragDropEvent, now:Instant) -> bool {
+		match event {
+			DragDropEvent::Enter { .. } => {
+				self.last_over.set(None);
+
+				let accepted = self.filter.as_ref().map_or(true, |filter| filter(event));
+
+				self.rejected.set(!accepted);
+
+				accepted
+			},
+			DragDropEvent::Over { .. } => {
+				if self.rejected.get() {
+					return false;
+				}
+
+				if self.last_over.get().is_some_and(|last| now - last < OVER_INTERVAL) {
+					return false;
+				}
+
+				self.last_over.set(Some(now));
+
+				true
+			},
+			// the drag ends, the next one runs the filter again
+			DragDropEvent::Drop { .. } | DragDropEvent::Leave => !self.rejected.replace(false),
+			_ => !self.rejected.get(),
+		}
+	}
+}
+
+/// The MIME types of the dragged `paths`, guessed from their extension.
+pub fn types(paths:&[PathBuf]) -> Vec<String> {
+	paths.iter().map(|path| path_type(path)).collect()
+}
+
+fn path_type(path:&Path) -> String {
+	if path.is_dir() {
+		return DIRECTORY_TYPE.into();
+	}
+
+	// the parent directories may contain dots
+	let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+
+	if !file_name.contains('.') {
+		return MimeType::OctetStream.to_string();
+	}
+
+	MimeType::parse_from_uri_with_fallback(&file_name, MimeType::OctetStream).to_string()
+}
+
+/// Shows the "no drop" cursor while the current drag is `rejected`.
+#[cfg(windows)]
+pub fn attach_feedback(webview:&wry::WebView, rejected:Rc<Cell<bool>>) {
+	use windows::{
+		Win32::{
+			Foundation::{BOOL, HWND, LPARAM},
+			System::Ole::{IDropTarget, RegisterDragDrop, RevokeDragDrop},
+			UI::WindowsAndMessaging::{EnumChildWindows, GetPropW},
+		},
+		core::{Interface, w},
+	};
+	use wry::WebViewExtWindows;
+
+	unsafe extern "system" fn collect(hwnd:HWND, children:LPARAM) -> BOOL {
+		unsafe { (*(children.0 as *mut Vec<HWND>)).push(hwnd) };
+
+		true.into()
+	}
+
+	let mut parent = HWND::default();
+
+	if let Err(e) = unsafe { webview.controller().ParentWindow(&mut parent) } {
+		log::error!("failed to get the webview window: {e}");
+
+		return;
+	}
+
+	let mut children = Vec::new();
+
+	unsafe {
+		let _ = EnumChildWindows(parent, Some(collect), LPARAM(&mut children as *mut _ as isize));
+	}
+
+	for hwnd in children {
+		// the drop target wry registered on the webview window
+		let target = unsafe { GetPropW(hwnd, w!("OleDropTargetInterface")) };
+
+		if target.is_invalid() {
+			continue;
+		}
+
+		let Some(inner) = (unsafe { IDropTarget::from_raw_borrowed(&target.0) }).cloned() else {
+			continue;
+		};
+
+		let feedback:IDropTarget = ole::DropFeedback { inner, rejected:rejected.clone() }.into();
+
+		unsafe {
+			let _ = RevokeDragDrop(hwnd);
+
+			if let Err(e) = RegisterDragDrop(hwnd, &feedback) {
+				log::error!("failed to register the webview drop target: {e}");
+			}
+		}
+	}
+}
+
+#[cfg(windows)]
+mod ole {
+	use std::{cell::Cell, rc::Rc};
+
+	use windows::{
+		Win32::{
+			Foundation::POINTL,
+			System::{
+				Com::IDataObject,
+				Ole::{DROPEFFECT, DROPEFFECT_NONE, IDropTarget, IDropTarget_Impl},
+				SystemServices::MODIFIERKEYS_FLAGS,
+			},
+		},
+		core::{Result, implement},
+	};
+
+	/// Forwards to the drop target of wry, denying the drop of rejected drags.
+	#[implement(IDropTarget)]
+	pub struct DropFeedback {
+		pub inner:IDropTarget,
+		pub rejected:Rc<Cell<bool>>,
+	}
+
+	impl DropFeedback {
+		fn deny_rejected(&self, effect:*mut DROPEFFECT) {
+			if self.rejected.get() && !effect.is_null() {
+				unsafe { *effect = DROPEFFECT_NONE };
+			}
+		}
+	}
+
+	impl IDropTarget_Impl for DropFeedback_Impl {
+		fn DragEnter(
+			&self,
+			data:Option<&IDataObject>,
+			keys:MODIFIERKEYS_FLAGS,
+			point:&POINTL,
+			effect:*mut DROPEFFECT,
+		) -> Result<()> {
+			unsafe { self.inner.DragEnter(data, keys, *point, effect) }?;
+
+			self.deny_rejected(effect);
+
+			Ok(())
+		}
+
+		fn DragOver(
+			&self,
+			keys:MODIFIERKEYS_FLAGS,
+			point:&POINTL,
+			effect:*mut DROPEFFECT,
+		) -> Result<()> {
+			unsafe { self.inner.DragOver(keys, *point, effect) }?;
+
+			self.deny_rejected(effect);
+
+			Ok(())
+		}
+
+		fn DragLeave(&self) -> Result<()> { unsafe { self.inner.DragLeave() } }
+
+		fn Drop(
+			&self,
+			data:Option<&IDataObject>,
+			keys:MODIFIERKEYS_FLAGS,
+			point:&POINTL,
+			effect:*mut DROPEFFECT,
+		) -> Result<()> {
+			unsafe { self.inner.Drop(data, keys, *point, effect) }?;
+
+			self.deny_rejected(effect);
+
+			Ok(())
+		}
+	}
+}
+
+/// Shows the "no drop" cursor while the current drag is `rejected`.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_feedback(webview:&wry::WebView, rejected:Rc<Cell<bool>>) {
+	use gtk::{gdk::DragAction, prelude::*};
+	use wry::WebViewExtUnix;
+
+	// connected after the wry handler, which reports the drag and does not
+	// set the drag status
+	webview.webview().connect_drag_motion(move |_, context, _, _, time| {
+		if !rejected.get() {
+			return false;
+		}
+
+		context.drag_status(DragAction::empty(), time);
+
+		true
+	});
+}
+
+/// Shows the "no drop" cursor while the current drag is `rejected`.
+#[cfg(target_os = "macos")]
+pub fn attach_feedback(webview:&wry::WebView, rejected:Rc<Cell<bool>>) {
+	use wry::WebViewExtMacOS;
+
+	macos::attach(&webview.webview(), rejected);
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::{
+		cell::{Cell, RefCell},
+		collections::HashMap,
+		rc::{Rc, Weak},
+		sync::OnceLock,
+	};
+
+	use objc2::{
+		ClassType,
+		runtime::{AnyObject, Imp, Sel},
+		sel,
+	};
+	use objc2_web_kit::WKWebView;
+
+	/// The `draggingEntered:` and `draggingUpdated:` signature.
+	type DraggingMethod = unsafe extern "C" fn(*mut AnyObject, Sel, *mut AnyObject) -> usize;
+
+	/// `NSDragOperationNone`.
+	const NO_DRAG_OPERATION:usize = 0;
+
+	thread_local! {
+		/// The rejected drag state of the webviews, by address.
+		static REJECTED:RefCell<HashMap<usize, Weak<Cell<bool>>>> = Default::default();
+	}
+
+	/// The wry implementations of `draggingEntered:` and `draggingUpdated:`.
+	static WRY_METHODS:OnceLock<Option<(Imp, Imp)>> = OnceLock::new();
+
+	pub fn attach(webview:&WKWebView, rejected:Rc<Cell<bool>>) {
+		let address = webview as *const WKWebView as usize;
+
+		REJECTED.with(|states| {
+			let mut states = states.borrow_mut();
+
+			// the addresses of the released webviews are reused
+			states.retain(|_, state| state.strong_count() > 0);
+
+			states.insert(address, Rc::downgrade(&rejected));
+		});
+
+		// the wry webview class is shared by every webview
+		WRY_METHODS.get_or_init(|| {
+			let class = webview.class();
+
+			if class == WKWebView::class() {
+				// replacing the WebKit methods would affect every web view
+				return None;
+			}
+
+			let entered = class.instance_method(sel!(draggingEntered:))?;
+
+			let updated = class.instance_method(sel!(draggingUpdated:))?;
+
+			unsafe {
+				Some((
+					entered.set_implementation(std::mem::transmute::<DraggingMethod, Imp>(
+						dragging_entered,
+					)),
+					updated.set_implementation(std::mem::transmute::<DraggingMethod, Imp>(
+						dragging_updated,
+					)),
+				))
+			}
+		});
+	}
+
+	fn is_rejected(webview:*mut AnyObject) -> bool {
+		REJECTED.with(|states| {
+			states
+				.borrow()
+				.get(&(webview as usize))
+				.and_then(Weak::upgrade)
+				.is_some_and(|rejected| rejected.get())
+		})
+	}
+
+	unsafe fn forward(
+		wry_method:fn(&(Imp, Imp)) -> Imp,
+		webview:*mut AnyObject,
+		sel:Sel,
+		info:*mut AnyObject,
+	) -> usize {
+		let Some(Some(methods)) = WRY_METHODS.get() else {
+			return NO_DRAG_OPERATION;
+		};
+
+		// wry runs the drag and drop handler, and so the filter, here
+		let operation = unsafe {
+			std::mem::transmute::<Imp, DraggingMethod>(wry_method(methods))(webview, sel, info)
+		};
+
+		if is_rejected(webview) { NO_DRAG_OPERATION } else { operation }
+	}
+
+	unsafe extern "C" fn dragging_entered(
+		webview:*mut AnyObject,
+		sel:Sel,
+		info:*mut AnyObject,
+	) -> usize {
+		unsafe { forward(|methods| methods.0, webview, sel, info) }
+	}
+
+	unsafe extern "C" fn dragging_updated(
+		webview:*mut AnyObject,
+		sel:Sel,
+		info:*mut AnyObject,
+	) -> usize {
+		unsafe { forward(|methods| methods.1, webview, sel, info) }
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_feedback(_webview:&wry::WebView, _rejected:Rc<Cell<bool>>) {}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		path::PathBuf,
+		sync::{
+			Arc,
+			atomic::{AtomicUsize, Ordering},
+		},
+		time::{Duration, Instant},
+	};
+
+	use tauri_runtime::{dpi::PhysicalPosition, window::DragDropEvent};
+
+	use super::{DragDropState, OVER_INTERVAL};
+
+	fn enter(path:&str) -> DragDropEvent {
+		DragDropEvent::Enter {
+			paths:vec![PathBuf::from(path)],
+			types:Vec::new(),
+			position:PhysicalPosition::new(0.0, 0.0),
+		}
+	}
+
+	fn over() -> DragDropEvent { DragDropEvent::Over { position:PhysicalPosition::new(0.0, 0.0) } }
+
+	fn drop_files() -> DragDropEvent {
+		DragDropEvent::Drop { paths:Vec::new(), position:PhysicalPosition::new(0.0, 0.0) }
+	}
+
+	fn markdown_only() -> DragDropState {
+		DragDropState::new(Some(Box::new(|event| {
+			match event {
+				DragDropEvent::Enter { paths, .. } => {
+					paths.iter().all(|path| path.extension().is_some_and(|ext| ext == "md"))
+				},
+				_ => true,
+			}
+		})))
+	}
+
+	#[test]
+	fn over_events_are_throttled_to_the_frame_rate() {
+		let state = DragDropState::new(None);
+
+		let start = Instant::now();
+
+		assert!(state.accept_at(&enter("a.md"), start));
+
+		assert!(state.accept_at(&over(), start));
+
+		assert!(!state.accept_at(&over(), start + Duration::from_millis(1)));
+
+		assert!(state.accept_at(&over(), start + OVER_INTERVAL));
+	}
+
+	#[test]
+	fn rejected_drags_are_not_reported() {
+		let state = markdown_only();
+
+		let now = Instant::now();
+
+		assert!(!state.accept_at(&enter("a.png"), now));
+
+		assert!(state.rejected().unwrap().get());
+
+		assert!(!state.accept_at(&over(), now));
+
+		assert!(!state.accept_at(&drop_files(), now));
+
+		// the drop ends the drag
+		assert!(!state.rejected().unwrap().get());
+	}
+
+	#[test]
+	fn the_filter_runs_again_for_the_next_drag() {
+		let runs = Arc::new(AtomicUsize::new(0));
+
+		let runs_ = runs.clone();
+
+		let state = DragDropState::new(Some(Box::new(move |event| {
+			runs_.fetch_add(1, Ordering::SeqCst);
+
+			matches!(event, DragDropEvent::Enter { paths, .. } if paths[0].ends_with("a.md"))
+		})));
+
+		let now = Instant::now();
+
+		assert!(!state.accept_at(&enter("a.png"), now));
+
+		assert!(!state.accept_at(&DragDropEvent::Leave, now));
+
+		assert!(state.accept_at(&enter("a.md"), now));
+
+		assert!(state.accept_at(&over(), now));
+
+		assert!(state.accept_at(&DragDropEvent::Leave, now));
+
+		// only the enter events run the filter
+		assert_eq!(runs.load(Ordering::SeqCst), 2);
+	}
+
+	#[test]
+	fn drags_without_a_filter_have_no_rejected_state() {
+		let state = DragDropState::new(None);
+
+		assert!(state.rejected().is_none());
+
+		assert!(state.accept(&enter("a.png")));
+	}
+
+	#[test]
+	fn types_are_guessed_from_the_file_names() {
+		let dir = std::env::temp_dir();
+
+		let types = super::types(&[
+			PathBuf::from("/notes.v2/readme.txt"),
+			PathBuf::from("/data/table.csv"),
+			PathBuf::from("/notes.v2/archive"),
+			PathBuf::from("/images/logo.svg"),
+			dir,
+		]);
+
+		assert_eq!(
+			types,
+			[
+				"text/plain",
+				"text/csv",
+				"application/octet-stream",
+				"image/svg+xml",
+				"inode/directory",
+			]
+		);
+	}
+}
@@ -0,0 +1,689 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview find in page.
+//!
+//! wry has no search API, so the text is found with the platform webview
+//! directly. The running search is tracked here to move to the next match
+//! when the same query is searched again and to report the match changes.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2_28::Find`, which requires WebView2
+//!   Runtime 1.0.2849 or later. The search always wraps around and a new
+//!   search always starts at the first match.
+//! - **Linux**: uses the `WebKitFindController`, counting up to
+//!   [`MAX_MATCH_COUNT`] matches. The active match index is not reported.
+//! - **macOS**: uses `WKWebView::findString`, which selects the active match
+//!   and reports neither the match count nor the active match index. The
+//!   matches are counted in the page text instead, the active match index is
+//!   not reported.
+//! - **iOS / Android**: Unsupported.
+
+use std::{
+	cell::{Cell, RefCell},
+	rc::{Rc, Weak},
+};
+
+use tauri_runtime::{
+	Error,
+	Result,
+	webview::{FindOptions, FindResult},
+};
+
+pub type FindHandler = Box<dyn FnOnce(Result<FindResult>) + Send>;
+
+/// The maximum number of matches counted on Linux.
+pub const MAX_MATCH_COUNT:u32 = 1000;
+
+/// The search running in a webview.
+pub struct FindState {
+	/// The query and options the running search started with.
+	#[cfg_attr(target_os = "macos", allow(dead_code))]
+	search:RefCell<Option<(String, FindOptions)>>,
+	/// The listeners of the platform webview match changes, removed with the
+	/// state.
+	#[cfg_attr(target_os = "macos", allow(dead_code))]
+	listeners:RefCell<Option<Listeners>>,
+	result:Cell<Option<FindResult>>,
+	pending:Cell<Option<FindHandler>>,
+	on_change:Box<dyn Fn(FindResult)>,
+}
+
+impl FindState {
+	/// Tracks the searches of a webview, calling `on_change` when the matches
+	/// change.
+	pub fn new(on_change:impl Fn(FindResult) + 'static) -> Self {
+		Self {
+			search:RefCell::new(None),
+			listeners:RefCell::new(None),
+			result:Cell::new(None),
+			pending:Cell::new(None),
+			on_change:Box::new(on_change),
+		}
+	}
+
+	/// Records the search of `query`, returning the options of the running
+	/// search if it continues it.
+	#[cfg_attr(target_os = "macos", allow(dead_code))]
+	fn continue_search(&self, query:&str, options:FindOptions) -> Option<FindOptions> {
+		let mut search = self.search.borrow_mut();
+
+		match &*search {
+			Some((running, running_options))
+				if running == query
+					&& running_options.case_sensitive == options.case_sensitive
+					&& running_options.wrap_around == options.wrap_around =>
+			{
+				Some(*running_options)
+			},
+			_ => {
+				*search = Some((query.to_owned(), options));
+
+				None
+			},
+		}
+	}
+
+	/// Waits for the result of a search with `handler`, failing the search it
+	/// replaces.
+	fn wait(&self, handler:FindHandler) {
+		if let Some(previous) = self.pending.replace(Some(handler)) {
+			previous(Err(Error::FailedToFindInPage("replaced by a new search".into())));
+		}
+	}
+
+	/// Records `result` as the current matches, reporting it if it changed.
+	fn update(&self, result:FindResult) {
+		if self.result.replace(Some(result)) != Some(result) {
+			(self.on_change)(result);
+		}
+	}
+
+	/// Reports `result` to the handler waiting for the search.
+	fn resolve(&self, result:Result<FindResult>) {
+		if let Ok(result) = &result {
+			self.update(*result);
+		}
+
+		if let Some(handler) = self.pending.take() {
+			handler(result);
+		}
+	}
+
+	/// Forgets the running search.
+	fn reset(&self) {
+		self.search.take();
+
+		self.result.take();
+	}
+}
+
+/// Calls `f` with the state of a handler, unless the webview was closed.
+#[cfg_attr(any(windows, target_os = "android", target_os = "ios"), allow(dead_code))]
+fn with_state(state:&Weak<FindState>, f:impl FnOnce(&FindState)) {
+	if let Some(state) = state.upgrade() {
+		f(&state);
+	}
+}
+
+/// The script counting the matches of `query` in the page text.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn match_count_script(query:&str, case_sensitive:bool) -> String {
+	// the code points cannot escape the string
+	let code_points = query.chars().map(|c| (c as u32).to_string()).collect::<Vec<_>>().join(",");
+
+	let normalize = if case_sensitive { "" } else { ".toLocaleLowerCase()" };
+
+	format!(
+		"(() => {{
+  const query = String.fromCodePoint({code_points}){normalize};
+  const text = (document.body ? document.body.innerText : ''){normalize};
+  let count = 0;
+  for (let i = text.indexOf(query); query && i !== -1; i = text.indexOf(query, i + query.length)) {{
+    count++;
+  }}
+  return count;
+}})()"
+	)
+}
+
+#[cfg(windows)]
+pub fn find(
+	webview:&wry::WebView,
+	state:&Rc<FindState>,
+	query:String,
+	options:FindOptions,
+	handler:FindHandler,
+) {
+	use webview2_com::{
+		FindActiveMatchIndexChangedEventHandler,
+		FindMatchCountChangedEventHandler,
+		FindStartCompletedHandler,
+		Microsoft::Web::WebView2::Win32::{
+			ICoreWebView2_2,
+			ICoreWebView2_28,
+			ICoreWebView2Environment15,
+			ICoreWebView2Find,
+		},
+	};
+	use windows::{
+		Win32::System::WinRT::EventRegistrationToken,
+		core::{HSTRING, Interface},
+	};
+	use wry::WebViewExtWindows;
+
+	fn find_result(find:&ICoreWebView2Find) -> windows::core::Result<FindResult> {
+		let mut match_count = 0;
+
+		let mut active_match_index = 0;
+
+		unsafe {
+			find.MatchCount(&mut match_count)?;
+
+			find.ActiveMatchIndex(&mut active_match_index)?;
+		}
+
+		// the active match index starts at 1 and is -1 without an active match
+		Ok(FindResult {
+			found:match_count > 0,
+			match_count:Some(match_count.max(0) as u32),
+			active_match_index:(active_match_index > 0).then(|| active_match_index as u32 - 1),
+		})
+	}
+
+	let error = |e:windows::core::Error| Error::FailedToFindInPage(e.to_string());
+
+	state.wait(handler);
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			let find = core_webview.cast::<ICoreWebView2_28>()?.Find()?;
+
+			if state.listeners.borrow().is_none() {
+				let mut match_count_token = EventRegistrationToken::default();
+
+				let mut active_match_token = EventRegistrationToken::default();
+
+				// the webview owns the handlers, which must not keep the state alive
+				let state_ = Rc::downgrade(state);
+
+				find.add_MatchCountChanged(
+					&FindMatchCountChangedEventHandler::create(Box::new(move |find, _| {
+						if let (Some(find), Some(state)) = (find, state_.upgrade()) {
+							state.update(find_result(&find)?);
+						}
+
+						Ok(())
+					})),
+					&mut match_count_token,
+				)?;
+
+				let state_ = Rc::downgrade(state);
+
+				find.add_ActiveMatchIndexChanged(
+					&FindActiveMatchIndexChangedEventHandler::create(Box::new(move |find, _| {
+						if let (Some(find), Some(state)) = (find, state_.upgrade()) {
+							state.update(find_result(&find)?);
+						}
+
+						Ok(())
+					})),
+					&mut active_match_token,
+				)?;
+
+				state.listeners.replace(Some(Listeners {
+					find:find.clone(),
+					match_count_token,
+					active_match_token,
+				}));
+			}
+
+			if state.continue_search(&query, options).is_some() {
+				if options.backwards {
+					find.FindPrevious()?;
+				} else {
+					find.FindNext()?;
+				}
+
+				// the new active match is reported by the `ActiveMatchIndexChanged` event
+				state.resolve(find_result(&find).map_err(error));
+
+				return Ok(());
+			}
+
+			let find_options = core_webview
+				.cast::<ICoreWebView2_2>()?
+				.Environment()?
+				.cast::<ICoreWebView2Environment15>()?
+				.CreateFindOptions()?;
+
+			find_options.SetFindTerm(&HSTRING::from(query.as_str()))?;
+			find_options.SetIsCaseSensitive(options.case_sensitive)?;
+			find_options.SetShouldHighlightAllMatches(true)?;
+			find_options.SetSuppressDefaultFindDialog(true)?;
+
+			let state_ = Rc::downgrade(state);
+
+			let find_ = find.clone();
+
+			find.Start(
+				&find_options,
+				&FindStartCompletedHandler::create(Box::new(move |result| {
+					if let Some(state) = state_.upgrade() {
+						state.resolve(result.and_then(|()| find_result(&find_)).map_err(error));
+					}
+
+					Ok(())
+				})),
+			)
+		})
+	};
+
+	if let Err(e) = result {
+		state.reset();
+
+		state.resolve(Err(error(e)));
+	}
+}
+
+/// The match change handlers registered on the webview search.
+#[cfg(windows)]
+pub struct Listeners {
+	find:webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2Find,
+	match_count_token:windows::Win32::System::WinRT::EventRegistrationToken,
+	active_match_token:windows::Win32::System::WinRT::EventRegistrationToken,
+}
+
+#[cfg(windows)]
+impl Drop for Listeners {
+	fn drop(&mut self) {
+		unsafe {
+			let _ = self.find.remove_MatchCountChanged(self.match_count_token);
+
+			let _ = self.find.remove_ActiveMatchIndexChanged(self.active_match_token);
+		}
+	}
+}
+
+#[cfg(windows)]
+pub fn stop(webview:&wry::WebView, state:&FindState, clear_highlights:bool) {
+	use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2_28;
+	use windows::core::Interface;
+	use wry::WebViewExtWindows;
+
+	state.reset();
+
+	if clear_highlights {
+		let result = unsafe {
+			webview
+				.controller()
+				.CoreWebView2()
+				.and_then(|core_webview| core_webview.cast::<ICoreWebView2_28>()?.Find()?.Stop())
+		};
+
+		if let Err(e) = result {
+			log::error!("failed to stop the webview search: {e}");
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn find(
+	webview:&wry::WebView,
+	state:&Rc<FindState>,
+	query:String,
+	options:FindOptions,
+	handler:FindHandler,
+) {
+	use webkit2gtk::{FindControllerExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	// the count is `u32::MAX` when there are more than `MAX_MATCH_COUNT` matches
+	fn match_count(count:u32) -> Option<u32> { (count != u32::MAX).then_some(count) }
+
+	let Some(controller) = webview.webview().find_controller() else {
+		handler(Err(Error::FailedToFindInPage("the webview has no find controller".into())));
+
+		return;
+	};
+
+	state.wait(handler);
+
+	if state.listeners.borrow().is_none() {
+		// the webview owns the handlers, which must not keep the state alive
+		let state_ = Rc::downgrade(state);
+
+		let found = controller.connect_found_text(move |_, count| {
+			with_state(&state_, |state| {
+				state.resolve(Ok(FindResult {
+					found:true,
+					match_count:match_count(count),
+					active_match_index:None,
+				}))
+			});
+		});
+
+		let state_ = Rc::downgrade(state);
+
+		let failed = controller.connect_failed_to_find_text(move |_| {
+			with_state(&state_, |state| {
+				state.resolve(Ok(FindResult {
+					found:false,
+					match_count:Some(0),
+					active_match_index:None,
+				}))
+			});
+		});
+
+		let state_ = Rc::downgrade(state);
+
+		let counted = controller.connect_counted_matches(move |_, count| {
+			with_state(&state_, |state| {
+				state.update(FindResult {
+					found:count > 0,
+					match_count:match_count(count),
+					active_match_index:None,
+				})
+			});
+		});
+
+		state.listeners.replace(Some(Listeners {
+			controller:controller.clone(),
+			handlers:vec![found, failed, counted],
+		}));
+	}
+
+	match state.continue_search(&query, options) {
+		// the direction is relative to the one the search started with
+		Some(running) if running.backwards == options.backwards => controller.search_next(),
+		Some(_) => controller.search_previous(),
+		None => {
+			let mut flags = webkit2gtk::FindOptions::empty();
+
+			if !options.case_sensitive {
+				flags |= webkit2gtk::FindOptions::CASE_INSENSITIVE;
+			}
+
+			if options.wrap_around {
+				flags |= webkit2gtk::FindOptions::WRAP_AROUND;
+			}
+
+			if options.backwards {
+				flags |= webkit2gtk::FindOptions::BACKWARDS;
+			}
+
+			controller.count_matches(&query, flags.bits(), MAX_MATCH_COUNT);
+
+			controller.search(&query, flags.bits(), MAX_MATCH_COUNT);
+		},
+	}
+}
+
+/// The match change handlers connected to the webview find controller.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub struct Listeners {
+	controller:webkit2gtk::FindController,
+	handlers:Vec<gtk::glib::SignalHandlerId>,
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+impl Drop for Listeners {
+	fn drop(&mut self) {
+		use gtk::glib::object::ObjectExt;
+
+		for handler in self.handlers.drain(..) {
+			self.controller.disconnect(handler);
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn stop(webview:&wry::WebView, state:&FindState, clear_highlights:bool) {
+	use webkit2gtk::{FindControllerExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	state.reset();
+
+	if clear_highlights {
+		if let Some(controller) = webview.webview().find_controller() {
+			controller.search_finish();
+		}
+	}
+}
+
+#[cfg(target_os = "macos")]
+pub fn find(
+	webview:&wry::WebView,
+	state:&Rc<FindState>,
+	query:String,
+	options:FindOptions,
+	handler:FindHandler,
+) {
+	use std::ptr::NonNull;
+
+	use block2::RcBlock;
+	use objc2::{msg_send, rc::Retained, runtime::AnyObject};
+	use objc2_foundation::{NSError, NSString};
+	use objc2_web_kit::{WKFindConfiguration, WKFindResult, WKWebView};
+	use wry::WebViewExtMacOS;
+
+	state.wait(handler);
+
+	let webview = unsafe { Retained::cast::<WKWebView>(webview.webview()) };
+
+	let count_script = NSString::from_str(&match_count_script(&query, options.case_sensitive));
+
+	let state = Rc::downgrade(state);
+
+	let webview_ = webview.clone();
+
+	// WebKit moves from the current selection, so searching again finds the next match
+	let block = RcBlock::new(move |result:NonNull<WKFindResult>| {
+		let found = unsafe { result.as_ref().matchFound() };
+
+		let state = state.clone();
+
+		// WebKit does not count the matches, the page text is searched for them
+		let count_block = RcBlock::new(move |count:*mut AnyObject, error:*mut NSError| {
+			let match_count = (!count.is_null() && error.is_null())
+				.then(|| unsafe { msg_send![count, unsignedIntValue] });
+
+			with_state(&state, |state| {
+				state.resolve(Ok(FindResult { found, match_count, active_match_index:None }))
+			});
+		});
+
+		unsafe { webview_.evaluateJavaScript_completionHandler(&count_script, Some(&count_block)) };
+	});
+
+	unsafe {
+		let configuration = WKFindConfiguration::new();
+		configuration.setCaseSensitive(options.case_sensitive);
+		configuration.setWraps(options.wrap_around);
+		configuration.setBackwards(options.backwards);
+
+		webview.findString_withConfiguration_completionHandler(
+			&NSString::from_str(&query),
+			Some(&configuration),
+			&block,
+		);
+	}
+}
+
+/// The listeners of the match changes, none on this platform.
+#[cfg(not(any(
+	windows,
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub struct Listeners;
+
+#[cfg(target_os = "macos")]
+pub fn stop(webview:&wry::WebView, state:&FindState, clear_highlights:bool) {
+	state.reset();
+
+	// the active match is the page selection
+	if clear_highlights {
+		if let Err(e) = webview.evaluate_script("window.getSelection().removeAllRanges()") {
+			log::error!("failed to stop the webview search: {e}");
+		}
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn find(
+	_webview:&wry::WebView,
+	_state:&Rc<FindState>,
+	_query:String,
+	_options:FindOptions,
+	handler:FindHandler,
+) {
+	handler(Err(Error::FailedToFindInPage("unsupported on this platform".into())));
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn stop(_webview:&wry::WebView, state:&FindState, _clear_highlights:bool) { state.reset(); }
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		cell::RefCell,
+		rc::Rc,
+		sync::{Arc, Mutex},
+	};
+
+	use tauri_runtime::webview::{FindOptions, FindResult};
+
+	use super::{FindState, match_count_script};
+
+	fn result(match_count:u32) -> FindResult {
+		FindResult { found:match_count > 0, match_count:Some(match_count), active_match_index:None }
+	}
+
+	#[test]
+	fn searching_the_same_query_continues_the_search() {
+		let state = FindState::new(|_| {});
+
+		let options = FindOptions::default();
+
+		assert_eq!(state.continue_search("tauri", options), None);
+
+		let backwards = FindOptions { backwards:true, ..options };
+
+		// the direction does not start a new search
+		assert_eq!(state.continue_search("tauri", backwards), Some(options));
+
+		let case_sensitive = FindOptions { case_sensitive:true, ..options };
+
+		assert_eq!(state.continue_search("tauri", case_sensitive), None);
+
+		assert_eq!(state.continue_search("wry", case_sensitive), None);
+
+		state.reset();
+
+		assert_eq!(state.continue_search("wry", case_sensitive), None);
+	}
+
+	#[test]
+	fn match_changes_are_reported_once() {
+		let reported = Rc::new(RefCell::new(Vec::new()));
+
+		let reported_ = reported.clone();
+
+		let state = FindState::new(move |result| reported_.borrow_mut().push(result));
+
+		state.update(result(3));
+
+		state.update(result(3));
+
+		state.resolve(Ok(result(2)));
+
+		assert_eq!(*reported.borrow(), [result(3), result(2)]);
+	}
+
+	#[test]
+	fn a_new_search_fails_the_pending_one() {
+		let results = Arc::new(Mutex::new(Vec::new()));
+
+		let state = FindState::new(|_| {});
+
+		for _ in 0..2 {
+			let results = results.clone();
+
+			state.wait(Box::new(move |result| results.lock().unwrap().push(result)));
+		}
+
+		state.resolve(Ok(result(1)));
+
+		let results = results.lock().unwrap();
+
+		assert!(results[0].is_err());
+
+		assert_eq!(results[1].as_ref().ok(), Some(&result(1)));
+
+		// the handlers are called once
+		state.resolve(Ok(result(1)));
+
+		assert_eq!(results.len(), 2);
+	}
+
+	#[test]
+	fn the_match_count_script_cannot_be_escaped() {
+		let script = match_count_script("'); alert(1) //", true);
+
+		let code_points = "39,41,59,32,97,108,101,114,116,40,49,41,32,47,47";
+
+		assert!(script.contains(&format!("String.fromCodePoint({code_points})")));
+
+		assert!(!script.contains("alert"));
+
+		assert!(!script.contains("toLocaleLowerCase"));
+
+		assert!(match_count_script("Tauri", false).contains("toLocaleLowerCase"));
+	}
+}
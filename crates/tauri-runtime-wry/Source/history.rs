@@ -0,0 +1,362 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview navigation history.
+//!
+//! wry can neither navigate the history nor report its changes, so the
+//! history of the platform webview is used directly.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: changes are read from the `HistoryChanged` event. Reloading
+//!   without the cache uses the `Page.reload` DevTools protocol method.
+//! - **Linux**: changes are read from the `changed` signal of the back forward
+//!   list.
+//! - **macOS**: changes are only checked when a page load starts or finishes,
+//!   so the same-document navigations are reported with the next page load.
+//! - **iOS / Android**: Unsupported.
+
+use std::cell::Cell;
+
+use tauri_runtime::{Result, webview::NavigationHistory};
+
+/// The back and forward navigation availability of a webview.
+pub struct HistoryState {
+	history:Cell<NavigationHistory>,
+	on_change:Box<dyn Fn(NavigationHistory)>,
+}
+
+impl HistoryState {
+	/// Tracks the history of a webview, calling `on_change` when the back or
+	/// forward navigation availability changes.
+	pub fn new(on_change:impl Fn(NavigationHistory) + 'static) -> Self {
+		Self { history:Cell::new(NavigationHistory::default()), on_change:Box::new(on_change) }
+	}
+
+	/// Reads the history of `webview`, reporting it if it changed.
+	pub fn update(&self, webview:&wry::WebView) {
+		let history = NavigationHistory {
+			can_go_back:can_go_back(webview).unwrap_or_default(),
+			can_go_forward:can_go_forward(webview).unwrap_or_default(),
+		};
+
+		if self.history.replace(history) != history {
+			(self.on_change)(history);
+		}
+	}
+}
+
+#[cfg(windows)]
+fn error(e:windows::core::Error) -> tauri_runtime::Error {
+	tauri_runtime::Error::History(e.to_string())
+}
+
+/// Calls `handler` when the history of the platform webview changes.
+#[cfg(windows)]
+pub fn attach_change_handler(webview:&wry::WebView, handler:impl Fn() + 'static) {
+	use webview2_com::HistoryChangedEventHandler;
+	use windows::Win32::System::WinRT::EventRegistrationToken;
+	use wry::WebViewExtWindows;
+
+	let mut token = EventRegistrationToken::default();
+
+	let result = unsafe {
+		webview.webview().add_HistoryChanged(
+			&HistoryChangedEventHandler::create(Box::new(move |_, _| {
+				handler();
+
+				Ok(())
+			})),
+			&mut token,
+		)
+	};
+
+	if let Err(e) = result {
+		log::error!("failed to listen to the webview history changes: {e}");
+	}
+}
+
+#[cfg(windows)]
+pub fn can_go_back(webview:&wry::WebView) -> Result<bool> {
+	use windows::Win32::Foundation::BOOL;
+	use wry::WebViewExtWindows;
+
+	let mut can_go_back = BOOL::default();
+
+	unsafe { webview.webview().CanGoBack(&mut can_go_back) }.map_err(error)?;
+
+	Ok(can_go_back.as_bool())
+}
+
+#[cfg(windows)]
+pub fn can_go_forward(webview:&wry::WebView) -> Result<bool> {
+	use windows::Win32::Foundation::BOOL;
+	use wry::WebViewExtWindows;
+
+	let mut can_go_forward = BOOL::default();
+
+	unsafe { webview.webview().CanGoForward(&mut can_go_forward) }.map_err(error)?;
+
+	Ok(can_go_forward.as_bool())
+}
+
+#[cfg(windows)]
+pub fn go_back(webview:&wry::WebView) -> Result<()> {
+	use wry::WebViewExtWindows;
+
+	unsafe { webview.webview().GoBack() }.map_err(error)
+}
+
+#[cfg(windows)]
+pub fn go_forward(webview:&wry::WebView) -> Result<()> {
+	use wry::WebViewExtWindows;
+
+	unsafe { webview.webview().GoForward() }.map_err(error)
+}
+
+#[cfg(windows)]
+pub fn reload(webview:&wry::WebView, ignore_cache:bool) -> Result<()> {
+	use webview2_com::CallDevToolsProtocolMethodCompletedHandler;
+	use windows::core::HSTRING;
+	use wry::WebViewExtWindows;
+
+	if !ignore_cache {
+		return unsafe { webview.webview().Reload() }.map_err(error);
+	}
+
+	unsafe {
+		webview.webview().CallDevToolsProtocolMethod(
+			&HSTRING::from("Page.reload"),
+			&HSTRING::from(r#"{"ignoreCache":true}"#),
+			&CallDevToolsProtocolMethodCompletedHandler::create(Box::new(|result, _| {
+				if let Err(e) = result {
+					log::error!("failed to reload the webview: {e}");
+				}
+
+				Ok(())
+			})),
+		)
+	}
+	.map_err(error)
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_change_handler(webview:&wry::WebView, handler:impl Fn() + 'static) {
+	use webkit2gtk::{BackForwardListExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	match webview.webview().back_forward_list() {
+		Some(list) => {
+			list.connect_changed(move |_, _, _| handler());
+		},
+		None => log::error!("the webview has no back forward list to listen to"),
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn can_go_back(webview:&wry::WebView) -> Result<bool> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	Ok(webview.webview().can_go_back())
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn can_go_forward(webview:&wry::WebView) -> Result<bool> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	Ok(webview.webview().can_go_forward())
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn go_back(webview:&wry::WebView) -> Result<()> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	webview.webview().go_back();
+
+	Ok(())
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn go_forward(webview:&wry::WebView) -> Result<()> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	webview.webview().go_forward();
+
+	Ok(())
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn reload(webview:&wry::WebView, ignore_cache:bool) -> Result<()> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	if ignore_cache {
+		webview.webview().reload_bypass_cache();
+	} else {
+		webview.webview().reload();
+	}
+
+	Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn wk_webview(webview:&wry::WebView) -> objc2::rc::Retained<objc2_web_kit::WKWebView> {
+	use wry::WebViewExtMacOS;
+
+	objc2::rc::Retained::cast::<objc2_web_kit::WKWebView>(webview.webview())
+}
+
+#[cfg(target_os = "macos")]
+pub fn can_go_back(webview:&wry::WebView) -> Result<bool> {
+	Ok(unsafe { wk_webview(webview).canGoBack() })
+}
+
+#[cfg(target_os = "macos")]
+pub fn can_go_forward(webview:&wry::WebView) -> Result<bool> {
+	Ok(unsafe { wk_webview(webview).canGoForward() })
+}
+
+#[cfg(target_os = "macos")]
+pub fn go_back(webview:&wry::WebView) -> Result<()> {
+	unsafe { wk_webview(webview).goBack() };
+
+	Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn go_forward(webview:&wry::WebView) -> Result<()> {
+	unsafe { wk_webview(webview).goForward() };
+
+	Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn reload(webview:&wry::WebView, ignore_cache:bool) -> Result<()> {
+	let webview = wk_webview(webview);
+
+	unsafe {
+		if ignore_cache {
+			webview.reloadFromOrigin();
+		} else {
+			webview.reload();
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_change_handler(_webview:&wry::WebView, _handler:impl Fn() + 'static) {}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn can_go_back(_webview:&wry::WebView) -> Result<bool> {
+	Err(tauri_runtime::Error::History("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn can_go_forward(_webview:&wry::WebView) -> Result<bool> {
+	Err(tauri_runtime::Error::History("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn go_back(_webview:&wry::WebView) -> Result<()> {
+	Err(tauri_runtime::Error::History("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn go_forward(_webview:&wry::WebView) -> Result<()> {
+	Err(tauri_runtime::Error::History("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn reload(_webview:&wry::WebView, _ignore_cache:bool) -> Result<()> {
+	Err(tauri_runtime::Error::History("unsupported on this platform".into()))
+}
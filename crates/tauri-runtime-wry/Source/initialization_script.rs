@@ -0,0 +1,280 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Initialization scripts added after the webview creation.
+//!
+//! wry only takes initialization scripts on its builder, so runtime scripts
+//! are registered on the platform webview directly. Each platform only reads
+//! its scripts when a navigation starts, so a script added or removed while a
+//! page is loading applies from the next navigation on.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2::AddScriptToExecuteOnDocumentCreated`,
+//!   which always runs on every frame. Main frame only scripts are wrapped in a
+//!   `window.self === window.top` block, so their top-level `let`, `const` and
+//!   `class` declarations are not global.
+//! - **Linux**: uses the `WebKitUserContentManager` of the webview.
+//! - **macOS**: uses the `WKUserContentController` of the webview.
+//! - **iOS / Android**: Unsupported.
+
+use std::{
+	cell::RefCell,
+	collections::HashMap,
+	sync::atomic::{AtomicU32, Ordering},
+};
+
+use tauri_runtime::{Error, InitializationScriptId, Result, webview::InitializationScript};
+
+static NEXT_ID:AtomicU32 = AtomicU32::new(0);
+
+#[cfg(windows)]
+type Registered = String;
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+type Registered = webkit2gtk::UserScript;
+
+#[cfg(target_os = "macos")]
+type Registered = objc2::rc::Retained<objc2_web_kit::WKUserScript>;
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+type Registered = ();
+
+/// The scripts added to a webview after its creation.
+#[derive(Default)]
+pub struct InitializationScripts(RefCell<HashMap<InitializationScriptId, Registered>>);
+
+/// Adds `script` to `webview`, returning the id used to remove it.
+pub fn add(
+	webview:&wry::WebView,
+	scripts:&InitializationScripts,
+	script:InitializationScript,
+) -> Result<InitializationScriptId> {
+	let registered = register(webview, script)?;
+
+	let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+	scripts.0.borrow_mut().insert(id, registered);
+
+	Ok(id)
+}
+
+/// Removes the script `id` added to `webview` with [`add`].
+pub fn remove(
+	webview:&wry::WebView,
+	scripts:&InitializationScripts,
+	id:InitializationScriptId,
+) -> Result<()> {
+	let registered = scripts.0.borrow_mut().remove(&id).ok_or_else(|| {
+		Error::InitializationScript(format!("the webview has no initialization script {id}"))
+	})?;
+
+	unregister(webview, registered)
+}
+
+#[cfg(windows)]
+fn register(webview:&wry::WebView, script:InitializationScript) -> Result<Registered> {
+	use std::{cell::Cell, rc::Rc};
+
+	use webview2_com::AddScriptToExecuteOnDocumentCreatedCompletedHandler;
+	use windows::core::HSTRING;
+	use wry::WebViewExtWindows;
+
+	let source = if script.for_main_frame_only {
+		format!("if (window.self === window.top) {{\n{}\n}}", script.script)
+	} else {
+		script.script
+	};
+
+	let core_webview = unsafe { webview.controller().CoreWebView2() }
+		.map_err(|e| Error::InitializationScript(e.to_string()))?;
+
+	let id = Rc::new(Cell::new(String::new()));
+
+	let id_ = id.clone();
+
+	// waits for the completion so the script applies to the next navigation
+	AddScriptToExecuteOnDocumentCreatedCompletedHandler::wait_for_async_operation(
+		Box::new(move |handler| {
+			unsafe {
+				core_webview.AddScriptToExecuteOnDocumentCreated(&HSTRING::from(source), &handler)
+			}
+			.map_err(webview2_com::Error::WindowsError)
+		}),
+		Box::new(move |result, id| {
+			id_.set(id);
+
+			result
+		}),
+	)
+	.map_err(|e| Error::InitializationScript(format!("{e:?}")))?;
+
+	Ok(id.take())
+}
+
+#[cfg(windows)]
+fn unregister(webview:&wry::WebView, id:Registered) -> Result<()> {
+	use windows::core::HSTRING;
+	use wry::WebViewExtWindows;
+
+	unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			core_webview.RemoveScriptToExecuteOnDocumentCreated(&HSTRING::from(id))
+		})
+	}
+	.map_err(|e| Error::InitializationScript(e.to_string()))
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn user_content_manager(webview:&wry::WebView) -> Result<webkit2gtk::UserContentManager> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	webview.webview().user_content_manager().ok_or_else(|| {
+		Error::InitializationScript("the webview has no user content manager".into())
+	})
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn register(webview:&wry::WebView, script:InitializationScript) -> Result<Registered> {
+	use webkit2gtk::{
+		UserContentInjectedFrames,
+		UserContentManagerExt,
+		UserScript,
+		UserScriptInjectionTime,
+	};
+
+	let frames = if script.for_main_frame_only {
+		UserContentInjectedFrames::TopFrame
+	} else {
+		UserContentInjectedFrames::AllFrames
+	};
+
+	let user_script =
+		UserScript::new(&script.script, frames, UserScriptInjectionTime::Start, &[], &[]);
+
+	user_content_manager(webview)?.add_script(&user_script);
+
+	Ok(user_script)
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn unregister(webview:&wry::WebView, user_script:Registered) -> Result<()> {
+	use webkit2gtk::UserContentManagerExt;
+
+	user_content_manager(webview)?.remove_script(&user_script);
+
+	Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn user_content_controller(
+	webview:&wry::WebView,
+) -> objc2::rc::Retained<objc2_web_kit::WKUserContentController> {
+	use objc2::rc::Retained;
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	let webview = unsafe { Retained::cast::<WKWebView>(webview.webview()) };
+
+	unsafe { webview.configuration().userContentController() }
+}
+
+#[cfg(target_os = "macos")]
+fn register(webview:&wry::WebView, script:InitializationScript) -> Result<Registered> {
+	use objc2::ClassType;
+	use objc2_foundation::NSString;
+	use objc2_web_kit::{WKUserScript, WKUserScriptInjectionTime};
+
+	let user_script = unsafe {
+		WKUserScript::initWithSource_injectionTime_forMainFrameOnly(
+			WKUserScript::alloc(),
+			&NSString::from_str(&script.script),
+			WKUserScriptInjectionTime::AtDocumentStart,
+			script.for_main_frame_only,
+		)
+	};
+
+	unsafe { user_content_controller(webview).addUserScript(&user_script) };
+
+	Ok(user_script)
+}
+
+#[cfg(target_os = "macos")]
+fn unregister(webview:&wry::WebView, user_script:Registered) -> Result<()> {
+	let controller = user_content_controller(webview);
+
+	// WebKit can only remove every script, the others are added back in order
+	unsafe {
+		let user_scripts = controller.userScripts();
+
+		controller.removeAllUserScripts();
+
+		for script in user_scripts.iter() {
+			if !std::ptr::eq(script, &*user_script) {
+				controller.addUserScript(script);
+			}
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn register(_webview:&wry::WebView, _script:InitializationScript) -> Result<Registered> {
+	Err(Error::InitializationScript("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn unregister(_webview:&wry::WebView, _registered:Registered) -> Result<()> {
+	Err(Error::InitializationScript("unsupported on this platform".into()))
+}
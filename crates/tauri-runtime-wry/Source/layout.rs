@@ -0,0 +1,272 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Child webview layouts.
+//!
+//! The layouts are resolved against the window size when they are set, and
+//! applied on the event loop when the window is resized, before it is painted.
+
+use tauri_runtime::{
+	dpi::{LogicalPosition, LogicalSize},
+	webview::{Anchors, DockSide, WebviewLayout},
+};
+
+/// `length` relative to the window `window` length, `0` for a window without
+/// length, as a minimized window.
+fn rate(length:f32, window:f32) -> f32 { if window > 0. { length / window } else { 0. } }
+
+/// The position and length of a webview on one axis of the window.
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+	start:f32,
+	length:f32,
+	end:f32,
+	center_rate:f32,
+}
+
+impl Span {
+	fn new(start:f32, length:f32, window:f32) -> Self {
+		Self {
+			start,
+			length,
+			end:window - start - length,
+			center_rate:rate(start + length / 2., window),
+		}
+	}
+
+	/// The position and length in a window of `window` length.
+	fn resolve(&self, anchor_start:bool, anchor_end:bool, window:f32) -> (f32, f32) {
+		match (anchor_start, anchor_end) {
+			(true, true) => (self.start, (window - self.start - self.end).max(0.)),
+			(true, false) => (self.start, self.length),
+			(false, true) => (window - self.end - self.length, self.length),
+			(false, false) => (window * self.center_rate - self.length / 2., self.length),
+		}
+	}
+}
+
+/// A webview layout resolved against a window size.
+#[derive(Debug, Clone)]
+pub enum WebviewBounds {
+	Relative { x_rate:f32, y_rate:f32, width_rate:f32, height_rate:f32 },
+	Anchored { anchors:Anchors, horizontal:Span, vertical:Span },
+	Docked { side:DockSide, size:f32 },
+}
+
+impl WebviewBounds {
+	/// Resolves `layout` for a webview at `position` with `size`, in a window of
+	/// `window_size`.
+	pub fn new(
+		layout:WebviewLayout,
+		position:LogicalPosition<f32>,
+		size:LogicalSize<f32>,
+		window_size:LogicalSize<f32>,
+	) -> Self {
+		match layout {
+			WebviewLayout::Proportional => {
+				Self::Relative {
+					x_rate:rate(position.x, window_size.width),
+					y_rate:rate(position.y, window_size.height),
+					width_rate:rate(size.width, window_size.width),
+					height_rate:rate(size.height, window_size.height),
+				}
+			},
+			WebviewLayout::Anchored(anchors) => {
+				Self::Anchored {
+					anchors,
+					horizontal:Span::new(position.x, size.width, window_size.width),
+					vertical:Span::new(position.y, size.height, window_size.height),
+				}
+			},
+			WebviewLayout::Docked { side, size } => Self::Docked { side, size:size as f32 },
+		}
+	}
+
+	/// Resolves the layout again after the webview was moved or resized.
+	pub fn update(
+		&mut self,
+		position:LogicalPosition<f32>,
+		size:LogicalSize<f32>,
+		window_size:LogicalSize<f32>,
+	) {
+		// the webview bounds relative to a window without area are unknown
+		if !matches!(self, Self::Docked { .. })
+			&& (window_size.width <= 0. || window_size.height <= 0.)
+		{
+			return;
+		}
+
+		*self = match *self {
+			Self::Relative { .. } => {
+				Self::new(WebviewLayout::Proportional, position, size, window_size)
+			},
+			Self::Anchored { anchors, .. } => {
+				Self::new(WebviewLayout::Anchored(anchors), position, size, window_size)
+			},
+			Self::Docked { side, .. } => {
+				let size = match side {
+					DockSide::Left | DockSide::Right => size.width,
+					DockSide::Top | DockSide::Bottom => size.height,
+				};
+
+				Self::Docked { side, size }
+			},
+		};
+	}
+
+	/// The webview bounds in a window of `window_size`.
+	pub fn resolve(&self, window_size:LogicalSize<f32>) -> wry::Rect {
+		let LogicalSize { width, height } = window_size;
+
+		let (x, y, w, h) = match self {
+			Self::Relative { x_rate, y_rate, width_rate, height_rate } => {
+				(width * x_rate, height * y_rate, width * width_rate, height * height_rate)
+			},
+			Self::Anchored { anchors, horizontal, vertical } => {
+				let (x, w) = horizontal.resolve(anchors.left, anchors.right, width);
+
+				let (y, h) = vertical.resolve(anchors.top, anchors.bottom, height);
+
+				(x, y, w, h)
+			},
+			Self::Docked { side, size } => {
+				match side {
+					DockSide::Left => (0., 0., size.min(width), height),
+					DockSide::Top => (0., 0., width, size.min(height)),
+					DockSide::Right => ((width - size).max(0.), 0., size.min(width), height),
+					DockSide::Bottom => (0., (height - size).max(0.), width, size.min(height)),
+				}
+			},
+		};
+
+		wry::Rect {
+			position:LogicalPosition::new(x, y).into(),
+			size:LogicalSize::new(w, h).into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use tauri_runtime::{
+		dpi::{LogicalPosition, LogicalSize},
+		webview::{Anchors, DockSide, WebviewLayout},
+	};
+
+	use super::WebviewBounds;
+
+	/// The logical `(x, y, width, height)` of `rect`.
+	fn bounds(rect:wry::Rect) -> (f32, f32, f32, f32) {
+		let position = rect.position.to_logical::<f32>(1.);
+
+		let size = rect.size.to_logical::<f32>(1.);
+
+		(position.x, position.y, size.width, size.height)
+	}
+
+	fn resolve(
+		layout:WebviewLayout,
+		window:(f32, f32),
+		resized:(f32, f32),
+	) -> (f32, f32, f32, f32) {
+		let bounds_ = WebviewBounds::new(
+			layout,
+			LogicalPosition::new(100., 50.),
+			LogicalSize::new(200., 100.),
+			LogicalSize::new(window.0, window.1),
+		);
+
+		bounds(bounds_.resolve(LogicalSize::new(resized.0, resized.1)))
+	}
+
+	#[test]
+	fn proportional_layouts_scale_with_the_window() {
+		assert_eq!(
+			resolve(WebviewLayout::Proportional, (800., 400.), (400., 800.)),
+			(50., 100., 100., 200.)
+		);
+	}
+
+	#[test]
+	fn webviews_anchored_to_opposite_edges_stretch() {
+		assert_eq!(
+			resolve(WebviewLayout::Anchored(Anchors::ALL), (800., 400.), (1000., 500.)),
+			(100., 50., 400., 200.)
+		);
+	}
+
+	#[test]
+	fn webviews_anchored_to_one_edge_keep_their_size() {
+		let anchors = Anchors { right:true, bottom:true, ..Default::default() };
+
+		assert_eq!(
+			resolve(WebviewLayout::Anchored(anchors), (800., 400.), (1000., 500.)),
+			(300., 150., 200., 100.)
+		);
+	}
+
+	#[test]
+	fn unanchored_webviews_keep_their_relative_center() {
+		assert_eq!(
+			resolve(WebviewLayout::Anchored(Anchors::default()), (800., 400.), (1600., 800.)),
+			(300., 150., 200., 100.)
+		);
+	}
+
+	#[test]
+	fn docked_webviews_fill_the_window_side() {
+		let dock = |side| WebviewLayout::Docked { side, size:300. };
+
+		assert_eq!(
+			resolve(dock(DockSide::Left), (800., 400.), (1000., 500.)),
+			(0., 0., 300., 500.)
+		);
+
+		assert_eq!(
+			resolve(dock(DockSide::Right), (800., 400.), (1000., 500.)),
+			(700., 0., 300., 500.)
+		);
+
+		assert_eq!(
+			resolve(dock(DockSide::Bottom), (800., 400.), (200., 200.)),
+			(0., 0., 200., 200.)
+		);
+	}
+
+	#[test]
+	fn layouts_in_a_window_without_area_are_finite() {
+		for layout in [
+			WebviewLayout::Proportional,
+			WebviewLayout::Anchored(Anchors::default()),
+			WebviewLayout::Anchored(Anchors::ALL),
+		] {
+			let (x, y, width, height) = resolve(layout, (0., 0.), (800., 400.));
+
+			assert!([x, y, width, height].iter().all(|value| value.is_finite()), "{layout:?}");
+
+			let (x, y, width, height) = resolve(layout, (800., 400.), (0., 0.));
+
+			assert!([x, y, width, height].iter().all(|value| value.is_finite()), "{layout:?}");
+		}
+	}
+
+	#[test]
+	fn updates_in_a_window_without_area_keep_the_layout() {
+		let mut bounds_ = WebviewBounds::new(
+			WebviewLayout::Proportional,
+			LogicalPosition::new(100., 50.),
+			LogicalSize::new(200., 100.),
+			LogicalSize::new(800., 400.),
+		);
+
+		// a minimized window
+		bounds_.update(
+			LogicalPosition::new(0., 0.),
+			LogicalSize::new(0., 0.),
+			LogicalSize::new(0., 0.),
+		);
+
+		assert_eq!(bounds(bounds_.resolve(LogicalSize::new(800., 400.))), (100., 50., 200., 100.));
+	}
+}
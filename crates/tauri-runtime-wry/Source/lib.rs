@@ -16,16 +16,23 @@ use http::Request;
 use raw_window_handle::{DisplayHandle, HasDisplayHandle, HasWindowHandle};
 
 use tauri_runtime::{
-  dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size},
+  dpi::{
+    LogicalPosition, LogicalSize, LogicalUnit, PhysicalPosition, PhysicalSize, PhysicalUnit,
+    PixelUnit, Position, Size,
+  },
   monitor::Monitor,
-  webview::{DetachedWebview, DownloadEvent, PendingWebview, WebviewIpcHandler},
+  webview::{
+    DetachedWebview, DownloadEvent, InitializationScript, PendingWebview, WebviewIpcHandler,
+    WebviewLayout,
+  },
   window::{
     CursorIcon, DetachedWindow, DetachedWindowWebview, DragDropEvent, PendingWindow, RawWindow,
     WebviewEvent, WindowBuilder, WindowBuilderBase, WindowEvent, WindowId, WindowSizeConstraints,
   },
-  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon, ProgressBarState,
-  ProgressBarStatus, Result, RunEvent, Runtime, RuntimeHandle, RuntimeInitArgs, UserAttentionType,
-  UserEvent, WebviewDispatch, WebviewEventId, WindowDispatch, WindowEventId,
+  DeviceEventFilter, Error, EventLoopProxy, ExitRequestedEventAction, Icon,
+  InitializationScriptId, ProgressBarState, ProgressBarStatus, Result, RunEvent, Runtime,
+  RuntimeHandle, RuntimeInitArgs, UserAttentionType, UserEvent, WebviewDispatch, WebviewEventId,
+  WindowDispatch, WindowEventId,
 };
 
 #[cfg(any(target_os = "macos", target_os = "ios"))]
@@ -66,7 +73,7 @@ use tao::{
 #[cfg(target_os = "macos")]
 use tauri_utils::TitleBarStyle;
 use tauri_utils::{
-  config::{Color, WindowConfig},
+  config::{Color, FullscreenMonitor, WindowConfig},
   Theme,
 };
 use url::Url;
@@ -125,6 +132,23 @@ use std::{
 pub type WebviewId = u32;
 type IpcHandler = dyn Fn(Request<String>) + 'static;
 
+mod aspect_ratio;
+mod badge;
+mod browsing_data;
+mod capture;
+mod cursor_region;
+mod devtools;
+mod dialog;
+mod drag_drop;
+mod find;
+mod history;
+mod initialization_script;
+mod layout;
+mod memory;
+mod page_load;
+mod pdf;
+mod proxy;
+mod system_theme;
 #[cfg(any(
   windows,
   target_os = "linux",
@@ -134,9 +158,13 @@ type IpcHandler = dyn Fn(Request<String>) + 'static;
   target_os = "openbsd"
 ))]
 mod undecorated_resizing;
+mod undecorated_shadow;
+mod user_agent;
 mod webview;
 mod window;
+mod zoom;
 
+use layout::WebviewBounds;
 pub use webview::Webview;
 use window::WindowExt as _;
 
@@ -732,15 +760,47 @@ pub struct WindowBuilderWrapper {
   center: bool,
   #[cfg(target_os = "macos")]
   tabbing_identifier: Option<String>,
+  #[cfg(target_os = "macos")]
+  traffic_light_position: Option<Position>,
+  #[cfg(windows)]
+  titlebar_overlay: bool,
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  shadow: bool,
+  fullscreen_monitor: Option<FullscreenMonitor>,
+  aspect_ratio: Option<f64>,
 }
 
 impl std::fmt::Debug for WindowBuilderWrapper {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     let mut s = f.debug_struct("WindowBuilderWrapper");
-    s.field("inner", &self.inner).field("center", &self.center);
+    s.field("inner", &self.inner)
+      .field("center", &self.center)
+      .field("fullscreen_monitor", &self.fullscreen_monitor)
+      .field("aspect_ratio", &self.aspect_ratio);
     #[cfg(target_os = "macos")]
     {
-      s.field("tabbing_identifier", &self.tabbing_identifier);
+      s.field("tabbing_identifier", &self.tabbing_identifier)
+        .field("traffic_light_position", &self.traffic_light_position);
+    }
+    #[cfg(windows)]
+    {
+      s.field("titlebar_overlay", &self.titlebar_overlay);
+    }
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    {
+      s.field("shadow", &self.shadow);
     }
     s.finish()
   }
@@ -830,8 +890,33 @@ impl WindowBuilder for WindowBuilderWrapper {
         .theme(config.theme)
         .closable(config.closable)
         .maximizable(config.maximizable)
-        .minimizable(config.minimizable)
-        .shadow(config.shadow);
+        .minimizable(config.minimizable);
+
+      // the shadow changes the decorations on Linux, so it is only applied there when set
+      #[cfg(not(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      )))]
+      {
+        window = window.shadow(config.shadow.unwrap_or(true));
+      }
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      if let Some(shadow) = config.shadow {
+        window = window.shadow(shadow);
+      }
+
+      if let Some(monitor) = &config.fullscreen_monitor {
+        window = window.fullscreen_monitor(monitor.clone());
+      }
 
       let mut constraints = WindowSizeConstraints::default();
 
@@ -909,6 +994,11 @@ impl WindowBuilder for WindowBuilderWrapper {
     self
   }
 
+  fn aspect_ratio(mut self, ratio: f64) -> Self {
+    self.aspect_ratio.replace(ratio);
+    self
+  }
+
   fn resizable(mut self, resizable: bool) -> Self {
     self.inner = self.inner.with_resizable(resizable);
     self
@@ -945,6 +1035,11 @@ impl WindowBuilder for WindowBuilderWrapper {
     self
   }
 
+  fn fullscreen_monitor(mut self, monitor: FullscreenMonitor) -> Self {
+    self.fullscreen_monitor.replace(monitor);
+    self
+  }
+
   fn focused(mut self, focused: bool) -> Self {
     self.inner = self.inner.with_focused(focused);
     self
@@ -1002,6 +1097,16 @@ impl WindowBuilder for WindowBuilderWrapper {
     {
       self.inner = self.inner.with_has_shadow(_enable);
     }
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    {
+      self.shadow = _enable;
+    }
     self
   }
 
@@ -1074,6 +1179,18 @@ impl WindowBuilder for WindowBuilderWrapper {
     self
   }
 
+  #[cfg(target_os = "macos")]
+  fn traffic_light_position<P: Into<Position>>(mut self, position: P) -> Self {
+    self.traffic_light_position.replace(position.into());
+    self
+  }
+
+  #[cfg(windows)]
+  fn titlebar_overlay(mut self, enabled: bool) -> Self {
+    self.titlebar_overlay = enabled;
+    self
+  }
+
   #[cfg(target_os = "macos")]
   fn tabbing_identifier(mut self, identifier: &str) -> Self {
     self.inner = self.inner.with_tabbing_identifier(identifier);
@@ -1194,6 +1311,7 @@ pub enum WindowMessage {
   OuterPosition(Sender<Result<PhysicalPosition<i32>>>),
   InnerSize(Sender<PhysicalSize<u32>>),
   OuterSize(Sender<PhysicalSize<u32>>),
+  SizeConstraints(Sender<WindowSizeConstraints>),
   IsFullscreen(Sender<bool>),
   IsMinimized(Sender<bool>),
   IsMaximized(Sender<bool>),
@@ -1227,7 +1345,9 @@ pub enum WindowMessage {
   GtkBox(Sender<GtkBox>),
   RawWindowHandle(Sender<std::result::Result<SendRawWindowHandle, raw_window_handle::HandleError>>),
   Theme(Sender<Theme>),
+  TitleBarRects(Sender<Vec<tauri_runtime::Rect>>),
   IsEnabled(Sender<bool>),
+  Confirm(String, String, Sender<Result<bool>>),
   // Setters
   Center,
   RequestUserAttention(Option<UserAttentionTypeWrapper>),
@@ -1255,8 +1375,10 @@ pub enum WindowMessage {
   SetMinSize(Option<Size>),
   SetMaxSize(Option<Size>),
   SetSizeConstraints(WindowSizeConstraints),
+  SetAspectRatio(Option<f64>),
   SetPosition(Position),
   SetFullscreen(bool),
+  SetFullscreenOnMonitor(Monitor, Sender<Result<()>>),
   SetFocus,
   SetIcon(TaoWindowIcon),
   SetSkipTaskbar(bool),
@@ -1265,6 +1387,7 @@ pub enum WindowMessage {
   SetCursorIcon(CursorIcon),
   SetCursorPosition(Position),
   SetIgnoreCursorEvents(bool),
+  SetIgnoreCursorEventsRegion(Option<Vec<tauri_runtime::Rect>>),
   SetBadgeCount(Option<i64>, Option<String>),
   SetBadgeLabel(Option<String>),
   SetOverlayIcon(Option<TaoIcon>),
@@ -1302,7 +1425,27 @@ pub enum WebviewMessage {
   WebviewEvent(WebviewEvent),
   SynthesizedWindowEvent(SynthesizedWindowEvent),
   Navigate(Url),
+  GoBack(Sender<Result<()>>),
+  GoForward(Sender<Result<()>>),
+  Reload(bool, Sender<Result<()>>),
+  // the platform webview history changed
+  SyncHistory,
   Print,
+  Capture(Option<tauri_runtime::Rect>, capture::CaptureHandler),
+  PrintToPdf(tauri_runtime::webview::PdfOptions, pdf::PdfHandler),
+  FindInPage(String, tauri_runtime::webview::FindOptions, find::FindHandler),
+  StopFind(bool),
+  ClearBrowsingData(tauri_runtime::webview::DataTypes, browsing_data::BrowsingDataHandler),
+  CookiesForUrl(Url, Sender<Result<Vec<tauri_runtime::webview::Cookie<'static>>>>),
+  DeleteCookie(String, Url, browsing_data::BrowsingDataHandler),
+  Cookies(Sender<Result<Vec<tauri_runtime::webview::Cookie<'static>>>>),
+  SetCookie(
+    tauri_runtime::webview::Cookie<'static>,
+    browsing_data::BrowsingDataHandler,
+  ),
+  SetProxy(Option<tauri_runtime::webview::ProxyConfig>, Sender<Result<()>>),
+  AddInitializationScript(InitializationScript, Sender<Result<InitializationScriptId>>),
+  RemoveInitializationScript(InitializationScriptId, Sender<Result<()>>),
   Close,
   Show,
   Hide,
@@ -1310,9 +1453,14 @@ pub enum WebviewMessage {
   SetSize(Size),
   SetBounds(tauri_runtime::Rect),
   SetFocus,
-  Reparent(WindowId, Sender<Result<()>>),
-  SetAutoResize(bool),
+  Reparent(WindowId, Option<tauri_runtime::Rect>, Sender<Result<()>>),
+  SetLayout(Option<WebviewLayout>),
   SetZoom(f64),
+  SetUserAgent(String, Sender<Result<()>>),
+  // the platform webview zoom changed
+  SyncZoom(f64),
+  // a navigation started, which may reset the zoom
+  RestoreZoom,
   SetBackgroundColor(Option<Color>),
   ClearAllBrowsingData,
   // Getters
@@ -1320,10 +1468,15 @@ pub enum WebviewMessage {
   Bounds(Sender<Result<tauri_runtime::Rect>>),
   Position(Sender<Result<PhysicalPosition<i32>>>),
   Size(Sender<Result<PhysicalSize<u32>>>),
+  Zoom(Sender<f64>),
+  UserAgent(Sender<Result<String>>),
+  MemoryUsage(Sender<Result<Option<u64>>>),
+  CanGoBack(Sender<Result<bool>>),
+  CanGoForward(Sender<Result<bool>>),
   WithWebview(Box<dyn FnOnce(Webview) + Send>),
   // Devtools
   #[cfg(any(debug_assertions, feature = "devtools"))]
-  OpenDevTools,
+  OpenDevTools(Option<tauri_runtime::webview::DevtoolsOptions>),
   #[cfg(any(debug_assertions, feature = "devtools"))]
   CloseDevTools,
   #[cfg(any(debug_assertions, feature = "devtools"))]
@@ -1349,6 +1502,9 @@ pub enum Message<T: 'static> {
   Window(WindowId, WindowMessage),
   Webview(WindowId, WebviewId, WebviewMessage),
   EventLoopWindowTarget(EventLoopWindowTargetMessage),
+  SetBadgeCount(Option<u64>, Option<String>),
+  SetBadgeLabel(Option<String>),
+  SystemThemeChanged(Theme),
   CreateWebview(WindowId, CreateWebviewClosure),
   CreateWindow(WindowId, CreateWindowClosure<T>),
   CreateRawWindow(
@@ -1411,11 +1567,27 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
       Message::Webview(
         *self.window_id.lock().unwrap(),
         self.webview_id,
-        WebviewMessage::OpenDevTools,
+        WebviewMessage::OpenDevTools(None),
       ),
     );
   }
 
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  fn open_devtools_with(&self, options: tauri_runtime::webview::DevtoolsOptions) -> Result<()> {
+    if !devtools::is_dock_supported(options.dock) {
+      return Err(Error::UnsupportedDevtoolsDock(options.dock));
+    }
+
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::OpenDevTools(Some(options)),
+      ),
+    )
+  }
+
   #[cfg(any(debug_assertions, feature = "devtools"))]
   fn close_devtools(&self) {
     let _ = send_user_message(
@@ -1452,6 +1624,26 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     webview_getter!(self, WebviewMessage::Size)?
   }
 
+  fn zoom(&self) -> Result<f64> {
+    webview_getter!(self, WebviewMessage::Zoom)
+  }
+
+  fn user_agent(&self) -> Result<String> {
+    webview_getter!(self, WebviewMessage::UserAgent)?
+  }
+
+  fn memory_usage(&self) -> Result<Option<u64>> {
+    webview_getter!(self, WebviewMessage::MemoryUsage)?
+  }
+
+  fn can_go_back(&self) -> Result<bool> {
+    webview_getter!(self, WebviewMessage::CanGoBack)?
+  }
+
+  fn can_go_forward(&self) -> Result<bool> {
+    webview_getter!(self, WebviewMessage::CanGoForward)?
+  }
+
   // Setters
 
   fn navigate(&self, url: Url) -> Result<()> {
@@ -1465,6 +1657,45 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn go_back(&self) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::GoBack(tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
+  fn go_forward(&self) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::GoForward(tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
+  fn reload(&self, ignore_cache: bool) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Reload(ignore_cache, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
   fn print(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1476,6 +1707,92 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn capture<F: FnOnce(Result<Icon<'static>>) + Send + 'static>(
+    &self,
+    region: Option<tauri_runtime::Rect>,
+    f: F,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Capture(region, Box::new(f)),
+      ),
+    )
+  }
+
+  fn print_to_pdf<F: FnOnce(Result<Vec<u8>>) + Send + 'static>(
+    &self,
+    options: tauri_runtime::webview::PdfOptions,
+    f: F,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::PrintToPdf(options, Box::new(f)),
+      ),
+    )
+  }
+
+  fn find_in_page<F: FnOnce(Result<tauri_runtime::webview::FindResult>) + Send + 'static>(
+    &self,
+    query: String,
+    options: tauri_runtime::webview::FindOptions,
+    f: F,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::FindInPage(query, options, Box::new(f)),
+      ),
+    )
+  }
+
+  fn stop_find(&self, clear_highlights: bool) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::StopFind(clear_highlights),
+      ),
+    )
+  }
+
+  fn add_initialization_script(
+    &self,
+    script: InitializationScript,
+  ) -> Result<InitializationScriptId> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::AddInitializationScript(script, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
+  fn remove_initialization_script(&self, id: InitializationScriptId) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::RemoveInitializationScript(id, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
   fn close(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1531,7 +1848,7 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
-  fn reparent(&self, window_id: WindowId) -> Result<()> {
+  fn reparent(&self, window_id: WindowId, bounds: Option<tauri_runtime::Rect>) -> Result<()> {
     let mut current_window_id = self.window_id.lock().unwrap();
     let (tx, rx) = channel();
     send_user_message(
@@ -1539,11 +1856,11 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
       Message::Webview(
         *current_window_id,
         self.webview_id,
-        WebviewMessage::Reparent(window_id, tx),
+        WebviewMessage::Reparent(window_id, bounds, tx),
       ),
     )?;
 
-    rx.recv().unwrap()?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)??;
 
     *current_window_id = window_id;
     Ok(())
@@ -1555,7 +1872,31 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
       Message::Webview(
         *self.window_id.lock().unwrap(),
         self.webview_id,
-        WebviewMessage::SetAutoResize(auto_resize),
+        WebviewMessage::SetLayout(auto_resize.then_some(WebviewLayout::Proportional)),
+      ),
+    )
+  }
+
+  fn set_proxy(&self, proxy: Option<tauri_runtime::webview::ProxyConfig>) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetProxy(proxy, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
+  fn set_layout(&self, layout: Option<WebviewLayout>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetLayout(layout),
       ),
     )
   }
@@ -1598,6 +1939,19 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
+  fn set_user_agent(&self, user_agent: String) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetUserAgent(user_agent, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
   fn clear_all_browsing_data(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -1609,52 +1963,124 @@ impl<T: UserEvent> WebviewDispatch<T> for WryWebviewDispatcher<T> {
     )
   }
 
-  fn hide(&self) -> Result<()> {
+  fn clear_browsing_data<F: FnOnce(Result<()>) + Send + 'static>(
+    &self,
+    types: tauri_runtime::webview::DataTypes,
+    f: F,
+  ) -> Result<()> {
     send_user_message(
       &self.context,
       Message::Webview(
         *self.window_id.lock().unwrap(),
         self.webview_id,
-        WebviewMessage::Hide,
+        WebviewMessage::ClearBrowsingData(types, Box::new(f)),
       ),
     )
   }
 
-  fn show(&self) -> Result<()> {
+  fn cookies_for_url(&self, url: Url) -> Result<Vec<tauri_runtime::webview::Cookie<'static>>> {
+    let (tx, rx) = channel();
     send_user_message(
       &self.context,
       Message::Webview(
         *self.window_id.lock().unwrap(),
         self.webview_id,
-        WebviewMessage::Show,
+        WebviewMessage::CookiesForUrl(url, tx),
       ),
-    )
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
   }
 
-  fn set_background_color(&self, color: Option<Color>) -> Result<()> {
+  fn delete_cookie<F: FnOnce(Result<()>) + Send + 'static>(
+    &self,
+    name: String,
+    url: Url,
+    f: F,
+  ) -> Result<()> {
     send_user_message(
       &self.context,
       Message::Webview(
         *self.window_id.lock().unwrap(),
         self.webview_id,
-        WebviewMessage::SetBackgroundColor(color),
+        WebviewMessage::DeleteCookie(name, url, Box::new(f)),
       ),
     )
   }
-}
 
-/// The Tauri [`WindowDispatch`] for [`Wry`].
-#[derive(Debug, Clone)]
-pub struct WryWindowDispatcher<T: UserEvent> {
-  window_id: WindowId,
-  context: Context<T>,
-}
+  fn cookies(&self) -> Result<Vec<tauri_runtime::webview::Cookie<'static>>> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Cookies(tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
 
-// SAFETY: this is safe since the `Context` usage is guarded on `send_user_message`.
-#[allow(clippy::non_send_fields_in_send_ty)]
-unsafe impl<T: UserEvent> Sync for WryWindowDispatcher<T> {}
+  fn set_cookie<F: FnOnce(Result<()>) + Send + 'static>(
+    &self,
+    cookie: tauri_runtime::webview::Cookie<'static>,
+    f: F,
+  ) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetCookie(cookie, Box::new(f)),
+      ),
+    )
+  }
 
-fn get_raw_window_handle<T: UserEvent>(
+  fn hide(&self) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Hide,
+      ),
+    )
+  }
+
+  fn show(&self) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::Show,
+      ),
+    )
+  }
+
+  fn set_background_color(&self, color: Option<Color>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Webview(
+        *self.window_id.lock().unwrap(),
+        self.webview_id,
+        WebviewMessage::SetBackgroundColor(color),
+      ),
+    )
+  }
+}
+
+/// The Tauri [`WindowDispatch`] for [`Wry`].
+#[derive(Debug, Clone)]
+pub struct WryWindowDispatcher<T: UserEvent> {
+  window_id: WindowId,
+  context: Context<T>,
+}
+
+// SAFETY: this is safe since the `Context` usage is guarded on `send_user_message`.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<T: UserEvent> Sync for WryWindowDispatcher<T> {}
+
+fn get_raw_window_handle<T: UserEvent>(
   dispatcher: &WryWindowDispatcher<T>,
 ) -> Result<std::result::Result<SendRawWindowHandle, raw_window_handle::HandleError>> {
   window_getter!(dispatcher, WindowMessage::RawWindowHandle)
@@ -1699,6 +2125,10 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     window_getter!(self, WindowMessage::OuterSize)
   }
 
+  fn size_constraints(&self) -> Result<WindowSizeConstraints> {
+    window_getter!(self, WindowMessage::SizeConstraints)
+  }
+
   fn is_fullscreen(&self) -> Result<bool> {
     window_getter!(self, WindowMessage::IsFullscreen)
   }
@@ -1784,10 +2214,23 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     window_getter!(self, WindowMessage::Theme)
   }
 
+  fn title_bar_rects(&self) -> Result<Vec<tauri_runtime::Rect>> {
+    window_getter!(self, WindowMessage::TitleBarRects)
+  }
+
   fn is_enabled(&self) -> Result<bool> {
     window_getter!(self, WindowMessage::IsEnabled)
   }
 
+  fn confirm(&self, title: String, message: String) -> Result<bool> {
+    let (tx, rx) = channel();
+    getter!(
+      self,
+      rx,
+      Message::Window(self.window_id, WindowMessage::Confirm(title, message, tx))
+    )?
+  }
+
   #[cfg(any(
     target_os = "linux",
     target_os = "dragonfly",
@@ -2040,6 +2483,13 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_aspect_ratio(&self, ratio: Option<f64>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(self.window_id, WindowMessage::SetAspectRatio(ratio)),
+    )
+  }
+
   fn set_position(&self, position: Position) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2054,6 +2504,18 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_fullscreen_on_monitor(&self, monitor: Monitor) -> Result<()> {
+    let (tx, rx) = channel();
+    send_user_message(
+      &self.context,
+      Message::Window(
+        self.window_id,
+        WindowMessage::SetFullscreenOnMonitor(monitor, tx),
+      ),
+    )?;
+    rx.recv().map_err(|_| Error::FailedToReceiveMessage)?
+  }
+
   fn set_focus(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2116,6 +2578,19 @@ impl<T: UserEvent> WindowDispatch<T> for WryWindowDispatcher<T> {
     )
   }
 
+  fn set_ignore_cursor_events_region(
+    &self,
+    regions: Option<Vec<tauri_runtime::Rect>>,
+  ) -> crate::Result<()> {
+    send_user_message(
+      &self.context,
+      Message::Window(
+        self.window_id,
+        WindowMessage::SetIgnoreCursorEventsRegion(regions),
+      ),
+    )
+  }
+
   fn start_dragging(&self) -> Result<()> {
     send_user_message(
       &self.context,
@@ -2198,6 +2673,12 @@ pub struct WebviewWrapper {
   // the key of the WebContext if it's not shared
   context_key: Option<PathBuf>,
   bounds: Arc<Mutex<Option<WebviewBounds>>>,
+  initialization_scripts: Rc<initialization_script::InitializationScripts>,
+  zoom: Rc<zoom::ZoomState>,
+  find: Rc<find::FindState>,
+  history: Rc<history::HistoryState>,
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  devtools: Rc<devtools::DevtoolsState>,
 }
 
 impl Deref for WebviewWrapper {
@@ -2239,6 +2720,41 @@ pub struct WindowWrapper {
   is_window_transparent: bool,
   #[cfg(windows)]
   surface: Option<softbuffer::Surface<Arc<Window>, Arc<Window>>>,
+  #[cfg(target_os = "macos")]
+  traffic_light_position: Option<Position>,
+  // the last reported title bar rects
+  title_bar_rects: Mutex<Vec<tauri_runtime::Rect>>,
+  // the interactive regions when the other cursor events are ignored
+  cursor_region: Option<cursor_region::CursorRegion>,
+  // whether all the cursor events are ignored, which takes precedence over the regions
+  ignore_cursor_events: bool,
+  // the monitor the window was made fullscreen on, to detect its disconnection
+  fullscreen_monitor: Option<MonitorHandle>,
+  // the inner size constraints, which tao does not expose
+  size_constraints: WindowSizeConstraints,
+  // the width to height ratio the inner size is locked to
+  aspect_ratio: Option<f64>,
+  // the taskbar overlay icon and badge
+  #[cfg(windows)]
+  overlay: badge::Overlay,
+  // whether an undecorated window has a shadow
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  shadow: bool,
+  // the theme set on the window and the last one reported to it
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  theme: system_theme::WindowTheme,
 }
 
 impl fmt::Debug for WindowWrapper {
@@ -2287,6 +2803,8 @@ pub trait Plugin<T: UserEvent> {
 pub struct Wry<T: UserEvent> {
   context: Context<T>,
   event_loop: EventLoop<Message<T>>,
+  // watches the system theme as long as the runtime lives
+  _system_theme: system_theme::Watcher,
 }
 
 impl<T: UserEvent> fmt::Debug for Wry<T> {
@@ -2456,6 +2974,21 @@ impl<T: UserEvent> RuntimeHandle<T> for WryHandle<T> {
       });
   }
 
+  fn system_theme(&self) -> Theme {
+    system_theme::current()
+  }
+
+  fn set_badge_count(&self, count: Option<u64>, desktop_filename: Option<String>) -> Result<()> {
+    send_user_message(
+      &self.context,
+      Message::SetBadgeCount(count, desktop_filename),
+    )
+  }
+
+  fn set_badge_label(&self, label: Option<String>) -> Result<()> {
+    send_user_message(&self.context, Message::SetBadgeLabel(label))
+  }
+
   #[cfg(target_os = "macos")]
   fn show(&self) -> tauri_runtime::Result<()> {
     send_user_message(
@@ -2523,6 +3056,11 @@ impl<T: UserEvent> Wry<T> {
     let windows = Arc::new(WindowsStore(RefCell::new(BTreeMap::default())));
     let window_id_map = WindowIdStore::default();
 
+    let proxy = event_loop.create_proxy();
+    let system_theme = system_theme::watch(move |theme| {
+      let _ = proxy.send_event(Message::SystemThemeChanged(theme));
+    });
+
     let context = Context {
       window_id_map,
       main_thread_id,
@@ -2544,6 +3082,7 @@ impl<T: UserEvent> Wry<T> {
     Ok(Self {
       context,
       event_loop,
+      _system_theme: system_theme,
     })
   }
 }
@@ -2751,6 +3290,10 @@ impl<T: UserEvent> Runtime<T> for Wry<T> {
     });
   }
 
+  fn system_theme(&self) -> Theme {
+    system_theme::current()
+  }
+
   #[cfg(target_os = "macos")]
   fn set_activation_policy(&mut self, activation_policy: ActivationPolicy) {
     self
@@ -2953,11 +3496,33 @@ fn handle_user_message<T: UserEvent>(
           WindowMessage::OuterSize(tx) => tx
             .send(PhysicalSizeWrapper(window.outer_size()).into())
             .unwrap(),
+          WindowMessage::SizeConstraints(tx) => {
+            let constraints = windows.0.borrow().get(&id).map(|w| w.size_constraints);
+            tx.send(constraints.unwrap_or_default()).unwrap()
+          }
           WindowMessage::IsFullscreen(tx) => tx.send(window.fullscreen().is_some()).unwrap(),
           WindowMessage::IsMinimized(tx) => tx.send(window.is_minimized()).unwrap(),
           WindowMessage::IsMaximized(tx) => tx.send(window.is_maximized()).unwrap(),
           WindowMessage::IsFocused(tx) => tx.send(window.is_focused()).unwrap(),
-          WindowMessage::IsDecorated(tx) => tx.send(window.is_decorated()).unwrap(),
+          WindowMessage::IsDecorated(tx) => {
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            let decorated = undecorated_shadow::is_decorated(&window);
+            #[cfg(not(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            )))]
+            let decorated = window.is_decorated();
+            tx.send(decorated).unwrap()
+          }
           WindowMessage::IsResizable(tx) => tx.send(window.is_resizable()).unwrap(),
           WindowMessage::IsMaximizable(tx) => tx.send(window.is_maximizable()).unwrap(),
           WindowMessage::IsMinimizable(tx) => tx.send(window.is_minimizable()).unwrap(),
@@ -3000,7 +3565,11 @@ fn handle_user_message<T: UserEvent>(
           WindowMessage::Theme(tx) => {
             tx.send(map_theme(&window.theme())).unwrap();
           }
+          WindowMessage::TitleBarRects(tx) => tx.send(window.title_bar_rects()).unwrap(),
           WindowMessage::IsEnabled(tx) => tx.send(window.is_enabled()).unwrap(),
+          WindowMessage::Confirm(title, message, tx) => {
+            tx.send(dialog::confirm(&window, &title, &message)).unwrap()
+          }
 
           // Setters
           WindowMessage::Center => window.center(),
@@ -3041,12 +3610,37 @@ fn handle_user_message<T: UserEvent>(
             } else if window.is_resizable() {
               undecorated_resizing::attach_resize_handler(window.hwnd());
             }
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            {
+              let shadow = windows.0.borrow().get(&id).is_some_and(|w| w.shadow);
+              undecorated_shadow::update(&window, decorations, shadow);
+            }
           }
           WindowMessage::SetShadow(_enable) => {
             #[cfg(windows)]
             window.set_undecorated_shadow(_enable);
             #[cfg(target_os = "macos")]
             window.set_has_shadow(_enable);
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            {
+              if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+                w.shadow = _enable;
+              }
+              let decorated = undecorated_shadow::is_decorated(&window);
+              undecorated_shadow::update(&window, decorated, _enable);
+            }
           }
           WindowMessage::SetAlwaysOnBottom(always_on_bottom) => {
             window.set_always_on_bottom(always_on_bottom)
@@ -3060,29 +3654,77 @@ fn handle_user_message<T: UserEvent>(
             window.set_inner_size(SizeWrapper::from(size).0);
           }
           WindowMessage::SetMinSize(size) => {
-            window.set_min_inner_size(size.map(|s| SizeWrapper::from(s).0));
+            let (width, height) = size.map(size_units).unzip();
+            let constraints = windows.0.borrow_mut().get_mut(&id).map(|w| {
+              w.size_constraints.min_width = width;
+              w.size_constraints.min_height = height;
+              (w.size_constraints, w.aspect_ratio)
+            });
+            if let Some((constraints, aspect_ratio)) = constraints {
+              apply_size_constraints(&window, &constraints, aspect_ratio, false);
+            }
           }
           WindowMessage::SetMaxSize(size) => {
-            window.set_max_inner_size(size.map(|s| SizeWrapper::from(s).0));
+            let (width, height) = size.map(size_units).unzip();
+            let constraints = windows.0.borrow_mut().get_mut(&id).map(|w| {
+              w.size_constraints.max_width = width;
+              w.size_constraints.max_height = height;
+              (w.size_constraints, w.aspect_ratio)
+            });
+            if let Some((constraints, aspect_ratio)) = constraints {
+              apply_size_constraints(&window, &constraints, aspect_ratio, false);
+            }
           }
           WindowMessage::SetSizeConstraints(constraints) => {
-            window.set_inner_size_constraints(tao::window::WindowSizeConstraints {
-              min_width: constraints.min_width,
-              min_height: constraints.min_height,
-              max_width: constraints.max_width,
-              max_height: constraints.max_height,
+            let aspect_ratio = windows.0.borrow_mut().get_mut(&id).map(|w| {
+              w.size_constraints = constraints;
+              w.aspect_ratio
             });
+            if let Some(aspect_ratio) = aspect_ratio {
+              apply_size_constraints(&window, &constraints, aspect_ratio, false);
+            }
+          }
+          WindowMessage::SetAspectRatio(ratio) => {
+            let constraints = windows.0.borrow_mut().get_mut(&id).map(|w| {
+              w.aspect_ratio = ratio;
+              w.size_constraints
+            });
+            if let Some(constraints) = constraints {
+              apply_size_constraints(&window, &constraints, ratio, true);
+            }
           }
           WindowMessage::SetPosition(position) => {
             window.set_outer_position(PositionWrapper::from(position).0)
           }
           WindowMessage::SetFullscreen(fullscreen) => {
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.fullscreen_monitor = None;
+            }
             if fullscreen {
               window.set_fullscreen(Some(Fullscreen::Borderless(None)))
             } else {
               window.set_fullscreen(None)
             }
           }
+          WindowMessage::SetFullscreenOnMonitor(monitor, tx) => {
+            let handle = window.available_monitors().find(|handle| {
+              handle.name() == monitor.name
+                && PhysicalPosition::from(PhysicalPositionWrapper(handle.position()))
+                  == monitor.position
+            });
+            let result = match handle {
+              Some(handle) => {
+                // tao moves the window to the monitor as part of the fullscreen transition
+                window.set_fullscreen(Some(Fullscreen::Borderless(Some(handle.clone()))));
+                if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+                  w.fullscreen_monitor = Some(handle);
+                }
+                Ok(())
+              }
+              None => Err(Error::FailedToGetMonitor),
+            };
+            tx.send(result).unwrap();
+          }
           WindowMessage::SetFocus => {
             window.set_focus();
           }
@@ -3107,7 +3749,16 @@ fn handle_user_message<T: UserEvent>(
             let _ = window.set_cursor_position(PositionWrapper::from(position).0);
           }
           WindowMessage::SetIgnoreCursorEvents(ignore) => {
-            let _ = window.set_ignore_cursor_events(ignore);
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.ignore_cursor_events = ignore;
+              cursor_region::set_ignore(&window, w.cursor_region.as_ref(), ignore);
+            }
+          }
+          WindowMessage::SetIgnoreCursorEventsRegion(regions) => {
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              let ignore = w.ignore_cursor_events;
+              cursor_region::set(&window, &mut w.cursor_region, regions, ignore);
+            }
           }
           WindowMessage::DragWindow => {
             let _ = window.drag_window();
@@ -3128,13 +3779,19 @@ fn handle_user_message<T: UserEvent>(
             window.request_redraw();
           }
           WindowMessage::SetBadgeCount(_count, _desktop_filename) => {
+            #[cfg(windows)]
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.overlay.label = badge::count_label(badge::window_count(_count));
+              w.overlay.apply(&window);
+            }
+
             #[cfg(target_os = "ios")]
             window.set_badge_count(
               _count.map_or(0, |x| x.clamp(i32::MIN as i64, i32::MAX as i64) as i32),
             );
 
             #[cfg(target_os = "macos")]
-            window.set_badge_label(_count.map(|x| x.to_string()));
+            window.set_badge_label(badge::count_label(badge::window_count(_count)));
 
             #[cfg(any(
               target_os = "linux",
@@ -3146,12 +3803,21 @@ fn handle_user_message<T: UserEvent>(
             window.set_badge_count(_count, _desktop_filename);
           }
           WindowMessage::SetBadgeLabel(_label) => {
+            #[cfg(windows)]
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.overlay.label = _label;
+              w.overlay.apply(&window);
+            }
+
             #[cfg(target_os = "macos")]
             window.set_badge_label(_label);
           }
           WindowMessage::SetOverlayIcon(_icon) => {
             #[cfg(windows)]
-            window.set_overlay_icon(_icon.map(|x| x.0).as_ref());
+            if let Some(w) = windows.0.borrow_mut().get_mut(&id) {
+              w.overlay.icon = _icon.map(|x| x.0);
+              w.overlay.apply(&window);
+            }
           }
           WindowMessage::SetProgressBar(progress_state) => {
             window.set_progress_bar(ProgressBarStateWrapper::from(progress_state).0);
@@ -3181,6 +3847,16 @@ fn handle_user_message<T: UserEvent>(
             };
           }
           WindowMessage::SetTheme(theme) => {
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            if let Some(w) = windows.0.borrow().get(&id) {
+              w.theme.set_fixed(theme);
+            }
             window.set_theme(match theme {
               Some(Theme::Light) => Some(TaoTheme::Light),
               Some(Theme::Dark) => Some(TaoTheme::Dark),
@@ -3203,7 +3879,7 @@ fn handle_user_message<T: UserEvent>(
         target_os = "netbsd",
         target_os = "openbsd"
       ))]
-      if let WebviewMessage::Reparent(new_parent_window_id, tx) = webview_message {
+      if let WebviewMessage::Reparent(new_parent_window_id, bounds, tx) = webview_message {
         let webview_handle = windows.0.borrow_mut().get_mut(&window_id).and_then(|w| {
           w.webviews
             .iter()
@@ -3211,13 +3887,19 @@ fn handle_user_message<T: UserEvent>(
             .map(|webview_index| w.webviews.remove(webview_index))
         });
 
-        if let Some(webview) = webview_handle {
-          if let Some((Some(new_parent_window), new_parent_window_webviews)) = windows
-            .0
-            .borrow_mut()
-            .get_mut(&new_parent_window_id)
-            .map(|w| (w.inner.clone(), &mut w.webviews))
-          {
+        let Some(webview) = webview_handle else {
+          tx.send(Err(Error::Reparent("webview not found".into()))).unwrap();
+          return;
+        };
+
+        let new_parent_window = windows
+          .0
+          .borrow()
+          .get(&new_parent_window_id)
+          .and_then(|w| w.inner.clone());
+
+        let reparent_result = match &new_parent_window {
+          Some(new_parent_window) => {
             #[cfg(target_os = "macos")]
             let reparent_result = {
               use wry::WebViewExtMacOS;
@@ -3241,21 +3923,54 @@ fn handle_user_message<T: UserEvent>(
               }
             };
 
-            match reparent_result {
-              Ok(_) => {
-                new_parent_window_webviews.push(webview);
-                tx.send(Ok(())).unwrap();
+            reparent_result.map_err(|e| Error::Reparent(e.to_string()))
+          }
+          None => Err(Error::WindowNotFound),
+        };
+
+        // the webview goes back to its window if it could not be moved
+        let (parent_window_id, result) = match (reparent_result, new_parent_window) {
+          (Ok(()), Some(new_parent_window)) => {
+            let scale_factor = new_parent_window.scale_factor();
+            let window_size = new_parent_window.inner_size().to_logical(scale_factor);
+            let mut webview_bounds = webview.bounds.lock().unwrap();
+
+            // layouts are resolved against the size of the new window
+            let bounds = match (bounds, &mut *webview_bounds) {
+              (Some(bounds), b) => {
+                let bounds = RectWrapper::from(bounds).0;
+                if let Some(b) = b {
+                  b.update(
+                    bounds.position.to_logical(scale_factor),
+                    bounds.size.to_logical(scale_factor),
+                    window_size,
+                  );
+                }
+                Some(bounds)
               }
-              Err(e) => {
-                log::error!("failed to reparent webview: {e}");
-                tx.send(Err(Error::FailedToSendMessage)).unwrap();
+              (None, Some(b)) => Some(b.resolve(window_size)),
+              (None, None) => None,
+            };
+
+            drop(webview_bounds);
+
+            if let Some(bounds) = bounds {
+              if let Err(e) = webview.set_bounds(bounds) {
+                log::error!("failed to set webview bounds: {e}");
               }
             }
+
+            (new_parent_window_id, Ok(()))
           }
-        } else {
-          tx.send(Err(Error::FailedToSendMessage)).unwrap();
+          (result, _) => (window_id, result),
+        };
+
+        if let Some(w) = windows.0.borrow_mut().get_mut(&parent_window_id) {
+          w.webviews.push(webview);
         }
 
+        tx.send(result).unwrap();
+
         return;
       }
 
@@ -3269,7 +3984,7 @@ fn handle_user_message<T: UserEvent>(
         match webview_message {
           WebviewMessage::WebviewEvent(_) => { /* already handled */ }
           WebviewMessage::SynthesizedWindowEvent(_) => { /* already handled */ }
-          WebviewMessage::Reparent(_window_id, _tx) => { /* already handled */ }
+          WebviewMessage::Reparent(_window_id, _bounds, _tx) => { /* already handled */ }
           WebviewMessage::AddEventListener(id, listener) => {
             webview
               .webview_event_listeners
@@ -3297,6 +4012,16 @@ fn handle_user_message<T: UserEvent>(
               log::error!("failed to navigate to url {}: {}", url, e);
             }
           }
+          WebviewMessage::GoBack(tx) => {
+            tx.send(history::go_back(&webview)).unwrap();
+          }
+          WebviewMessage::GoForward(tx) => {
+            tx.send(history::go_forward(&webview)).unwrap();
+          }
+          WebviewMessage::Reload(ignore_cache, tx) => {
+            tx.send(history::reload(&webview, ignore_cache)).unwrap();
+          }
+          WebviewMessage::SyncHistory => webview.history.update(&webview),
           WebviewMessage::Show => {
             if let Err(e) = webview.set_visible(true) {
               log::error!("failed to change webview visibility: {e}");
@@ -3310,6 +4035,69 @@ fn handle_user_message<T: UserEvent>(
           WebviewMessage::Print => {
             let _ = webview.print();
           }
+          WebviewMessage::Capture(region, f) => {
+            let scale_factor = window.scale_factor();
+            let width = webview
+              .bounds()
+              .map(|bounds| bounds.size.to_logical::<f64>(scale_factor).width)
+              .unwrap_or_else(|_| window.inner_size().to_logical::<f64>(scale_factor).width);
+            capture::capture(&webview, region, scale_factor, width, f);
+          }
+          WebviewMessage::PrintToPdf(options, f) => {
+            pdf::print_to_pdf(&webview, options, f);
+          }
+          WebviewMessage::FindInPage(query, options, f) => {
+            find::find(&webview, &webview.find, query, options, f);
+          }
+          WebviewMessage::StopFind(clear_highlights) => {
+            find::stop(&webview, &webview.find, clear_highlights);
+          }
+          WebviewMessage::ClearBrowsingData(types, f) => {
+            browsing_data::clear(&webview, types, f);
+          }
+          WebviewMessage::CookiesForUrl(url, tx) => {
+            tx.send(
+              webview
+                .cookies_for_url(url.as_str())
+                .map_err(|e| Error::BrowsingData(e.to_string())),
+            )
+            .unwrap();
+          }
+          WebviewMessage::DeleteCookie(name, url, f) => {
+            browsing_data::delete_cookie(&webview, name, url, f);
+          }
+          WebviewMessage::Cookies(tx) => {
+            tx.send(
+              webview
+                .cookies()
+                .map_err(|e| Error::BrowsingData(e.to_string())),
+            )
+            .unwrap();
+          }
+          WebviewMessage::SetCookie(cookie, f) => {
+            let url = webview.url().ok().and_then(|url| url.parse::<Url>().ok());
+            let cookie = browsing_data::with_default_domain(cookie, url.as_ref());
+            browsing_data::set_cookie(&webview, cookie, f);
+          }
+          WebviewMessage::SetProxy(proxy_config, tx) => {
+            tx.send(proxy::set(&webview, proxy_config.as_ref())).unwrap();
+          }
+          WebviewMessage::AddInitializationScript(script, tx) => {
+            tx.send(initialization_script::add(
+              &webview,
+              &webview.initialization_scripts,
+              script,
+            ))
+            .unwrap();
+          }
+          WebviewMessage::RemoveInitializationScript(id, tx) => {
+            tx.send(initialization_script::remove(
+              &webview,
+              &webview.initialization_scripts,
+              id,
+            ))
+            .unwrap();
+          }
           WebviewMessage::Close => {
             #[allow(unknown_lints, clippy::manual_inspect)]
             windows.0.borrow_mut().get_mut(&window_id).map(|window| {
@@ -3325,13 +4113,11 @@ fn handle_user_message<T: UserEvent>(
 
             if let Some(b) = &mut *webview.bounds.lock().unwrap() {
               let scale_factor = window.scale_factor();
-              let size = bounds.size.to_logical::<f32>(scale_factor);
-              let position = bounds.position.to_logical::<f32>(scale_factor);
-              let window_size = window.inner_size().to_logical::<f32>(scale_factor);
-              b.width_rate = size.width / window_size.width;
-              b.height_rate = size.height / window_size.height;
-              b.x_rate = position.x / window_size.width;
-              b.y_rate = position.y / window_size.height;
+              b.update(
+                bounds.position.to_logical(scale_factor),
+                bounds.size.to_logical(scale_factor),
+                window.inner_size().to_logical(scale_factor),
+              );
             }
 
             if let Err(e) = webview.set_bounds(bounds) {
@@ -3342,13 +4128,13 @@ fn handle_user_message<T: UserEvent>(
             Ok(mut bounds) => {
               bounds.size = size;
 
-              let scale_factor = window.scale_factor();
-              let size = size.to_logical::<f32>(scale_factor);
-
               if let Some(b) = &mut *webview.bounds.lock().unwrap() {
-                let window_size = window.inner_size().to_logical::<f32>(scale_factor);
-                b.width_rate = size.width / window_size.width;
-                b.height_rate = size.height / window_size.height;
+                let scale_factor = window.scale_factor();
+                b.update(
+                  bounds.position.to_logical(scale_factor),
+                  bounds.size.to_logical(scale_factor),
+                  window.inner_size().to_logical(scale_factor),
+                );
               }
 
               if let Err(e) = webview.set_bounds(bounds) {
@@ -3363,13 +4149,13 @@ fn handle_user_message<T: UserEvent>(
             Ok(mut bounds) => {
               bounds.position = position;
 
-              let scale_factor = window.scale_factor();
-              let position = position.to_logical::<f32>(scale_factor);
-
               if let Some(b) = &mut *webview.bounds.lock().unwrap() {
-                let window_size = window.inner_size().to_logical::<f32>(scale_factor);
-                b.x_rate = position.x / window_size.width;
-                b.y_rate = position.y / window_size.height;
+                let scale_factor = window.scale_factor();
+                b.update(
+                  bounds.position.to_logical(scale_factor),
+                  bounds.size.to_logical(scale_factor),
+                  window.inner_size().to_logical(scale_factor),
+                );
               }
 
               if let Err(e) = webview.set_bounds(bounds) {
@@ -3380,9 +4166,17 @@ fn handle_user_message<T: UserEvent>(
               log::error!("failed to get webview bounds: {e}");
             }
           },
-          WebviewMessage::SetZoom(scale_factor) => {
-            if let Err(e) = webview.zoom(scale_factor) {
-              log::error!("failed to set webview zoom: {e}");
+          WebviewMessage::SetZoom(scale_factor) => match webview.zoom(scale_factor) {
+            Ok(()) => webview.zoom.update(scale_factor),
+            Err(e) => log::error!("failed to set webview zoom: {e}"),
+          },
+          WebviewMessage::SyncZoom(scale_factor) => webview.zoom.update(scale_factor),
+          WebviewMessage::SetUserAgent(user_agent, tx) => {
+            tx.send(user_agent::set(&webview, &user_agent)).unwrap();
+          }
+          WebviewMessage::RestoreZoom => {
+            if let Err(e) = webview.zoom(webview.zoom.factor()) {
+              log::error!("failed to restore webview zoom: {e}");
             }
           }
           WebviewMessage::SetBackgroundColor(color) => {
@@ -3428,6 +4222,21 @@ fn handle_user_message<T: UserEvent>(
             )
             .unwrap();
           }
+          WebviewMessage::Zoom(tx) => {
+            tx.send(webview.zoom.factor()).unwrap();
+          }
+          WebviewMessage::UserAgent(tx) => {
+            tx.send(user_agent::get(&webview)).unwrap();
+          }
+          WebviewMessage::MemoryUsage(tx) => {
+            tx.send(memory::memory_usage(&webview)).unwrap();
+          }
+          WebviewMessage::CanGoBack(tx) => {
+            tx.send(history::can_go_back(&webview)).unwrap();
+          }
+          WebviewMessage::CanGoForward(tx) => {
+            tx.send(history::can_go_forward(&webview)).unwrap();
+          }
           WebviewMessage::Size(tx) => {
             tx.send(
               webview
@@ -3442,22 +4251,27 @@ fn handle_user_message<T: UserEvent>(
               log::error!("failed to focus webview: {e}");
             }
           }
-          WebviewMessage::SetAutoResize(auto_resize) => match webview.bounds() {
+          WebviewMessage::SetLayout(layout) => match webview.bounds() {
             Ok(bounds) => {
               let scale_factor = window.scale_factor();
               let window_size = window.inner_size().to_logical::<f32>(scale_factor);
-              *webview.bounds.lock().unwrap() = if auto_resize {
-                let size = bounds.size.to_logical::<f32>(scale_factor);
-                let position = bounds.position.to_logical::<f32>(scale_factor);
-                Some(WebviewBounds {
-                  x_rate: position.x / window_size.width,
-                  y_rate: position.y / window_size.height,
-                  width_rate: size.width / window_size.width,
-                  height_rate: size.height / window_size.height,
-                })
-              } else {
-                None
-              };
+              let webview_bounds = layout.map(|layout| {
+                WebviewBounds::new(
+                  layout,
+                  bounds.position.to_logical(scale_factor),
+                  bounds.size.to_logical(scale_factor),
+                  window_size,
+                )
+              });
+
+              // docked webviews move to their side right away
+              if let Some(webview_bounds) = &webview_bounds {
+                if let Err(e) = webview.set_bounds(webview_bounds.resolve(window_size)) {
+                  log::error!("failed to set webview bounds: {e}");
+                }
+              }
+
+              *webview.bounds.lock().unwrap() = webview_bounds;
             }
             Err(e) => {
               log::error!("failed to get webview bounds: {e}");
@@ -3512,16 +4326,16 @@ fn handle_user_message<T: UserEvent>(
             }
           }
           #[cfg(any(debug_assertions, feature = "devtools"))]
-          WebviewMessage::OpenDevTools => {
-            webview.open_devtools();
+          WebviewMessage::OpenDevTools(options) => {
+            webview.devtools.open(&webview, options);
           }
           #[cfg(any(debug_assertions, feature = "devtools"))]
           WebviewMessage::CloseDevTools => {
-            webview.close_devtools();
+            webview.devtools.close(&webview);
           }
           #[cfg(any(debug_assertions, feature = "devtools"))]
           WebviewMessage::IsDevToolsOpen(tx) => {
-            tx.send(webview.is_devtools_open()).unwrap();
+            tx.send(webview.devtools.is_open()).unwrap();
           }
         }
       }
@@ -3585,6 +4399,12 @@ fn handle_user_message<T: UserEvent>(
           None
         };
 
+        // new windows show the app badge too
+        #[cfg(windows)]
+        let overlay = badge::Overlay::default();
+        #[cfg(windows)]
+        overlay.apply(&window);
+
         windows.0.borrow_mut().insert(
           window_id,
           WindowWrapper {
@@ -3599,22 +4419,92 @@ fn handle_user_message<T: UserEvent>(
             is_window_transparent,
             #[cfg(windows)]
             surface,
-          },
-        );
-        sender.send(Ok(Arc::downgrade(&window))).unwrap();
-      } else {
-        sender.send(Err(Error::CreateWindow)).unwrap();
-      }
-    }
-
-    Message::UserEvent(_) => (),
-    Message::EventLoopWindowTarget(message) => match message {
-      EventLoopWindowTargetMessage::CursorPosition(sender) => {
-        let pos = event_loop
-          .cursor_position()
-          .map_err(|_| Error::FailedToSendMessage);
-        sender.send(pos).unwrap();
-      }
+            #[cfg(target_os = "macos")]
+            traffic_light_position: None,
+            title_bar_rects: Mutex::new(window.title_bar_rects()),
+            cursor_region: None,
+            ignore_cursor_events: false,
+            fullscreen_monitor: None,
+            size_constraints: WindowSizeConstraints::default(),
+            aspect_ratio: None,
+            #[cfg(windows)]
+            overlay,
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            shadow: false,
+            #[cfg(any(
+              target_os = "linux",
+              target_os = "dragonfly",
+              target_os = "freebsd",
+              target_os = "netbsd",
+              target_os = "openbsd"
+            ))]
+            theme: Default::default(),
+          },
+        );
+        sender.send(Ok(Arc::downgrade(&window))).unwrap();
+      } else {
+        sender.send(Err(Error::CreateWindow)).unwrap();
+      }
+    }
+
+    Message::UserEvent(_) | Message::SystemThemeChanged(_) => (),
+    Message::SetBadgeCount(_count, _desktop_filename) => {
+      #[cfg(windows)]
+      {
+        badge::set_app_label(badge::count_label(_count));
+
+        for w in windows.0.borrow().values() {
+          if let Some(window) = &w.inner {
+            w.overlay.apply(window);
+          }
+        }
+      }
+
+      #[cfg(target_os = "ios")]
+      if let Some(window) = windows.0.borrow().values().find_map(|w| w.inner.clone()) {
+        window.set_badge_count(_count.map_or(0, |x| x.min(i32::MAX as u64) as i32));
+      }
+
+      #[cfg(target_os = "macos")]
+      badge::set_dock_label(badge::count_label(_count));
+
+      #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      ))]
+      badge::set_launcher_count(_count, _desktop_filename);
+    }
+    Message::SetBadgeLabel(_label) => {
+      #[cfg(windows)]
+      {
+        badge::set_app_label(_label);
+
+        for w in windows.0.borrow().values() {
+          if let Some(window) = &w.inner {
+            w.overlay.apply(window);
+          }
+        }
+      }
+
+      #[cfg(target_os = "macos")]
+      badge::set_dock_label(_label);
+    }
+    Message::EventLoopWindowTarget(message) => match message {
+      EventLoopWindowTargetMessage::CursorPosition(sender) => {
+        let pos = event_loop
+          .cursor_position()
+          .map_err(|_| Error::FailedToSendMessage);
+        sender.send(pos).unwrap();
+      }
     },
   }
 }
@@ -3755,6 +4645,25 @@ fn handle_event_loop<T: UserEvent>(
           }
         }
 
+        if matches!(
+          event,
+          TaoWindowEvent::Resized(_)
+            | TaoWindowEvent::ScaleFactorChanged { .. }
+            | TaoWindowEvent::ThemeChanged(_)
+        ) {
+          on_title_bar_changed(callback, window_id, windows.clone());
+        }
+
+        // the system moves the windows off a disconnected monitor
+        if matches!(
+          event,
+          TaoWindowEvent::Moved(_)
+            | TaoWindowEvent::Resized(_)
+            | TaoWindowEvent::ScaleFactorChanged { .. }
+        ) {
+          on_fullscreen_monitor_changed(callback, window_id, windows.clone());
+        }
+
         match event {
           #[cfg(windows)]
           TaoWindowEvent::ThemeChanged(theme) => {
@@ -3771,6 +4680,18 @@ fn handle_event_loop<T: UserEvent>(
               }
             }
           }
+          #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+          ))]
+          TaoWindowEvent::ThemeChanged(theme) => {
+            if let Some(window) = windows.0.borrow().get(&window_id) {
+              window.theme.reported(map_theme(theme));
+            }
+          }
           TaoWindowEvent::CloseRequested => {
             on_close_requested(callback, window_id, windows);
           }
@@ -3801,12 +4722,7 @@ fn handle_event_loop<T: UserEvent>(
               let size = size.to_logical::<f32>(window.scale_factor());
               for webview in webviews {
                 if let Some(b) = &*webview.bounds.lock().unwrap() {
-                  if let Err(e) = webview.set_bounds(wry::Rect {
-                    position: LogicalPosition::new(size.width * b.x_rate, size.height * b.y_rate)
-                      .into(),
-                    size: LogicalSize::new(size.width * b.width_rate, size.height * b.height_rate)
-                      .into(),
-                  }) {
+                  if let Err(e) = webview.set_bounds(b.resolve(size)) {
                     log::error!("failed to autoresize webview: {e}");
                   }
                 }
@@ -3839,6 +4755,19 @@ fn handle_event_loop<T: UserEvent>(
         on_window_close(id, windows);
       }
       Message::UserEvent(t) => callback(RunEvent::UserEvent(t)),
+      Message::SystemThemeChanged(theme) => {
+        callback(RunEvent::ThemeChanged(theme));
+
+        // tao doesn't report the changes made through the XDG desktop portal
+        #[cfg(any(
+          target_os = "linux",
+          target_os = "dragonfly",
+          target_os = "freebsd",
+          target_os = "netbsd",
+          target_os = "openbsd"
+        ))]
+        on_system_theme_changed(callback, theme, windows);
+      }
       message => {
         handle_user_message(
           event_loop,
@@ -3865,6 +4794,40 @@ fn handle_event_loop<T: UserEvent>(
   }
 }
 
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn on_system_theme_changed<T: UserEvent>(
+  callback: &mut (dyn FnMut(RunEvent<T>) + 'static),
+  theme: Theme,
+  windows: Arc<WindowsStore>,
+) {
+  let windows = windows
+    .0
+    .borrow()
+    .values()
+    .filter(|window| window.inner.is_some() && window.theme.follow(theme))
+    .map(|window| (window.label.clone(), window.window_event_listeners.clone()))
+    .collect::<Vec<_>>();
+
+  for (label, window_event_listeners) in windows {
+    let event = WindowEvent::ThemeChanged(theme);
+
+    callback(RunEvent::WindowEvent {
+      label,
+      event: event.clone(),
+    });
+
+    for handler in window_event_listeners.lock().unwrap().values() {
+      handler(&event);
+    }
+  }
+}
+
 fn on_close_requested<'a, T: UserEvent>(
   callback: &'a mut (dyn FnMut(RunEvent<T>) + 'static),
   window_id: WindowId,
@@ -3896,6 +4859,171 @@ fn on_close_requested<'a, T: UserEvent>(
   }
 }
 
+// moves the traffic lights back after the system laid out the title bar again,
+// and reports the title bar rects when they changed
+fn on_title_bar_changed<T: UserEvent>(
+  callback: &mut (dyn FnMut(RunEvent<T>) + 'static),
+  window_id: WindowId,
+  windows: Arc<WindowsStore>,
+) {
+  let (label, window_event_listeners, rects) = {
+    let windows_ref = windows.0.borrow();
+    let Some(window) = windows_ref.get(&window_id) else {
+      return;
+    };
+    let Some(w) = &window.inner else {
+      return;
+    };
+
+    #[cfg(target_os = "macos")]
+    if let Some(position) = window.traffic_light_position {
+      w.set_traffic_light_position(position);
+    }
+
+    let rects = w.title_bar_rects();
+    let mut last_rects = window.title_bar_rects.lock().unwrap();
+    if *last_rects == rects {
+      return;
+    }
+    last_rects.clone_from(&rects);
+
+    (
+      window.label.clone(),
+      window.window_event_listeners.clone(),
+      rects,
+    )
+  };
+
+  let event = WindowEvent::TitleBarRectsChanged(rects);
+
+  callback(RunEvent::WindowEvent {
+    label,
+    event: event.clone(),
+  });
+
+  let listeners = window_event_listeners.lock().unwrap();
+  let handlers = listeners.values();
+  for handler in handlers {
+    handler(&event);
+  }
+}
+
+fn size_units(size: Size) -> (PixelUnit, PixelUnit) {
+  match size {
+    Size::Physical(size) => (
+      PhysicalUnit::new(size.width as i32).into(),
+      PhysicalUnit::new(size.height as i32).into(),
+    ),
+    Size::Logical(size) => (
+      LogicalUnit::new(size.width).into(),
+      LogicalUnit::new(size.height).into(),
+    ),
+  }
+}
+
+/// Applies the inner size `constraints` and `aspect_ratio` to `window`, resizing it when its
+/// current size does not satisfy the constraints, or to the aspect ratio when `lock` is set.
+fn apply_size_constraints(
+  window: &Window,
+  constraints: &WindowSizeConstraints,
+  aspect_ratio: Option<f64>,
+  lock: bool,
+) {
+  let tao_constraints = tao::window::WindowSizeConstraints {
+    min_width: constraints.min_width,
+    min_height: constraints.min_height,
+    max_width: constraints.max_width,
+    max_height: constraints.max_height,
+  };
+
+  let size = window.inner_size();
+  let clamped = aspect_ratio::constrained_size(
+    size,
+    &tao_constraints,
+    aspect_ratio.filter(|_| lock),
+    window.scale_factor(),
+  );
+
+  window.set_inner_size_constraints(tao_constraints);
+  aspect_ratio::set(window, constraints, aspect_ratio);
+
+  // tao emits the `Resized` event of the new size
+  if clamped != size {
+    window.set_inner_size(clamped);
+  }
+}
+
+/// How the fullscreen monitor of a window changed.
+#[derive(Debug, PartialEq, Eq)]
+enum FullscreenMonitorChange {
+  /// The window is still fullscreen on its monitor.
+  Unchanged,
+  /// The window is no longer fullscreen.
+  Left,
+  /// The monitor of the fullscreen window was disconnected.
+  Disconnected,
+}
+
+fn fullscreen_monitor_change(is_fullscreen: bool, is_connected: bool) -> FullscreenMonitorChange {
+  match (is_fullscreen, is_connected) {
+    (false, _) => FullscreenMonitorChange::Left,
+    (true, true) => FullscreenMonitorChange::Unchanged,
+    (true, false) => FullscreenMonitorChange::Disconnected,
+  }
+}
+
+// makes the window fullscreen on the primary monitor when the monitor it was made fullscreen on
+// is disconnected
+fn on_fullscreen_monitor_changed<T: UserEvent>(
+  callback: &mut (dyn FnMut(RunEvent<T>) + 'static),
+  window_id: WindowId,
+  windows: Arc<WindowsStore>,
+) {
+  let (w, label, window_event_listeners) = {
+    let mut windows_ref = windows.0.borrow_mut();
+    let Some(window) = windows_ref.get_mut(&window_id) else {
+      return;
+    };
+    let (Some(w), Some(monitor)) = (&window.inner, &window.fullscreen_monitor) else {
+      return;
+    };
+    let change = fullscreen_monitor_change(
+      w.fullscreen().is_some(),
+      w.available_monitors().any(|handle| &handle == monitor),
+    );
+    if change == FullscreenMonitorChange::Unchanged {
+      return;
+    }
+    let w = w.clone();
+    window.fullscreen_monitor = None;
+    // the window left the fullscreen, which must not be entered again
+    if change == FullscreenMonitorChange::Left {
+      return;
+    }
+
+    (
+      w,
+      window.label.clone(),
+      window.window_event_listeners.clone(),
+    )
+  };
+
+  w.set_fullscreen(Some(Fullscreen::Borderless(w.primary_monitor())));
+
+  let event = WindowEvent::FullscreenMonitorDisconnected;
+
+  callback(RunEvent::WindowEvent {
+    label,
+    event: event.clone(),
+  });
+
+  let listeners = window_event_listeners.lock().unwrap();
+  let handlers = listeners.values();
+  for handler in handlers {
+    handler(&event);
+  }
+}
+
 fn on_window_close(window_id: WindowId, windows: Arc<WindowsStore>) {
   if let Some(window_wrapper) = windows.0.borrow_mut().get_mut(&window_id) {
     window_wrapper.inner = None;
@@ -3951,6 +5079,9 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
   #[cfg(windows)]
   let is_window_transparent = window_builder.inner.window.transparent;
 
+  #[cfg(target_os = "macos")]
+  let traffic_light_position = window_builder.traffic_light_position;
+
   #[cfg(target_os = "macos")]
   {
     if window_builder.tabbing_identifier.is_none()
@@ -4013,8 +5144,84 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
     }
   }
 
+  #[cfg(windows)]
+  let titlebar_overlay = window_builder.titlebar_overlay;
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  let shadow = window_builder.shadow;
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  let theme = system_theme::WindowTheme::new(window_builder.get_theme());
+
+  let size_constraints = {
+    let constraints = &window_builder.inner.window.inner_size_constraints;
+    WindowSizeConstraints {
+      min_width: constraints.min_width,
+      min_height: constraints.min_height,
+      max_width: constraints.max_width,
+      max_height: constraints.max_height,
+    }
+  };
+  let aspect_ratio = window_builder.aspect_ratio;
+
+  let fullscreen_monitor = match window_builder.fullscreen_monitor.take() {
+    Some(selector) => {
+      let monitor = match &selector {
+        FullscreenMonitor::Index(index) => event_loop.available_monitors().nth(*index),
+        FullscreenMonitor::Name(name) => event_loop
+          .available_monitors()
+          .find(|monitor| monitor.name().as_ref() == Some(name)),
+      };
+      if monitor.is_none() {
+        log::warn!("fullscreen monitor {selector:?} not found, using the default monitor");
+      }
+      window_builder.inner = window_builder
+        .inner
+        .with_fullscreen(Some(Fullscreen::Borderless(monitor.clone())));
+      monitor
+    }
+    None => None,
+  };
+
   let window = window_builder.inner.build(event_loop).unwrap();
 
+  #[cfg(target_os = "macos")]
+  if let Some(position) = traffic_light_position {
+    window.set_traffic_light_position(position);
+  }
+
+  #[cfg(windows)]
+  if titlebar_overlay {
+    window.set_titlebar_overlay(true);
+  }
+
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  if shadow {
+    undecorated_shadow::install(&window, window.is_decorated());
+  }
+
+  if aspect_ratio.is_some() {
+    aspect_ratio::set(&window, &size_constraints, aspect_ratio);
+  }
+
   #[cfg(feature = "tracing")]
   {
     drop(window_create_span);
@@ -4091,6 +5298,14 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
     None
   };
 
+  let title_bar_rects = Mutex::new(window.title_bar_rects());
+
+  // new windows show the app badge too
+  #[cfg(windows)]
+  let overlay = badge::Overlay::default();
+  #[cfg(windows)]
+  overlay.apply(&window);
+
   Ok(WindowWrapper {
     label,
     has_children: AtomicBool::new(false),
@@ -4103,6 +5318,32 @@ fn create_window<T: UserEvent, F: Fn(RawWindow) + Send + 'static>(
     is_window_transparent,
     #[cfg(windows)]
     surface,
+    #[cfg(target_os = "macos")]
+    traffic_light_position,
+    title_bar_rects,
+    cursor_region: None,
+    ignore_cursor_events: false,
+    fullscreen_monitor,
+    size_constraints,
+    aspect_ratio,
+    #[cfg(windows)]
+    overlay,
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    shadow,
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "dragonfly",
+      target_os = "freebsd",
+      target_os = "netbsd",
+      target_os = "openbsd"
+    ))]
+    theme,
   })
 }
 
@@ -4115,14 +5356,6 @@ enum WebviewKind {
   WindowChild,
 }
 
-#[derive(Debug, Clone)]
-struct WebviewBounds {
-  x_rate: f32,
-  y_rate: f32,
-  width_rate: f32,
-  height_rate: f32,
-}
-
 fn create_webview<T: UserEvent>(
   kind: WebviewKind,
   window: &Window,
@@ -4172,16 +5405,37 @@ fn create_webview<T: UserEvent>(
     }
   };
 
+  // the initial load waits for the cookies to be stored so they are sent with the first request
+  let initial_cookies = if webview_attributes.cookies.is_empty() {
+    None
+  } else {
+    url.parse::<Url>().ok().map(|url| {
+      let cookies = webview_attributes
+        .cookies
+        .into_iter()
+        .map(|cookie| browsing_data::with_default_domain(cookie, Some(&url)))
+        .collect::<Vec<_>>();
+      (cookies, url)
+    })
+  };
+
   let mut webview_builder = WebViewBuilder::with_web_context(&mut web_context.inner)
     .with_id(&label)
     .with_focused(webview_attributes.focus)
-    .with_url(&url)
     .with_transparent(webview_attributes.transparent)
     .with_accept_first_mouse(webview_attributes.accept_first_mouse)
     .with_incognito(webview_attributes.incognito)
     .with_clipboard(webview_attributes.clipboard)
     .with_hotkeys_zoom(webview_attributes.zoom_hotkeys_enabled);
 
+  // the appended user agent is only known once the webview exists, so it is loaded afterwards
+  let defer_initial_load =
+    initial_cookies.is_some() || webview_attributes.append_user_agent.is_some();
+
+  if !defer_initial_load {
+    webview_builder = webview_builder.with_url(&url);
+  }
+
   #[cfg(any(target_os = "windows", target_os = "android"))]
   {
     webview_builder = webview_builder.with_https_scheme(webview_attributes.use_https_scheme);
@@ -4191,15 +5445,19 @@ fn create_webview<T: UserEvent>(
     webview_builder = webview_builder.with_background_color(color.into());
   }
 
+  let mut drag_drop_rejected = None;
   if webview_attributes.drag_drop_handler_enabled {
     let proxy = context.proxy.clone();
     let window_id_ = window_id.clone();
+    let drag_drop = drag_drop::DragDropState::new(pending.drag_drop_filter);
+    drag_drop_rejected = drag_drop.rejected();
     webview_builder = webview_builder.with_drag_drop_handler(move |event| {
       let event = match event {
         WryDragDropEvent::Enter {
           paths,
           position: (x, y),
         } => DragDropEvent::Enter {
+          types: drag_drop::types(&paths),
           paths,
           position: PhysicalPosition::new(x as _, y as _),
         },
@@ -4217,6 +5475,11 @@ fn create_webview<T: UserEvent>(
         _ => unimplemented!(),
       };
 
+      // the default handling of a rejected drag is still blocked
+      if !drag_drop.accept(&event) {
+        return true;
+      }
+
       let message = if kind == WebviewKind::WindowContent {
         WebviewMessage::SynthesizedWindowEvent(SynthesizedWindowEvent::DragDrop(event))
       } else {
@@ -4237,41 +5500,46 @@ fn create_webview<T: UserEvent>(
     });
   }
 
+  let scale_factor = window.scale_factor();
+  let window_size = window.inner_size().to_logical::<f32>(scale_factor);
+  let layout = webview_attributes.layout.or(
+    webview_attributes
+      .auto_resize
+      .then_some(WebviewLayout::Proportional),
+  );
+
   let webview_bounds = if let Some(bounds) = webview_attributes.bounds {
     let bounds: RectWrapper = bounds.into();
     let bounds = bounds.0;
 
-    let scale_factor = window.scale_factor();
-    let position = bounds.position.to_logical::<f32>(scale_factor);
-    let size = bounds.size.to_logical::<f32>(scale_factor);
-
-    webview_builder = webview_builder.with_bounds(bounds);
+    let webview_bounds = layout.map(|layout| {
+      WebviewBounds::new(
+        layout,
+        bounds.position.to_logical(scale_factor),
+        bounds.size.to_logical(scale_factor),
+        window_size,
+      )
+    });
 
-    let window_size = window.inner_size().to_logical::<f32>(scale_factor);
+    // docked webviews ignore the initial bounds
+    webview_builder = webview_builder.with_bounds(
+      webview_bounds
+        .as_ref()
+        .map_or(bounds, |webview_bounds| webview_bounds.resolve(window_size)),
+    );
 
-    if webview_attributes.auto_resize {
-      Some(WebviewBounds {
-        x_rate: position.x / window_size.width,
-        y_rate: position.y / window_size.height,
-        width_rate: size.width / window_size.width,
-        height_rate: size.height / window_size.height,
-      })
-    } else {
-      None
-    }
+    webview_bounds
   } else {
     #[cfg(feature = "unstable")]
     {
-      webview_builder = webview_builder.with_bounds(wry::Rect {
-        position: LogicalPosition::new(0, 0).into(),
-        size: window.inner_size().into(),
-      });
-      Some(WebviewBounds {
-        x_rate: 0.,
-        y_rate: 0.,
-        width_rate: 1.,
-        height_rate: 1.,
-      })
+      let webview_bounds = WebviewBounds::new(
+        layout.unwrap_or(WebviewLayout::Proportional),
+        LogicalPosition::new(0., 0.),
+        window_size,
+        window_size,
+      );
+      webview_builder = webview_builder.with_bounds(webview_bounds.resolve(window_size));
+      Some(webview_bounds)
     }
     #[cfg(not(feature = "unstable"))]
     None
@@ -4296,8 +5564,32 @@ fn create_webview<T: UserEvent>(
     });
   }
 
-  if let Some(page_load_handler) = pending.on_page_load_handler {
-    webview_builder = webview_builder.with_on_page_load_handler(move |event, url| {
+  let page_load_handler: Option<Rc<page_load::PageLoadHandler>> = pending
+    .on_page_load_handler
+    .map(|handler| Rc::new(handler) as Rc<page_load::PageLoadHandler>);
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  let page_load_handler_ = page_load_handler.clone();
+  webview_builder = webview_builder.with_on_page_load_handler(move |event, url| {
+    // the zoom is kept across navigations
+    if matches!(event, wry::PageLoadEvent::Started) {
+      let _ = proxy.send_event(Message::Webview(
+        *window_id_.lock().unwrap(),
+        id,
+        WebviewMessage::RestoreZoom,
+      ));
+    }
+
+    // the availability of the history navigation is checked on every page load, which is the only
+    // change notification on macOS
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::SyncHistory,
+    ));
+
+    if let Some(page_load_handler) = &page_load_handler_ {
       let _ = url.parse().map(|url| {
         page_load_handler(
           url,
@@ -4305,24 +5597,43 @@ fn create_webview<T: UserEvent>(
             wry::PageLoadEvent::Started => tauri_runtime::webview::PageLoadEvent::Started,
             wry::PageLoadEvent::Finished => tauri_runtime::webview::PageLoadEvent::Finished,
           },
+          None,
         )
       });
-    });
-  }
+    }
+  });
 
   if let Some(user_agent) = webview_attributes.user_agent {
     webview_builder = webview_builder.with_user_agent(&user_agent);
   }
 
-  if let Some(proxy_url) = webview_attributes.proxy_url {
-    let config = parse_proxy_url(&proxy_url)?;
+  #[allow(deprecated)]
+  let proxy_config = webview_attributes
+    .proxy
+    .clone()
+    .or_else(|| webview_attributes.proxy_url.clone().map(Into::into));
+
+  proxy::check_support(
+    proxy_config.as_ref(),
+    pending.proxy_authentication_handler.is_some(),
+  )?;
+
+  if let Some(proxy_config) = &proxy_config {
+    let config = parse_proxy_url(&proxy_config.url)?;
 
     webview_builder = webview_builder.with_proxy_config(config);
   }
 
   #[cfg(windows)]
   {
-    if let Some(additional_browser_args) = webview_attributes.additional_browser_args {
+    let additional_browser_args = match &proxy_config {
+      Some(proxy_config) => {
+        proxy::browser_args(webview_attributes.additional_browser_args, proxy_config)?
+      }
+      None => webview_attributes.additional_browser_args,
+    };
+
+    if let Some(additional_browser_args) = additional_browser_args {
       webview_builder = webview_builder.with_additional_browser_args(&additional_browser_args);
     }
 
@@ -4370,7 +5681,8 @@ fn create_webview<T: UserEvent>(
   ));
 
   for script in webview_attributes.initialization_scripts {
-    webview_builder = webview_builder.with_initialization_script(&script);
+    webview_builder = webview_builder
+      .with_initialization_script_for_main_only(&script.script, script.for_main_frame_only);
   }
 
   for (scheme, protocol) in uri_scheme_protocols {
@@ -4465,6 +5777,142 @@ fn create_webview<T: UserEvent>(
   }
   .map_err(|e| Error::CreateWebview(Box::new(e)))?;
 
+  if let Some(page_load_handler) = page_load_handler {
+    page_load::attach_failure_handler(&webview, page_load_handler);
+  }
+
+  if let Some(rejected) = drag_drop_rejected {
+    drag_drop::attach_feedback(&webview, rejected);
+  }
+
+  if let Some(suffix) = &webview_attributes.append_user_agent {
+    if let Err(e) = user_agent::append(&webview, suffix) {
+      log::error!("failed to append to the user agent: {e}");
+    }
+  }
+
+  if let Some((cookies, url)) = initial_cookies {
+    let proxy = context.proxy.clone();
+    let window_id_ = window_id.clone();
+    browsing_data::set_cookies(
+      &webview,
+      cookies,
+      Box::new(move |result| {
+        if let Err(e) = result {
+          log::error!("failed to set the initial cookies: {e}");
+        }
+
+        let _ = proxy.send_event(Message::Webview(
+          *window_id_.lock().unwrap(),
+          id,
+          WebviewMessage::Navigate(url),
+        ));
+      }),
+    );
+  } else if defer_initial_load {
+    if let Err(e) = webview.load_url(&url) {
+      log::error!("failed to load the webview URL: {e}");
+    }
+  }
+
+  if let Some(scale_factor) = webview_attributes.zoom {
+    if let Err(e) = webview.zoom(scale_factor) {
+      log::error!("failed to set webview zoom: {e}");
+    }
+  }
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  let zoom_state = zoom::ZoomState::new(webview_attributes.zoom.unwrap_or(1.0), move |factor| {
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::WebviewEvent(WebviewEvent::ZoomChanged(factor)),
+    ));
+  });
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  zoom::attach_change_handler(&webview, move |factor| {
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::SyncZoom(factor),
+    ));
+  });
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  let find_state = find::FindState::new(move |result| {
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::WebviewEvent(WebviewEvent::FindResultChanged(result)),
+    ));
+  });
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  let history_state = history::HistoryState::new(move |history| {
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::WebviewEvent(WebviewEvent::HistoryChanged(history)),
+    ));
+  });
+
+  let proxy = context.proxy.clone();
+  let window_id_ = window_id.clone();
+  history::attach_change_handler(&webview, move || {
+    let _ = proxy.send_event(Message::Webview(
+      *window_id_.lock().unwrap(),
+      id,
+      WebviewMessage::SyncHistory,
+    ));
+  });
+
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  let devtools_state = {
+    let proxy = context.proxy.clone();
+    let window_id_ = window_id.clone();
+    let state = Rc::new(devtools::DevtoolsState::new(move |open| {
+      let event = if open {
+        WebviewEvent::DevtoolsOpened
+      } else {
+        WebviewEvent::DevtoolsClosed
+      };
+      let _ = proxy.send_event(Message::Webview(
+        *window_id_.lock().unwrap(),
+        id,
+        WebviewMessage::WebviewEvent(event),
+      ));
+    }));
+
+    devtools::attach_change_handler(&webview, &state);
+
+    state
+  };
+
+  // wry only sets the proxy host and port
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  ))]
+  if let Some(proxy_config) = &proxy_config {
+    if !proxy_config.bypass.is_empty() || proxy_config.credentials.is_some() {
+      if let Err(e) = proxy::set(&webview, Some(proxy_config)) {
+        log::error!("failed to set the webview proxy: {e}");
+      }
+    }
+  }
+
+  if let Some(handler) = pending.proxy_authentication_handler {
+    proxy::attach_authentication_handler(&webview, handler);
+  }
+
   if kind == WebviewKind::WindowContent {
     #[cfg(any(
       target_os = "linux",
@@ -4529,6 +5977,12 @@ fn create_webview<T: UserEvent>(
       web_context_key
     },
     bounds: Arc::new(Mutex::new(webview_bounds)),
+    initialization_scripts: Default::default(),
+    zoom: Rc::new(zoom_state),
+    find: Rc::new(find_state),
+    history: Rc::new(history_state),
+    #[cfg(any(debug_assertions, feature = "devtools"))]
+    devtools: devtools_state,
   })
 }
 
@@ -4585,3 +6039,37 @@ fn inner_size(
 ) -> TaoPhysicalSize<u32> {
   window.inner_size()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{fullscreen_monitor_change, FullscreenMonitorChange};
+
+  #[test]
+  fn fullscreen_windows_keep_their_connected_monitor() {
+    assert_eq!(
+      fullscreen_monitor_change(true, true),
+      FullscreenMonitorChange::Unchanged
+    );
+  }
+
+  #[test]
+  fn windows_leaving_the_fullscreen_forget_their_monitor() {
+    // the monitor must not bring the window back to fullscreen once disconnected
+    assert_eq!(
+      fullscreen_monitor_change(false, true),
+      FullscreenMonitorChange::Left
+    );
+    assert_eq!(
+      fullscreen_monitor_change(false, false),
+      FullscreenMonitorChange::Left
+    );
+  }
+
+  #[test]
+  fn fullscreen_windows_move_off_a_disconnected_monitor() {
+    assert_eq!(
+      fullscreen_monitor_change(true, false),
+      FullscreenMonitorChange::Disconnected
+    );
+  }
+}
@@ -0,0 +1,216 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview memory usage.
+//!
+//! wry does not expose the process rendering a webview, so it is looked up on
+//! the platform webview and its memory is read from the operating system.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: reports the private bytes of the renderer process of the main
+//!   frame, found with `ICoreWebView2Environment13::GetProcessExtendedInfos`.
+//!   The lookup is asynchronous, the window messages are pumped while waiting
+//!   for it. Requires WebView2 Runtime 1.0.2210 or newer.
+//! - **macOS**: reports the physical footprint of the web content process,
+//!   found with the private `_webProcessIdentifier` API, so it requires the
+//!   `macos-private-api` feature and is unsupported otherwise.
+//! - **Linux**: Unsupported. WebKitGTK has no public API returning the web
+//!   process of a webview, and the web processes spawned by the app can't be
+//!   matched to their webviews from the outside, they may be sandboxed and are
+//!   shared depending on the process model of the web context. Only the total
+//!   memory of the web processes of the app can be read.
+//! - **iOS / Android**: Unsupported.
+
+use tauri_runtime::Result;
+
+/// The memory used by the process rendering `webview`, in bytes.
+#[cfg(windows)]
+pub fn memory_usage(webview:&wry::WebView) -> Result<Option<u64>> {
+	use webview2_com::{
+		GetProcessExtendedInfosCompletedHandler,
+		Microsoft::Web::WebView2::Win32::{ICoreWebView2_2, ICoreWebView2_20, ICoreWebView2Environment13},
+	};
+	use windows::core::Interface;
+	use wry::WebViewExtWindows;
+
+	let (tx, rx) = std::sync::mpsc::channel();
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			let mut frame_id = 0;
+
+			core_webview.cast::<ICoreWebView2_20>()?.FrameId(&mut frame_id)?;
+
+			core_webview
+				.cast::<ICoreWebView2_2>()?
+				.Environment()?
+				.cast::<ICoreWebView2Environment13>()?
+				.GetProcessExtendedInfos(&GetProcessExtendedInfosCompletedHandler::create(Box::new(
+					move |result, infos| {
+						let memory = result.and_then(|()| {
+							match infos {
+								Some(infos) => renderer_memory(&infos, frame_id),
+								None => Ok(None),
+							}
+						});
+
+						let _ = tx.send(memory);
+
+						Ok(())
+					},
+				)))
+		})
+	};
+
+	// older runtimes don't have the interfaces, the usage is simply unknown
+	if let Err(e) = result {
+		log::debug!("failed to look up the webview renderer process: {e}");
+
+		return Ok(None);
+	}
+
+	match webview2_com::wait_with_pump(rx) {
+		Ok(Ok(memory)) => Ok(memory),
+		Ok(Err(e)) => {
+			log::debug!("failed to read the webview memory usage: {e}");
+
+			Ok(None)
+		},
+		Err(e) => {
+			log::debug!("failed to read the webview memory usage: {e:?}");
+
+			Ok(None)
+		},
+	}
+}
+
+/// The private bytes of the renderer process hosting the frame `frame_id`.
+#[cfg(windows)]
+unsafe fn renderer_memory(
+	infos:&webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2ProcessExtendedInfoCollection,
+	frame_id:u32,
+) -> windows::core::Result<Option<u64>> {
+	use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2FrameInfo2;
+	use windows::{Win32::Foundation::BOOL, core::Interface};
+
+	let mut count = 0;
+
+	infos.Count(&mut count)?;
+
+	for index in 0..count {
+		let info = infos.GetValueAtIndex(index)?;
+
+		let frames = info.AssociatedFrameInfos()?.GetIterator()?;
+
+		let mut has_current = BOOL::default();
+
+		frames.HasCurrent(&mut has_current)?;
+
+		while has_current.as_bool() {
+			let mut id = 0;
+
+			frames.GetCurrent()?.cast::<ICoreWebView2FrameInfo2>()?.FrameId(&mut id)?;
+
+			if id == frame_id {
+				let mut process_id = 0;
+
+				info.ProcessInfo()?.ProcessId(&mut process_id)?;
+
+				return private_bytes(process_id as u32).map(Some);
+			}
+
+			frames.MoveNext(&mut has_current)?;
+		}
+	}
+
+	Ok(None)
+}
+
+#[cfg(windows)]
+unsafe fn private_bytes(process_id:u32) -> windows::core::Result<u64> {
+	use windows::Win32::{
+		Foundation::CloseHandle,
+		System::{
+			ProcessStatus::{K32GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS, PROCESS_MEMORY_COUNTERS_EX},
+			Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION},
+		},
+	};
+
+	let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)?;
+
+	let mut counters = PROCESS_MEMORY_COUNTERS_EX::default();
+
+	let result = K32GetProcessMemoryInfo(
+		process,
+		&mut counters as *mut PROCESS_MEMORY_COUNTERS_EX as *mut PROCESS_MEMORY_COUNTERS,
+		std::mem::size_of::<PROCESS_MEMORY_COUNTERS_EX>() as u32,
+	);
+
+	let _ = CloseHandle(process);
+
+	result.ok()?;
+
+	Ok(counters.PrivateUsage as u64)
+}
+
+/// The memory used by the process rendering `webview`, in bytes.
+#[cfg(all(target_os = "macos", feature = "macos-private-api"))]
+pub fn memory_usage(webview:&wry::WebView) -> Result<Option<u64>> {
+	use objc2::msg_send;
+	use wry::WebViewExtMacOS;
+
+	/// `rusage_info_v2` from `sys/resource.h`.
+	#[repr(C)]
+	#[derive(Default)]
+	struct RusageInfoV2 {
+		ri_uuid:[u8; 16],
+		ri_user_time:u64,
+		ri_system_time:u64,
+		ri_pkg_idle_wkups:u64,
+		ri_interrupt_wkups:u64,
+		ri_pageins:u64,
+		ri_wired_size:u64,
+		ri_resident_size:u64,
+		ri_phys_footprint:u64,
+		ri_proc_start_abstime:u64,
+		ri_proc_exit_abstime:u64,
+		ri_child_user_time:u64,
+		ri_child_system_time:u64,
+		ri_child_pkg_idle_wkups:u64,
+		ri_child_interrupt_wkups:u64,
+		ri_child_pageins:u64,
+		ri_child_elapsed_abstime:u64,
+		ri_diskio_bytesread:u64,
+		ri_diskio_byteswritten:u64,
+	}
+
+	const RUSAGE_INFO_V2:i32 = 2;
+
+	extern "C" {
+		fn proc_pid_rusage(pid:i32, flavor:i32, buffer:*mut RusageInfoV2) -> i32;
+	}
+
+	let wk_webview = webview.webview();
+
+	// `_webProcessIdentifier` is a private API, 0 until the process is launched
+	let process_id:i32 = unsafe { msg_send![&*wk_webview, _webProcessIdentifier] };
+
+	if process_id <= 0 {
+		return Ok(None);
+	}
+
+	let mut info = RusageInfoV2::default();
+
+	// `task_info` would need the task port of the process, which is not granted
+	// to the app
+	if unsafe { proc_pid_rusage(process_id, RUSAGE_INFO_V2, &mut info) } != 0 {
+		return Ok(None);
+	}
+
+	Ok(Some(info.ri_phys_footprint))
+}
+
+#[cfg(not(any(windows, all(target_os = "macos", feature = "macos-private-api"))))]
+pub fn memory_usage(_webview:&wry::WebView) -> Result<Option<u64>> { Ok(None) }
@@ -0,0 +1,393 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Page load failure detection.
+//!
+//! wry only reports started/finished page loads, so navigation failures are
+//! read from the platform webview and reported as
+//! [`PageLoadEvent::Failed`](tauri_runtime::webview::PageLoadEvent::Failed).
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: reported from the `NavigationCompleted` event.
+//! - **Linux**: reported from the `load-failed` and
+//!   `load-failed-with-tls-errors` signals, and from the main resource
+//!   response status when the load finishes.
+//! - **macOS**: reported from the `didFailProvisionalNavigation` and
+//!   `didFailNavigation` navigation delegate methods. wry owns the navigation
+//!   delegate, so it is wrapped by a delegate reading the failures and
+//!   forwarding every message to it.
+//! - **iOS / Android**: Unsupported.
+
+use std::rc::Rc;
+
+use tauri_runtime::webview::{PageLoadError, PageLoadErrorKind, PageLoadEvent};
+use url::Url;
+
+pub type PageLoadHandler = dyn Fn(Url, PageLoadEvent, Option<PageLoadError>);
+
+#[cfg(windows)]
+pub fn attach_failure_handler(webview:&wry::WebView, handler:Rc<PageLoadHandler>) {
+	use webview2_com::{
+		Microsoft::Web::WebView2::Win32::*,
+		NavigationCompletedEventHandler,
+		take_pwstr,
+	};
+	use windows::{
+		Win32::{Foundation::BOOL, System::WinRT::EventRegistrationToken},
+		core::{Interface, PWSTR},
+	};
+	use wry::WebViewExtWindows;
+
+	fn error_kind(status:COREWEBVIEW2_WEB_ERROR_STATUS) -> (PageLoadErrorKind, &'static str) {
+		match status {
+			COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_COMMON_NAME_IS_INCORRECT
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_EXPIRED
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CLIENT_CERTIFICATE_CONTAINS_ERRORS
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_REVOKED
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CERTIFICATE_IS_INVALID => {
+				(PageLoadErrorKind::Tls, "the server certificate is invalid")
+			},
+			COREWEBVIEW2_WEB_ERROR_STATUS_SERVER_UNREACHABLE
+			| COREWEBVIEW2_WEB_ERROR_STATUS_TIMEOUT
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CONNECTION_ABORTED
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CONNECTION_RESET
+			| COREWEBVIEW2_WEB_ERROR_STATUS_DISCONNECTED
+			| COREWEBVIEW2_WEB_ERROR_STATUS_CANNOT_CONNECT
+			| COREWEBVIEW2_WEB_ERROR_STATUS_HOST_NAME_NOT_RESOLVED => {
+				(PageLoadErrorKind::Network, "the server could not be reached")
+			},
+			COREWEBVIEW2_WEB_ERROR_STATUS_OPERATION_CANCELED => {
+				(PageLoadErrorKind::Cancelled, "the navigation was cancelled")
+			},
+			_ => (PageLoadErrorKind::Other, "the navigation failed"),
+		}
+	}
+
+	let controller = webview.controller();
+
+	let Ok(core_webview) = (unsafe { controller.CoreWebView2() }) else {
+		return;
+	};
+
+	let mut token = EventRegistrationToken::default();
+
+	let _ = unsafe {
+		core_webview.add_NavigationCompleted(
+			&NavigationCompletedEventHandler::create(Box::new(move |webview, args| {
+				let (Some(webview), Some(args)) = (webview, args) else {
+					return Ok(());
+				};
+
+				let mut is_success = BOOL::default();
+				args.IsSuccess(&mut is_success)?;
+
+				let mut http_status = 0;
+				if let Ok(args) = args.cast::<ICoreWebView2NavigationCompletedEventArgs2>() {
+					args.HttpStatusCode(&mut http_status)?;
+				}
+
+				let http_status = u16::try_from(http_status).ok().filter(|status| *status > 0);
+
+				let error = if !is_success.as_bool() {
+					let mut status = COREWEBVIEW2_WEB_ERROR_STATUS::default();
+					args.WebErrorStatus(&mut status)?;
+
+					let (kind, description) = error_kind(status);
+					Some(PageLoadError { kind, description:description.into(), http_status })
+				} else {
+					http_status.filter(|status| *status >= 400).map(|status| {
+						PageLoadError {
+							kind:PageLoadErrorKind::Http,
+							description:format!("the server responded with status {status}"),
+							http_status:Some(status),
+						}
+					})
+				};
+
+				if let Some(error) = error {
+					let mut source = PWSTR::null();
+					webview.Source(&mut source)?;
+
+					if let Ok(url) = take_pwstr(source).parse() {
+						handler(url, PageLoadEvent::Failed, Some(error));
+					}
+				}
+
+				Ok(())
+			})),
+			&mut token,
+		)
+	};
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_failure_handler(webview:&wry::WebView, handler:Rc<PageLoadHandler>) {
+	use webkit2gtk::{LoadEvent, NetworkError, URIResponseExt, WebResourceExt, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	let webview = webview.webview();
+
+	let handler_ = handler.clone();
+
+	webview.connect_load_failed(move |_webview, _event, failing_uri, error| {
+		let kind = if error.matches(NetworkError::Cancelled) {
+			PageLoadErrorKind::Cancelled
+		} else if error.kind::<NetworkError>().is_some() {
+			PageLoadErrorKind::Network
+		} else {
+			PageLoadErrorKind::Other
+		};
+
+		if let Ok(url) = failing_uri.parse() {
+			handler_(
+				url,
+				PageLoadEvent::Failed,
+				Some(PageLoadError { kind, description:error.message().into(), http_status:None }),
+			);
+		}
+
+		// let the default error page handling run
+		false
+	});
+
+	let handler_ = handler.clone();
+
+	webview.connect_load_failed_with_tls_errors(move |_webview, failing_uri, _certificate, errors| {
+		if let Ok(url) = failing_uri.parse() {
+			handler_(
+				url,
+				PageLoadEvent::Failed,
+				Some(PageLoadError {
+					kind:PageLoadErrorKind::Tls,
+					description:format!("the server certificate is invalid: {errors:?}"),
+					http_status:None,
+				}),
+			);
+		}
+
+		false
+	});
+
+	webview.connect_load_changed(move |webview, event| {
+		if event != LoadEvent::Finished {
+			return;
+		}
+
+		let status = webview
+			.main_resource()
+			.and_then(|resource| resource.response())
+			.map(|response| response.status_code())
+			.and_then(|status| u16::try_from(status).ok());
+
+		if let (Some(status), Some(uri)) = (status.filter(|s| *s >= 400), webview.uri()) {
+			if let Ok(url) = uri.parse() {
+				handler(
+					url,
+					PageLoadEvent::Failed,
+					Some(PageLoadError {
+						kind:PageLoadErrorKind::Http,
+						description:format!("the server responded with status {status}"),
+						http_status:Some(status),
+					}),
+				);
+			}
+		}
+	});
+}
+
+#[cfg(target_os = "macos")]
+pub fn attach_failure_handler(webview:&wry::WebView, handler:Rc<PageLoadHandler>) {
+	use objc2::runtime::ProtocolObject;
+	use objc2_foundation::MainThreadMarker;
+	use wry::WebViewExtMacOS;
+
+	let Some(mtm) = MainThreadMarker::new() else {
+		return;
+	};
+
+	let wk_webview = webview.webview();
+
+	let delegate = unsafe { wk_webview.navigationDelegate() };
+
+	let failure_delegate = macos::FailureDelegate::new(mtm, handler, delegate);
+
+	unsafe {
+		wk_webview.setNavigationDelegate(Some(ProtocolObject::from_ref(&*failure_delegate)));
+	}
+
+	// the navigation delegate is a weak reference, the webview keeps it alive
+	macos::retain_with(&wk_webview, failure_delegate);
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::rc::Rc;
+
+	use objc2::{
+		ClassType,
+		DeclaredClass,
+		declare_class,
+		msg_send,
+		msg_send_id,
+		mutability::MainThreadOnly,
+		rc::Retained,
+		runtime::{AnyObject, NSObject, NSObjectProtocol, ProtocolObject, Sel},
+		sel,
+	};
+	use objc2_foundation::{MainThreadMarker, NSError, NSString};
+	use objc2_web_kit::{WKNavigation, WKNavigationDelegate, WKWebView};
+	use tauri_runtime::webview::{PageLoadError, PageLoadErrorKind, PageLoadEvent};
+
+	use super::PageLoadHandler;
+
+	pub struct Ivars {
+		handler:Rc<PageLoadHandler>,
+		/// The navigation delegate of wry.
+		delegate:Option<Retained<ProtocolObject<dyn WKNavigationDelegate>>>,
+	}
+
+	declare_class!(
+		pub struct FailureDelegate;
+
+		unsafe impl ClassType for FailureDelegate {
+			type Super = NSObject;
+			type Mutability = MainThreadOnly;
+			const NAME:&'static str = "TauriPageLoadFailureDelegate";
+		}
+
+		impl DeclaredClass for FailureDelegate {
+			type Ivars = Ivars;
+		}
+
+		unsafe impl FailureDelegate {
+			#[method(respondsToSelector:)]
+			fn responds_to_selector(&self, selector:Sel) -> bool {
+				let responds:bool = unsafe { msg_send![super(self), respondsToSelector: selector] };
+
+				responds || self.ivars().delegate.as_ref().is_some_and(|delegate| delegate.respondsToSelector(selector))
+			}
+
+			#[method(forwardingTargetForSelector:)]
+			fn forwarding_target_for_selector(&self, _selector:Sel) -> *mut AnyObject {
+				self.ivars()
+					.delegate
+					.as_ref()
+					.map_or(std::ptr::null_mut(), |delegate| Retained::as_ptr(delegate) as *mut AnyObject)
+			}
+		}
+
+		unsafe impl NSObjectProtocol for FailureDelegate {}
+
+		unsafe impl WKNavigationDelegate for FailureDelegate {
+			#[method(webView:didFailProvisionalNavigation:withError:)]
+			fn did_fail_provisional_navigation(
+				&self,
+				webview:&WKWebView,
+				navigation:Option<&WKNavigation>,
+				error:&NSError,
+			) {
+				self.report(error);
+
+				if let Some(delegate) = self.forward(sel!(webView:didFailProvisionalNavigation:withError:)) {
+					unsafe { delegate.webView_didFailProvisionalNavigation_withError(webview, navigation, error) };
+				}
+			}
+
+			#[method(webView:didFailNavigation:withError:)]
+			fn did_fail_navigation(&self, webview:&WKWebView, navigation:Option<&WKNavigation>, error:&NSError) {
+				self.report(error);
+
+				if let Some(delegate) = self.forward(sel!(webView:didFailNavigation:withError:)) {
+					unsafe { delegate.webView_didFailNavigation_withError(webview, navigation, error) };
+				}
+			}
+		}
+	);
+
+	impl FailureDelegate {
+		pub fn new(
+			mtm:MainThreadMarker,
+			handler:Rc<PageLoadHandler>,
+			delegate:Option<Retained<ProtocolObject<dyn WKNavigationDelegate>>>,
+		) -> Retained<Self> {
+			let this = mtm.alloc::<Self>().set_ivars(Ivars { handler, delegate });
+
+			unsafe { msg_send_id![super(this), init] }
+		}
+
+		/// The delegate of wry, if it implements `selector`.
+		fn forward(&self, selector:Sel) -> Option<&ProtocolObject<dyn WKNavigationDelegate>> {
+			self.ivars().delegate.as_deref().filter(|delegate| delegate.respondsToSelector(selector))
+		}
+
+		fn report(&self, error:&NSError) {
+			let key = NSString::from_str("NSErrorFailingURLStringKey");
+
+			let url:Option<Retained<NSString>> =
+				unsafe { msg_send_id![&*error.userInfo(), objectForKey: &*key] };
+
+			let Some(Ok(url)) = url.map(|url| url.to_string().parse()) else {
+				return;
+			};
+
+			(self.ivars().handler)(
+				url,
+				PageLoadEvent::Failed,
+				Some(PageLoadError {
+					kind:error_kind(error),
+					description:error.localizedDescription().to_string(),
+					http_status:None,
+				}),
+			);
+		}
+	}
+
+	fn error_kind(error:&NSError) -> PageLoadErrorKind {
+		if error.domain().to_string() != "NSURLErrorDomain" {
+			return PageLoadErrorKind::Other;
+		}
+
+		// the `NSURLError` codes
+		match error.code() {
+			-999 => PageLoadErrorKind::Cancelled,
+			-1206..=-1200 => PageLoadErrorKind::Tls,
+			-1001 | -1003..=-1006 | -1009 | -1018..=-1020 => PageLoadErrorKind::Network,
+			_ => PageLoadErrorKind::Other,
+		}
+	}
+
+	/// Keeps `delegate` alive as long as `webview`.
+	pub fn retain_with(webview:&WKWebView, delegate:Retained<FailureDelegate>) {
+		use objc2::ffi::{OBJC_ASSOCIATION_RETAIN_NONATOMIC, objc_setAssociatedObject};
+
+		static KEY:u8 = 0;
+
+		unsafe {
+			objc_setAssociatedObject(
+				webview as *const WKWebView as *mut _,
+				&KEY as *const u8 as *const _,
+				Retained::as_ptr(&delegate) as *mut _,
+				OBJC_ASSOCIATION_RETAIN_NONATOMIC,
+			);
+		}
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_failure_handler(_webview:&wry::WebView, _handler:Rc<PageLoadHandler>) {}
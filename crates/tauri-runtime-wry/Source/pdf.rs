@@ -0,0 +1,257 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview printing to PDF.
+//!
+//! wry only opens the print dialog, so the document is printed with the
+//! platform webview directly.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2_16::PrintToPdfStream`, which requires
+//!   WebView2 Runtime 1.0.1518 or later.
+//! - **Linux**: uses a `WebKitPrintOperation` printing to a temporary file. The
+//!   backgrounds follow the `print-color-adjust` CSS property of the page.
+//! - **macOS**: uses `WKWebView::createPDFWithConfiguration`, which renders the
+//!   whole page on a single PDF page, so the page size, margins and orientation
+//!   are ignored and the backgrounds are always printed.
+//! - **iOS / Android**: Unsupported.
+
+use tauri_runtime::{Error, Result, webview::PdfOptions};
+
+pub type PdfHandler = Box<dyn FnOnce(Result<Vec<u8>>) + Send>;
+
+#[cfg(windows)]
+pub fn print_to_pdf(webview:&wry::WebView, options:PdfOptions, handler:PdfHandler) {
+	use std::{cell::Cell, rc::Rc};
+
+	use webview2_com::{
+		Microsoft::Web::WebView2::Win32::{
+			COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE,
+			COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT,
+			ICoreWebView2_2,
+			ICoreWebView2_16,
+			ICoreWebView2Environment6,
+		},
+		PrintToPdfStreamCompletedHandler,
+	};
+	use windows::{
+		Win32::{
+			Foundation::E_POINTER,
+			System::Com::{IStream, STREAM_SEEK_SET},
+		},
+		core::Interface,
+	};
+	use wry::WebViewExtWindows;
+
+	fn read_stream(stream:&IStream) -> windows::core::Result<Vec<u8>> {
+		let mut bytes = Vec::new();
+
+		let mut buffer = [0u8; 64 * 1024];
+
+		unsafe {
+			stream.Seek(0, STREAM_SEEK_SET, None)?;
+
+			loop {
+				let mut read = 0;
+
+				stream
+					.Read(buffer.as_mut_ptr().cast(), buffer.len() as u32, Some(&mut read))
+					.ok()?;
+
+				if read == 0 {
+					break;
+				}
+
+				bytes.extend_from_slice(&buffer[..read as usize]);
+			}
+		}
+
+		Ok(bytes)
+	}
+
+	let error = |e:windows::core::Error| Error::FailedToPrintToPdf(e.to_string());
+
+	// shared with the completion handler, whichever runs first reports the result
+	let handler = Rc::new(Cell::new(Some(handler)));
+
+	let handler_ = handler.clone();
+
+	let result = unsafe {
+		webview.controller().CoreWebView2().and_then(|core_webview| {
+			let settings = core_webview
+				.cast::<ICoreWebView2_2>()?
+				.Environment()?
+				.cast::<ICoreWebView2Environment6>()?
+				.CreatePrintSettings()?;
+
+			settings.SetOrientation(if options.landscape {
+				COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE
+			} else {
+				COREWEBVIEW2_PRINT_ORIENTATION_PORTRAIT
+			})?;
+			settings.SetPageWidth(options.page_size.width)?;
+			settings.SetPageHeight(options.page_size.height)?;
+			settings.SetMarginTop(options.margins.top)?;
+			settings.SetMarginRight(options.margins.right)?;
+			settings.SetMarginBottom(options.margins.bottom)?;
+			settings.SetMarginLeft(options.margins.left)?;
+			settings.SetShouldPrintBackgrounds(options.print_background)?;
+
+			core_webview.cast::<ICoreWebView2_16>()?.PrintToPdfStream(
+				&settings,
+				&PrintToPdfStreamCompletedHandler::create(Box::new(move |result, stream| {
+					if let Some(handler) = handler_.take() {
+						handler(
+							result
+								.and_then(|()| stream.ok_or_else(|| E_POINTER.into()))
+								.and_then(|stream| read_stream(&stream))
+								.map_err(error),
+						);
+					}
+
+					Ok(())
+				})),
+			)
+		})
+	};
+
+	if let Err(e) = result {
+		if let Some(handler) = handler.take() {
+			handler(Err(error(e)));
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn print_to_pdf(webview:&wry::WebView, options:PdfOptions, handler:PdfHandler) {
+	use std::{
+		cell::Cell,
+		rc::Rc,
+		sync::atomic::{AtomicU32, Ordering},
+	};
+
+	use gtk::{PageOrientation, PageSetup, PaperSize, PrintSettings, Unit};
+	use webkit2gtk::{PrintOperation, PrintOperationExt};
+	use wry::WebViewExtUnix;
+
+	static NEXT_ID:AtomicU32 = AtomicU32::new(0);
+
+	let path = std::env::temp_dir().join(format!(
+		"tauri-print-{}-{}.pdf",
+		std::process::id(),
+		NEXT_ID.fetch_add(1, Ordering::Relaxed)
+	));
+
+	let Ok(uri) = url::Url::from_file_path(&path) else {
+		handler(Err(Error::FailedToPrintToPdf("invalid temporary file path".into())));
+
+		return;
+	};
+
+	let settings = PrintSettings::new();
+	settings.set_printer(Some("Print to File"));
+	settings.set("output-file-format", Some("pdf"));
+	settings.set("output-uri", Some(uri.as_str()));
+
+	let page_setup = PageSetup::new();
+	page_setup.set_paper_size(&PaperSize::new_custom(
+		"custom",
+		"custom",
+		options.page_size.width,
+		options.page_size.height,
+		Unit::Inch,
+	));
+	page_setup.set_orientation(if options.landscape {
+		PageOrientation::Landscape
+	} else {
+		PageOrientation::Portrait
+	});
+	page_setup.set_top_margin(options.margins.top, Unit::Inch);
+	page_setup.set_right_margin(options.margins.right, Unit::Inch);
+	page_setup.set_bottom_margin(options.margins.bottom, Unit::Inch);
+	page_setup.set_left_margin(options.margins.left, Unit::Inch);
+
+	let operation = PrintOperation::new(&webview.webview());
+	operation.set_print_settings(&settings);
+	operation.set_page_setup(&page_setup);
+
+	// `failed` is emitted before `finished`, whichever runs first reports the result
+	let handler = Rc::new(Cell::new(Some(handler)));
+
+	let handler_ = handler.clone();
+
+	operation.connect_failed(move |_, e| {
+		if let Some(handler) = handler_.take() {
+			handler(Err(Error::FailedToPrintToPdf(e.to_string())));
+		}
+	});
+
+	operation.connect_finished(move |_| {
+		let result = std::fs::read(&path).map_err(|e| Error::FailedToPrintToPdf(e.to_string()));
+
+		let _ = std::fs::remove_file(&path);
+
+		if let Some(handler) = handler.take() {
+			handler(result);
+		}
+	});
+
+	operation.print();
+}
+
+#[cfg(target_os = "macos")]
+pub fn print_to_pdf(webview:&wry::WebView, _options:PdfOptions, handler:PdfHandler) {
+	use std::cell::Cell;
+
+	use block2::RcBlock;
+	use objc2::rc::Retained;
+	use objc2_foundation::{NSData, NSError};
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	let handler = Cell::new(Some(handler));
+
+	let block = RcBlock::new(move |data:*mut NSData, error:*mut NSError| {
+		let Some(handler) = handler.take() else {
+			return;
+		};
+
+		let result = match unsafe { data.as_ref() } {
+			Some(data) => Ok(data.bytes().to_vec()),
+			None => {
+				Err(Error::FailedToPrintToPdf(
+					unsafe { error.as_ref() }
+						.map(|error| error.localizedDescription().to_string())
+						.unwrap_or_else(|| "the PDF creation failed".into()),
+				))
+			},
+		};
+
+		handler(result);
+	});
+
+	let webview = unsafe { Retained::cast::<WKWebView>(webview.webview()) };
+
+	unsafe { webview.createPDFWithConfiguration_completionHandler(None, &block) };
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn print_to_pdf(_webview:&wry::WebView, _options:PdfOptions, handler:PdfHandler) {
+	handler(Err(Error::FailedToPrintToPdf("unsupported on this platform".into())));
+}
@@ -0,0 +1,260 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview proxy settings.
+//!
+//! wry only sets the proxy host and port when the webview is created, the
+//! bypass list, the credentials and the runtime changes are applied here.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: the proxy is a browser argument of the WebView2 environment,
+//!   so it cannot change after the webview creation. The bypass list is added
+//!   to the additional browser arguments, which replace the wry defaults, so
+//!   they must be set with it. The credentials are unsupported, WebView2
+//!   prompts for them.
+//! - **Linux**: the proxy is a setting of the website data manager, shared by
+//!   the webviews using the same data directory. The credentials are part of the
+//!   proxy URL and the challenges they do not answer go to the authentication
+//!   handler.
+//! - **macOS**: only the proxy host and port are supported, set when the webview
+//!   is created.
+//! - **iOS / Android**: Unsupported.
+//!
+//! The unsupported settings fail the webview creation instead of being
+//! ignored.
+
+use tauri_runtime::{
+	Error,
+	Result,
+	webview::{ProxyAuthenticationChallenge, ProxyConfig, ProxyCredentials},
+};
+
+pub type ProxyAuthenticationHandler =
+	Box<dyn Fn(ProxyAuthenticationChallenge) -> Option<ProxyCredentials> + Send>;
+
+/// Whether the bypass list is supported.
+const SUPPORTS_BYPASS:bool = cfg!(any(
+	windows,
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+));
+
+/// Whether the credentials and the authentication handler are supported.
+const SUPPORTS_AUTHENTICATION:bool = cfg!(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+));
+
+/// Fails when `proxy` or an authentication handler, with
+/// `authentication_handler`, is not supported on this platform.
+pub fn check_support(proxy:Option<&ProxyConfig>, authentication_handler:bool) -> Result<()> {
+	check(proxy, authentication_handler, SUPPORTS_BYPASS, SUPPORTS_AUTHENTICATION)
+}
+
+fn check(
+	proxy:Option<&ProxyConfig>,
+	authentication_handler:bool,
+	supports_bypass:bool,
+	supports_authentication:bool,
+) -> Result<()> {
+	if !supports_bypass && proxy.is_some_and(|proxy| !proxy.bypass.is_empty()) {
+		return Err(Error::Proxy("the bypass list is unsupported on this platform".into()));
+	}
+
+	if !supports_authentication && proxy.is_some_and(|proxy| proxy.credentials.is_some()) {
+		return Err(Error::Proxy("the proxy credentials are unsupported on this platform".into()));
+	}
+
+	if !supports_authentication && authentication_handler {
+		return Err(Error::Proxy(
+			"the proxy authentication handler is unsupported on this platform".into(),
+		));
+	}
+
+	Ok(())
+}
+
+/// The browser arguments `args` with the bypass list of `proxy`, wry
+/// adding the proxy server itself.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn browser_args(args:Option<String>, proxy:&ProxyConfig) -> Result<Option<String>> {
+	if proxy.bypass.is_empty() {
+		return Ok(args);
+	}
+
+	// the arguments replace the wry defaults, which cannot be extended
+	let Some(args) = args else {
+		return Err(Error::Proxy(
+			"the bypass list requires the additional browser arguments to be set".into(),
+		));
+	};
+
+	Ok(Some(format!("{args} --proxy-bypass-list=\"{}\"", proxy.bypass.join(";"))))
+}
+
+/// Sets the proxy of `webview`, `None` for a direct connection.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn set(webview:&wry::WebView, proxy:Option<&ProxyConfig>) -> Result<()> {
+	use webkit2gtk::{NetworkProxyMode, NetworkProxySettings, WebViewExt, WebsiteDataManagerExt};
+	use wry::WebViewExtUnix;
+
+	let data_manager = webview
+		.webview()
+		.website_data_manager()
+		.ok_or_else(|| Error::Proxy("the webview has no website data manager".into()))?;
+
+	let Some(proxy) = proxy else {
+		data_manager.set_network_proxy_settings(NetworkProxyMode::NoProxy, None);
+
+		return Ok(());
+	};
+
+	if !matches!(proxy.url.scheme(), "http" | "socks5") {
+		return Err(Error::InvalidProxyUrl);
+	}
+
+	let mut url = proxy.url.clone();
+
+	if let Some(credentials) = &proxy.credentials {
+		url.set_username(&credentials.username)
+			.and_then(|()| url.set_password(Some(&credentials.password)))
+			.map_err(|()| Error::InvalidProxyUrl)?;
+	}
+
+	let bypass = proxy.bypass.iter().map(String::as_str).collect::<Vec<_>>();
+
+	let mut settings = NetworkProxySettings::new(Some(url.as_str()), &bypass);
+
+	data_manager.set_network_proxy_settings(NetworkProxyMode::Custom, Some(&mut settings));
+
+	Ok(())
+}
+
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn set(_webview:&wry::WebView, _proxy:Option<&ProxyConfig>) -> Result<()> {
+	Err(Error::Proxy("unsupported on this platform".into()))
+}
+
+/// Calls `handler` with the proxy authentication challenges of `webview`,
+/// cancelling the ones it does not answer.
+///
+/// The handler is rejected by [`check_support`] on the other platforms.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_authentication_handler(webview:&wry::WebView, handler:ProxyAuthenticationHandler) {
+	use webkit2gtk::{Credential, CredentialPersistence, WebViewExt};
+	use wry::WebViewExtUnix;
+
+	webview.webview().connect_authenticate(move |_, request| {
+		if !request.is_for_proxy() {
+			return false;
+		}
+
+		let challenge = ProxyAuthenticationChallenge {
+			host:request.host().map(|host| host.to_string()).unwrap_or_default(),
+			port:request.port() as u16,
+			realm:request.realm().map(|realm| realm.to_string()).unwrap_or_default(),
+			is_retry:request.is_retry(),
+		};
+
+		match handler(challenge) {
+			Some(credentials) => {
+				request.authenticate(Some(&Credential::new(
+					&credentials.username,
+					&credentials.password,
+					CredentialPersistence::ForSession,
+				)));
+			},
+			None => request.cancel(),
+		}
+
+		true
+	});
+}
+
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_authentication_handler(_webview:&wry::WebView, _handler:ProxyAuthenticationHandler) {}
+
+#[cfg(test)]
+mod tests {
+	use tauri_runtime::webview::{ProxyConfig, ProxyCredentials};
+
+	use super::{browser_args, check};
+
+	fn proxy() -> ProxyConfig { "http://localhost:8080".parse::<url::Url>().unwrap().into() }
+
+	fn with_bypass() -> ProxyConfig {
+		ProxyConfig { bypass:vec!["localhost".into(), "*.example.com".into()], ..proxy() }
+	}
+
+	fn with_credentials() -> ProxyConfig {
+		ProxyConfig {
+			credentials:Some(ProxyCredentials { username:"user".into(), password:"secret".into() }),
+			..proxy()
+		}
+	}
+
+	#[test]
+	fn plain_proxies_are_supported_everywhere() {
+		assert!(check(Some(&proxy()), false, false, false).is_ok());
+
+		assert!(check(None, false, false, false).is_ok());
+	}
+
+	#[test]
+	fn unsupported_settings_are_errors() {
+		assert!(check(Some(&with_bypass()), false, false, true).is_err());
+
+		assert!(check(Some(&with_credentials()), false, true, false).is_err());
+
+		assert!(check(None, true, true, false).is_err());
+
+		assert!(check(Some(&with_bypass()), false, true, false).is_ok());
+
+		assert!(check(Some(&with_credentials()), true, false, true).is_ok());
+	}
+
+	#[test]
+	fn the_bypass_list_is_added_to_the_browser_args() {
+		assert_eq!(
+			browser_args(Some("--disable-gpu".into()), &with_bypass()).unwrap().unwrap(),
+			"--disable-gpu --proxy-bypass-list=\"localhost;*.example.com\""
+		);
+
+		assert_eq!(browser_args(None, &proxy()).unwrap(), None);
+
+		// the wry default arguments are unknown
+		assert!(browser_args(None, &with_bypass()).is_err());
+	}
+}
@@ -0,0 +1,623 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! System theme.
+//!
+//! tao only reports the theme changes to the windows, and on Linux misses the
+//! changes made through the XDG desktop portal, so the system theme is read
+//! and watched here.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: reads the `AppsUseLightTheme` registry value, watched
+//!   through the `ImmersiveColorSet` `WM_SETTINGCHANGE` broadcast received by
+//!   a hidden window.
+//! - **Linux**: reads the `org.freedesktop.appearance` `color-scheme` setting
+//!   of the XDG desktop portal, watched through its `SettingChanged` signal.
+//!   The theme is light when there is no portal or no preference.
+//! - **macOS**: reads the effective appearance of the app, which follows the
+//!   system unless the app overrides it, observed with key-value observing.
+//! - **iOS / Android**: Unsupported, the theme is always light.
+
+use tauri_utils::Theme;
+
+#[cfg(windows)]
+const PERSONALIZE_KEY:&str = r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize";
+
+/// The current system theme.
+#[cfg(windows)]
+pub fn current() -> Theme {
+	use windows::{
+		Win32::System::Registry::{HKEY_CURRENT_USER, RRF_RT_REG_DWORD, RegGetValueW},
+		core::HSTRING,
+	};
+
+	let mut light = 1u32;
+
+	let mut size = std::mem::size_of::<u32>() as u32;
+
+	let result = unsafe {
+		RegGetValueW(
+			HKEY_CURRENT_USER,
+			&HSTRING::from(PERSONALIZE_KEY),
+			&HSTRING::from("AppsUseLightTheme"),
+			RRF_RT_REG_DWORD,
+			None,
+			Some(&mut light as *mut u32 as *mut _),
+			Some(&mut size),
+		)
+	};
+
+	if result.is_ok() && light == 0 { Theme::Dark } else { Theme::Light }
+}
+
+/// Watches the system theme until dropped.
+#[cfg(windows)]
+pub struct Watcher(Option<windows::Win32::Foundation::HWND>);
+
+#[cfg(windows)]
+struct WatcherState {
+	theme:std::cell::Cell<Theme>,
+	on_change:Box<dyn Fn(Theme)>,
+}
+
+/// Calls `on_change` with the system theme when it changes.
+///
+/// Must be called on the main thread, which receives the window messages.
+#[cfg(windows)]
+pub fn watch(on_change:impl Fn(Theme) + Send + 'static) -> Watcher {
+	use windows::{
+		Win32::{
+			Foundation::{HINSTANCE, HWND},
+			System::LibraryLoader::GetModuleHandleW,
+			UI::WindowsAndMessaging::{
+				CreateWindowExW,
+				GWLP_USERDATA,
+				HMENU,
+				RegisterClassW,
+				SetWindowLongPtrW,
+				WINDOW_EX_STYLE,
+				WINDOW_STYLE,
+				WNDCLASSW,
+			},
+		},
+		core::{PCWSTR, w},
+	};
+
+	const CLASS_NAME:PCWSTR = w!("TAURI_SYSTEM_THEME");
+
+	let module = unsafe { GetModuleHandleW(PCWSTR::null()).unwrap_or_default() };
+
+	let class = WNDCLASSW {
+		lpfnWndProc:Some(watcher_proc),
+		hInstance:HINSTANCE(module.0),
+		lpszClassName:CLASS_NAME,
+		..Default::default()
+	};
+
+	// fails when an earlier runtime registered it already
+	unsafe { RegisterClassW(&class) };
+
+	// message-only windows don't receive the broadcasts, so this is a
+	// top-level window that is never shown
+	let hwnd = unsafe {
+		CreateWindowExW(
+			WINDOW_EX_STYLE::default(),
+			CLASS_NAME,
+			PCWSTR::null(),
+			WINDOW_STYLE::default(),
+			0,
+			0,
+			0,
+			0,
+			HWND::default(),
+			HMENU::default(),
+			module,
+			None,
+		)
+	};
+
+	let hwnd = match hwnd {
+		Ok(hwnd) => hwnd,
+		Err(e) => {
+			log::error!("failed to watch the system theme: {e}");
+
+			return Watcher(None);
+		},
+	};
+
+	let state = Box::new(WatcherState {
+		theme:std::cell::Cell::new(current()),
+		on_change:Box::new(on_change),
+	});
+
+	unsafe { SetWindowLongPtrW(hwnd, GWLP_USERDATA, Box::into_raw(state) as _) };
+
+	Watcher(Some(hwnd))
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn watcher_proc(
+	hwnd:windows::Win32::Foundation::HWND,
+	msg:u32,
+	wparam:windows::Win32::Foundation::WPARAM,
+	lparam:windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+	use windows::{
+		Win32::UI::WindowsAndMessaging::{
+			DefWindowProcW,
+			GWLP_USERDATA,
+			GetWindowLongPtrW,
+			WM_SETTINGCHANGE,
+		},
+		core::PCWSTR,
+	};
+
+	// the theme changes are broadcast with the `ImmersiveColorSet` area
+	if msg == WM_SETTINGCHANGE
+		&& lparam.0 != 0
+		&& PCWSTR(lparam.0 as *const u16).to_string().is_ok_and(|area| area == "ImmersiveColorSet")
+	{
+		let state = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const WatcherState;
+
+		if let Some(state) = state.as_ref() {
+			let changed = current();
+
+			if state.theme.replace(changed) != changed {
+				(state.on_change)(changed);
+			}
+		}
+	}
+
+	DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+#[cfg(windows)]
+impl Drop for Watcher {
+	fn drop(&mut self) {
+		use windows::Win32::UI::WindowsAndMessaging::{
+			DestroyWindow,
+			GWLP_USERDATA,
+			SetWindowLongPtrW,
+		};
+
+		let Some(hwnd) = self.0.take() else {
+			return;
+		};
+
+		unsafe {
+			let state = SetWindowLongPtrW(hwnd, GWLP_USERDATA, 0) as *mut WatcherState;
+
+			let _ = DestroyWindow(hwnd);
+
+			if !state.is_null() {
+				drop(Box::from_raw(state));
+			}
+		}
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+mod portal {
+	use gtk::{gio, glib};
+	use tauri_utils::Theme;
+
+	pub const NAME:&str = "org.freedesktop.portal.Desktop";
+	pub const PATH:&str = "/org/freedesktop/portal/desktop";
+	pub const SETTINGS:&str = "org.freedesktop.portal.Settings";
+	pub const APPEARANCE:&str = "org.freedesktop.appearance";
+	pub const COLOR_SCHEME:&str = "color-scheme";
+
+	/// The theme of a `color-scheme` value, `None` without preference.
+	pub fn color_scheme(value:&glib::Variant) -> Option<Theme> {
+		// `Read` wraps the value in two variants
+		let mut value = value.clone();
+
+		while let Some(inner) = value.as_variant() {
+			value = inner;
+		}
+
+		match value.get::<u32>()? {
+			1 => Some(Theme::Dark),
+			2 => Some(Theme::Light),
+			_ => None,
+		}
+	}
+
+	pub fn connection() -> Option<gio::DBusConnection> {
+		gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE)
+			.map_err(|e| log::debug!("failed to connect to the session bus: {e}"))
+			.ok()
+	}
+
+	/// Reads the `color-scheme` setting, blocking on the portal for up to
+	/// 100ms.
+	pub fn read() -> Theme {
+		use gtk::glib::ToVariant;
+
+		let Some(connection) = connection() else {
+			return Theme::Light;
+		};
+
+		connection
+			.call_sync(
+				Some(NAME),
+				PATH,
+				SETTINGS,
+				"Read",
+				Some(&(APPEARANCE, COLOR_SCHEME).to_variant()),
+				None,
+				gio::DBusCallFlags::NONE,
+				100,
+				gio::Cancellable::NONE,
+			)
+			.map_err(|e| log::debug!("failed to read the portal color scheme: {e}"))
+			.ok()
+			.and_then(|reply| color_scheme(&reply.child_value(0)))
+			.unwrap_or(Theme::Light)
+	}
+}
+
+/// The last known system theme, kept up to date by the watchers so the portal
+/// is only read once.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd",
+	target_os = "macos"
+))]
+static THEME:std::sync::Mutex<Option<Theme>> = std::sync::Mutex::new(None);
+
+/// The current system theme.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn current() -> Theme { *THEME.lock().unwrap().get_or_insert_with(portal::read) }
+
+/// Watches the system theme until dropped.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub struct Watcher(Option<(gtk::gio::DBusConnection, gtk::gio::SignalSubscriptionId)>);
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+impl Drop for Watcher {
+	fn drop(&mut self) {
+		if let Some((connection, subscription)) = self.0.take() {
+			connection.signal_unsubscribe(subscription);
+		}
+	}
+}
+
+/// Calls `on_change` with the system theme when it changes.
+///
+/// Must be called on the main thread, which runs the signal handler.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn watch(on_change:impl Fn(Theme) + Send + 'static) -> Watcher {
+	use std::cell::Cell;
+
+	use gtk::gio;
+
+	let Some(connection) = portal::connection() else {
+		return Watcher(None);
+	};
+
+	// the portal isn't read until the theme is needed
+	let theme = Cell::new(*THEME.lock().unwrap());
+
+	let subscription = connection.signal_subscribe(
+		Some(portal::NAME),
+		Some(portal::SETTINGS),
+		Some("SettingChanged"),
+		Some(portal::PATH),
+		None,
+		gio::DBusSignalFlags::NONE,
+		move |_, _, _, _, _, parameters| {
+			let namespace = parameters.child_value(0);
+
+			let key = parameters.child_value(1);
+
+			if namespace.str() != Some(portal::APPEARANCE) || key.str() != Some(portal::COLOR_SCHEME) {
+				return;
+			}
+
+			let changed = portal::color_scheme(&parameters.child_value(2)).unwrap_or(Theme::Light);
+
+			*THEME.lock().unwrap() = Some(changed);
+
+			if theme.replace(Some(changed)) != Some(changed) {
+				on_change(changed);
+			}
+		},
+	);
+
+	Watcher(Some((connection, subscription)))
+}
+
+/// The theme of a window, which tao doesn't update when the theme changes
+/// through the XDG desktop portal.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+#[derive(Default)]
+pub struct WindowTheme {
+	/// The theme set on the window, which then doesn't follow the system.
+	fixed:std::cell::Cell<Option<Theme>>,
+	/// The last theme reported to the window.
+	reported:std::cell::Cell<Option<Theme>>,
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+impl WindowTheme {
+	pub fn new(fixed:Option<Theme>) -> Self {
+		Self { fixed:std::cell::Cell::new(fixed), reported:Default::default() }
+	}
+
+	pub fn set_fixed(&self, theme:Option<Theme>) { self.fixed.set(theme); }
+
+	/// Records a theme change tao reported to the window.
+	pub fn reported(&self, theme:Theme) { self.reported.set(Some(theme)); }
+
+	/// Whether a system theme change must be reported to the window, in which
+	/// case it is recorded.
+	pub fn follow(&self, theme:Theme) -> bool {
+		if self.fixed.get().is_some() || self.reported.get() == Some(theme) {
+			return false;
+		}
+
+		self.reported.set(Some(theme));
+
+		true
+	}
+}
+
+/// The current system theme.
+#[cfg(target_os = "macos")]
+pub fn current() -> Theme {
+	// the app appearance can only be read on the main thread
+	match objc2_foundation::MainThreadMarker::new() {
+		Some(mtm) => macos::effective_theme(mtm),
+		None => THEME.lock().unwrap().unwrap_or(Theme::Light),
+	}
+}
+
+/// Watches the system theme until dropped.
+#[cfg(target_os = "macos")]
+pub struct Watcher(Option<objc2::rc::Retained<macos::ThemeObserver>>);
+
+#[cfg(target_os = "macos")]
+impl Drop for Watcher {
+	fn drop(&mut self) {
+		use objc2_app_kit::NSApplication;
+		use objc2_foundation::{MainThreadMarker, NSObjectNSKeyValueObserverRegistration};
+
+		if let Some(observer) = self.0.take() {
+			let app = NSApplication::sharedApplication(MainThreadMarker::from(&*observer));
+
+			unsafe { app.removeObserver_forKeyPath(&observer, macos::key_path()) };
+		}
+	}
+}
+
+/// Calls `on_change` with the system theme when it changes.
+///
+/// Must be called on the main thread, which owns the app appearance.
+#[cfg(target_os = "macos")]
+pub fn watch(on_change:impl Fn(Theme) + Send + 'static) -> Watcher {
+	use objc2_app_kit::NSApplication;
+	use objc2_foundation::{
+		MainThreadMarker,
+		NSKeyValueObservingOptions,
+		NSObjectNSKeyValueObserverRegistration,
+	};
+
+	let Some(mtm) = MainThreadMarker::new() else {
+		return Watcher(None);
+	};
+
+	let theme = macos::effective_theme(mtm);
+
+	*THEME.lock().unwrap() = Some(theme);
+
+	let observer = macos::ThemeObserver::new(mtm, theme, Box::new(on_change));
+
+	unsafe {
+		NSApplication::sharedApplication(mtm).addObserver_forKeyPath_options_context(
+			&observer,
+			macos::key_path(),
+			NSKeyValueObservingOptions::NSKeyValueObservingOptionNew,
+			std::ptr::null_mut(),
+		);
+	}
+
+	Watcher(Some(observer))
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::{cell::Cell, ffi::c_void};
+
+	use objc2::{
+		ClassType,
+		DeclaredClass,
+		declare_class,
+		msg_send_id,
+		mutability::MainThreadOnly,
+		rc::Retained,
+		runtime::{AnyObject, NSObject, NSObjectProtocol},
+	};
+	use objc2_app_kit::{
+		NSAppearanceCustomization,
+		NSAppearanceNameAqua,
+		NSAppearanceNameDarkAqua,
+		NSApplication,
+	};
+	use objc2_foundation::{MainThreadMarker, NSArray, NSDictionary, NSString, ns_string};
+	use tauri_utils::Theme;
+
+	pub fn key_path() -> &'static NSString { ns_string!("effectiveAppearance") }
+
+	/// The theme of the effective appearance of the app.
+	pub fn effective_theme(mtm:MainThreadMarker) -> Theme {
+		let appearance = unsafe { NSApplication::sharedApplication(mtm).effectiveAppearance() };
+
+		let best = unsafe {
+			appearance.bestMatchFromAppearancesWithNames(&NSArray::from_slice(&[
+				NSAppearanceNameAqua,
+				NSAppearanceNameDarkAqua,
+			]))
+		};
+
+		match best {
+			Some(name) if name.isEqualToString(unsafe { NSAppearanceNameDarkAqua }) => Theme::Dark,
+			_ => Theme::Light,
+		}
+	}
+
+	pub struct Ivars {
+		theme:Cell<Theme>,
+		on_change:Box<dyn Fn(Theme)>,
+	}
+
+	declare_class!(
+		pub struct ThemeObserver;
+
+		unsafe impl ClassType for ThemeObserver {
+			type Super = NSObject;
+			type Mutability = MainThreadOnly;
+			const NAME:&'static str = "TauriThemeObserver";
+		}
+
+		impl DeclaredClass for ThemeObserver {
+			type Ivars = Ivars;
+		}
+
+		unsafe impl ThemeObserver {
+			#[method(observeValueForKeyPath:ofObject:change:context:)]
+			fn observe_value(
+				&self,
+				_key_path:Option<&NSString>,
+				_object:Option<&AnyObject>,
+				_change:Option<&NSDictionary<NSString, AnyObject>>,
+				_context:*mut c_void,
+			) {
+				let changed = effective_theme(MainThreadMarker::from(self));
+
+				*super::THEME.lock().unwrap() = Some(changed);
+
+				if self.ivars().theme.replace(changed) != changed {
+					(self.ivars().on_change)(changed);
+				}
+			}
+		}
+
+		unsafe impl NSObjectProtocol for ThemeObserver {}
+	);
+
+	impl ThemeObserver {
+		pub fn new(
+			mtm:MainThreadMarker,
+			theme:Theme,
+			on_change:Box<dyn Fn(Theme)>,
+		) -> Retained<Self> {
+			let this = mtm.alloc::<Self>().set_ivars(Ivars { theme:Cell::new(theme), on_change });
+
+			unsafe { msg_send_id![super(this), init] }
+		}
+	}
+}
+
+/// The current system theme.
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub fn current() -> Theme { Theme::Light }
+
+/// Watches the system theme until dropped.
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub struct Watcher;
+
+/// Calls `on_change` with the system theme when it changes.
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub fn watch(_on_change:impl Fn(Theme) + Send + 'static) -> Watcher { Watcher }
+
+#[cfg(all(
+	test,
+	any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	)
+))]
+mod tests {
+	use tauri_utils::Theme;
+
+	use super::WindowTheme;
+
+	#[test]
+	fn portal_changes_are_reported_once_to_the_windows_following_the_system() {
+		let window = WindowTheme::new(None);
+
+		assert!(window.follow(Theme::Dark));
+
+		assert!(!window.follow(Theme::Dark));
+
+		// tao already reported the change to the window
+		window.reported(Theme::Light);
+
+		assert!(!window.follow(Theme::Light));
+
+		assert!(window.follow(Theme::Dark));
+
+		window.set_fixed(Some(Theme::Light));
+
+		assert!(!window.follow(Theme::Light));
+
+		let fixed = WindowTheme::new(Some(Theme::Dark));
+
+		assert!(!fixed.follow(Theme::Light));
+
+		fixed.set_fixed(None);
+
+		assert!(fixed.follow(Theme::Light));
+	}
+}
@@ -0,0 +1,123 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Shadows of undecorated windows on Linux.
+//!
+//! GTK only draws the window shadow and the resize borders around it as part
+//! of the client-side decorations, which undecorated windows do not have. A
+//! window asking for a shadow is then created with the client-side
+//! decorations and a header bar as its title bar, hidden while the window is
+//! undecorated, which keeps the shadow on both X11 and Wayland without showing
+//! a title bar.
+//!
+//! GTK only switches to the client-side decorations when the window is
+//! realized, so they are never added to or removed from a live window: the
+//! header bar is shown or hidden and the decorations turned off instead.
+
+#![cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+
+use gtk::prelude::*;
+use tao::{platform::unix::WindowExtUnix, window::Window};
+
+/// The name of the header bar used as the title bar.
+const TITLEBAR:&str = "tauri-undecorated-shadow";
+
+/// The style class of the transparent windows with a shadow.
+const TRANSPARENT:&str = "tauri-transparent-shadow";
+
+fn shadow_titlebar(window:&gtk::ApplicationWindow) -> Option<gtk::Widget> {
+	window.titlebar().filter(|titlebar| titlebar.widget_name() == TITLEBAR)
+}
+
+/// Whether `window` is decorated, ignoring the decorations used for the
+/// shadow.
+pub fn is_decorated(window:&Window) -> bool {
+	let window = window.gtk_window();
+
+	match shadow_titlebar(window) {
+		Some(titlebar) => titlebar.is_visible(),
+		None => window.is_decorated(),
+	}
+}
+
+/// Creates `window` with the client-side decorations, showing their header bar
+/// only when it is `decorated`.
+///
+/// Must be called before the webview is added to the window.
+pub fn install(window:&Window, decorated:bool) {
+	let window = window.gtk_window();
+
+	// the window has no content yet, so nothing is lost when GTK recreates it
+	let mapped = window.is_mapped();
+
+	if window.is_realized() {
+		window.unrealize();
+	}
+
+	let titlebar = gtk::HeaderBar::new();
+
+	titlebar.set_widget_name(TITLEBAR);
+
+	titlebar.set_show_close_button(true);
+
+	titlebar.set_title(window.title().as_deref());
+
+	// tao shows every child of the window
+	titlebar.set_no_show_all(true);
+
+	titlebar.set_visible(decorated);
+
+	window.set_titlebar(Some(&titlebar));
+
+	// GTK does not draw the shadow of app paintable windows, which tao
+	// transparent windows are, so their background is made transparent with
+	// CSS instead
+	if window.is_app_paintable() {
+		let provider = gtk::CssProvider::new();
+
+		let css = format!("window.{TRANSPARENT} {{ background: transparent; }}");
+
+		if let Err(e) = provider.load_from_data(css.as_bytes()) {
+			log::error!("failed to load the transparent shadow style: {e}");
+		} else {
+			let style = window.style_context();
+
+			style.add_provider(&provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION);
+
+			style.add_class(TRANSPARENT);
+
+			window.set_app_paintable(false);
+		}
+	}
+
+	window.set_decorated(true);
+
+	if mapped {
+		window.map();
+	}
+}
+
+/// Applies the decorations and the shadow to the live `window`.
+///
+/// The shadow has no effect on the windows created without it, see
+/// [`install`].
+pub fn update(window:&Window, decorated:bool, shadow:bool) {
+	let window = window.gtk_window();
+
+	match shadow_titlebar(window) {
+		Some(titlebar) => {
+			titlebar.set_visible(decorated);
+
+			// turning the client-side decorations off also removes their shadow
+			window.set_decorated(decorated || shadow);
+		},
+		None => window.set_decorated(decorated),
+	}
+}
@@ -0,0 +1,254 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview user agent.
+//!
+//! wry can only set the user agent when the webview is created, so it is read
+//! and changed on the platform webview here.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses `ICoreWebView2Settings2`. The change applies to the
+//!   requests made after it, the `navigator.userAgent` of the current page
+//!   being updated on the next navigation.
+//! - **Linux**: uses the `WebKitSettings` of the webview. The change applies to
+//!   the requests made after it and to the `navigator.userAgent` of the current
+//!   page.
+//! - **macOS**: uses the custom user agent of the `WKWebView`. The change
+//!   applies to the requests made after it, the `navigator.userAgent` of the
+//!   current page being updated on the next navigation. WebKit has no API to
+//!   read its default user agent, which is the frozen Safari one followed by
+//!   the application name of the webview configuration.
+//! - **iOS / Android**: Unsupported.
+
+use tauri_runtime::{Error, Result};
+
+/// Appends `suffix` to the current user agent of `webview`.
+pub fn append(webview:&wry::WebView, suffix:&str) -> Result<()> {
+	let user_agent = get(webview)?;
+
+	set(webview, &appended(&user_agent, suffix))
+}
+
+/// Sets the user agent of `webview`, rejecting the values that can't be sent
+/// as an HTTP header.
+pub fn set(webview:&wry::WebView, user_agent:&str) -> Result<()> {
+	validate(user_agent)?;
+
+	apply(webview, user_agent)
+}
+
+/// `user_agent` followed by `suffix`, separated by a space.
+fn appended(user_agent:&str, suffix:&str) -> String {
+	match (user_agent.trim_end(), suffix.trim()) {
+		(user_agent, "") => user_agent.to_string(),
+		("", suffix) => suffix.to_string(),
+		(user_agent, suffix) => format!("{user_agent} {suffix}"),
+	}
+}
+
+/// Fails if the user agent has control characters, such as line breaks that
+/// would split the `User-Agent` request header.
+fn validate(user_agent:&str) -> Result<()> {
+	if user_agent.chars().any(char::is_control) {
+		return Err(Error::UserAgent(format!(
+			"the user agent `{}` has control characters",
+			user_agent.escape_debug()
+		)));
+	}
+
+	Ok(())
+}
+
+#[cfg(windows)]
+fn settings(
+	webview:&wry::WebView,
+) -> Result<webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2Settings2> {
+	use webview2_com::Microsoft::Web::WebView2::Win32::ICoreWebView2Settings2;
+	use windows::core::Interface;
+	use wry::WebViewExtWindows;
+
+	unsafe { webview.webview().Settings() }
+		.and_then(|settings| settings.cast::<ICoreWebView2Settings2>())
+		.map_err(|e| Error::UserAgent(e.to_string()))
+}
+
+/// The user agent of `webview`.
+#[cfg(windows)]
+pub fn get(webview:&wry::WebView) -> Result<String> {
+	use windows::core::PWSTR;
+
+	let mut user_agent = PWSTR::null();
+
+	unsafe { settings(webview)?.UserAgent(&mut user_agent) }
+		.map_err(|e| Error::UserAgent(e.to_string()))?;
+
+	Ok(webview2_com::take_pwstr(user_agent))
+}
+
+#[cfg(windows)]
+fn apply(webview:&wry::WebView, user_agent:&str) -> Result<()> {
+	use windows::core::HSTRING;
+
+	unsafe { settings(webview)?.SetUserAgent(&HSTRING::from(user_agent)) }
+		.map_err(|e| Error::UserAgent(e.to_string()))
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn settings(webview:&wry::WebView) -> Result<webkit2gtk::Settings> {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	WebViewExt::settings(&webview.webview())
+		.ok_or_else(|| Error::UserAgent("the webview has no settings".into()))
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn get(webview:&wry::WebView) -> Result<String> {
+	use webkit2gtk::SettingsExt;
+
+	Ok(settings(webview)?.user_agent().map(|user_agent| user_agent.to_string()).unwrap_or_default())
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn apply(webview:&wry::WebView, user_agent:&str) -> Result<()> {
+	use webkit2gtk::SettingsExt;
+
+	settings(webview)?.set_user_agent(Some(user_agent));
+
+	Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub fn get(webview:&wry::WebView) -> Result<String> {
+	use objc2::rc::Retained;
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	let webview = Retained::cast::<WKWebView>(webview.webview());
+
+	unsafe {
+		// an empty custom user agent means the default one
+		if let Some(user_agent) = webview.customUserAgent().filter(|u| !u.is_empty()) {
+			return Ok(user_agent.to_string());
+		}
+
+		let name = webview.configuration().applicationNameForUserAgent();
+
+		Ok(macos_default(name.map(|name| name.to_string()).as_deref()))
+	}
+}
+
+/// The default user agent of WebKit, followed by the application name of the
+/// webview configuration.
+#[cfg(target_os = "macos")]
+fn macos_default(application_name:Option<&str>) -> String {
+	// the user agent of Safari, frozen since macOS 10.15.7
+	const DEFAULT:&str =
+		"Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko)";
+
+	appended(DEFAULT, application_name.unwrap_or_default())
+}
+
+#[cfg(target_os = "macos")]
+fn apply(webview:&wry::WebView, user_agent:&str) -> Result<()> {
+	use objc2::rc::Retained;
+	use objc2_foundation::NSString;
+	use objc2_web_kit::WKWebView;
+	use wry::WebViewExtMacOS;
+
+	let webview = Retained::cast::<WKWebView>(webview.webview());
+
+	unsafe { webview.setCustomUserAgent(Some(&NSString::from_str(user_agent))) };
+
+	Ok(())
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn get(_webview:&wry::WebView) -> Result<String> {
+	Err(Error::UserAgent("unsupported on this platform".into()))
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+fn apply(_webview:&wry::WebView, _user_agent:&str) -> Result<()> {
+	Err(Error::UserAgent("unsupported on this platform".into()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn suffixes_are_separated_by_a_space() {
+		assert_eq!(appended("Mozilla/5.0", "MyApp/1.0"), "Mozilla/5.0 MyApp/1.0");
+		assert_eq!(appended("Mozilla/5.0 ", " MyApp/1.0"), "Mozilla/5.0 MyApp/1.0");
+	}
+
+	#[test]
+	fn empty_parts_are_not_separated() {
+		assert_eq!(appended("", "MyApp/1.0"), "MyApp/1.0");
+		assert_eq!(appended("Mozilla/5.0", ""), "Mozilla/5.0");
+		assert_eq!(appended("", ""), "");
+	}
+
+	#[test]
+	fn user_agents_with_control_characters_are_rejected() {
+		assert!(validate("Mozilla/5.0 (X11; Linux x86_64) MyApp/1.0").is_ok());
+		assert!(validate("").is_ok());
+
+		for invalid in ["MyApp/1.0\r\nX-Injected: 1", "MyApp/1.0\n", "MyApp\0/1.0", "MyApp\t1.0"] {
+			assert!(matches!(validate(invalid), Err(Error::UserAgent(_))), "{invalid:?} was accepted");
+		}
+	}
+
+	#[test]
+	fn whitespace_around_suffixes_is_trimmed() {
+		assert!(validate(&appended("Mozilla/5.0", "MyApp/1.0\n")).is_ok());
+		assert!(validate(&appended("Mozilla/5.0", "My\nApp/1.0")).is_err());
+	}
+
+	#[test]
+	#[cfg(target_os = "macos")]
+	fn macos_default_ends_with_the_application_name() {
+		let default = macos_default(None);
+
+		assert!(default.starts_with("Mozilla/5.0 (Macintosh;"));
+		assert_eq!(macos_default(Some("")), default);
+		assert_eq!(macos_default(Some("Version/17.0 Safari/605.1.15")), format!("{default} Version/17.0 Safari/605.1.15"));
+	}
+}
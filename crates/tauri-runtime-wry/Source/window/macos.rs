@@ -2,9 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use objc2_app_kit::{NSBackingStoreType, NSWindow, NSWindowStyleMask};
+use objc2_app_kit::{NSBackingStoreType, NSWindow, NSWindowButton, NSWindowStyleMask};
 use objc2_foundation::MainThreadMarker;
 use tao::platform::macos::WindowExtMacOS;
+use tauri_runtime::{
+	Rect,
+	dpi::{PhysicalPosition, PhysicalSize, Position},
+};
+
+const WINDOW_BUTTONS:[NSWindowButton; 3] = [
+	NSWindowButton::NSWindowCloseButton,
+	NSWindowButton::NSWindowMiniaturizeButton,
+	NSWindowButton::NSWindowZoomButton,
+];
 
 impl super::WindowExt for tao::window::Window {
 	// based on electron implementation
@@ -43,4 +53,102 @@ impl super::WindowExt for tao::window::Window {
 
 		ns_window.center();
 	}
+
+	// based on electron `setTrafficLightPosition` implementation
+	fn set_traffic_light_position(&self, position:Position) {
+		let ns_window:&NSWindow = unsafe { &*self.ns_window().cast() };
+
+		let position = position.to_logical::<f64>(self.scale_factor());
+
+		unsafe {
+			let [Some(close), Some(miniaturize), Some(zoom)] =
+				WINDOW_BUTTONS.map(|button| ns_window.standardWindowButton(button))
+			else {
+				return;
+			};
+
+			// the buttons are laid out in the title bar container view
+			let Some(title_bar_container) = close.superview().and_then(|view| view.superview())
+			else {
+				return;
+			};
+
+			let mut title_bar_frame = title_bar_container.frame();
+
+			title_bar_frame.size.height = close.frame().size.height + position.y;
+			title_bar_frame.origin.y = ns_window.frame().size.height - title_bar_frame.size.height;
+
+			title_bar_container.setFrame(title_bar_frame);
+
+			let spacing = miniaturize.frame().origin.x - close.frame().origin.x;
+
+			for (i, button) in [close, miniaturize, zoom].iter().enumerate() {
+				let mut origin = button.frame().origin;
+
+				origin.x = position.x + i as f64 * spacing;
+				origin.y = 0.;
+
+				button.setFrameOrigin(origin);
+			}
+		}
+	}
+
+	fn title_bar_rects(&self) -> Vec<Rect> {
+		let ns_window:&NSWindow = unsafe { &*self.ns_window().cast() };
+
+		unsafe {
+			let Some(content_view) = ns_window.contentView() else {
+				return Vec::new();
+			};
+
+			let Some((min_x, min_y, max_x, max_y)) = WINDOW_BUTTONS
+				.into_iter()
+				.filter_map(|button| ns_window.standardWindowButton(button))
+				.filter(|button| !button.isHidden())
+				.map(|button| button.convertRect_toView(button.bounds(), Some(&content_view)))
+				.map(|frame| {
+					(
+						frame.origin.x,
+						frame.origin.y,
+						frame.origin.x + frame.size.width,
+						frame.origin.y + frame.size.height,
+					)
+				})
+				.reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+			else {
+				return Vec::new();
+			};
+
+			// from the top-left corner of the content view
+			let top = if content_view.isFlipped() {
+				min_y
+			} else {
+				content_view.frame().size.height - max_y
+			};
+
+			let bottom = top + max_y - min_y;
+
+			// the buttons are above the content view without a full size content view
+			if bottom <= 0. {
+				return Vec::new();
+			}
+
+			let top = top.max(0.);
+
+			let scale_factor = ns_window.backingScaleFactor();
+
+			vec![Rect {
+				position:PhysicalPosition::new(
+					(min_x * scale_factor).round() as i32,
+					(top * scale_factor).round() as i32,
+				)
+				.into(),
+				size:PhysicalSize::new(
+					((max_x - min_x) * scale_factor).round() as u32,
+					((bottom - top) * scale_factor).round() as u32,
+				)
+				.into(),
+			}]
+		}
+	}
 }
@@ -37,6 +37,24 @@ pub trait WindowExt {
 	/// - **Android / iOS**: Unsupported.
 	fn center(&self) {}
 
+	/// Moves the window controls (traffic lights) to `position`, from the
+	/// top-left corner of the window.
+	#[cfg(target_os = "macos")]
+	fn set_traffic_light_position(&self, position:tauri_runtime::dpi::Position);
+
+	/// Extends the client area into the title bar, keeping the caption buttons
+	/// drawn by the system over it.
+	#[cfg(windows)]
+	fn set_titlebar_overlay(&self, enabled:bool);
+
+	/// The regions of the client area covered by the title bar controls, in
+	/// physical pixels.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Linux / iOS / Android**: Unsupported, returns an empty list.
+	fn title_bar_rects(&self) -> Vec<tauri_runtime::Rect> { Vec::new() }
+
 	/// Clears the window sufrace. i.e make it it transparent.
 	#[cfg(windows)]
 	fn draw_surface(
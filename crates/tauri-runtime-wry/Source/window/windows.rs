@@ -3,12 +3,70 @@
 // SPDX-License-Identifier: MIT
 
 use tao::platform::windows::WindowExtWindows;
+use tauri_runtime::{
+	Rect,
+	dpi::{PhysicalPosition, PhysicalSize},
+};
+use std::cell::Cell;
+
 use windows::Win32::{
-	Foundation::{HWND, RECT},
-	Graphics::Dwm::{DWMWA_EXTENDED_FRAME_BOUNDS, DwmGetWindowAttribute},
-	UI::Input::KeyboardAndMouse::{EnableWindow, IsWindowEnabled},
+	Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM},
+	Graphics::{
+		Dwm::{
+			DWMWA_CAPTION_BUTTON_BOUNDS,
+			DWMWA_EXTENDED_FRAME_BOUNDS,
+			DwmDefWindowProc,
+			DwmExtendFrameIntoClientArea,
+			DwmGetWindowAttribute,
+		},
+		Gdi::{
+			ClientToScreen,
+			CombineRgn,
+			CreateRectRgn,
+			DeleteObject,
+			HRGN,
+			MapWindowPoints,
+			RGN_DIFF,
+			SetWindowRgn,
+		},
+	},
+	UI::{
+		Controls::MARGINS,
+		HiDpi::{AdjustWindowRectExForDpi, GetDpiForWindow},
+		Input::KeyboardAndMouse::{EnableWindow, IsWindowEnabled},
+		Shell::{DefSubclassProc, GetWindowSubclass, RemoveWindowSubclass, SetWindowSubclass},
+		WindowsAndMessaging::{
+			GW_CHILD,
+			GW_HWNDNEXT,
+			GetWindow,
+			GetWindowInfo,
+			GetWindowRect,
+			HTCLIENT,
+			HTTOP,
+			IsZoomed,
+			NCCALCSIZE_PARAMS,
+			SWP_FRAMECHANGED,
+			SWP_NOACTIVATE,
+			SWP_NOMOVE,
+			SWP_NOSIZE,
+			SWP_NOZORDER,
+			SetWindowPos,
+			WINDOWINFO,
+			WM_CREATE,
+			WM_DPICHANGED,
+			WM_NCCALCSIZE,
+			WM_NCDESTROY,
+			WM_NCHITTEST,
+			WM_PARENTNOTIFY,
+			WM_SIZE,
+			WM_USER,
+			WS_CAPTION,
+		},
+	},
 };
 
+const TITLEBAR_OVERLAY_SUBCLASS_ID:usize = (WM_USER + 2) as usize;
+
 impl super::WindowExt for tao::window::Window {
 	fn set_enabled(&self, enabled:bool) {
 		let _ = unsafe { EnableWindow(HWND(self.hwnd() as _), enabled) };
@@ -42,6 +100,57 @@ impl super::WindowExt for tao::window::Window {
 		}
 	}
 
+	fn set_titlebar_overlay(&self, enabled:bool) {
+		let hwnd = HWND(self.hwnd() as _);
+
+		unsafe {
+			let state = overlay_state(hwnd);
+
+			if enabled == state.is_some() {
+				return;
+			}
+
+			match state {
+				Some(state) => remove_titlebar_overlay(hwnd, state),
+				None => {
+					let _ = SetWindowSubclass(
+						hwnd,
+						Some(titlebar_overlay_proc),
+						TITLEBAR_OVERLAY_SUBCLASS_ID,
+						Box::into_raw(Box::<OverlayState>::default()) as usize,
+					);
+				},
+			}
+
+			// recomputes the client area
+			let _ = SetWindowPos(
+				hwnd,
+				HWND::default(),
+				0,
+				0,
+				0,
+				0,
+				SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+			);
+
+			match overlay_state(hwnd) {
+				Some(state) => update_titlebar_overlay(hwnd, &*state),
+				None => {
+					extend_frame(hwnd, 0);
+
+					clip_children(hwnd, None);
+				},
+			}
+		}
+	}
+
+	fn title_bar_rects(&self) -> Vec<Rect> {
+		unsafe { caption_button_bounds(HWND(self.hwnd() as _)) }
+			.and_then(title_bar_rect)
+			.into_iter()
+			.collect()
+	}
+
 	fn draw_surface(
 		&self,
 		surface:&mut softbuffer::Surface<
@@ -65,3 +174,331 @@ impl super::WindowExt for tao::window::Window {
 		}
 	}
 }
+
+/// The title bar overlay state of a window, to only update the frame and the
+/// child windows when the caption buttons change.
+#[derive(Default)]
+struct OverlayState {
+	top_margin:Cell<Option<i32>>,
+	buttons:Cell<Option<RECT>>,
+}
+
+/// Sets `cell` to `value`, returning whether it changed.
+fn replace_changed<T:Copy + PartialEq>(cell:&Cell<T>, value:T) -> bool {
+	cell.replace(value) != value
+}
+
+/// The title bar overlay state of `hwnd`, `None` without the overlay.
+unsafe fn overlay_state(hwnd:HWND) -> Option<*mut OverlayState> {
+	let mut state = 0usize;
+
+	GetWindowSubclass(
+		hwnd,
+		Some(titlebar_overlay_proc),
+		TITLEBAR_OVERLAY_SUBCLASS_ID,
+		Some(&mut state as *mut _),
+	)
+	.as_bool()
+	.then_some(state as *mut OverlayState)
+}
+
+unsafe fn remove_titlebar_overlay(hwnd:HWND, state:*mut OverlayState) {
+	let _ = RemoveWindowSubclass(hwnd, Some(titlebar_overlay_proc), TITLEBAR_OVERLAY_SUBCLASS_ID);
+
+	drop(Box::from_raw(state));
+}
+
+/// Extends the frame over the title bar and clips the child windows, when
+/// the caption buttons moved.
+unsafe fn update_titlebar_overlay(hwnd:HWND, state:&OverlayState) {
+	let buttons = caption_button_bounds(hwnd);
+
+	// the caption buttons and the frame above them
+	let top_margin = buttons.map_or(0, |buttons| buttons.bottom.max(0));
+
+	if replace_changed(&state.top_margin, Some(top_margin)) {
+		extend_frame(hwnd, top_margin);
+	}
+
+	if replace_changed(&state.buttons, buttons) {
+		clip_children(hwnd, buttons);
+	}
+}
+
+// based on https://learn.microsoft.com/en-us/windows/win32/dwm/customframe
+unsafe extern "system" fn titlebar_overlay_proc(
+	hwnd:HWND,
+	msg:u32,
+	wparam:WPARAM,
+	lparam:LPARAM,
+	_:usize,
+	state:usize,
+) -> LRESULT {
+	// the caption buttons are drawn and handled by DWM
+	let mut result = LRESULT(0);
+
+	if DwmDefWindowProc(hwnd, msg, wparam, lparam, &mut result).as_bool() {
+		return result;
+	}
+
+	let state = &*(state as *const OverlayState);
+
+	match msg {
+		WM_NCCALCSIZE if wparam.0 != 0 => {
+			let params = &mut *(lparam.0 as *mut NCCALCSIZE_PARAMS);
+
+			let top = params.rgrc[0].top;
+
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+
+			// removes the title bar, a maximized window overflows the monitor by its frame
+			params.rgrc[0].top =
+				if IsZoomed(hwnd).as_bool() { top + resize_border(hwnd) } else { top };
+
+			result
+		},
+
+		WM_NCHITTEST => {
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+
+			if result.0 != HTCLIENT as isize || IsZoomed(hwnd).as_bool() {
+				return result;
+			}
+
+			// the top resize border is part of the client area
+			let y = (((lparam.0 as usize) & 0xFFFF_0000) >> 16) as u16 as i16 as i32;
+
+			let mut rect = RECT::default();
+
+			if GetWindowRect(hwnd, &mut rect).is_ok()
+				&& is_top_resize_border(y, rect.top, resize_border(hwnd))
+			{
+				return LRESULT(HTTOP as _);
+			}
+
+			result
+		},
+
+		WM_SIZE | WM_DPICHANGED => {
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+
+			update_titlebar_overlay(hwnd, state);
+
+			result
+		},
+
+		WM_PARENTNOTIFY if (wparam.0 & 0xFFFF) as u32 == WM_CREATE => {
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+
+			// the new child window is not clipped yet
+			clip_children(hwnd, state.buttons.get());
+
+			result
+		},
+
+		WM_NCDESTROY => {
+			let result = DefSubclassProc(hwnd, msg, wparam, lparam);
+
+			remove_titlebar_overlay(hwnd, state as *const OverlayState as *mut OverlayState);
+
+			result
+		},
+
+		_ => DefSubclassProc(hwnd, msg, wparam, lparam),
+	}
+}
+
+/// The height of the top resize border, for the window style and DPI.
+unsafe fn resize_border(hwnd:HWND) -> i32 {
+	let mut info =
+		WINDOWINFO { cbSize:std::mem::size_of::<WINDOWINFO>() as u32, ..Default::default() };
+
+	if GetWindowInfo(hwnd, &mut info).is_err() {
+		return 0;
+	}
+
+	// the frame of the window without its title bar
+	let mut frame = RECT::default();
+
+	if AdjustWindowRectExForDpi(
+		&mut frame,
+		info.dwStyle & !WS_CAPTION,
+		false,
+		info.dwExStyle,
+		GetDpiForWindow(hwnd),
+	)
+	.is_err()
+	{
+		return 0;
+	}
+
+	-frame.top
+}
+
+/// Whether the screen `y` is on the top resize border of a window at `top`.
+fn is_top_resize_border(y:i32, top:i32, border:i32) -> bool { y >= top && y < top + border }
+
+unsafe fn extend_frame(hwnd:HWND, top_margin:i32) {
+	let margins = MARGINS { cyTopHeight:top_margin, ..Default::default() };
+
+	let _ = DwmExtendFrameIntoClientArea(hwnd, &margins);
+}
+
+/// The caption buttons bounds, relative to the top-left corner of the client
+/// area.
+unsafe fn caption_button_bounds(hwnd:HWND) -> Option<RECT> {
+	let mut bounds = RECT::default();
+
+	DwmGetWindowAttribute(
+		hwnd,
+		DWMWA_CAPTION_BUTTON_BOUNDS,
+		&mut bounds as *mut _ as *mut _,
+		std::mem::size_of::<RECT>() as u32,
+	)
+	.ok()?;
+
+	let mut window = RECT::default();
+
+	GetWindowRect(hwnd, &mut window).ok()?;
+
+	let mut client_origin = POINT::default();
+
+	if !ClientToScreen(hwnd, &mut client_origin).as_bool() {
+		return None;
+	}
+
+	to_client_bounds(bounds, window, client_origin)
+}
+
+/// The caption button `bounds` relative to the `window` rect, moved relative
+/// to the client area at `client_origin`.
+fn to_client_bounds(bounds:RECT, window:RECT, client_origin:POINT) -> Option<RECT> {
+	if bounds.right <= bounds.left || bounds.bottom <= bounds.top {
+		return None;
+	}
+
+	let (x, y) = (window.left - client_origin.x, window.top - client_origin.y);
+
+	Some(RECT {
+		left:bounds.left + x,
+		top:bounds.top + y,
+		right:bounds.right + x,
+		bottom:bounds.bottom + y,
+	})
+}
+
+/// The part of the caption button `bounds` over the client area.
+fn title_bar_rect(bounds:RECT) -> Option<Rect> {
+	// the buttons are above the client area without the title bar overlay
+	let top = bounds.top.max(0);
+
+	if bounds.bottom <= top || bounds.right <= bounds.left {
+		return None;
+	}
+
+	Some(Rect {
+		position:PhysicalPosition::new(bounds.left, top).into(),
+		size:PhysicalSize::new((bounds.right - bounds.left) as u32, (bounds.bottom - top) as u32)
+			.into(),
+	})
+}
+
+/// Keeps the child windows, i.e. the webviews, from covering the caption
+/// `buttons`.
+unsafe fn clip_children(hwnd:HWND, buttons:Option<RECT>) {
+	let mut child = GetWindow(hwnd, GW_CHILD);
+
+	while let Ok(current) = child {
+		let region = match buttons {
+			Some(buttons) => {
+				let mut points = [
+					POINT { x:buttons.left, y:buttons.top },
+					POINT { x:buttons.right, y:buttons.bottom },
+				];
+
+				MapWindowPoints(hwnd, current, &mut points);
+
+				// larger than the child window, so it does not need to follow its size
+				let region = CreateRectRgn(0, 0, i16::MAX as i32, i16::MAX as i32);
+
+				let buttons = CreateRectRgn(points[0].x, points[0].y, points[1].x, points[1].y);
+
+				CombineRgn(region, region, buttons, RGN_DIFF);
+
+				let _ = DeleteObject(buttons);
+
+				region
+			},
+			None => HRGN::default(),
+		};
+
+		// the system owns the region from now on
+		SetWindowRgn(current, region, true);
+
+		child = GetWindow(current, GW_HWNDNEXT);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::cell::Cell;
+
+	use tauri_runtime::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+	use windows::Win32::Foundation::{POINT, RECT};
+
+	use super::{is_top_resize_border, replace_changed, title_bar_rect, to_client_bounds};
+
+	fn rect(left:i32, top:i32, right:i32, bottom:i32) -> RECT { RECT { left, top, right, bottom } }
+
+	#[test]
+	fn caption_buttons_are_moved_relative_to_the_client_area() {
+		// a window at (100, 50) with its client area 8px right and 31px down
+		let bounds = to_client_bounds(
+			rect(700, 1, 838, 31),
+			rect(100, 50, 946, 650),
+			POINT { x:108, y:81 },
+		);
+
+		assert_eq!(bounds, Some(rect(692, -30, 830, 0)));
+
+		let empty = to_client_bounds(rect(0, 0, 0, 0), rect(0, 0, 800, 600), POINT::default());
+
+		assert_eq!(empty, None);
+	}
+
+	#[test]
+	fn title_bar_rects_are_the_buttons_over_the_client_area() {
+		let rect_ = title_bar_rect(rect(692, -8, 830, 22)).unwrap();
+
+		assert_eq!(rect_.position, Position::Physical(PhysicalPosition::new(692, 0)));
+
+		assert_eq!(rect_.size, Size::Physical(PhysicalSize::new(138, 22)));
+
+		// a title bar above the client area
+		assert!(title_bar_rect(rect(692, -30, 830, 0)).is_none());
+	}
+
+	#[test]
+	fn the_top_resize_border_is_hit_below_the_window_top() {
+		assert!(is_top_resize_border(100, 100, 8));
+
+		assert!(is_top_resize_border(107, 100, 8));
+
+		assert!(!is_top_resize_border(108, 100, 8));
+
+		assert!(!is_top_resize_border(99, 100, 8));
+	}
+
+	#[test]
+	fn overlay_updates_only_run_on_changes() {
+		let buttons = Cell::new(None);
+
+		assert!(replace_changed(&buttons, Some(rect(692, 0, 830, 22))));
+
+		assert!(!replace_changed(&buttons, Some(rect(692, 0, 830, 22))));
+
+		assert!(replace_changed(&buttons, Some(rect(892, 0, 1030, 22))));
+
+		assert!(replace_changed(&buttons, None));
+	}
+}
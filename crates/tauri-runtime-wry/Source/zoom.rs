@@ -0,0 +1,252 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Webview zoom tracking.
+//!
+//! wry can only set the zoom, so the applied zoom is tracked here to be read
+//! back, restored when a navigation resets it and reported when it changes.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: changes made by the user with `ctrl` + wheel or the zoom
+//!   hotkeys are read from the `ZoomFactorChanged` event.
+//! - **Linux**: changes are read from the `zoom-level` property notifications.
+//! - **macOS**: changes are read from the key-value observation of the
+//!   `pageZoom` property, which the zoom hotkeys polyfill also sets. Pinch
+//!   magnification doesn't change the zoom.
+//! - **iOS / Android**: Unsupported.
+
+use std::cell::Cell;
+
+/// The zoom applied to a webview.
+pub struct ZoomState {
+	factor:Cell<f64>,
+	on_change:Box<dyn Fn(f64)>,
+}
+
+impl ZoomState {
+	/// Tracks a webview zoomed to `factor`, calling `on_change` when it changes.
+	pub fn new(factor:f64, on_change:impl Fn(f64) + 'static) -> Self {
+		Self { factor:Cell::new(factor), on_change:Box::new(on_change) }
+	}
+
+	/// The zoom factor applied to the webview.
+	pub fn factor(&self) -> f64 { self.factor.get() }
+
+	/// Records `factor` as the applied zoom, reporting it if it changed.
+	pub fn update(&self, factor:f64) {
+		if (self.factor.replace(factor) - factor).abs() > f64::EPSILON {
+			(self.on_change)(factor);
+		}
+	}
+}
+
+/// Calls `handler` with the new zoom factor when the platform webview zoom
+/// changes.
+#[cfg(windows)]
+pub fn attach_change_handler(webview:&wry::WebView, handler:impl Fn(f64) + 'static) {
+	use webview2_com::ZoomFactorChangedEventHandler;
+	use windows::Win32::System::WinRT::EventRegistrationToken;
+	use wry::WebViewExtWindows;
+
+	let mut token = EventRegistrationToken::default();
+
+	let result = unsafe {
+		webview.controller().add_ZoomFactorChanged(
+			&ZoomFactorChangedEventHandler::create(Box::new(move |controller, _| {
+				if let Some(controller) = controller {
+					let mut factor = 1.0;
+
+					controller.ZoomFactor(&mut factor)?;
+
+					handler(factor);
+				}
+
+				Ok(())
+			})),
+			&mut token,
+		)
+	};
+
+	if let Err(e) = result {
+		log::error!("failed to listen to the webview zoom changes: {e}");
+	}
+}
+
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+pub fn attach_change_handler(webview:&wry::WebView, handler:impl Fn(f64) + 'static) {
+	use webkit2gtk::WebViewExt;
+	use wry::WebViewExtUnix;
+
+	webview.webview().connect_zoom_level_notify(move |webview| handler(webview.zoom_level()));
+}
+
+#[cfg(target_os = "macos")]
+pub fn attach_change_handler(webview:&wry::WebView, handler:impl Fn(f64) + 'static) {
+	use objc2_foundation::{
+		MainThreadMarker,
+		NSKeyValueObservingOptions,
+		NSObjectNSKeyValueObserverRegistration,
+		ns_string,
+	};
+	use wry::WebViewExtMacOS;
+
+	let Some(mtm) = MainThreadMarker::new() else {
+		return;
+	};
+
+	let wk_webview = webview.webview();
+
+	let observer = macos::ZoomObserver::new(mtm, Box::new(handler));
+
+	unsafe {
+		wk_webview.addObserver_forKeyPath_options_context(
+			&observer,
+			ns_string!("pageZoom"),
+			NSKeyValueObservingOptions::NSKeyValueObservingOptionNew,
+			std::ptr::null_mut(),
+		);
+	}
+
+	// the observer must outlive its registration, which ends with the webview
+	macos::retain_with(&wk_webview, observer);
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+	use std::ffi::c_void;
+
+	use objc2::{
+		ClassType,
+		DeclaredClass,
+		declare_class,
+		msg_send,
+		msg_send_id,
+		mutability::MainThreadOnly,
+		rc::Retained,
+		runtime::{AnyObject, NSObject, NSObjectProtocol},
+	};
+	use objc2_foundation::{MainThreadMarker, NSDictionary, NSString};
+	use objc2_web_kit::WKWebView;
+
+	pub struct Ivars {
+		handler:Box<dyn Fn(f64)>,
+	}
+
+	declare_class!(
+		pub struct ZoomObserver;
+
+		unsafe impl ClassType for ZoomObserver {
+			type Super = NSObject;
+			type Mutability = MainThreadOnly;
+			const NAME:&'static str = "TauriZoomObserver";
+		}
+
+		impl DeclaredClass for ZoomObserver {
+			type Ivars = Ivars;
+		}
+
+		unsafe impl ZoomObserver {
+			#[method(observeValueForKeyPath:ofObject:change:context:)]
+			fn observe_value(
+				&self,
+				_key_path:Option<&NSString>,
+				object:Option<&AnyObject>,
+				_change:Option<&NSDictionary<NSString, AnyObject>>,
+				_context:*mut c_void,
+			) {
+				if let Some(object) = object {
+					let factor:f64 = unsafe { msg_send![object, pageZoom] };
+
+					(self.ivars().handler)(factor);
+				}
+			}
+		}
+
+		unsafe impl NSObjectProtocol for ZoomObserver {}
+	);
+
+	impl ZoomObserver {
+		pub fn new(mtm:MainThreadMarker, handler:Box<dyn Fn(f64)>) -> Retained<Self> {
+			let this = mtm.alloc::<Self>().set_ivars(Ivars { handler });
+
+			unsafe { msg_send_id![super(this), init] }
+		}
+	}
+
+	/// Keeps `observer` alive as long as `webview`.
+	pub fn retain_with(webview:&WKWebView, observer:Retained<ZoomObserver>) {
+		use objc2::ffi::{OBJC_ASSOCIATION_RETAIN_NONATOMIC, objc_setAssociatedObject};
+
+		static KEY:u8 = 0;
+
+		unsafe {
+			objc_setAssociatedObject(
+				webview as *const WKWebView as *mut _,
+				&KEY as *const u8 as *const _,
+				Retained::as_ptr(&observer) as *mut _,
+				OBJC_ASSOCIATION_RETAIN_NONATOMIC,
+			);
+		}
+	}
+}
+
+#[cfg(not(any(
+	windows,
+	target_os = "macos",
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+pub fn attach_change_handler(_webview:&wry::WebView, _handler:impl Fn(f64) + 'static) {}
+
+#[cfg(test)]
+mod tests {
+	use std::{cell::RefCell, rc::Rc};
+
+	use super::ZoomState;
+
+	#[test]
+	fn zoom_changes_are_reported_once() {
+		let reported = Rc::new(RefCell::new(Vec::new()));
+
+		let reported_ = reported.clone();
+
+		let zoom = ZoomState::new(1.0, move |factor| reported_.borrow_mut().push(factor));
+
+		// the platform reports the zoom set through the API back
+		zoom.update(1.5);
+
+		zoom.update(1.5);
+
+		assert_eq!(zoom.factor(), 1.5);
+
+		zoom.update(1.0);
+
+		assert_eq!(*reported.borrow(), [1.5, 1.0]);
+	}
+
+	#[test]
+	fn the_initial_zoom_is_not_reported() {
+		let reported = Rc::new(RefCell::new(Vec::new()));
+
+		let reported_ = reported.clone();
+
+		let zoom = ZoomState::new(2.0, move |factor| reported_.borrow_mut().push(factor));
+
+		zoom.update(2.0);
+
+		assert_eq!(zoom.factor(), 2.0);
+
+		assert!(reported.borrow().is_empty());
+	}
+}
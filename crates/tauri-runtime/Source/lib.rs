@@ -52,7 +52,7 @@ pub type WindowEventId = u32;
 pub type WebviewEventId = u32;
 
 /// A rectangular region.
-#[derive(Clone, Copy, Debug, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Rect {
 	/// Rect position.
 	pub position:dpi::Position,
@@ -185,11 +185,48 @@ pub enum Error {
 	InvalidProxyUrl,
 	#[error("window not found")]
 	WindowNotFound,
+	/// Failed to capture the webview contents.
+	#[error("failed to capture the webview: {0}")]
+	FailedToCaptureWebview(String),
+	/// Failed to add or remove an initialization script.
+	#[error("failed to update the initialization scripts: {0}")]
+	InitializationScript(String),
+	/// Failed to change the webview proxy.
+	#[error("failed to set the webview proxy: {0}")]
+	Proxy(String),
+	/// Failed to print the webview to PDF.
+	#[error("failed to print the webview to PDF: {0}")]
+	FailedToPrintToPdf(String),
+	/// Failed to find text in the webview.
+	#[error("failed to find in the webview: {0}")]
+	FailedToFindInPage(String),
+	/// Failed to read or remove the webview browsing data.
+	#[error("failed to update the browsing data: {0}")]
+	BrowsingData(String),
+	/// Failed to read or change the webview user agent.
+	#[error("failed to update the user agent: {0}")]
+	UserAgent(String),
+	/// Failed to navigate the webview history.
+	#[error("failed to navigate the webview history: {0}")]
+	History(String),
+	/// Failed to show a dialog.
+	#[error("failed to show the dialog: {0}")]
+	Dialog(String),
+	/// Failed to move a webview to another window.
+	#[error("failed to reparent the webview: {0}")]
+	Reparent(String),
+	/// The devtools can't be docked as requested on this platform.
+	#[error("the devtools can not be docked {0:?} on this platform")]
+	UnsupportedDevtoolsDock(webview::DevtoolsDock),
 }
 
 /// Result type.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Identifier of a script added with
+/// [`WebviewDispatch::add_initialization_script`].
+pub type InitializationScriptId = u32;
+
 /// Window icon.
 #[derive(Debug, Clone)]
 pub struct Icon<'a> {
@@ -255,6 +292,8 @@ pub enum RunEvent<T:UserEvent> {
 		/// in your application.
 		has_visible_windows:bool,
 	},
+	/// The system theme changed.
+	ThemeChanged(Theme),
 	/// A custom event defined by the user.
 	UserEvent(T),
 }
@@ -323,6 +362,28 @@ pub trait RuntimeHandle<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'sta
 
 	fn set_theme(&self, theme:Option<Theme>);
 
+	/// The theme of the system, regardless of the theme set on the app.
+	fn system_theme(&self) -> Theme;
+
+	/// Sets the application badge count. Using `0` or `None` will remove the
+	/// badge.
+	///
+	/// ## Platform-specific
+	/// - **Windows:** The count is drawn as the taskbar overlay icon of every
+	///   window without a badge of its own.
+	/// - **Linux:** Sent to the launcher entry of `desktop_filename`.
+	/// - **iOS:** iOS expects i32, the value will be clamped to i32::MAX.
+	/// - **Android:** Unsupported.
+	fn set_badge_count(&self, count:Option<u64>, desktop_filename:Option<String>) -> Result<()>;
+
+	/// Sets the application badge label. Using `None` will remove the badge.
+	///
+	/// ## Platform-specific
+	/// - **Windows:** The label is drawn as the taskbar overlay icon of every
+	///   window without a badge of its own.
+	/// - **Linux / iOS / Android:** Unsupported.
+	fn set_badge_label(&self, label:Option<String>) -> Result<()>;
+
 	/// Shows the application, but does not automatically focus it.
 	#[cfg(target_os = "macos")]
 	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
@@ -420,6 +481,9 @@ pub trait Runtime<T:UserEvent>: Debug + Sized + 'static {
 
 	fn set_theme(&self, theme:Option<Theme>);
 
+	/// The theme of the system, regardless of the theme set on the app.
+	fn system_theme(&self) -> Theme;
+
 	/// Sets the activation policy for the application.
 	#[cfg(target_os = "macos")]
 	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
@@ -476,6 +540,13 @@ pub trait WebviewDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 's
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn open_devtools(&self);
 
+	/// Open the web inspector with the given options.
+	///
+	/// Returns [`Error::UnsupportedDevtoolsDock`] if the platform can't dock
+	/// the devtools as set in `options`.
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools_with(&self, options:webview::DevtoolsOptions) -> Result<()>;
+
 	/// Close the web inspector which is usually called devtools.
 	#[cfg(any(debug_assertions, feature = "devtools"))]
 	fn close_devtools(&self);
@@ -499,14 +570,91 @@ pub trait WebviewDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 's
 	/// Returns the physical size of the webviews's client area.
 	fn size(&self) -> Result<PhysicalSize<u32>>;
 
+	/// Returns the webview zoom level.
+	fn zoom(&self) -> Result<f64>;
+
+	/// Returns the user agent of the webview, the platform default unless it
+	/// was overridden.
+	fn user_agent(&self) -> Result<String>;
+
+	/// Returns the memory used by the process rendering the webview, in bytes,
+	/// or `None` if the platform does not expose it.
+	///
+	/// Webviews can share their rendering process, in which case they report
+	/// the same usage.
+	fn memory_usage(&self) -> Result<Option<u64>>;
+
+	/// Whether the webview can navigate back in its history.
+	fn can_go_back(&self) -> Result<bool>;
+
+	/// Whether the webview can navigate forward in its history.
+	fn can_go_forward(&self) -> Result<bool>;
+
 	// SETTER
 
 	/// Navigate to the given URL.
 	fn navigate(&self, url:Url) -> Result<()>;
 
+	/// Navigates back in the webview history.
+	fn go_back(&self) -> Result<()>;
+
+	/// Navigates forward in the webview history.
+	fn go_forward(&self) -> Result<()>;
+
+	/// Reloads the current page, bypassing the cache if `ignore_cache` is true.
+	fn reload(&self, ignore_cache:bool) -> Result<()>;
+
 	/// Opens the dialog to prints the contents of the webview.
 	fn print(&self) -> Result<()>;
 
+	/// Captures the contents of the webview as a RGBA bitmap, limited to
+	/// `region` if given, relative to the top-left corner of the webview.
+	///
+	/// `f` is called on the main thread with the bitmap once the platform
+	/// webview finished the capture.
+	fn capture<F:FnOnce(Result<Icon<'static>>) + Send + 'static>(
+		&self,
+		region:Option<Rect>,
+		f:F,
+	) -> Result<()>;
+
+	/// Prints the contents of the webview to a PDF document.
+	///
+	/// `f` is called on the main thread with the document bytes once the
+	/// platform webview finished printing.
+	fn print_to_pdf<F:FnOnce(Result<Vec<u8>>) + Send + 'static>(
+		&self,
+		options:webview::PdfOptions,
+		f:F,
+	) -> Result<()>;
+
+	/// Finds `query` in the page, highlighting the matches.
+	///
+	/// Searching the same query again moves the active match to the next one,
+	/// in the direction given by the options. `f` is called on the main thread
+	/// with the result once the platform webview finished the search.
+	fn find_in_page<F:FnOnce(Result<webview::FindResult>) + Send + 'static>(
+		&self,
+		query:String,
+		options:webview::FindOptions,
+		f:F,
+	) -> Result<()>;
+
+	/// Ends the search started with [`Self::find_in_page`], removing the
+	/// highlights if `clear_highlights` is true.
+	fn stop_find(&self, clear_highlights:bool) -> Result<()>;
+
+	/// Adds a script that runs before any other script of the page on every
+	/// navigation that starts after this call, returning its id.
+	fn add_initialization_script(
+		&self,
+		script:webview::InitializationScript,
+	) -> Result<InitializationScriptId>;
+
+	/// Removes a script added with [`Self::add_initialization_script`], from the
+	/// next navigation on.
+	fn remove_initialization_script(&self, id:InitializationScriptId) -> Result<()>;
+
 	/// Closes the webview.
 	fn close(&self) -> Result<()>;
 
@@ -531,21 +679,75 @@ pub trait WebviewDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 's
 	/// Executes javascript on the window this [`WindowDispatch`] represents.
 	fn eval_script<S:Into<String>>(&self, script:S) -> Result<()>;
 
-	/// Moves the webview to the given window.
-	fn reparent(&self, window_id:WindowId) -> Result<()>;
+	/// Moves the webview to the given window, at the given `bounds` relative
+	/// to it or keeping its current bounds if `None`.
+	///
+	/// The webview is left in its window if it could not be moved.
+	fn reparent(&self, window_id:WindowId, bounds:Option<Rect>) -> Result<()>;
 
 	/// Sets whether the webview should automatically grow and shrink its size
 	/// and position when the parent window resizes.
 	fn set_auto_resize(&self, auto_resize:bool) -> Result<()>;
 
+	/// Sets the proxy of the webview, `None` for a direct connection.
+	fn set_proxy(&self, proxy:Option<webview::ProxyConfig>) -> Result<()>;
+
+	/// Sets how the webview is moved and resized when the parent window
+	/// resizes, `None` keeping its bounds.
+	fn set_layout(&self, layout:Option<webview::WebviewLayout>) -> Result<()>;
+
 	/// Set the webview zoom level
 	fn set_zoom(&self, scale_factor:f64) -> Result<()>;
 
+	/// Sets the user agent of the webview, sent with the requests made after
+	/// this call.
+	fn set_user_agent(&self, user_agent:String) -> Result<()>;
+
 	/// Set the webview background.
 	fn set_background_color(&self, color:Option<Color>) -> Result<()>;
 
 	/// Clear all browsing data for this webview.
 	fn clear_all_browsing_data(&self) -> Result<()>;
+
+	/// Removes the browsing data of the given kinds from the webview data
+	/// store.
+	///
+	/// `f` is called on the main thread once the platform webview removed the
+	/// data.
+	fn clear_browsing_data<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		types:webview::DataTypes,
+		f:F,
+	) -> Result<()>;
+
+	/// Returns the cookies of the webview data store sent to `url`.
+	fn cookies_for_url(&self, url:Url) -> Result<Vec<webview::Cookie<'static>>>;
+
+	/// Returns every cookie of the webview data store.
+	fn cookies(&self) -> Result<Vec<webview::Cookie<'static>>>;
+
+	/// Adds `cookie` to the webview data store, replacing the cookie with the
+	/// same name, domain and path.
+	///
+	/// `f` is called on the main thread once the platform webview stored the
+	/// cookie.
+	fn set_cookie<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		cookie:webview::Cookie<'static>,
+		f:F,
+	) -> Result<()>;
+
+	/// Deletes the cookie named `name` sent to `url` from the webview data
+	/// store.
+	///
+	/// `f` is called on the main thread once the platform webview deleted the
+	/// cookie.
+	fn delete_cookie<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		name:String,
+		url:Url,
+		f:F,
+	) -> Result<()>;
 }
 
 /// Window dispatcher. A thread-safe handle to the window APIs.
@@ -588,6 +790,9 @@ pub trait WindowDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	/// that (and you usually don't), use inner_size instead.
 	fn outer_size(&self) -> Result<PhysicalSize<u32>>;
 
+	/// Gets the window's current inner size constraints.
+	fn size_constraints(&self) -> Result<WindowSizeConstraints>;
+
 	/// Gets the window's current fullscreen state.
 	fn is_fullscreen(&self) -> Result<bool>;
 
@@ -682,6 +887,25 @@ pub trait WindowDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	/// Returns the current window theme.
 	fn theme(&self) -> Result<Theme>;
 
+	/// Returns the regions of the client area covered by the title bar
+	/// controls, in physical pixels from its top-left corner.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux / iOS / Android**: Unsupported, returns an empty list.
+	fn title_bar_rects(&self) -> Result<Vec<Rect>>;
+
+	/// Shows a modal dialog over the window asking to confirm `message`, with
+	/// OK and Cancel buttons, and returns whether it was confirmed.
+	///
+	/// This blocks until the dialog is answered.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS**: The dialog is modal to the whole application.
+	/// - **iOS / Android**: Unsupported.
+	fn confirm(&self, title:String, message:String) -> Result<bool>;
+
 	// SETTERS
 
 	/// Centers the window.
@@ -792,21 +1016,36 @@ pub trait WindowDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	/// Resizes the window.
 	fn set_size(&self, size:Size) -> Result<()>;
 
-	/// Updates the window min inner size.
+	/// Updates the window min inner size, resizing the window if it is smaller.
 	fn set_min_size(&self, size:Option<Size>) -> Result<()>;
 
-	/// Updates the window max inner size.
+	/// Updates the window max inner size, resizing the window if it is larger.
 	fn set_max_size(&self, size:Option<Size>) -> Result<()>;
 
-	/// Sets this window's minimum inner width.
+	/// Sets this window's minimum and maximum inner size, resizing the window
+	/// if its current size does not satisfy them.
 	fn set_size_constraints(&self, constraints:WindowSizeConstraints) -> Result<()>;
 
+	/// Locks this window's inner size to the given width to height ratio,
+	/// resizing the window to it, or unlocks it when `None`.
+	fn set_aspect_ratio(&self, ratio:Option<f64>) -> Result<()>;
+
 	/// Updates the window position.
 	fn set_position(&self, position:Position) -> Result<()>;
 
 	/// Updates the window fullscreen state.
 	fn set_fullscreen(&self, fullscreen:bool) -> Result<()>;
 
+	/// Moves the window to `monitor` and makes it fullscreen there in a single
+	/// step.
+	///
+	/// If the monitor is disconnected while the window is fullscreen, the window
+	/// is made fullscreen on the primary monitor and
+	/// [`WindowEvent::FullscreenMonitorDisconnected`] is emitted.
+	///
+	/// Fails with [`Error::FailedToGetMonitor`] when `monitor` is not connected.
+	fn set_fullscreen_on_monitor(&self, monitor:Monitor) -> Result<()>;
+
 	/// Bring the window to front and focus.
 	fn set_focus(&self) -> Result<()>;
 
@@ -837,6 +1076,11 @@ pub trait WindowDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	/// Ignores the window cursor events.
 	fn set_ignore_cursor_events(&self, ignore:bool) -> Result<()>;
 
+	/// Ignores the window cursor events outside of `regions`, in window
+	/// coordinates, `None` handling the cursor events of the whole window
+	/// again.
+	fn set_ignore_cursor_events_region(&self, regions:Option<Vec<Rect>>) -> Result<()>;
+
 	/// Starts dragging the window.
 	fn start_dragging(&self) -> Result<()>;
 
@@ -848,15 +1092,20 @@ pub trait WindowDispatch<T:UserEvent>: Debug + Clone + Send + Sync + Sized + 'st
 	/// Using `0` or using `None` will remove the badge
 	///
 	/// ## Platform-specific
-	/// - **Windows:** Unsupported, use [`WindowDispatch::set_overlay_icon`]
-	///   instead.
+	/// - **Windows:** The count is drawn as the taskbar overlay icon of this
+	///   window until it is removed, counts above 99 are shown as `99+` and
+	///   negative counts remove the badge.
 	/// - **Android:** Unsupported.
 	/// - **iOS:** iOS expects i32, if the value is larger than i32::MAX, it
 	///   will be clamped to i32::MAX.
 	fn set_badge_count(&self, count:Option<i64>, desktop_filename:Option<String>) -> Result<()>;
 
-	/// Sets the badge count on the taskbar **macOS only**. Using `None` will
-	/// remove the badge
+	/// Sets the badge label on the taskbar. Using `None` will remove the badge
+	///
+	/// ## Platform-specific
+	/// - **Windows:** The label is drawn as the taskbar overlay icon of this
+	///   window until it is removed, so it should be a few characters at most.
+	/// - **Linux / iOS / Android:** Unsupported.
 	fn set_badge_label(&self, label:Option<String>) -> Result<()>;
 
 	/// Sets the overlay icon on the taskbar **Windows only**. Using `None` will
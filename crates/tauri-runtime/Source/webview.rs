@@ -6,16 +6,24 @@
 use std::{
 	borrow::Cow,
 	collections::HashMap,
+	fmt,
 	hash::{Hash, Hasher},
 	path::PathBuf,
 	sync::Arc,
 };
 
+pub use cookie::{Cookie, SameSite};
 use http::Request;
+use serde::{Deserialize, Serialize};
 use tauri_utils::config::{Color, WebviewUrl, WindowConfig, WindowEffectsConfig};
 use url::Url;
 
-use crate::{Rect, Runtime, UserEvent, window::is_label_valid};
+use crate::{
+	Rect,
+	Runtime,
+	UserEvent,
+	window::{DragDropEvent, is_label_valid},
+};
 
 type UriSchemeProtocol = dyn Fn(&str, http::Request<Vec<u8>>, Box<dyn FnOnce(http::Response<Cow<'static, [u8]>>) + Send>)
 	+ Send
@@ -27,10 +35,15 @@ type WebResourceRequestHandler =
 
 type NavigationHandler = dyn Fn(&Url) -> bool + Send;
 
-type OnPageLoadHandler = dyn Fn(Url, PageLoadEvent) + Send;
+type OnPageLoadHandler = dyn Fn(Url, PageLoadEvent, Option<PageLoadError>) + Send;
 
 type DownloadHandler = dyn Fn(DownloadEvent) -> bool + Send + Sync;
 
+type DragDropFilter = dyn Fn(&DragDropEvent) -> bool + Send;
+
+type ProxyAuthenticationHandler =
+	dyn Fn(ProxyAuthenticationChallenge) -> Option<ProxyCredentials> + Send;
+
 /// Download event.
 pub enum DownloadEvent<'a> {
 	/// Download requested.
@@ -62,12 +75,48 @@ pub struct CreationContext<'a, 'b> {
 }
 
 /// Kind of event for the page load handler.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum PageLoadEvent {
 	/// Page started to load.
 	Started,
 	/// Page finished loading.
 	Finished,
+	/// Page failed to load. The handler receives a [`PageLoadError`] describing
+	/// the failure.
+	///
+	/// Emitted after [`PageLoadEvent::Finished`] when the platform reports the
+	/// navigation failed.
+	Failed,
+}
+
+/// The kind of a [`PageLoadError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum PageLoadErrorKind {
+	/// The server responded with an HTTP error status code.
+	Http,
+	/// The TLS handshake or certificate validation failed.
+	Tls,
+	/// The host could not be resolved or the connection failed.
+	Network,
+	/// The navigation was cancelled or replaced by another navigation.
+	Cancelled,
+	/// Any other failure reported by the webview.
+	Other,
+}
+
+/// Details of a failed page load.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageLoadError {
+	/// The kind of failure.
+	pub kind:PageLoadErrorKind,
+	/// The platform description of the failure.
+	pub description:String,
+	/// The HTTP status code of the response, if one was received.
+	pub http_status:Option<u16>,
 }
 
 /// A webview that has yet to be built.
@@ -99,6 +148,13 @@ pub struct PendingWebview<T:UserEvent, R:Runtime<T>> {
 	pub on_page_load_handler:Option<Box<OnPageLoadHandler>>,
 
 	pub download_handler:Option<Arc<DownloadHandler>>,
+
+	/// A handler to decide if a drag operation entering the webview is
+	/// accepted.
+	pub drag_drop_filter:Option<Box<DragDropFilter>>,
+
+	/// A handler answering the authentication challenges of the proxy.
+	pub proxy_authentication_handler:Option<Box<ProxyAuthenticationHandler>>,
 }
 
 impl<T:UserEvent, R:Runtime<T>> PendingWebview<T, R> {
@@ -125,6 +181,8 @@ impl<T:UserEvent, R:Runtime<T>> PendingWebview<T, R> {
 				web_resource_request_handler:None,
 				on_page_load_handler:None,
 				download_handler:None,
+				drag_drop_filter:None,
+				proxy_authentication_handler:None,
 			})
 		}
 	}
@@ -188,12 +246,259 @@ impl<T:UserEvent, R:Runtime<T>> PartialEq for DetachedWebview<T, R> {
 	fn eq(&self, other:&Self) -> bool { self.label.eq(&other.label) }
 }
 
+/// A script that runs before any other script of the page, on every
+/// navigation.
+#[derive(Debug, Clone)]
+pub struct InitializationScript {
+	/// The script source.
+	pub script:String,
+	/// Whether the script only runs on the main frame or on every frame.
+	pub for_main_frame_only:bool,
+}
+
+/// The window edges a child webview keeps its distance to when the window is
+/// resized.
+///
+/// A webview anchored to two opposite edges stretches with the window, one
+/// anchored to neither of them keeps its center at the same relative position.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Anchors {
+	/// Keeps the distance to the left window edge.
+	pub left:bool,
+	/// Keeps the distance to the top window edge.
+	pub top:bool,
+	/// Keeps the distance to the right window edge.
+	pub right:bool,
+	/// Keeps the distance to the bottom window edge.
+	pub bottom:bool,
+}
+
+impl Anchors {
+	/// Anchored to every edge, the webview stretches with the window.
+	pub const ALL:Self = Self { left:true, top:true, right:true, bottom:true };
+}
+
+/// The window side a docked webview is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DockSide {
+	/// The left side, the webview fills the window height.
+	Left,
+	/// The top side, the webview fills the window width.
+	Top,
+	/// The right side, the webview fills the window height.
+	Right,
+	/// The bottom side, the webview fills the window width.
+	Bottom,
+}
+
+/// How a child webview is moved and resized when its window is resized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebviewLayout {
+	/// Keeps the webview position and size relative to the window size.
+	Proportional,
+	/// Keeps the distances of the webview to the anchored window edges.
+	Anchored(Anchors),
+	/// Attaches the webview to a window side, filling the other dimension.
+	Docked {
+		/// The window side.
+		side:DockSide,
+		/// The webview width for the left and right sides, or height for the
+		/// top and bottom sides, in logical pixels.
+		size:f64,
+	},
+}
+
+/// The credentials of a proxy.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ProxyCredentials {
+	pub username:String,
+	pub password:String,
+}
+
+impl fmt::Debug for ProxyCredentials {
+	fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ProxyCredentials")
+			.field("username", &self.username)
+			.field("password", &"<redacted>")
+			.finish()
+	}
+}
+
+/// The proxy used by a webview for its network requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+	/// The proxy URL, either a `http://` or a `socks5://` URL.
+	pub url:Url,
+	/// The hosts reached without the proxy, e.g. `localhost` or
+	/// `*.example.com`.
+	pub bypass:Vec<String>,
+	/// The credentials answered to the proxy authentication challenges.
+	pub credentials:Option<ProxyCredentials>,
+}
+
+impl From<Url> for ProxyConfig {
+	fn from(url:Url) -> Self { Self { url, bypass:Vec::new(), credentials:None } }
+}
+
+/// An authentication challenge of the webview proxy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyAuthenticationChallenge {
+	/// The proxy host.
+	pub host:String,
+	/// The proxy port.
+	pub port:u16,
+	/// The authentication realm.
+	pub realm:String,
+	/// Whether the previous credentials were rejected.
+	pub is_retry:bool,
+}
+
+/// A page size, in inches.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PdfPageSize {
+	/// The page width.
+	pub width:f64,
+	/// The page height.
+	pub height:f64,
+}
+
+impl PdfPageSize {
+	/// The ISO A4 size, 210 x 297 mm.
+	pub const A4:Self = Self { width:8.27, height:11.69 };
+	/// The US Legal size, 8.5 x 14 inches.
+	pub const LEGAL:Self = Self { width:8.5, height:14.0 };
+	/// The US Letter size, 8.5 x 11 inches.
+	pub const LETTER:Self = Self { width:8.5, height:11.0 };
+}
+
+impl Default for PdfPageSize {
+	fn default() -> Self { Self::LETTER }
+}
+
+/// The page margins, in inches.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PdfMargins {
+	pub top:f64,
+	pub right:f64,
+	pub bottom:f64,
+	pub left:f64,
+}
+
+impl Default for PdfMargins {
+	fn default() -> Self { Self { top:0.4, right:0.4, bottom:0.4, left:0.4 } }
+}
+
+/// The options used to print a webview to PDF.
+///
+/// ## Platform-specific
+///
+/// - **macOS**: `WKWebView` renders the whole page on a single PDF page, so
+///   none of the options apply and the backgrounds are always printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PdfOptions {
+	/// The page size, in portrait orientation. Ignored on macOS.
+	pub page_size:PdfPageSize,
+	/// The page margins. Ignored on macOS.
+	pub margins:PdfMargins,
+	/// Whether the pages are in landscape orientation. Ignored on macOS.
+	pub landscape:bool,
+	/// Whether the background colors and images are printed. Ignored on Linux,
+	/// where the `print-color-adjust` CSS property of the page applies, and
+	/// always printed on macOS.
+	pub print_background:bool,
+}
+
+/// The options used to find text in a webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FindOptions {
+	/// Whether the matches must have the same case as the query.
+	pub case_sensitive:bool,
+	/// Whether the search continues from the other end of the page when it
+	/// reaches the first or last match.
+	pub wrap_around:bool,
+	/// Whether the search moves towards the beginning of the page.
+	pub backwards:bool,
+}
+
+impl Default for FindOptions {
+	fn default() -> Self { Self { case_sensitive:false, wrap_around:true, backwards:false } }
+}
+
+/// The result of a search in a webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FindResult {
+	/// Whether the query was found in the page.
+	pub found:bool,
+	/// The number of matches in the page, if reported by the platform webview.
+	pub match_count:Option<u32>,
+	/// The index of the active match, starting at 0, if reported by the
+	/// platform webview.
+	pub active_match_index:Option<u32>,
+}
+
+/// The back and forward navigation availability of a webview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationHistory {
+	/// Whether the webview can navigate back.
+	pub can_go_back:bool,
+	/// Whether the webview can navigate forward.
+	pub can_go_forward:bool,
+}
+
+/// Where the devtools are opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DevtoolsDock {
+	/// In a separate window.
+	Detached,
+	/// Docked to the right side of the webview.
+	Right,
+	/// Docked to the bottom side of the webview.
+	#[default]
+	Bottom,
+}
+
+/// The options used to open the devtools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DevtoolsOptions {
+	/// Where the devtools are opened.
+	pub dock:DevtoolsDock,
+}
+
+bitflags::bitflags! {
+	/// The kinds of browsing data removed by
+	/// [`WebviewDispatch::clear_browsing_data`](crate::WebviewDispatch::clear_browsing_data).
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub struct DataTypes: u32 {
+		/// The cookies.
+		const COOKIES = 1 << 0;
+		/// The `localStorage` and `sessionStorage` data.
+		const LOCAL_STORAGE = 1 << 1;
+		/// The IndexedDB databases.
+		const INDEXED_DB = 1 << 2;
+		/// The HTTP disk and memory caches.
+		const CACHE = 1 << 3;
+		/// Every kind of browsing data above.
+		const ALL = Self::COOKIES.bits()
+			| Self::LOCAL_STORAGE.bits()
+			| Self::INDEXED_DB.bits()
+			| Self::CACHE.bits();
+	}
+}
+
 /// The attributes used to create an webview.
 #[derive(Debug, Clone)]
 pub struct WebviewAttributes {
 	pub url:WebviewUrl,
 	pub user_agent:Option<String>,
-	pub initialization_scripts:Vec<String>,
+	pub append_user_agent:Option<String>,
+	pub initialization_scripts:Vec<InitializationScript>,
 	pub data_directory:Option<PathBuf>,
 	pub drag_drop_handler_enabled:bool,
 	pub clipboard:bool,
@@ -205,14 +510,20 @@ pub struct WebviewAttributes {
 	pub focus:bool,
 	pub bounds:Option<Rect>,
 	pub auto_resize:bool,
+	pub layout:Option<WebviewLayout>,
+	#[deprecated(since = "2.4.0", note = "Use `proxy` instead, which takes precedence over it.")]
 	pub proxy_url:Option<Url>,
+	pub proxy:Option<ProxyConfig>,
 	pub zoom_hotkeys_enabled:bool,
+	pub zoom:Option<f64>,
 	pub browser_extensions_enabled:bool,
 	pub extensions_path:Option<PathBuf>,
 	pub data_store_identifier:Option<[u8; 16]>,
 	pub use_https_scheme:bool,
 	pub devtools:Option<bool>,
 	pub background_color:Option<Color>,
+	/// The cookies added to the data store before the initial page load.
+	pub cookies:Vec<Cookie<'static>>,
 }
 
 impl From<&WindowConfig> for WebviewAttributes {
@@ -255,16 +566,22 @@ impl From<&WindowConfig> for WebviewAttributes {
 			builder = builder.background_color(color);
 		}
 
+		if let Some(zoom) = config.zoom {
+			builder = builder.zoom(zoom);
+		}
+
 		builder
 	}
 }
 
 impl WebviewAttributes {
 	/// Initializes the default attributes for a webview.
+	#[allow(deprecated)]
 	pub fn new(url:WebviewUrl) -> Self {
 		Self {
 			url,
 			user_agent:None,
+			append_user_agent:None,
 			initialization_scripts:Vec::new(),
 			data_directory:None,
 			drag_drop_handler_enabled:true,
@@ -277,14 +594,18 @@ impl WebviewAttributes {
 			focus:true,
 			bounds:None,
 			auto_resize:false,
+			layout:None,
 			proxy_url:None,
+			proxy:None,
 			zoom_hotkeys_enabled:false,
+			zoom:None,
 			browser_extensions_enabled:false,
 			data_store_identifier:None,
 			extensions_path:None,
 			use_https_scheme:false,
 			devtools:None,
 			background_color:None,
+			cookies:Vec::new(),
 		}
 	}
 
@@ -296,10 +617,29 @@ impl WebviewAttributes {
 		self
 	}
 
-	/// Sets the init script.
+	/// Appends `suffix` to the default user agent of the platform webview, or
+	/// to the one set with [`Self::user_agent`].
+	#[must_use]
+	pub fn append_user_agent(mut self, suffix:&str) -> Self {
+		self.append_user_agent = Some(suffix.to_string());
+
+		self
+	}
+
+	/// Sets the init script, running on the main frame and every child frame.
 	#[must_use]
 	pub fn initialization_script(mut self, script:&str) -> Self {
-		self.initialization_scripts.push(script.to_string());
+		self.initialization_scripts
+			.push(InitializationScript { script:script.to_string(), for_main_frame_only:false });
+
+		self
+	}
+
+	/// Sets the init script, running on the main frame only.
+	#[must_use]
+	pub fn initialization_script_for_main_frame_only(mut self, script:&str) -> Self {
+		self.initialization_scripts
+			.push(InitializationScript { script:script.to_string(), for_main_frame_only:true });
 
 		self
 	}
@@ -392,10 +732,33 @@ impl WebviewAttributes {
 		self
 	}
 
+	/// Sets how the webview is moved and resized when the parent window
+	/// resizes, taking precedence over [`Self::auto_resize`].
+	#[must_use]
+	pub fn layout(mut self, layout:WebviewLayout) -> Self {
+		self.layout = Some(layout);
+
+		self
+	}
+
 	/// Enable proxy for the WebView
+	///
+	/// The same as [`Self::proxy`] without a bypass list or credentials.
+	#[must_use]
+	pub fn proxy_url(self, url:Url) -> Self { self.proxy(url.into()) }
+
+	/// Sets the proxy of the webview, with its bypass list and credentials.
 	#[must_use]
-	pub fn proxy_url(mut self, url:Url) -> Self {
-		self.proxy_url = Some(url);
+	pub fn proxy(mut self, proxy:ProxyConfig) -> Self {
+		self.proxy = Some(proxy);
+
+		self
+	}
+
+	/// Adds a cookie to the data store before the initial page load.
+	#[must_use]
+	pub fn cookie(mut self, cookie:Cookie<'static>) -> Self {
+		self.cookies.push(cookie);
 
 		self
 	}
@@ -418,6 +781,18 @@ impl WebviewAttributes {
 		self
 	}
 
+	/// Sets the initial zoom factor of the webview, kept across navigations.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Android / iOS**: Unsupported.
+	#[must_use]
+	pub fn zoom(mut self, scale_factor:f64) -> Self {
+		self.zoom = Some(scale_factor);
+
+		self
+	}
+
 	/// Whether browser extensions can be installed for the webview process
 	///
 	/// ## Platform-specific:
@@ -15,7 +15,7 @@ use dpi::PixelUnit;
 use serde::{Deserialize, Deserializer, Serialize};
 use tauri_utils::{
 	Theme,
-	config::{Color, WindowConfig},
+	config::{Color, FullscreenMonitor, WindowConfig},
 };
 #[cfg(windows)]
 use windows::Win32::Foundation::HWND;
@@ -71,6 +71,16 @@ pub enum WindowEvent {
 	/// Applications might wish to react to this to change the theme of the
 	/// content of the window when the system changes the window theme.
 	ThemeChanged(Theme),
+	/// The regions of the client area covered by the title bar controls have
+	/// changed, after a resize or a scale factor or theme change.
+	///
+	/// See [`WindowDispatch::title_bar_rects`].
+	TitleBarRectsChanged(Vec<crate::Rect>),
+	/// The monitor the window was made fullscreen on was disconnected, the
+	/// window being made fullscreen on the primary monitor instead.
+	///
+	/// See [`WindowDispatch::set_fullscreen_on_monitor`].
+	FullscreenMonitorDisconnected,
 }
 
 /// An event from a window.
@@ -78,6 +88,16 @@ pub enum WindowEvent {
 pub enum WebviewEvent {
 	/// An event associated with the drag and drop action.
 	DragDrop(DragDropEvent),
+	/// The webview zoom level changed, from the API or by the user.
+	ZoomChanged(f64),
+	/// The matches of the search started with `find_in_page` changed.
+	FindResultChanged(crate::webview::FindResult),
+	/// The back or forward navigation availability changed.
+	HistoryChanged(crate::webview::NavigationHistory),
+	/// The devtools were opened, from the API or by the user.
+	DevtoolsOpened,
+	/// The devtools were closed, from the API or by the user.
+	DevtoolsClosed,
 }
 
 /// The drag drop event payload.
@@ -85,13 +105,21 @@ pub enum WebviewEvent {
 #[non_exhaustive]
 pub enum DragDropEvent {
 	/// A drag operation has entered the webview.
+	///
+	/// The webview drag and drop filter runs on this event, a rejected drag
+	/// does not emit any other event.
 	Enter {
-		/// List of paths that are being dragged onto the webview.
+		/// List of paths that are being dragged onto the webview, available
+		/// before the drop.
 		paths:Vec<PathBuf>,
+		/// The MIME types of the dragged paths, guessed from their extension,
+		/// `inode/directory` for directories.
+		types:Vec<String>,
 		/// The position of the mouse cursor.
 		position:dpi::PhysicalPosition<f64>,
 	},
-	/// A drag operation is moving over the webview.
+	/// A drag operation is moving over the webview, emitted at most once per
+	/// frame.
 	Over {
 		/// The position of the mouse cursor.
 		position:dpi::PhysicalPosition<f64>,
@@ -282,6 +310,11 @@ pub trait WindowBuilder: WindowBuilderBase {
 	#[must_use]
 	fn inner_size_constraints(self, constraints:WindowSizeConstraints) -> Self;
 
+	/// Locks the inner size of the window to the given width to height ratio
+	/// when it is resized.
+	#[must_use]
+	fn aspect_ratio(self, ratio:f64) -> Self;
+
 	/// Whether the window is resizable or not.
 	/// When resizable is set to false, native window's maximize button is
 	/// automatically disabled.
@@ -326,6 +359,11 @@ pub trait WindowBuilder: WindowBuilderBase {
 	#[must_use]
 	fn fullscreen(self, fullscreen:bool) -> Self;
 
+	/// Starts the window in fullscreen on the given monitor, falling back to the
+	/// monitor it would be created on when it is not found.
+	#[must_use]
+	fn fullscreen_monitor(self, monitor:FullscreenMonitor) -> Self;
+
 	/// Whether the window will be initially focused or not.
 	#[must_use]
 	fn focused(self, focused:bool) -> Self;
@@ -386,7 +424,8 @@ pub trait WindowBuilder: WindowBuilderBase {
 	///   - `false` has no effect on decorated window, shadows are always ON.
 	///   - `true` will make undecorated window have a 1px white border, and on
 	///     Windows 11, it will have a rounded corners.
-	/// - **Linux:** Unsupported.
+	/// - **Linux:** `true` creates the window with the GTK client-side decorations,
+	///   which draw the shadow and the resize borders around it when undecorated.
 	#[must_use]
 	fn shadow(self, enable:bool) -> Self;
 
@@ -447,6 +486,23 @@ pub trait WindowBuilder: WindowBuilderBase {
 	#[must_use]
 	fn hidden_title(self, hidden:bool) -> Self;
 
+	/// Sets the position of the window controls (traffic lights) from the
+	/// top-left corner of the window.
+	///
+	/// Requires a title bar, usually with the
+	/// [`tauri_utils::TitleBarStyle::Overlay`] style.
+	#[cfg(target_os = "macos")]
+	#[must_use]
+	fn traffic_light_position<P:Into<dpi::Position>>(self, position:P) -> Self;
+
+	/// Extends the client area into the title bar, the caption buttons being
+	/// drawn by the system over it.
+	///
+	/// Requires the window decorations.
+	#[cfg(windows)]
+	#[must_use]
+	fn titlebar_overlay(self, enabled:bool) -> Self;
+
 	/// Defines the window [tabbing identifier] for macOS.
 	///
 	/// Windows with matching tabbing identifiers will be grouped together.
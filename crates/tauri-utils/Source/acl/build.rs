@@ -12,6 +12,7 @@ use std::{
 };
 
 use super::{
+	Identifier,
 	PERMISSION_SCHEMA_FILE_NAME,
 	PERMISSION_SCHEMAS_FOLDER_NAME,
 	capability::{Capability, CapabilityFile},
@@ -247,12 +248,50 @@ pub struct AutogeneratedPermissions {
 	pub denied:Vec<String>,
 }
 
+/// Formats the identifier of an autogenerated command permission.
+///
+/// Receives the command name and whether the permission allows (`true`) or
+/// denies (`false`) the command. The result must be a valid permission
+/// identifier: ASCII alphanumeric words separated by single hyphens,
+/// underscores or dots, e.g. `allow-read-file` or `cmd.read_file.allow`.
+pub type PermissionIdentifierFormatter = fn(&str, bool) -> String;
+
+/// The default [`PermissionIdentifierFormatter`], generating `allow-$command`
+/// and `deny-$command` identifiers where $command is the command name in
+/// kebab-case.
+pub fn default_permission_identifier(command:&str, allow:bool) -> String {
+	format!("{}-{}", if allow { "allow" } else { "deny" }, command.replace('_', "-"))
+}
+
 /// Autogenerate permission files for a list of commands.
 pub fn autogenerate_command_permissions(
 	path:&Path,
 	commands:&[&str],
 	license_header:&str,
 	schema_ref:bool,
+) -> AutogeneratedPermissions {
+	autogenerate_command_permissions_with_format(
+		path,
+		commands,
+		license_header,
+		schema_ref,
+		default_permission_identifier,
+	)
+}
+
+/// Autogenerate permission files for a list of commands, using the given
+/// formatter for the permission identifiers.
+///
+/// # Panics
+///
+/// Panics if the formatter returns an invalid identifier or the same
+/// identifier for the allow and deny permissions of a command.
+pub fn autogenerate_command_permissions_with_format(
+	path:&Path,
+	commands:&[&str],
+	license_header:&str,
+	schema_ref:bool,
+	format_identifier:PermissionIdentifierFormatter,
 ) -> AutogeneratedPermissions {
 	if !path.exists() {
 		fs::create_dir_all(path).expect("unable to create autogenerated commands dir");
@@ -280,18 +319,33 @@ pub fn autogenerate_command_permissions(
 	let mut autogenerated = AutogeneratedPermissions { allowed:Vec::new(), denied:Vec::new() };
 
 	for command in commands {
-		let slugified_command = command.replace('_', "-");
+		let allow_identifier = format_identifier(command, true);
+
+		let deny_identifier = format_identifier(command, false);
+
+		for identifier in [&allow_identifier, &deny_identifier] {
+			if let Err(e) = Identifier::try_from(identifier.clone()) {
+				panic!("invalid permission identifier `{identifier}` for command `{command}`: {e}");
+			}
+		}
+
+		if allow_identifier == deny_identifier {
+			panic!(
+				"the allow and deny permissions of command `{command}` have the same identifier \
+				 `{allow_identifier}`"
+			);
+		}
 
 		let toml = format!(
 			r###"{license_header}# Automatically generated - DO NOT EDIT!
 {schema_entry}
 [[permission]]
-identifier = "allow-{slugified_command}"
+identifier = "{allow_identifier}"
 description = "Enables the {command} command without any pre-configured scope."
 commands.allow = ["{command}"]
 
 [[permission]]
-identifier = "deny-{slugified_command}"
+identifier = "{deny_identifier}"
 description = "Denies the {command} command without any pre-configured scope."
 commands.deny = ["{command}"]
 "###,
@@ -302,9 +356,9 @@ commands.deny = ["{command}"]
 		write_if_changed(&out_path, toml)
 			.unwrap_or_else(|_| panic!("unable to autogenerate {out_path:?}"));
 
-		autogenerated.allowed.push(format!("allow-{slugified_command}"));
+		autogenerated.allowed.push(allow_identifier);
 
-		autogenerated.denied.push(format!("deny-{slugified_command}"));
+		autogenerated.denied.push(deny_identifier);
 	}
 
 	autogenerated
@@ -382,3 +436,67 @@ pub fn generate_docs(
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn dotted_identifier(command:&str, allow:bool) -> String {
+		format!("cmd.{command}.{}", if allow { "allow" } else { "deny" })
+	}
+
+	fn autogenerate(name:&str, format_identifier:PermissionIdentifierFormatter) -> (PathBuf, AutogeneratedPermissions) {
+		let dir = env::temp_dir().join(format!("tauri-autogenerated-{name}-{}", std::process::id()));
+
+		let _ = fs::remove_dir_all(&dir);
+
+		let autogenerated =
+			autogenerate_command_permissions_with_format(&dir, &["read_file"], "", false, format_identifier);
+
+		(dir, autogenerated)
+	}
+
+	#[test]
+	fn custom_identifier_format() {
+		let (dir, autogenerated) = autogenerate("custom", dotted_identifier);
+
+		assert_eq!(autogenerated.allowed, ["cmd.read_file.allow"]);
+
+		assert_eq!(autogenerated.denied, ["cmd.read_file.deny"]);
+
+		let permissions = parse_permissions(vec![dir.join("read_file.toml")]).unwrap();
+
+		let identifiers =
+			permissions[0].permission.iter().map(|p| p.identifier.as_str()).collect::<Vec<_>>();
+
+		assert_eq!(identifiers, ["cmd.read_file.allow", "cmd.read_file.deny"]);
+
+		// capabilities can reference them
+		assert!(Identifier::try_from("plugin:cmd.read_file.allow".to_string()).is_ok());
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	fn default_identifier_format() {
+		let (dir, autogenerated) = autogenerate("default", default_permission_identifier);
+
+		assert_eq!(autogenerated.allowed, ["allow-read-file"]);
+
+		assert_eq!(autogenerated.denied, ["deny-read-file"]);
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[test]
+	#[should_panic(expected = "invalid permission identifier `read file` for command `read_file`")]
+	fn invalid_identifier_format() {
+		autogenerate("invalid", |command, _| command.replace('_', " "));
+	}
+
+	#[test]
+	#[should_panic(expected = "the allow and deny permissions of command `read_file` have the same identifier")]
+	fn ambiguous_identifier_format() {
+		autogenerate("ambiguous", |command, _| command.to_string());
+	}
+}
@@ -76,24 +76,28 @@ enum ValidByte {
 	Byte(u8),
 }
 
+/// Whether the byte separates two words of an identifier, e.g. `allow-read-file`
+/// or `cmd.read_file.allow`.
+fn is_word_separator(byte:u8) -> bool { matches!(byte, b'-' | b'_' | b'.') }
+
 impl ValidByte {
 	fn alpha_numeric(byte:u8) -> Option<Self> {
 		byte.is_ascii_alphanumeric().then_some(Self::Byte(byte))
 	}
 
-	fn alpha_numeric_hyphen(byte:u8) -> Option<Self> {
-		(byte.is_ascii_alphanumeric() || byte == b'-').then_some(Self::Byte(byte))
+	fn alpha_numeric_word_separator(byte:u8) -> Option<Self> {
+		(byte.is_ascii_alphanumeric() || is_word_separator(byte)).then_some(Self::Byte(byte))
 	}
 
 	fn next(&self, next:u8) -> Option<ValidByte> {
 		match (self, next) {
-			(ValidByte::Byte(b'-'), IDENTIFIER_SEPARATOR) => None,
-			(ValidByte::Separator, b'-') => None,
+			(ValidByte::Byte(prev), IDENTIFIER_SEPARATOR) if is_word_separator(*prev) => None,
+			(ValidByte::Separator, next) if is_word_separator(next) => None,
 
 			(_, IDENTIFIER_SEPARATOR) => Some(ValidByte::Separator),
 			(ValidByte::Separator, next) => ValidByte::alpha_numeric(next),
-			(ValidByte::Byte(b'-'), next) => ValidByte::alpha_numeric(next),
-			(ValidByte::Byte(_), next) => ValidByte::alpha_numeric_hyphen(next),
+			(ValidByte::Byte(prev), next) if is_word_separator(*prev) => ValidByte::alpha_numeric(next),
+			(ValidByte::Byte(_), next) => ValidByte::alpha_numeric_word_separator(next),
 		}
 	}
 }
@@ -115,8 +119,8 @@ pub enum ParseIdentifierError {
 
 	/// Identifier is not in a valid format.
 	#[error(
-		"identifiers can only include lowercase ASCII, hyphens which are not leading or trailing, \
-		 and a single colon if using a prefix"
+		"identifiers can only include lowercase ASCII, single hyphens, underscores or dots which are \
+		 not leading or trailing, and a single colon if using a prefix"
 	)]
 	InvalidFormat,
 
@@ -124,8 +128,8 @@ pub enum ParseIdentifierError {
 	#[error("identifiers can only include a single separator '{}'", IDENTIFIER_SEPARATOR)]
 	MultipleSeparators,
 
-	/// Identifier has a trailing hyphen.
-	#[error("identifiers cannot have a trailing hyphen")]
+	/// Identifier has a trailing hyphen, underscore or dot.
+	#[error("identifiers cannot have a trailing hyphen, underscore or dot")]
 	TrailingHyphen,
 
 	/// Identifier has a prefix without a base.
@@ -186,8 +190,8 @@ impl TryFrom<String> for Identifier {
 			// empty base
 			ValidByte::Separator => return Err(Self::Error::PrefixWithoutBase),
 
-			// trailing hyphen
-			ValidByte::Byte(b'-') => return Err(Self::Error::TrailingHyphen),
+			// trailing hyphen, underscore or dot
+			ValidByte::Byte(byte) if is_word_separator(byte) => return Err(Self::Error::TrailingHyphen),
 
 			_ => (),
 		}
@@ -231,6 +235,10 @@ mod tests {
 
 		assert!(ident("preFix:base").is_ok());
 
+		assert!(ident("prefix:cmd.read_file.allow").is_ok());
+
+		assert!(ident("prefix:allow_read-file").is_ok());
+
 		// bad
 		assert!(ident("tauri-plugin-prefix:base").is_err());
 
@@ -252,6 +260,16 @@ mod tests {
 
 		assert!(ident("prefix::base").is_err());
 
+		assert!(ident("prefix:cmd..allow").is_err());
+
+		assert!(ident("prefix:cmd._allow").is_err());
+
+		assert!(ident("prefix:.cmd").is_err());
+
+		assert!(ident("prefix:cmd.").is_err());
+
+		assert!(ident("prefix_:cmd").is_err());
+
 		assert!(ident(":base").is_err());
 
 		assert!(ident("prefix:").is_err());
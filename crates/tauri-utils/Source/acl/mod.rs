@@ -42,6 +42,39 @@ pub const APP_ACL_KEY:&str = "__app-acl__";
 pub const ACL_MANIFESTS_FILE_NAME:&str = "acl-manifests.json";
 /// Known capabilityies file
 pub const CAPABILITIES_FILE_NAME:&str = "capabilities.json";
+/// Environment variable that overrides the folder where schemas, ACL
+/// manifests and capabilities are saved.
+pub const SCHEMAS_OUT_DIR_ENV:&str = "TAURI_SCHEMAS_OUT_DIR";
+/// Default folder where schemas, ACL manifests and capabilities are saved,
+/// relative to the Tauri project directory.
+pub const DEFAULT_SCHEMAS_OUT_DIR:&str = "gen/schemas";
+
+/// Resolves the folder where schemas, ACL manifests and capabilities are
+/// saved.
+///
+/// The first of these values is used:
+///
+/// 1. the [`SCHEMAS_OUT_DIR_ENV`] environment variable;
+/// 2. `attribute`, the folder set with `tauri_build::Attributes::schemas_out_dir`,
+///    only known to the build script;
+/// 3. `configured`, the `build > schemasDir` configuration value;
+/// 4. [`DEFAULT_SCHEMAS_OUT_DIR`].
+///
+/// Relative paths are resolved against `project_dir`.
+pub fn schemas_out_dir(
+	project_dir:&std::path::Path,
+	attribute:Option<&std::path::Path>,
+	configured:Option<&std::path::Path>,
+) -> std::path::PathBuf {
+	let dir = std::env::var_os(SCHEMAS_OUT_DIR_ENV)
+		.filter(|dir| !dir.is_empty())
+		.map(std::path::PathBuf::from)
+		.or_else(|| attribute.map(ToOwned::to_owned))
+		.or_else(|| configured.map(ToOwned::to_owned))
+		.unwrap_or_else(|| DEFAULT_SCHEMAS_OUT_DIR.into());
+
+	project_dir.join(dir)
+}
 
 #[cfg(feature = "build")]
 pub mod build;
@@ -388,6 +421,28 @@ mod tests {
 
 		assert!(pattern.test(&"custom://localhost/path".parse().unwrap()));
 	}
+
+	#[test]
+	fn schemas_out_dir_precedence() {
+		use std::path::Path;
+
+		use super::{DEFAULT_SCHEMAS_OUT_DIR, SCHEMAS_OUT_DIR_ENV, schemas_out_dir};
+
+		if std::env::var_os(SCHEMAS_OUT_DIR_ENV).is_some() {
+			return;
+		}
+
+		let project = Path::new("project");
+
+		assert_eq!(
+			schemas_out_dir(project, Some(Path::new("attribute")), Some(Path::new("config"))),
+			project.join("attribute")
+		);
+
+		assert_eq!(schemas_out_dir(project, None, Some(Path::new("config"))), project.join("config"));
+
+		assert_eq!(schemas_out_dir(project, None, None), project.join(DEFAULT_SCHEMAS_OUT_DIR));
+	}
 }
 
 #[cfg(feature = "build")]
@@ -7,7 +7,7 @@
 use std::{
 	collections::{BTreeMap, btree_map::Values},
 	fs,
-	path::{Path, PathBuf},
+	path::Path,
 	slice::Iter,
 };
 
@@ -26,8 +26,10 @@ use crate::{platform::Target, write_if_changed};
 
 /// Capability schema file name.
 pub const CAPABILITIES_SCHEMA_FILE_NAME:&str = "schema.json";
-/// Path of the folder where schemas are saved.
-pub const CAPABILITIES_SCHEMA_FOLDER_PATH:&str = "gen/schemas";
+/// Path of the folder where schemas are saved by default.
+///
+/// See [`super::schemas_out_dir`] to resolve the configured folder.
+pub const CAPABILITIES_SCHEMA_FOLDER_PATH:&str = super::DEFAULT_SCHEMAS_OUT_DIR;
 
 // TODO: once MSRV is high enough, remove generic and use impl <trait>
 // see https://github.com/tauri-apps/tauri/commit/b5561d74aee431f93c0c5b0fa6784fc0a956effe#diff-7c31d393f83cae149122e74ad44ac98e7d70ffb45c9e5b0a94ec52881b6f1cebR30-R42
@@ -256,6 +258,16 @@ fn extend_permission_entry_schema(root_schema:&mut RootSchema, acl:&BTreeMap<Str
 pub fn generate_capability_schema(
 	acl:&BTreeMap<String, Manifest>,
 	target:Target,
+) -> crate::Result<()> {
+	generate_capability_schema_in(acl, target, Path::new(CAPABILITIES_SCHEMA_FOLDER_PATH))
+}
+
+/// Generate schema for CapabilityFile with all possible plugins permissions in
+/// the given folder.
+pub fn generate_capability_schema_in(
+	acl:&BTreeMap<String, Manifest>,
+	target:Target,
+	out_dir:&Path,
 ) -> crate::Result<()> {
 	let mut schema = schemars::schema_for!(CapabilityFile);
 
@@ -263,8 +275,7 @@ pub fn generate_capability_schema(
 	extend_permission_entry_schema(&mut schema, acl);
 
 	let schema_str = serde_json::to_string_pretty(&schema).unwrap();
-	let out_dir = PathBuf::from(CAPABILITIES_SCHEMA_FOLDER_PATH);
-	fs::create_dir_all(&out_dir)?;
+	fs::create_dir_all(out_dir)?;
 
 	let schema_path = out_dir.join(format!("{target}-{CAPABILITIES_SCHEMA_FILE_NAME}"));
 	if schema_str != fs::read_to_string(&schema_path).unwrap_or_default() {
@@ -40,6 +40,7 @@ use std::{
   collections::HashMap,
   fmt::{self, Display},
   fs::read_to_string,
+  num::NonZeroU64,
   path::PathBuf,
   str::FromStr,
 };
@@ -1449,6 +1450,17 @@ pub struct WindowEffectsConfig {
   pub color: Option<Color>,
 }
 
+/// The monitor a window enters fullscreen on.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(untagged)]
+pub enum FullscreenMonitor {
+  /// The index of the monitor in the list of available monitors.
+  Index(usize),
+  /// The name of the monitor.
+  Name(String),
+}
+
 /// The window configuration object.
 ///
 /// See more: <https://v2.tauri.app/reference/config/#windowconfig>
@@ -1536,6 +1548,16 @@ pub struct WindowConfig {
   /// Whether the window starts as fullscreen or not.
   #[serde(default)]
   pub fullscreen: bool,
+  /// The monitor the window starts fullscreen on, either its index in the list of available
+  /// monitors or its name. Starts the window as fullscreen.
+  ///
+  /// The window falls back to the monitor it would be created on when the monitor is not found.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS**: Unsupported.
+  #[serde(alias = "fullscreen-monitor")]
+  pub fullscreen_monitor: Option<FullscreenMonitor>,
   /// Whether the window will be initially focused or not.
   #[serde(default = "default_true")]
   pub focus: bool,
@@ -1606,9 +1628,13 @@ pub struct WindowConfig {
   ///   - `false` has no effect on decorated window, shadow are always ON.
   ///   - `true` will make undecorated window have a 1px white border,
   /// and on Windows 11, it will have a rounded corners.
-  /// - **Linux:** Unsupported.
-  #[serde(default = "default_true")]
-  pub shadow: bool,
+  /// - **Linux:** Opt-in, `true` makes the window use the GTK client-side decorations,
+  ///   which draw the shadow and the resize borders around undecorated windows.
+  ///   Windows created without it keep their decorations when the shadow is changed later.
+  ///
+  /// Defaults to `true` on Windows and macOS, and to no shadow on Linux.
+  #[serde(default)]
+  pub shadow: Option<bool>,
   /// Window effects.
   ///
   /// Requires the window to be transparent.
@@ -1658,6 +1684,14 @@ pub struct WindowConfig {
   /// - **Android / iOS**: Unsupported.
   #[serde(default, alias = "zoom-hotkeys-enabled")]
   pub zoom_hotkeys_enabled: bool,
+  /// The initial zoom factor of the webview, `1.0` being the default size.
+  ///
+  /// The zoom is kept across the navigations of the webview.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android / iOS**: Unsupported.
+  pub zoom: Option<f64>,
   /// Whether browser extensions can be installed for the webview process
   ///
   /// ## Platform-specific:
@@ -1698,6 +1732,12 @@ pub struct WindowConfig {
   /// - **Windows**: On Windows 8 and newer, if alpha channel is not `0`, it will be ignored for the webview layer.
   #[serde(alias = "background-color")]
   pub background_color: Option<Color>,
+  /// The name of a preset in `app > windowPresets` this window is based on.
+  ///
+  /// The preset values are used for every field this window leaves at its default value,
+  /// the `label`, `url` and `create` values always come from this window.
+  /// An unknown preset name fails the window creation.
+  pub preset: Option<String>,
 }
 
 impl Default for WindowConfig {
@@ -1723,6 +1763,7 @@ impl Default for WindowConfig {
       closable: true,
       title: default_title(),
       fullscreen: false,
+      fullscreen_monitor: None,
       focus: false,
       transparent: false,
       maximized: false,
@@ -1740,17 +1781,106 @@ impl Default for WindowConfig {
       accept_first_mouse: false,
       tabbing_identifier: None,
       additional_browser_args: None,
-      shadow: true,
+      shadow: None,
       window_effects: None,
       incognito: false,
       parent: None,
       proxy_url: None,
       zoom_hotkeys_enabled: false,
+      zoom: None,
       browser_extensions_enabled: false,
       use_https_scheme: false,
       devtools: None,
       background_color: None,
+      preset: None,
+    }
+  }
+}
+
+impl WindowConfig {
+  /// Bases this window configuration on a preset from `app > windowPresets`.
+  ///
+  /// Every field this configuration leaves at its default value takes the preset value,
+  /// except `label`, `url` and `create` which are kept. The returned configuration has no
+  /// [`Self::preset`].
+  pub fn with_preset(&self, preset: &WindowConfig) -> Self {
+    let default = Self::default();
+    let mut config = preset.clone();
+
+    macro_rules! keep_set_fields {
+      ($($field:ident),*) => {
+        // destructured so a new field fails to compile until it is listed here
+        let Self {
+          label: _,
+          url: _,
+          create: _,
+          preset: _,
+          $($field: _),*
+        } = self;
+
+        $(
+          if self.$field != default.$field {
+            config.$field = self.$field.clone();
+          }
+        )*
+      };
     }
+
+    keep_set_fields!(
+      user_agent,
+      drag_drop_enabled,
+      center,
+      x,
+      y,
+      width,
+      height,
+      min_width,
+      min_height,
+      max_width,
+      max_height,
+      resizable,
+      maximizable,
+      minimizable,
+      closable,
+      title,
+      fullscreen,
+      fullscreen_monitor,
+      focus,
+      transparent,
+      maximized,
+      visible,
+      decorations,
+      always_on_bottom,
+      always_on_top,
+      visible_on_all_workspaces,
+      content_protected,
+      skip_taskbar,
+      window_classname,
+      theme,
+      title_bar_style,
+      hidden_title,
+      accept_first_mouse,
+      tabbing_identifier,
+      additional_browser_args,
+      shadow,
+      window_effects,
+      incognito,
+      parent,
+      proxy_url,
+      zoom_hotkeys_enabled,
+      zoom,
+      browser_extensions_enabled,
+      use_https_scheme,
+      devtools,
+      background_color
+    );
+
+    config.label = self.label.clone();
+    config.url = self.url.clone();
+    config.create = self.create;
+    config.preset = None;
+
+    config
   }
 }
 
@@ -2326,6 +2456,14 @@ pub struct SecurityConfig {
   /// This doesn't include IPC Messages and error responses
   #[serde(default)]
   pub headers: Option<HeaderConfig>,
+  /// Rotates the AES-GCM keys shared with the application of the
+  /// [isolation pattern](#SecurityConfig.pattern) every given number of seconds.
+  /// The keys live for the whole app session by default.
+  ///
+  /// Messages encrypted with the previous keys are still accepted for a short grace period
+  /// after a rotation so the in-flight invokes don't fail.
+  #[serde(default, alias = "isolation-key-rotation-interval-seconds")]
+  pub isolation_key_rotation_interval_seconds: Option<NonZeroU64>,
 }
 
 /// A capability entry which can be either an inlined capability or a reference to a capability defined on its own file.
@@ -2398,6 +2536,13 @@ pub struct AppConfig {
   /// If set to true "identifier" will be set as GTK app ID (on systems that use GTK).
   #[serde(rename = "enableGTKAppId", alias = "enable-gtk-app-id", default)]
   pub enable_gtk_app_id: bool,
+  /// Named window configurations that can be reused with the `preset` value of `windows`
+  /// entries and to create windows with
+  /// [`WebviewWindowBuilder::from_preset`](https://docs.rs/tauri/2/tauri/webview/struct.WebviewWindowBuilder.html#method.from_preset).
+  ///
+  /// The `label` and `url` values of a preset are ignored, they are provided when creating the window.
+  #[serde(default, alias = "window-presets")]
+  pub window_presets: HashMap<String, WindowConfig>,
 }
 
 impl AppConfig {
@@ -2661,6 +2806,13 @@ pub struct BuildConfig {
   pub before_bundle_command: Option<HookCommand>,
   /// Features passed to `cargo` commands.
   pub features: Option<Vec<String>>,
+  /// The directory where the capability schemas, ACL manifests and resolved capabilities are written,
+  /// relative to the Tauri project directory. Defaults to `gen/schemas`.
+  ///
+  /// The `TAURI_SCHEMAS_OUT_DIR` environment variable and the `tauri_build::Attributes::schemas_out_dir` value take
+  /// precedence over this value.
+  #[serde(alias = "schemas-dir")]
+  pub schemas_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -2849,6 +3001,7 @@ fn default_build() -> BuildConfig {
     before_build_command: None,
     before_bundle_command: None,
     features: None,
+    schemas_dir: None,
   }
 }
 
@@ -2888,6 +3041,20 @@ mod build {
     }
   }
 
+  impl ToTokens for FullscreenMonitor {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+      let prefix = quote! { ::tauri::utils::config::FullscreenMonitor };
+
+      tokens.append_all(match self {
+        Self::Index(index) => quote! { #prefix::Index(#index) },
+        Self::Name(name) => {
+          let name = str_lit(name);
+          quote! { #prefix::Name(#name) }
+        }
+      })
+    }
+  }
+
   impl ToTokens for crate::Theme {
     fn to_tokens(&self, tokens: &mut TokenStream) {
       let prefix = quote! { ::tauri::utils::Theme };
@@ -3008,6 +3175,7 @@ mod build {
       let title = str_lit(&self.title);
       let proxy_url = opt_lit(self.proxy_url.as_ref().map(url_lit).as_ref());
       let fullscreen = self.fullscreen;
+      let fullscreen_monitor = opt_lit(self.fullscreen_monitor.as_ref());
       let focus = self.focus;
       let transparent = self.transparent;
       let maximized = self.maximized;
@@ -3025,15 +3193,17 @@ mod build {
       let accept_first_mouse = self.accept_first_mouse;
       let tabbing_identifier = opt_str_lit(self.tabbing_identifier.as_ref());
       let additional_browser_args = opt_str_lit(self.additional_browser_args.as_ref());
-      let shadow = self.shadow;
+      let shadow = opt_lit(self.shadow.as_ref());
       let window_effects = opt_lit(self.window_effects.as_ref());
       let incognito = self.incognito;
       let parent = opt_str_lit(self.parent.as_ref());
       let zoom_hotkeys_enabled = self.zoom_hotkeys_enabled;
+      let zoom = opt_lit(self.zoom.as_ref());
       let browser_extensions_enabled = self.browser_extensions_enabled;
       let use_https_scheme = self.use_https_scheme;
       let devtools = opt_lit(self.devtools.as_ref());
       let background_color = opt_lit(self.background_color.as_ref());
+      let preset = opt_str_lit(self.preset.as_ref());
 
       literal_struct!(
         tokens,
@@ -3059,6 +3229,7 @@ mod build {
         title,
         proxy_url,
         fullscreen,
+        fullscreen_monitor,
         focus,
         transparent,
         maximized,
@@ -3081,10 +3252,12 @@ mod build {
         incognito,
         parent,
         zoom_hotkeys_enabled,
+        zoom,
         browser_extensions_enabled,
         use_https_scheme,
         devtools,
-        background_color
+        background_color,
+        preset
       );
     }
   }
@@ -3226,6 +3399,7 @@ mod build {
       let before_build_command = quote!(None);
       let before_bundle_command = quote!(None);
       let features = quote!(None);
+      let schemas_dir = quote!(None);
 
       literal_struct!(
         tokens,
@@ -3236,7 +3410,8 @@ mod build {
         before_dev_command,
         before_build_command,
         before_bundle_command,
-        features
+        features,
+        schemas_dir
       );
     }
   }
@@ -3384,6 +3559,15 @@ mod build {
       let pattern = &self.pattern;
       let capabilities = vec_lit(&self.capabilities, identity);
       let headers = opt_lit(self.headers.as_ref());
+      let isolation_key_rotation_interval_seconds = opt_lit(
+        self
+          .isolation_key_rotation_interval_seconds
+          .map(|seconds| {
+            let seconds = seconds.get();
+            quote!(::std::num::NonZeroU64::new(#seconds).unwrap())
+          })
+          .as_ref(),
+      );
 
       literal_struct!(
         tokens,
@@ -3395,7 +3579,8 @@ mod build {
         asset_protocol,
         pattern,
         capabilities,
-        headers
+        headers,
+        isolation_key_rotation_interval_seconds
       );
     }
   }
@@ -3459,6 +3644,12 @@ mod build {
       let macos_private_api = self.macos_private_api;
       let with_global_tauri = self.with_global_tauri;
       let enable_gtk_app_id = self.enable_gtk_app_id;
+      let window_presets = map_lit(
+        quote! { ::std::collections::HashMap },
+        &self.window_presets,
+        str_lit,
+        identity,
+      );
 
       literal_struct!(
         tokens,
@@ -3468,7 +3659,8 @@ mod build {
         tray_icon,
         macos_private_api,
         with_global_tauri,
-        enable_gtk_app_id
+        enable_gtk_app_id,
+        window_presets
       );
     }
   }
@@ -3544,11 +3736,13 @@ mod test {
         pattern: Default::default(),
         capabilities: Vec::new(),
         headers: None,
+        isolation_key_rotation_interval_seconds: None,
       },
       tray_icon: None,
       macos_private_api: false,
       with_global_tauri: false,
       enable_gtk_app_id: false,
+      window_presets: Default::default(),
     };
 
     // create a build config
@@ -3560,6 +3754,7 @@ mod test {
       before_build_command: None,
       before_bundle_command: None,
       features: None,
+      schemas_dir: None,
     };
 
     // create a bundle config
@@ -3611,4 +3806,66 @@ mod test {
 
     assert_eq!(Color(0, 255, 0, 255), "#00ff00ff".parse().unwrap());
   }
+
+  #[test]
+  fn isolation_key_rotation_interval_is_never_zero() {
+    let security = |interval: u64| {
+      serde_json::from_value::<SecurityConfig>(serde_json::json!({
+        "isolationKeyRotationIntervalSeconds": interval
+      }))
+    };
+
+    assert_eq!(
+      security(60).unwrap().isolation_key_rotation_interval_seconds,
+      NonZeroU64::new(60)
+    );
+
+    assert!(security(0).is_err());
+  }
+
+  #[test]
+  fn window_shadow_is_only_set_when_configured() {
+    let shadow = |window: serde_json::Value| {
+      serde_json::from_value::<WindowConfig>(window)
+        .unwrap()
+        .shadow
+    };
+
+    // the platforms pick their default, which is no shadow on Linux
+    assert_eq!(shadow(serde_json::json!({})), None);
+    assert_eq!(shadow(serde_json::json!({ "shadow": true })), Some(true));
+    assert_eq!(shadow(serde_json::json!({ "shadow": false })), Some(false));
+  }
+
+  #[test]
+  fn windows_keep_their_set_fields_over_the_preset() {
+    let preset = serde_json::from_value::<WindowConfig>(serde_json::json!({
+      "label": "ignored",
+      "url": "ignored.html",
+      "title": "Palette",
+      "width": 300,
+      "decorations": false,
+      "alwaysOnTop": true
+    }))
+    .unwrap();
+
+    let window = serde_json::from_value::<WindowConfig>(serde_json::json!({
+      "label": "colors",
+      "url": "colors.html",
+      "title": "Colors",
+      "preset": "palette"
+    }))
+    .unwrap();
+
+    let config = window.with_preset(&preset);
+
+    assert_eq!(config.label, "colors");
+    assert_eq!(config.url, WebviewUrl::App("colors.html".into()));
+    assert_eq!(config.preset, None);
+    assert_eq!(config.title, "Colors");
+    assert_eq!(config.width, 300.);
+    assert!(!config.decorations);
+    assert!(config.always_on_top);
+    assert_eq!(config.height, WindowConfig::default().height);
+  }
 }
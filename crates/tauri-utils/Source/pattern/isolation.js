@@ -27,16 +27,45 @@
    */
   const aesGcmKeyRaw = new Uint8Array(__TEMPLATE_runtime_aes_gcm_key__)
 
+  /**
+   * @type {number | null} - How often the rotated keys are fetched, injected by Tauri during runtime
+   */
+  const keyRefreshIntervalMs = __TEMPLATE_key_refresh_interval_ms__
+
+  /**
+   * @param {Uint8Array} raw
+   * @return {Promise<CryptoKey>}
+   */
+  function importKey(raw) {
+    return window.crypto.subtle.importKey('raw', raw, 'AES-GCM', false, [
+      'encrypt'
+    ])
+  }
+
   /**
    * @type {CryptoKey}
    */
-  const aesGcmKey = await window.crypto.subtle.importKey(
-    'raw',
-    aesGcmKeyRaw,
-    'AES-GCM',
-    false,
-    ['encrypt']
-  )
+  let aesGcmKey = await importKey(aesGcmKeyRaw)
+
+  /**
+   * Replaces the key with the one the backend currently uses.
+   *
+   * The key is served by the isolation origin, so the main frame cannot read it.
+   */
+  async function refreshKey() {
+    try {
+      const response = await fetch('/__tauri_isolation_key__')
+      if (response.ok) {
+        aesGcmKey = await importKey(new Uint8Array(await response.json()))
+      }
+    } catch (e) {
+      console.error('[TAURI] failed to refresh the isolation key', e)
+    }
+  }
+
+  if (keyRefreshIntervalMs !== null) {
+    setInterval(refreshKey, keyRefreshIntervalMs)
+  }
 
   /**
    * @param {object} data
@@ -7,6 +7,8 @@ use std::{
 	borrow::Cow,
 	fmt::{Debug, Formatter},
 	string::FromUtf8Error,
+	sync::RwLock,
+	time::{Duration, Instant},
 };
 
 use aes_gcm::{Aes256Gcm, KeyInit, Nonce, aead::Aead};
@@ -16,6 +18,9 @@ use serialize_to_javascript::{Template, default_template};
 /// The style for the isolation iframe.
 pub const IFRAME_STYLE:&str = "#__tauri_isolation__ { display: none !important }";
 
+/// How long the keys replaced by a rotation keep decrypting messages.
+pub const KEY_ROTATION_GRACE_PERIOD:Duration = Duration::from_secs(30);
+
 /// Errors that can occur during Isolation keys generation.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -93,18 +98,124 @@ impl Keys {
 	pub fn aes_gcm(&self) -> &AesGcmPair { &self.aes_gcm }
 
 	/// Decrypts a message using the generated keys.
-	pub fn decrypt(&self, raw:RawIsolationPayload<'_>) -> Result<Vec<u8>, Error> {
-		let RawIsolationPayload { nonce, payload, .. } = raw;
+	pub fn decrypt(&self, raw:RawIsolationPayload<'_>) -> Result<Vec<u8>, Error> { self.decrypt_ref(&raw) }
 
-		let nonce:[u8; 12] = nonce.as_ref().try_into()?;
+	fn decrypt_ref(&self, raw:&RawIsolationPayload<'_>) -> Result<Vec<u8>, Error> {
+		let nonce:[u8; 12] = raw.nonce.as_ref().try_into()?;
 
 		self.aes_gcm
 			.key
-			.decrypt(Nonce::from_slice(&nonce), payload.as_ref())
+			.decrypt(Nonce::from_slice(&nonce), raw.payload.as_ref())
 			.map_err(|_| self::Error::Aes)
 	}
 }
 
+/// The isolation [`Keys`], optionally rotated on an interval.
+///
+/// Rotations happen lazily, when the keys are used after the interval elapsed.
+/// The replaced keys keep decrypting messages for
+/// [`KEY_ROTATION_GRACE_PERIOD`] so the messages encrypted right before a
+/// rotation are still accepted.
+#[derive(Debug)]
+pub struct KeyRing {
+	rotation_interval:Option<Duration>,
+	state:RwLock<KeyRingState>,
+}
+
+#[derive(Debug)]
+struct KeyRingState {
+	current:Keys,
+	rotated_at:Instant,
+	previous:Option<Keys>,
+}
+
+impl KeyRingState {
+	fn replace(&mut self, keys:Keys) {
+		self.previous = Some(std::mem::replace(&mut self.current, keys));
+
+		self.rotated_at = Instant::now();
+
+		log::debug!("rotated the isolation pattern keys");
+	}
+}
+
+impl KeyRing {
+	/// Creates a key ring that uses the given keys until they are rotated.
+	pub fn new(keys:Keys) -> Self {
+		Self {
+			rotation_interval:None,
+			state:RwLock::new(KeyRingState { current:keys, rotated_at:Instant::now(), previous:None }),
+		}
+	}
+
+	/// Rotates the keys every `interval`, never when `None`.
+	pub fn set_rotation_interval(&mut self, interval:Option<Duration>) {
+		self.rotation_interval = interval;
+	}
+
+	/// The interval the keys are rotated on.
+	pub fn rotation_interval(&self) -> Option<Duration> { self.rotation_interval }
+
+	/// The keys new messages must be encrypted with, rotating them first if
+	/// the interval elapsed.
+	pub fn current(&self) -> Result<Keys, Error> {
+		self.rotate_if_expired()?;
+
+		Ok(self.state.read().unwrap().current.clone())
+	}
+
+	/// Replaces the keys, the previous ones are accepted for
+	/// [`KEY_ROTATION_GRACE_PERIOD`].
+	pub fn rotate(&self) -> Result<(), Error> {
+		let keys = Keys::new()?;
+
+		self.state.write().unwrap().replace(keys);
+
+		Ok(())
+	}
+
+	fn rotate_if_expired(&self) -> Result<(), Error> {
+		let Some(interval) = self.rotation_interval else {
+			return Ok(());
+		};
+
+		if self.state.read().unwrap().rotated_at.elapsed() < interval {
+			return Ok(());
+		}
+
+		let keys = Keys::new()?;
+
+		let mut state = self.state.write().unwrap();
+
+		// another thread might have rotated them in the meantime
+		if state.rotated_at.elapsed() >= interval {
+			state.replace(keys);
+		}
+
+		Ok(())
+	}
+
+	/// Decrypts a message with the current keys, or with the previous keys
+	/// during the grace period of a rotation.
+	pub fn decrypt(&self, raw:RawIsolationPayload<'_>) -> Result<Vec<u8>, Error> {
+		self.rotate_if_expired()?;
+
+		let state = self.state.read().unwrap();
+
+		match state.current.decrypt_ref(&raw) {
+			Err(Error::Aes) => {
+				match &state.previous {
+					Some(previous) if state.rotated_at.elapsed() < KEY_ROTATION_GRACE_PERIOD => {
+						previous.decrypt_ref(&raw)
+					},
+					_ => Err(Error::Aes),
+				}
+			},
+			result => result,
+		}
+	}
+}
+
 /// Raw representation of
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -146,6 +257,9 @@ pub struct IsolationJavascriptRuntime<'a> {
 	pub runtime_aes_gcm_key:&'a [u8; 32],
 	/// The origin the isolation application is expecting messages from.
 	pub origin:String,
+	/// How often the isolation application fetches the rotated keys, `None`
+	/// when they are never rotated.
+	pub key_refresh_interval_ms:Option<u64>,
 	/// The function that processes the IPC message.
 	#[raw]
 	pub process_ipc_message_fn:&'a str,
@@ -159,4 +273,59 @@ mod test {
 
 		Ok(())
 	}
+
+	fn encrypt(keys:&super::Keys, payload:&[u8]) -> Vec<u8> {
+		let nonce = [7u8; 12];
+
+		let encrypted = keys.aes_gcm().encrypt(&nonce, payload).unwrap();
+
+		serde_json::to_vec(&serde_json::json!({
+		  "nonce": nonce,
+		  "payload": encrypted,
+		  "contentType": "application/json",
+		}))
+		.unwrap()
+	}
+
+	#[test]
+	fn decrypt_across_rotation() -> Result<(), Box<dyn std::error::Error>> {
+		let ring = super::KeyRing::new(super::Keys::new()?);
+
+		let before = ring.current()?;
+
+		// an invoke encrypted right before the rotation
+		let in_flight = encrypt(&before, b"{}");
+
+		ring.rotate()?;
+
+		let after = ring.current()?;
+
+		assert_ne!(before.aes_gcm().raw(), after.aes_gcm().raw());
+
+		assert_eq!(ring.decrypt((&in_flight).try_into()?)?, b"{}");
+
+		assert_eq!(ring.decrypt((&encrypt(&after, b"[]")).try_into()?)?, b"[]");
+
+		// the keys from two rotations ago are rejected
+		ring.rotate()?;
+
+		assert!(ring.decrypt((&in_flight).try_into()?).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn rotate_on_interval() -> Result<(), Box<dyn std::error::Error>> {
+		let mut ring = super::KeyRing::new(super::Keys::new()?);
+
+		let initial = ring.current()?;
+
+		assert_eq!(ring.current()?.aes_gcm().raw(), initial.aes_gcm().raw());
+
+		ring.set_rotation_interval(Some(std::time::Duration::ZERO));
+
+		assert_ne!(ring.current()?.aes_gcm().raw(), initial.aes_gcm().raw());
+
+		Ok(())
+	}
 }
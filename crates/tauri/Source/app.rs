@@ -6,7 +6,13 @@ use std::{
 	borrow::Cow,
 	collections::HashMap,
 	fmt,
-	sync::{Arc, MutexGuard, mpsc::Sender},
+	future::Future,
+	sync::{
+		Arc,
+		MutexGuard,
+		mpsc::{Sender, channel},
+	},
+	time::Duration,
 };
 
 use raw_window_handle::HasDisplayHandle;
@@ -34,6 +40,7 @@ use crate::{
 	Emitter,
 	Event,
 	EventLoopMessage,
+	EventPayloadRef,
 	EventTarget,
 	Listener,
 	Manager,
@@ -46,16 +53,21 @@ use crate::{
 	Webview,
 	WebviewWindowBuilder,
 	Window,
-	event::EventId,
+	event::{EventId, EventReplayConfig},
 	image::Image,
 	ipc::{
 		CallbackFn,
 		CommandArg,
+		CommandExecution,
 		CommandItem,
+		HandlerCollection,
 		Invoke,
+		InvokeDispatcher,
 		InvokeError,
 		InvokeHandler,
 		InvokeResponseBody,
+		InvokeSystem,
+		InvokeTransport,
 		channel::ChannelDataIpcQueue,
 	},
 	manager::{AppManager, Asset, webview::UriSchemeProtocol},
@@ -72,14 +84,25 @@ use crate::{
 	webview::PageLoadPayload,
 };
 
+pub(crate) mod metrics;
 pub(crate) mod plugin;
+pub(crate) mod shutdown;
+#[cfg(single_instance)]
+pub(crate) mod single_instance;
 
 #[cfg(desktop)]
 pub(crate) type GlobalMenuEventListener<T> = Box<dyn Fn(&T, crate::menu::MenuEvent) + Send + Sync>;
+#[cfg(desktop)]
+pub(crate) type MenuItemClickHandler<R> = Arc<dyn Fn(&AppHandle<R>) + Send + Sync>;
 #[cfg(all(desktop, feature = "tray-icon"))]
 pub(crate) type GlobalTrayIconEventListener<T> =
 	Box<dyn Fn(&T, crate::tray::TrayIconEvent) + Send + Sync>;
+#[cfg(all(desktop, feature = "tray-icon"))]
+pub(crate) type TrayMenuFactory<R> = Arc<
+	dyn Fn(crate::menu::MenuBuilder<'_, R, AppHandle<R>>) -> crate::Result<Menu<R>> + Send + Sync,
+>;
 pub(crate) type GlobalWindowEventListener<R> = Box<dyn Fn(&Window<R>, &WindowEvent) + Send + Sync>;
+pub(crate) type OnWindowStateDropped<R> = Box<dyn Fn(&Window<R>) + Send + Sync>;
 pub(crate) type GlobalWebviewEventListener<R> =
 	Box<dyn Fn(&Webview<R>, &WebviewEvent) + Send + Sync>;
 /// A closure that is run when the Tauri application is setting up.
@@ -161,10 +184,24 @@ pub enum WindowEvent {
 	/// Applications might wish to react to this to change the theme of the
 	/// content of the window when the system changes the window theme.
 	///
+	/// The app is notified of the system theme changes with
+	/// [`RunEvent::ThemeChanged`], even without windows.
+	///
 	/// ## Platform-specific
 	///
-	/// - **Linux**: Not supported.
+	/// - **Linux**: Also delivered when the theme changes through the XDG
+	///   desktop portal, once per change.
 	ThemeChanged(Theme),
+	/// The regions of the client area covered by the title bar controls have
+	/// changed, after a resize or a scale factor or theme change.
+	///
+	/// See [`Window::title_bar_rects`](crate::window::Window::title_bar_rects).
+	TitleBarRectsChanged(Vec<crate::Rect>),
+	/// The monitor the window was made fullscreen on was disconnected, the
+	/// window being made fullscreen on the primary monitor instead.
+	///
+	/// See [`Window::set_fullscreen_on_monitor`](crate::window::Window::set_fullscreen_on_monitor).
+	FullscreenMonitorDisconnected,
 }
 
 impl From<RuntimeWindowEvent> for WindowEvent {
@@ -182,6 +219,10 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 			},
 			RuntimeWindowEvent::DragDrop(event) => Self::DragDrop(event),
 			RuntimeWindowEvent::ThemeChanged(theme) => Self::ThemeChanged(theme),
+			RuntimeWindowEvent::TitleBarRectsChanged(rects) => Self::TitleBarRectsChanged(rects),
+			RuntimeWindowEvent::FullscreenMonitorDisconnected => {
+				Self::FullscreenMonitorDisconnected
+			},
 		}
 	}
 }
@@ -192,12 +233,30 @@ impl From<RuntimeWindowEvent> for WindowEvent {
 pub enum WebviewEvent {
 	/// An event associated with the drag and drop action.
 	DragDrop(DragDropEvent),
+	/// The webview zoom level changed, from the API or by the user.
+	ZoomChanged(f64),
+	/// The matches of the search started with
+	/// [`Webview::find_in_page`](crate::webview::Webview::find_in_page) changed.
+	FindResultChanged(crate::webview::FindResult),
+	/// The back or forward navigation availability changed, to update the
+	/// history navigation controls.
+	HistoryChanged(crate::webview::NavigationHistory),
+	/// The devtools were opened, from the API or by the user, to update the
+	/// devtools menu items.
+	DevtoolsOpened,
+	/// The devtools were closed, from the API or by the user.
+	DevtoolsClosed,
 }
 
 impl From<RuntimeWebviewEvent> for WebviewEvent {
 	fn from(event:RuntimeWebviewEvent) -> Self {
 		match event {
 			RuntimeWebviewEvent::DragDrop(e) => Self::DragDrop(e),
+			RuntimeWebviewEvent::ZoomChanged(factor) => Self::ZoomChanged(factor),
+			RuntimeWebviewEvent::FindResultChanged(result) => Self::FindResultChanged(result),
+			RuntimeWebviewEvent::HistoryChanged(history) => Self::HistoryChanged(history),
+			RuntimeWebviewEvent::DevtoolsOpened => Self::DevtoolsOpened,
+			RuntimeWebviewEvent::DevtoolsClosed => Self::DevtoolsClosed,
 		}
 	}
 }
@@ -265,6 +324,10 @@ pub enum RunEvent {
 	#[cfg(all(desktop, feature = "tray-icon"))]
 	#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
 	TrayIconEvent(crate::tray::TrayIconEvent),
+	/// The system theme changed, whether or not a window is open.
+	///
+	/// See [`AppHandle::system_theme`].
+	ThemeChanged(Theme),
 	/// Emitted when the NSApplicationDelegate's applicationShouldHandleReopen
 	/// gets called
 	#[non_exhaustive]
@@ -425,6 +488,9 @@ impl<R:Runtime> AppHandle<R> {
 	/// dynamically e.g. after login. For plugins that are created when the app
 	/// is started, prefer [`Builder::plugin`].
 	///
+	/// Fails if the plugins it [requires](crate::plugin::Builder::requires)
+	/// are not initialized yet.
+	///
 	/// See [`Builder::plugin`] for more information.
 	///
 	/// # Examples
@@ -459,7 +525,11 @@ impl<R:Runtime> AppHandle<R> {
 		Ok(())
 	}
 
-	/// Removes the plugin with the given name.
+	/// Removes the plugin with the given name, returning whether it was
+	/// registered.
+	///
+	/// The plugin is kept, returning `false`, while other plugins
+	/// [require](crate::plugin::Builder::requires) it, remove them first.
 	///
 	/// # Examples
 	///
@@ -484,11 +554,55 @@ impl<R:Runtime> AppHandle<R> {
 	/// });
 	/// ```
 	pub fn remove_plugin(&self, plugin:&'static str) -> bool {
-		self.manager().plugins.lock().unwrap().unregister(plugin)
+		let removed = self.manager().plugins.lock().unwrap().unregister(plugin);
+
+		if removed {
+			self.manager().initialized_plugins.lock().unwrap().remove(plugin);
+		}
+
+		removed
+	}
+
+	/// Whether the plugin with the given name is initialized.
+	///
+	/// The plugins registered on the [`Builder`] are initialized before the
+	/// [setup hook](Builder::setup) runs, after the plugins they
+	/// [require](crate::plugin::Builder::requires).
+	pub fn plugin_initialized(&self, plugin:&str) -> bool {
+		self.manager().initialized_plugins.lock().unwrap().contains(plugin)
+	}
+
+	/// A snapshot of the resources used by the app: its windows and webviews,
+	/// the memory the webviews use, its event listeners and the invokes the
+	/// commands did not resolve yet.
+	///
+	/// Reading the metrics is cheap enough to poll them every few seconds, the
+	/// values the platform does not expose are `None`. See
+	/// [`AppMetrics`](crate::AppMetrics) for the platform specific behavior.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// #[tauri::command]
+	/// fn diagnostics(app: tauri::AppHandle) -> tauri::Result<tauri::AppMetrics> {
+	/// 	app.metrics()
+	/// }
+	/// ```
+	pub fn metrics(&self) -> crate::Result<metrics::AppMetrics> { metrics::collect(self) }
+
+	/// Saves the `flags` properties of every window, restored by
+	/// [`WindowBuilder::restore_state`](crate::window::WindowBuilder::restore_state).
+	///
+	/// The windows created with `restore_state` are already saved when they are
+	/// requested to close and when the app is requested to exit.
+	#[cfg(desktop)]
+	pub fn save_window_state(&self, flags:crate::window::StateFlags) -> crate::Result<()> {
+		crate::window::state::save_all(self, flags)
 	}
 
 	/// Exits the app by triggering [`RunEvent::ExitRequested`] and
 	/// [`RunEvent::Exit`].
+	///
+	/// The [shutdown hooks](Builder::on_shutdown) run before the app exits.
 	pub fn exit(&self, exit_code:i32) {
 		if let Err(e) = self.runtime_handle.request_exit(exit_code) {
 			log::error!("failed to exit: {}", e);
@@ -499,14 +613,89 @@ impl<R:Runtime> AppHandle<R> {
 
 	/// Restarts the app by triggering [`RunEvent::ExitRequested`] with code
 	/// [`RESTART_EXIT_CODE`] and [`RunEvent::Exit`]..
+	///
+	/// The [shutdown hooks](Builder::on_shutdown) run before the app restarts,
+	/// blocking the calling thread until they finish or the
+	/// [shutdown timeout](Builder::shutdown_timeout) elapses. The event loop
+	/// doesn't run meanwhile when this is called on the main thread, so the
+	/// hooks can't use the windows then.
 	pub fn restart(&self) -> ! {
+		self.run_shutdown_hooks();
+
 		if self.runtime_handle.request_exit(RESTART_EXIT_CODE).is_err() {
 			self.cleanup_before_exit();
 		}
 
+		self.release_single_instance();
+
 		crate::process::restart(&self.env());
 	}
 
+	/// Restarts the app with the given arguments and environment variables,
+	/// optionally with elevated privileges.
+	///
+	/// The replacement process is spawned first, then the
+	/// [shutdown hooks](Builder::on_shutdown) run before the app exits, so they
+	/// may still run once the replacement starts. See
+	/// [`crate::process::current_binary`] for the platform specific behavior.
+	///
+	/// Only returns if the replacement process couldn't be spawned, in which
+	/// case the app keeps running and its shutdown hooks didn't run, but it
+	/// doesn't hold the [single instance](Builder::single_instance) lock
+	/// anymore, or if `elevate` is set on a platform other than Windows.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// use tauri::process::RestartOptions;
+	///
+	/// #[tauri::command]
+	/// fn finish_migration(app: tauri::AppHandle) -> tauri::Result<()> {
+	/// 	app
+	/// 		.restart_with(RestartOptions {
+	/// 			args: vec!["--migrated".into()],
+	/// 			env: vec![("APP_MIGRATION".into(), "1".into())],
+	/// 			elevate: false,
+	/// 		})
+	/// 		.map(|never| match never {})
+	/// }
+	/// ```
+	pub fn restart_with(
+		&self,
+		options:crate::process::RestartOptions,
+	) -> crate::Result<std::convert::Infallible> {
+		if options.elevate && !cfg!(windows) {
+			return Err(crate::Error::ElevationUnsupported);
+		}
+
+		self.release_single_instance();
+
+		crate::process::spawn_replacement(&self.env(), &options)?;
+
+		self.run_shutdown_hooks();
+
+		if self.runtime_handle.request_exit(RESTART_EXIT_CODE).is_err() {
+			self.cleanup_before_exit();
+		}
+
+		std::process::exit(0);
+	}
+
+	/// Releases the [single instance](Builder::single_instance) lock, so the
+	/// process replacing the app doesn't send its launch to it.
+	fn release_single_instance(&self) {
+		#[cfg(single_instance)]
+		if let Some(single_instance) = self.try_state::<single_instance::SingleInstance>() {
+			single_instance.release();
+		}
+	}
+
+	/// Runs the [shutdown hooks](Builder::on_shutdown) if they didn't run yet.
+	pub(crate) fn run_shutdown_hooks(&self) {
+		if let Some(hooks) = self.try_state::<shutdown::ShutdownHooks<R>>() {
+			hooks.run(self);
+		}
+	}
+
 	/// Sets the activation policy for the application. It is set to
 	/// `NSApplicationActivationPolicyRegular` by default.
 	///
@@ -742,6 +931,75 @@ macro_rules! shared_app_impl {
 				}
 			}
 
+			/// The theme of the system, regardless of the theme
+			/// [set](Self::set_theme) on the app except on macOS. Its changes are
+			/// reported with [`RunEvent::ThemeChanged`].
+			///
+			/// ## Platform-specific
+			///
+			/// - **Linux:** Read once from the XDG desktop portal, light when there is
+			///   no portal or no preference.
+			/// - **macOS:** The effective appearance of the app, which includes the
+			///   theme [set](Self::set_theme) on the app.
+			/// - **iOS / Android:** Unsupported, always light.
+			pub fn system_theme(&self) -> Theme {
+				match self.runtime() {
+					RuntimeOrDispatch::Runtime(h) => h.system_theme(),
+					RuntimeOrDispatch::RuntimeHandle(h) => h.system_theme(),
+					_ => unreachable!(),
+				}
+			}
+
+			/// Sets the badge count of the app. Using `0` or `None` will remove the
+			/// badge.
+			///
+			/// ## Platform-specific
+			///
+			/// - **Windows:** The count is drawn as the taskbar overlay icon of every
+			///   window without a badge of its own, including the windows created
+			///   later. Counts above 99 are shown as `99+`.
+			/// - **Linux:** Shown by desktop environments implementing the `libunity`
+			///   launcher API, e.g. KDE or GNOME with the Dash to Dock extension, for
+			///   the `.desktop` file named after [`Builder::linux_app_id`].
+			/// - **iOS:** iOS expects i32, the value will be clamped to i32::MAX.
+			/// - **Android:** Unsupported.
+			pub fn set_badge_count(&self, count:Option<u64>) -> crate::Result<()> {
+				let desktop_filename = Some(self.manager.desktop_file_name());
+
+				match self.runtime() {
+					RuntimeOrDispatch::Runtime(h) => {
+						h.handle().set_badge_count(count, desktop_filename)?
+					},
+					RuntimeOrDispatch::RuntimeHandle(h) => {
+						h.set_badge_count(count, desktop_filename)?
+					},
+					_ => unreachable!(),
+				}
+
+				Ok(())
+			}
+
+			/// Sets the badge label of the app, for non-numeric badges. Using `None`
+			/// will remove the badge.
+			///
+			/// ## Platform-specific
+			///
+			/// - **Windows:** The label is drawn as the taskbar overlay icon of every
+			///   window without a badge of its own, including the windows created
+			///   later, so it should be a few characters at most.
+			/// - **Linux / iOS / Android:** Unsupported.
+			pub fn set_badge_label(&self, label:Option<&str>) -> crate::Result<()> {
+				let label = label.map(ToOwned::to_owned);
+
+				match self.runtime() {
+					RuntimeOrDispatch::Runtime(h) => h.handle().set_badge_label(label)?,
+					RuntimeOrDispatch::RuntimeHandle(h) => h.set_badge_label(label)?,
+					_ => unreachable!(),
+				}
+
+				Ok(())
+			}
+
 			/// Returns the default window icon.
 			pub fn default_window_icon(&self) -> Option<&Image<'_>> {
 				self.manager.window.default_icon.as_ref()
@@ -898,7 +1156,21 @@ macro_rules! shared_app_impl {
 			/// Runs necessary cleanup tasks before exiting the process.
 			/// **You should always exit the tauri app immediately after this function
 			/// returns and not use any tauri-related APIs.**
+			///
+			/// The [shutdown hooks](Builder::on_shutdown) run first if they didn't
+			/// run yet, blocking the calling thread until they finish or the
+			/// [shutdown timeout](Builder::shutdown_timeout) elapses. The hooks can't
+			/// use the windows when this is called on the main thread.
 			pub fn cleanup_before_exit(&self) {
+				self.managed_app_handle().run_shutdown_hooks();
+
+				#[cfg(single_instance)]
+				if let Some(single_instance) =
+					self.try_state::<single_instance::SingleInstance>()
+				{
+					single_instance.release();
+				}
+
 				#[cfg(all(desktop, feature = "tray-icon"))]
 				self.manager.tray.icons.lock().unwrap().clear();
 
@@ -956,6 +1228,31 @@ macro_rules! shared_app_impl {
 				self.manager.once(event.into(), EventTarget::App, handler)
 			}
 
+			/// Listen to the events accepted by `filter` on this app.
+			///
+			/// See [`Listener::listen_filter`] for more information.
+			fn listen_filter<P, F>(&self, event:impl Into<String>, filter:P, handler:F) -> EventId
+			where
+				P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+				F: Fn(Event) + Send + 'static, {
+				self.manager.listen_filter(
+					event.into(),
+					EventTarget::App,
+					Box::new(filter),
+					handler,
+				)
+			}
+
+			/// Listen to the first event accepted by `filter` on this app.
+			///
+			/// See [`Listener::listen_filter`] for more information.
+			fn once_filter<P, F>(&self, event:impl Into<String>, filter:P, handler:F) -> EventId
+			where
+				P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+				F: FnOnce(Event) + Send + 'static, {
+				self.manager.once_filter(event.into(), EventTarget::App, Box::new(filter), handler)
+			}
+
 			/// Unlisten to an event on this app.
 			///
 			/// # Examples
@@ -1072,6 +1369,11 @@ impl<R:Runtime> App<R> {
 		self.handle.plugin(crate::resources::plugin::init())?;
 
 		self.handle.plugin(crate::image::plugin::init())?;
+
+		self.handle.plugin(crate::ipc::plugin::init())?;
+
+		#[cfg(feature = "opener")]
+		self.handle.plugin(crate::opener::plugin::init())?;
 		#[cfg(desktop)]
 		self.handle.plugin(crate::menu::plugin::init())?;
 		#[cfg(all(desktop, feature = "tray-icon"))]
@@ -1169,12 +1471,17 @@ impl<R:Runtime> App<R> {
 					callback(&app_handle, event);
 				},
 				RuntimeRunEvent::Exit => {
+					app_handle.run_shutdown_hooks();
+
 					let event = on_event_loop_event(&app_handle, RuntimeRunEvent::Exit, &manager);
 
 					callback(&app_handle, event);
 
 					app_handle.cleanup_before_exit();
 				},
+				RuntimeRunEvent::ExitRequested { code, tx } => {
+					on_exit_requested(&app_handle, code, tx, &manager, &mut callback);
+				},
 				_ => {
 					let event = on_event_loop_event(&app_handle, event, &manager);
 
@@ -1220,6 +1527,12 @@ impl<R:Runtime> App<R> {
 		}
 
 		self.runtime.as_mut().unwrap().run_iteration(move |event| {
+			if let RuntimeRunEvent::ExitRequested { code, tx } = event {
+				on_exit_requested(&app_handle, code, tx, &manager, &mut callback);
+
+				return;
+			}
+
 			let event = on_event_loop_event(&app_handle, event, &manager);
 			callback(&app_handle, event);
 		})
@@ -1245,12 +1558,21 @@ pub struct Builder<R:Runtime> {
 	/// The JS message handler.
 	invoke_handler:Box<InvokeHandler<R>>,
 
+	/// Error from [`Self::invoke_handlers`], returned by [`Self::build`].
+	invoke_handler_error:Option<crate::Error>,
+
+	/// Where non-async commands are executed by default.
+	default_command_execution:CommandExecution,
+
 	/// The script that initializes the `window.__TAURI_INTERNALS__.postMessage`
 	/// function.
 	pub(crate) invoke_initialization_script:String,
 
 	channel_interceptor:Option<ChannelInterceptor<R>>,
 
+	/// Transports receiving invokes besides the webview invoke system.
+	invoke_transports:Vec<Box<dyn InvokeTransport<R>>>,
+
 	/// The setup hook.
 	setup:SetupHook<R>,
 
@@ -1282,15 +1604,39 @@ pub struct Builder<R:Runtime> {
 	#[allow(unused)]
 	enable_macos_default_menu:bool,
 
+	/// Export window menus over DBus on Linux.
+	#[cfg(desktop)]
+	#[allow(unused)]
+	menu_export_dbus:bool,
+
+	/// The app id used by the Linux launcher APIs.
+	linux_app_id:Option<String>,
+
 	/// Window event handlers that listens to all windows.
 	window_event_listeners:Vec<GlobalWindowEventListener<R>>,
 
+	/// The hooks run before the state scoped to a window is released.
+	window_state_dropped_hooks:Vec<OnWindowStateDropped<R>>,
+
 	/// Webview event handlers that listens to all webviews.
 	webview_event_listeners:Vec<GlobalWebviewEventListener<R>>,
 
 	/// The device event filter.
 	device_event_filter:DeviceEventFilter,
 
+	/// The events buffered until a listener attaches.
+	event_replay:Option<EventReplayConfig>,
+
+	/// The hooks run before the app exits.
+	shutdown_hooks:Vec<(&'static std::panic::Location<'static>, shutdown::ShutdownHook<R>)>,
+
+	/// How long the app waits for the shutdown hooks.
+	shutdown_timeout:Duration,
+
+	/// Called with the launches of the later instances of the app.
+	#[cfg(single_instance)]
+	single_instance:Option<single_instance::OnSecondInstance<R>>,
+
 	pub(crate) invoke_key:String,
 }
 
@@ -1328,6 +1674,8 @@ impl<R:Runtime> Builder<R> {
 			runtime_any_thread:false,
 			setup:Box::new(|_| Ok(())),
 			invoke_handler:Box::new(|_| false),
+			invoke_handler_error:None,
+			default_command_execution:CommandExecution::default(),
 			invoke_initialization_script:InvokeInitializationScript {
 				process_ipc_message_fn:crate::manager::webview::PROCESS_IPC_MESSAGE_FN,
 				os_name:std::env::consts::OS,
@@ -1338,6 +1686,7 @@ impl<R:Runtime> Builder<R> {
 			.unwrap()
 			.into_string(),
 			channel_interceptor:None,
+			invoke_transports:Vec::new(),
 			on_page_load:None,
 			plugins:PluginStore::default(),
 			uri_scheme_protocols:Default::default(),
@@ -1349,9 +1698,18 @@ impl<R:Runtime> Builder<R> {
 			#[cfg(all(desktop, feature = "tray-icon"))]
 			tray_icon_event_listeners:Vec::new(),
 			enable_macos_default_menu:true,
+			#[cfg(desktop)]
+			menu_export_dbus:false,
+			linux_app_id:None,
 			window_event_listeners:Vec::new(),
+			window_state_dropped_hooks:Vec::new(),
 			webview_event_listeners:Vec::new(),
 			device_event_filter:Default::default(),
+			event_replay:None,
+			shutdown_hooks:Vec::new(),
+			shutdown_timeout:shutdown::DEFAULT_SHUTDOWN_TIMEOUT,
+			#[cfg(single_instance)]
+			single_instance:None,
 			invoke_key,
 		}
 	}
@@ -1394,6 +1752,41 @@ impl<R:Runtime> Builder<R> {
 		self
 	}
 
+	/// Defines the JS message handler from a [`HandlerCollection`], usually
+	/// composed with [`compose_handlers!`](crate::compose_handlers).
+	///
+	/// If a command is defined by more than one collection, [`Self::build`]
+	/// fails with [`Error::DuplicateCommand`](crate::Error::DuplicateCommand).
+	#[must_use]
+	pub fn invoke_handlers(mut self, handlers:HandlerCollection<R>) -> Self {
+		match handlers.into_handler() {
+			Ok(handler) => {
+				self.invoke_handler = Box::new(handler);
+				self.invoke_handler_error = None;
+			},
+			Err(e) => self.invoke_handler_error = Some(e),
+		}
+
+		self
+	}
+
+	/// Sets where non-async commands without an `exec` attribute are executed.
+	///
+	/// Defaults to [`CommandExecution::Main`], use [`CommandExecution::Blocking`]
+	/// to keep commands doing I/O from freezing the UI.
+	///
+	/// # Examples
+	/// ```
+	/// tauri::Builder::default()
+	/// 	.default_command_execution(tauri::ipc::CommandExecution::Blocking);
+	/// ```
+	#[must_use]
+	pub fn default_command_execution(mut self, execution:CommandExecution) -> Self {
+		self.default_command_execution = execution;
+
+		self
+	}
+
 	/// Defines a custom JS message system.
 	///
 	/// The `initialization_script` is a script that initializes
@@ -1416,8 +1809,42 @@ impl<R:Runtime> Builder<R> {
 	/// ```
 	///
 	/// Note that the implementation details is up to your implementation.
+	///
+	/// It can also be given an [`InvokeTransport`](crate::ipc::InvokeTransport)
+	/// with [`InvokeSystem::transport`](crate::ipc::InvokeSystem::transport), to
+	/// receive the invokes of a frontend running outside of the webviews. The
+	/// built-in transport keeps serving the webviews unless the transport
+	/// returns an initialization script.
+	///
+	/// ```rust,no_run
+	/// use tauri::ipc::{InvokeDispatcher, InvokeSystem, InvokeTransport};
+	///
+	/// struct WebSocketTransport;
+	///
+	/// impl<R:tauri::Runtime> InvokeTransport<R> for WebSocketTransport {
+	/// 	fn origin(&self) -> tauri::Url { "http://kiosk.local".parse().unwrap() }
+	///
+	/// 	fn start(&self, dispatcher:InvokeDispatcher<R>) {
+	/// 		// for every accepted connection, `dispatcher.connect` it to a window with
+	/// 		// a sender writing the scripts to the socket, then call `invoke` on the
+	/// 		// returned connection for every request it sends
+	/// 	}
+	/// }
+	///
+	/// tauri::Builder::default().invoke_system(InvokeSystem::transport(WebSocketTransport));
+	/// ```
 	#[must_use]
-	pub fn invoke_system(mut self, initialization_script:String) -> Self {
+	pub fn invoke_system(mut self, system:impl Into<InvokeSystem<R>>) -> Self {
+		let system = system.into();
+
+		if let Some(transport) = system.transport {
+			self.invoke_transports.push(transport);
+		}
+
+		let Some(initialization_script) = system.initialization_script else {
+			return self;
+		};
+
 		self.invoke_initialization_script =
 			initialization_script.replace("__INVOKE_KEY__", &format!("\"{}\"", self.invoke_key));
 
@@ -1766,6 +2193,57 @@ tauri::Builder::default()
 		self
 	}
 
+	/// Export window menus over DBus (`com.canonical.dbusmenu`) so they are
+	/// displayed by the desktop global menu bar or HUD. Disabled by default.
+	///
+	/// Each window menu is registered with the `com.canonical.AppMenu.Registrar`
+	/// service and kept in sync when items change. If no registrar is running
+	/// the menu stays in the window. Menu events triggered from the exported menu
+	/// are delivered to the regular `on_menu_event` handlers.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux**: Requires the `linux-dbus-menu` Cargo feature and an X11
+	///   session.
+	/// - **Windows / macOS**: Unsupported.
+	///
+	/// # Examples
+	/// ```
+	/// tauri::Builder::default().menu_export_dbus(true);
+	/// ```
+	#[must_use]
+	#[cfg(desktop)]
+	pub fn menu_export_dbus(mut self, enable:bool) -> Self {
+		self.menu_export_dbus = enable;
+
+		self
+	}
+
+	/// Sets the app id used by the Linux launcher APIs, which show the
+	/// [taskbar progress](crate::window::Window::set_progress) and
+	/// [badge count](crate::window::Window::set_badge_count) on the launcher
+	/// entry of the `<id>.desktop` file. Defaults to the bundle identifier,
+	/// except for [`Window::set_badge_count`] and [`Window::set_progress_bar`],
+	/// which keep using the package name unless an id is set.
+	///
+	/// [`Window::set_badge_count`]: crate::window::Window::set_badge_count
+	/// [`Window::set_progress_bar`]: crate::window::Window::set_progress_bar
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / macOS / iOS / Android**: Unsupported.
+	///
+	/// # Examples
+	/// ```
+	/// tauri::Builder::default().linux_app_id("my-app");
+	/// ```
+	#[must_use]
+	pub fn linux_app_id(mut self, id:impl Into<String>) -> Self {
+		self.linux_app_id.replace(id.into());
+
+		self
+	}
+
 	/// Registers a window event handler for all windows.
 	///
 	/// # Examples
@@ -1792,6 +2270,31 @@ tauri::Builder::default()
 		self
 	}
 
+	/// Registers a hook run when a window is destroyed, before the state scoped
+	/// to it with [`Window::manage_scoped`] is released.
+	///
+	/// # Examples
+	/// ```
+	/// use std::sync::Mutex;
+	///
+	/// struct Document(Mutex<String>);
+	///
+	/// tauri::Builder::default().on_window_state_dropped(|window| {
+	///   if let Some(document) = window.try_scoped_state::<Document>() {
+	///     let _ = std::fs::write("autosave.txt", &*document.0.lock().unwrap());
+	///   }
+	/// });
+	/// ```
+	#[must_use]
+	pub fn on_window_state_dropped<F:Fn(&Window<R>) + Send + Sync + 'static>(
+		mut self,
+		hook:F,
+	) -> Self {
+		self.window_state_dropped_hooks.push(Box::new(hook));
+
+		self
+	}
+
 	/// Registers a webview event handler for all webviews.
 	///
 	/// # Examples
@@ -1941,10 +2444,151 @@ tauri::Builder::default()
 		self
 	}
 
+	/// Buffers the given events while no listener of their target is
+	/// registered, replaying them to the first matching listener that
+	/// attaches.
+	///
+	/// This avoids losing the events emitted before the frontend calls
+	/// `listen`. Each event name and target gets its own buffer of
+	/// [`EventReplayConfig::buffer`] events, dropping the oldest event when it
+	/// is full. Events emitted with a filter are never buffered.
+	///
+	/// # Examples
+	/// ```no_run
+	/// tauri::Builder::default().event_replay(tauri::EventReplayConfig {
+	///   events: vec!["backend-ready".into()],
+	///   buffer: 1,
+	/// });
+	/// ```
+	#[must_use]
+	pub fn event_replay(mut self, config:EventReplayConfig) -> Self {
+		self.event_replay.replace(config);
+
+		self
+	}
+
+	/// Registers an asynchronous hook run before the app exits, to flush
+	/// caches or close connections for instance.
+	///
+	/// The hooks run concurrently when the app exits, either from
+	/// [`AppHandle::exit`], [`AppHandle::restart`] or when its last window
+	/// closes, unless [`ExitRequestApi::prevent_exit`] is called. The app
+	/// waits for them up to the [shutdown timeout](Self::shutdown_timeout)
+	/// and then exits regardless, logging the hooks that didn't finish.
+	///
+	/// The exit is prevented until the hooks finish, so the event loop keeps
+	/// running and the hooks can use the windows. [`AppHandle::restart`] and
+	/// [`App::cleanup_before_exit`] block the calling thread instead, so the
+	/// hooks must not wait for work done on the main thread when those are
+	/// called from it.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// use tauri::Manager;
+	///
+	/// struct Database;
+	///
+	/// impl Database {
+	///   async fn close(&self) {}
+	/// }
+	///
+	/// tauri::Builder::default()
+	///   .manage(Database)
+	///   .on_shutdown(|app| async move {
+	///     app.state::<Database>().close().await;
+	///   });
+	/// ```
+	#[must_use]
+	#[track_caller]
+	pub fn on_shutdown<F, Fut>(mut self, hook:F) -> Self
+	where
+		F: Fn(AppHandle<R>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = ()> + Send + 'static, {
+		self.shutdown_hooks.push((
+			std::panic::Location::caller(),
+			Box::new(move |app| Box::pin(hook(app))),
+		));
+
+		self
+	}
+
+	/// Sets how long the app waits for the [shutdown hooks](Self::on_shutdown)
+	/// before exiting. Defaults to 5 seconds.
+	///
+	/// This is also how long [`AppHandle::restart`] and
+	/// [`App::cleanup_before_exit`] may block the calling thread.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// tauri::Builder::default().shutdown_timeout(std::time::Duration::from_secs(2));
+	/// ```
+	#[must_use]
+	pub fn shutdown_timeout(mut self, timeout:Duration) -> Self {
+		self.shutdown_timeout = timeout;
+
+		self
+	}
+
+	/// Only lets one instance of the app run: the later launches call `callback`
+	/// on the first instance with their arguments and working directory, then
+	/// exit before creating their runtime, so no window shows up.
+	///
+	/// The instances are identified by the bundle identifier of the app. The
+	/// development builds use their own lock, so they run alongside the
+	/// installed app. The lock is released when the app exits, and before the
+	/// replacement process is spawned when it restarts, so the replacement
+	/// becomes the first instance.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows**: uses a named pipe per user and session, which only the
+	///   current user can access.
+	/// - **Linux / macOS**: uses a Unix domain socket only the current user can
+	///   access, in `$XDG_RUNTIME_DIR` on Linux or a private directory of the
+	///   temporary directory, named after its hash if the identifier is too
+	///   long. A lock file next to it tells which instance is the first one.
+	///
+	/// # Examples
+	/// ```rust,no_run
+	/// use tauri::Manager;
+	///
+	/// tauri::Builder::default()
+	///   .single_instance(|app, args, cwd| {
+	///     println!("launched again from {} with {args:?}", cwd.display());
+	///
+	///     if let Some(window) = app.get_webview_window("main") {
+	///       let _ = window.set_focus();
+	///     }
+	///   });
+	/// ```
+	#[cfg(single_instance)]
+	#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "single-instance"))))]
+	#[must_use]
+	pub fn single_instance<F>(mut self, callback:F) -> Self
+	where
+		F: Fn(AppHandle<R>, Vec<String>, std::path::PathBuf) + Send + Sync + 'static, {
+		self.single_instance = Some(Box::new(callback));
+
+		self
+	}
+
 	/// Builds the application.
 	#[allow(clippy::type_complexity, unused_mut)]
 	#[cfg_attr(feature = "tracing", tracing::instrument(name = "app::build", skip_all))]
 	pub fn build(mut self, context:Context<R>) -> crate::Result<App<R>> {
+		if let Some(e) = self.invoke_handler_error.take() {
+			return Err(e);
+		}
+
+		// the later instances exit here, before anything is created
+		#[cfg(single_instance)]
+		let single_instance = match self.single_instance.take() {
+			Some(callback) => {
+				Some((single_instance::acquire(&context.config().identifier)?, callback))
+			},
+			None => None,
+		};
+
 		#[cfg(target_os = "macos")]
 		if self.menu.is_none() && self.enable_macos_default_menu {
 			self.menu = Some(Box::new(|app_handle| crate::menu::Menu::default(app_handle)));
@@ -2066,6 +2710,23 @@ tauri::Builder::default()
 			ran_setup:false,
 		};
 
+		if let Some(app_id) = self.linux_app_id {
+			let _ = app.manager.window.linux_app_id.set(app_id);
+		}
+
+		let _ = app.manager.window.state_dropped_hooks.set(self.window_state_dropped_hooks);
+
+		if let Some(config) = self.event_replay {
+			app.manager.listeners().set_replay(config);
+		}
+
+		#[cfg(linux_dbus_menu)]
+		if self.menu_export_dbus {
+			if let Some(exporter) = crate::menu::dbus::DbusMenuExporter::new() {
+				let _ = app.manager.menu.dbus_exporter.set(exporter);
+			}
+		}
+
 		#[cfg(desktop)]
 		if let Some(menu) = self.menu {
 			let menu = menu(&app.handle)?;
@@ -2093,8 +2754,32 @@ tauri::Builder::default()
 
 		app.manage(ChannelDataIpcQueue::default());
 
+		app.manage(crate::ipc::stream::StreamRegistry::default());
+
+		app.manage(crate::ipc::cancel::CancellationRegistry::default());
+
+		app.manage(crate::ipc::body_stream::RequestStreamRegistry::default());
+
+		app.manage(crate::ipc::response_stream::ResponseStreamRegistry::default());
+
+		app.manage(crate::ipc::channel::ChannelAckRegistry::default());
+
+		app.manage(crate::ipc::DefaultCommandExecution(self.default_command_execution));
+
+		app.manage(shutdown::ShutdownHooks::new(
+			std::mem::take(&mut self.shutdown_hooks),
+			self.shutdown_timeout,
+		));
+
 		app.handle.plugin(crate::ipc::channel::plugin())?;
 
+		app.handle.plugin(crate::ipc::body_stream::plugin())?;
+
+		#[cfg(single_instance)]
+		if let Some((lock, callback)) = single_instance {
+			single_instance::listen(lock, app.handle.clone(), callback);
+		}
+
 		#[cfg(windows)]
 		{
 			if let crate::utils::config::WebviewInstallMode::FixedRuntime { path } =
@@ -2148,6 +2833,10 @@ tauri::Builder::default()
 
 		app.manager.initialize_plugins(handle)?;
 
+		for transport in self.invoke_transports {
+			transport.start(InvokeDispatcher::new(handle.clone(), transport.origin()));
+		}
+
 		Ok(app)
 	}
 
@@ -2239,6 +2928,41 @@ fn setup<R:Runtime>(app:&mut App<R>) -> crate::Result<()> {
 	Ok(())
 }
 
+/// Dispatches [`RunEvent::ExitRequested`], then prevents the exit until the
+/// [shutdown hooks](Builder::on_shutdown) finish unless it was prevented by the
+/// app, so the event loop keeps running while the hooks use the windows.
+fn on_exit_requested<R:Runtime, F:FnMut(&AppHandle<R>, RunEvent)>(
+	app_handle:&AppHandle<R>,
+	code:Option<i32>,
+	tx:Sender<ExitRequestedEventAction>,
+	manager:&AppManager<R>,
+	callback:&mut F,
+) {
+	let hooks = app_handle.try_state::<shutdown::ShutdownHooks<R>>();
+
+	// the exit requested again once the hooks finished was already dispatched
+	if hooks.as_ref().is_some_and(|hooks| hooks.take_exit_request()) {
+		return;
+	}
+
+	// saved before the windows are destroyed, even if the exit is prevented
+	#[cfg(desktop)]
+	crate::window::state::save_restored(app_handle);
+
+	let (api_tx, api_rx) = channel();
+
+	let event =
+		on_event_loop_event(app_handle, RuntimeRunEvent::ExitRequested { code, tx:api_tx }, manager);
+
+	callback(app_handle, event);
+
+	let prevented = matches!(api_rx.try_recv(), Ok(ExitRequestedEventAction::Prevent));
+
+	if prevented || hooks.is_some_and(|hooks| hooks.defer_exit(app_handle, code)) {
+		let _ = tx.send(ExitRequestedEventAction::Prevent);
+	}
+}
+
 fn on_event_loop_event<R:Runtime>(
 	app_handle:&AppHandle<R>,
 	event:RuntimeRunEvent<EventLoopMessage>,
@@ -2286,9 +3010,21 @@ fn on_event_loop_event<R:Runtime>(
 			match t {
 				#[cfg(desktop)]
 				EventLoopMessage::MenuEvent(ref e) => {
-					for listener in &*app_handle.manager.menu.global_event_listeners.lock().unwrap()
-					{
-						listener(app_handle, e.clone());
+					for group in app_handle.manager.menu.check_groups(e.id()) {
+						if let Err(err) = group.select(e.id()) {
+							log::error!("failed to select menu item `{}`: {err}", e.id().0);
+						}
+					}
+					let handlers = app_handle.manager.menu.item_click_handlers(e.id());
+					for handler in &handlers {
+						handler(app_handle);
+					}
+					if handlers.is_empty() {
+						for listener in
+							&*app_handle.manager.menu.global_event_listeners.lock().unwrap()
+						{
+							listener(app_handle, e.clone());
+						}
 					}
 					for (label, listener) in
 						&*app_handle.manager.menu.event_listeners.lock().unwrap()
@@ -2313,6 +3049,20 @@ fn on_event_loop_event<R:Runtime>(
 							}
 						}
 					}
+
+					let id = e.id();
+					let factory =
+						app_handle.manager.tray.menu_factories.lock().unwrap().get(id).cloned();
+					if let (Some(factory), Some(tray)) = (factory, app_handle.tray_by_id(id)) {
+						if e.opens_menu(tray.shows_menu_on_left_click()) {
+							if let Err(e) = tray.rebuild_menu(|builder| factory(builder)) {
+								log::error!(
+									"failed to rebuild the menu of tray icon `{}`: {e}",
+									id.as_ref()
+								);
+							}
+						}
+					}
 				},
 			}
 
@@ -2323,6 +3073,18 @@ fn on_event_loop_event<R:Runtime>(
 		RuntimeRunEvent::Opened { urls } => RunEvent::Opened { urls },
 		#[cfg(target_os = "macos")]
 		RuntimeRunEvent::Reopen { has_visible_windows } => RunEvent::Reopen { has_visible_windows },
+		RuntimeRunEvent::ThemeChanged(theme) => {
+			#[cfg(all(desktop, feature = "tray-icon"))]
+			{
+				let icons = app_handle.manager.tray.icons.lock().unwrap().clone();
+
+				for icon in icons {
+					icon.on_system_theme_changed(theme);
+				}
+			}
+
+			RunEvent::ThemeChanged(theme)
+		},
 		_ => unimplemented!(),
 	};
 
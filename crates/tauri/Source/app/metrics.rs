@@ -0,0 +1,220 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The resource usage of the app, read with
+//! [`AppHandle::metrics`](crate::AppHandle::metrics).
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{AppHandle, Manager, Runtime, ipc::cancel::CancellationRegistry, sealed::ManagerBase};
+
+/// A snapshot of the resources used by the app.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct AppMetrics {
+	/// The number of open windows.
+	pub windows:usize,
+	/// The open webviews.
+	pub webviews:Vec<WebviewMetrics>,
+	/// The number of Rust and JavaScript listeners of each event.
+	///
+	/// The Rust listeners are not counted when the metrics are read from one of
+	/// them.
+	pub event_listeners:HashMap<String, usize>,
+	/// The number of invokes the commands did not resolve yet, including the
+	/// invoke reading the metrics if any.
+	pub pending_invokes:usize,
+	/// The memory used by all the processes rendering the webviews, in bytes,
+	/// `None` if the platform does not expose it.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux**: the proportional set size of the WebKitGTK web processes
+	///   spawned by the app. Unlike [`WebviewMetrics::memory`], which WebKitGTK
+	///   can't attribute to each webview.
+	/// - **Windows / macOS / iOS / Android**: Unsupported, see
+	///   [`WebviewMetrics::memory`].
+	pub web_processes_memory:Option<u64>,
+}
+
+/// The resources used by a webview.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct WebviewMetrics {
+	/// The webview label.
+	pub label:String,
+	/// The label of the window the webview is in.
+	pub window:String,
+	/// The memory used by the process rendering the webview, in bytes, `None`
+	/// if the platform does not expose it: on Linux, and on macOS without the
+	/// `macos-private-api` feature.
+	///
+	/// See [`Webview::memory_usage`](crate::webview::Webview::memory_usage).
+	pub memory:Option<u64>,
+	/// The number of invokes made by the webview that the commands did not
+	/// resolve yet.
+	pub pending_invokes:usize,
+}
+
+pub(crate) fn collect<R:Runtime>(app:&AppHandle<R>) -> crate::Result<AppMetrics> {
+	let pending = app
+		.try_state::<CancellationRegistry>()
+		.map(|registry| registry.pending())
+		.unwrap_or_default();
+
+	let mut webviews = app
+		.manager()
+		.webviews()
+		.into_values()
+		.map(|webview| WebviewMetrics {
+			pending_invokes:pending.get(webview.label()).copied().unwrap_or_default(),
+			// the webview may be closing
+			memory:webview.memory_usage().ok().flatten(),
+			window:webview.window().label().to_string(),
+			label:webview.label().to_string(),
+		})
+		.collect::<Vec<_>>();
+
+	webviews.sort_by(|a, b| a.label.cmp(&b.label));
+
+	Ok(AppMetrics {
+		windows:app.manager().windows().len(),
+		webviews,
+		event_listeners:app.manager().listeners().listener_counts(),
+		pending_invokes:pending.values().sum(),
+		web_processes_memory:web_processes_memory(),
+	})
+}
+
+/// The memory used by the WebKitGTK web processes the app spawned, in bytes.
+#[cfg(target_os = "linux")]
+fn web_processes_memory() -> Option<u64> {
+	// the web processes may be sandboxed, and spawned by the sandbox then
+	let mut processes = child_processes(std::process::id())?;
+
+	let mut memory = None;
+
+	while let Some(process) = processes.pop() {
+		processes.extend(child_processes(process).unwrap_or_default());
+
+		// the command name is truncated to 15 bytes
+		let name = std::fs::read_to_string(format!("/proc/{process}/comm")).unwrap_or_default();
+
+		if name.trim_end() == "WebKitWebProces" {
+			if let Some(size) = proportional_set_size(process) {
+				*memory.get_or_insert(0) += size;
+			}
+		}
+	}
+
+	memory
+}
+
+#[cfg(not(target_os = "linux"))]
+fn web_processes_memory() -> Option<u64> { None }
+
+/// The processes spawned by any thread of the given process.
+#[cfg(target_os = "linux")]
+fn child_processes(process:u32) -> Option<Vec<u32>> {
+	let mut children = Vec::new();
+
+	for task in std::fs::read_dir(format!("/proc/{process}/task")).ok()?.flatten() {
+		let pids = std::fs::read_to_string(task.path().join("children")).ok()?;
+
+		children.extend(pids.split_whitespace().filter_map(|pid| pid.parse::<u32>().ok()));
+	}
+
+	Some(children)
+}
+
+/// The proportional set size of the given process in bytes, which splits the
+/// memory shared between the web processes instead of counting it for each.
+#[cfg(target_os = "linux")]
+fn proportional_set_size(process:u32) -> Option<u64> {
+	let rollup = std::fs::read_to_string(format!("/proc/{process}/smaps_rollup")).ok()?;
+
+	let size = rollup.lines().find_map(|line| line.strip_prefix("Pss:"))?;
+
+	size.trim().strip_suffix("kB")?.trim().parse::<u64>().ok().map(|size| size * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::mpsc::channel, time::Duration};
+
+	use crate::{
+		Listener,
+		Manager,
+		WebviewWindowBuilder,
+		ipc::{CallbackFn, CancellationToken, InvokeBody, cancel::CancellationRegistry},
+		test::{INVOKE_KEY, mock_builder, mock_context, noop_assets},
+		webview::InvokeRequest,
+	};
+
+	#[crate::command(root = "crate")]
+	async fn index(cancel:CancellationToken) { cancel.cancelled().await }
+
+	#[test]
+	fn metrics_count_the_resources_of_the_app() {
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![index])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let main = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		WebviewWindowBuilder::new(&app, "other", Default::default()).build().unwrap();
+
+		app.listen_any("progress", |_| {});
+
+		app.listen_any("progress", |_| {});
+
+		let (tx, rx) = channel();
+
+		main.as_ref().clone().on_message(
+			InvokeRequest {
+				cmd:"index".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::default(),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+			Box::new(move |_webview, _cmd, _response, _callback, _error| tx.send(()).unwrap()),
+		);
+
+		let metrics = app.handle().metrics().unwrap();
+
+		assert_eq!(metrics.windows, 2);
+
+		assert_eq!(
+			metrics
+				.webviews
+				.iter()
+				.map(|w| (w.label.as_str(), w.window.as_str(), w.pending_invokes))
+				.collect::<Vec<_>>(),
+			[("main", "main", 1), ("other", "other", 0)]
+		);
+
+		// the mock runtime doesn't render the webviews
+		assert!(metrics.webviews.iter().all(|webview| webview.memory.is_none()));
+
+		assert_eq!(metrics.web_processes_memory, None);
+
+		assert_eq!(metrics.event_listeners["progress"], 2);
+
+		assert_eq!(metrics.pending_invokes, 1);
+
+		app.state::<CancellationRegistry>().cancel_webview("main");
+
+		rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+		assert_eq!(app.handle().metrics().unwrap().pending_invokes, 0);
+	}
+}
@@ -53,6 +53,16 @@ pub async fn set_app_theme<R:Runtime>(app:AppHandle<R>, theme:Option<Theme>) {
 	app.set_theme(theme);
 }
 
+#[command(root = "crate")]
+pub fn set_app_badge_count<R:Runtime>(app:AppHandle<R>, count:Option<u64>) -> crate::Result<()> {
+	app.set_badge_count(count)
+}
+
+#[command(root = "crate")]
+pub fn set_app_badge_label<R:Runtime>(app:AppHandle<R>, label:Option<String>) -> crate::Result<()> {
+	app.set_badge_label(label.as_deref())
+}
+
 pub fn init<R:Runtime>() -> TauriPlugin<R> {
 	Builder::new("app")
 		.invoke_handler(crate::generate_handler![
@@ -63,6 +73,8 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 			app_hide,
 			default_window_icon,
 			set_app_theme,
+			set_app_badge_count,
+			set_app_badge_label,
 		])
 		.build()
 }
@@ -0,0 +1,322 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The asynchronous hooks run before the app exits.
+
+use std::{
+	panic::Location,
+	sync::{
+		Mutex,
+		atomic::{AtomicBool, Ordering},
+		mpsc::channel,
+	},
+	time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+
+use crate::{AppHandle, Manager, Runtime};
+
+/// How long the app waits for its shutdown hooks by default.
+pub(crate) const DEFAULT_SHUTDOWN_TIMEOUT:Duration = Duration::from_secs(5);
+
+/// A hook registered with [`Builder::on_shutdown`](crate::Builder::on_shutdown).
+pub(crate) type ShutdownHook<R> = Box<dyn Fn(AppHandle<R>) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// A callback called once the shutdown hooks finish.
+type OnFinished = Box<dyn FnOnce() + Send>;
+
+enum Phase<R:Runtime> {
+	/// The hooks didn't run yet, with where they were registered to name them in
+	/// the logs.
+	Pending(Vec<(&'static Location<'static>, ShutdownHook<R>)>),
+	/// The hooks are running, with the callbacks to call once they finish.
+	Running(Vec<OnFinished>),
+	Finished,
+}
+
+/// The shutdown hooks of the app.
+pub(crate) struct ShutdownHooks<R:Runtime> {
+	phase:Mutex<Phase<R>>,
+	timeout:Duration,
+	/// Whether the exit deferred by [`Self::defer_exit`] was requested again.
+	exit_requested:AtomicBool,
+}
+
+impl<R:Runtime> ShutdownHooks<R> {
+	pub(crate) fn new(
+		hooks:Vec<(&'static Location<'static>, ShutdownHook<R>)>,
+		timeout:Duration,
+	) -> Self {
+		Self {
+			phase:Mutex::new(Phase::Pending(hooks)),
+			timeout,
+			exit_requested:AtomicBool::new(false),
+		}
+	}
+
+	/// Spawns the hooks on the [`async_runtime`](crate::async_runtime) if they
+	/// didn't run yet, calling `on_finished` once they all finish or the timeout
+	/// elapses.
+	///
+	/// Returns whether the hooks are running, `on_finished` isn't called
+	/// otherwise.
+	pub(crate) fn spawn(&self, app:&AppHandle<R>, on_finished:impl FnOnce() + Send + 'static) -> bool {
+		let mut phase = self.phase.lock().unwrap();
+
+		let hooks = match &mut *phase {
+			Phase::Pending(hooks) if !hooks.is_empty() => std::mem::take(hooks),
+			Phase::Pending(_) | Phase::Finished => {
+				*phase = Phase::Finished;
+
+				return false;
+			},
+			Phase::Running(callbacks) => {
+				callbacks.push(Box::new(on_finished));
+
+				return true;
+			},
+		};
+
+		*phase = Phase::Running(vec![Box::new(on_finished)]);
+
+		drop(phase);
+
+		let tasks = hooks
+			.iter()
+			.map(|(location, hook)| (*location, crate::async_runtime::spawn(hook(app.clone()))))
+			.collect::<Vec<_>>();
+
+		let timeout = self.timeout;
+
+		let app = app.clone();
+
+		crate::async_runtime::spawn(async move {
+			let deadline = tokio::time::Instant::now() + timeout;
+
+			for (location, mut task) in tasks {
+				match tokio::time::timeout_at(deadline, &mut task).await {
+					Ok(Ok(())) => {},
+					Ok(Err(e)) => log::error!("the shutdown hook registered at {location} failed: {e}"),
+					Err(_) => {
+						log::warn!(
+							"the shutdown hook registered at {location} did not finish within \
+							 {timeout:?}, exiting anyway"
+						);
+
+						task.abort();
+					},
+				}
+			}
+
+			if let Some(hooks) = app.try_state::<Self>() {
+				hooks.finish();
+			}
+		});
+
+		true
+	}
+
+	fn finish(&self) {
+		let callbacks = match std::mem::replace(&mut *self.phase.lock().unwrap(), Phase::Finished) {
+			Phase::Running(callbacks) => callbacks,
+			_ => Vec::new(),
+		};
+
+		for on_finished in callbacks {
+			on_finished();
+		}
+	}
+
+	/// Runs the hooks before the exit requested with `code`, without blocking
+	/// the event loop so the hooks can use the windows.
+	///
+	/// Returns whether the exit must be prevented, in which case it is requested
+	/// again once the hooks finish.
+	pub(crate) fn defer_exit(&self, app:&AppHandle<R>, code:Option<i32>) -> bool {
+		let app_ = app.clone();
+
+		self.spawn(app, move || {
+			if let Some(hooks) = app_.try_state::<Self>() {
+				hooks.exit_requested.store(true, Ordering::SeqCst);
+			}
+
+			app_.exit(code.unwrap_or(0));
+		})
+	}
+
+	/// Whether the exit was requested again after being deferred by
+	/// [`Self::defer_exit`], it must go through without being dispatched twice.
+	pub(crate) fn take_exit_request(&self) -> bool {
+		self.exit_requested.swap(false, Ordering::SeqCst)
+	}
+
+	/// Runs the hooks concurrently, blocking until they all finish or the
+	/// timeout elapses.
+	///
+	/// The hooks only run once, the next calls return immediately. Waiting
+	/// doesn't go through the async runtime, which may be a current thread
+	/// runtime driven by the calling thread, but the hooks can't make progress
+	/// then, nor can they use the windows when called on the main thread.
+	pub(crate) fn run(&self, app:&AppHandle<R>) {
+		let (tx, rx) = channel();
+
+		if self.spawn(app, move || {
+			let _ = tx.send(());
+		}) {
+			let _ = rx.recv_timeout(self.timeout);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::{
+			Arc,
+			Mutex,
+			atomic::{AtomicUsize, Ordering},
+			mpsc::channel,
+		},
+		time::{Duration, Instant},
+	};
+
+	use super::ShutdownHooks;
+	use crate::{
+		Manager,
+		RunEvent,
+		WebviewWindowBuilder,
+		test::{MockRuntime, mock_builder, mock_context, noop_assets},
+	};
+
+	#[test]
+	fn hooks_run_once_until_the_timeout() {
+		let finished = Arc::new(AtomicUsize::new(0));
+
+		let finished_ = finished.clone();
+
+		let app = mock_builder()
+			.on_shutdown(move |_| {
+				let finished = finished_.clone();
+
+				async move {
+					finished.fetch_add(1, Ordering::SeqCst);
+				}
+			})
+			.on_shutdown(|_| std::future::pending())
+			.shutdown_timeout(Duration::from_millis(50))
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let started = Instant::now();
+
+		app.cleanup_before_exit();
+
+		// the pending hook doesn't keep the app from exiting
+		assert!(started.elapsed() < Duration::from_secs(5));
+
+		assert_eq!(finished.load(Ordering::SeqCst), 1);
+
+		app.handle().run_shutdown_hooks();
+
+		assert_eq!(finished.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn hooks_use_the_windows_without_blocking_the_exit() {
+		let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+		let release = Mutex::new(Some(release_rx));
+
+		let (titled_tx, titled_rx) = channel();
+
+		let app = mock_builder()
+			.on_shutdown(move |app| {
+				let release = release.lock().unwrap().take();
+
+				let titled_tx = titled_tx.clone();
+
+				async move {
+					if let Some(release) = release {
+						let _ = release.await;
+					}
+
+					let window = app.get_webview_window("main").unwrap();
+
+					titled_tx.send(window.set_title("exiting").is_ok()).unwrap();
+				}
+			})
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let (finished_tx, finished_rx) = channel();
+
+		let hooks = app.state::<ShutdownHooks<MockRuntime>>();
+
+		// returns while the hook waits, like the event loop handling the exit request
+		assert!(hooks.spawn(app.handle(), move || finished_tx.send(()).unwrap()));
+
+		release_tx.send(()).unwrap();
+
+		assert!(titled_rx.recv_timeout(Duration::from_secs(5)).unwrap());
+
+		finished_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+		// the exit goes through once the hooks finished
+		assert!(!hooks.defer_exit(app.handle(), Some(0)));
+	}
+
+	#[test]
+	fn exit_is_deferred_while_the_event_loop_runs_the_hooks() {
+		let events = Arc::new(Mutex::new(Vec::new()));
+
+		let events_ = events.clone();
+
+		let app = mock_builder()
+			.on_shutdown(move |app| {
+				let events = events_.clone();
+
+				async move {
+					tokio::time::sleep(Duration::from_millis(100)).await;
+
+					app.get_webview_window("main").unwrap().set_title("exiting").unwrap();
+
+					events.lock().unwrap().push("hooks finished".to_string());
+				}
+			})
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let events_ = events.clone();
+
+		app.run(move |app, event| {
+			let mut events = events_.lock().unwrap();
+
+			match event {
+				RunEvent::Ready => app.exit(3),
+				RunEvent::ExitRequested { code, .. } => {
+					events.push(format!("exit requested {code:?}"))
+				},
+				// the event loop keeps running while the hooks wait
+				RunEvent::MainEventsCleared
+					if events.last().is_some_and(|event| event.starts_with("exit requested")) =>
+				{
+					events.push("event loop running".to_string())
+				},
+				RunEvent::Exit => events.push("exit".to_string()),
+				_ => {},
+			}
+		});
+
+		// the exit requested again once the hooks finished isn't dispatched twice
+		assert_eq!(
+			*events.lock().unwrap(),
+			["exit requested Some(3)", "event loop running", "hooks finished", "exit"]
+		);
+	}
+}
@@ -0,0 +1,686 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Single instance support, see
+//! [`Builder::single_instance`](crate::Builder::single_instance).
+//!
+//! The first instance of the app owns a local socket named after the bundle
+//! identifier, the later ones send it their arguments and working directory
+//! and exit.
+//!
+//! ## Platform-specific
+//!
+//! - **Windows**: uses a named pipe, whose first instance is the lock. The
+//!   pipe is named after the user and the session, only the current user can
+//!   access it, and the later instances check that it is owned by that user.
+//! - **Linux / macOS**: uses a Unix domain socket only the current user can
+//!   access, in `$XDG_RUNTIME_DIR` on Linux or a `tauri-{uid}` directory of
+//!   the temporary directory created with the `0700` mode. The lock is an
+//!   advisory lock on a file next to the socket, which the system releases
+//!   when the instance exits, so only its holder replaces the socket left
+//!   behind by a crashed instance.
+
+use std::{
+	path::PathBuf,
+	sync::{Mutex, mpsc::Receiver},
+	time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{AppHandle, Manager, Runtime};
+
+/// The callback registered with
+/// [`Builder::single_instance`](crate::Builder::single_instance).
+pub(crate) type OnSecondInstance<R> = Box<dyn Fn(AppHandle<R>, Vec<String>, PathBuf) + Send + Sync>;
+
+/// How long the first instance waits for a later one to send its launch.
+const RECEIVE_TIMEOUT:Duration = Duration::from_secs(1);
+
+/// How many times a later instance tries to reach the first one, which may be
+/// between two connections or not listen yet.
+const CONNECT_ATTEMPTS:usize = 20;
+
+/// How long a later instance waits between its attempts to reach the first one.
+const CONNECT_DELAY:Duration = Duration::from_millis(50);
+
+/// The launch of a later instance.
+#[derive(Serialize, Deserialize)]
+struct Launch {
+	args:Vec<String>,
+	cwd:PathBuf,
+}
+
+impl Launch {
+	fn current() -> Self {
+		Self {
+			args:std::env::args_os().map(|arg| arg.to_string_lossy().into_owned()).collect(),
+			cwd:std::env::current_dir().unwrap_or_default(),
+		}
+	}
+}
+
+/// The socket owned by the first instance.
+pub(crate) struct Lock {
+	#[cfg(unix)]
+	path:PathBuf,
+	/// The file whose advisory lock makes this instance the first one.
+	#[cfg(unix)]
+	file:std::fs::File,
+	#[cfg(unix)]
+	listener:std::os::unix::net::UnixListener,
+	#[cfg(windows)]
+	pipe:windows_pipe::Pipe,
+	#[cfg(windows)]
+	server:tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+/// The connection of a later instance to the first one.
+#[cfg(unix)]
+type Connection = std::os::unix::net::UnixStream;
+
+/// The connection of a later instance to the first one.
+#[cfg(windows)]
+type Connection = std::fs::File;
+
+/// The lock of the first instance, released when the app exits or restarts.
+pub(crate) struct SingleInstance {
+	#[cfg(unix)]
+	path:PathBuf,
+	#[cfg(unix)]
+	file:Mutex<Option<std::fs::File>>,
+	/// The task listening to the later instances, with a receiver disconnected
+	/// once the task is dropped.
+	listener:Mutex<Option<(crate::async_runtime::JoinHandle<()>, Receiver<()>)>>,
+}
+
+impl SingleInstance {
+	/// Stops listening to the later instances and releases the lock, so a new
+	/// instance, the one replacing the app when it restarts for instance,
+	/// becomes the first one.
+	///
+	/// The next calls do nothing, they would remove the socket of the new first
+	/// instance otherwise.
+	pub(crate) fn release(&self) {
+		if let Some((task, stopped)) = self.listener.lock().unwrap().take() {
+			task.abort();
+
+			// the pipe is only closed once the task is dropped
+			let _ = stopped.recv_timeout(RECEIVE_TIMEOUT);
+		}
+
+		// the socket is removed before the lock is, the new first instance binds
+		// its own
+		#[cfg(unix)]
+		if let Some(file) = self.file.lock().unwrap().take() {
+			let _ = std::fs::remove_file(&self.path);
+
+			drop(file);
+		}
+	}
+}
+
+/// The socket name, different for the development builds so they don't
+/// forward their launches to the installed app, and the other way around.
+fn socket_name(identifier:&str) -> String {
+	let identifier = identifier
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+		.collect::<String>();
+
+	if cfg!(dev) { format!("{identifier}-dev-si") } else { format!("{identifier}-si") }
+}
+
+/// Acquires the lock of the app with the given identifier, or sends the launch
+/// to the instance holding it and exits.
+pub(crate) fn acquire(identifier:&str) -> crate::Result<Lock> {
+	use std::io::Write;
+
+	match try_acquire(identifier)? {
+		Ok(lock) => Ok(lock),
+		Err(mut connection) => {
+			connection.write_all(&serde_json::to_vec(&Launch::current())?)?;
+
+			drop(connection);
+
+			std::process::exit(0);
+		},
+	}
+}
+
+/// The longest socket path `bind` accepts, `sun_path` has 104 bytes on macOS
+/// and 108 on Linux, including the nul terminator.
+#[cfg(unix)]
+const MAX_SOCKET_PATH:usize = 103;
+
+/// The path of the socket, named after its hash if the name is too long.
+#[cfg(unix)]
+fn socket_path(directory:&std::path::Path, name:&str) -> PathBuf {
+	let path = directory.join(format!("{name}.sock"));
+
+	if path.as_os_str().len() <= MAX_SOCKET_PATH {
+		return path;
+	}
+
+	// FNV-1a, the name must be the same for every build of the app
+	let hash = name
+		.bytes()
+		.fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+
+	directory.join(format!("{hash:016x}-si.sock"))
+}
+
+/// Acquires the lock of the app with the given identifier, or connects to the
+/// instance holding it.
+#[cfg(unix)]
+fn try_acquire(identifier:&str) -> crate::Result<Result<Lock, Connection>> {
+	use std::{
+		fs::{OpenOptions, Permissions},
+		io::{Error, ErrorKind},
+		os::unix::{
+			fs::{OpenOptionsExt, PermissionsExt},
+			io::AsRawFd,
+			net::{UnixListener, UnixStream},
+		},
+	};
+
+	let path = socket_path(&socket_directory()?, &socket_name(identifier));
+
+	let file =
+		OpenOptions::new().create(true).write(true).mode(0o600).open(path.with_extension("lock"))?;
+
+	// SAFETY: the file descriptor is valid while the file is open
+	if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+		let e = Error::last_os_error();
+
+		if e.kind() != ErrorKind::WouldBlock {
+			return Err(e.into());
+		}
+
+		for _ in 0..CONNECT_ATTEMPTS {
+			match UnixStream::connect(&path) {
+				Ok(stream) => return Ok(Err(stream)),
+				Err(_) => std::thread::sleep(CONNECT_DELAY),
+			}
+		}
+
+		return Err(crate::Error::SingleInstanceBusy);
+	}
+
+	// only the lock holder gets here, the socket may be left behind by a crashed
+	// instance
+	match std::fs::remove_file(&path) {
+		Ok(()) => {},
+		Err(e) if e.kind() == ErrorKind::NotFound => {},
+		Err(e) => return Err(e.into()),
+	}
+
+	let listener = UnixListener::bind(&path)?;
+
+	std::fs::set_permissions(&path, Permissions::from_mode(0o600))?;
+
+	listener.set_nonblocking(true)?;
+
+	Ok(Ok(Lock { path, file, listener }))
+}
+
+/// The directory of the socket, which only the current user can access so
+/// other users can neither reach the app nor take its socket name.
+#[cfg(unix)]
+fn socket_directory() -> crate::Result<PathBuf> {
+	use std::{
+		io::{Error, ErrorKind},
+		os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt},
+	};
+
+	// the runtime directory is private to the user
+	if cfg!(target_os = "linux") {
+		if let Some(directory) = std::env::var_os("XDG_RUNTIME_DIR") {
+			return Ok(PathBuf::from(directory));
+		}
+	}
+
+	// SAFETY: `getuid` always succeeds
+	let uid = unsafe { libc::getuid() };
+
+	let directory = std::env::temp_dir().join(format!("tauri-{uid}"));
+
+	match std::fs::DirBuilder::new().mode(0o700).create(&directory) {
+		Ok(()) => {},
+		Err(e) if e.kind() == ErrorKind::AlreadyExists => {},
+		Err(e) => return Err(e.into()),
+	}
+
+	// another user may have created it first
+	let metadata = std::fs::symlink_metadata(&directory)?;
+
+	if !metadata.is_dir() || metadata.uid() != uid || metadata.permissions().mode() & 0o077 != 0 {
+		return Err(Error::new(
+			ErrorKind::PermissionDenied,
+			format!("`{}` is not a directory private to the current user", directory.display()),
+		)
+		.into());
+	}
+
+	Ok(directory)
+}
+
+/// Acquires the lock of the app with the given identifier, or connects to the
+/// instance holding it.
+#[cfg(windows)]
+fn try_acquire(identifier:&str) -> crate::Result<Result<Lock, Connection>> {
+	use windows::Win32::Foundation::{ERROR_ACCESS_DENIED, ERROR_PIPE_BUSY};
+
+	let pipe = windows_pipe::Pipe::new(identifier).map_err(std::io::Error::from)?;
+
+	// the pipe is registered with the async runtime
+	let handle = crate::async_runtime::handle();
+
+	let _guard = handle.inner().enter();
+
+	match pipe.create(true) {
+		Ok(server) => Ok(Ok(Lock { pipe, server })),
+		Err(e) if e.raw_os_error() == Some(ERROR_ACCESS_DENIED.0 as i32) => {
+			for _ in 0..CONNECT_ATTEMPTS {
+				match std::fs::OpenOptions::new().write(true).open(&pipe.name) {
+					Ok(client) => {
+						pipe.check_owner(&client)?;
+
+						return Ok(Err(client));
+					},
+					Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY.0 as i32) => {
+						std::thread::sleep(CONNECT_DELAY);
+					},
+					Err(e) => return Err(e.into()),
+				}
+			}
+
+			Err(crate::Error::SingleInstanceBusy)
+		},
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// The named pipe of the app, private to the current user.
+#[cfg(windows)]
+mod windows_pipe {
+	use std::os::windows::io::AsRawHandle;
+
+	use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+	use windows::{
+		Win32::{
+			Foundation::{CloseHandle, HANDLE, HLOCAL, LocalFree},
+			Security::{
+				Authorization::{
+					ConvertSidToStringSidW,
+					ConvertStringSecurityDescriptorToSecurityDescriptorW,
+					GetSecurityInfo,
+					SDDL_REVISION_1,
+					SE_KERNEL_OBJECT,
+				},
+				EqualSid,
+				GetTokenInformation,
+				OWNER_SECURITY_INFORMATION,
+				PSECURITY_DESCRIPTOR,
+				PSID,
+				SECURITY_ATTRIBUTES,
+				TOKEN_QUERY,
+				TOKEN_USER,
+				TokenUser,
+			},
+			System::{
+				RemoteDesktop::ProcessIdToSessionId,
+				Threading::{GetCurrentProcess, GetCurrentProcessId, OpenProcessToken},
+			},
+		},
+		core::{HSTRING, PWSTR},
+	};
+
+	use super::socket_name;
+
+	pub(crate) struct Pipe {
+		/// The pipe name, with the user SID and the session id so other users
+		/// and sessions have their own instances.
+		pub(crate) name:String,
+		/// The SID of the current user.
+		sid:String,
+	}
+
+	impl Pipe {
+		pub(crate) fn new(identifier:&str) -> windows::core::Result<Self> {
+			let sid = current_user_sid()?;
+
+			let mut session = 0;
+
+			unsafe { ProcessIdToSessionId(GetCurrentProcessId(), &mut session)? };
+
+			Ok(Self { name:format!(r"\\.\pipe\{}-{sid}-{session}", socket_name(identifier)), sid })
+		}
+
+		/// Creates an instance of the pipe that only the current user can access.
+		pub(crate) fn create(&self, first:bool) -> std::io::Result<NamedPipeServer> {
+			// protected DACL granting all access to the current user only
+			let sddl = HSTRING::from(format!("D:P(A;;GA;;;{})", self.sid));
+
+			let mut descriptor = PSECURITY_DESCRIPTOR::default();
+
+			unsafe {
+				ConvertStringSecurityDescriptorToSecurityDescriptorW(
+					&sddl,
+					SDDL_REVISION_1,
+					&mut descriptor,
+					None,
+				)?;
+			}
+
+			let mut attributes = SECURITY_ATTRIBUTES {
+				nLength:std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+				lpSecurityDescriptor:descriptor.0,
+				bInheritHandle:false.into(),
+			};
+
+			let server = unsafe {
+				ServerOptions::new()
+					.first_pipe_instance(first)
+					.reject_remote_clients(true)
+					.create_with_security_attributes_raw(
+						&self.name,
+						&mut attributes as *mut SECURITY_ATTRIBUTES as *mut _,
+					)
+			};
+
+			unsafe {
+				let _ = LocalFree(HLOCAL(descriptor.0));
+			}
+
+			server
+		}
+
+		/// Fails if the pipe is not owned by the current user, who would be
+		/// sent the launch otherwise.
+		pub(crate) fn check_owner(&self, pipe:&std::fs::File) -> crate::Result<()> {
+			let user = current_user().map_err(std::io::Error::from)?;
+
+			let owned = unsafe {
+				let mut owner = PSID::default();
+
+				let mut descriptor = PSECURITY_DESCRIPTOR::default();
+
+				let result = GetSecurityInfo(
+					HANDLE(pipe.as_raw_handle() as _),
+					SE_KERNEL_OBJECT,
+					OWNER_SECURITY_INFORMATION,
+					Some(&mut owner),
+					None,
+					None,
+					None,
+					Some(&mut descriptor),
+				);
+
+				let owned = result.is_ok()
+					&& EqualSid(owner, (*user.as_ptr().cast::<TOKEN_USER>()).User.Sid).is_ok();
+
+				let _ = LocalFree(HLOCAL(descriptor.0));
+
+				owned
+			};
+
+			if owned {
+				Ok(())
+			} else {
+				Err(std::io::Error::new(
+					std::io::ErrorKind::PermissionDenied,
+					format!("the pipe `{}` is not owned by the current user", self.name),
+				)
+				.into())
+			}
+		}
+	}
+
+	/// The `TOKEN_USER` of the current process.
+	fn current_user() -> windows::core::Result<Vec<u64>> {
+		unsafe {
+			let mut token = HANDLE::default();
+
+			OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token)?;
+
+			let mut length = 0;
+
+			let _ = GetTokenInformation(token, TokenUser, None, 0, &mut length);
+
+			// u64 for the alignment of the SID pointer
+			let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+
+			let result = GetTokenInformation(
+				token,
+				TokenUser,
+				Some(buffer.as_mut_ptr().cast()),
+				length,
+				&mut length,
+			);
+
+			let _ = CloseHandle(token);
+
+			result.map(|()| buffer)
+		}
+	}
+
+	fn current_user_sid() -> windows::core::Result<String> {
+		let user = current_user()?;
+
+		unsafe {
+			let mut sid = PWSTR::null();
+
+			ConvertSidToStringSidW((*user.as_ptr().cast::<TOKEN_USER>()).User.Sid, &mut sid)?;
+
+			let string = String::from_utf16_lossy(sid.as_wide());
+
+			let _ = LocalFree(HLOCAL(sid.0.cast()));
+
+			Ok(string)
+		}
+	}
+}
+
+/// Calls `callback` with the launches of the later instances.
+pub(crate) fn listen<R:Runtime>(lock:Lock, app:AppHandle<R>, callback:OnSecondInstance<R>) {
+	let (stopped_tx, stopped_rx) = std::sync::mpsc::channel::<()>();
+
+	#[cfg(unix)]
+	let (path, file) = (lock.path.clone(), lock.file);
+
+	let app_ = app.clone();
+
+	let task = crate::async_runtime::spawn(async move {
+		// disconnects the receiver once the task is dropped
+		let _stopped = stopped_tx;
+
+		let app = app_;
+
+		#[cfg(unix)]
+		{
+			let listener = match tokio::net::UnixListener::from_std(lock.listener) {
+				Ok(listener) => listener,
+				Err(e) => {
+					log::error!("failed to listen to the other instances: {e}");
+
+					return;
+				},
+			};
+
+			loop {
+				match listener.accept().await {
+					Ok((stream, _)) => receive(stream, &app, &callback).await,
+					Err(e) => log::error!("failed to accept a connection from another instance: {e}"),
+				}
+			}
+		}
+
+		#[cfg(windows)]
+		{
+			let mut server = lock.server;
+
+			loop {
+				if let Err(e) = server.connect().await {
+					log::error!("failed to accept a connection from another instance: {e}");
+
+					continue;
+				}
+
+				// the next instance is created before reading, so the pipe always exists
+				let connected = server;
+
+				server = match lock.pipe.create(false) {
+					Ok(server) => server,
+					Err(e) => {
+						log::error!("failed to listen to the other instances: {e}");
+
+						return;
+					},
+				};
+
+				receive(connected, &app, &callback).await;
+			}
+		}
+	});
+
+	app.manage(SingleInstance {
+		#[cfg(unix)]
+		path,
+		#[cfg(unix)]
+		file:Mutex::new(Some(file)),
+		listener:Mutex::new(Some((task, stopped_rx))),
+	});
+}
+
+async fn receive<R:Runtime>(
+	mut stream:impl AsyncRead + Unpin,
+	app:&AppHandle<R>,
+	callback:&OnSecondInstance<R>,
+) {
+	let mut bytes = Vec::new();
+
+	match tokio::time::timeout(RECEIVE_TIMEOUT, stream.read_to_end(&mut bytes)).await {
+		Ok(Ok(_)) => {},
+		Ok(Err(e)) => {
+			log::error!("failed to read the launch of another instance: {e}");
+
+			return;
+		},
+		Err(_) => {
+			log::error!("another instance did not send its launch within {RECEIVE_TIMEOUT:?}");
+
+			return;
+		},
+	}
+
+	match serde_json::from_slice::<Launch>(&bytes) {
+		Ok(launch) => callback(app.clone(), launch.args, launch.cwd),
+		Err(e) => log::error!("failed to parse the launch of another instance: {e}"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{io::Write, path::PathBuf, sync::mpsc::channel, time::Duration};
+
+	use super::{Launch, OnSecondInstance, SingleInstance, listen, socket_name, try_acquire};
+	use crate::{
+		Manager,
+		test::{MockRuntime, mock_builder, mock_context, noop_assets},
+	};
+
+	#[test]
+	fn socket_name_is_sanitized() {
+		let name = socket_name("com.example/My App");
+
+		assert!(name.starts_with("com.example_My_App-"));
+		assert!(name.ends_with("-si"));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn socket_directory_is_private() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let directory = super::socket_directory().unwrap();
+
+		let mode = std::fs::metadata(directory).unwrap().permissions().mode();
+
+		assert_eq!(mode & 0o077, 0);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn long_socket_names_are_hashed() {
+		// the temporary directory of macOS
+		let directory =
+			std::path::Path::new("/var/folders/zz/zyxvpxvq6csfxvn_n0000000000000/T/tauri-501");
+
+		let short = super::socket_path(directory, "com.example.app-si");
+
+		assert_eq!(short, directory.join("com.example.app-si.sock"));
+
+		let name = socket_name("com.example.a-very-long-identifier.that-does-not-fit-in-sun-path");
+
+		let long = super::socket_path(directory, &name);
+
+		assert!(long.as_os_str().len() <= super::MAX_SOCKET_PATH);
+
+		assert_eq!(long, super::socket_path(directory, &name));
+
+		assert_ne!(long, super::socket_path(directory, &format!("{name}2")));
+	}
+
+	#[test]
+	fn launches_are_forwarded_until_the_lock_is_released() {
+		let identifier = format!("com.tauri.single-instance-test-{}", std::process::id());
+
+		let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+
+		let Ok(lock) = try_acquire(&identifier).unwrap() else {
+			panic!("another instance holds the lock");
+		};
+
+		let (tx, rx) = channel();
+
+		let callback:OnSecondInstance<MockRuntime> =
+			Box::new(move |_, args, cwd| tx.send((args, cwd)).unwrap());
+
+		listen(lock, app.handle().clone(), callback);
+
+		let Err(mut connection) = try_acquire(&identifier).unwrap() else {
+			panic!("the lock was acquired twice");
+		};
+
+		let args = vec!["app".to_string(), "--open".to_string(), "notes.md".to_string()];
+
+		let launch = Launch { args:args.clone(), cwd:"/home".into() };
+
+		connection.write_all(&serde_json::to_vec(&launch).unwrap()).unwrap();
+
+		drop(connection);
+
+		assert_eq!(
+			rx.recv_timeout(Duration::from_secs(5)).unwrap(),
+			(args, PathBuf::from("/home"))
+		);
+
+		app.state::<SingleInstance>().release();
+
+		// the replacement of a restarting app becomes the first instance
+		let Ok(replacement) = try_acquire(&identifier).unwrap() else {
+			panic!("the lock was not released");
+		};
+
+		// releasing again doesn't remove the socket of the new first instance
+		app.state::<SingleInstance>().release();
+
+		#[cfg(unix)]
+		assert!(replacement.path.exists());
+
+		drop(replacement);
+	}
+}
@@ -60,6 +60,17 @@ pub enum Error {
 	/// Error initializing plugin.
 	#[error("failed to initialize plugin `{0}`: {1}")]
 	PluginInitialization(String, String),
+	/// A plugin requires a plugin that is not registered.
+	#[error("plugin `{plugin}` requires plugin `{dependency}`, which is not registered")]
+	MissingPluginDependency {
+		/// The name of the plugin.
+		plugin:String,
+		/// The name of the missing plugin.
+		dependency:String,
+	},
+	/// The plugins require each other, listed from and to the same plugin.
+	#[error("plugin dependency cycle: {}", .0.join(" -> "))]
+	PluginDependencyCycle(Vec<String>),
 	/// A part of the URL is malformed or invalid. This may occur when parsing
 	/// and combining user-provided URLs and paths.
 	#[error("invalid url: {0}")]
@@ -95,6 +106,59 @@ pub enum Error {
 	#[error("menu error: {0}")]
 	#[cfg(desktop)]
 	Menu(#[from] muda::Error),
+	/// Invalid menu definition, see [`Menu::from_config`](crate::menu::Menu::from_config).
+	#[error("invalid menu definition at `{path}`: {reason}")]
+	#[cfg(desktop)]
+	InvalidMenuConfig {
+		/// The path of the invalid item, e.g. `items[0].items[2].item`.
+		path:String,
+		/// Why the item is invalid.
+		reason:String,
+	},
+	/// Menu item not found.
+	#[error("menu item `{0}` not found")]
+	#[cfg(desktop)]
+	MenuItemNotFound(String),
+	/// The click handler of a menu item was set for another runtime than the
+	/// one it was built with, see
+	/// [`MenuItemBuilder::on_click`](crate::menu::MenuItemBuilder::on_click).
+	#[error("the click handler of menu item `{0}` doesn't match the runtime of the menu item")]
+	#[cfg(desktop)]
+	MenuItemClickHandlerRuntime(String),
+	/// Failed to add an item while replacing the items of a menu, see
+	/// [`Menu::set_items`](crate::menu::Menu::set_items).
+	#[error("failed to set menu item {index} (`{id}`): {source}")]
+	#[cfg(desktop)]
+	MenuSetItems {
+		/// The position of the item that failed in the new items.
+		index:usize,
+		/// The id of the item that failed.
+		id:String,
+		/// The underlying menu error.
+		source:muda::Error,
+	},
+	/// Items of a menu builder failed to be created, see
+	/// [`MenuBuilder::build`](crate::menu::MenuBuilder::build).
+	#[error("failed to build menu:{}", format_menu_build_errors(.0))]
+	#[cfg(desktop)]
+	MenuBuild(Vec<(usize, Error)>),
+	/// Two items of a menu have the same accelerator, see
+	/// [`MenuBuilder::allow_duplicate_accelerators`](crate::menu::MenuBuilder::allow_duplicate_accelerators).
+	#[error("accelerator `{accelerator}` of `{second_id}` is already used by `{first_id}`")]
+	#[cfg(desktop)]
+	AcceleratorConflict {
+		/// The accelerator, as given to the second item.
+		accelerator:String,
+		/// The id of the item already using the accelerator.
+		first_id:String,
+		/// The id of the item that was added.
+		second_id:String,
+	},
+	/// Conflicting menu roles, see
+	/// [`SubmenuBuilder::window_menu_role`](crate::menu::SubmenuBuilder::window_menu_role).
+	#[error("menu role conflict: {0}")]
+	#[cfg(desktop)]
+	MenuRoleConflict(String),
 	/// Bad menu icon error.
 	#[error(transparent)]
 	#[cfg(desktop)]
@@ -104,6 +168,12 @@ pub enum Error {
 	#[cfg(all(desktop, feature = "tray-icon"))]
 	#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
 	Tray(#[from] tray_icon::Error),
+	/// Invalid tray icon animation, see
+	/// [`TrayIcon::set_icon_animation`](crate::tray::TrayIcon::set_icon_animation).
+	#[error("invalid tray icon animation: {0}")]
+	#[cfg(all(desktop, feature = "tray-icon"))]
+	#[cfg_attr(docsrs, doc(cfg(all(desktop, feature = "tray-icon"))))]
+	InvalidTrayIconAnimation(String),
 	/// Bad tray icon error.
 	#[error(transparent)]
 	#[cfg(all(desktop, feature = "tray-icon"))]
@@ -156,6 +226,67 @@ pub enum Error {
 	/// Bad `__TAURI_INVOKE_KEY__` value received in ipc message.
 	#[error("bad __TAURI_INVOKE_KEY__ value received in ipc message")]
 	InvokeKey,
+	/// Failed to resolve the Access Control List.
+	#[error("failed to resolve the access control list: {0}")]
+	Acl(#[from] tauri_utils::acl::Error),
+	/// Opener error.
+	#[cfg(feature = "opener")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "opener")))]
+	#[error(transparent)]
+	Opener(#[from] crate::opener::Error),
+	/// Window preset not found in the `app > windowPresets` configuration.
+	#[error("window preset `{0}` not found, available presets: {1}")]
+	WindowPresetNotFound(String, String),
+	/// A raw IPC response body doesn't match its content type.
+	#[error("invalid IPC response body: {0}")]
+	InvalidResponseBody(String),
+	/// An IPC response header has an invalid name or value.
+	#[error("invalid IPC response header: {0}")]
+	InvalidHeader(String),
+	/// The channel has as many messages waiting to be acknowledged as its
+	/// capacity.
+	#[error("the channel is full")]
+	ChannelFull,
+	/// The JavaScript side of the channel was closed.
+	#[error("the channel is closed")]
+	ChannelClosed,
+	/// The JavaScript side stopped sending a request body stream before it
+	/// ended.
+	#[error("the request body stream was aborted: {0}")]
+	RequestBodyAborted(String),
+	/// A command responded with a body stream to an invoke that doesn't read
+	/// its response as a stream.
+	#[error("the response is a stream but the invoke doesn't use the `responseType: 'stream'` option")]
+	ResponseStreamNotRequested,
+	/// Restarting the app with elevated privileges is not supported on this
+	/// platform.
+	#[error("restarting the app with elevated privileges is only supported on Windows")]
+	ElevationUnsupported,
+	/// A command was defined by more than one handler collection.
+	#[error("command `{command}` is defined in both `{first}` and `{second}`")]
+	DuplicateCommand {
+		/// The command name.
+		command:String,
+		/// The module path of the first definition.
+		first:String,
+		/// The module path of the second definition.
+		second:String,
+	},
+	/// The first instance of the app holds its lock but did not accept the
+	/// connection, so the launch could not be sent to it.
+	#[error("the first instance of the app is busy and did not receive the launch")]
+	SingleInstanceBusy,
+}
+
+/// One line per failing item, so nested builders stay readable in logs.
+#[cfg(desktop)]
+fn format_menu_build_errors(errors:&[(usize, Error)]) -> String {
+	errors
+		.iter()
+		.map(|(index, error)| {
+			format!("\n  item {index}: {}", error.to_string().replace('\n', "\n  "))
+		})
+		.collect()
 }
 
 impl From<getrandom::Error> for Error {
@@ -173,4 +304,22 @@ mod tests {
 
 		crate::test_utils::assert_sync::<super::Error>();
 	}
+
+	#[test]
+	#[cfg(desktop)]
+	fn menu_build_error_lists_each_failure() {
+		let error = super::Error::MenuBuild(vec![
+			(1, super::Error::MenuItemNotFound("open".into())),
+			(
+				3,
+				super::Error::MenuBuild(vec![(0, super::Error::MenuItemNotFound("save".into()))]),
+			),
+		]);
+
+		assert_eq!(
+			error.to_string(),
+			"failed to build menu:\n  item 1: menu item `open` not found\n  item 3: failed to build \
+			 menu:\n    item 0: menu item `save` not found"
+		);
+	}
 }
@@ -5,15 +5,29 @@
 use std::{
 	boxed::Box,
 	cell::Cell,
-	collections::{HashMap, HashSet},
+	collections::{HashMap, HashSet, VecDeque},
 	sync::{
 		Arc,
 		Mutex,
+		OnceLock,
 		atomic::{AtomicU32, Ordering},
 	},
 };
 
-use super::{EmitArgs, Event, EventId, EventTarget};
+use serde_json::Value as JsonValue;
+use tokio::sync::oneshot;
+
+use super::{
+	EmitArgs,
+	Event,
+	EventId,
+	EventPayloadRef,
+	EventReplayConfig,
+	EventTarget,
+	PayloadFilter,
+	event_patterns,
+	is_event_matched,
+};
 use crate::{Runtime, Webview};
 
 /// What to do with the pending handler when resolving it?
@@ -23,38 +37,80 @@ enum Pending {
 	Emit(EmitArgs),
 }
 
+/// Decides whether an event is dispatched to a handler, before its payload is
+/// cloned.
+pub(crate) type EventFilter = Box<dyn Fn(&EventPayloadRef<'_>) -> bool + Send>;
+
 /// Stored in [`Listeners`] to be called upon, when the event that stored it, is
 /// triggered.
 struct Handler {
 	target:EventTarget,
+	filter:Option<EventFilter>,
 	callback:Box<dyn Fn(Event) + Send>,
 }
 
 impl Handler {
-	fn new<F:Fn(Event) + Send + 'static>(target:EventTarget, callback:F) -> Self {
-		Self { target, callback:Box::new(callback) }
+	fn new<F:Fn(Event) + Send + 'static>(
+		target:EventTarget,
+		filter:Option<EventFilter>,
+		callback:F,
+	) -> Self {
+		Self { target, filter, callback:Box::new(callback) }
+	}
+
+	fn accepts(&self, payload:&EventPayloadRef<'_>) -> bool {
+		self.filter.as_ref().map(|f| f(payload)).unwrap_or(true)
 	}
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A JavaScript listener, identified by its target and id.
+#[derive(Debug, Clone)]
 struct JsHandler {
 	target:EventTarget,
 	id:EventId,
+	filter:Option<PayloadFilter>,
 }
 
 impl JsHandler {
-	fn new(target:EventTarget, id:EventId) -> Self { Self { target, id } }
+	fn new(target:EventTarget, id:EventId, filter:Option<PayloadFilter>) -> Self {
+		Self { target, id, filter }
+	}
+}
+
+impl PartialEq for JsHandler {
+	fn eq(&self, other:&Self) -> bool { self.target == other.target && self.id == other.id }
+}
+
+impl Eq for JsHandler {}
+
+impl std::hash::Hash for JsHandler {
+	fn hash<H:std::hash::Hasher>(&self, state:&mut H) {
+		self.target.hash(state);
+
+		self.id.hash(state);
+	}
 }
 
 type WebviewLabel = String;
 type EventName = String;
 
+/// The endpoints of a private channel: the receiving webview, the sending
+/// webview and the event name.
+type PrivateChannel = (WebviewLabel, WebviewLabel, EventName);
+
+type PrivateHandler = Arc<dyn Fn(Event) + Send + Sync>;
+
 /// Holds event handlers and pending event handlers, along with the salts
 /// associating them.
 struct InnerListeners {
 	pending:Mutex<Vec<Pending>>,
 	handlers:Mutex<HashMap<EventName, HashMap<EventId, Handler>>>,
 	js_event_listeners:Mutex<HashMap<WebviewLabel, HashMap<EventName, HashSet<JsHandler>>>>,
+	replay:OnceLock<EventReplayConfig>,
+	replay_buffers:Mutex<HashMap<EventName, HashMap<EventTarget, VecDeque<EmitArgs>>>>,
+	private_handlers:Mutex<HashMap<PrivateChannel, HashMap<EventId, PrivateHandler>>>,
+	private_js_listeners:Mutex<HashMap<PrivateChannel, HashSet<EventId>>>,
+	acks:Mutex<HashMap<u32, (WebviewLabel, oneshot::Sender<()>)>>,
 	function_name:&'static str,
 	listeners_object_name:&'static str,
 	next_event_id:Arc<AtomicU32>,
@@ -73,6 +129,11 @@ impl Default for Listeners {
 				pending:Mutex::default(),
 				handlers:Mutex::default(),
 				js_event_listeners:Mutex::default(),
+				replay:OnceLock::new(),
+				replay_buffers:Mutex::default(),
+				private_handlers:Mutex::default(),
+				private_js_listeners:Mutex::default(),
+				acks:Mutex::default(),
 				function_name:"__internal_unstable_listeners_function_id__",
 				listeners_object_name:"__internal_unstable_listeners_object_id__",
 				next_event_id:Default::default(),
@@ -92,6 +153,96 @@ impl Listeners {
 	/// Listener object name to represent the JavaScript event listener object.
 	pub(crate) fn listeners_object_name(&self) -> &str { self.inner.listeners_object_name }
 
+	/// Sets the events buffered until a listener can receive them.
+	pub(crate) fn set_replay(&self, config:EventReplayConfig) {
+		let _ = self.inner.replay.set(config);
+	}
+
+	/// Whether `event`, or an event matching the `event` prefix pattern, is
+	/// buffered until a listener can receive it.
+	pub(crate) fn is_replayed(&self, event:&str) -> bool {
+		self.inner
+			.replay
+			.get()
+			.map(|config| config.buffer > 0 && config.events.iter().any(|e| is_event_matched(event, e)))
+			.unwrap_or(false)
+	}
+
+	/// Buffers an event emitted to `target`, dropping the oldest buffered event
+	/// of this target when the buffer is full.
+	#[cfg(test)]
+	pub(crate) fn buffer_replay(&self, target:EventTarget, emit_args:EmitArgs) {
+		self.buffer_replay_unless(target, emit_args, || false);
+	}
+
+	/// Buffers an event emitted to `target` unless `has_listener` finds a
+	/// listener that can receive it, returning the event back to be emitted
+	/// then.
+	///
+	/// The buffers stay locked from the check to the insert, so a listener
+	/// attaching meanwhile waits in [`Self::take_replay`] and gets the event.
+	pub(crate) fn buffer_replay_unless<F:FnOnce() -> bool>(
+		&self,
+		target:EventTarget,
+		emit_args:EmitArgs,
+		has_listener:F,
+	) -> Option<EmitArgs> {
+		let Some(config) = self.inner.replay.get() else {
+			return Some(emit_args);
+		};
+
+		let mut buffers = self.inner.replay_buffers.lock().expect("poisoned replay buffers");
+
+		if has_listener() {
+			return Some(emit_args);
+		}
+
+		let buffer = buffers
+			.entry(emit_args.event_name.clone())
+			.or_default()
+			.entry(target)
+			.or_default();
+
+		if buffer.len() >= config.buffer {
+			buffer.pop_front();
+		}
+
+		buffer.push_back(emit_args);
+
+		None
+	}
+
+	/// Takes the buffered events matching `event`, whose target matches
+	/// `filter`, oldest first for each event name.
+	pub(crate) fn take_replay<F:Fn(&EventTarget) -> bool>(
+		&self,
+		event:&str,
+		filter:F,
+	) -> Vec<(EventTarget, EmitArgs)> {
+		let mut buffers = self.inner.replay_buffers.lock().expect("poisoned replay buffers");
+
+		let mut replayed = Vec::new();
+
+		buffers.retain(|name, targets| {
+			if !is_event_matched(event, name) {
+				return true;
+			}
+
+			let matched =
+				targets.keys().filter(|target| filter(target)).cloned().collect::<Vec<_>>();
+
+			for target in matched {
+				if let Some(buffer) = targets.remove(&target) {
+					replayed.extend(buffer.into_iter().map(|args| (target.clone(), args)));
+				}
+			}
+
+			!targets.is_empty()
+		});
+
+		replayed
+	}
+
 	/// Insert a pending event action to the queue.
 	fn insert_pending(&self, action:Pending) {
 		self.inner.pending.lock().expect("poisoned pending event queue").push(action)
@@ -132,10 +283,22 @@ impl Listeners {
 		event:String,
 		target:EventTarget,
 		handler:F,
+	) -> EventId {
+		self.listen_filter(event, target, None, handler)
+	}
+
+	/// Adds an event listener called only with the events accepted by
+	/// `filter`.
+	pub(crate) fn listen_filter<F:Fn(Event) + Send + 'static>(
+		&self,
+		event:String,
+		target:EventTarget,
+		filter:Option<EventFilter>,
+		handler:F,
 	) -> EventId {
 		let id = self.next_event_id();
 
-		let handler = Handler::new(target, handler);
+		let handler = Handler::new(target, filter, handler);
 
 		self.listen_with_id(id, event, handler);
 
@@ -148,12 +311,23 @@ impl Listeners {
 		event:String,
 		target:EventTarget,
 		handler:F,
+	) -> EventId {
+		self.once_filter(event, target, None, handler)
+	}
+
+	/// Listen to the first event accepted by `filter` and immediately unlisten.
+	pub(crate) fn once_filter<F:FnOnce(Event) + Send + 'static>(
+		&self,
+		event:String,
+		target:EventTarget,
+		filter:Option<EventFilter>,
+		handler:F,
 	) -> EventId {
 		let self_ = self.clone();
 
 		let handler = Cell::new(Some(handler));
 
-		self.listen(event, target, move |event| {
+		self.listen_filter(event, target, filter, move |event| {
 			let id = event.id;
 			let handler = handler.take().expect("attempted to call handler more than once");
 			handler(event);
@@ -171,6 +345,141 @@ impl Listeners {
 				})
 			},
 		}
+
+		self.inner.private_handlers.lock().unwrap().retain(|_, handlers| {
+			handlers.remove(&id);
+
+			!handlers.is_empty()
+		});
+	}
+
+	/// Adds a listener of the `event` events that `source` privately emits to
+	/// `receiver`.
+	pub(crate) fn listen_private<F:Fn(Event) + Send + Sync + 'static>(
+		&self,
+		receiver:&str,
+		source:&str,
+		event:String,
+		handler:F,
+	) -> EventId {
+		let id = self.next_event_id();
+
+		self.inner
+			.private_handlers
+			.lock()
+			.unwrap()
+			.entry((receiver.to_string(), source.to_string(), event))
+			.or_default()
+			.insert(id, Arc::new(handler));
+
+		id
+	}
+
+	pub(crate) fn listen_private_js(&self, receiver:&str, source:&str, event:&str, id:EventId) {
+		self.inner
+			.private_js_listeners
+			.lock()
+			.unwrap()
+			.entry((receiver.to_string(), source.to_string(), event.to_string()))
+			.or_default()
+			.insert(id);
+	}
+
+	/// Removes a JavaScript private listener of `receiver`.
+	pub(crate) fn unlisten_private_js(&self, receiver:&str, id:EventId) {
+		self.inner.private_js_listeners.lock().unwrap().retain(|(label, ..), ids| {
+			if label == receiver {
+				ids.remove(&id);
+			}
+
+			!ids.is_empty()
+		});
+	}
+
+	/// Calls the Rust private listeners of the event `source` emitted to
+	/// `receiver`, returning the IDs of its JavaScript private listeners.
+	pub(crate) fn emit_private(
+		&self,
+		receiver:&str,
+		source:&str,
+		emit_args:&EmitArgs,
+	) -> Vec<EventId> {
+		let key = (receiver.to_string(), source.to_string(), emit_args.event_name.clone());
+
+		// cloned so the handlers can (un)listen without deadlocking
+		let handlers = self
+			.inner
+			.private_handlers
+			.lock()
+			.unwrap()
+			.get(&key)
+			.map(|handlers| handlers.iter().map(|(id, h)| (*id, h.clone())).collect::<Vec<_>>())
+			.unwrap_or_default();
+
+		for (id, handler) in handlers {
+			handler(Event::new(id, emit_args));
+		}
+
+		self.inner
+			.private_js_listeners
+			.lock()
+			.unwrap()
+			.get(&key)
+			.map(|ids| ids.iter().copied().collect())
+			.unwrap_or_default()
+	}
+
+	/// Removes the JS listeners of a destroyed webview.
+	pub(crate) fn remove_js(&self, webview:&str) {
+		self.inner.js_event_listeners.lock().unwrap().remove(webview);
+	}
+
+	/// Removes the private channels of a destroyed webview.
+	pub(crate) fn remove_private(&self, webview:&str) {
+		let is_endpoint =
+			|(receiver, source, _):&PrivateChannel| receiver == webview || source == webview;
+
+		self.inner.private_handlers.lock().unwrap().retain(|key, _| !is_endpoint(key));
+
+		self.inner.private_js_listeners.lock().unwrap().retain(|key, _| !is_endpoint(key));
+	}
+
+	/// Waits for `webview` to acknowledge an event, returning the ID it
+	/// acknowledges it with.
+	pub(crate) fn pending_ack(&self, webview:&str) -> (u32, oneshot::Receiver<()>) {
+		let id = self.next_event_id();
+
+		let (tx, rx) = oneshot::channel();
+
+		self.inner.acks.lock().unwrap().insert(id, (webview.to_string(), tx));
+
+		(id, rx)
+	}
+
+	/// Acknowledges an event, ignored if it was sent to another webview.
+	pub(crate) fn ack(&self, webview:&str, id:u32) {
+		let mut acks = self.inner.acks.lock().unwrap();
+
+		if acks.get(&id).is_some_and(|(label, _)| label == webview) {
+			if let Some((_, tx)) = acks.remove(&id) {
+				let _ = tx.send(());
+			}
+		}
+	}
+
+	/// Stops waiting for the given acknowledgements.
+	pub(crate) fn remove_acks(&self, ids:&[u32]) {
+		let mut acks = self.inner.acks.lock().unwrap();
+
+		for id in ids {
+			acks.remove(id);
+		}
+	}
+
+	/// Stops waiting for the acknowledgements of a webview that navigated away
+	/// or was destroyed.
+	pub(crate) fn remove_webview_acks(&self, webview:&str) {
+		self.inner.acks.lock().unwrap().retain(|_, (label, _)| label != webview);
 	}
 
 	/// Emits the given event with its payload based on a filter.
@@ -182,13 +491,20 @@ impl Listeners {
 		match self.inner.handlers.try_lock() {
 			Err(_) => self.insert_pending(Pending::Emit(emit_args)),
 			Ok(lock) => {
-				if let Some(handlers) = lock.get(&emit_args.event_name) {
+				let payload = EventPayloadRef::new(&emit_args);
+
+				for pattern in event_patterns(&emit_args.event_name) {
+					let Some(handlers) = lock.get(pattern.as_ref()) else {
+						continue;
+					};
+
 					let handlers = handlers.iter();
-					let handlers =
-						handlers.filter(|(_, h)| match_any_or_filter(&h.target, &filter));
+					let handlers = handlers.filter(|(_, h)| {
+						match_any_or_filter(&h.target, &filter) && h.accepts(&payload)
+					});
 					for (&id, Handler { callback, .. }) in handlers {
 						maybe_pending = true;
-						(callback)(Event::new(id, emit_args.payload.clone()))
+						(callback)(Event::new(id, &emit_args))
 					}
 				}
 			},
@@ -212,6 +528,7 @@ impl Listeners {
 		source_webview_label:&str,
 		target:EventTarget,
 		id:EventId,
+		filter:Option<PayloadFilter>,
 	) {
 		let mut listeners = self.inner.js_event_listeners.lock().unwrap();
 
@@ -220,7 +537,7 @@ impl Listeners {
 			.or_default()
 			.entry(event.to_string())
 			.or_default()
-			.insert(JsHandler::new(target, id));
+			.insert(JsHandler::new(target, id, filter));
 	}
 
 	pub(crate) fn unlisten_js(&self, event:&str, id:EventId) {
@@ -239,43 +556,130 @@ impl Listeners {
 		}
 	}
 
+	/// Whether a Rust listener of `event` matches `filter`, assuming there is one
+	/// while the listeners are being called.
+	pub(crate) fn has_listener<F:Fn(&EventTarget) -> bool>(&self, event:&str, filter:F) -> bool {
+		match self.inner.handlers.try_lock() {
+			Err(_) => true,
+			Ok(lock) => {
+				event_patterns(event).any(|pattern| {
+					lock.get(pattern.as_ref())
+						.map(|handlers| {
+							handlers.values().any(|h| match_any_or_filter(&h.target, &Some(&filter)))
+						})
+						.unwrap_or(false)
+				})
+			},
+		}
+	}
+
+	/// The number of Rust and JS listeners of each event.
+	///
+	/// The Rust listeners are not counted while they are being called, when the
+	/// counts are read from one of them.
+	pub(crate) fn listener_counts(&self) -> HashMap<String, usize> {
+		let mut counts = HashMap::<String, usize>::new();
+
+		if let Ok(handlers) = self.inner.handlers.try_lock() {
+			for (event, handlers) in handlers.iter().filter(|(_, handlers)| !handlers.is_empty()) {
+				*counts.entry(event.clone()).or_default() += handlers.len();
+			}
+		}
+
+		for events in self.inner.js_event_listeners.lock().unwrap().values() {
+			for (event, handlers) in events {
+				*counts.entry(event.clone()).or_default() += handlers.len();
+			}
+		}
+
+		counts
+	}
+
 	pub(crate) fn has_js_listener<F:Fn(&EventTarget) -> bool>(&self, event:&str, filter:F) -> bool {
 		let js_listeners = self.inner.js_event_listeners.lock().unwrap();
 
 		js_listeners.values().any(|events| {
-			events
-				.get(event)
-				.map(|handlers| handlers.iter().any(|handler| filter(&handler.target)))
-				.unwrap_or(false)
+			event_patterns(event).any(|pattern| {
+				events
+					.get(pattern.as_ref())
+					.map(|handlers| handlers.iter().any(|handler| filter(&handler.target)))
+					.unwrap_or(false)
+			})
 		})
 	}
 
 	pub(crate) fn emit_js_filter<'a, R, I, F>(
 		&self,
-		mut webviews:I,
+		webviews:I,
 		event:&str,
 		emit_args:&EmitArgs,
 		filter:Option<F>,
 	) -> crate::Result<()>
+	where
+		R: Runtime,
+		I: Iterator<Item = &'a Webview<R>>,
+		F: Fn(&EventTarget) -> bool, {
+		self.js_listener_ids(webviews, event, emit_args, filter)
+			.into_iter()
+			.try_for_each(|(webview, ids)| webview.emit_js(emit_args, &ids))
+	}
+
+	/// The webviews with JS listeners of the event, along with the IDs of these
+	/// listeners.
+	pub(crate) fn js_listener_ids<'a, R, I, F>(
+		&self,
+		webviews:I,
+		event:&str,
+		emit_args:&EmitArgs,
+		filter:Option<F>,
+	) -> Vec<(&'a Webview<R>, Vec<EventId>)>
 	where
 		R: Runtime,
 		I: Iterator<Item = &'a Webview<R>>,
 		F: Fn(&EventTarget) -> bool, {
 		let js_listeners = self.inner.js_event_listeners.lock().unwrap();
 
-		webviews.try_for_each(|webview| {
-			if let Some(handlers) = js_listeners.get(webview.label()).and_then(|s| s.get(event)) {
-				let ids = handlers
-					.iter()
-					.filter(|handler| match_any_or_filter(&handler.target, &filter))
-					.map(|handler| handler.id)
-					.collect::<Vec<_>>();
+		// only parsed if a listener filters the payload
+		let mut payload = None;
 
-				webview.emit_js(emit_args, &ids)?;
+		let parse = || serde_json::from_str(&emit_args.payload).unwrap_or(JsonValue::Null);
+
+		let mut matched = Vec::new();
+
+		for webview in webviews {
+			let Some(events) = js_listeners.get(webview.label()) else {
+				continue;
+			};
+
+			let mut ids = Vec::new();
+
+			for pattern in event_patterns(event) {
+				let Some(handlers) = events.get(pattern.as_ref()) else {
+					continue;
+				};
+
+				ids.extend(
+					handlers
+						.iter()
+						.filter(|handler| match_any_or_filter(&handler.target, &filter))
+						.filter(|handler| {
+							handler
+								.filter
+								.as_ref()
+								.map(|f| f.matches(payload.get_or_insert_with(parse)))
+								.unwrap_or(true)
+						})
+						.map(|handler| handler.id),
+				);
 			}
 
-			Ok(())
-		})
+			// the events filtered out of every listener are not sent to the webview
+			if !ids.is_empty() {
+				matched.push((webview, ids));
+			}
+		}
+
+		matched
 	}
 
 	pub(crate) fn emit_js<'a, R, I>(
@@ -308,6 +712,310 @@ mod test {
 		println!("{s:?}");
 	}
 
+	#[test]
+	fn replay_buffer_drops_oldest() {
+		let listeners:Listeners = Default::default();
+
+		listeners.set_replay(EventReplayConfig { events:vec!["ready".into()], buffer:2 });
+
+		assert!(listeners.is_replayed("ready"));
+		assert!(!listeners.is_replayed("other"));
+
+		for i in 0..3 {
+			listeners.buffer_replay(EventTarget::labeled("main"), EmitArgs::new("ready", i).unwrap());
+		}
+
+		listeners.buffer_replay(EventTarget::labeled("other"), EmitArgs::new("ready", 3).unwrap());
+
+		let replayed = listeners.take_replay("ready", |t| *t == EventTarget::labeled("main"));
+
+		assert_eq!(
+			replayed.iter().map(|(_, args)| args.payload.as_str()).collect::<Vec<_>>(),
+			["1", "2"]
+		);
+
+		assert!(listeners.take_replay("ready", |t| *t == EventTarget::labeled("main")).is_empty());
+
+		assert_eq!(listeners.take_replay("ready", |_| true).len(), 1);
+	}
+
+	#[test]
+	fn prefix_listeners_receive_namespaced_events() {
+		let listeners:Listeners = Default::default();
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let received_ = received.clone();
+
+		listeners.listen("sync:*".into(), EventTarget::Any, move |event| {
+			received_.lock().unwrap().push(event.event().to_string());
+		});
+
+		for event in ["sync:error", "sync:file:progress", "synchronized", "other:sync"] {
+			listeners.emit(EmitArgs::new(event, ()).unwrap()).unwrap();
+		}
+
+		assert_eq!(*received.lock().unwrap(), ["sync:error", "sync:file:progress"]);
+		assert!(listeners.has_listener("sync:file:done", |_| true));
+		assert!(!listeners.has_listener("synchronized", |_| true));
+	}
+
+	#[test]
+	fn listener_counts_include_js_listeners() {
+		let listeners:Listeners = Default::default();
+
+		let id = listeners.listen("ready".into(), EventTarget::Any, event_fn);
+
+		listeners.listen("ready".into(), EventTarget::Any, event_fn);
+
+		listeners.listen_js("ready", "main", EventTarget::Any, 1, None);
+
+		listeners.listen_js("progress", "popup", EventTarget::Any, 2, None);
+
+		listeners.unlisten(id);
+
+		let counts = listeners.listener_counts();
+
+		assert_eq!(counts["ready"], 2);
+		assert_eq!(counts["progress"], 1);
+		assert_eq!(counts.len(), 2);
+	}
+
+	#[test]
+	fn private_events_only_reach_the_receiver() {
+		let listeners:Listeners = Default::default();
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let received_ = received.clone();
+
+		listeners.listen("token".into(), EventTarget::Any, |_| panic!("broadcast listener called"));
+
+		listeners.listen_private("main", "auth", "token".into(), move |event| {
+			received_.lock().unwrap().push(event.payload().to_string());
+		});
+
+		listeners.listen_private_js("main", "auth", "token", 42);
+
+		let args = EmitArgs::new("token", "secret").unwrap();
+
+		assert!(listeners.emit_private("popup", "auth", &args).is_empty());
+		assert!(listeners.emit_private("main", "other", &args).is_empty());
+		assert_eq!(listeners.emit_private("main", "auth", &args), [42]);
+
+		assert_eq!(*received.lock().unwrap(), ["\"secret\""]);
+
+		listeners.remove_private("auth");
+
+		assert!(listeners.emit_private("main", "auth", &args).is_empty());
+		assert_eq!(received.lock().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn completion_waits_for_guards() {
+		let listeners:Listeners = Default::default();
+
+		let guards = Arc::new(Mutex::new(Vec::new()));
+
+		let guards_ = guards.clone();
+
+		listeners.listen("flush".into(), EventTarget::Any, move |event| {
+			guards_.lock().unwrap().push(event.completion_guard());
+		});
+
+		let (completion, mut completed) = tokio::sync::mpsc::channel::<()>(1);
+
+		let mut args = EmitArgs::new("flush", ()).unwrap();
+
+		args.completion =
+			Some(crate::event::EmitCompletion { listeners:completion, acks:Default::default() });
+
+		listeners.emit(args).unwrap();
+
+		assert!(matches!(completed.try_recv(), Err(tokio::sync::mpsc::error::TryRecvError::Empty)));
+
+		guards.lock().unwrap().clear();
+
+		assert!(matches!(
+			completed.try_recv(),
+			Err(tokio::sync::mpsc::error::TryRecvError::Disconnected)
+		));
+	}
+
+	#[test]
+	fn acks_are_only_accepted_from_their_webview() {
+		let listeners:Listeners = Default::default();
+
+		let (id, mut ack) = listeners.pending_ack("main");
+
+		listeners.ack("other", id);
+
+		assert!(ack.try_recv().is_err());
+
+		listeners.ack("main", id);
+
+		assert!(ack.try_recv().is_ok());
+	}
+
+	#[test]
+	fn acks_are_only_resolved_once() {
+		use tokio::sync::oneshot::error::TryRecvError;
+
+		let listeners:Listeners = Default::default();
+
+		let (id, mut ack) = listeners.pending_ack("main");
+
+		let (other, mut other_ack) = listeners.pending_ack("main");
+
+		assert_ne!(id, other);
+
+		listeners.ack("main", id);
+
+		listeners.ack("main", id);
+
+		listeners.ack("main", u32::MAX);
+
+		assert!(ack.try_recv().is_ok());
+		assert!(matches!(other_ack.try_recv(), Err(TryRecvError::Empty)));
+	}
+
+	#[test]
+	fn removed_acks_stop_being_waited_for() {
+		use tokio::sync::oneshot::error::TryRecvError;
+
+		let listeners:Listeners = Default::default();
+
+		let (first, mut first_ack) = listeners.pending_ack("main");
+
+		let (_, mut second_ack) = listeners.pending_ack("main");
+
+		let (_, mut popup_ack) = listeners.pending_ack("popup");
+
+		listeners.remove_acks(&[first]);
+
+		assert!(matches!(first_ack.try_recv(), Err(TryRecvError::Closed)));
+		assert!(matches!(second_ack.try_recv(), Err(TryRecvError::Empty)));
+
+		listeners.remove_webview_acks("main");
+
+		assert!(matches!(second_ack.try_recv(), Err(TryRecvError::Closed)));
+		assert!(matches!(popup_ack.try_recv(), Err(TryRecvError::Empty)));
+
+		// acknowledging a removed event is ignored
+		listeners.ack("main", first);
+	}
+
+	#[test]
+	fn wildcard_only_valid_as_trailing_segment() {
+		use crate::event::is_event_pattern_valid;
+
+		assert!(is_event_pattern_valid("sync:*"));
+		assert!(is_event_pattern_valid("sync:file:*"));
+		assert!(is_event_pattern_valid("sync:file:progress"));
+
+		assert!(!is_event_pattern_valid("*"));
+		assert!(!is_event_pattern_valid(":*"));
+		assert!(!is_event_pattern_valid("sync*"));
+		assert!(!is_event_pattern_valid("sync:*:progress"));
+	}
+
+	#[test]
+	fn events_are_matched_by_their_prefix_patterns() {
+		assert_eq!(
+			event_patterns("sync:file:progress").collect::<Vec<_>>(),
+			["sync:file:progress", "sync:*", "sync:file:*"]
+		);
+		assert_eq!(event_patterns("ready").collect::<Vec<_>>(), ["ready"]);
+
+		assert!(is_event_matched("sync:*", "sync:file:progress"));
+		assert!(is_event_matched("sync:file:*", "sync:file:progress"));
+		assert!(is_event_matched("ready", "ready"));
+
+		assert!(!is_event_matched("sync:*", "synchronized"));
+		assert!(!is_event_matched("sync:file:*", "sync:error"));
+		assert!(!is_event_matched("ready", "ready:now"));
+	}
+
+	#[test]
+	fn invalid_event_patterns_are_rejected() {
+		use crate::event::plugin::EventPattern;
+
+		let pattern = |pattern:&str| serde_json::from_value::<EventPattern>(pattern.into());
+
+		assert_eq!(&*pattern("sync:*").unwrap(), "sync:*");
+		assert_eq!(&*pattern("sync:file:progress").unwrap(), "sync:file:progress");
+
+		for invalid in ["*", "sync*", "sync:*:progress", "sync file", "sync:**"] {
+			assert!(pattern(invalid).is_err(), "{invalid} was accepted");
+		}
+	}
+
+	#[test]
+	fn filtered_listeners_only_receive_accepted_events() {
+		let listeners:Listeners = Default::default();
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let received_ = received.clone();
+
+		let filter:EventFilter = Box::new(|payload:&EventPayloadRef<'_>| {
+			serde_json::from_str::<JsonValue>(payload.payload())
+				.map(|payload| payload["id"] == "main")
+				.unwrap_or(false)
+		});
+
+		listeners.listen_filter("progress".into(), EventTarget::Any, Some(filter), move |event| {
+			received_.lock().unwrap().push(event.payload().to_string());
+		});
+
+		listeners.emit(EmitArgs::new("progress", serde_json::json!({ "id": "other" })).unwrap()).unwrap();
+		listeners.emit(EmitArgs::new("progress", serde_json::json!({ "id": "main" })).unwrap()).unwrap();
+		listeners.emit(EmitArgs::new("progress", "main").unwrap()).unwrap();
+
+		assert_eq!(*received.lock().unwrap(), [r#"{"id":"main"}"#]);
+	}
+
+	#[test]
+	fn once_filter_waits_for_an_accepted_event() {
+		let listeners:Listeners = Default::default();
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let received_ = received.clone();
+
+		listeners.once_filter(
+			"progress".into(),
+			EventTarget::Any,
+			Some(Box::new(|payload:&EventPayloadRef<'_>| payload.payload() == "2")),
+			move |event| received_.lock().unwrap().push(event.payload().to_string()),
+		);
+
+		for i in 0..4 {
+			listeners.emit(EmitArgs::new("progress", i).unwrap()).unwrap();
+		}
+
+		listeners.emit(EmitArgs::new("progress", 2).unwrap()).unwrap();
+
+		assert_eq!(*received.lock().unwrap(), ["2"]);
+	}
+
+	#[test]
+	fn payload_filters_match_nested_keys() {
+		let filter:PayloadFilter =
+			serde_json::from_value(serde_json::json!({ "/file/id": 1, "/done": false })).unwrap();
+
+		assert!(filter.matches(&serde_json::json!({ "file": { "id": 1, "name": "a" }, "done": false })));
+
+		assert!(!filter.matches(&serde_json::json!({ "file": { "id": 2 }, "done": false })));
+		assert!(!filter.matches(&serde_json::json!({ "file": { "id": 1 } })));
+		assert!(!filter.matches(&serde_json::json!({ "id": 1, "done": false })));
+		assert!(!filter.matches(&JsonValue::Null));
+
+		let empty:PayloadFilter = serde_json::from_value(serde_json::json!({})).unwrap();
+
+		assert!(empty.matches(&JsonValue::Null));
+	}
+
 	proptest! {
 	  #![proptest_config(ProptestConfig::with_cases(10000))]
 
@@ -363,7 +1071,10 @@ mod test {
 		listeners.emit(EmitArgs {
 		  event_name: key.clone(),
 		  event: serde_json::to_string(&key).unwrap(),
-		  payload: serde_json::to_string(&d).unwrap()
+		  payload: serde_json::to_string(&d).unwrap(),
+		  bytes: None,
+		  target: None,
+		  completion: None,
 		})?;
 
 		// lock the mutex
@@ -4,16 +4,38 @@
 
 mod listener;
 pub(crate) mod plugin;
-use std::{convert::Infallible, str::FromStr};
+use std::{
+	borrow::Cow,
+	collections::BTreeMap,
+	convert::Infallible,
+	str::FromStr,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
 
-pub(crate) use listener::Listeners;
+pub(crate) use listener::{EventFilter, Listeners};
+use crate::ipc::channel::{ACK_EVENT_COMMAND, CHANNEL_ID_HEADER_NAME, FETCH_CHANNEL_DATA_COMMAND};
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tokio::sync::{mpsc, oneshot};
+
+/// How long [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait) waits
+/// for the listeners.
+pub const DEFAULT_EMIT_WAIT_TIMEOUT:Duration = Duration::from_secs(5);
+
+/// Separates the segments of hierarchical event names, e.g.
+/// `sync:file:progress`.
+pub const EVENT_NAME_SEPARATOR:char = ':';
 
 /// Checks if an event name is valid.
+///
+/// Event names can be namespaced with the [`EVENT_NAME_SEPARATOR`], so a
+/// listener of `sync:*` receives both `sync:error` and `sync:file:progress`.
 pub fn is_event_name_valid(event:&str) -> bool {
 	event
 		.chars()
-		.all(|c| c.is_alphanumeric() || c == '-' || c == '/' || c == ':' || c == '_')
+		.all(|c| c.is_alphanumeric() || c == '-' || c == '/' || c == EVENT_NAME_SEPARATOR || c == '_')
 }
 
 pub fn assert_event_name_is_valid(event:&str) {
@@ -23,6 +45,45 @@ pub fn assert_event_name_is_valid(event:&str) {
 	);
 }
 
+/// Checks if an event name or prefix pattern is valid to listen to.
+///
+/// A pattern is an event name followed by the separator and a `*` wildcard,
+/// e.g. `sync:*`. The wildcard is only valid as the trailing segment.
+pub fn is_event_pattern_valid(pattern:&str) -> bool {
+	match pattern.strip_suffix('*') {
+		Some(prefix) => {
+			prefix.len() > 1 && prefix.ends_with(EVENT_NAME_SEPARATOR) && is_event_name_valid(prefix)
+		},
+		None => is_event_name_valid(pattern),
+	}
+}
+
+pub fn assert_event_pattern_is_valid(pattern:&str) {
+	assert!(
+		is_event_pattern_valid(pattern),
+		"Event name must include only alphanumeric characters, `-`, `/`, `:` and `_`, optionally \
+		 followed by a trailing `:*` wildcard."
+	);
+}
+
+/// The event name followed by the prefix patterns matching it, e.g. `sync:*`
+/// and `sync:file:*` for `sync:file:progress`.
+pub(crate) fn event_patterns(event:&str) -> impl Iterator<Item = Cow<'_, str>> {
+	std::iter::once(Cow::Borrowed(event)).chain(
+		event
+			.match_indices(EVENT_NAME_SEPARATOR)
+			.map(move |(i, _)| Cow::Owned(format!("{}*", &event[..=i]))),
+	)
+}
+
+/// Whether `event` is the event name or matches the prefix pattern.
+pub(crate) fn is_event_matched(pattern:&str, event:&str) -> bool {
+	match pattern.strip_suffix('*') {
+		Some(prefix) => event.starts_with(prefix),
+		None => pattern == event,
+	}
+}
+
 /// Unique id of an event.
 pub type EventId = u32;
 
@@ -95,6 +156,19 @@ impl FromStr for EventTarget {
 	fn from_str(s:&str) -> Result<Self, Self::Err> { Ok(Self::AnyLabel { label:s.to_string() }) }
 }
 
+/// Events buffered while no listener can receive them, replayed to the first
+/// matching listener that attaches.
+///
+/// See [`Builder::event_replay`](crate::Builder::event_replay).
+#[derive(Debug, Clone, Default)]
+pub struct EventReplayConfig {
+	/// Names of the events to buffer.
+	pub events:Vec<String>,
+	/// Maximum number of events buffered per event name and target, the oldest
+	/// event is dropped when it is full.
+	pub buffer:usize,
+}
+
 /// Serialized emit arguments.
 #[derive(Clone)]
 pub struct EmitArgs {
@@ -102,8 +176,38 @@ pub struct EmitArgs {
 	pub event_name:String,
 	/// Serialized event name.
 	pub event:String,
-	/// Serialized payload.
+	/// Serialized payload, `null` for binary events.
 	pub payload:String,
+	/// Binary payload, see [`Emitter::emit_bytes`](crate::Emitter::emit_bytes).
+	pub bytes:Option<Bytes>,
+	/// The target the event was emitted to, `None` if it was emitted with a
+	/// filter or to several targets.
+	pub target:Option<EventTarget>,
+	/// What an [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait) call
+	/// waits for.
+	pub(crate) completion:Option<EmitCompletion>,
+}
+
+/// Tracks the listeners of an
+/// [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait) event.
+#[derive(Clone)]
+pub(crate) struct EmitCompletion {
+	/// Never sent to, closed once the Rust listeners dropped every clone.
+	pub(crate) listeners:mpsc::Sender<()>,
+	/// The acknowledgements awaited from the webviews the event was sent to.
+	pub(crate) acks:Arc<Mutex<Vec<(u32, String, oneshot::Receiver<()>)>>>,
+}
+
+impl EmitCompletion {
+	/// Waits for `webview` to acknowledge the event, returning the ID it
+	/// acknowledges it with.
+	pub(crate) fn wait_for_ack(&self, listeners:&Listeners, webview:&str) -> u32 {
+		let (id, ack) = listeners.pending_ack(webview);
+
+		self.acks.lock().unwrap().push((id, webview.to_string(), ack));
+
+		id
+	}
 }
 
 impl EmitArgs {
@@ -115,25 +219,181 @@ impl EmitArgs {
 			event_name:event.into(),
 			event:serde_json::to_string(event)?,
 			payload:serde_json::to_string(&payload)?,
+			bytes:None,
+			target:None,
+			completion:None,
 		})
 	}
+
+	pub fn binary(event:&str, bytes:Bytes) -> crate::Result<Self> {
+		Ok(EmitArgs {
+			event_name:event.into(),
+			event:serde_json::to_string(event)?,
+			payload:"null".into(),
+			bytes:Some(bytes),
+			target:None,
+			completion:None,
+		})
+	}
+}
+
+/// An event about to be dispatched, inspected by the filter of a listener
+/// before the payload is cloned for it.
+///
+/// See [`Listener::listen_filter`](crate::Listener::listen_filter).
+#[derive(Clone, Copy)]
+pub struct EventPayloadRef<'a> {
+	args:&'a EmitArgs,
+}
+
+impl<'a> EventPayloadRef<'a> {
+	pub(crate) fn new(args:&'a EmitArgs) -> Self { Self { args } }
+
+	/// The event name.
+	pub fn event(&self) -> &'a str { &self.args.event_name }
+
+	/// The serialized event payload, `null` for binary events.
+	pub fn payload(&self) -> &'a str { &self.args.payload }
+
+	/// The event payload.
+	pub fn data(&self) -> EventPayload<'a> {
+		match &self.args.bytes {
+			Some(bytes) => EventPayload::Binary(bytes),
+			None => EventPayload::Json(&self.args.payload),
+		}
+	}
+
+	/// The target the event was emitted to, `None` if it was emitted with
+	/// [`Emitter::emit_filter`](crate::Emitter::emit_filter).
+	pub fn target(&self) -> Option<&'a EventTarget> { self.args.target.as_ref() }
+}
+
+/// A filter on the payload of the events dispatched to a JavaScript listener,
+/// mapping [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901) to
+/// the values they must point to.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct PayloadFilter(BTreeMap<String, JsonValue>);
+
+impl PayloadFilter {
+	/// Whether all the pointers of the filter point to their value in
+	/// `payload`.
+	pub(crate) fn matches(&self, payload:&JsonValue) -> bool {
+		self.0.iter().all(|(pointer, value)| payload.pointer(pointer) == Some(value))
+	}
+}
+
+/// The payload of an [`Event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventPayload<'a> {
+	/// A JSON serialized payload.
+	Json(&'a str),
+	/// A binary payload, emitted with
+	/// [`Emitter::emit_bytes`](crate::Emitter::emit_bytes).
+	Binary(&'a [u8]),
+}
+
+/// Keeps an [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait) call
+/// waiting until it is dropped, see [`Event::completion_guard`].
+#[derive(Debug)]
+pub struct EmitCompletionGuard(#[allow(dead_code)] Option<mpsc::Sender<()>>);
+
+/// The outcome of [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait).
+#[derive(Debug, Clone, Default)]
+pub struct EmitReport {
+	/// Whether the Rust listeners, and the completion guards they took, finished
+	/// before the timeout.
+	pub listeners_completed:bool,
+	/// The labels of the webviews whose JavaScript listeners handled the event.
+	pub acknowledged:Vec<String>,
+	/// The labels of the webviews whose JavaScript listeners didn't handle the
+	/// event before the timeout.
+	pub unacknowledged:Vec<String>,
+}
+
+impl EmitReport {
+	/// Whether every listener handled the event before the timeout.
+	pub fn is_complete(&self) -> bool { self.listeners_completed && self.unacknowledged.is_empty() }
+}
+
+#[derive(Debug, Clone)]
+enum EventData {
+	Json(String),
+	// shared by all the listeners of the event
+	Binary(Bytes),
 }
 
 /// An event that was emitted.
 #[derive(Debug, Clone)]
 pub struct Event {
 	id:EventId,
-	data:String,
+	event:String,
+	data:EventData,
+	completion:Option<mpsc::WeakSender<()>>,
 }
 
 impl Event {
-	fn new(id:EventId, data:String) -> Self { Self { id, data } }
+	fn new(id:EventId, args:&EmitArgs) -> Self {
+		let data = match &args.bytes {
+			Some(bytes) => EventData::Binary(bytes.clone()),
+			None => EventData::Json(args.payload.clone()),
+		};
+
+		Self {
+			id,
+			event:args.event_name.clone(),
+			data,
+			completion:args.completion.as_ref().map(|completion| completion.listeners.downgrade()),
+		}
+	}
+
+	/// Delays the completion of
+	/// [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait) until the
+	/// returned guard is dropped, for listeners that finish handling the event
+	/// asynchronously.
+	///
+	/// The guard does nothing for the events emitted otherwise.
+	///
+	/// # Examples
+	/// ```
+	/// use tauri::Listener;
+	///
+	/// tauri::Builder::default().setup(|app| {
+	///   app.listen("flush-state", |event| {
+	///     let guard = event.completion_guard();
+	///     tauri::async_runtime::spawn(async move {
+	///       // write the state to disk...
+	///       drop(guard);
+	///     });
+	///   });
+	///   Ok(())
+	/// });
+	/// ```
+	pub fn completion_guard(&self) -> EmitCompletionGuard {
+		EmitCompletionGuard(self.completion.as_ref().and_then(mpsc::WeakSender::upgrade))
+	}
 
 	/// The [`EventId`] of the handler that was triggered.
 	pub fn id(&self) -> EventId { self.id }
 
+	/// The full event name, e.g. `sync:file:progress` for a listener of
+	/// `sync:*`.
+	pub fn event(&self) -> &str { &self.event }
+
+	/// The JSON serialized event payload, `null` for binary events.
+	pub fn payload(&self) -> &str {
+		match &self.data {
+			EventData::Json(payload) => payload,
+			EventData::Binary(_) => "null",
+		}
+	}
+
 	/// The event payload.
-	pub fn payload(&self) -> &str { &self.data }
+	pub fn data(&self) -> EventPayload<'_> {
+		match &self.data {
+			EventData::Json(payload) => EventPayload::Json(payload),
+			EventData::Binary(bytes) => EventPayload::Binary(bytes),
+		}
+	}
 }
 
 pub fn listen_js_script(
@@ -164,21 +424,44 @@ pub fn listen_js_script(
 	)
 }
 
+/// `ack` is the ID the webview acknowledges the event with once its listeners
+/// handled it, see [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait).
 pub fn emit_js_script(
 	event_emit_function_name:&str,
 	emit_args:&EmitArgs,
 	serialized_ids:&str,
+	ack:Option<u32>,
 ) -> crate::Result<String> {
 	Ok(format!(
-		"(function () {{ const fn = window['{}']; fn && fn({{event: {}, payload: {}}}, {ids}) \
-		 }})()",
+		"(function () {{ const fn = window['{}']; fn && fn({{event: {}, payload: {}}}, {ids}, \
+		 {ack}) }})()",
 		event_emit_function_name,
 		emit_args.event,
 		emit_args.payload,
 		ids = serialized_ids,
+		ack = serde_json::to_string(&ack)?,
 	))
 }
 
+pub fn emit_binary_js_script(
+	event_emit_function_name:&str,
+	emit_args:&EmitArgs,
+	data_id:u32,
+	serialized_ids:&str,
+	ack:Option<u32>,
+) -> String {
+	let ack = ack.map(|id| id.to_string()).unwrap_or_else(|| "null".into());
+
+	format!(
+		"(function () {{ const fn = window['{event_emit_function_name}']; fn && fn({{event: \
+		 {event}}}, {serialized_ids}, {ack}, \
+		 window.__TAURI_INTERNALS__.invoke('{FETCH_CHANNEL_DATA_COMMAND}', null, {{ headers: {{ \
+		 '{CHANNEL_ID_HEADER_NAME}': '{data_id}' }} }}).then((response) => new \
+		 Uint8Array(response))) }})()",
+		event = emit_args.event,
+	)
+}
+
 pub fn unlisten_js_script(listeners_object_name:&str, event_name:&str, event_id:EventId) -> String {
 	format!(
 		"(function () {{
@@ -193,18 +476,56 @@ pub fn unlisten_js_script(listeners_object_name:&str, event_name:&str, event_id:
 
 pub fn event_initialization_script(function:&str, listeners:&str) -> String {
 	format!(
-		"Object.defineProperty(window, '{function}', {{
-      value: function (eventData, ids) {{
-        const listeners = (window['{listeners}'] && window['{listeners}'][eventData.event]) || []
+		"(function () {{
+      function deliver(eventData, ids, ack) {{
+        const all = window['{listeners}'] || {{}}
+        const name = eventData.event
+        // the listeners of the event and of the `prefix:*` patterns matching it
+        const listeners = [all[name] || {{}}]
+        for (let i = name.indexOf(':'); i !== -1; i = name.indexOf(':', i + 1)) {{
+          listeners.push(all[`${{name.slice(0, i + 1)}}*`] || {{}})
+        }}
+        const results = []
         for (const id of ids) {{
-          const listener = listeners[id]
+          const listener = listeners.map((l) => l[id]).find(Boolean)
           if (listener && listener.handler) {{
             eventData.id = id
-            listener.handler(eventData)
+            results.push(listener.handler(eventData))
           }}
         }}
+        // acknowledged once the listeners, including the async ones, are done
+        if (ack != null) {{
+          Promise.allSettled(results).then(() =>
+            window.__TAURI_INTERNALS__.invoke('{ACK_EVENT_COMMAND}', {{ id: ack }})
+          )
+        }}
       }}
-    }});
+      // the events waiting for a binary payload to be fetched, and the ones emitted after them
+      let pending = null
+      Object.defineProperty(window, '{function}', {{
+        value: function (eventData, ids, ack, binaryPayload) {{
+          if (!pending && !binaryPayload) {{
+            deliver(eventData, ids, ack)
+            return
+          }}
+          const queue = (pending || Promise.resolve())
+            .then(() => binaryPayload)
+            .then((payload) => {{
+              if (binaryPayload) {{
+                eventData.payload = payload
+              }}
+              deliver(eventData, ids, ack)
+            }})
+            .catch(console.error)
+          pending = queue
+          queue.then(() => {{
+            if (pending === queue) {{
+              pending = null
+            }}
+          }})
+        }}
+      }});
+    }})();
   "
 	)
 }
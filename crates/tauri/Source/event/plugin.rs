@@ -8,7 +8,7 @@ use serde::{Deserialize, Deserializer};
 use serde_json::Value as JsonValue;
 use tauri_runtime::window::is_label_valid;
 
-use super::{EventTarget, is_event_name_valid};
+use super::{EventTarget, PayloadFilter, is_event_name_valid, is_event_pattern_valid};
 use crate::{
 	AppHandle,
 	Emitter,
@@ -45,6 +45,32 @@ impl<'de> Deserialize<'de> for EventName {
 	}
 }
 
+/// An event name or a `prefix:*` pattern, see [`is_event_pattern_valid`].
+pub struct EventPattern(String);
+
+impl Deref for EventPattern {
+	type Target = str;
+
+	fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<'de> Deserialize<'de> for EventPattern {
+	fn deserialize<D>(deserializer:D) -> std::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>, {
+		let pattern = String::deserialize(deserializer)?;
+
+		if is_event_pattern_valid(&pattern) {
+			Ok(EventPattern(pattern))
+		} else {
+			Err(serde::de::Error::custom(
+				"Event name must include only alphanumeric characters, `-`, `/`, `:` and `_`, \
+				 optionally followed by a trailing `:*` wildcard.",
+			))
+		}
+	}
+}
+
 pub struct WebviewLabel(String);
 
 impl AsRef<str> for WebviewLabel {
@@ -70,17 +96,18 @@ impl<'de> Deserialize<'de> for WebviewLabel {
 #[command(root = "crate")]
 pub async fn listen<R:Runtime>(
 	webview:Webview<R>,
-	event:EventName,
+	event:EventPattern,
 	target:EventTarget,
 	handler:CallbackFn,
+	filter:Option<PayloadFilter>,
 ) -> Result<EventId> {
-	webview.listen_js(&event, target, handler)
+	webview.listen_js(&event, target, handler, filter)
 }
 
 #[command(root = "crate")]
 pub async fn unlisten<R:Runtime>(
 	webview:Webview<R>,
-	event:EventName,
+	event:EventPattern,
 	event_id:EventId,
 ) -> Result<()> {
 	webview.unlisten_js(&event, event_id)
@@ -105,9 +132,174 @@ pub async fn emit_to<R:Runtime>(
 	app.emit_to(target, &event, payload)
 }
 
+#[command(root = "crate")]
+pub async fn emit_to_many<R:Runtime>(
+	app:AppHandle<R>,
+	targets:Vec<EventTarget>,
+	event:EventName,
+	payload:Option<JsonValue>,
+) -> Result<usize> {
+	app.emit_to_many(targets, &event, payload)
+}
+
+/// Listens to the private events the `source` webview emits to the calling
+/// webview, which is always the receiving end.
+#[command(root = "crate")]
+pub async fn listen_private<R:Runtime>(
+	webview:Webview<R>,
+	source:WebviewLabel,
+	event:EventName,
+	handler:CallbackFn,
+) -> Result<EventId> {
+	webview.listen_private_js(source.as_ref(), &event, handler)
+}
+
+#[command(root = "crate")]
+pub async fn unlisten_private<R:Runtime>(
+	webview:Webview<R>,
+	event:EventName,
+	event_id:EventId,
+) -> Result<()> {
+	webview.unlisten_private_js(&event, event_id)
+}
+
+/// Emits a private event to the `target` webview.
+///
+/// Not part of the default permissions, since any webview allowed to call it
+/// can push events to the private listeners of every other webview: it must
+/// be granted with `core:event:allow-emit-private`.
+#[command(root = "crate")]
+pub async fn emit_private<R:Runtime>(
+	webview:Webview<R>,
+	target:WebviewLabel,
+	event:EventName,
+	payload:Option<JsonValue>,
+) -> Result<()> {
+	webview.emit_private(target.as_ref(), &event, payload)
+}
+
 /// Initializes the event plugin.
 pub(crate) fn init<R:Runtime>() -> TauriPlugin<R> {
 	Builder::new("event")
-		.invoke_handler(crate::generate_handler![listen, unlisten, emit, emit_to])
+		.invoke_handler(crate::generate_handler![
+			listen,
+			unlisten,
+			emit,
+			emit_to,
+			emit_to_many,
+			listen_private,
+			unlisten_private,
+			emit_private
+		])
 		.build()
 }
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{Arc, Mutex};
+
+	use tauri_utils::{
+		acl::{PermissionSet, manifest::Manifest},
+		platform::Target,
+	};
+
+	use crate::{
+		Manager,
+		WebviewWindowBuilder,
+		ipc::{CallbackFn, InvokeBody, RuntimeAuthority},
+		test::{
+			INVOKE_KEY,
+			get_ipc_response,
+			mock_builder,
+			mock_capability,
+			mock_context,
+			mock_manifest,
+			noop_assets,
+		},
+		webview::InvokeRequest,
+	};
+
+	/// The `core:event` manifest, with the default permissions of the build
+	/// script.
+	fn event_manifest() -> Manifest {
+		Manifest {
+			default_permission:Some(PermissionSet {
+				identifier:"default".into(),
+				description:"Default permissions for the plugin.".into(),
+				permissions:vec!["allow-emit".into(), "allow-listen-private".into()],
+			}),
+			..mock_manifest(&[
+				("allow-emit", "emit"),
+				("allow-listen-private", "listen_private"),
+				("allow-emit-private", "emit_private"),
+			])
+		}
+	}
+
+	#[test]
+	fn emit_private_requires_its_own_permission() {
+		let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+
+		let capabilities = [
+			(
+				"events".to_string(),
+				mock_capability("events", &["main", "popup", "receiver"], &["core:event:default"]),
+			),
+			(
+				"private".to_string(),
+				mock_capability("private", &["popup"], &["core:event:allow-emit-private"]),
+			),
+		]
+		.into_iter()
+		.collect();
+
+		*app.handle().manager.runtime_authority.lock().unwrap() = RuntimeAuthority::resolve(
+			[("core:event".to_string(), event_manifest())].into_iter().collect(),
+			capabilities,
+			Target::current(),
+		)
+		.unwrap();
+
+		for label in ["main", "popup", "receiver"] {
+			WebviewWindowBuilder::new(&app, label, Default::default()).build().unwrap();
+		}
+
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let receiver = app.get_webview("receiver").unwrap();
+
+		for source in ["main", "popup"] {
+			let received = received.clone();
+
+			receiver.listen_private(source, "token", move |event| {
+				received.lock().unwrap().push(event.payload().to_string());
+			});
+		}
+
+		let invoke = |webview:&str, command:&str| {
+			let request = InvokeRequest {
+				cmd:format!("plugin:event|{command}"),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(serde_json::json!({
+					"target": "receiver",
+					"event": "token",
+					"payload": webview,
+				})),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			};
+
+			get_ipc_response(&app.get_webview_window(webview).unwrap(), request)
+		};
+
+		// the default permissions only allow broadcasting the event
+		assert!(invoke("main", "emit").is_ok());
+		assert!(invoke("main", "emit_private").is_err());
+
+		assert!(invoke("popup", "emit_private").is_ok());
+
+		assert_eq!(*received.lock().unwrap(), [r#""popup""#]);
+	}
+}
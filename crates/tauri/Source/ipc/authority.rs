@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-	collections::BTreeMap,
+	collections::{BTreeMap, HashSet},
 	fmt::{Debug, Display},
+	path::Path,
 	sync::Arc,
 };
 
@@ -238,6 +239,72 @@ impl RuntimeAuthority {
 		}
 	}
 
+	/// Resolves the runtime authority from the given ACL manifests and
+	/// capabilities, without running the application.
+	///
+	/// This uses the same resolution as the application runtime, so it can be
+	/// used to test that capabilities grant the expected commands with
+	/// [`Self::is_command_allowed`].
+	///
+	/// Note that all the given capabilities are enabled, regardless of the
+	/// `app > security > capabilities` configuration.
+	pub fn resolve(
+		acl:BTreeMap<String, Manifest>,
+		capabilities:BTreeMap<String, Capability>,
+		target:Target,
+	) -> crate::Result<Self> {
+		let resolved = Resolved::resolve(&acl, capabilities, target)?;
+
+		Ok(Self::new(acl, resolved))
+	}
+
+	/// Resolves the runtime authority from the ACL manifests and capabilities
+	/// files generated by `tauri-build`, usually
+	/// `gen/schemas/acl-manifests.json` and `gen/schemas/capabilities.json`.
+	///
+	/// See [`Self::resolve`] for more information.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use tauri::{ipc::{Origin, RuntimeAuthority}, utils::platform::Target};
+	///
+	/// #[test]
+	/// fn settings_cannot_use_shell() {
+	///   let authority = RuntimeAuthority::load(
+	///     "gen/schemas/acl-manifests.json",
+	///     "gen/schemas/capabilities.json",
+	///     Target::current(),
+	///   )
+	///   .unwrap();
+	///
+	///   assert!(!authority.is_command_allowed("settings", &Origin::Local, "plugin:shell|execute"));
+	/// }
+	/// ```
+	pub fn load<P:AsRef<Path>, C:AsRef<Path>>(
+		acl_manifests_path:P,
+		capabilities_path:C,
+		target:Target,
+	) -> crate::Result<Self> {
+		let acl = serde_json::from_str(&std::fs::read_to_string(acl_manifests_path)?)?;
+
+		let capabilities = serde_json::from_str(&std::fs::read_to_string(capabilities_path)?)?;
+
+		Self::resolve(acl, capabilities, target)
+	}
+
+	/// Whether the given command can be called from the webview window with the
+	/// given label and origin.
+	///
+	/// Plugin commands are in the `plugin:$plugin|$command` format and core
+	/// plugin commands omit the `core:` prefix, e.g. `plugin:window|close`.
+	///
+	/// To check a webview that is not the main webview of its window, use
+	/// [`Self::resolve_access`].
+	pub fn is_command_allowed(&self, window:&str, origin:&Origin, command:&str) -> bool {
+		self.resolve_access(command, window, window, origin).is_some()
+	}
+
 	pub(crate) fn has_app_manifest(&self) -> bool { self.acl.contains_key(APP_ACL_KEY) }
 
 	#[doc(hidden)]
@@ -535,6 +602,49 @@ impl RuntimeAuthority {
 		}
 	}
 
+	/// Checks if the given permission identifier or command name is granted for
+	/// the given window, webview and origin.
+	///
+	/// Command names use the same format as the IPC request (e.g.
+	/// `plugin:fs|write_file`, or the bare command name for app commands) while
+	/// permission identifiers use the capability format (e.g.
+	/// `fs:allow-write-file`, `core:app:default`). A permission is considered
+	/// granted when every command it allows can be invoked.
+	///
+	/// This uses the same resolved ACL and origin matching as
+	/// [`Self::resolve_access`] so the result matches the actual IPC behavior.
+	pub fn is_granted(&self, identifier:&str, window:&str, webview:&str, origin:&Origin) -> bool {
+		if identifier.starts_with("plugin:") || identifier.contains('|') {
+			return self.resolve_access(identifier, window, webview, origin).is_some();
+		}
+
+		let (key, permission) = match identifier.rsplit_once(':') {
+			Some((key, permission)) => (key, permission),
+			None => (APP_ACL_KEY, identifier),
+		};
+
+		let mut commands = Vec::new();
+
+		let is_permission = self
+			.acl
+			.get(key)
+			.is_some_and(|manifest| {
+				collect_allowed_commands(manifest, permission, &mut commands, &mut HashSet::new())
+			});
+
+		if !is_permission {
+			// not a known permission, fallback to an app command name
+			return key == APP_ACL_KEY
+				&& self.resolve_access(identifier, window, webview, origin).is_some();
+		}
+
+		!commands.is_empty()
+			&& commands.iter().all(|command| {
+				self.resolve_access(&acl_command_name(key, command), window, webview, origin)
+					.is_some()
+			})
+	}
+
 	/// Checks if the given IPC execution is allowed and returns the
 	/// [`ResolvedCommand`] if it is.
 	pub fn resolve_access(
@@ -569,6 +679,49 @@ impl RuntimeAuthority {
 	}
 }
 
+/// Formats a manifest command as the command name used by the IPC.
+fn acl_command_name(key:&str, command:&str) -> String {
+	if key == APP_ACL_KEY {
+		command.to_string()
+	} else if let Some(core_plugin_name) = key.strip_prefix("core:") {
+		format!("plugin:{core_plugin_name}|{command}")
+	} else {
+		format!("plugin:{key}|{command}")
+	}
+}
+
+/// Collects the commands allowed by the given permission or permission set,
+/// returning `false` if the manifest does not define it.
+///
+/// The sets already expanded are skipped, so sets including each other don't
+/// recurse forever.
+fn collect_allowed_commands<'a>(
+	manifest:&'a Manifest,
+	permission:&'a str,
+	commands:&mut Vec<String>,
+	visited:&mut HashSet<&'a str>,
+) -> bool {
+	let set = if permission == "default" {
+		manifest.default_permission.as_ref()
+	} else {
+		manifest.permission_sets.get(permission)
+	};
+
+	if let Some(set) = set {
+		if visited.insert(permission) {
+			for permission in &set.permissions {
+				collect_allowed_commands(manifest, permission, commands, visited);
+			}
+		}
+		true
+	} else if let Some(permission) = manifest.permissions.get(permission) {
+		commands.extend(permission.commands.allow.iter().cloned());
+		true
+	} else {
+		false
+	}
+}
+
 /// List of allowed and denied objects that match either the command-specific or
 /// plugin global scope criteria.
 #[derive(Debug)]
@@ -906,7 +1059,10 @@ mod tests {
 	};
 
 	use super::RuntimeAuthority;
-	use crate::ipc::Origin;
+	use crate::{
+		ipc::Origin,
+		test::{mock_capability, mock_manifest},
+	};
 
 	#[test]
 	fn window_glob_pattern_matches() {
@@ -1103,6 +1259,120 @@ mod tests {
 		assert!(authority.resolve_access(command, window, webview, &Origin::Local).is_none());
 	}
 
+	#[test]
+	fn permission_granted() {
+		let window = "main";
+
+		let webview = "main";
+
+		let resolved_cmd = vec![ResolvedCommand {
+			windows:vec![Pattern::new(window).unwrap()],
+			..Default::default()
+		}];
+
+		let allowed_commands = [("plugin:fs|write_file".to_string(), resolved_cmd)]
+			.into_iter()
+			.collect();
+
+		let authority = RuntimeAuthority::new(
+			[(
+				"fs".to_string(),
+				mock_manifest(&[
+					("allow-write-file", "write_file"),
+					("allow-read-file", "read_file"),
+				]),
+			)]
+			.into_iter()
+			.collect(),
+			Resolved { allowed_commands, ..Default::default() },
+		);
+
+		assert!(authority.is_granted("plugin:fs|write_file", window, webview, &Origin::Local));
+		assert!(authority.is_granted("fs:allow-write-file", window, webview, &Origin::Local));
+		assert!(!authority.is_granted("fs:allow-read-file", window, webview, &Origin::Local));
+		assert!(!authority.is_granted("fs:unknown", window, webview, &Origin::Local));
+		assert!(!authority.is_granted("fs:allow-write-file", "other", "other", &Origin::Local));
+		assert!(
+			!authority.is_granted(
+				"fs:allow-write-file",
+				window,
+				webview,
+				&Origin::Remote { url:"https://tauri.app".parse().unwrap() }
+			)
+		);
+	}
+
+	#[test]
+	fn cyclic_permission_sets_are_granted() {
+		use tauri_utils::acl::PermissionSet;
+
+		let window = "main";
+
+		let webview = "main";
+
+		let resolved_cmd = vec![ResolvedCommand {
+			windows:vec![Pattern::new(window).unwrap()],
+			..Default::default()
+		}];
+
+		let allowed_commands = [("plugin:fs|write_file".to_string(), resolved_cmd)]
+			.into_iter()
+			.collect();
+
+		let mut manifest = mock_manifest(&[("allow-write-file", "write_file")]);
+
+		for (identifier, permissions) in
+			[("write", ["allow-write-file", "write-all"]), ("write-all", ["write", "allow-write-file"])]
+		{
+			manifest.permission_sets.insert(
+				identifier.to_string(),
+				PermissionSet {
+					identifier:identifier.to_string(),
+					description:String::new(),
+					permissions:permissions.iter().map(ToString::to_string).collect(),
+				},
+			);
+		}
+
+		let authority = RuntimeAuthority::new(
+			[("fs".to_string(), manifest)].into_iter().collect(),
+			Resolved { allowed_commands, ..Default::default() },
+		);
+
+		assert!(authority.is_granted("fs:write", window, webview, &Origin::Local));
+		assert!(authority.is_granted("fs:write-all", window, webview, &Origin::Local));
+		assert!(!authority.is_granted("fs:write", "other", "other", &Origin::Local));
+	}
+
+	#[test]
+	fn resolve_capabilities() {
+		use tauri_utils::platform::Target;
+
+		let acl = [("fs".to_string(), mock_manifest(&[("allow-read-file", "read_file")]))]
+			.into_iter()
+			.collect();
+
+		let capabilities = [
+			("main".to_string(), mock_capability("main", &["main"], &["fs:allow-read-file"])),
+			("settings".to_string(), mock_capability("settings", &["settings"], &[])),
+		]
+		.into_iter()
+		.collect();
+
+		let authority = RuntimeAuthority::resolve(acl, capabilities, Target::current()).unwrap();
+
+		assert!(authority.is_command_allowed("main", &Origin::Local, "plugin:fs|read_file"));
+		assert!(!authority.is_command_allowed("settings", &Origin::Local, "plugin:fs|read_file"));
+		assert!(!authority.is_command_allowed("main", &Origin::Local, "plugin:fs|write_file"));
+		assert!(
+			!authority.is_command_allowed(
+				"main",
+				&Origin::Remote { url:"https://tauri.app".parse().unwrap() },
+				"plugin:fs|read_file"
+			)
+		);
+	}
+
 	#[cfg(debug_assertions)]
 	#[test]
 	fn resolve_access_message() {
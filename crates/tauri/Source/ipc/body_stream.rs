@@ -0,0 +1,521 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streaming command request bodies.
+
+use std::{
+	collections::HashMap,
+	pin::Pin,
+	sync::{Arc, Mutex, Weak},
+	task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_util::Stream;
+use tokio::sync::mpsc;
+
+use super::{CommandArg, CommandItem, Invoke, InvokeBody, InvokeError};
+use crate::{
+	Manager,
+	Runtime,
+	State,
+	Webview,
+	command,
+	plugin::{Builder as PluginBuilder, TauriPlugin},
+	webview::PageLoadEvent,
+};
+
+/// The header the JavaScript `invoke` function uses to send the ID of the
+/// request body stream when it is given a `ReadableStream` or a `Blob`.
+pub(crate) const REQUEST_STREAM_HEADER_NAME:&str = "Tauri-Request-Stream";
+const REQUEST_STREAM_PLUGIN_NAME:&str = "request-stream";
+
+/// How many chunks are buffered before the JavaScript side waits for the
+/// command to read them.
+const REQUEST_STREAM_CAPACITY:usize = 4;
+
+/// How many request body streams a webview can have open at once.
+const MAX_REQUEST_STREAMS:usize = 16;
+
+/// A message sent by the JavaScript side of a request body stream.
+enum Frame {
+	Chunk(Bytes),
+	End,
+	Abort(String),
+}
+
+/// The request body of an invoke made with a `ReadableStream` or a `Blob`,
+/// received in chunks as the JavaScript side reads them.
+///
+/// The JavaScript side only sends the next chunk once the previous ones are
+/// read, so a command that stops polling the stream pauses the upload.
+/// Dropping the stream stops the upload.
+///
+/// The stream yields an error when the JavaScript stream errors, when the
+/// invoke is aborted or when the webview navigates away before the body is
+/// fully sent.
+///
+/// The invoke body is the stream, so the command cannot take other arguments
+/// from the invoke payload.
+///
+/// Sending the chunks requires the `core:request-stream:default` permission,
+/// which is part of `core:default`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+/// use tauri::ipc::RequestBodyStream;
+/// use tokio::io::AsyncWriteExt;
+///
+/// #[tauri::command]
+/// async fn upload(mut body:RequestBodyStream) -> Result<u64, String> {
+/// 	let mut file = tokio::fs::File::create("upload.bin").await.map_err(|e| e.to_string())?;
+/// 	let mut written = 0;
+///
+/// 	while let Some(chunk) = body.next().await {
+/// 		let chunk = chunk.map_err(|e| e.to_string())?;
+/// 		file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+/// 		written += chunk.len() as u64;
+/// 	}
+///
+/// 	Ok(written)
+/// }
+/// ```
+///
+/// ```javascript
+/// import { invoke } from '@tauri-apps/api/core'
+/// const file = document.querySelector('input[type=file]').files[0]
+/// const written = await invoke('upload', file)
+/// ```
+pub struct RequestBodyStream {
+	receiver:Option<mpsc::Receiver<Frame>>,
+}
+
+impl Stream for RequestBodyStream {
+	type Item = crate::Result<Bytes>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx:&mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let Some(receiver) = self.receiver.as_mut() else {
+			return Poll::Ready(None);
+		};
+
+		let item = match receiver.poll_recv(cx) {
+			Poll::Pending => return Poll::Pending,
+			Poll::Ready(Some(Frame::Chunk(chunk))) => return Poll::Ready(Some(Ok(chunk))),
+			Poll::Ready(Some(Frame::End)) => None,
+			Poll::Ready(Some(Frame::Abort(reason))) => {
+				Some(Err(crate::Error::RequestBodyAborted(reason)))
+			},
+			// the registry dropped the sender without an end frame
+			Poll::Ready(None) => {
+				Some(Err(crate::Error::RequestBodyAborted(
+					"the webview closed the stream before it ended".into(),
+				)))
+			},
+		};
+
+		self.receiver.take();
+
+		Poll::Ready(item)
+	}
+}
+
+impl<'de, R:Runtime> CommandArg<'de, R> for RequestBodyStream {
+	/// Takes the request body stream of the invoke.
+	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
+		let id = stream_id(command.message.headers()).ok_or_else(|| {
+			InvokeError::from(format!(
+				"command `{}` reads a request body stream but the `{REQUEST_STREAM_HEADER_NAME}` header \
+				 is missing, invoke it with a `ReadableStream` or a `Blob`",
+				command.name
+			))
+		})?;
+
+		let webview = command.message.webview_ref();
+
+		webview
+			.state::<RequestStreamRegistry>()
+			.take_receiver(webview.label(), id)
+			.map(|receiver| Self { receiver:Some(receiver) })
+			.ok_or_else(|| InvokeError::from("the request body stream was already taken or closed"))
+	}
+}
+
+struct Slot {
+	sender:Option<mpsc::Sender<Frame>>,
+	receiver:Option<mpsc::Receiver<Frame>>,
+	/// Identifies the invoke that opened the slot.
+	owner:Arc<()>,
+}
+
+/// Request body streams, keyed by webview label and stream ID.
+///
+/// The slot of a stream is opened by the invoke that reads it, so the chunks
+/// can only be sent to the streams of running invokes.
+#[derive(Default)]
+pub(crate) struct RequestStreamRegistry(Arc<Mutex<HashMap<(String, u32), Slot>>>);
+
+/// Keeps the request body stream of an invoke open, removing it when the
+/// invoke settles or is dropped.
+pub(crate) struct OpenRequestStream {
+	streams:Weak<Mutex<HashMap<(String, u32), Slot>>>,
+	key:(String, u32),
+	owner:Arc<()>,
+}
+
+impl Drop for OpenRequestStream {
+	fn drop(&mut self) {
+		let Some(streams) = self.streams.upgrade() else {
+			return;
+		};
+
+		let mut streams = streams.lock().unwrap();
+
+		// the webview may have reloaded and reused the ID
+		if streams.get(&self.key).is_some_and(|slot| Arc::ptr_eq(&slot.owner, &self.owner)) {
+			streams.remove(&self.key);
+		}
+	}
+}
+
+impl RequestStreamRegistry {
+	/// Opens the request body stream of an invoke made with the
+	/// [`REQUEST_STREAM_HEADER_NAME`] header, returning `None` if the header is
+	/// missing.
+	pub(crate) fn open(
+		&self,
+		webview:&str,
+		headers:&http::HeaderMap,
+	) -> Result<Option<OpenRequestStream>, String> {
+		if !headers.contains_key(REQUEST_STREAM_HEADER_NAME) {
+			return Ok(None);
+		}
+
+		let id = stream_id(headers)
+			.ok_or_else(|| format!("the `{REQUEST_STREAM_HEADER_NAME}` header must be a u32"))?;
+
+		let mut streams = self.0.lock().unwrap();
+
+		let key = (webview.to_string(), id);
+
+		if streams.contains_key(&key) {
+			return Err(format!("the request body stream {id} is already open"));
+		}
+
+		if streams.keys().filter(|(label, _)| label == webview).count() >= MAX_REQUEST_STREAMS {
+			return Err(format!(
+				"the webview already has {MAX_REQUEST_STREAMS} open request body streams"
+			));
+		}
+
+		let (sender, receiver) = mpsc::channel(REQUEST_STREAM_CAPACITY);
+
+		let owner = Arc::new(());
+
+		streams.insert(
+			key.clone(),
+			Slot { sender:Some(sender), receiver:Some(receiver), owner:owner.clone() },
+		);
+
+		Ok(Some(OpenRequestStream { streams:Arc::downgrade(&self.0), key, owner }))
+	}
+
+	fn sender(&self, webview:&str, id:u32) -> Result<mpsc::Sender<Frame>, InvokeError> {
+		self
+			.0
+			.lock()
+			.unwrap()
+			.get(&(webview.to_string(), id))
+			.ok_or_else(|| InvokeError::from(format!("unknown request body stream {id}")))?
+			.sender
+			.clone()
+			.ok_or_else(|| InvokeError::from("the request body stream already ended"))
+	}
+
+	fn take_receiver(&self, webview:&str, id:u32) -> Option<mpsc::Receiver<Frame>> {
+		self.0.lock().unwrap().get_mut(&(webview.to_string(), id))?.receiver.take()
+	}
+
+	/// Drops the sender of the stream, the receiver stays until the command
+	/// takes it.
+	fn close(&self, webview:&str, id:u32) {
+		if let Some(slot) = self.0.lock().unwrap().get_mut(&(webview.to_string(), id)) {
+			slot.sender.take();
+		}
+	}
+
+	/// Errors the stream without waiting for the command to read the pending
+	/// chunks, the stream errors even if the reason does not fit.
+	fn abort(&self, webview:&str, id:u32, reason:String) {
+		if let Ok(sender) = self.sender(webview, id) {
+			let _ = sender.try_send(Frame::Abort(reason));
+		}
+
+		self.close(webview, id);
+	}
+
+	/// Drops every stream of the given webview, erroring the commands reading
+	/// them.
+	pub(crate) fn close_webview(&self, webview:&str) {
+		self.0.lock().unwrap().retain(|(label, _), _| label != webview);
+	}
+}
+
+/// The ID of the request body stream of an invoke.
+fn stream_id(headers:&http::HeaderMap) -> Option<u32> {
+	headers
+		.get(REQUEST_STREAM_HEADER_NAME)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|id| id.parse().ok())
+}
+
+/// Sends a chunk to the command, resolving once there's room for it.
+///
+/// Takes the invoke to move its body into the chunk instead of copying it.
+fn push_request_chunk<R:Runtime>(invoke:Invoke<R>) {
+	let Invoke { message, resolver, .. } = invoke;
+
+	let chunk = match message.payload {
+		InvokeBody::Raw(bytes) => Bytes::from(bytes),
+		InvokeBody::Json(_) => return resolver.reject("the request body chunk must be raw bytes"),
+	};
+
+	let sender = stream_id(&message.headers)
+		.ok_or_else(|| {
+			InvokeError::from(format!("missing the `{REQUEST_STREAM_HEADER_NAME}` header"))
+		})
+		.and_then(|id| {
+			message.webview.state::<RequestStreamRegistry>().sender(message.webview.label(), id)
+		});
+
+	resolver.respond_async(async move {
+		sender?
+			.send(Frame::Chunk(chunk))
+			.await
+			.map_err(|_| InvokeError::from("the command stopped reading the request body"))
+	});
+}
+
+/// Ends the stream, erroring it if `error` is set.
+#[command(root = "crate")]
+async fn end_request_stream<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, RequestStreamRegistry>,
+	id:u32,
+	error:Option<String>,
+) -> Result<(), InvokeError> {
+	match error {
+		Some(reason) => registry.abort(webview.label(), id, reason),
+		None => {
+			// the command already dropped the stream
+			let _ = registry.sender(webview.label(), id)?.send(Frame::End).await;
+
+			registry.close(webview.label(), id);
+		},
+	}
+
+	Ok(())
+}
+
+/// The plugin sending the request body streams, the
+/// `core:request-stream:default` permission allows it.
+pub(crate) fn plugin<R:Runtime>() -> TauriPlugin<R> {
+	PluginBuilder::new(REQUEST_STREAM_PLUGIN_NAME)
+		.invoke_handler(|invoke| {
+			if invoke.message.command() == "push_request_chunk" {
+				push_request_chunk(invoke);
+
+				return true;
+			}
+
+			crate::generate_handler![end_request_stream](invoke)
+		})
+		.on_page_load(|webview, payload| {
+			if payload.event() == PageLoadEvent::Started {
+				webview.state::<RequestStreamRegistry>().close_webview(webview.label());
+			}
+		})
+		.build()
+}
+
+#[cfg(test)]
+mod tests {
+	use futures_util::StreamExt;
+
+	use super::*;
+
+	fn headers(id:u32) -> http::HeaderMap {
+		let mut headers = http::HeaderMap::new();
+
+		headers.insert(REQUEST_STREAM_HEADER_NAME, id.to_string().parse().unwrap());
+
+		headers
+	}
+
+	fn open(registry:&RequestStreamRegistry, id:u32) -> OpenRequestStream {
+		registry.open("main", &headers(id)).unwrap().unwrap()
+	}
+
+	fn stream(registry:&RequestStreamRegistry, id:u32) -> RequestBodyStream {
+		RequestBodyStream { receiver:registry.take_receiver("main", id) }
+	}
+
+	#[test]
+	fn chunks_sent_before_the_command_reads() {
+		let registry = RequestStreamRegistry::default();
+
+		let invoke = open(&registry, 1);
+
+		crate::async_runtime::block_on(async {
+			let sender = registry.sender("main", 1).unwrap();
+
+			sender.send(Frame::Chunk(Bytes::from_static(b"ab"))).await.unwrap();
+			sender.send(Frame::Chunk(Bytes::from_static(b"c"))).await.unwrap();
+			sender.send(Frame::End).await.unwrap();
+
+			registry.close("main", 1);
+
+			let chunks = stream(&registry, 1).collect::<Vec<_>>().await;
+
+			assert_eq!(chunks.len(), 2);
+			assert_eq!(chunks[0].as_ref().unwrap().as_ref(), b"ab");
+			assert_eq!(chunks[1].as_ref().unwrap().as_ref(), b"c");
+		});
+
+		drop(invoke);
+
+		assert!(registry.0.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn abort_errors_the_stream() {
+		let registry = RequestStreamRegistry::default();
+
+		let _invokes = (open(&registry, 1), open(&registry, 2));
+
+		crate::async_runtime::block_on(async {
+			let mut aborted = stream(&registry, 1);
+			let mut closed = stream(&registry, 2);
+
+			registry.abort("main", 1, "cancelled".into());
+
+			registry.close_webview("main");
+
+			assert!(matches!(
+				aborted.next().await,
+				Some(Err(crate::Error::RequestBodyAborted(reason))) if reason == "cancelled"
+			));
+			assert!(aborted.next().await.is_none());
+
+			assert!(matches!(closed.next().await, Some(Err(crate::Error::RequestBodyAborted(_)))));
+			assert!(closed.next().await.is_none());
+		});
+	}
+
+	#[test]
+	fn only_streams_opened_by_an_invoke_receive_chunks() {
+		let registry = RequestStreamRegistry::default();
+
+		assert!(registry.sender("main", 1).is_err());
+		assert!(registry.take_receiver("main", 1).is_none());
+
+		assert!(registry.open("main", &http::HeaderMap::new()).unwrap().is_none());
+
+		let mut invalid = http::HeaderMap::new();
+
+		invalid.insert(REQUEST_STREAM_HEADER_NAME, "-1".parse().unwrap());
+
+		assert!(registry.open("main", &invalid).is_err());
+
+		let invokes =
+			(1..=MAX_REQUEST_STREAMS as u32).map(|id| open(&registry, id)).collect::<Vec<_>>();
+
+		assert!(registry.open("main", &headers(1)).is_err());
+		assert!(registry.open("main", &headers(MAX_REQUEST_STREAMS as u32 + 1)).is_err());
+
+		// the limit is per webview
+		assert!(registry.open("other", &headers(1)).unwrap().is_some());
+
+		drop(invokes);
+
+		assert!(registry.open("main", &headers(MAX_REQUEST_STREAMS as u32 + 1)).unwrap().is_some());
+	}
+
+	#[test]
+	fn settled_invokes_release_the_waiting_chunks() {
+		let registry = RequestStreamRegistry::default();
+
+		let invoke = open(&registry, 1);
+
+		crate::async_runtime::block_on(async {
+			let sender = registry.sender("main", 1).unwrap();
+
+			for _ in 0..REQUEST_STREAM_CAPACITY {
+				sender.send(Frame::Chunk(Bytes::from_static(b"a"))).await.unwrap();
+			}
+
+			// the command never takes the stream, so this chunk waits for room
+			let waiting = sender.send(Frame::Chunk(Bytes::from_static(b"b")));
+
+			drop(invoke);
+
+			assert!(waiting.await.is_err());
+		});
+
+		assert!(registry.0.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn settled_invokes_keep_a_reused_id_open() {
+		let registry = RequestStreamRegistry::default();
+
+		let before_reload = open(&registry, 1);
+
+		registry.close_webview("main");
+
+		let _after_reload = open(&registry, 1);
+
+		drop(before_reload);
+
+		assert!(registry.sender("main", 1).is_ok());
+	}
+
+	#[command(root = "crate")]
+	fn ignore_body() {}
+
+	#[test]
+	fn invokes_that_never_read_their_stream_remove_it() {
+		use crate::{
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![ignore_body])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let response = get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:"ignore_body".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Raw(Vec::new()),
+				headers:headers(1),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		);
+
+		assert!(response.is_ok());
+
+		assert!(app.state::<RequestStreamRegistry>().0.lock().unwrap().is_empty());
+	}
+}
@@ -0,0 +1,320 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Cancellation of running invokes.
+
+use std::{
+	collections::HashMap,
+	future::Future,
+	sync::{Arc, Mutex, Weak},
+	time::Duration,
+};
+
+use futures_util::future::Either;
+use tokio::sync::watch;
+
+use super::{CommandArg, CommandItem, InvokeError, InvokeMessage, channel::CHANNEL_PLUGIN_NAME};
+use crate::{Manager, Runtime, State, Webview, command};
+
+/// The header the JavaScript `invoke` function uses to send the ID that
+/// identifies the invoke when it is given an `AbortSignal` or a timeout.
+pub(crate) const INVOKE_ID_HEADER_NAME:&str = "Tauri-Invoke-Id";
+pub const CANCEL_INVOKE_COMMAND:&str = "plugin:__TAURI_CHANNEL__|cancel_invoke";
+
+/// Notifies a command that its invoke was cancelled.
+///
+/// The token is triggered when the JavaScript caller aborts the invoke with
+/// the `signal` or `timeout` options, when the webview that made the invoke
+/// navigates away or is destroyed, and when the `timeout` of the
+/// [`command`](macro@crate::command) elapses.
+///
+/// Commands must check the token themselves, nothing is interrupted
+/// automatically, except for the async commands that declare a `timeout`,
+/// which are rejected with a `cancelled` error kind when it elapses.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::ipc::CancellationToken;
+///
+/// #[tauri::command(timeout = "30s")]
+/// async fn index(cancel:CancellationToken) -> Result<usize, String> {
+/// 	let mut indexed = 0;
+///
+/// 	while !cancel.is_cancelled() {
+/// 		tokio::select! {
+/// 			_ = cancel.cancelled() => break,
+/// 			_ = tokio::task::yield_now() => indexed += 1,
+/// 		}
+/// 	}
+///
+/// 	Ok(indexed)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken(Arc<watch::Sender<bool>>);
+
+impl Default for CancellationToken {
+	fn default() -> Self { Self(Arc::new(watch::Sender::new(false))) }
+}
+
+impl CancellationToken {
+	/// Whether the invoke was cancelled.
+	pub fn is_cancelled(&self) -> bool { *self.0.borrow() }
+
+	/// Waits until the invoke is cancelled.
+	pub async fn cancelled(&self) {
+		let mut receiver = self.0.subscribe();
+
+		// the sender is owned by the token, so the channel cannot close
+		let _ = receiver.wait_for(|cancelled| *cancelled).await;
+	}
+
+	pub(crate) fn cancel(&self) { self.0.send_replace(true); }
+
+	/// Runs the future until it completes or the invoke is cancelled.
+	pub(crate) async fn run<T, F>(self, task:F) -> Result<T, InvokeError>
+	where
+		F: Future<Output = Result<T, InvokeError>>, {
+		let task = std::pin::pin!(task);
+
+		let cancelled = std::pin::pin!(self.cancelled());
+
+		match futures_util::future::select(task, cancelled).await {
+			Either::Left((result, _)) => result,
+			Either::Right(_) => Err(cancelled_error()),
+		}
+	}
+}
+
+impl<'de, R:Runtime> CommandArg<'de, R> for CancellationToken {
+	/// Returns the cancellation token of the invoke. This will never fail.
+	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
+		Ok(command.message.cancellation.clone())
+	}
+}
+
+/// The code of the error the cancelled invokes are rejected with.
+const CANCELLED_ERROR_CODE:&str = "E_CANCELLED";
+
+/// The error of the invokes rejected because they were cancelled.
+pub(crate) fn cancelled_error() -> InvokeError {
+	InvokeError::envelope(
+		"the invoke was cancelled".into(),
+		Some(CANCELLED_ERROR_CODE.into()),
+		serde_json::Value::Null,
+		Vec::new(),
+	)
+}
+
+/// Cancels the invoke after the given duration, returning its token.
+pub(crate) fn cancel_after<R:Runtime>(
+	message:&InvokeMessage<R>,
+	duration:Duration,
+) -> CancellationToken {
+	let token = Arc::downgrade(&message.cancellation.0);
+
+	crate::async_runtime::spawn(async move {
+		tokio::time::sleep(duration).await;
+
+		if let Some(sender) = token.upgrade() {
+			CancellationToken(sender).cancel();
+		}
+	});
+
+	message.cancellation.clone()
+}
+
+type Registered = (Option<u32>, Weak<watch::Sender<bool>>);
+
+/// Tokens of the running invokes, keyed by webview label.
+#[derive(Default)]
+pub(crate) struct CancellationRegistry {
+	tokens:Mutex<HashMap<String, Vec<Registered>>>,
+	/// The number of invokes each webview is waiting for.
+	pending:Arc<Mutex<HashMap<String, usize>>>,
+}
+
+/// Counts an invoke as pending until it is dropped, see
+/// [`CancellationRegistry::start`].
+pub(crate) struct PendingInvoke {
+	pending:Arc<Mutex<HashMap<String, usize>>>,
+	webview:String,
+}
+
+impl Drop for PendingInvoke {
+	fn drop(&mut self) {
+		let mut pending = self.pending.lock().unwrap();
+
+		if let Some(count) = pending.get_mut(&self.webview) {
+			*count -= 1;
+
+			if *count == 0 {
+				pending.remove(&self.webview);
+			}
+		}
+	}
+}
+
+impl CancellationRegistry {
+	/// Registers the token of an invoke made by the given webview, along with
+	/// the ID the JavaScript side cancels it with.
+	pub(crate) fn register(&self, webview:&str, id:Option<u32>, token:&CancellationToken) {
+		let mut tokens = self.tokens.lock().unwrap();
+
+		let tokens = tokens.entry(webview.to_string()).or_default();
+
+		// the tokens of the settled invokes are gone
+		tokens.retain(|(_, token)| token.strong_count() > 0);
+
+		tokens.push((id, Arc::downgrade(&token.0)));
+	}
+
+	/// Counts the invoke of `command` made by the given webview as pending
+	/// until the returned guard is dropped, along with the responder of the
+	/// invoke.
+	///
+	/// The commands of the IPC internals, acknowledging events or pulling
+	/// stream chunks for instance, are not counted.
+	pub(crate) fn start(&self, webview:&str, command:&str) -> Option<PendingInvoke> {
+		if command
+			.strip_prefix("plugin:")
+			.and_then(|command| command.split_once('|'))
+			.is_some_and(|(plugin, _)| plugin == CHANNEL_PLUGIN_NAME)
+		{
+			return None;
+		}
+
+		*self.pending.lock().unwrap().entry(webview.to_string()).or_default() += 1;
+
+		Some(PendingInvoke { pending:self.pending.clone(), webview:webview.to_string() })
+	}
+
+	fn cancel(&self, webview:&str, id:u32) {
+		if let Some(tokens) = self.tokens.lock().unwrap().get_mut(webview) {
+			tokens.retain(|(token_id, token)| {
+				if *token_id != Some(id) {
+					return true;
+				}
+
+				if let Some(sender) = token.upgrade() {
+					CancellationToken(sender).cancel();
+				}
+
+				false
+			});
+		}
+	}
+
+	/// The number of invokes that were not answered yet, keyed by the label of
+	/// the webview that made them.
+	pub(crate) fn pending(&self) -> HashMap<String, usize> { self.pending.lock().unwrap().clone() }
+
+	/// Cancels every invoke that was made by the given webview.
+	pub(crate) fn cancel_webview(&self, webview:&str) {
+		if let Some(tokens) = self.tokens.lock().unwrap().remove(webview) {
+			for (_, token) in tokens {
+				if let Some(sender) = token.upgrade() {
+					CancellationToken(sender).cancel();
+				}
+			}
+		}
+	}
+}
+
+/// The ID sent by the JavaScript side to cancel the invoke.
+pub(crate) fn invoke_id(headers:&http::HeaderMap) -> Option<u32> {
+	headers
+		.get(INVOKE_ID_HEADER_NAME)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|id| id.parse().ok())
+}
+
+#[command(root = "crate")]
+pub(crate) fn cancel_invoke<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, CancellationRegistry>,
+	id:u32,
+) {
+	registry.cancel(webview.label(), id);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cancel_registered_invokes() {
+		let registry = CancellationRegistry::default();
+
+		let aborted = CancellationToken::default();
+		let other = CancellationToken::default();
+		let destroyed = CancellationToken::default();
+
+		registry.register("main", Some(1), &aborted);
+		registry.register("main", Some(2), &other);
+		registry.register("popup", None, &destroyed);
+
+		registry.cancel("main", 1);
+		registry.cancel("popup", 1);
+
+		assert!(aborted.is_cancelled());
+		assert!(!other.is_cancelled());
+		assert!(!destroyed.is_cancelled());
+
+		registry.cancel_webview("popup");
+
+		assert!(destroyed.is_cancelled());
+		assert!(!other.is_cancelled());
+	}
+
+	#[test]
+	fn pending_invokes_are_counted_until_answered() {
+		let registry = CancellationRegistry::default();
+
+		let first = registry.start("main", "export");
+
+		let second = registry.start("main", "plugin:dialog|open");
+
+		let popup = registry.start("popup", "export");
+
+		assert!(registry.start("main", crate::ipc::channel::ACK_EVENT_COMMAND).is_none());
+
+		assert!(
+			registry
+				.start("main", crate::ipc::response_stream::PULL_RESPONSE_CHUNK_COMMAND)
+				.is_none()
+		);
+
+		assert_eq!(registry.pending(), HashMap::from([("main".into(), 2), ("popup".into(), 1)]));
+
+		// the tokens cloned by the commands don't keep their invokes pending
+		let token = CancellationToken::default();
+
+		registry.register("main", None, &token);
+
+		let _clone = token.clone();
+
+		drop((first, popup));
+
+		assert_eq!(registry.pending(), HashMap::from([("main".into(), 1)]));
+
+		drop(second);
+
+		assert!(registry.pending().is_empty());
+	}
+
+	#[test]
+	fn run_until_cancelled() {
+		let token = CancellationToken::default();
+
+		token.cancel();
+
+		let result = crate::async_runtime::block_on(
+			token.run(futures_util::future::pending::<Result<(), InvokeError>>()),
+		);
+
+		assert_eq!(result.unwrap_err().code(), Some(CANCELLED_ERROR_CODE));
+	}
+}
@@ -4,17 +4,31 @@
 
 use std::{
 	collections::HashMap,
+	future::Future,
 	str::FromStr,
 	sync::{
 		Arc,
 		Mutex,
-		atomic::{AtomicU32, AtomicUsize, Ordering},
+		Weak,
+		atomic::{AtomicU32, Ordering},
 	},
 };
 
+use bytes::Bytes;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-
-use super::{CallbackFn, InvokeError, InvokeResponseBody, IpcResponse, Request, Response};
+use tokio::sync::oneshot;
+
+use super::{
+	CallbackFn,
+	InvokeError,
+	InvokeResponseBody,
+	IpcResponse,
+	Request,
+	Response,
+	cancel::CancellationRegistry,
+	response_stream::ResponseStreamRegistry,
+	stream::StreamRegistry,
+};
 use crate::{
 	Manager,
 	Runtime,
@@ -23,11 +37,16 @@ use crate::{
 	command,
 	ipc::{CommandArg, CommandItem},
 	plugin::{Builder as PluginBuilder, TauriPlugin},
+	sealed::ManagerBase,
+	webview::PageLoadEvent,
 };
 
 pub const IPC_PAYLOAD_PREFIX:&str = "__CHANNEL__:";
 pub const CHANNEL_PLUGIN_NAME:&str = "__TAURI_CHANNEL__";
 pub const FETCH_CHANNEL_DATA_COMMAND:&str = "plugin:__TAURI_CHANNEL__|fetch";
+pub const ACK_CHANNEL_MESSAGE_COMMAND:&str = "plugin:__TAURI_CHANNEL__|ack";
+pub const CLOSE_CHANNEL_COMMAND:&str = "plugin:__TAURI_CHANNEL__|close";
+pub const ACK_EVENT_COMMAND:&str = "plugin:__TAURI_CHANNEL__|ack_event";
 pub(crate) const CHANNEL_ID_HEADER_NAME:&str = "Tauri-Channel-Id";
 
 static CHANNEL_COUNTER:AtomicU32 = AtomicU32::new(0);
@@ -36,13 +55,164 @@ static CHANNEL_DATA_COUNTER:AtomicU32 = AtomicU32::new(0);
 /// Maps a channel id to a pending data that must be send to the JavaScript side
 /// via the IPC.
 #[derive(Default, Clone)]
-pub struct ChannelDataIpcQueue(pub(crate) Arc<Mutex<HashMap<u32, InvokeResponseBody>>>);
+pub struct ChannelDataIpcQueue(pub(crate) Arc<Mutex<HashMap<u32, QueuedData>>>);
+
+/// Data waiting to be fetched by a webview.
+pub(crate) enum QueuedData {
+	Body(InvokeResponseBody),
+	/// Bytes shared by several webviews, copied into the response of each one
+	/// when it fetches them.
+	Shared(Bytes),
+}
+
+impl From<InvokeResponseBody> for QueuedData {
+	fn from(body:InvokeResponseBody) -> Self { Self::Body(body) }
+}
+
+impl From<Bytes> for QueuedData {
+	fn from(bytes:Bytes) -> Self { Self::Shared(bytes) }
+}
+
+impl From<QueuedData> for InvokeResponseBody {
+	fn from(data:QueuedData) -> Self {
+		match data {
+			QueuedData::Body(body) => body,
+			QueuedData::Shared(bytes) => Self::Raw(bytes.to_vec()),
+		}
+	}
+}
+
+/// Queues `data` to be fetched by `webview` with [`FETCH_CHANNEL_DATA_COMMAND`],
+/// returning the id to fetch it with.
+pub(crate) fn queue_data<R:Runtime>(webview:&Webview<R>, data:impl Into<QueuedData>) -> u32 {
+	let data_id = CHANNEL_DATA_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+	webview.state::<ChannelDataIpcQueue>().0.lock().unwrap().insert(data_id, data.into());
+
+	data_id
+}
+
+/// Resolves when a message is acknowledged by the webview.
+type AckSender = oneshot::Sender<crate::Result<()>>;
+
+#[derive(Default)]
+struct AckState {
+	next_index:usize,
+	capacity:Option<usize>,
+	pending:HashMap<usize, Option<AckSender>>,
+	closed:bool,
+}
+
+/// Indexes the messages sent to a channel defined on the JavaScript side,
+/// tracking the ones waiting to be acknowledged by the webview.
+#[derive(Default)]
+pub(crate) struct ChannelAcks(Mutex<AckState>);
+
+impl ChannelAcks {
+	/// Reserves the index of the next message, which must be acknowledged if
+	/// there is a `waiter` or the channel has a capacity.
+	fn reserve(&self, waiter:Option<AckSender>) -> crate::Result<(usize, bool)> {
+		let mut state = self.0.lock().unwrap();
+
+		if state.closed {
+			return Err(crate::Error::ChannelClosed);
+		}
+
+		let tracked = waiter.is_some() || state.capacity.is_some();
+
+		if state.capacity.is_some_and(|capacity| state.pending.len() >= capacity) {
+			return Err(crate::Error::ChannelFull);
+		}
+
+		let index = state.next_index;
+
+		state.next_index += 1;
+
+		if tracked {
+			state.pending.insert(index, waiter);
+		}
+
+		Ok((index, tracked))
+	}
+
+	fn ack(&self, index:usize) {
+		if let Some(Some(waiter)) = self.0.lock().unwrap().pending.remove(&index) {
+			let _ = waiter.send(Ok(()));
+		}
+	}
+
+	/// Fails the pending acknowledgements and the next messages.
+	fn close(&self) {
+		let mut state = self.0.lock().unwrap();
+
+		state.closed = true;
+
+		for waiter in state.pending.drain().filter_map(|(_, waiter)| waiter) {
+			let _ = waiter.send(Err(crate::Error::ChannelClosed));
+		}
+	}
+}
+
+/// The acknowledgement state of the JavaScript channels, keyed by webview
+/// label and channel ID.
+#[derive(Default)]
+pub(crate) struct ChannelAckRegistry(Mutex<HashMap<(String, u32), Weak<ChannelAcks>>>);
+
+impl ChannelAckRegistry {
+	/// The state of the given channel, shared by all its Rust handles.
+	fn get_or_insert(&self, webview:&str, id:u32) -> Arc<ChannelAcks> {
+		let mut channels = self.0.lock().unwrap();
+
+		channels.retain(|_, acks| acks.strong_count() > 0);
+
+		let key = (webview.to_string(), id);
+
+		if let Some(acks) = channels.get(&key).and_then(Weak::upgrade) {
+			return acks;
+		}
+
+		let acks = Arc::new(ChannelAcks::default());
+
+		channels.insert(key, Arc::downgrade(&acks));
+
+		acks
+	}
+
+	fn get(&self, webview:&str, id:u32) -> Option<Arc<ChannelAcks>> {
+		self.0.lock().unwrap().get(&(webview.to_string(), id)).and_then(Weak::upgrade)
+	}
+
+	/// Closes every channel of the given webview.
+	pub(crate) fn close_webview(&self, webview:&str) {
+		self.0.lock().unwrap().retain(|(label, _), acks| {
+			if label != webview {
+				return true;
+			}
+
+			if let Some(acks) = acks.upgrade() {
+				acks.close();
+			}
+
+			false
+		});
+	}
+}
+
+/// Delivers a message with its index, asking the webview to acknowledge it.
+type Deliver = dyn Fn(InvokeResponseBody, usize, bool) -> crate::Result<()> + Send + Sync;
+
+/// A channel defined on the JavaScript side.
+struct JsChannel {
+	sink:Arc<Deliver>,
+	acks:Arc<ChannelAcks>,
+}
 
 /// An IPC channel.
 #[derive(Clone)]
 pub struct Channel<TSend = InvokeResponseBody> {
 	id:u32,
 	on_message:Arc<dyn Fn(InvokeResponseBody) -> crate::Result<()> + Send + Sync>,
+	js:Option<Arc<JsChannel>>,
 	phantom:std::marker::PhantomData<TSend>,
 }
 
@@ -84,7 +254,7 @@ impl<TSend> Serialize for Channel<TSend> {
 /// 	channel.send("clicked").unwrap();
 /// }
 /// ```
-pub struct JavaScriptChannelId(CallbackFn);
+pub struct JavaScriptChannelId(pub(crate) CallbackFn);
 
 impl FromStr for JavaScriptChannelId {
 	type Err = &'static str;
@@ -102,30 +272,47 @@ impl JavaScriptChannelId {
 	pub fn channel_on<R:Runtime, TSend>(&self, webview:Webview<R>) -> Channel<TSend> {
 		let callback_id = self.0;
 
-		let counter = AtomicUsize::new(0);
+		let acks =
+			webview.state::<ChannelAckRegistry>().get_or_insert(webview.label(), callback_id.0);
 
-		Channel::new_with_id(callback_id.0, move |body| {
-			let i = counter.fetch_add(1, Ordering::Relaxed);
+		let acks_ = acks.clone();
 
+		let sink:Arc<Deliver> = Arc::new(move |body, i, ack| {
 			if let Some(interceptor) = &webview.manager.channel_interceptor {
 				if interceptor(&webview, callback_id, i, &body) {
+					// the webview never sees the message
+					acks_.ack(i);
+
 					return Ok(());
 				}
 			}
 
-			let data_id = CHANNEL_DATA_COUNTER.fetch_add(1, Ordering::Relaxed);
-
-			webview.state::<ChannelDataIpcQueue>().0.lock().unwrap().insert(data_id, body);
+			let data_id = queue_data(&webview, body);
 
 			webview.eval(&format!(
 				"window.__TAURI_INTERNALS__.invoke('{FETCH_CHANNEL_DATA_COMMAND}', null, {{ \
 				 headers: {{ '{CHANNEL_ID_HEADER_NAME}': '{data_id}' }} }}).then((response) => \
-				 window['_' + {}]({{ message: response, id: {i} }})).catch(console.error)",
+				 window['_' + {}]({{ message: response, id: {i}, ack: {ack} \
+				 }})).catch(console.error)",
 				callback_id.0
 			))?;
 
 			Ok(())
-		})
+		});
+
+		let js = Arc::new(JsChannel { sink, acks });
+
+		let js_ = js.clone();
+
+		let mut channel = Channel::new_with_id(callback_id.0, move |body| {
+			let (index, ack) = js_.acks.reserve(None)?;
+
+			js_.deliver(body, index, ack)
+		});
+
+		channel.js = Some(js);
+
+		channel
 	}
 }
 
@@ -157,12 +344,14 @@ impl<TSend> Channel<TSend> {
 		on_message:F,
 	) -> Self {
 		#[allow(clippy::let_and_return)]
-		let channel = Self { id, on_message:Arc::new(on_message), phantom:Default::default() };
+		let channel =
+			Self { id, on_message:Arc::new(on_message), js:None, phantom:Default::default() };
 
 		#[cfg(mobile)]
 		crate::plugin::mobile::register_channel(Channel {
 			id,
 			on_message:channel.on_message.clone(),
+			js:None,
 			phantom:Default::default(),
 		});
 
@@ -171,9 +360,7 @@ impl<TSend> Channel<TSend> {
 
 	pub(crate) fn from_callback_fn<R:Runtime>(webview:Webview<R>, callback:CallbackFn) -> Self {
 		Channel::new_with_id(callback.0, move |body| {
-			let data_id = CHANNEL_DATA_COUNTER.fetch_add(1, Ordering::Relaxed);
-
-			webview.state::<ChannelDataIpcQueue>().0.lock().unwrap().insert(data_id, body);
+			let data_id = queue_data(&webview, body);
 
 			webview.eval(&format!(
 				"window.__TAURI_INTERNALS__.invoke('{FETCH_CHANNEL_DATA_COMMAND}', null, {{ \
@@ -190,11 +377,76 @@ impl<TSend> Channel<TSend> {
 	pub fn id(&self) -> u32 { self.id }
 
 	/// Sends the given data through the channel.
+	///
+	/// Fails with [`Error::ChannelFull`](crate::Error::ChannelFull) if the
+	/// channel has a [capacity](Self::with_capacity) and it is reached, and
+	/// with [`Error::ChannelClosed`](crate::Error::ChannelClosed) if the
+	/// JavaScript channel was closed.
 	pub fn send(&self, data:TSend) -> crate::Result<()>
 	where
 		TSend: IpcResponse, {
 		(self.on_message)(data.body()?)
 	}
+
+	/// Sends the given data through the channel, resolving when the JavaScript
+	/// side has handled it.
+	///
+	/// The future fails with [`Error::ChannelClosed`](crate::Error::ChannelClosed)
+	/// if the JavaScript channel is closed or its webview navigates before that.
+	/// Channels that are not defined on the JavaScript side resolve as soon as
+	/// the data is sent.
+	pub fn send_ack(&self, data:TSend) -> impl Future<Output = crate::Result<()>> + Send + 'static
+	where
+		TSend: IpcResponse, {
+		let sent = data.body().and_then(|body| {
+			match &self.js {
+				Some(js) => {
+					let (tx, rx) = oneshot::channel();
+
+					let (index, _) = js.acks.reserve(Some(tx))?;
+
+					js.deliver(body, index, true).map(|()| Some(rx))
+				},
+				None => (self.on_message)(body).map(|()| None),
+			}
+		});
+
+		async move {
+			match sent? {
+				Some(rx) => rx.await.unwrap_or(Err(crate::Error::ChannelClosed)),
+				None => Ok(()),
+			}
+		}
+	}
+
+	/// Limits the number of messages waiting to be handled by the JavaScript
+	/// side, [`Self::send`] failing with
+	/// [`Error::ChannelFull`](crate::Error::ChannelFull) once `capacity` is
+	/// reached.
+	///
+	/// The capacity is shared by all the handles to the JavaScript channel.
+	/// Channels that are not defined on the JavaScript side are never full.
+	pub fn with_capacity(self, capacity:usize) -> Self {
+		if let Some(js) = &self.js {
+			js.acks.0.lock().unwrap().capacity = Some(capacity);
+		}
+
+		self
+	}
+}
+
+impl JsChannel {
+	/// Delivers a message whose index was reserved, releasing the index if it
+	/// could not be delivered.
+	fn deliver(&self, body:InvokeResponseBody, index:usize, ack:bool) -> crate::Result<()> {
+		let result = (self.sink)(body, index, ack);
+
+		if result.is_err() && ack {
+			self.acks.0.lock().unwrap().pending.remove(&index);
+		}
+
+		result
+	}
 }
 
 impl<'de, R:Runtime, TSend:Clone> CommandArg<'de, R> for Channel<TSend> {
@@ -233,7 +485,7 @@ fn fetch(
 		.and_then(|id| id.parse().ok())
 	{
 		if let Some(data) = cache.0.lock().unwrap().remove(&id) {
-			Ok(Response::new(data))
+			Ok(Response::new(InvokeResponseBody::from(data)))
 		} else {
 			Err("data not found")
 		}
@@ -242,8 +494,151 @@ fn fetch(
 	}
 }
 
+#[command(root = "crate")]
+fn ack<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, ChannelAckRegistry>,
+	id:u32,
+	index:usize,
+) {
+	if let Some(acks) = registry.get(webview.label(), id) {
+		acks.ack(index);
+	}
+}
+
+/// Acknowledges an event emitted with
+/// [`Emitter::emit_and_wait`](crate::Emitter::emit_and_wait).
+#[command(root = "crate")]
+fn ack_event<R:Runtime>(webview:Webview<R>, id:u32) {
+	webview.manager().listeners().ack(webview.label(), id);
+}
+
+#[command(root = "crate")]
+fn close<R:Runtime>(webview:Webview<R>, registry:State<'_, ChannelAckRegistry>, id:u32) {
+	if let Some(acks) = registry.get(webview.label(), id) {
+		acks.close();
+	}
+}
+
 pub fn plugin<R:Runtime>() -> TauriPlugin<R> {
 	PluginBuilder::new(CHANNEL_PLUGIN_NAME)
-		.invoke_handler(crate::generate_handler![fetch])
+		.invoke_handler(crate::generate_handler![
+			fetch,
+			ack,
+			ack_event,
+			close,
+			super::stream::cancel_stream,
+			super::cancel::cancel_invoke,
+			super::response_stream::pull_response_chunk,
+			super::response_stream::cancel_response_stream
+		])
+		.on_page_load(|webview, payload| {
+			if payload.event() == PageLoadEvent::Started {
+				webview.state::<StreamRegistry>().cancel_webview(webview.label());
+
+				webview.state::<CancellationRegistry>().cancel_webview(webview.label());
+
+				webview.state::<ChannelAckRegistry>().close_webview(webview.label());
+
+				webview.state::<ResponseStreamRegistry>().close_webview(webview.label());
+
+				webview.manager().listeners().remove_webview_acks(webview.label());
+			}
+		})
 		.build()
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn acks_resolve_their_waiter_once() {
+		let acks = ChannelAcks::default();
+
+		let (tx, mut rx) = oneshot::channel();
+
+		let (index, tracked) = acks.reserve(Some(tx)).unwrap();
+
+		assert!(tracked);
+
+		acks.ack(index + 1);
+
+		assert!(matches!(rx.try_recv(), Err(oneshot::error::TryRecvError::Empty)));
+
+		acks.ack(index);
+
+		acks.ack(index);
+
+		assert!(matches!(rx.try_recv(), Ok(Ok(()))));
+		assert!(acks.0.lock().unwrap().pending.is_empty());
+	}
+
+	#[test]
+	fn untracked_messages_are_not_waited_for() {
+		let acks = ChannelAcks::default();
+
+		assert_eq!(acks.reserve(None).unwrap(), (0, false));
+		assert_eq!(acks.reserve(None).unwrap(), (1, false));
+
+		assert!(acks.0.lock().unwrap().pending.is_empty());
+	}
+
+	#[test]
+	fn capacity_is_released_by_acks() {
+		let acks = ChannelAcks::default();
+
+		acks.0.lock().unwrap().capacity = Some(2);
+
+		let (first, tracked) = acks.reserve(None).unwrap();
+
+		assert!(tracked);
+
+		acks.reserve(None).unwrap();
+
+		assert!(matches!(acks.reserve(None), Err(crate::Error::ChannelFull)));
+
+		acks.ack(first);
+
+		acks.ack(first);
+
+		acks.reserve(None).unwrap();
+
+		assert!(matches!(acks.reserve(None), Err(crate::Error::ChannelFull)));
+	}
+
+	#[test]
+	fn closing_fails_pending_and_next_messages() {
+		let acks = ChannelAcks::default();
+
+		let (tx, mut rx) = oneshot::channel();
+
+		acks.reserve(Some(tx)).unwrap();
+
+		acks.close();
+
+		assert!(matches!(rx.try_recv(), Ok(Err(crate::Error::ChannelClosed))));
+		assert!(matches!(acks.reserve(None), Err(crate::Error::ChannelClosed)));
+	}
+
+	#[test]
+	fn closing_a_webview_closes_its_channels() {
+		let registry = ChannelAckRegistry::default();
+
+		let main = registry.get_or_insert("main", 1);
+
+		let popup = registry.get_or_insert("popup", 1);
+
+		assert!(Arc::ptr_eq(&main, &registry.get_or_insert("main", 1)));
+
+		registry.close_webview("main");
+
+		assert!(registry.get("main", 1).is_none());
+		assert!(matches!(main.reserve(None), Err(crate::Error::ChannelClosed)));
+		assert!(popup.reserve(None).is_ok());
+
+		drop(popup);
+
+		assert!(registry.get("popup", 1).is_none());
+	}
+}
@@ -40,6 +40,147 @@ pub struct CommandItem<'a, R:Runtime> {
 	pub acl:&'a Option<Vec<ResolvedCommand>>,
 }
 
+impl<R:Runtime> CommandItem<'_, R> {
+	/// Whether the invoke payload has a value for this item's key.
+	///
+	/// When the payload is raw bytes, the value is read from the headers.
+	pub(crate) fn has_value(&self) -> bool {
+		has_key(&self.message.payload, &self.message.headers, self.key)
+	}
+
+	/// Reads the JSON encoded value of this item from the request headers.
+	///
+	/// Used for the arguments of commands invoked with a bytes payload.
+	fn header_value(&self) -> Option<Result<serde_json::Value, serde_json::Error>> {
+		if self.key.is_empty() {
+			return None;
+		}
+
+		self.message.headers.get(self.key).map(|value| {
+			value
+				.to_str()
+				.map_err(serde_json::Error::custom)
+				.and_then(serde_json::from_str)
+		})
+	}
+}
+
+/// Information about the command being invoked, given to the guards declared
+/// with `#[command(guard = "path::to::guard")]`.
+///
+/// Guards run before the command arguments are deserialized. Returning an
+/// error rejects the invoke with that error. Async commands can also use
+/// `async fn` guards.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::{Runtime, ipc::{CommandContext, InvokeError}};
+///
+/// fn require_main_window<R:Runtime>(ctx:&CommandContext<'_, R>) -> Result<(), InvokeError> {
+/// 	if ctx.webview().label() == "main" {
+/// 		Ok(())
+/// 	} else {
+/// 		Err(InvokeError::from(format!("{} can only be called from the main window", ctx.command())))
+/// 	}
+/// }
+///
+/// // guard paths are resolved where `generate_handler!` is called
+/// #[tauri::command(guard = "crate::require_main_window")]
+/// fn delete_account() {}
+///
+/// fn main() {
+/// 	let _handler = tauri::generate_handler![delete_account];
+/// }
+/// ```
+pub struct CommandContext<'a, R:Runtime> {
+	plugin:Option<&'static str>,
+	name:&'static str,
+	message:&'a InvokeMessage<R>,
+}
+
+impl<'a, R:Runtime> CommandContext<'a, R> {
+	#[doc(hidden)]
+	pub fn new(plugin:Option<&'static str>, name:&'static str, message:&'a InvokeMessage<R>) -> Self {
+		Self { plugin, name, message }
+	}
+
+	/// The handle of the application.
+	pub fn app_handle(&self) -> &'a crate::AppHandle<R> {
+		crate::Manager::app_handle(self.message.webview_ref())
+	}
+
+	/// The webview that invoked the command.
+	pub fn webview(&self) -> &'a crate::Webview<R> { self.message.webview_ref() }
+
+	/// Name of the plugin if the command targets one.
+	pub fn plugin(&self) -> Option<&'static str> { self.plugin }
+
+	/// The name of the command.
+	pub fn command(&self) -> &'static str { self.name }
+
+	/// The raw payload of the invoke, before the arguments are deserialized.
+	pub fn payload(&self) -> &'a InvokeBody { self.message.payload() }
+
+	/// The request headers.
+	pub fn headers(&self) -> &'a http::HeaderMap { self.message.headers() }
+}
+
+/// Where non-async commands without an `exec` attribute are executed, see
+/// [`Builder::default_command_execution`](crate::Builder::default_command_execution).
+///
+/// Async commands and commands declared with `#[command(async)]` always run
+/// on the async runtime.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommandExecution {
+	/// Run the command on the thread that handles the IPC message, which is
+	/// the main thread.
+	#[default]
+	Main,
+	/// Run the command on the blocking thread pool of the async runtime, same as
+	/// `#[command(exec = "blocking")]`.
+	Blocking,
+}
+
+/// The [`CommandExecution`] configured on the builder.
+pub(crate) struct DefaultCommandExecution(pub(crate) CommandExecution);
+
+/// Describes a command, generated by the [`command`](macro@crate::command)
+/// macro when the `command-metadata` feature is enabled.
+///
+/// Types are captured as written in the function signature, they are not
+/// resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct CommandMeta {
+	/// The command name.
+	pub name:&'static str,
+	/// The command arguments, in declaration order.
+	///
+	/// Includes arguments that are not read from the invoke payload, such as
+	/// [`crate::State`] or [`crate::Window`].
+	pub args:&'static [CommandArgMeta],
+	/// The return type, `()` if the function doesn't declare one.
+	pub return_type:&'static str,
+	/// The doc comment of the command function.
+	pub docs:&'static str,
+	/// Whether the command is executed asynchronously.
+	pub is_async:bool,
+	/// The note of `#[command(deprecated = "...")]`.
+	pub deprecated:Option<&'static str>,
+}
+
+/// Describes a command argument, see [`CommandMeta`].
+#[derive(Debug, Clone, Copy)]
+pub struct CommandArgMeta {
+	/// The argument name in the Rust function.
+	pub name:&'static str,
+	/// The key used to read the argument from the invoke payload.
+	pub key:&'static str,
+	/// The argument type.
+	pub ty:&'static str,
+}
+
 /// Trait implemented by command arguments to derive a value from a
 /// [`CommandItem`].
 ///
@@ -79,6 +220,161 @@ impl<'de, D:Deserialize<'de>, R:Runtime> CommandArg<'de, R> for D {
 	}
 }
 
+/// A custom command argument extracted from the invoke request, used by
+/// arguments marked with `#[arg(from_request)]`.
+///
+/// Unlike [`CommandArg`], the implementing type doesn't need to avoid
+/// [`Deserialize`], and the command is rejected with an error naming the
+/// extractor, the argument and the command when extraction fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::{Manager, Runtime, ipc::{CommandItem, FromCommandRequest}};
+///
+/// struct Session(std::sync::Mutex<Option<String>>);
+///
+/// struct CurrentUser(String);
+///
+/// impl<'a, R:Runtime> FromCommandRequest<'a, R> for CurrentUser {
+/// 	type Error = &'static str;
+///
+/// 	fn from_request(item:CommandItem<'a, R>) -> Result<Self, Self::Error> {
+/// 		let session = item.message.webview_ref().state::<Session>();
+/// 		let user = session.0.lock().unwrap().clone();
+/// 		user.map(CurrentUser).ok_or("not logged in")
+/// 	}
+/// }
+///
+/// #[tauri::command]
+/// fn profile(#[arg(from_request)] user:CurrentUser) -> String { user.0 }
+/// ```
+pub trait FromCommandRequest<'a, R:Runtime>: Sized {
+	/// The extraction error, included in the message the command is rejected
+	/// with.
+	type Error: std::fmt::Display;
+
+	/// Extracts an instance of `Self` from the [`CommandItem`], which gives
+	/// access to the whole [`InvokeMessage`].
+	fn from_request(item:CommandItem<'a, R>) -> Result<Self, Self::Error>;
+}
+
+/// The bytes payload of a command invoked with an `ArrayBuffer` or
+/// `Uint8Array`, borrowed from the request without copying.
+///
+/// The other arguments of the command are read from the JSON encoded request
+/// headers. A command can only have one [`RawBytes`] argument.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::ipc::RawBytes;
+///
+/// #[tauri::command]
+/// fn upload(name:String, data:RawBytes<'_>) -> usize {
+/// 	println!("received {name}");
+/// 	data.len()
+/// }
+/// ```
+///
+/// ```javascript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const data = new Uint8Array([1, 2, 3]);
+/// await invoke('upload', data, { headers: { name: JSON.stringify('file.bin') } });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RawBytes<'a>(&'a [u8]);
+
+impl<'a> RawBytes<'a> {
+	/// The borrowed bytes.
+	pub fn as_slice(&self) -> &'a [u8] { self.0 }
+}
+
+impl std::ops::Deref for RawBytes<'_> {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target { self.0 }
+}
+
+impl AsRef<[u8]> for RawBytes<'_> {
+	fn as_ref(&self) -> &[u8] { self.0 }
+}
+
+impl<'a> RawBytes<'a> {
+	/// Borrows the bytes payload, `command` and `key` name the argument in the
+	/// error returned for a JSON payload.
+	fn from_payload(command:&str, key:&str, payload:&'a InvokeBody) -> Result<Self, InvokeError> {
+		match payload {
+			InvokeBody::Raw(body) => Ok(Self(body)),
+			InvokeBody::Json(_) => {
+				Err(InvokeError::from(format!(
+					"command {command} expected a bytes payload for key {key} but the IPC call used a \
+					 JSON payload"
+				)))
+			},
+		}
+	}
+}
+
+impl<'de, R:Runtime> CommandArg<'de, R> for RawBytes<'de> {
+	/// Borrows the bytes payload of the [`InvokeMessage`].
+	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
+		RawBytes::from_payload(command.name, command.key, &command.message.payload)
+	}
+}
+
+/// Whether the payload has a value for `key`, read from the headers when the
+/// payload is raw bytes.
+fn has_key(payload:&InvokeBody, headers:&http::HeaderMap, key:&str) -> bool {
+	!key.is_empty()
+		&& match payload {
+			InvokeBody::Raw(_) => headers.contains_key(key),
+			InvokeBody::Json(v) => v.get(key).is_some(),
+		}
+}
+
+/// The key an argument is read from with `#[command(arg_case = "any")]`: the
+/// `declared` key when the payload has it, otherwise the `alternate` one if
+/// the payload has that spelling.
+fn any_case_key(
+	payload:&InvokeBody,
+	headers:&http::HeaderMap,
+	declared:&'static str,
+	alternate:&'static str,
+) -> &'static str {
+	if !has_key(payload, headers, declared) && has_key(payload, headers, alternate) {
+		alternate
+	} else {
+		declared
+	}
+}
+
+/// Normalizes an argument key so `fooBar`, `foo_bar` and `foo-bar` compare
+/// equal.
+fn normalize_key(key:&str) -> String {
+	key.chars().filter(|c| *c != '_' && *c != '-').flat_map(char::to_lowercase).collect()
+}
+
+/// The error returned when a required argument key is missing from the
+/// payload, hinting at keys that only differ in casing.
+fn missing_key_error(command:&str, key:&str, payload:&serde_json::Value) -> serde_json::Error {
+	let normalized = normalize_key(key);
+
+	let similar = payload
+		.as_object()
+		.and_then(|payload| payload.keys().find(|k| normalize_key(k) == normalized));
+
+	match similar {
+		Some(similar) => {
+			serde_json::Error::custom(format!(
+				"command {command} missing required key {key}, but found {similar}; did you pass \
+				 it with the right casing?"
+			))
+		},
+		None => serde_json::Error::custom(format!("command {command} missing required key {key}")),
+	}
+}
+
 /// Pass the result of [`serde_json::Value::get`] into [`serde_json::Value`]'s
 /// deserializer.
 ///
@@ -95,21 +391,20 @@ macro_rules! pass {
 
       match &self.message.payload {
         InvokeBody::Raw(_body) => {
-          Err(serde_json::Error::custom(format!(
-            "command {} expected a value for key {} but the IPC call used a bytes payload",
-            self.name, self.key
-          )))
+          match self.header_value() {
+            Some(value) => value?.$fn($($arg),*),
+            None => Err(serde_json::Error::custom(format!(
+              "command {} expected a value for key {} but the IPC call used a bytes payload, \
+              pass it as a JSON encoded header instead",
+              self.name, self.key
+            ))),
+          }
         }
 
         InvokeBody::Json(v) => {
           match v.get(self.key) {
             Some(value) => value.$fn($($arg),*),
-            None => {
-              Err(serde_json::Error::custom(format!(
-                "command {} missing required key {}",
-                self.name, self.key
-              )))
-            }
+            None => Err(missing_key_error(self.name, self.key, v)),
           }
         }
       }
@@ -199,10 +494,10 @@ impl<'de, R:Runtime> Deserializer<'de> for CommandItem<'de, R> {
 	fn deserialize_option<V:Visitor<'de>>(self, visitor:V) -> Result<V::Value, Self::Error> {
 		match &self.message.payload {
 			InvokeBody::Raw(_body) => {
-				Err(serde_json::Error::custom(format!(
-					"command {} expected a value for key {} but the IPC call used a bytes payload",
-					self.name, self.key
-				)))
+				match self.header_value() {
+					Some(value) => value?.deserialize_option(visitor),
+					None => visitor.visit_none(),
+				}
 			},
 			InvokeBody::Json(v) => {
 				match v.get(self.key) {
@@ -219,17 +514,520 @@ impl<'de, R:Runtime> Deserializer<'de> for CommandItem<'de, R> {
 /// Nothing in this module is considered stable.
 #[doc(hidden)]
 pub mod private {
-	use std::future::Future;
+	use std::{
+		future::Future,
+		marker::PhantomData,
+		sync::atomic::{AtomicBool, Ordering},
+	};
 
 	use futures_util::{FutureExt, TryFutureExt};
+	pub use serde;
+	pub use serde_json;
 	#[cfg(feature = "tracing")]
 	pub use tracing;
 
+	use futures_util::Stream;
+	use serde::{Deserialize, Serialize};
+
+	use super::DefaultCommandExecution;
 	use crate::{
+		Manager,
 		Runtime,
-		ipc::{InvokeError, InvokeResolver, InvokeResponseBody, IpcResponse},
+		ipc::{
+			CancellationToken,
+			CommandArg,
+			CommandExecution,
+			CommandItem,
+			CommandStream,
+			FromCommandRequest,
+			InvokeError,
+			InvokeMessage,
+			InvokeResolver,
+			IpcResponse,
+			Response,
+			StreamContext,
+		},
 	};
 
+	// ===== #[arg(default)] =====
+
+	/// Deserializes a command argument, falling back to `default` when the
+	/// invoke payload doesn't have a value for it.
+	#[inline(always)]
+	pub fn default_arg<'de, R, T, F>(item:CommandItem<'de, R>, default:F) -> Result<T, InvokeError>
+	where
+		R: Runtime,
+		T: Deserialize<'de>,
+		F: FnOnce() -> T, {
+		if item.has_value() { T::from_command(item) } else { Ok(default()) }
+	}
+
+	// ===== #[command(arg_case = "any")] =====
+
+	/// Reads the item from its `alternate` key when the payload only has that
+	/// spelling.
+	#[inline(always)]
+	pub fn any_case_item<'a, R:Runtime>(
+		mut item:CommandItem<'a, R>,
+		alternate:&'static str,
+	) -> CommandItem<'a, R> {
+		let key =
+			super::any_case_key(&item.message.payload, &item.message.headers, item.key, alternate);
+
+		if key != item.key {
+			log::debug!(
+				"command {} received argument `{}` with the `{key}` key",
+				item.name,
+				item.key
+			);
+
+			item.key = key;
+		}
+
+		item
+	}
+
+	// ===== #[arg(from_request)] =====
+
+	/// Runs a custom extractor, naming it in the error.
+	#[inline(always)]
+	pub fn from_request_arg<'a, R, T>(item:CommandItem<'a, R>) -> Result<T, InvokeError>
+	where
+		R: Runtime,
+		T: FromCommandRequest<'a, R>, {
+		let (name, key) = (item.name, item.key);
+
+		T::from_request(item).map_err(|e| {
+			InvokeError::from(format!(
+				"command {name} failed to extract argument `{key}` with `{}`: {e}",
+				std::any::type_name::<T>()
+			))
+		})
+	}
+
+	// ===== #[arg(window = "...")] =====
+
+	/// Types that can be looked up by label for `#[arg(window = "label")]`.
+	pub trait LabeledArg<R:Runtime>: Sized {
+		fn from_label(webview:&crate::Webview<R>, label:&str) -> Option<Self>;
+	}
+
+	impl<R:Runtime> LabeledArg<R> for crate::WebviewWindow<R> {
+		fn from_label(webview:&crate::Webview<R>, label:&str) -> Option<Self> {
+			webview.get_webview_window(label)
+		}
+	}
+
+	#[cfg(feature = "unstable")]
+	impl<R:Runtime> LabeledArg<R> for crate::Window<R> {
+		fn from_label(webview:&crate::Webview<R>, label:&str) -> Option<Self> { webview.get_window(label) }
+	}
+
+	#[cfg(feature = "unstable")]
+	impl<R:Runtime> LabeledArg<R> for crate::Webview<R> {
+		fn from_label(webview:&crate::Webview<R>, label:&str) -> Option<Self> {
+			webview.get_webview(label)
+		}
+	}
+
+	/// Resolves the window with the given label, rejecting the invoke if it
+	/// doesn't exist.
+	#[inline(always)]
+	pub fn labeled_arg<R, T>(item:CommandItem<'_, R>, label:&str) -> Result<T, InvokeError>
+	where
+		R: Runtime,
+		T: LabeledArg<R>, {
+		T::from_label(item.message.webview_ref(), label).ok_or_else(|| {
+			InvokeError::from(format!(
+				"command {} needs the window `{label}` for its `{}` argument but it doesn't exist",
+				item.name, item.key
+			))
+		})
+	}
+
+	// ===== #[command(deprecated = "...")] =====
+
+	/// Warns that a deprecated command was invoked, once per command.
+	pub fn warn_deprecated<R:Runtime>(
+		warned:&AtomicBool,
+		command:&str,
+		note:&str,
+		message:&InvokeMessage<R>,
+	) {
+		if warned.swap(true, Ordering::Relaxed) {
+			return;
+		}
+
+		let label = message.webview_ref().label();
+
+		#[cfg(feature = "tracing")]
+		tracing::warn!(command, webview = label, "deprecated command invoked: {note}");
+
+		#[cfg(not(feature = "tracing"))]
+		if log::max_level() >= log::LevelFilter::Warn {
+			log::warn!("command {command} invoked by webview `{label}` is deprecated: {note}");
+		} else if cfg!(debug_assertions) {
+			eprintln!("command {command} invoked by webview `{label}` is deprecated: {note}");
+		}
+	}
+
+	// ===== #[command(exec = "...")] =====
+
+	/// The execution of non-async commands that don't declare one.
+	#[inline(always)]
+	pub fn default_command_execution<R:Runtime>(message:&InvokeMessage<R>) -> CommandExecution {
+		message
+			.webview_ref()
+			.try_state::<DefaultCommandExecution>()
+			.map(|execution| execution.0)
+			.unwrap_or_default()
+	}
+
+	// ===== #[command(guard = "...")] =====
+
+	/// Checks the outcome of a guard on a blocking command.
+	#[inline(always)]
+	pub fn check_guard<E:Into<InvokeError>>(outcome:Result<(), E>) -> Result<(), InvokeError> {
+		outcome.map_err(Into::into)
+	}
+
+	pub struct GuardTag;
+
+	pub trait GuardKind {
+		#[inline(always)]
+		fn guard_kind(&self) -> GuardTag { GuardTag }
+	}
+
+	impl<E:Into<InvokeError>> GuardKind for Result<(), E> {}
+
+	impl GuardTag {
+		#[inline(always)]
+		pub fn future<E:Into<InvokeError>>(
+			self,
+			outcome:Result<(), E>,
+		) -> impl Future<Output = Result<(), InvokeError>> {
+			std::future::ready(outcome.map_err(Into::into))
+		}
+	}
+
+	pub struct FutureGuardTag;
+
+	pub trait FutureGuardKind {
+		#[inline(always)]
+		fn guard_kind(&self) -> FutureGuardTag { FutureGuardTag }
+	}
+
+	impl<E:Into<InvokeError>, F:Future<Output = Result<(), E>>> FutureGuardKind for F {}
+
+	impl FutureGuardTag {
+		#[inline(always)]
+		pub fn future<E, F>(self, outcome:F) -> impl Future<Output = Result<(), InvokeError>>
+		where
+			E: Into<InvokeError>,
+			F: Future<Output = Result<(), E>>, {
+			outcome.err_into()
+		}
+	}
+
+	// ===== async fn(state: State<'_, T>) =====
+
+	/// Waits for the asynchronous initialization of the argument of type `T` if
+	/// it is a [`State`](crate::State), see [`Manager::manage_async`].
+	///
+	/// The macro only matches the name of the type, other types named `State`
+	/// don't wait.
+	pub struct StateArg<T:?Sized>(PhantomData<T>);
+
+	impl<T:?Sized> StateArg<T> {
+		#[allow(clippy::new_without_default)]
+		#[inline(always)]
+		pub fn new() -> Self { Self(PhantomData) }
+	}
+
+	pub trait ManagedStateKind {
+		fn state_ready<R:Runtime>(
+			&self,
+			message:&InvokeMessage<R>,
+		) -> impl Future<Output = Result<(), InvokeError>> + Send + 'static;
+	}
+
+	impl<T:Send + Sync + 'static> ManagedStateKind for &StateArg<crate::State<'_, T>> {
+		#[inline(always)]
+		fn state_ready<R:Runtime>(
+			&self,
+			message:&InvokeMessage<R>,
+		) -> impl Future<Output = Result<(), InvokeError>> + Send + 'static {
+			message.state_ref().ready::<T>()
+		}
+	}
+
+	pub trait OtherStateKind {
+		#[inline(always)]
+		fn state_ready<R:Runtime>(
+			&self,
+			_message:&InvokeMessage<R>,
+		) -> impl Future<Output = Result<(), InvokeError>> + Send + 'static {
+			std::future::ready(Ok(()))
+		}
+	}
+
+	impl<T:?Sized> OtherStateKind for StateArg<T> {}
+
+	// ===== #[command(timeout = "...")] =====
+
+	/// Cancels the invoke when the timeout of the command elapses.
+	#[inline(always)]
+	pub fn cancel_after<R:Runtime>(message:&InvokeMessage<R>, millis:u64) -> CancellationToken {
+		crate::ipc::cancel::cancel_after(message, std::time::Duration::from_millis(millis))
+	}
+
+	/// Rejects the invoke of an async command as soon as it is cancelled.
+	#[inline(always)]
+	pub fn until_cancelled<T, F>(
+		token:CancellationToken,
+		task:F,
+	) -> impl Future<Output = Result<T, InvokeError>>
+	where
+		F: Future<Output = Result<T, InvokeError>>, {
+		token.run(task)
+	}
+
+	/// Records the metadata of the commands of a generated handler, see
+	/// [`crate::ipc::command_metadata`].
+	#[cfg(feature = "command-metadata")]
+	pub fn register_command_metadata(metadata:&[&'static crate::ipc::CommandMeta]) {
+		crate::ipc::handler::register_metadata(metadata)
+	}
+
+	// ===== CommandStream<impl Serialize> =====
+
+	pub struct CommandStreamTag;
+
+	pub trait CommandStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> CommandStreamTag { CommandStreamTag }
+	}
+
+	impl<T:Serialize + Send + 'static> CommandStreamKind for CommandStream<T> {}
+
+	impl CommandStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T>(
+			self,
+			value:CommandStream<T>,
+			context:&StreamContext<R>,
+		) -> Result<(), InvokeError>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static, {
+			context.attach(value)
+		}
+	}
+
+	// ===== Stream<Item = impl Serialize> =====
+
+	pub struct StreamTag;
+
+	pub trait StreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> StreamTag { StreamTag }
+	}
+
+	impl<T:Serialize + Send + 'static, S:Stream<Item = T> + Send + 'static> StreamKind for S {}
+
+	impl StreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, S>(self, value:S, context:&StreamContext<R>) -> Result<(), InvokeError>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			S: Stream<Item = T> + Send + 'static, {
+			context.attach(CommandStream::new(value))
+		}
+	}
+
+	// ===== Result<CommandStream<impl Serialize>, impl Into<InvokeError>> =====
+
+	pub struct ResultCommandStreamTag;
+
+	pub trait ResultCommandStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> ResultCommandStreamTag { ResultCommandStreamTag }
+	}
+
+	impl<T:Serialize + Send + 'static, E:Into<InvokeError>> ResultCommandStreamKind
+		for Result<CommandStream<T>, E>
+	{
+	}
+
+	impl ResultCommandStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, E>(
+			self,
+			value:Result<CommandStream<T>, E>,
+			context:&StreamContext<R>,
+		) -> Result<(), InvokeError>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			E: Into<InvokeError>, {
+			value.map_err(Into::into).and_then(|stream| context.attach(stream))
+		}
+	}
+
+	// ===== Future<Output = CommandStream<impl Serialize>> =====
+
+	pub struct FutureCommandStreamTag;
+
+	pub trait FutureCommandStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> FutureCommandStreamTag { FutureCommandStreamTag }
+	}
+
+	impl<T:Serialize + Send + 'static, F:Future<Output = CommandStream<T>>> FutureCommandStreamKind
+		for F
+	{
+	}
+
+	impl FutureCommandStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, F>(
+			self,
+			value:F,
+			context:&StreamContext<R>,
+		) -> impl Future<Output = Result<(), InvokeError>>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			F: Future<Output = CommandStream<T>> + Send, {
+			let context = context.clone();
+
+			value.map(move |stream| context.attach(stream))
+		}
+	}
+
+	// ===== Future<Output = Result<CommandStream<impl Serialize>, impl Into<InvokeError>>> =====
+
+	pub struct FutureResultCommandStreamTag;
+
+	pub trait FutureResultCommandStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> FutureResultCommandStreamTag { FutureResultCommandStreamTag }
+	}
+
+	impl<
+		T:Serialize + Send + 'static,
+		E:Into<InvokeError>,
+		F:Future<Output = Result<CommandStream<T>, E>>,
+	> FutureResultCommandStreamKind for F
+	{
+	}
+
+	impl FutureResultCommandStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, E, F>(
+			self,
+			value:F,
+			context:&StreamContext<R>,
+		) -> impl Future<Output = Result<(), InvokeError>>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			E: Into<InvokeError>,
+			F: Future<Output = Result<CommandStream<T>, E>> + Send, {
+			let context = context.clone();
+
+			value.map(move |result| result.map_err(Into::into).and_then(|stream| context.attach(stream)))
+		}
+	}
+
+	// ===== Future<Output = impl Stream<Item = impl Serialize>> =====
+
+	pub struct FutureStreamTag;
+
+	pub trait FutureStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> FutureStreamTag { FutureStreamTag }
+	}
+
+	impl<T:Serialize + Send + 'static, S:Stream<Item = T> + Send + 'static, F:Future<Output = S>>
+		FutureStreamKind for F
+	{
+	}
+
+	impl FutureStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, S, F>(
+			self,
+			value:F,
+			context:&StreamContext<R>,
+		) -> impl Future<Output = Result<(), InvokeError>>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			S: Stream<Item = T> + Send + 'static,
+			F: Future<Output = S> + Send, {
+			let context = context.clone();
+
+			value.map(move |stream| context.attach(CommandStream::new(stream)))
+		}
+	}
+
+	// ===== Future<Output = Result<impl Stream<Item = impl Serialize>, impl Into<InvokeError>>> =====
+
+	pub struct FutureResultStreamTag;
+
+	pub trait FutureResultStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> FutureResultStreamTag { FutureResultStreamTag }
+	}
+
+	impl<
+		T:Serialize + Send + 'static,
+		S:Stream<Item = T> + Send + 'static,
+		E:Into<InvokeError>,
+		F:Future<Output = Result<S, E>>,
+	> FutureResultStreamKind for F
+	{
+	}
+
+	impl FutureResultStreamTag {
+		#[inline(always)]
+		pub fn attach<R, T, S, E, F>(
+			self,
+			value:F,
+			context:&StreamContext<R>,
+		) -> impl Future<Output = Result<(), InvokeError>>
+		where
+			R: Runtime,
+			T: Serialize + Send + 'static,
+			S: Stream<Item = T> + Send + 'static,
+			E: Into<InvokeError>,
+			F: Future<Output = Result<S, E>> + Send, {
+			let context = context.clone();
+
+			value.map(move |result| {
+				result.map_err(Into::into).and_then(|stream| context.attach(CommandStream::new(stream)))
+			})
+		}
+	}
+
+	// ===== any other return value =====
+
+	pub struct NoStreamTag;
+
+	pub trait NoStreamKind {
+		#[inline(always)]
+		fn stream_kind(&self) -> NoStreamTag { NoStreamTag }
+	}
+
+	impl<T> NoStreamKind for &T {}
+
+	impl NoStreamTag {
+		#[inline(always)]
+		pub fn attach<R:Runtime, T>(self, value:T, _context:&StreamContext<R>) -> T { value }
+	}
+
 	// ===== impl IpcResponse =====
 
 	pub struct ResponseTag;
@@ -257,10 +1055,10 @@ pub mod private {
 		pub fn future<T>(
 			self,
 			value:T,
-		) -> impl Future<Output = Result<InvokeResponseBody, InvokeError>>
+		) -> impl Future<Output = Result<Response, InvokeError>>
 		where
 			T: IpcResponse, {
-			std::future::ready(value.body().map_err(InvokeError::from_error))
+			std::future::ready(Response::from_ipc_response(value))
 		}
 	}
 
@@ -292,15 +1090,11 @@ pub mod private {
 		pub fn future<T, E>(
 			self,
 			value:Result<T, E>,
-		) -> impl Future<Output = Result<InvokeResponseBody, InvokeError>>
+		) -> impl Future<Output = Result<Response, InvokeError>>
 		where
 			T: IpcResponse,
 			E: Into<InvokeError>, {
-			std::future::ready(
-				value
-					.map_err(Into::into)
-					.and_then(|value| value.body().map_err(InvokeError::from_error)),
-			)
+			std::future::ready(value.map_err(Into::into).and_then(Response::from_ipc_response))
 		}
 	}
 
@@ -319,11 +1113,11 @@ pub mod private {
 		pub fn future<T, F>(
 			self,
 			value:F,
-		) -> impl Future<Output = Result<InvokeResponseBody, InvokeError>>
+		) -> impl Future<Output = Result<Response, InvokeError>>
 		where
 			T: IpcResponse,
 			F: Future<Output = T> + Send + 'static, {
-			value.map(|value| value.body().map_err(InvokeError::from_error))
+			value.map(Response::from_ipc_response)
 		}
 	}
 
@@ -343,14 +1137,546 @@ pub mod private {
 		pub fn future<T, E, F>(
 			self,
 			value:F,
-		) -> impl Future<Output = Result<InvokeResponseBody, InvokeError>>
+		) -> impl Future<Output = Result<Response, InvokeError>>
 		where
 			T: IpcResponse,
 			E: Into<InvokeError>,
 			F: Future<Output = Result<T, E>> + Send, {
-			value.err_into().map(|result| {
-				result.and_then(|value| value.body().map_err(InvokeError::from_error))
-			})
+			value.err_into().map(|result| result.and_then(Response::from_ipc_response))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use super::{RawBytes, any_case_key, missing_key_error};
+	use crate::ipc::InvokeBody;
+
+	#[test]
+	fn missing_key_hints_at_other_casing() {
+		let payload = json!({ "filePath": "a.txt", "max_len": 3 });
+
+		assert_eq!(
+			missing_key_error("read", "file-path", &payload).to_string(),
+			"command read missing required key file-path, but found filePath; did you pass it \
+			 with the right casing?"
+		);
+
+		assert_eq!(
+			missing_key_error("read", "maxLen", &payload).to_string(),
+			"command read missing required key maxLen, but found max_len; did you pass it with \
+			 the right casing?"
+		);
+
+		assert_eq!(
+			missing_key_error("read", "offset", &payload).to_string(),
+			"command read missing required key offset"
+		);
+	}
+
+	#[test]
+	fn raw_bytes_borrow_the_payload() {
+		let payload = InvokeBody::Raw(vec![1, 2, 3]);
+
+		let bytes = RawBytes::from_payload("upload", "data", &payload).unwrap();
+
+		let InvokeBody::Raw(body) = &payload else { unreachable!() };
+
+		assert_eq!(bytes.as_slice().as_ptr(), body.as_ptr());
+		assert_eq!(&*bytes, &[1, 2, 3]);
+	}
+
+	#[test]
+	fn raw_bytes_reject_json_payloads() {
+		let payload = InvokeBody::Json(json!({ "data": [1, 2, 3] }));
+
+		let error = RawBytes::from_payload("upload", "data", &payload).unwrap_err();
+
+		assert_eq!(
+			error.message(),
+			Some(
+				"command upload expected a bytes payload for key data but the IPC call used a JSON \
+				 payload"
+			)
+		);
+	}
+
+	#[test]
+	fn any_case_prefers_the_declared_key() {
+		let headers = http::HeaderMap::new();
+
+		let both = InvokeBody::Json(json!({ "filePath": "a.txt", "file_path": "b.txt" }));
+
+		assert_eq!(any_case_key(&both, &headers, "filePath", "file_path"), "filePath");
+
+		let alternate = InvokeBody::Json(json!({ "file_path": "b.txt" }));
+
+		assert_eq!(any_case_key(&alternate, &headers, "filePath", "file_path"), "file_path");
+
+		let neither = InvokeBody::Json(json!({ "path": "c.txt" }));
+
+		assert_eq!(any_case_key(&neither, &headers, "filePath", "file_path"), "filePath");
+	}
+
+	#[test]
+	fn any_case_reads_the_headers_of_bytes_payloads() {
+		let payload = InvokeBody::Raw(vec![1, 2, 3]);
+
+		let mut headers = http::HeaderMap::new();
+
+		headers.insert("file_path", http::HeaderValue::from_static("\"a.txt\""));
+
+		assert_eq!(any_case_key(&payload, &headers, "filePath", "file_path"), "file_path");
+
+		// header names are case insensitive
+		headers.insert("filepath", http::HeaderValue::from_static("\"b.txt\""));
+
+		assert_eq!(any_case_key(&payload, &headers, "filePath", "file_path"), "filePath");
+	}
+
+	#[crate::command(root = "crate")]
+	fn search(query:String, #[arg(default = 20)] limit:u32, #[arg(default)] offset:u32) -> String {
+		format!("{query}:{limit}:{offset}")
+	}
+
+	fn invoke_search(args:serde_json::Value) -> Result<serde_json::Value, serde_json::Value> {
+		use crate::{
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![search])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:"search".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(args),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+	}
+
+	#[test]
+	fn arg_defaults_fill_missing_keys() {
+		assert_eq!(invoke_search(json!({ "query": "tauri" })), Ok(json!("tauri:20:0")));
+
+		assert_eq!(
+			invoke_search(json!({ "query": "tauri", "limit": 5, "offset": 10 })),
+			Ok(json!("tauri:5:10"))
+		);
+
+		// a present value must still deserialize
+		assert!(invoke_search(json!({ "query": "tauri", "limit": "five" })).is_err());
+
+		// arguments without a default are still required
+		assert!(invoke_search(json!({ "limit": 5 })).is_err());
+	}
+
+	fn require_header<R:crate::Runtime>(ctx:&super::CommandContext<'_, R>) -> Result<(), crate::ipc::InvokeError> {
+		if ctx.headers().contains_key("authorization") {
+			Ok(())
+		} else {
+			Err(format!("{} requires authorization", ctx.command()).into())
+		}
+	}
+
+	async fn require_payload<R:crate::Runtime>(
+		ctx:&super::CommandContext<'_, R>,
+	) -> Result<(), crate::ipc::InvokeError> {
+		match ctx.payload() {
+			InvokeBody::Json(payload) if payload.get("id").is_some() => Ok(()),
+			_ => Err("missing id".into()),
+		}
+	}
+
+	#[crate::command(root = "crate", guard = "crate::ipc::command::tests::require_header")]
+	fn guarded(id:u32) -> u32 { id }
+
+	#[crate::command(
+		root = "crate",
+		guard = "crate::ipc::command::tests::require_header",
+		guard = "crate::ipc::command::tests::require_payload"
+	)]
+	async fn guarded_async(id:u32) -> u32 { id }
+
+	fn invoke_guarded(cmd:&str, authorized:bool, args:serde_json::Value) -> Result<serde_json::Value, serde_json::Value> {
+		use crate::{
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![guarded, guarded_async])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let mut headers = http::HeaderMap::new();
+
+		if authorized {
+			headers.insert("authorization", http::HeaderValue::from_static("token"));
+		}
+
+		get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:cmd.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(args),
+				headers,
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+	}
+
+	#[test]
+	fn guards_reject_invokes() {
+		assert_eq!(invoke_guarded("guarded", true, json!({ "id": 1 })), Ok(json!(1)));
+
+		assert_eq!(
+			invoke_guarded("guarded", false, json!({ "id": 1 })),
+			Err(json!("guarded requires authorization"))
+		);
+
+		assert_eq!(invoke_guarded("guarded_async", true, json!({ "id": 2 })), Ok(json!(2)));
+
+		// guards run in declaration order
+		assert_eq!(
+			invoke_guarded("guarded_async", false, json!({})),
+			Err(json!("guarded_async requires authorization"))
+		);
+
+		// guards run before the arguments are deserialized
+		assert_eq!(invoke_guarded("guarded_async", true, json!({})), Err(json!("missing id")));
+	}
+
+	/// An error type that does not implement `Serialize`.
+	#[derive(Debug)]
+	struct ParseError(String);
+
+	fn to_invoke_error(error:ParseError) -> crate::ipc::InvokeError {
+		crate::ipc::InvokeError::from(format!("invalid number: {}", error.0))
+	}
+
+	#[crate::command(root = "crate", error = "crate::ipc::command::tests::to_invoke_error")]
+	fn parse(value:String) -> Result<u32, ParseError> { value.parse().map_err(|_| ParseError(value)) }
+
+	#[crate::command(root = "crate", error = "crate::ipc::command::tests::to_invoke_error")]
+	async fn parse_async(value:String) -> Result<u32, ParseError> { value.parse().map_err(|_| ParseError(value)) }
+
+	#[crate::command(root = "crate")]
+	fn parse_unconverted(value:String) -> Result<u32, String> { value.parse().map_err(|_| value) }
+
+	fn invoke_parse(cmd:&str, value:&str) -> Result<serde_json::Value, serde_json::Value> {
+		use crate::{
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![parse, parse_async, parse_unconverted])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:cmd.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(json!({ "value": value })),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+	}
+
+	#[test]
+	fn error_conversions_apply_to_the_err_branch() {
+		assert_eq!(invoke_parse("parse", "7"), Ok(json!(7)));
+		assert_eq!(invoke_parse("parse", "seven"), Err(json!("invalid number: seven")));
+
+		assert_eq!(invoke_parse("parse_async", "7"), Ok(json!(7)));
+		assert_eq!(invoke_parse("parse_async", "seven"), Err(json!("invalid number: seven")));
+
+		// without the attribute the error goes through `Into<InvokeError>`
+		assert_eq!(invoke_parse("parse_unconverted", "seven"), Err(json!("seven")));
+	}
+
+	fn current_thread() -> String { format!("{:?}", std::thread::current().id()) }
+
+	#[crate::command(root = "crate")]
+	fn thread_default() -> String { current_thread() }
+
+	#[crate::command(root = "crate", exec = "blocking")]
+	fn thread_blocking() -> String { current_thread() }
+
+	#[crate::command(root = "crate", exec = "main")]
+	fn thread_main() -> String { current_thread() }
+
+	fn invoke_thread(execution:super::CommandExecution, cmd:&str) -> String {
+		use crate::{
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.default_command_execution(execution)
+			.invoke_handler(crate::generate_handler![thread_default, thread_blocking, thread_main])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:cmd.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::default(),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.unwrap()
+		.deserialize()
+		.unwrap()
+	}
+
+	#[test]
+	fn commands_run_on_their_execution_context() {
+		use super::CommandExecution;
+
+		// the mock runtime handles the IPC messages and the main thread tasks on the
+		// test thread
+		let main = current_thread();
+
+		assert_eq!(invoke_thread(CommandExecution::Main, "thread_default"), main);
+		assert_ne!(invoke_thread(CommandExecution::Blocking, "thread_default"), main);
+
+		// an `exec` attribute wins over the builder default
+		assert_ne!(invoke_thread(CommandExecution::Main, "thread_blocking"), main);
+		assert_eq!(invoke_thread(CommandExecution::Blocking, "thread_main"), main);
+	}
+
+	#[crate::command(root = "crate")]
+	fn window_labels<R:crate::Runtime>(
+		caller:crate::WebviewWindow<R>,
+		#[arg(window = "main")] main:crate::WebviewWindow<R>,
+	) -> String {
+		format!("{}->{}", caller.label(), main.label())
+	}
+
+	fn invoke_window_labels(windows:&[&str], caller:&str) -> Result<serde_json::Value, serde_json::Value> {
+		use crate::{
+			Manager,
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![window_labels])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		for label in windows {
+			WebviewWindowBuilder::new(&app, *label, Default::default()).build().unwrap();
 		}
+
+		get_ipc_response(
+			&app.get_webview_window(caller).unwrap(),
+			InvokeRequest {
+				cmd:"window_labels".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::default(),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+	}
+
+	#[test]
+	fn window_args_resolve_the_label() {
+		assert_eq!(invoke_window_labels(&["main", "settings"], "settings"), Ok(json!("settings->main")));
+
+		// the calling window can be the labeled one too
+		assert_eq!(invoke_window_labels(&["main"], "main"), Ok(json!("main->main")));
+
+		assert_eq!(
+			invoke_window_labels(&["settings"], "settings"),
+			Err(json!(
+				"command window_labels needs the window `main` for its `main` argument but it doesn't \
+				 exist"
+			))
+		);
+	}
+
+	/// The users of the app, by session token.
+	struct Sessions(std::collections::HashMap<&'static str, &'static str>);
+
+	struct CurrentUser(String);
+
+	impl<'a, R:crate::Runtime> super::FromCommandRequest<'a, R> for CurrentUser {
+		type Error = &'static str;
+
+		fn from_request(item:super::CommandItem<'a, R>) -> Result<Self, Self::Error> {
+			use crate::Manager;
+
+			let token = item
+				.message
+				.headers()
+				.get("authorization")
+				.and_then(|token| token.to_str().ok())
+				.ok_or("missing authorization header")?;
+
+			let sessions = item.message.webview_ref().state::<Sessions>();
+
+			sessions.0.get(token).map(|user| CurrentUser(user.to_string())).ok_or("unknown session")
+		}
+	}
+
+	/// Deserializable, but always read from the request when marked with
+	/// `from_request`.
+	#[derive(serde::Deserialize)]
+	struct Locale(String);
+
+	impl<'a, R:crate::Runtime> super::FromCommandRequest<'a, R> for Locale {
+		type Error = std::convert::Infallible;
+
+		fn from_request(item:super::CommandItem<'a, R>) -> Result<Self, Self::Error> {
+			let locale = item.message.headers().get("accept-language").and_then(|locale| locale.to_str().ok());
+
+			Ok(Locale(locale.unwrap_or("en").to_string()))
+		}
+	}
+
+	#[crate::command(root = "crate")]
+	fn greet(#[arg(from_request)] user:CurrentUser, #[arg(from_request)] locale:Locale, greeting:String) -> String {
+		format!("{greeting} {} ({})", user.0, locale.0)
+	}
+
+	#[crate::command(root = "crate")]
+	async fn greet_async(#[arg(from_request)] user:CurrentUser) -> String { user.0 }
+
+	fn invoke_greet(
+		cmd:&str,
+		headers:&[(&'static str, &'static str)],
+		args:serde_json::Value,
+	) -> Result<serde_json::Value, serde_json::Value> {
+		use crate::{
+			Manager,
+			WebviewWindowBuilder,
+			ipc::CallbackFn,
+			test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![greet, greet_async])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		app.manage(Sessions([("token", "tauri")].into_iter().collect()));
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let mut header_map = http::HeaderMap::new();
+
+		for (name, value) in headers {
+			header_map.insert(*name, http::HeaderValue::from_static(*value));
+		}
+
+		get_ipc_response(
+			&webview,
+			InvokeRequest {
+				cmd:cmd.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(args),
+				headers:header_map,
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+	}
+
+	#[test]
+	fn from_request_args_use_the_extractor() {
+		assert_eq!(
+			invoke_greet("greet", &[("authorization", "token")], json!({ "greeting": "hello" })),
+			Ok(json!("hello tauri (en)"))
+		);
+
+		// the payload is never read for extracted arguments, even if the type is
+		// deserializable
+		assert_eq!(
+			invoke_greet(
+				"greet",
+				&[("authorization", "token"), ("accept-language", "pt-BR")],
+				json!({ "greeting": "olá", "locale": "fr", "user": "someone" }),
+			),
+			Ok(json!("olá tauri (pt-BR)"))
+		);
+
+		assert_eq!(
+			invoke_greet("greet_async", &[("authorization", "token")], json!({})),
+			Ok(json!("tauri"))
+		);
+	}
+
+	#[test]
+	fn from_request_errors_name_the_extractor() {
+		assert_eq!(
+			invoke_greet("greet", &[], json!({ "greeting": "hello" })),
+			Err(json!(
+				"command greet failed to extract argument `user` with \
+				 `tauri::ipc::command::tests::CurrentUser`: missing authorization header"
+			))
+		);
+
+		assert_eq!(
+			invoke_greet("greet_async", &[("authorization", "expired")], json!({})),
+			Err(json!(
+				"command greet_async failed to extract argument `user` with \
+				 `tauri::ipc::command::tests::CurrentUser`: unknown session"
+			))
+		);
 	}
 }
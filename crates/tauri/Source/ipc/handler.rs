@@ -0,0 +1,315 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[cfg(feature = "command-metadata")]
+use std::sync::Mutex;
+use std::{collections::HashMap, sync::Arc};
+
+use super::{CommandMeta, Invoke, InvokeHandler};
+use crate::Runtime;
+
+/// A set of command handlers that can be merged with handlers defined in other
+/// modules.
+///
+/// Created by the [`generate_handler_collection!`](crate::generate_handler_collection)
+/// macro and merged with [`Self::merge`] or the
+/// [`compose_handlers!`](crate::compose_handlers) macro.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// mod files {
+/// 	#[tauri::command]
+/// 	fn read() {}
+///
+/// 	pub fn handlers<R:tauri::Runtime>() -> tauri::ipc::HandlerCollection<R> {
+/// 		tauri::generate_handler_collection![read]
+/// 	}
+/// }
+///
+/// mod settings {
+/// 	#[tauri::command]
+/// 	fn save() {}
+///
+/// 	pub fn handlers<R:tauri::Runtime>() -> tauri::ipc::HandlerCollection<R> {
+/// 		tauri::generate_handler_collection![save]
+/// 	}
+/// }
+///
+/// tauri::Builder::default()
+/// 	.invoke_handlers(tauri::compose_handlers![files::handlers(), settings::handlers()]);
+/// ```
+pub struct HandlerCollection<R:Runtime> {
+	/// Maps a command name to the module that defined it and its handler.
+	commands:HashMap<&'static str, (&'static str, Arc<InvokeHandler<R>>)>,
+	/// Commands that were defined more than once.
+	duplicates:Vec<(&'static str, &'static str, &'static str)>,
+	/// Metadata of the commands, only set with the `command-metadata` feature.
+	metadata:Vec<&'static CommandMeta>,
+}
+
+impl<R:Runtime> HandlerCollection<R> {
+	/// Creates a collection for the given commands, all handled by `handler`.
+	///
+	/// `source` is the module path used to report duplicated commands.
+	pub fn new<F>(source:&'static str, commands:&[&'static str], handler:F) -> Self
+	where
+		F: Fn(Invoke<R>) -> bool + Send + Sync + 'static, {
+		let handler:Arc<InvokeHandler<R>> = Arc::new(handler);
+
+		let mut collection =
+			Self { commands:HashMap::new(), duplicates:Vec::new(), metadata:Vec::new() };
+
+		for command in commands {
+			collection.insert(command, source, handler.clone());
+		}
+
+		collection
+	}
+
+	/// Sets the metadata of the commands in this collection.
+	#[doc(hidden)]
+	#[must_use]
+	pub fn with_metadata(mut self, metadata:&[&'static CommandMeta]) -> Self {
+		self.metadata.extend_from_slice(metadata);
+
+		self
+	}
+
+	fn insert(&mut self, command:&'static str, source:&'static str, handler:Arc<InvokeHandler<R>>) {
+		if let Some((existing, _)) = self.commands.get(command) {
+			self.duplicates.push((command, existing, source));
+		} else {
+			self.commands.insert(command, (source, handler));
+		}
+	}
+
+	/// Adds the commands of `other` to this collection.
+	///
+	/// Commands defined in both collections are reported by
+	/// [`Self::into_handler`].
+	#[must_use]
+	pub fn merge(mut self, other:Self) -> Self {
+		self.duplicates.extend(other.duplicates);
+
+		self.metadata.extend(other.metadata);
+
+		for (command, (source, handler)) in other.commands {
+			self.insert(command, source, handler);
+		}
+
+		self
+	}
+
+	/// The names of the commands in this collection.
+	pub fn commands(&self) -> impl Iterator<Item = &'static str> + '_ {
+		self.commands.keys().copied()
+	}
+
+	/// The metadata of the commands in this collection.
+	///
+	/// Empty unless the `command-metadata` feature is enabled.
+	pub fn metadata(&self) -> &[&'static CommandMeta] { &self.metadata }
+
+	/// The handler of `command`, `None` if no collection defined it.
+	fn handler(&self, command:&str) -> Option<&Arc<InvokeHandler<R>>> {
+		self.commands.get(command).map(|(_, handler)| handler)
+	}
+
+	/// Converts this collection into an invoke handler.
+	///
+	/// Fails if a command was defined in more than one collection.
+	pub fn into_handler(self) -> crate::Result<impl Fn(Invoke<R>) -> bool + Send + Sync + 'static> {
+		if let Some(&(command, first, second)) = self.duplicates.first() {
+			return Err(crate::Error::DuplicateCommand {
+				command:command.into(),
+				first:first.into(),
+				second:second.into(),
+			});
+		}
+
+		Ok(move |invoke:Invoke<R>| {
+			match self.handler(invoke.message.command()) {
+				Some(handler) => handler(invoke),
+				None => false,
+			}
+		})
+	}
+}
+
+/// The metadata of the commands listed in every handler created so far.
+#[cfg(feature = "command-metadata")]
+static REGISTERED_METADATA:Mutex<Vec<&'static CommandMeta>> = Mutex::new(Vec::new());
+
+/// Records the metadata of the commands of a handler created with
+/// [`generate_handler!`](crate::generate_handler) or
+/// [`generate_handler_collection!`](crate::generate_handler_collection).
+#[cfg(feature = "command-metadata")]
+pub(crate) fn register_metadata(metadata:&[&'static CommandMeta]) {
+	REGISTERED_METADATA.lock().unwrap().extend_from_slice(metadata);
+}
+
+/// The metadata of the commands listed in every handler created with
+/// [`generate_handler!`](crate::generate_handler) or
+/// [`generate_handler_collection!`](crate::generate_handler_collection),
+/// including the handlers of plugins.
+///
+/// Each macro invocation is recorded once, when its handler is first created.
+/// Use [`HandlerCollection::metadata`] to get the commands of a single
+/// collection.
+#[cfg(feature = "command-metadata")]
+#[cfg_attr(docsrs, doc(cfg(feature = "command-metadata")))]
+pub fn command_metadata() -> Vec<&'static CommandMeta> { REGISTERED_METADATA.lock().unwrap().clone() }
+
+/// Merges the given [`HandlerCollection`]s into one.
+///
+/// Duplicated commands are reported when the collection is passed to
+/// [`Builder::invoke_handlers`](crate::Builder::invoke_handlers).
+#[macro_export]
+macro_rules! compose_handlers {
+	($first:expr $(, $rest:expr)* $(,)?) => {
+		$first$(.merge($rest))*
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use super::HandlerCollection;
+	use crate::Runtime;
+
+	fn handlers<R:Runtime>(source:&'static str, commands:&[&'static str]) -> HandlerCollection<R> {
+		HandlerCollection::new(source, commands, |_| true)
+	}
+
+	mod commands {
+		#[crate::command(root = "crate")]
+		pub fn reexported_command() {}
+	}
+
+	mod api {
+		pub use super::commands::{reexported_command, reexported_command as renamed_command};
+	}
+
+	mod glob {
+		pub use super::commands::*;
+	}
+
+	fn collection<R:Runtime>() -> HandlerCollection<R> {
+		HandlerCollection::new(
+			module_path!(),
+			&["reexported_command", "renamed_command"],
+			crate::generate_handler![api::reexported_command, api::renamed_command],
+		)
+		.merge(HandlerCollection::new(
+			"glob",
+			&["glob_command"],
+			crate::generate_handler![glob::reexported_command],
+		))
+	}
+
+	#[test]
+	#[cfg(feature = "command-metadata")]
+	fn generated_handlers_register_their_metadata() {
+		mod metadata {
+			/// Returns the version.
+			#[crate::command(root = "crate")]
+			pub fn registered_command(prefix:String) -> String { prefix }
+		}
+
+		fn handler<R:Runtime>() -> impl Fn(crate::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+			crate::generate_handler![metadata::registered_command]
+		}
+
+		let _ = handler::<crate::test::MockRuntime>();
+		let _ = handler::<crate::test::MockRuntime>();
+
+		let registered = super::command_metadata()
+			.into_iter()
+			.filter(|meta| meta.name == "registered_command")
+			.collect::<Vec<_>>();
+
+		// the invocation is only recorded once
+		assert_eq!(registered.len(), 1);
+		assert_eq!(registered[0].docs, "Returns the version.");
+		assert_eq!(registered[0].args[0].key, "prefix");
+	}
+
+	#[test]
+	fn generated_collections_resolve_the_crate_root() {
+		fn generated<R:Runtime>() -> HandlerCollection<R> {
+			crate::generate_handler_collection![api::reexported_command, api::renamed_command]
+		}
+
+		let collection = generated::<crate::test::MockRuntime>();
+
+		let mut commands = collection.commands().collect::<Vec<_>>();
+
+		commands.sort_unstable();
+
+		assert_eq!(commands, ["reexported_command", "renamed_command"]);
+	}
+
+	#[test]
+	#[cfg(feature = "wry")]
+	fn resolves_reexported_commands() {
+		let collection = collection::<crate::Wry>();
+
+		let mut commands = collection.commands().collect::<Vec<_>>();
+
+		commands.sort_unstable();
+
+		assert_eq!(commands, ["glob_command", "reexported_command", "renamed_command"]);
+	}
+
+	#[test]
+	#[cfg(feature = "wry")]
+	fn merges_collections() {
+		let collection =
+			handlers::<crate::Wry>("app::files", &["read", "write"]).merge(handlers("app::settings", &["save"]));
+
+		let mut commands = collection.commands().collect::<Vec<_>>();
+
+		commands.sort_unstable();
+
+		assert_eq!(commands, ["read", "save", "write"]);
+
+		assert!(collection.into_handler().is_ok());
+	}
+
+	#[test]
+	#[cfg(feature = "wry")]
+	fn reports_duplicate_commands() {
+		let collection = handlers::<crate::Wry>("app::files", &["read", "save"])
+			.merge(handlers("app::settings", &["save"]));
+
+		let Err(error) = collection.into_handler() else {
+			panic!("the duplicated `save` command must be reported");
+		};
+
+		assert!(matches!(
+			&error,
+			crate::Error::DuplicateCommand { command, first, second }
+				if command == "save" && first == "app::files" && second == "app::settings"
+		));
+
+		assert_eq!(error.to_string(), "command `save` is defined in both `app::files` and `app::settings`");
+	}
+
+	#[test]
+	#[cfg(feature = "wry")]
+	fn routes_commands_to_their_collection() {
+		let collection =
+			handlers::<crate::Wry>("app::files", &["read"]).merge(handlers("app::settings", &["save"]));
+
+		let read = collection.handler("read").expect("`read` is defined");
+
+		let save = collection.handler("save").expect("`save` is defined");
+
+		assert!(!Arc::ptr_eq(read, save));
+
+		assert!(collection.handler("unknown").is_none());
+	}
+}
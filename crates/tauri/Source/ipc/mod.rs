@@ -21,13 +21,21 @@ pub use serialize_to_javascript::Options as SerializeOptions;
 use tauri_macros::default_runtime;
 use tauri_utils::acl::resolved::ResolvedCommand;
 
-use crate::{Runtime, StateManager, webview::Webview};
+use crate::{Manager, Runtime, StateManager, webview::Webview};
 
 mod authority;
+pub(crate) mod body_stream;
+pub(crate) mod cancel;
 pub(crate) mod channel;
 mod command;
 pub(crate) mod format_callback;
+mod handler;
+pub(crate) mod plugin;
+mod progress;
 pub(crate) mod protocol;
+pub(crate) mod response_stream;
+pub(crate) mod stream;
+pub(crate) mod transport;
 
 pub use authority::{
 	CapabilityBuilder,
@@ -40,8 +48,36 @@ pub use authority::{
 	ScopeObjectMatch,
 	ScopeValue,
 };
+pub use body_stream::RequestBodyStream;
+pub use cancel::CancellationToken;
 pub use channel::{Channel, JavaScriptChannelId};
-pub use command::{CommandArg, CommandItem, private};
+pub use command::{
+	CommandArg,
+	CommandArgMeta,
+	CommandContext,
+	CommandExecution,
+	CommandItem,
+	CommandMeta,
+	FromCommandRequest,
+	RawBytes,
+	private,
+};
+pub(crate) use command::DefaultCommandExecution;
+#[cfg(feature = "command-metadata")]
+pub use handler::command_metadata;
+pub use handler::HandlerCollection;
+pub use progress::Progress;
+pub use response_stream::ResponseStream;
+pub use stream::{CommandStream, StreamContext};
+pub use transport::{
+	InvokeDispatcher,
+	InvokeSystem,
+	InvokeTransport,
+	RemoteConnection,
+	TransportRequest,
+	transport_frontend_script,
+	transport_script,
+};
 
 /// A closure that is run every time Tauri receives a message it doesn't
 /// explicitly handle.
@@ -164,6 +200,13 @@ impl<'a, R:Runtime> CommandArg<'a, R> for Request<'a> {
 pub trait IpcResponse {
 	/// Resolve the IPC response body.
 	fn body(self) -> crate::Result<InvokeResponseBody>;
+
+	/// Takes the headers that must be sent along with the response body.
+	fn take_headers(&mut self) -> Option<HeaderMap> { None }
+
+	/// Takes the stream the response body is sent with, see
+	/// [`Response::from_stream`].
+	fn take_stream(&mut self) -> Option<ResponseStream> { None }
 }
 
 impl<T:Serialize> IpcResponse for T {
@@ -175,15 +218,200 @@ impl<T:Serialize> IpcResponse for T {
 /// The IPC response.
 pub struct Response {
 	body:InvokeResponseBody,
+	headers:HeaderMap,
+	stream:Option<ResponseStream>,
 }
 
 impl IpcResponse for Response {
 	fn body(self) -> crate::Result<InvokeResponseBody> { Ok(self.body) }
+
+	fn take_headers(&mut self) -> Option<HeaderMap> {
+		if self.headers.is_empty() { None } else { Some(std::mem::take(&mut self.headers)) }
+	}
+
+	fn take_stream(&mut self) -> Option<ResponseStream> { self.stream.take() }
 }
 
 impl Response {
 	/// Defines a response with the given body.
-	pub fn new(body:impl Into<InvokeResponseBody>) -> Self { Self { body:body.into() } }
+	pub fn new(body:impl Into<InvokeResponseBody>) -> Self {
+		Self { body:body.into(), headers:HeaderMap::new(), stream:None }
+	}
+
+	/// Defines a response whose body is sent to the webview in chunks as the
+	/// stream yields them, instead of being collected in memory first.
+	///
+	/// The JavaScript side reads it as a `ReadableStream` by calling `invoke`
+	/// with the `responseType: 'stream'` option, the next chunk is only polled
+	/// once the previous one is read. An error of the stream errors the
+	/// `ReadableStream`, so a failed body is never mistaken for a complete one.
+	///
+	/// The stream is dropped when the `ReadableStream` is cancelled or when the
+	/// webview navigates away. An invoke without the `responseType: 'stream'`
+	/// option is rejected with [`Error::ResponseStreamNotRequested`](crate::Error::ResponseStreamNotRequested)
+	/// instead, since nothing would ever read the stream.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use tauri::ipc::Response;
+	///
+	/// #[tauri::command]
+	/// fn export_csv(rows:Vec<Vec<String>>) -> Response {
+	/// 	Response::from_stream(futures_util::stream::iter(rows.into_iter().map(|row| {
+	/// 		Ok::<_, String>(bytes::Bytes::from(format!("{}\n", row.join(","))))
+	/// 	})))
+	/// }
+	/// ```
+	///
+	/// ```javascript
+	/// import { invoke } from '@tauri-apps/api/core'
+	/// const csv = await invoke('export_csv', { rows }, { responseType: 'stream' })
+	/// for await (const chunk of csv) {
+	///   console.log(chunk.byteLength)
+	/// }
+	/// ```
+	pub fn from_stream<S, E>(stream:S) -> Self
+	where
+		S: futures_util::Stream<Item = Result<bytes::Bytes, E>> + Send + 'static,
+		E: Into<InvokeError>, {
+		Self {
+			body:InvokeResponseBody::Json("null".into()),
+			headers:HeaderMap::new(),
+			stream:Some(ResponseStream::new(stream)),
+		}
+	}
+
+	/// Defines a response whose body is read from the given reader while the
+	/// webview reads it, see [`Self::from_stream`].
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use tauri::ipc::Response;
+	///
+	/// #[tauri::command]
+	/// async fn read_video(path:std::path::PathBuf) -> Result<Response, String> {
+	/// 	let file = tokio::fs::File::open(path).await.map_err(|e| e.to_string())?;
+	///
+	/// 	Ok(Response::from_async_read(file))
+	/// }
+	/// ```
+	pub fn from_async_read<A>(reader:A) -> Self
+	where
+		A: tokio::io::AsyncRead + Send + 'static, {
+		Self {
+			body:InvokeResponseBody::Json("null".into()),
+			headers:HeaderMap::new(),
+			stream:Some(ResponseStream::from_async_read(reader)),
+		}
+	}
+
+	/// Converts any [`IpcResponse`] into a [`Response`], keeping its headers.
+	pub(crate) fn from_ipc_response<T:IpcResponse>(mut value:T) -> Result<Self, InvokeError> {
+		let headers = value.take_headers().unwrap_or_default();
+
+		let stream = value.take_stream();
+
+		value
+			.body()
+			.map(|body| Self { body, headers, stream })
+			.map_err(InvokeError::from_error)
+	}
+
+	/// The headers sent along with the response.
+	///
+	/// With the custom protocol IPC they are regular HTTP response headers.
+	/// The JavaScript side can read them by calling `invoke` with the
+	/// `responseType: 'detailed'` option.
+	pub fn headers(&self) -> &HeaderMap { &self.headers }
+
+	/// A mutable reference to the headers sent along with the response.
+	pub fn headers_mut(&mut self) -> &mut HeaderMap { &mut self.headers }
+
+	/// Adds a header to the response.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tauri::ipc::Response;
+	///
+	/// #[tauri::command]
+	/// fn report() -> tauri::Result<Response> {
+	/// 	Response::new("{}".to_string()).with_header("Cache-Control", "no-store")
+	/// }
+	/// ```
+	pub fn with_header(mut self, name:&str, value:&str) -> crate::Result<Self> {
+		let name = http::HeaderName::from_bytes(name.as_bytes())
+			.map_err(|e| crate::Error::InvalidHeader(e.to_string()))?;
+
+		let value = http::HeaderValue::from_str(value)
+			.map_err(|e| crate::Error::InvalidHeader(e.to_string()))?;
+
+		self.headers.insert(name, value);
+
+		Ok(self)
+	}
+
+	/// Defines a response from bytes that are already serialized as
+	/// `content_type`.
+	///
+	/// `application/json` bodies are parsed by the JavaScript side, any other
+	/// content type is received as an `ArrayBuffer` and sent as the
+	/// `Content-Type` header of the response, which `invoke` exposes with the
+	/// `responseType: 'detailed'` option.
+	pub fn with_content_type(body:Vec<u8>, content_type:&str) -> crate::Result<Self> {
+		let mime = content_type
+			.parse::<mime::Mime>()
+			.map_err(|e| crate::Error::InvalidResponseBody(format!("invalid content type: {e}")))?;
+
+		if mime.essence_str() == mime::APPLICATION_JSON.essence_str() {
+			String::from_utf8(body)
+				.map(Self::new)
+				.map_err(|e| crate::Error::InvalidResponseBody(e.to_string()))
+		} else {
+			Self::new(body).with_header(http::header::CONTENT_TYPE.as_str(), content_type)
+		}
+	}
+}
+
+/// A command return value that is sent to the webview without going through
+/// [`Serialize`], used by `#[command(raw_response)]`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[tauri::command(raw_response)]
+/// fn messagepack() -> (Vec<u8>, &'static str) { (vec![0x93, 0x01, 0x02, 0x03], "application/msgpack") }
+///
+/// #[tauri::command(raw_response)]
+/// fn cached_json() -> tauri::Result<tauri::ipc::Response> {
+/// 	tauri::ipc::Response::with_content_type(b"{\"cached\":true}".to_vec(), "application/json")
+/// }
+/// ```
+pub trait IntoRawResponse {
+	/// Converts the value into the response sent to the webview.
+	fn into_raw_response(self) -> Result<Response, InvokeError>;
+}
+
+impl IntoRawResponse for Response {
+	fn into_raw_response(self) -> Result<Response, InvokeError> { Ok(self) }
+}
+
+impl IntoRawResponse for Vec<u8> {
+	fn into_raw_response(self) -> Result<Response, InvokeError> { Ok(Response::new(self)) }
+}
+
+impl<M:AsRef<str>> IntoRawResponse for (Vec<u8>, M) {
+	fn into_raw_response(self) -> Result<Response, InvokeError> {
+		Response::with_content_type(self.0, self.1.as_ref()).map_err(InvokeError::from_error)
+	}
+}
+
+impl<T:IntoRawResponse, E:Into<InvokeError>> IntoRawResponse for Result<T, E> {
+	fn into_raw_response(self) -> Result<Response, InvokeError> {
+		self.map_err(Into::into).and_then(IntoRawResponse::into_raw_response)
+	}
 }
 
 /// The message and resolver given to a custom command.
@@ -206,18 +434,116 @@ pub struct Invoke<R:Runtime> {
 pub struct InvokeError(pub serde_json::Value);
 
 impl InvokeError {
-	/// Create an [`InvokeError`] as a string of the [`std::error::Error`]
-	/// message.
+	/// Create a structured [`InvokeError`] from the message and
+	/// [`source`](std::error::Error::source) chain of the
+	/// [`std::error::Error`], see [`Self::with_code`].
 	#[inline(always)]
 	pub fn from_error<E:std::error::Error>(error:E) -> Self {
-		Self(serde_json::Value::String(error.to_string()))
+		Self::envelope(error.to_string(), None, JsonValue::Null, error_sources(&error))
 	}
 
-	/// Create an [`InvokeError`] as a string of the [`anyhow::Error`] message.
+	/// Create a structured [`InvokeError`] from the message and context chain
+	/// of the [`anyhow::Error`], see [`Self::with_code`].
 	#[inline(always)]
 	pub fn from_anyhow(error:anyhow::Error) -> Self {
-		Self(serde_json::Value::String(format!("{error:#}")))
+		let sources = error.chain().skip(1).map(ToString::to_string).collect();
+
+		Self::envelope(error.to_string(), None, JsonValue::Null, sources)
 	}
+
+	/// Create a structured [`InvokeError`] with a machine-readable code, e.g.
+	/// `E_NOT_FOUND`, and the message and [`source`](std::error::Error::source)
+	/// chain of the error.
+	///
+	/// It is serialized as `{ message, code, data, sources }` along with a
+	/// `__TAURI_INVOKE_ERROR__` marker, which the JavaScript `invoke` rejects
+	/// with as an `InvokeError` instance. Plain string errors are rejected as
+	/// an `InvokeError` that only has a message.
+	///
+	/// # Examples
+	/// ```
+	/// use tauri::ipc::InvokeError;
+	///
+	/// #[tauri::command]
+	/// fn read_config() -> Result<String, InvokeError> {
+	///   std::fs::read_to_string("config.json").map_err(|e| InvokeError::with_code("E_NOT_FOUND", e))
+	/// }
+	/// ```
+	pub fn with_code<E:std::error::Error>(code:impl Into<String>, error:E) -> Self {
+		Self::envelope(error.to_string(), Some(code.into()), JsonValue::Null, error_sources(&error))
+	}
+
+	/// Attaches data to the error, turning it into a structured error.
+	pub fn with_data<T:Serialize>(self, data:T) -> Self {
+		let data = serde_json::to_value(data).unwrap_or_default();
+
+		match self.0 {
+			JsonValue::Object(mut envelope) if is_error_envelope(&envelope) => {
+				envelope.insert("data".into(), data);
+
+				Self(JsonValue::Object(envelope))
+			},
+			JsonValue::String(message) => Self::envelope(message, None, data, Vec::new()),
+			value => Self::envelope(value.to_string(), None, data, Vec::new()),
+		}
+	}
+
+	/// The error message, for string and structured errors.
+	pub fn message(&self) -> Option<&str> {
+		match &self.0 {
+			JsonValue::String(message) => Some(message),
+			JsonValue::Object(envelope) if is_error_envelope(envelope) => envelope["message"].as_str(),
+			_ => None,
+		}
+	}
+
+	/// The machine-readable code of a structured error.
+	pub fn code(&self) -> Option<&str> { self.field("code").and_then(JsonValue::as_str) }
+
+	/// The data attached to a structured error with [`Self::with_data`].
+	pub fn data(&self) -> Option<&JsonValue> { self.field("data").filter(|data| !data.is_null()) }
+
+	/// The messages of the sources of a structured error, from the closest to
+	/// the root cause.
+	pub fn sources(&self) -> Vec<&str> {
+		self.field("sources")
+			.and_then(JsonValue::as_array)
+			.map(|sources| sources.iter().filter_map(JsonValue::as_str).collect())
+			.unwrap_or_default()
+	}
+
+	fn field(&self, name:&str) -> Option<&JsonValue> {
+		match &self.0 {
+			JsonValue::Object(envelope) if is_error_envelope(envelope) => envelope.get(name),
+			_ => None,
+		}
+	}
+
+	fn envelope(message:String, code:Option<String>, data:JsonValue, sources:Vec<String>) -> Self {
+		Self(serde_json::json!({
+			INVOKE_ERROR_MARKER: true,
+			"message": message,
+			"code": code,
+			"data": data,
+			"sources": sources,
+		}))
+	}
+}
+
+/// The key marking the envelope of a structured [`InvokeError`], so errors
+/// that only have the same fields are not mistaken for it.
+const INVOKE_ERROR_MARKER:&str = "__TAURI_INVOKE_ERROR__";
+
+/// Whether the object is the `{ message, code, data, sources }` envelope of a
+/// structured [`InvokeError`].
+fn is_error_envelope(value:&serde_json::Map<String, JsonValue>) -> bool {
+	value.get(INVOKE_ERROR_MARKER) == Some(&JsonValue::Bool(true))
+}
+
+fn error_sources(error:&dyn std::error::Error) -> Vec<String> {
+	std::iter::successors(error.source(), |error| error.source())
+		.map(ToString::to_string)
+		.collect()
 }
 
 impl<T:Serialize> From<T> for InvokeError {
@@ -260,6 +586,22 @@ impl From<InvokeError> for InvokeResponse {
 	fn from(error:InvokeError) -> Self { Self::Err(error) }
 }
 
+thread_local! {
+	/// The headers sent along with the response the current responder is
+	/// called with, see [`take_response_headers`].
+	static RESPONSE_HEADERS: std::cell::RefCell<Option<HeaderMap>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Takes the headers attached to the response by the command, see
+/// [`Response::with_header`].
+///
+/// The responders are called synchronously by the [`InvokeResolver`], so the
+/// headers are handed to them out of band rather than on [`InvokeResponse`].
+/// Only returns them when called from within the responder.
+pub(crate) fn take_response_headers() -> HeaderMap {
+	RESPONSE_HEADERS.with(|current| current.borrow_mut().take()).unwrap_or_default()
+}
+
 /// Resolver of a invoke message.
 #[default_runtime(crate::Wry, wry)]
 pub struct InvokeResolver<R:Runtime> {
@@ -325,6 +667,7 @@ impl<R:Runtime> InvokeResolver<R> {
 				self.webview,
 				self.responder,
 				response,
+				HeaderMap::new(),
 				self.cmd,
 				self.callback,
 				self.error,
@@ -334,16 +677,62 @@ impl<R:Runtime> InvokeResolver<R> {
 
 	/// Reply to the invoke promise with a serializable value.
 	pub fn respond<T:IpcResponse>(self, value:Result<T, InvokeError>) {
+		let (response, headers) = Self::into_response(&self.webview, self.callback, value);
+
 		Self::return_result(
 			self.webview,
 			self.responder,
-			value.into(),
+			response,
+			headers,
 			self.cmd,
 			self.callback,
 			self.error,
 		)
 	}
 
+	/// Converts the value into the response sent to the webview and the headers
+	/// sent along with it, registering the body stream of the response if any.
+	fn into_response<T:IpcResponse>(
+		webview:&Webview<R>,
+		callback:CallbackFn,
+		value:Result<T, InvokeError>,
+	) -> (InvokeResponse, HeaderMap) {
+		let mut value = match value {
+			Ok(value) => value,
+			Err(error) => return (InvokeResponse::Err(error), HeaderMap::new()),
+		};
+
+		let mut headers = value.take_headers().unwrap_or_default();
+
+		let Some(stream) = value.take_stream() else {
+			return (Result::<T, InvokeError>::Ok(value).into(), headers);
+		};
+
+		let registry = webview.state::<response_stream::ResponseStreamRegistry>();
+
+		// nothing would ever pull the stream
+		if !registry.is_requested(webview.label(), callback.0) {
+			return (InvokeResponse::Err(crate::Error::ResponseStreamNotRequested.into()), HeaderMap::new());
+		}
+
+		let body = match value.body() {
+			Ok(body) => body,
+			Err(error) => return (InvokeResponse::Err(InvokeError::from_error(error)), HeaderMap::new()),
+		};
+
+		let id = registry.insert(webview.label(), stream);
+
+		response_stream::ResponseStreamRegistry::watch(
+			webview.app_handle().clone(),
+			webview.label().to_string(),
+			id,
+		);
+
+		headers.insert(response_stream::RESPONSE_STREAM_HEADER_NAME, id.into());
+
+		(InvokeResponse::Ok(body), headers)
+	}
+
 	/// Resolve the invoke promise with a value.
 	pub fn resolve<T:IpcResponse>(self, value:T) { self.respond(Ok(value)) }
 
@@ -353,6 +742,7 @@ impl<R:Runtime> InvokeResolver<R> {
 			self.webview,
 			self.responder,
 			Result::<(), _>::Err(value).into(),
+			HeaderMap::new(),
 			self.cmd,
 			self.callback,
 			self.error,
@@ -365,6 +755,7 @@ impl<R:Runtime> InvokeResolver<R> {
 			self.webview,
 			self.responder,
 			error.into(),
+			HeaderMap::new(),
 			self.cmd,
 			self.callback,
 			self.error,
@@ -402,24 +793,32 @@ impl<R:Runtime> InvokeResolver<R> {
 		success_callback:CallbackFn,
 		error_callback:CallbackFn,
 	) {
-		Self::return_result(webview, responder, f().into(), cmd, success_callback, error_callback)
+		let (response, headers) = Self::into_response(&webview, success_callback, f());
+
+		Self::return_result(webview, responder, response, headers, cmd, success_callback, error_callback)
 	}
 
 	pub(crate) fn return_result(
 		webview:Webview<R>,
 		responder:Arc<Mutex<Option<Box<OwnedInvokeResponder<R>>>>>,
 		response:InvokeResponse,
+		headers:HeaderMap,
 		cmd:String,
 		success_callback:CallbackFn,
 		error_callback:CallbackFn,
 	) {
-		(responder.lock().unwrap().take().expect("resolver consumed"))(
-			webview,
-			cmd,
-			response,
-			success_callback,
-			error_callback,
-		);
+		if let Some(registry) = webview.try_state::<response_stream::ResponseStreamRegistry>() {
+			registry.responded(webview.label(), success_callback.0);
+		}
+
+		let responder = responder.lock().unwrap().take().expect("resolver consumed");
+
+		RESPONSE_HEADERS.with(|current| *current.borrow_mut() = Some(headers));
+
+		responder(webview, cmd, response, success_callback, error_callback);
+
+		// the responder does not have to read them
+		RESPONSE_HEADERS.with(|current| current.borrow_mut().take());
 	}
 }
 
@@ -437,6 +836,8 @@ pub struct InvokeMessage<R:Runtime> {
 	pub(crate) payload:InvokeBody,
 	/// The request headers.
 	pub(crate) headers:HeaderMap,
+	/// Triggered when the invoke is cancelled.
+	pub(crate) cancellation:CancellationToken,
 }
 
 impl<R:Runtime> Clone for InvokeMessage<R> {
@@ -447,6 +848,7 @@ impl<R:Runtime> Clone for InvokeMessage<R> {
 			command:self.command.clone(),
 			payload:self.payload.clone(),
 			headers:self.headers.clone(),
+			cancellation:self.cancellation.clone(),
 		}
 	}
 }
@@ -460,7 +862,14 @@ impl<R:Runtime> InvokeMessage<R> {
 		payload:InvokeBody,
 		headers:HeaderMap,
 	) -> Self {
-		Self { webview, state, command, payload, headers }
+		Self {
+			webview,
+			state,
+			command,
+			payload,
+			headers,
+			cancellation:CancellationToken::default(),
+		}
 	}
 
 	/// The invoke command.
@@ -490,6 +899,10 @@ impl<R:Runtime> InvokeMessage<R> {
 	/// The request headers.
 	#[inline(always)]
 	pub fn headers(&self) -> &HeaderMap { &self.headers }
+
+	/// The token triggered when the invoke is cancelled.
+	#[inline(always)]
+	pub fn cancellation_token(&self) -> &CancellationToken { &self.cancellation }
 }
 
 /// The `Callback` type is the return value of the `transformCallback`
@@ -521,4 +934,391 @@ mod tests {
 
 		assert_eq!(raw.deserialize::<Vec<u8>>().unwrap(), values);
 	}
+
+	#[derive(Debug, thiserror::Error, crate::CommandError)]
+	#[command_error(root = "crate", rename_all = "snake_case")]
+	enum TestError {
+		#[error("io error")]
+		Io(#[command_error(from, skip)] std::io::Error),
+		#[error("{path} was not found")]
+		NotFound { path:String, #[command_error(flatten)] details:Details },
+		#[error("invalid range")]
+		#[command_error(kind = "range")]
+		InvalidRange(u32, u32),
+		#[error("cancelled")]
+		Cancelled,
+	}
+
+	#[derive(Debug, Serialize)]
+	struct Details {
+		retry:bool,
+	}
+
+	#[test]
+	fn command_error_format() {
+		let io:TestError = std::io::Error::other("disk full").into();
+
+		assert_eq!(
+			serde_json::to_value(io).unwrap(),
+			serde_json::json!({ "kind": "io", "message": "io error", "data": null })
+		);
+
+		let not_found =
+			TestError::NotFound { path:"a.txt".into(), details:Details { retry:false } };
+
+		assert_eq!(
+			serde_json::to_value(not_found).unwrap(),
+			serde_json::json!({
+				"kind": "not_found",
+				"message": "a.txt was not found",
+				"data": { "path": "a.txt", "retry": false }
+			})
+		);
+
+		assert_eq!(
+			serde_json::to_value(TestError::InvalidRange(3, 1)).unwrap(),
+			serde_json::json!({ "kind": "range", "message": "invalid range", "data": [3, 1] })
+		);
+
+		assert_eq!(
+			InvokeError::from(TestError::Cancelled).0,
+			serde_json::json!({ "kind": "cancelled", "message": "cancelled", "data": null })
+		);
+	}
+
+	#[derive(Debug, thiserror::Error, crate::CommandError)]
+	#[command_error(root = "crate")]
+	enum PlainError {
+		#[error("failed to parse")]
+		Parse {
+			#[command_error(from, skip)]
+			source:serde_json::Error,
+		},
+		#[error("denied {0}")]
+		Denied(String),
+	}
+
+	#[test]
+	fn command_error_default_kind() {
+		let parse:PlainError = serde_json::from_str::<u32>("-").unwrap_err().into();
+
+		assert_eq!(
+			serde_json::to_value(parse).unwrap(),
+			serde_json::json!({ "kind": "Parse", "message": "failed to parse", "data": null })
+		);
+
+		assert_eq!(
+			serde_json::to_value(PlainError::Denied("write".into())).unwrap(),
+			serde_json::json!({ "kind": "Denied", "message": "denied write", "data": "write" })
+		);
+	}
+
+	#[derive(Debug, thiserror::Error)]
+	#[error("failed to load the config")]
+	struct ConfigError(#[source] std::io::Error);
+
+	#[test]
+	fn structured_invoke_error() {
+		let error = InvokeError::with_code(
+			"E_NOT_FOUND",
+			ConfigError(std::io::Error::other("config.json not found")),
+		);
+
+		assert_eq!(
+			error.0,
+			serde_json::json!({
+				"__TAURI_INVOKE_ERROR__": true,
+				"message": "failed to load the config",
+				"code": "E_NOT_FOUND",
+				"data": null,
+				"sources": ["config.json not found"]
+			})
+		);
+
+		assert_eq!(error.code(), Some("E_NOT_FOUND"));
+		assert_eq!(error.sources(), ["config.json not found"]);
+
+		let error = error.with_data(serde_json::json!({ "path": "config.json" }));
+
+		assert_eq!(error.data(), Some(&serde_json::json!({ "path": "config.json" })));
+
+		let plain = InvokeError::from("denied");
+
+		assert_eq!(plain.0, serde_json::json!("denied"));
+		assert_eq!(plain.message(), Some("denied"));
+		assert_eq!(plain.code(), None);
+		assert!(plain.sources().is_empty());
+
+		let plain = plain.with_data(1);
+
+		assert_eq!(plain.message(), Some("denied"));
+		assert_eq!(plain.data(), Some(&serde_json::json!(1)));
+	}
+
+	#[test]
+	fn error_chains_fill_the_sources() {
+		let error = InvokeError::from_error(ConfigError(std::io::Error::other("disk full")));
+
+		assert_eq!(error.message(), Some("failed to load the config"));
+		assert_eq!(error.code(), None);
+		assert_eq!(error.sources(), ["disk full"]);
+
+		let error = InvokeError::from_anyhow(
+			anyhow::anyhow!("disk full").context("failed to write").context("failed to save"),
+		);
+
+		assert_eq!(error.message(), Some("failed to save"));
+		assert_eq!(error.sources(), ["failed to write", "disk full"]);
+	}
+
+	#[test]
+	fn errors_shaped_like_the_envelope_are_not_structured() {
+		let error = InvokeError::from(serde_json::json!({
+			"message": "denied",
+			"code": "E_DENIED",
+			"data": null,
+			"sources": [],
+		}));
+
+		assert_eq!(error.message(), None);
+		assert_eq!(error.code(), None);
+	}
+
+	#[crate::command(root = "crate")]
+	fn export_stream() -> Response {
+		Response::from_stream(futures_util::stream::iter([Ok::<_, String>(
+			bytes::Bytes::from_static(b"chunk"),
+		)]))
+	}
+
+	#[crate::command(root = "crate")]
+	fn export_failing_stream() -> Response {
+		Response::from_stream(futures_util::stream::iter([
+			Ok(bytes::Bytes::from_static(b"chunk")),
+			Err("disk full".to_string()),
+		]))
+	}
+
+	type MockWindow = crate::WebviewWindow<crate::test::MockRuntime>;
+
+	fn export_app() -> (crate::App<crate::test::MockRuntime>, MockWindow) {
+		use crate::{
+			WebviewWindowBuilder,
+			test::{mock_builder, mock_context, noop_assets},
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![export_stream, export_failing_stream])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let webview = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		(app, webview)
+	}
+
+	fn invoke_on(
+		webview:&MockWindow,
+		cmd:&str,
+		body:InvokeBody,
+		headers:HeaderMap,
+	) -> (InvokeResponse, HeaderMap) {
+		let (tx, rx) = std::sync::mpsc::channel();
+
+		webview.webview.clone().on_message(
+			crate::webview::InvokeRequest {
+				cmd:cmd.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body,
+				headers,
+				invoke_key:crate::test::INVOKE_KEY.to_string(),
+			},
+			Box::new(move |_, _, response, _, _| {
+				tx.send((response, take_response_headers())).unwrap();
+			}),
+		);
+
+		rx.recv().unwrap()
+	}
+
+	/// Invokes the command asking for a stream response, returning the stream ID.
+	fn open_stream(webview:&MockWindow, cmd:&str) -> u32 {
+		let mut headers = HeaderMap::new();
+
+		headers.insert(
+			response_stream::ACCEPT_RESPONSE_STREAM_HEADER_NAME,
+			http::HeaderValue::from_static("true"),
+		);
+
+		let (response, headers) = invoke_on(webview, cmd, InvokeBody::default(), headers);
+
+		assert!(matches!(response, InvokeResponse::Ok(_)));
+
+		headers
+			.get(response_stream::RESPONSE_STREAM_HEADER_NAME)
+			.expect("the stream ID was not sent")
+			.to_str()
+			.unwrap()
+			.parse()
+			.unwrap()
+	}
+
+	fn pull(webview:&MockWindow, id:u32) -> InvokeResponse {
+		invoke_on(
+			webview,
+			response_stream::PULL_RESPONSE_CHUNK_COMMAND,
+			InvokeBody::Json(serde_json::json!({ "id": id })),
+			HeaderMap::new(),
+		)
+		.0
+	}
+
+	fn assert_chunk(response:InvokeResponse, expected:&[u8]) {
+		match response {
+			InvokeResponse::Ok(InvokeResponseBody::Raw(chunk)) => assert_eq!(chunk, expected),
+			response => panic!("expected a {expected:?} chunk, got {response:?}"),
+		}
+	}
+
+	#[test]
+	fn streams_are_rejected_without_the_stream_response_type() {
+		let (_app, webview) = export_app();
+
+		match invoke_on(&webview, "export_stream", InvokeBody::default(), HeaderMap::new()).0 {
+			InvokeResponse::Err(error) => {
+				assert_eq!(error.0, serde_json::json!(crate::Error::ResponseStreamNotRequested.to_string()));
+			},
+			InvokeResponse::Ok(body) => panic!("the stream was sent with a {body:?} body"),
+		}
+	}
+
+	#[test]
+	fn stream_ids_are_sent_with_the_response() {
+		let (_app, webview) = export_app();
+
+		let id = open_stream(&webview, "export_stream");
+
+		assert_chunk(pull(&webview, id), b"chunk");
+		// an empty chunk ends the stream
+		assert_chunk(pull(&webview, id), b"");
+
+		assert!(matches!(pull(&webview, id), InvokeResponse::Err(_)));
+	}
+
+	#[test]
+	fn stream_errors_reject_the_pull() {
+		let (_app, webview) = export_app();
+
+		let id = open_stream(&webview, "export_failing_stream");
+
+		assert_chunk(pull(&webview, id), b"chunk");
+
+		match pull(&webview, id) {
+			InvokeResponse::Err(error) => assert_eq!(error.0, serde_json::json!("disk full")),
+			response => panic!("the stream error was not sent, got {response:?}"),
+		}
+
+		match pull(&webview, id) {
+			InvokeResponse::Err(error) => {
+				assert_eq!(error.0, serde_json::json!("the response stream already ended"))
+			},
+			response => panic!("the stream was kept after its error, got {response:?}"),
+		}
+	}
+
+	#[test]
+	fn cancelled_streams_are_dropped() {
+		let (_app, webview) = export_app();
+
+		let id = open_stream(&webview, "export_stream");
+
+		let (response, _) = invoke_on(
+			&webview,
+			response_stream::CANCEL_RESPONSE_STREAM_COMMAND,
+			InvokeBody::Json(serde_json::json!({ "id": id })),
+			HeaderMap::new(),
+		);
+
+		assert!(matches!(response, InvokeResponse::Ok(_)));
+
+		assert!(matches!(pull(&webview, id), InvokeResponse::Err(_)));
+	}
+
+	#[test]
+	fn raw_responses_carry_their_content_type() {
+		let response = Response::with_content_type(vec![0x93, 0x01], "application/msgpack").unwrap();
+
+		assert_eq!(response.headers().get(http::header::CONTENT_TYPE).unwrap(), "application/msgpack");
+		assert!(matches!(response.body(), Ok(InvokeResponseBody::Raw(bytes)) if bytes == [0x93, 0x01]));
+
+		let response =
+			Response::with_content_type(b"{\"cached\":true}".to_vec(), "application/json; charset=utf-8")
+				.unwrap();
+
+		assert!(response.headers().is_empty());
+		assert!(matches!(response.body(), Ok(InvokeResponseBody::Json(json)) if json == "{\"cached\":true}"));
+
+		assert!(matches!(
+			Response::with_content_type(vec![0xff], "application/json"),
+			Err(crate::Error::InvalidResponseBody(_))
+		));
+
+		assert!(matches!(
+			Response::with_content_type(Vec::new(), "not a mime"),
+			Err(crate::Error::InvalidResponseBody(_))
+		));
+	}
+
+	#[crate::command(root = "crate")]
+	fn cached() -> Response {
+		Response::new(vec![1, 2, 3]).with_header("Cache-Control", "no-store").unwrap()
+	}
+
+	#[test]
+	fn response_headers_are_sent_to_the_responder() {
+		use crate::{
+			WebviewWindowBuilder,
+			test::{mock_builder, mock_context, noop_assets},
+		};
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![cached])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let dispatcher = InvokeDispatcher::new(app.handle().clone(), "tauri://localhost".parse().unwrap());
+
+		let (response, headers) = crate::async_runtime::block_on(dispatcher.invoke(TransportRequest {
+			webview:"main".into(),
+			cmd:"cached".into(),
+			body:InvokeBody::default(),
+			headers:HeaderMap::new(),
+		}))
+		.unwrap();
+
+		// the body is untouched, the headers are handed over next to it
+		assert!(matches!(response, InvokeResponse::Ok(InvokeResponseBody::Raw(bytes)) if bytes == [1, 2, 3]));
+		assert_eq!(headers.get("cache-control").unwrap(), "no-store");
+
+		// and only to the responder of that response
+		assert!(take_response_headers().is_empty());
+	}
+
+	#[test]
+	fn response_headers_are_carried_by_the_response() {
+		let response = Response::new("{}".to_string()).with_header("Cache-Control", "no-store").unwrap();
+
+		let response = Response::from_ipc_response(response).unwrap();
+
+		assert_eq!(response.headers().get("cache-control").unwrap(), "no-store");
+
+		assert!(matches!(
+			Response::new(Vec::new()).with_header("Cache Control", "no-store"),
+			Err(crate::Error::InvalidHeader(_))
+		));
+	}
 }
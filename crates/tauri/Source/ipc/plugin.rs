@@ -0,0 +1,54 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use crate::{
+	Runtime,
+	Webview,
+	command,
+	plugin::{Builder, TauriPlugin},
+	sealed::ManagerBase,
+};
+
+#[command(root = "crate")]
+pub fn check<R:Runtime>(webview:Webview<R>, identifier:String) -> crate::Result<bool> {
+	let origin = webview.acl_origin()?;
+
+	let window_label = webview.window_label();
+
+	Ok(webview.manager().runtime_authority.lock().unwrap().is_granted(
+		&identifier,
+		&window_label,
+		webview.label(),
+		&origin,
+	))
+}
+
+#[command(root = "crate")]
+pub fn check_all<R:Runtime>(
+	webview:Webview<R>,
+	identifiers:Vec<String>,
+) -> crate::Result<HashMap<String, bool>> {
+	let origin = webview.acl_origin()?;
+
+	let window_label = webview.window_label();
+
+	let authority = webview.manager().runtime_authority.lock().unwrap();
+
+	Ok(identifiers
+		.into_iter()
+		.map(|identifier| {
+			let granted =
+				authority.is_granted(&identifier, &window_label, webview.label(), &origin);
+			(identifier, granted)
+		})
+		.collect())
+}
+
+pub fn init<R:Runtime>() -> TauriPlugin<R> {
+	Builder::new("permissions")
+		.invoke_handler(crate::generate_handler![check, check_all])
+		.build()
+}
@@ -0,0 +1,156 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Progress reports of a running invoke.
+
+use serde::Serialize;
+
+use super::{
+	CallbackFn,
+	Channel,
+	CommandArg,
+	CommandItem,
+	InvokeError,
+	IpcResponse,
+	JavaScriptChannelId,
+};
+use crate::Runtime;
+
+/// The header the JavaScript `invoke` function uses to send the ID of the
+/// channel that receives the progress reports when it is given an
+/// `onProgress` callback.
+pub(crate) const PROGRESS_CHANNEL_HEADER_NAME:&str = "Tauri-Progress-Channel";
+
+/// Reports the progress of a command to the `onProgress` callback of its
+/// `invoke` call.
+///
+/// Reporting does nothing when the caller didn't pass an `onProgress`
+/// callback. The JavaScript side stops receiving the reports once the invoke
+/// settles.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::ipc::Progress;
+///
+/// #[tauri::command]
+/// async fn export(rows:Vec<String>, progress:Progress) -> tauri::Result<()> {
+/// 	for (done, _row) in rows.iter().enumerate() {
+/// 		progress.report(serde_json::json!({ "done": done + 1, "total": rows.len() }))?;
+/// 	}
+///
+/// 	Ok(())
+/// }
+/// ```
+///
+/// ```javascript
+/// import { invoke } from '@tauri-apps/api/core'
+/// await invoke('export', { rows }, {
+///   onProgress: ({ done, total }) => console.log(`${done}/${total}`)
+/// })
+/// ```
+#[derive(Clone)]
+pub struct Progress {
+	channel:Option<Channel>,
+}
+
+impl Progress {
+	/// Sends a progress report to the `onProgress` callback.
+	pub fn report<T:Serialize>(&self, progress:T) -> crate::Result<()> {
+		match &self.channel {
+			Some(channel) => channel.send(progress.body()?),
+			None => Ok(()),
+		}
+	}
+
+	/// Whether the caller listens to the progress reports, to skip computing
+	/// them otherwise.
+	pub fn is_listened(&self) -> bool { self.channel.is_some() }
+}
+
+impl<'de, R:Runtime> CommandArg<'de, R> for Progress {
+	/// Returns the progress reporter of the invoke. This will never fail.
+	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
+		let channel = command
+			.message
+			.headers()
+			.get(PROGRESS_CHANNEL_HEADER_NAME)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|id| id.parse().ok())
+			.map(|id| JavaScriptChannelId(CallbackFn(id)).channel_on(command.message.webview()));
+
+		Ok(Self { channel })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		WebviewWindowBuilder,
+		ipc::InvokeBody,
+		test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets},
+		webview::InvokeRequest,
+	};
+
+	#[crate::command(root = "crate")]
+	fn export(progress:Progress) -> bool {
+		progress.report(serde_json::json!({ "done": 1, "total": 1 })).unwrap();
+
+		progress.is_listened()
+	}
+
+	fn invoke_export(channel:Option<&'static str>) -> (bool, Vec<String>) {
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![export])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let window = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let mut headers = http::HeaderMap::new();
+
+		if let Some(channel) = channel {
+			headers.insert(PROGRESS_CHANNEL_HEADER_NAME, http::HeaderValue::from_static(channel));
+		}
+
+		let listened = get_ipc_response(
+			&window,
+			InvokeRequest {
+				cmd:"export".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::default(),
+				headers,
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.unwrap()
+		.deserialize::<bool>()
+		.unwrap();
+
+		(listened, window.webview.webview.dispatcher.state().evaluated_scripts)
+	}
+
+	#[test]
+	fn reports_are_sent_to_the_progress_channel() {
+		let (listened, scripts) = invoke_export(Some("7"));
+
+		assert!(listened);
+
+		assert_eq!(scripts.iter().filter(|script| script.contains("window['_' + 7]")).count(), 1);
+	}
+
+	#[test]
+	fn reports_do_nothing_without_the_progress_channel() {
+		for channel in [None, Some("not a channel")] {
+			let (listened, scripts) = invoke_export(channel);
+
+			assert!(!listened);
+
+			assert!(!scripts.iter().any(|script| script.contains("window['_' + ")));
+		}
+	}
+}
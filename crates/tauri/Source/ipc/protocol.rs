@@ -56,9 +56,19 @@ pub fn get<R:Runtime>(manager:Arc<AppManager<R>>) -> UriSchemeProtocolHandler {
 			response
 				.headers_mut()
 				.insert(ACCESS_CONTROL_ALLOW_ORIGIN, HeaderValue::from_static("*"));
+			// expose the headers set by the command along with the IPC ones
+			let exposed = response
+				.headers()
+				.keys()
+				.map(|name| name.as_str())
+				.filter(|name| *name != TAURI_RESPONSE_HEADER_NAME.to_lowercase())
+				.fold(TAURI_RESPONSE_HEADER_NAME.to_string(), |exposed, name| {
+					format!("{exposed}, {name}")
+				});
 			response.headers_mut().insert(
 				ACCESS_CONTROL_EXPOSE_HEADERS,
-				HeaderValue::from_static(TAURI_RESPONSE_HEADER_NAME),
+				HeaderValue::from_str(&exposed)
+					.unwrap_or_else(|_| HeaderValue::from_static(TAURI_RESPONSE_HEADER_NAME)),
 			);
 			responder.respond(response);
 		};
@@ -89,6 +99,8 @@ pub fn get<R:Runtime>(manager:Arc<AppManager<R>>) -> UriSchemeProtocolHandler {
 									)
 									.entered();
 
+									let headers = super::take_response_headers();
+
 									#[cfg(feature = "tracing")]
 									let response_span = match &response {
 										InvokeResponse::Ok(InvokeResponseBody::Json(v)) => {
@@ -122,29 +134,20 @@ pub fn get<R:Runtime>(manager:Arc<AppManager<R>>) -> UriSchemeProtocolHandler {
 										InvokeResponse::Err(_) => TAURI_RESPONSE_HEADER_ERROR,
 									};
 
-									let (mut response, mime_type) = match response {
+									let mime_type = response_mime_type(&response, &headers);
+
+									let mut response = match response {
 										InvokeResponse::Ok(InvokeResponseBody::Json(v)) => {
-											(
-												http::Response::new(v.as_bytes().to_vec().into()),
-												mime::APPLICATION_JSON,
-											)
-										},
-										InvokeResponse::Ok(InvokeResponseBody::Raw(v)) => {
-											(
-												http::Response::new(v.into()),
-												mime::APPLICATION_OCTET_STREAM,
-											)
+											http::Response::new(v.as_bytes().to_vec().into())
 										},
+										InvokeResponse::Ok(InvokeResponseBody::Raw(v)) => http::Response::new(v.into()),
 										InvokeResponse::Err(e) => {
-											(
-												http::Response::new(
-													serde_json::to_vec(&e.0).unwrap().into(),
-												),
-												mime::APPLICATION_JSON,
-											)
+											http::Response::new(serde_json::to_vec(&e.0).unwrap().into())
 										},
 									};
 
+									response.headers_mut().extend(headers);
+
 									response.headers_mut().insert(
 										TAURI_RESPONSE_HEADER_NAME,
 										response_header.parse().unwrap(),
@@ -210,6 +213,41 @@ pub fn get<R:Runtime>(manager:Arc<AppManager<R>>) -> UriSchemeProtocolHandler {
 	})
 }
 
+/// The content type of the response, raw bodies keep the one set with
+/// [`Response::with_content_type`](crate::ipc::Response::with_content_type).
+fn response_mime_type(response:&InvokeResponse, headers:&http::HeaderMap) -> mime::Mime {
+	match response {
+		InvokeResponse::Ok(InvokeResponseBody::Raw(_)) => {
+			headers
+				.get(CONTENT_TYPE)
+				.and_then(|value| value.to_str().ok())
+				.and_then(|value| value.parse().ok())
+				.unwrap_or(mime::APPLICATION_OCTET_STREAM)
+		},
+		InvokeResponse::Ok(InvokeResponseBody::Json(_)) | InvokeResponse::Err(_) => mime::APPLICATION_JSON,
+	}
+}
+
+/// The script that makes the success callback resolve to a `{ data, headers }`
+/// object, evaluated before the response body is sent so the body keeps going
+/// through the channel, which preserves raw bodies as an `ArrayBuffer`.
+fn detailed_callback_script(callback:CallbackFn, headers:&http::HeaderMap) -> String {
+	let headers = headers
+		.iter()
+		.filter_map(|(name, value)| value.to_str().ok().map(|value| (name.as_str(), value)))
+		.collect::<std::collections::HashMap<_, _>>();
+
+	let headers = serde_json::to_string(&headers).unwrap_or_else(|_| "{}".into());
+
+	// the callbacks are defined as read-only but configurable properties
+	format!(
+		"(() => {{ const cb = window['_{id}']; if (cb) {{ Object.defineProperty(window, '_{id}', {{ \
+		 value: (data) => cb({{ data, headers: {headers} }}), writable: false, configurable: true \
+		 }}) }} }})()",
+		id = callback.0
+	)
+}
+
 fn handle_ipc_message<R:Runtime>(request:Request<String>, manager:&AppManager<R>, label:&str) {
 	if let Some(webview) = manager.get_webview(label) {
 		#[cfg(feature = "tracing")]
@@ -258,6 +296,8 @@ fn handle_ipc_message<R:Runtime>(request:Request<String>, manager:&AppManager<R>
 			headers:HeaderMap,
 			#[serde(default)]
 			custom_protocol_ipc_blocked:bool,
+			#[serde(default)]
+			response_type:Option<String>,
 		}
 
 		#[derive(Deserialize)]
@@ -287,7 +327,7 @@ fn handle_ipc_message<R:Runtime>(request:Request<String>, manager:&AppManager<R>
 				invoke_key:String,
 			}
 
-			if let crate::Pattern::Isolation { crypto_keys, .. } = &*manager.pattern {
+			if let Some(crypto_keys) = &manager.isolation_keys {
 				#[cfg(feature = "tracing")]
 				let _span = tracing::trace_span!("ipc::request::decrypt_isolation_payload").entered();
 
@@ -366,6 +406,15 @@ fn handle_ipc_message<R:Runtime>(request:Request<String>, manager:&AppManager<R>
 							let _ = webview.eval(&eval_js);
 						}
 
+						let headers = super::take_response_headers();
+
+						// `invoke(cmd, args, { responseType: 'detailed' })` resolves to `{ data, headers }`
+						if matches!(response, InvokeResponse::Ok(_))
+							&& options.response_type.as_deref() == Some("detailed")
+						{
+							let _ = webview.eval(&detailed_callback_script(callback, &headers));
+						}
+
 						let can_use_channel_for_response = cmd
 							!= crate::ipc::channel::FETCH_CHANNEL_DATA_COMMAND
 							&& !options.custom_protocol_ipc_blocked;
@@ -501,7 +550,7 @@ fn parse_invoke_request<R:Runtime>(
 		.map_err(|_| "unknown content type")?;
 
 	#[cfg(feature = "isolation")]
-	if let crate::Pattern::Isolation { crypto_keys, .. } = &*manager.pattern {
+	if let Some(crypto_keys) = &manager.isolation_keys {
 		// if the platform does not support request body, we ignore it
 		if has_payload {
 			#[cfg(feature = "tracing")]
@@ -599,6 +648,42 @@ mod tests {
 	use super::*;
 	use crate::{StateManager, Wry, ipc::InvokeBody, manager::AppManager, plugin::PluginStore};
 
+	#[test]
+	fn raw_responses_keep_their_content_type() {
+		let mut headers = http::HeaderMap::new();
+
+		headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/msgpack"));
+
+		assert_eq!(
+			response_mime_type(&InvokeResponse::Ok(InvokeResponseBody::Raw(vec![0x93])), &headers).essence_str(),
+			"application/msgpack"
+		);
+
+		assert_eq!(
+			response_mime_type(&InvokeResponse::Ok(InvokeResponseBody::Raw(vec![0x93])), &http::HeaderMap::new()),
+			mime::APPLICATION_OCTET_STREAM
+		);
+
+		// JSON bodies are always parsed as JSON by the webview
+		assert_eq!(
+			response_mime_type(&InvokeResponse::Ok(InvokeResponseBody::Json("{}".into())), &headers),
+			mime::APPLICATION_JSON
+		);
+	}
+
+	#[test]
+	fn detailed_callbacks_keep_the_body_untouched() {
+		let mut headers = http::HeaderMap::new();
+
+		headers.insert("x-total-count", HeaderValue::from_static("42"));
+
+		let script = detailed_callback_script(CallbackFn(12), &headers);
+
+		assert!(script.contains("const cb = window['_12']"));
+		// the body is forwarded as is, raw bodies stay an `ArrayBuffer`
+		assert!(script.contains(r#"cb({ data, headers: {"x-total-count":"42"} })"#));
+	}
+
 	#[test]
 	fn parse_invoke_request() {
 		let context =
@@ -0,0 +1,309 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streaming command response bodies.
+
+use std::{
+	collections::{HashMap, HashSet},
+	pin::Pin,
+	sync::{
+		Arc,
+		Mutex,
+		atomic::{AtomicU32, Ordering},
+	},
+	time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+
+use super::{InvokeError, Response};
+use crate::{AppHandle, Manager, Runtime, State, Webview, command};
+
+/// The response header carrying the ID of the body stream of a [`Response`]
+/// created with [`Response::from_stream`] or [`Response::from_async_read`].
+pub(crate) const RESPONSE_STREAM_HEADER_NAME:&str = "Tauri-Response-Stream";
+/// The request header set by `invoke` with the `responseType: 'stream'`
+/// option, without it the body stream of the response is dropped.
+pub(crate) const ACCEPT_RESPONSE_STREAM_HEADER_NAME:&str = "Tauri-Accept-Response-Stream";
+pub const PULL_RESPONSE_CHUNK_COMMAND:&str = "plugin:__TAURI_CHANNEL__|pull_response_chunk";
+pub const CANCEL_RESPONSE_STREAM_COMMAND:&str =
+	"plugin:__TAURI_CHANNEL__|cancel_response_stream";
+
+/// The size of the chunks read from an [`AsyncRead`](tokio::io::AsyncRead)
+/// response body.
+const READ_CHUNK_SIZE:usize = 64 * 1024;
+
+/// How long a stream the webview stopped pulling is kept, so the streams of
+/// invokes whose response is never read don't live until the next navigation.
+const RESPONSE_STREAM_IDLE_TIMEOUT:Duration = Duration::from_secs(30);
+
+static RESPONSE_STREAM_COUNTER:AtomicU32 = AtomicU32::new(0);
+
+/// The body of a streamed [`Response`], created with [`Response::from_stream`]
+/// or [`Response::from_async_read`] and taken from the response with
+/// [`IpcResponse::take_stream`](super::IpcResponse::take_stream).
+pub struct ResponseStream(Pin<Box<dyn Stream<Item = Result<Vec<u8>, InvokeError>> + Send>>);
+
+impl ResponseStream {
+	pub(crate) fn new<S, E>(stream:S) -> Self
+	where
+		S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+		E: Into<InvokeError>, {
+		// reuses the buffer of the chunks that aren't shared
+		Self(Box::pin(stream.map(|chunk| chunk.map(Vec::from).map_err(Into::into))))
+	}
+
+	pub(crate) fn from_async_read<A>(reader:A) -> Self
+	where
+		A: tokio::io::AsyncRead + Send + 'static, {
+		use tokio::io::AsyncReadExt;
+
+		let stream = futures_util::stream::unfold(Some(Box::pin(reader)), |reader| {
+			async move {
+				let mut reader = reader?;
+
+				let mut buffer = Vec::with_capacity(READ_CHUNK_SIZE);
+
+				match reader.read_buf(&mut buffer).await {
+					Ok(0) => None,
+					Ok(_) => Some((Ok(buffer), Some(reader))),
+					// nothing is read after an error
+					Err(e) => Some((Err(InvokeError::from_error(e)), None)),
+				}
+			}
+		});
+
+		Self(Box::pin(stream))
+	}
+}
+
+struct Entry {
+	stream:Arc<tokio::sync::Mutex<ResponseStream>>,
+	last_pulled:Instant,
+}
+
+/// The body streams of the responses sent to the webviews, keyed by webview
+/// label and stream ID.
+#[derive(Default)]
+pub(crate) struct ResponseStreamRegistry {
+	streams:Mutex<HashMap<(String, u32), Entry>>,
+	/// The pending invokes that read their response as a stream, keyed by
+	/// webview label and callback ID.
+	requested:Mutex<HashSet<(String, u32)>>,
+}
+
+impl ResponseStreamRegistry {
+	/// Keeps the stream until the webview reads it, returning its ID.
+	pub(crate) fn insert(&self, webview:&str, stream:ResponseStream) -> u32 {
+		let id = RESPONSE_STREAM_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+		self.streams.lock().unwrap().insert(
+			(webview.to_string(), id),
+			Entry { stream:Arc::new(tokio::sync::Mutex::new(stream)), last_pulled:Instant::now() },
+		);
+
+		id
+	}
+
+	fn get(&self, webview:&str, id:u32) -> Option<Arc<tokio::sync::Mutex<ResponseStream>>> {
+		let mut streams = self.streams.lock().unwrap();
+
+		let entry = streams.get_mut(&(webview.to_string(), id))?;
+
+		entry.last_pulled = Instant::now();
+
+		Some(entry.stream.clone())
+	}
+
+	/// Drops the stream if it was not pulled for the given duration, returning
+	/// whether it is still kept.
+	fn drop_if_idle(&self, webview:&str, id:u32, timeout:Duration) -> bool {
+		let mut streams = self.streams.lock().unwrap();
+
+		let key = (webview.to_string(), id);
+
+		let Some(entry) = streams.get(&key) else {
+			return false;
+		};
+
+		// a pull waiting on the stream holds a reference to it
+		if Arc::strong_count(&entry.stream) > 1 || entry.last_pulled.elapsed() < timeout {
+			return true;
+		}
+
+		streams.remove(&key);
+
+		false
+	}
+
+	/// Drops the stream once the webview stops pulling it for
+	/// [`RESPONSE_STREAM_IDLE_TIMEOUT`].
+	pub(crate) fn watch<R:Runtime>(app:AppHandle<R>, webview:String, id:u32) {
+		crate::async_runtime::spawn(async move {
+			loop {
+				tokio::time::sleep(RESPONSE_STREAM_IDLE_TIMEOUT).await;
+
+				let registry = app.state::<ResponseStreamRegistry>();
+
+				if !registry.drop_if_idle(&webview, id, RESPONSE_STREAM_IDLE_TIMEOUT) {
+					break;
+				}
+			}
+		});
+	}
+
+	fn remove(&self, webview:&str, id:u32) {
+		self.streams.lock().unwrap().remove(&(webview.to_string(), id));
+	}
+
+	/// Records that the invoke reads its response as a stream if its headers ask
+	/// for it.
+	pub(crate) fn request(&self, webview:&str, callback:u32, headers:&http::HeaderMap) {
+		if headers.contains_key(ACCEPT_RESPONSE_STREAM_HEADER_NAME) {
+			self.requested.lock().unwrap().insert((webview.to_string(), callback));
+		}
+	}
+
+	/// Whether the pending invoke reads its response as a stream.
+	pub(crate) fn is_requested(&self, webview:&str, callback:u32) -> bool {
+		self.requested.lock().unwrap().contains(&(webview.to_string(), callback))
+	}
+
+	/// Forgets the invoke once it is responded to.
+	pub(crate) fn responded(&self, webview:&str, callback:u32) {
+		self.requested.lock().unwrap().remove(&(webview.to_string(), callback));
+	}
+
+	/// Drops every stream sent to the given webview.
+	pub(crate) fn close_webview(&self, webview:&str) {
+		self.streams.lock().unwrap().retain(|(label, _), _| label != webview);
+
+		self.requested.lock().unwrap().retain(|(label, _)| label != webview);
+	}
+}
+
+/// Reads the next chunk of the stream, an empty body meaning the stream ended.
+///
+/// The stream is dropped once it ends or errors, so its error is only
+/// received once.
+#[command(root = "crate")]
+pub(crate) async fn pull_response_chunk<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, ResponseStreamRegistry>,
+	id:u32,
+) -> Result<Response, InvokeError> {
+	let stream = registry
+		.get(webview.label(), id)
+		.ok_or_else(|| InvokeError::from("the response stream already ended"))?;
+
+	let mut stream = stream.lock().await;
+
+	loop {
+		match stream.0.next().await {
+			// an empty chunk would end the JavaScript stream
+			Some(Ok(chunk)) if chunk.is_empty() => {},
+			Some(Ok(chunk)) => return Ok(Response::new(chunk)),
+			Some(Err(error)) => {
+				registry.remove(webview.label(), id);
+
+				return Err(error);
+			},
+			None => {
+				registry.remove(webview.label(), id);
+
+				return Ok(Response::new(Vec::new()));
+			},
+		}
+	}
+}
+
+/// Drops the stream, called when the JavaScript stream is cancelled.
+#[command(root = "crate")]
+pub(crate) fn cancel_response_stream<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, ResponseStreamRegistry>,
+	id:u32,
+) {
+	registry.remove(webview.label(), id);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn async_read_is_chunked_until_eof() {
+		crate::async_runtime::block_on(async {
+			let data = vec![7u8; READ_CHUNK_SIZE + 1];
+
+			let mut stream = ResponseStream::from_async_read(std::io::Cursor::new(data));
+
+			let mut read = 0;
+
+			while let Some(chunk) = stream.0.next().await {
+				read += chunk.unwrap().len();
+			}
+
+			assert_eq!(read, READ_CHUNK_SIZE + 1);
+		});
+	}
+
+	#[test]
+	fn streams_are_only_requested_with_the_header() {
+		let registry = ResponseStreamRegistry::default();
+
+		let mut headers = http::HeaderMap::new();
+
+		registry.request("main", 1, &headers);
+
+		assert!(!registry.is_requested("main", 1));
+
+		headers.insert(ACCEPT_RESPONSE_STREAM_HEADER_NAME, http::HeaderValue::from_static("true"));
+
+		registry.request("main", 2, &headers);
+
+		assert!(registry.is_requested("main", 2));
+		assert!(!registry.is_requested("other", 2));
+
+		registry.responded("main", 2);
+
+		assert!(!registry.is_requested("main", 2));
+	}
+
+	#[test]
+	fn idle_streams_are_dropped() {
+		let registry = ResponseStreamRegistry::default();
+
+		let stream = ResponseStream::new(futures_util::stream::pending::<Result<Bytes, String>>());
+
+		let id = registry.insert("main", stream);
+
+		assert!(registry.drop_if_idle("main", id, Duration::from_secs(60)));
+
+		// a pull in progress keeps the stream
+		let pulling = registry.get("main", id).unwrap();
+
+		assert!(registry.drop_if_idle("main", id, Duration::ZERO));
+
+		drop(pulling);
+
+		assert!(!registry.drop_if_idle("main", id, Duration::ZERO));
+		assert!(registry.get("main", id).is_none());
+	}
+
+	#[test]
+	fn stream_errors_are_kept() {
+		crate::async_runtime::block_on(async {
+			let mut stream = ResponseStream::new(futures_util::stream::iter([
+				Ok(Bytes::from_static(b"a")),
+				Err("disk full"),
+			]));
+
+			assert_eq!(stream.0.next().await.unwrap().unwrap(), b"a");
+
+			assert_eq!(stream.0.next().await.unwrap().unwrap_err().0, serde_json::json!("disk full"));
+		});
+	}
+}
@@ -0,0 +1,334 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Streaming command responses.
+
+use std::{
+	collections::HashMap,
+	pin::Pin,
+	sync::{
+		Mutex,
+		atomic::{AtomicU64, Ordering},
+	},
+};
+
+use futures_util::{
+	Stream,
+	StreamExt,
+	future::{AbortHandle, abortable},
+};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::{CallbackFn, InvokeError, InvokeMessage, JavaScriptChannelId};
+use crate::{Manager, Runtime, State, Webview, command};
+
+/// The header `invoke` sets with the `responseType: 'iterator'` option to send
+/// the ID of the channel that receives the stream events.
+pub(crate) const STREAM_CHANNEL_HEADER_NAME:&str = "Tauri-Stream-Channel";
+pub const CANCEL_STREAM_COMMAND:&str = "plugin:__TAURI_CHANNEL__|cancel_stream";
+
+static STREAM_COUNTER:AtomicU64 = AtomicU64::new(0);
+
+/// A stream of values returned by a command.
+///
+/// Each item is sent to the JavaScript side as soon as it is produced, and
+/// `invoke` from `@tauri-apps/api/core` exposes them as an async iterator with
+/// the `responseType: 'iterator'` option. Polling stops when the iterator is
+/// dropped on the JavaScript side or when the webview navigates away.
+///
+/// Commands can also return any `impl Stream<Item = T>` directly, from async
+/// commands as well, this type is only needed when the stream can yield errors
+/// or must be named.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tauri::ipc::CommandStream;
+///
+/// #[tauri::command]
+/// fn read_lines(path:std::path::PathBuf) -> CommandStream<String> {
+/// 	let lines = std::fs::read_to_string(path)
+/// 		.map(|content| content.lines().map(|line| Ok(line.to_string())).collect::<Vec<_>>())
+/// 		.unwrap_or_else(|e| vec![Err(e.to_string())]);
+///
+/// 	CommandStream::try_new(futures_util::stream::iter(lines))
+/// }
+/// ```
+pub struct CommandStream<T> {
+	inner:Pin<Box<dyn Stream<Item = Result<T, InvokeError>> + Send>>,
+}
+
+impl<T> CommandStream<T> {
+	/// Creates a command stream that sends every item of the given stream.
+	pub fn new<S>(stream:S) -> Self
+	where
+		S: Stream<Item = T> + Send + 'static, {
+		Self { inner:Box::pin(stream.map(Ok)) }
+	}
+
+	/// Creates a command stream from a stream of results.
+	///
+	/// The stream ends after the first error, which is thrown by the
+	/// JavaScript iterator.
+	pub fn try_new<S, E>(stream:S) -> Self
+	where
+		S: Stream<Item = Result<T, E>> + Send + 'static,
+		E: Into<InvokeError>, {
+		Self { inner:Box::pin(stream.map(|item| item.map_err(Into::into))) }
+	}
+}
+
+/// An event sent to the stream channel.
+#[derive(Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+enum StreamEvent<T> {
+	Item(T),
+	Error(JsonValue),
+	End,
+}
+
+/// Running streams, keyed by webview label and channel ID.
+#[derive(Default)]
+pub(crate) struct StreamRegistry(Mutex<HashMap<(String, u32), (u64, AbortHandle)>>);
+
+impl StreamRegistry {
+	fn cancel(&self, webview:&str, id:u32) {
+		if let Some((_, handle)) = self.0.lock().unwrap().remove(&(webview.to_string(), id)) {
+			handle.abort();
+		}
+	}
+
+	/// Stops polling every stream that was started by the given webview.
+	pub(crate) fn cancel_webview(&self, webview:&str) {
+		self.0.lock().unwrap().retain(|(label, _), (_, handle)| {
+			if label == webview {
+				handle.abort();
+				false
+			} else {
+				true
+			}
+		});
+	}
+}
+
+/// Information required to forward a [`CommandStream`] to the webview.
+///
+/// Created by the [`command`](macro@crate::command) macro for every invoke.
+#[doc(hidden)]
+pub struct StreamContext<R:Runtime> {
+	webview:Webview<R>,
+	channel:Option<u32>,
+}
+
+impl<R:Runtime> Clone for StreamContext<R> {
+	fn clone(&self) -> Self { Self { webview:self.webview.clone(), channel:self.channel } }
+}
+
+impl<R:Runtime> StreamContext<R> {
+	pub fn from_message(message:&InvokeMessage<R>) -> Self {
+		Self {
+			webview:message.webview(),
+			channel:message
+				.headers()
+				.get(STREAM_CHANNEL_HEADER_NAME)
+				.and_then(|v| v.to_str().ok())
+				.and_then(|id| id.parse().ok()),
+		}
+	}
+
+	/// Spawns a task that forwards the stream items to the stream channel.
+	pub(crate) fn attach<T>(&self, stream:CommandStream<T>) -> Result<(), InvokeError>
+	where
+		T: Serialize + Send + 'static, {
+		let id = self.channel.ok_or_else(|| {
+			InvokeError::from(format!(
+				"command returns a stream but the `{STREAM_CHANNEL_HEADER_NAME}` header is missing, \
+				 use the `responseType: 'iterator'` invoke option to call it"
+			))
+		})?;
+
+		let channel = JavaScriptChannelId(CallbackFn(id))
+			.channel_on::<R, StreamEvent<T>>(self.webview.clone());
+
+		let (task, handle) = abortable(async move {
+			let mut stream = stream.inner;
+
+			while let Some(item) = stream.next().await {
+				let (event, is_error) = match item {
+					Ok(value) => (StreamEvent::Item(value), false),
+					Err(error) => (StreamEvent::Error(error.0), true),
+				};
+
+				// the webview is gone, there's no one left to read the stream
+				if channel.send(event).is_err() || is_error {
+					return;
+				}
+			}
+
+			let _ = channel.send(StreamEvent::End);
+		});
+
+		let key = (self.webview.label().to_string(), id);
+
+		let token = STREAM_COUNTER.fetch_add(1, Ordering::Relaxed);
+
+		self.webview
+			.state::<StreamRegistry>()
+			.0
+			.lock()
+			.unwrap()
+			.insert(key.clone(), (token, handle));
+
+		let webview = self.webview.clone();
+
+		crate::async_runtime::spawn(async move {
+			let _ = task.await;
+
+			let registry = webview.state::<StreamRegistry>();
+
+			let mut streams = registry.0.lock().unwrap();
+
+			if streams.get(&key).is_some_and(|(t, _)| *t == token) {
+				streams.remove(&key);
+			}
+		});
+
+		Ok(())
+	}
+}
+
+#[command(root = "crate")]
+pub(crate) fn cancel_stream<R:Runtime>(
+	webview:Webview<R>,
+	registry:State<'_, StreamRegistry>,
+	id:u32,
+) {
+	registry.cancel(webview.label(), id);
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::mpsc, time::Duration};
+
+	use futures_util::Stream;
+	use http::{HeaderMap, HeaderValue};
+	use serde_json::{Value as JsonValue, json};
+
+	use super::{STREAM_CHANNEL_HEADER_NAME, StreamEvent};
+	use crate::{
+		WebviewWindowBuilder,
+		ipc::{InvokeBody, InvokeDispatcher, InvokeResponse, TransportRequest},
+		test::{mock_builder, mock_context, noop_assets},
+	};
+
+	#[crate::command(root = "crate")]
+	fn count() -> impl Stream<Item = u32> { futures_util::stream::iter([1, 2]) }
+
+	#[crate::command(root = "crate")]
+	async fn count_async() -> impl Stream<Item = u32> { futures_util::stream::iter([1, 2]) }
+
+	/// Invokes the command with the stream channel header and collects the
+	/// events sent to the channel.
+	fn stream_events(cmd:&str, headers:HeaderMap) -> (Result<(), JsonValue>, Vec<JsonValue>) {
+		let (tx, rx) = mpsc::channel();
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![count, count_async])
+			.channel_interceptor(move |_webview, callback, _index, body| {
+				assert_eq!(callback.0, 7);
+
+				tx.send(body.deserialize::<JsonValue>().unwrap()).unwrap();
+
+				true
+			})
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let dispatcher = InvokeDispatcher::new(app.handle().clone(), "tauri://localhost".parse().unwrap());
+
+		let (response, _) = crate::async_runtime::block_on(dispatcher.invoke(TransportRequest {
+			webview:"main".into(),
+			cmd:cmd.into(),
+			body:InvokeBody::default(),
+			headers,
+		}))
+		.unwrap();
+
+		let result = match response {
+			InvokeResponse::Ok(_) => Ok(()),
+			InvokeResponse::Err(error) => Err(error.0),
+		};
+
+		let mut events = Vec::new();
+
+		if result.is_ok() {
+			while let Ok(event) = rx.recv_timeout(Duration::from_secs(5)) {
+				let end = event["event"] == "end";
+
+				events.push(event);
+
+				if end {
+					break;
+				}
+			}
+		}
+
+		(result, events)
+	}
+
+	fn channel_headers() -> HeaderMap {
+		let mut headers = HeaderMap::new();
+
+		headers.insert(STREAM_CHANNEL_HEADER_NAME, HeaderValue::from_static("7"));
+
+		headers
+	}
+
+	#[test]
+	fn stream_items_are_sent_to_the_channel() {
+		for cmd in ["count", "count_async"] {
+			let (result, events) = stream_events(cmd, channel_headers());
+
+			assert_eq!(result, Ok(()));
+			assert_eq!(
+				events,
+				vec![
+					json!({ "event": "item", "data": 1 }),
+					json!({ "event": "item", "data": 2 }),
+					json!({ "event": "end" }),
+				],
+				"{cmd}"
+			);
+		}
+	}
+
+	#[test]
+	fn streams_require_the_channel_header() {
+		let (result, events) = stream_events("count", HeaderMap::new());
+
+		assert!(result.unwrap_err().as_str().unwrap().contains(STREAM_CHANNEL_HEADER_NAME));
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn stream_event_format() {
+		assert_eq!(
+			serde_json::to_value(StreamEvent::Item(1)).unwrap(),
+			serde_json::json!({ "event": "item", "data": 1 })
+		);
+
+		assert_eq!(
+			serde_json::to_value(StreamEvent::<()>::Error("failed".into())).unwrap(),
+			serde_json::json!({ "event": "error", "data": "failed" })
+		);
+
+		assert_eq!(
+			serde_json::to_value(StreamEvent::<()>::End).unwrap(),
+			serde_json::json!({ "event": "end" })
+		);
+	}
+}
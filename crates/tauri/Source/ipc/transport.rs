@@ -0,0 +1,400 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Alternative transports for the invokes, e.g. to serve a frontend rendered
+//! in a browser.
+
+use std::sync::Arc;
+
+use http::HeaderMap;
+use serialize_to_javascript::{Template, default_template};
+use url::Url;
+
+use super::{CallbackFn, InvokeBody, InvokeError, InvokeResponse};
+use crate::{AppHandle, Manager, Runtime, Webview, webview::InvokeRequest};
+
+/// Evaluates a script in a remote frontend.
+pub(crate) type RemoteSender = dyn Fn(String) + Send + Sync;
+
+/// Carries invokes to the backend over a channel other than the webview
+/// custom protocol, such as a WebSocket connection to a frontend running in a
+/// regular browser.
+///
+/// The requests go through the same runtime authority as the webview invokes,
+/// with the [`origin`](Self::origin) of the transport as the origin of the
+/// request, so capabilities with matching `remote.urls` scope what the
+/// transport can call.
+///
+/// Register it with [`Builder::invoke_system`](crate::Builder::invoke_system)
+/// and [`InvokeSystem::transport`].
+///
+/// Every frontend is connected with [`InvokeDispatcher::connect`], which gives
+/// it its own webview label. Its events and channel messages are sent back
+/// over its connection, so `@tauri-apps/api` works unchanged.
+pub trait InvokeTransport<R:Runtime>: Send + Sync + 'static {
+	/// The origin of the requests received by the transport.
+	fn origin(&self) -> Url;
+
+	/// The script that replaces `window.__TAURI_INTERNALS__.postMessage` in the
+	/// webviews, usually created with [`transport_script`].
+	///
+	/// Returns `None` by default, keeping the built-in transport in the
+	/// webviews, for transports that only serve remote frontends.
+	fn initialization_script(&self) -> Option<String> { None }
+
+	/// Starts receiving requests, called once when the app is built.
+	fn start(&self, dispatcher:InvokeDispatcher<R>);
+}
+
+/// The invoke system of the app, see
+/// [`Builder::invoke_system`](crate::Builder::invoke_system).
+pub struct InvokeSystem<R:Runtime> {
+	pub(crate) initialization_script:Option<String>,
+	pub(crate) transport:Option<Box<dyn InvokeTransport<R>>>,
+}
+
+impl<R:Runtime> InvokeSystem<R> {
+	/// An invoke system that receives the requests with the given transport.
+	pub fn transport<T:InvokeTransport<R>>(transport:T) -> Self {
+		Self {
+			initialization_script:transport.initialization_script(),
+			transport:Some(Box::new(transport)),
+		}
+	}
+}
+
+impl<R:Runtime> From<String> for InvokeSystem<R> {
+	fn from(initialization_script:String) -> Self {
+		Self { initialization_script:Some(initialization_script), transport:None }
+	}
+}
+
+impl<R:Runtime> From<&str> for InvokeSystem<R> {
+	fn from(initialization_script:&str) -> Self { initialization_script.to_string().into() }
+}
+
+/// An invoke received by an [`InvokeTransport`].
+#[derive(Debug)]
+pub struct TransportRequest {
+	/// The invoke command, e.g. `greet` or `plugin:event|listen`.
+	pub cmd:String,
+	/// The body of the request.
+	pub body:InvokeBody,
+	/// The request headers.
+	pub headers:HeaderMap,
+}
+
+/// Connects the frontends of an [`InvokeTransport`] to the app.
+pub struct InvokeDispatcher<R:Runtime> {
+	app:AppHandle<R>,
+	origin:Url,
+}
+
+impl<R:Runtime> Clone for InvokeDispatcher<R> {
+	fn clone(&self) -> Self { Self { app:self.app.clone(), origin:self.origin.clone() } }
+}
+
+impl<R:Runtime> InvokeDispatcher<R> {
+	pub(crate) fn new(app:AppHandle<R>, origin:Url) -> Self { Self { app, origin } }
+
+	/// The app handle.
+	pub fn app_handle(&self) -> &AppHandle<R> { &self.app }
+
+	/// Connects a frontend hosted by the window with the given label.
+	///
+	/// The commands it invokes receive a [`Webview`] labeled `label` and the
+	/// host [`Window`](crate::Window), so capabilities scope it with their
+	/// `webviews` or `windows` along with `remote.urls`. The scripts delivering
+	/// its events and channel messages are given to `send`, which must evaluate
+	/// them in the frontend.
+	///
+	/// Fails if the window does not exist or if `label` is already used by a
+	/// webview or another connection.
+	pub fn connect<F>(
+		&self,
+		window:&str,
+		label:impl Into<String>,
+		send:F,
+	) -> crate::Result<RemoteConnection<R>>
+	where
+		F: Fn(String) + Send + Sync + 'static, {
+		let label = label.into();
+
+		let window = self.app.get_window(window).ok_or(crate::Error::WindowNotFound)?;
+
+		let webview = Webview::remote(window, label.clone(), Arc::new(send))?;
+
+		let manager = &self.app.manager;
+
+		// same lock order as the webview creation
+		let webviews = manager.webview.webviews_lock();
+
+		let mut remote_webviews = manager.webview.remote_webviews_lock();
+
+		if webviews.contains_key(&label) || remote_webviews.contains_key(&label) {
+			return Err(crate::Error::WebviewLabelAlreadyExists(label));
+		}
+
+		remote_webviews.insert(label, webview.clone());
+
+		Ok(RemoteConnection { webview, origin:self.origin.clone() })
+	}
+}
+
+/// A frontend connected with [`InvokeDispatcher::connect`].
+///
+/// Dropping it cancels the running invokes of the frontend and removes its
+/// event listeners.
+pub struct RemoteConnection<R:Runtime> {
+	webview:Webview<R>,
+	origin:Url,
+}
+
+impl<R:Runtime> RemoteConnection<R> {
+	/// The webview label of the frontend.
+	pub fn label(&self) -> &str { self.webview.label() }
+
+	/// Runs the command, calling `respond` with its response and the headers
+	/// the command attached to it.
+	pub fn dispatch<F>(&self, request:TransportRequest, respond:F)
+	where
+		F: FnOnce(InvokeResponse, HeaderMap) + Send + 'static, {
+		let request = InvokeRequest {
+			cmd:request.cmd,
+			// the responder gets the response directly, the callbacks are never called
+			callback:CallbackFn(0),
+			error:CallbackFn(1),
+			url:self.origin.clone(),
+			body:request.body,
+			headers:request.headers,
+			invoke_key:self.webview.manager.invoke_key().to_string(),
+		};
+
+		self.webview.clone().on_message(
+			request,
+			Box::new(move |_, _, response, _, _| respond(response, super::take_response_headers())),
+		);
+	}
+
+	/// Runs the command and waits for its response and the headers the command
+	/// attached to it.
+	pub async fn invoke(&self, request:TransportRequest) -> (InvokeResponse, HeaderMap) {
+		let (tx, rx) = tokio::sync::oneshot::channel();
+
+		self.dispatch(request, move |response, headers| {
+			let _ = tx.send((response, headers));
+		});
+
+		rx.await.unwrap_or_else(|_| {
+			let error = InvokeError::from("the command was dropped without responding");
+
+			(InvokeResponse::Err(error), HeaderMap::new())
+		})
+	}
+}
+
+impl<R:Runtime> Drop for RemoteConnection<R> {
+	fn drop(&mut self) { self.webview.manager.on_remote_webview_close(self.webview.label()); }
+}
+
+#[derive(Template)]
+#[default_template("../../scripts/ipc-transport.js")]
+struct TransportScript<'a> {
+	#[raw]
+	send_fn:&'a str,
+	standalone:bool,
+	window_label:Option<&'a str>,
+	webview_label:Option<&'a str>,
+}
+
+/// The script that sends the webview invokes with the given JavaScript
+/// function, to return from [`InvokeTransport::initialization_script`].
+///
+/// The function is called with `{ cmd, payload, headers }` and must return a
+/// promise resolving to `{ ok, data, headers }`, `ok` being `false` when the
+/// command rejected.
+pub fn transport_script(send_fn:&str) -> crate::Result<String> {
+	let script =
+		TransportScript { send_fn, standalone:false, window_label:None, webview_label:None }
+			.render_default(&Default::default())?;
+
+	Ok(script.into_string())
+}
+
+/// The script a frontend running in a regular browser loads before
+/// `@tauri-apps/api` to send its invokes with the given JavaScript function,
+/// see [`transport_script`].
+///
+/// `window` and `webview` are the labels the frontend is connected with, see
+/// [`InvokeDispatcher::connect`]. The scripts the connection sends must be
+/// evaluated in the page.
+pub fn transport_frontend_script(send_fn:&str, window:&str, webview:&str) -> crate::Result<String> {
+	let script = TransportScript {
+		send_fn,
+		standalone:true,
+		window_label:Some(window),
+		webview_label:Some(webview),
+	}
+	.render_default(&Default::default())?;
+
+	Ok(script.into_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use serde_json::json;
+
+	use super::*;
+	use crate::{
+		Emitter,
+		WebviewWindowBuilder,
+		command,
+		event::EventTarget,
+		ipc::{
+			Channel,
+			InvokeResponseBody,
+			channel::{CHANNEL_ID_HEADER_NAME, FETCH_CHANNEL_DATA_COMMAND},
+		},
+		test::{MockRuntime, mock_builder, mock_context, noop_assets},
+	};
+
+	struct TestTransport(Arc<Mutex<Option<InvokeDispatcher<MockRuntime>>>>);
+
+	impl InvokeTransport<MockRuntime> for TestTransport {
+		fn origin(&self) -> Url { "http://kiosk.local".parse().unwrap() }
+
+		fn start(&self, dispatcher:InvokeDispatcher<MockRuntime>) {
+			self.0.lock().unwrap().replace(dispatcher);
+		}
+	}
+
+	#[command(root = "crate")]
+	fn greet<R:Runtime>(webview:Webview<R>, name:String) -> String {
+		format!("{name} from {}", webview.label())
+	}
+
+	#[command(root = "crate")]
+	fn subscribe<R:Runtime>(webview:Webview<R>) -> crate::Result<u32> {
+		webview.listen_js("ping", EventTarget::Any, CallbackFn(7), None)
+	}
+
+	#[command(root = "crate")]
+	fn count(on_item:Channel<u32>) -> crate::Result<()> { on_item.send(1) }
+
+	fn invoke(
+		connection:&RemoteConnection<MockRuntime>,
+		cmd:&str,
+		body:InvokeBody,
+		headers:HeaderMap,
+	) -> Result<InvokeResponseBody, InvokeError> {
+		let request = TransportRequest { cmd:cmd.into(), body, headers };
+
+		match crate::async_runtime::block_on(connection.invoke(request)).0 {
+			InvokeResponse::Ok(body) => Ok(body),
+			InvokeResponse::Err(error) => Err(error),
+		}
+	}
+
+	#[test]
+	fn remote_frontends_get_their_responses_events_and_channels() {
+		let dispatcher = Arc::new(Mutex::new(None));
+
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![greet, subscribe, count])
+			.invoke_system(InvokeSystem::transport(TestTransport(dispatcher.clone())))
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let main = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let dispatcher = dispatcher.lock().unwrap().clone().expect("the transport was not started");
+
+		let scripts = Arc::new(Mutex::new(Vec::<String>::new()));
+
+		let scripts_ = scripts.clone();
+
+		let connection = dispatcher
+			.connect("main", "kiosk", move |script| scripts_.lock().unwrap().push(script))
+			.unwrap();
+
+		// the labels are unique across the webviews and the connections
+		assert!(dispatcher.connect("main", "main", |_| {}).is_err());
+		assert!(dispatcher.connect("main", "kiosk", |_| {}).is_err());
+		assert!(dispatcher.connect("missing", "other", |_| {}).is_err());
+
+		let greeting =
+			invoke(&connection, "greet", json!({ "name": "tauri" }).into(), HeaderMap::new())
+				.unwrap()
+				.deserialize::<String>()
+				.unwrap();
+
+		assert_eq!(greeting, "tauri from kiosk");
+
+		// the event reaches the connection, not the webview of its window
+		invoke(&connection, "subscribe", json!({}).into(), HeaderMap::new()).unwrap();
+
+		let evaluated = || main.webview.webview.dispatcher.state().evaluated_scripts.len();
+
+		let evaluated_before = evaluated();
+
+		scripts.lock().unwrap().clear();
+
+		app.emit("ping", 1).unwrap();
+
+		assert!(!scripts.lock().unwrap().is_empty());
+		assert_eq!(evaluated(), evaluated_before);
+
+		// the channel message is fetched over the connection
+		scripts.lock().unwrap().clear();
+
+		invoke(&connection, "count", json!({ "onItem": "__CHANNEL__:9" }).into(), HeaderMap::new())
+			.unwrap();
+
+		let script = scripts.lock().unwrap().pop().expect("the channel message was not sent");
+
+		let data_id = script
+			.split(&format!("'{CHANNEL_ID_HEADER_NAME}': '"))
+			.nth(1)
+			.and_then(|rest| rest.split('\'').next())
+			.expect("the script does not fetch the channel data")
+			.to_string();
+
+		let mut headers = HeaderMap::new();
+
+		headers.insert(CHANNEL_ID_HEADER_NAME, data_id.parse().unwrap());
+
+		let message = invoke(&connection, FETCH_CHANNEL_DATA_COMMAND, json!(null).into(), headers)
+			.unwrap()
+			.deserialize::<u32>()
+			.unwrap();
+
+		assert_eq!(message, 1);
+
+		// the listeners of a dropped connection are removed
+		drop(connection);
+
+		scripts.lock().unwrap().clear();
+
+		app.emit("ping", 2).unwrap();
+
+		assert!(scripts.lock().unwrap().is_empty());
+
+		assert!(dispatcher.connect("main", "kiosk", |_| {}).is_ok());
+	}
+
+	#[test]
+	fn scripts_call_the_send_function() {
+		let script = transport_script("window.__SEND__").unwrap();
+
+		assert!(script.contains("window.__SEND__"));
+		assert!(script.contains("const standalone = false"));
+
+		let script = transport_frontend_script("window.__SEND__", "main", "kiosk").unwrap();
+
+		assert!(script.contains("const standalone = true"));
+		assert!(script.contains(r#"label: "kiosk""#));
+	}
+}
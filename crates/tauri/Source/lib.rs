@@ -34,6 +34,9 @@
 //! - **image-ico**: Adds support to parse `.ico` image, see [`Image`].
 //! - **image-png**: Adds support to parse `.png` image, see [`Image`].
 //! - **macos-proxy**: Adds support for [`WebviewBuilder::proxy_url`] on macOS. Requires macOS 14+.
+//! - **command-metadata**: Makes the [`command`] macro generate a [`ipc::CommandMeta`] for every command, available via [`ipc::command_metadata`] and [`ipc::HandlerCollection::metadata`].
+//! - **single-instance**: Enables [`Builder::single_instance`] to only let one instance of the app run on desktop.
+//! - **opener**: Enables the [`opener`] module and the `core:opener` commands to open URLs and paths with the default system handler.
 //! - **specta**: Add support for [`specta::specta`](https://docs.rs/specta/%5E2.0.0-rc.9/specta/attr.specta.html) with Tauri arguments such as [`State`](crate::State), [`Window`](crate::Window) and [`AppHandle`](crate::AppHandle)
 //!
 //! ## Cargo allowlist features
@@ -72,7 +75,7 @@ pub use swift_rs;
 pub use tauri_macros::include_image;
 #[cfg(mobile)]
 pub use tauri_macros::mobile_entry_point;
-pub use tauri_macros::{command, generate_handler};
+pub use tauri_macros::{CommandError, command, generate_handler, generate_handler_collection};
 
 use tauri_utils::assets::AssetsIter;
 pub use url::Url;
@@ -97,6 +100,9 @@ mod ios;
 #[cfg(desktop)]
 #[cfg_attr(docsrs, doc(cfg(desktop)))]
 pub mod menu;
+#[cfg(feature = "opener")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opener")))]
+pub mod opener;
 /// Path APIs.
 pub mod path;
 pub mod process;
@@ -208,9 +214,13 @@ pub use runtime::ActivationPolicy;
 #[cfg(target_os = "macos")]
 pub use self::utils::TitleBarStyle;
 
-pub use self::event::{Event, EventId, EventTarget};
+pub use self::event::{
+  EmitCompletionGuard, EmitReport, Event, EventId, EventPayload, EventPayloadRef,
+  EventReplayConfig, EventTarget, DEFAULT_EMIT_WAIT_TIMEOUT, EVENT_NAME_SEPARATOR,
+};
 pub use {
   self::app::{
+    metrics::{AppMetrics, WebviewMetrics},
     App, AppHandle, AssetResolver, Builder, CloseRequestApi, RunEvent, UriSchemeContext,
     UriSchemeResponder, WebviewEvent, WindowEvent,
   },
@@ -221,7 +231,7 @@ pub use {
     window::{CursorIcon, DragDropEvent, WindowSizeConstraints},
     DeviceEventFilter, Rect, UserAttentionType,
   },
-  self::state::{State, StateManager},
+  self::state::{State, StateManager, WindowState},
   self::utils::{
     config::{Config, WebviewUrl},
     Env, PackageInfo, Theme,
@@ -715,7 +725,193 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
     self.manager().state().set(state)
   }
 
-  /// Removes the state managed by the application for T. Returns the state if it was actually removed.
+  /// Add `state` to the state managed by the application, giving it back if a state of the same
+  /// type is already managed.
+  ///
+  /// See [`Self::manage`] for more information.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Manager;
+  ///
+  /// struct Counter(u32);
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   assert!(app.try_manage(Counter(0)).is_ok());
+  ///   let rejected = app.try_manage(Counter(1)).unwrap_err();
+  ///   assert_eq!(rejected.0, 1);
+  ///   Ok(())
+  /// });
+  /// ```
+  fn try_manage<T>(&self, state: T) -> std::result::Result<(), T>
+  where
+    T: Send + Sync + 'static,
+  {
+    self.manager().state().try_set(state)
+  }
+
+  /// Add the value the `init` future resolves to to the state managed by the application.
+  ///
+  /// The future is lazy: it is spawned on the [`async_runtime`] by the first access to the
+  /// state. Async commands declaring a [`State<'_, T>`](State) argument wait for the
+  /// initialization before running, and [`Self::state_async`] waits for it from Rust. Once the
+  /// state is ready, accessing it is as cheap as for [managed](Self::manage) states. Until then
+  /// [`Self::state`] panics, [`Self::try_state`] returns `None` and synchronous commands using it
+  /// fail, all of them reporting that the state is still initializing.
+  ///
+  /// Commands only wait for the states whose type doesn't use their generic parameters: a command
+  /// declaring a `State<'_, Pool<R>>` argument fails while the state is initializing, and should
+  /// take an [`AppHandle`] and call [`Self::state_async`] instead.
+  ///
+  /// [Unmanaging](Self::unmanage) the state cancels its initialization. A failed initialization
+  /// can be replaced by managing the type again.
+  ///
+  /// Returns `false` if a state for the type `T` is already managed or initializing.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::{Manager, State};
+  ///
+  /// struct Database { url: String }
+  ///
+  /// async fn connect(url: &str) -> Database {
+  ///   Database { url: url.into() }
+  /// }
+  ///
+  /// #[tauri::command]
+  /// async fn database_url(db: State<'_, Database>) -> Result<String, ()> {
+  ///   // only runs once `connect` resolved
+  ///   Ok(db.url.clone())
+  /// }
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.manage_async(connect("sqlite://app.db"));
+  ///     Ok(())
+  ///   })
+  ///   .invoke_handler(tauri::generate_handler![database_url]);
+  /// ```
+  fn manage_async<T, F>(&self, init: F) -> bool
+  where
+    T: Send + Sync + 'static,
+    F: std::future::Future<Output = T> + Send + 'static,
+  {
+    self.manage_try_async(async move { Ok::<_, ipc::InvokeError>(init.await) })
+  }
+
+  /// Add the value the fallible `init` future resolves to to the state managed by the
+  /// application.
+  ///
+  /// When the initialization fails, the state is never managed and its error is returned to
+  /// every command using it, as an [`InvokeError`](ipc::InvokeError), and by
+  /// [`Self::state_async`].
+  ///
+  /// See [`Self::manage_async`] for more information.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Manager;
+  ///
+  /// struct Config(String);
+  ///
+  /// tauri::Builder::default().setup(|app| {
+  ///   let path = app.path().app_config_dir()?.join("config.json");
+  ///   app.manage_try_async(async move {
+  ///     std::fs::read_to_string(path)
+  ///       .map(Config)
+  ///       .map_err(|e| format!("failed to read the config: {e}"))
+  ///   });
+  ///   Ok(())
+  /// });
+  /// ```
+  fn manage_try_async<T, E, F>(&self, init: F) -> bool
+  where
+    T: Send + Sync + 'static,
+    E: Into<ipc::InvokeError>,
+    F: std::future::Future<Output = std::result::Result<T, E>> + Send + 'static,
+  {
+    self.manager().state().set_async(init)
+  }
+
+  /// Waits for the managed state for the type `T`, driving its initialization if it was
+  /// [managed asynchronously](Self::manage_async).
+  ///
+  /// Resolves to an error if the initialization failed or if the state isn't managed.
+  fn state_async<T>(
+    &self,
+  ) -> impl std::future::Future<Output = std::result::Result<State<'_, T>, ipc::InvokeError>>
+       + Send
+       + '_
+  where
+    T: Send + Sync + 'static,
+  {
+    let state = &*self.manager().state;
+    let ready = state.ready::<T>();
+
+    async move {
+      ready.await?;
+
+      state
+        .try_get()
+        .ok_or_else(|| ipc::InvokeError::from(state.missing_reason::<T>()))
+    }
+  }
+
+  /// Atomically replaces the state managed by the application for `T`, returning the previous
+  /// value if there was one.
+  ///
+  /// The [`State`] guards obtained before the swap keep reading the previous value, which is
+  /// dropped along with the last of them. A value borrowed with [`State::inner`] is kept alive
+  /// until the application exits instead, since the borrow may outlive its guard. Release its
+  /// resources explicitly through the returned [`Arc`](std::sync::Arc) if needed, e.g. by
+  /// closing a connection pool.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Manager;
+  ///
+  /// struct Database { profile: String }
+  ///
+  /// #[tauri::command]
+  /// fn switch_profile(app: tauri::AppHandle, profile: String) {
+  ///   if let Some(previous) = app.replace_state(Database { profile }) {
+  ///     println!("closing the {} database", previous.profile);
+  ///   }
+  /// }
+  /// ```
+  fn replace_state<T>(&self, state: T) -> Option<std::sync::Arc<T>>
+  where
+    T: Send + Sync + 'static,
+  {
+    self.manager().state().replace(state)
+  }
+
+  /// Removes the state managed by the application for `T`, e.g. to reclaim the memory of a cache
+  /// tied to a user session.
+  ///
+  /// Returns the state if no [`State`] guard holds it anymore. Otherwise `None` is returned and
+  /// the state is dropped with the last guard: the commands currently holding it keep reading it
+  /// until they return, the later ones fail.
+  ///
+  /// Once unmanaged, [`state`](Self::state) panics and [`try_state`](Self::try_state) returns
+  /// `None` until the type is managed again.
+  ///
+  /// The borrows obtained with [`State::inner`] may outlive their guard, so a state borrowed that
+  /// way is not returned and is only dropped with the app.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Manager;
+  ///
+  /// struct SessionCache { entries: Vec<String> }
+  ///
+  /// #[tauri::command]
+  /// fn logout(app: tauri::AppHandle) {
+  ///   if let Some(cache) = app.unmanage::<SessionCache>() {
+  ///     println!("dropping {} cached entries", cache.entries.len());
+  ///   }
+  /// }
+  /// ```
   fn unmanage<T>(&self) -> Option<T>
   where
     T: Send + Sync + 'static,
@@ -727,22 +923,20 @@ pub trait Manager<R: Runtime>: sealed::ManagerBase<R> {
   ///
   /// # Panics
   ///
-  /// Panics if the state for the type `T` has not been previously [managed](Self::manage).
-  /// Use [try_state](Self::try_state) for a non-panicking version.
+  /// Panics if the state for the type `T` has not been previously [managed](Self::manage), was
+  /// [unmanaged](Self::unmanage) or is [managed asynchronously](Self::manage_async) and still
+  /// initializing. Use [try_state](Self::try_state) for a non-panicking version.
   fn state<T>(&self) -> State<'_, T>
   where
     T: Send + Sync + 'static,
   {
-    self
-      .manager()
-      .state
-      .try_get()
-      .expect("state() called before manage() for given type")
+    self.manager().state.get()
   }
 
   /// Attempts to retrieve the managed state for the type `T`.
   ///
-  /// Returns `Some` if the state has previously been [managed](Self::manage). Otherwise returns `None`.
+  /// Returns `Some` if the state has previously been [managed](Self::manage) and not
+  /// [unmanaged](Self::unmanage). Otherwise returns `None`.
   fn try_state<T>(&self) -> Option<State<'_, T>>
   where
     T: Send + Sync + 'static,
@@ -855,6 +1049,78 @@ pub trait Listener<R: Runtime>: sealed::ManagerBase<R> {
   where
     F: FnOnce(Event) + Send + 'static;
 
+  /// Listen to an emitted event on this manager, only calling `handler` with the events accepted
+  /// by `filter`.
+  ///
+  /// The filter runs when the event is emitted, before the payload is cloned for the handler, so
+  /// it can cheaply discard high-frequency events by looking at the raw payload and the target
+  /// they were emitted to.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Listener;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     app.listen_filter(
+  ///       "download://progress",
+  ///       |event| event.payload().starts_with(r#"{"id":1,"#),
+  ///       |event| {
+  ///         println!("download 1 progressed: {}", event.payload());
+  ///       },
+  ///     );
+  ///     Ok(())
+  ///   });
+  /// ```
+  fn listen_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: Fn(Event) + Send + 'static;
+
+  /// Listen to the first event accepted by `filter` on this manager.
+  ///
+  /// See [`Self::listen_filter`] for more information.
+  fn once_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: FnOnce(Event) + Send + 'static;
+
+  /// Listen to every event whose name starts with `prefix`, which must end with the
+  /// [`EVENT_NAME_SEPARATOR`].
+  ///
+  /// This is the same as listening to the `prefix*` pattern, [`Event::event`] is the full name
+  /// of the emitted event.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the prefix doesn't end with the separator.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Listener;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     // receives `sync:error` and `sync:file:progress`
+  ///     app.listen_prefix("sync:", |event| {
+  ///       println!("{}: {}", event.event(), event.payload());
+  ///     });
+  ///     Ok(())
+  ///   });
+  /// ```
+  fn listen_prefix<F>(&self, prefix: impl Into<String>, handler: F) -> EventId
+  where
+    F: Fn(Event) + Send + 'static,
+  {
+    let mut pattern = prefix.into();
+    assert!(
+      pattern.ends_with(EVENT_NAME_SEPARATOR),
+      "Event prefix `{pattern}` must end with `{EVENT_NAME_SEPARATOR}`."
+    );
+    pattern.push('*');
+    self.listen(pattern, handler)
+  }
+
   /// Remove an event listener.
   ///
   /// # Examples
@@ -987,6 +1253,137 @@ pub trait Emitter<R: Runtime>: sealed::ManagerBase<R> {
   where
     S: Serialize + Clone,
     F: Fn(&EventTarget) -> bool;
+
+  /// Emits an event to all [targets](EventTarget) matching any of the given targets, serializing
+  /// the payload once.
+  ///
+  /// The listeners matching several of the targets get the event once. Returns the number of
+  /// targets that have a listener for the event, the event is not emitted if there is none.
+  /// The listeners of any target, see [`Listener::listen_any`], get the event too but aren't
+  /// counted.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::{Emitter, EventTarget};
+  ///
+  /// #[tauri::command]
+  /// fn sync_settings(app: tauri::AppHandle) {
+  ///   let targets = ["main", "settings", "preview"].map(EventTarget::labeled);
+  ///   let received = app.emit_to_many(targets, "settings-changed", ()).unwrap();
+  ///   println!("{received} windows were notified");
+  /// }
+  /// ```
+  fn emit_to_many<I, S>(&self, targets: I, event: &str, payload: S) -> Result<usize>
+  where
+    I: IntoIterator<Item = EventTarget>,
+    S: Serialize + Clone,
+  {
+    self
+      .manager()
+      .emit_to_many(targets.into_iter().collect(), event, payload)
+  }
+
+  /// Emits an event with a binary payload to all [targets](EventTarget) matching the given target,
+  /// skipping the JSON serialization.
+  ///
+  /// Rust listeners get the bytes as [`EventPayload::Binary`] from [`Event::data`] and JavaScript
+  /// listeners get a `Uint8Array` payload, fetched by each webview as a raw IPC response. The bytes
+  /// are shared by all of them and only copied into the responses, and the JavaScript listeners
+  /// still get the binary and JSON events in the order they were emitted.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::{Emitter, EventTarget};
+  ///
+  /// #[tauri::command]
+  /// fn stream_waveform(app: tauri::AppHandle, chunk: Vec<u8>) {
+  ///   app.emit_bytes("waveform", EventTarget::labeled("main"), chunk);
+  /// }
+  /// ```
+  fn emit_bytes<'a, I>(
+    &self,
+    event: &str,
+    target: I,
+    bytes: impl Into<Cow<'a, [u8]>>,
+  ) -> Result<()>
+  where
+    I: Into<EventTarget>,
+  {
+    // owned bytes are moved, not copied, and then shared by all the listeners
+    let bytes = bytes::Bytes::from(bytes.into().into_owned());
+
+    self.manager().emit_bytes_to(target.into(), event, bytes)
+  }
+
+  /// Emits an event to all [targets](EventTarget), resolving once every listener handled it or
+  /// once the [`DEFAULT_EMIT_WAIT_TIMEOUT`] elapses.
+  ///
+  /// The Rust listeners handled the event once they returned and dropped the guards they took
+  /// with [`Event::completion_guard`]. The JavaScript listeners handled it once their returned
+  /// promises settled, each webview acknowledging the event. The [`EmitReport`] lists the webviews
+  /// that didn't acknowledge it in time.
+  ///
+  /// The event is dispatched before the future is polled. When it is called from a listener of the
+  /// same event, the Rust listeners only run after the calling listener returns, so the listener
+  /// must not block on the future.
+  ///
+  /// An event buffered for its [replay](crate::Builder::event_replay) because nothing listens to it
+  /// yet resolves right away.
+  ///
+  /// # Examples
+  /// ```
+  /// use tauri::Emitter;
+  ///
+  /// async fn flush_state(app: tauri::AppHandle) {
+  ///   let report = app.emit_and_wait("flush-state", ()).await.unwrap();
+  ///   if !report.is_complete() {
+  ///     eprintln!("{:?} did not flush their state", report.unacknowledged);
+  ///   }
+  /// }
+  /// ```
+  fn emit_and_wait<S>(
+    &self,
+    event: &str,
+    payload: S,
+  ) -> impl std::future::Future<Output = Result<EmitReport>> + Send + 'static
+  where
+    S: Serialize + Clone,
+  {
+    self.emit_and_wait_timeout(event, payload, DEFAULT_EMIT_WAIT_TIMEOUT)
+  }
+
+  /// Same as [`Self::emit_and_wait`], with the given timeout.
+  fn emit_and_wait_timeout<S>(
+    &self,
+    event: &str,
+    payload: S,
+    timeout: std::time::Duration,
+  ) -> impl std::future::Future<Output = Result<EmitReport>> + Send + 'static
+  where
+    S: Serialize + Clone,
+  {
+    self.emit_to_and_wait(EventTarget::Any, event, payload, timeout)
+  }
+
+  /// Same as [`Self::emit_and_wait_timeout`], emitting the event to the given [target](EventTarget)
+  /// like [`Self::emit_to`].
+  fn emit_to_and_wait<I, S>(
+    &self,
+    target: I,
+    event: &str,
+    payload: S,
+    timeout: std::time::Duration,
+  ) -> impl std::future::Future<Output = Result<EmitReport>> + Send + 'static
+  where
+    I: Into<EventTarget>,
+    S: Serialize + Clone,
+  {
+    let report = self
+      .manager()
+      .emit_and_wait(target.into(), event, payload, timeout);
+
+    async move { Ok(report?.await) }
+  }
 }
 
 /// Prevent implementation details from leaking out of the [`Manager`] trait.
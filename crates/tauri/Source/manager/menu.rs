@@ -3,17 +3,78 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	sync::{Arc, Mutex, MutexGuard},
 };
 
+use muda::accelerator::Accelerator;
+
 use crate::{
 	AppHandle,
 	Runtime,
 	Window,
-	menu::{Menu, MenuEvent, MenuId},
+	menu::{
+		CheckMenuItemGroup,
+		Menu,
+		MenuEvent,
+		MenuId,
+		MenuItemKey,
+		MenuItemKind,
+		MenuItemMetadata,
+		MenuRoot,
+	},
 };
 
+/// The state of a native menu, submenu or item that can't be stored in the
+/// native item, kept until the item is dropped.
+pub(crate) struct ItemState<R:Runtime> {
+	id:MenuId,
+	/// The number of live wrappers of the item, e.g. [`MenuItem`]s. The state is
+	/// dropped once there are none and the item isn't in a live menu.
+	///
+	/// [`MenuItem`]: crate::menu::MenuItem
+	wrappers:usize,
+	pub(crate) metadata:MenuItemMetadata,
+	/// See [`MenuItemBuilder::on_click`](crate::menu::MenuItemBuilder::on_click).
+	pub(crate) click_handler:Option<crate::app::MenuItemClickHandler<R>>,
+	/// The group the item was last added to, see [`CheckMenuItemGroup`].
+	pub(crate) check_group:Option<CheckMenuItemGroup<R>>,
+	/// Whether the menu or submenu was built with mnemonics disabled, see
+	/// [`MenuBuilder::mnemonics`](crate::menu::MenuBuilder::mnemonics).
+	pub(crate) mnemonics_disabled:bool,
+	/// Whether the menu or submenu allows items with the same accelerator, see
+	/// [`MenuBuilder::allow_duplicate_accelerators`](crate::menu::MenuBuilder::allow_duplicate_accelerators).
+	pub(crate) duplicate_accelerators_allowed:bool,
+}
+
+/// The keys of the items without a live wrapper.
+///
+/// The wrappers of a check group only held by the state of its items don't
+/// count, they must not keep the items alive on their own.
+fn items_without_wrappers<R:Runtime>(
+	items:&HashMap<MenuItemKey, ItemState<R>>,
+) -> HashSet<MenuItemKey> {
+	let mut group_states = HashMap::new();
+
+	for group in items.values().filter_map(|state| state.check_group.as_ref()) {
+		*group_states.entry(group.key()).or_insert(0) += 1;
+	}
+
+	items
+		.iter()
+		.filter(|(key, state)| {
+			let group_wrappers = state
+				.check_group
+				.as_ref()
+				.filter(|group| group.clones() == group_states[&group.key()])
+				.map_or(0, |group| group.own_wrappers(**key));
+
+			state.wrappers <= group_wrappers
+		})
+		.map(|(key, _)| *key)
+		.collect()
+}
+
 pub struct MenuManager<R:Runtime> {
 	/// A set containing a reference to the active menus, including
 	/// the app-wide menu and the window-specific menus
@@ -25,8 +86,16 @@ pub struct MenuManager<R:Runtime> {
 	pub menu:Mutex<Option<Menu<R>>>,
 	/// Menu event listeners to all windows.
 	pub global_event_listeners:Mutex<Vec<crate::app::GlobalMenuEventListener<AppHandle<R>>>>,
+	/// The state of the menus, submenus and items, by native item.
+	pub(crate) items:Mutex<HashMap<MenuItemKey, ItemState<R>>>,
+	/// The menus and submenus items can be in, to drop the state of the items
+	/// that aren't in any menu anymore.
+	pub(crate) roots:Mutex<Vec<MenuRoot<R>>>,
 	/// Menu event listeners to specific windows.
 	pub event_listeners:Mutex<HashMap<String, crate::app::GlobalMenuEventListener<Window<R>>>>,
+	/// Exports window menus over DBus, set when enabled on the app builder.
+	#[cfg(linux_dbus_menu)]
+	pub(crate) dbus_exporter:std::sync::OnceLock<crate::menu::dbus::DbusMenuExporter>,
 }
 
 impl<R:Runtime> MenuManager<R> {
@@ -88,4 +157,560 @@ impl<R:Runtime> MenuManager<R> {
 	pub fn on_menu_event<F:Fn(&AppHandle<R>, MenuEvent) + Send + Sync + 'static>(&self, handler:F) {
 		self.global_event_listeners.lock().unwrap().push(Box::new(handler));
 	}
+
+	/// Keeps the state of the item with the given key while the new wrapper of
+	/// it is alive.
+	pub(crate) fn retain_item(&self, key:MenuItemKey, id:&MenuId) {
+		self.items
+			.lock()
+			.unwrap()
+			.entry(key)
+			.or_insert_with(|| {
+				ItemState {
+					id:id.clone(),
+					wrappers:0,
+					metadata:Default::default(),
+					click_handler:None,
+					check_group:None,
+					mnemonics_disabled:false,
+					duplicate_accelerators_allowed:false,
+				}
+			})
+			.wrappers += 1;
+	}
+
+	/// Releases a wrapper of the item with the given key, see
+	/// [`Self::drop_unreachable_item_state`].
+	pub(crate) fn release_item(&self, key:MenuItemKey) {
+		if let Some(state) = self.items.lock().unwrap().get_mut(&key) {
+			state.wrappers = state.wrappers.saturating_sub(1);
+		}
+	}
+
+	/// Updates the state of the item with the given key, if it's still alive.
+	pub(crate) fn update_item_state(&self, key:MenuItemKey, f:impl FnOnce(&mut ItemState<R>)) {
+		if let Some(state) = self.items.lock().unwrap().get_mut(&key) {
+			f(state);
+		}
+	}
+
+	/// Sets the click handler of an item, replacing its previous one.
+	pub(crate) fn on_menu_item_click(
+		&self,
+		key:MenuItemKey,
+		handler:crate::app::MenuItemClickHandler<R>,
+	) {
+		let mut previous = None;
+
+		self.update_item_state(key, |state| previous = state.click_handler.replace(handler));
+
+		// dropped once the lock is released, the closure may own menu items
+		drop(previous);
+	}
+
+	/// The click handlers of the items with the given id.
+	///
+	/// The native click events only carry the item id, so all the items sharing
+	/// it are considered clicked.
+	pub(crate) fn item_click_handlers(
+		&self,
+		id:&MenuId,
+	) -> Vec<crate::app::MenuItemClickHandler<R>> {
+		self.items
+			.lock()
+			.unwrap()
+			.values()
+			.filter(|state| &state.id == id)
+			.filter_map(|state| state.click_handler.clone())
+			.collect()
+	}
+
+	/// Makes `group` the check group of its items.
+	pub(crate) fn set_check_group(&self, group:&CheckMenuItemGroup<R>) {
+		let mut items = self.items.lock().unwrap();
+
+		let previous = group
+			.items()
+			.iter()
+			.filter_map(|item| items.get_mut(&item.key()))
+			.map(|state| state.check_group.replace(group.clone()))
+			.collect::<Vec<_>>();
+
+		drop(items);
+
+		// dropped once the lock is released, the groups own menu items
+		drop(previous);
+	}
+
+	/// The check groups of the items with the given id.
+	pub(crate) fn check_groups(&self, id:&MenuId) -> Vec<CheckMenuItemGroup<R>> {
+		self.items
+			.lock()
+			.unwrap()
+			.values()
+			.filter(|state| &state.id == id)
+			.filter_map(|state| state.check_group.clone())
+			.collect()
+	}
+
+	/// Registers a menu or submenu whose items are kept alive by it.
+	pub(crate) fn register_root(&self, root:MenuRoot<R>) {
+		let mut roots = self.roots.lock().unwrap();
+
+		// prune the dropped roots before growing, so it's amortized
+		if roots.len() == roots.capacity() {
+			roots.retain(MenuRoot::is_alive);
+		}
+
+		roots.push(root);
+	}
+
+	/// Drops the state of the items that were dropped: the ones without a live
+	/// wrapper and that aren't in a live menu or submenu.
+	///
+	/// Must be called on the main thread.
+	pub(crate) fn drop_unreachable_item_state(&self) {
+		let orphans = items_without_wrappers(&self.items.lock().unwrap());
+
+		if orphans.is_empty() {
+			return;
+		}
+
+		let roots = self.roots.lock().unwrap().clone();
+
+		let mut reachable = HashSet::new();
+
+		for root in &roots {
+			root.collect_item_keys(&mut reachable);
+		}
+
+		let mut items = self.items.lock().unwrap();
+
+		// wrappers may have been created since
+		let still_orphans = items_without_wrappers(&items);
+
+		let dropped = orphans
+			.into_iter()
+			.filter(|key| !reachable.contains(key) && still_orphans.contains(key))
+			.filter_map(|key| items.remove(&key))
+			.collect::<Vec<_>>();
+
+		drop(items);
+
+		// dropped once the lock is released, the click handlers may own menu items
+		drop(dropped);
+	}
+
+	pub(crate) fn update_item_metadata(
+		&self,
+		key:MenuItemKey,
+		f:impl FnOnce(&mut MenuItemMetadata),
+	) {
+		self.update_item_state(key, |state| f(&mut state.metadata));
+	}
+
+	pub(crate) fn item_metadata(&self, key:MenuItemKey) -> MenuItemMetadata {
+		self.items
+			.lock()
+			.unwrap()
+			.get(&key)
+			.map(|state| state.metadata.clone())
+			.unwrap_or_default()
+	}
+
+	/// The text to give to the native item, escaped if the item has
+	/// mnemonics disabled.
+	pub(crate) fn native_text(&self, key:MenuItemKey, text:&str) -> String {
+		if self.item_metadata(key).escaped_mnemonics {
+			crate::menu::escape_mnemonics(text)
+		} else {
+			text.to_string()
+		}
+	}
+
+	/// The text of the native item as it was given to its setter.
+	pub(crate) fn display_text(&self, key:MenuItemKey, text:String) -> String {
+		if self.item_metadata(key).escaped_mnemonics {
+			crate::menu::unescape_mnemonics(&text)
+		} else {
+			text
+		}
+	}
+
+	/// Returns [`crate::Error::AcceleratorConflict`] if an item of `items`, or
+	/// of their submenus, has the same accelerator as another one of them or
+	/// as an item of `existing`, the items already in `menu`.
+	///
+	/// Accelerators are compared once parsed, so equivalent spellings like
+	/// `CmdOrCtrl+E` and `Cmd+E` on macOS conflict.
+	///
+	/// The items of `replaced`, a submenu in `existing` whose items are
+	/// replaced by `items`, are ignored.
+	pub(crate) fn check_accelerators<F>(
+		&self,
+		menu:MenuItemKey,
+		existing:F,
+		replaced:Option<MenuItemKey>,
+		items:&[MenuItemKind<R>],
+	) -> crate::Result<()>
+	where
+		F: FnOnce() -> crate::Result<Vec<MenuItemKind<R>>>, {
+		if self.duplicate_accelerators_allowed(menu) {
+			return Ok(());
+		}
+
+		let mut accelerators = HashMap::new();
+
+		self.collect_accelerators(&existing()?, &mut accelerators, replaced, false)?;
+
+		self.collect_accelerators(items, &mut accelerators, None, true)
+	}
+
+	/// Whether the menu or submenu with the given key allows items with the same
+	/// accelerator.
+	pub(crate) fn duplicate_accelerators_allowed(&self, menu:MenuItemKey) -> bool {
+		self.items
+			.lock()
+			.unwrap()
+			.get(&menu)
+			.is_some_and(|state| state.duplicate_accelerators_allowed)
+	}
+
+	/// The menus containing the item with the given key, directly or in a
+	/// submenu.
+	///
+	/// Must be called on the main thread.
+	pub(crate) fn menus_containing(&self, key:MenuItemKey) -> Vec<Menu<R>> {
+		let roots = self.roots.lock().unwrap().clone();
+
+		roots.iter().filter_map(|root| root.menu_containing(key)).collect()
+	}
+
+	fn collect_accelerators(
+		&self,
+		items:&[MenuItemKind<R>],
+		accelerators:&mut HashMap<Accelerator, (String, MenuId)>,
+		replaced:Option<MenuItemKey>,
+		check:bool,
+	) -> crate::Result<()> {
+		for item in items {
+			if let MenuItemKind::Submenu(submenu) = item {
+				if replaced != Some(submenu.key()) {
+					let items = submenu.items()?;
+
+					self.collect_accelerators(&items, accelerators, replaced, check)?;
+				}
+
+				continue;
+			}
+
+			let Some(text) = self.item_metadata(item.key()).accelerator else {
+				continue;
+			};
+
+			let Ok(accelerator) = text.parse::<Accelerator>() else {
+				continue;
+			};
+
+			match accelerators.get(&accelerator) {
+				Some((_, first_id)) if check && first_id != item.id() => {
+					return Err(crate::Error::AcceleratorConflict {
+						accelerator:text,
+						first_id:first_id.0.clone(),
+						second_id:item.id().0.clone(),
+					});
+				},
+				Some(_) => {},
+				None => {
+					accelerators.insert(accelerator, (text, item.id().clone()));
+				},
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Escapes the text of an item added to `menu` if the menu has mnemonics
+	/// disabled, unless the item has its own setting.
+	pub(crate) fn apply_mnemonics(
+		&self,
+		menu:MenuItemKey,
+		item:&MenuItemKind<R>,
+	) -> crate::Result<()> {
+		if !self.items.lock().unwrap().get(&menu).is_some_and(|state| state.mnemonics_disabled) {
+			return Ok(());
+		}
+
+		let metadata = self.item_metadata(item.key());
+
+		if metadata.mnemonics.is_some() || metadata.escaped_mnemonics {
+			return Ok(());
+		}
+
+		let text = item.text()?;
+
+		self.update_item_metadata(item.key(), |m| m.escaped_mnemonics = true);
+
+		if text.contains('&') {
+			item.set_text(&text)?;
+		}
+
+		Ok(())
+	}
+
+	/// Notifies external menu hosts that a menu item or the items of a menu
+	/// changed.
+	pub(crate) fn notify_changed(&self) {
+		#[cfg(linux_dbus_menu)]
+		if let Some(exporter) = self.dbus_exporter.get() {
+			exporter.notify_changed::<R>();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{
+		Arc,
+		atomic::{AtomicUsize, Ordering},
+	};
+
+	use crate::{
+		AppHandle,
+		menu::{MenuBuilder, MenuItem, MenuItemBuilder, SubmenuBuilder},
+		test::{MockRuntime, mock_app},
+	};
+
+	fn noop(_:&AppHandle<MockRuntime>) {}
+
+	#[test]
+	fn click_handlers_are_dropped_with_their_item() {
+		let app = mock_app();
+
+		let item = MenuItemBuilder::with_id("open", "Open").on_click(noop).build(&app).unwrap();
+
+		assert_eq!(app.manager.menu.item_click_handlers(&"open".into()).len(), 1);
+
+		// a wrapper of the same item keeps the handler too
+		let menu = MenuBuilder::new(&app).item(&item).build().unwrap();
+
+		let wrapper = menu.get("open").unwrap();
+
+		drop(item);
+
+		menu.remove(&wrapper).unwrap();
+
+		assert_eq!(app.manager.menu.item_click_handlers(&"open".into()).len(), 1);
+
+		drop(wrapper);
+
+		assert!(app.manager.menu.item_click_handlers(&"open".into()).is_empty());
+
+		let item = MenuItemBuilder::with_id("save", "Save").on_click(noop).build(&app).unwrap();
+
+		drop(item);
+
+		assert!(app.manager.menu.item_click_handlers(&"save".into()).is_empty());
+	}
+
+	#[test]
+	fn click_handlers_live_as_long_as_their_menu() {
+		let app = mock_app();
+
+		let file = SubmenuBuilder::new(&app, "File")
+			.text_with_handler("open", "Open", noop)
+			.build()
+			.unwrap();
+
+		let menu = MenuBuilder::new(&app).item(&file).build().unwrap();
+
+		drop(file);
+
+		assert_eq!(app.manager.menu.item_click_handlers(&"open".into()).len(), 1);
+
+		drop(menu);
+
+		assert!(app.manager.menu.item_click_handlers(&"open".into()).is_empty());
+	}
+
+	#[test]
+	fn items_with_the_same_id_keep_their_own_handler() {
+		let app = mock_app();
+
+		let clicks = Arc::new(AtomicUsize::new(0));
+
+		let first_clicks = clicks.clone();
+
+		let second_clicks = clicks.clone();
+
+		let menu = MenuBuilder::new(&app)
+			.text_with_handler("same", "First", move |_| {
+				first_clicks.fetch_add(1, Ordering::Relaxed);
+			})
+			.text_with_handler("same", "Second", move |_| {
+				second_clicks.fetch_add(10, Ordering::Relaxed);
+			})
+			.build()
+			.unwrap();
+
+		for handler in app.manager.menu.item_click_handlers(&"same".into()) {
+			handler(app.handle());
+		}
+
+		assert_eq!(clicks.load(Ordering::Relaxed), 11);
+
+		menu.remove_at(0).unwrap();
+
+		assert_eq!(app.manager.menu.item_click_handlers(&"same".into()).len(), 1);
+	}
+
+	#[test]
+	fn item_metadata_is_dropped_with_the_item() {
+		let app = mock_app();
+
+		let menu = MenuBuilder::new(&app)
+			.text_with_accelerator("copy", "Copy", "Ctrl+C")
+			.icon("logo", "Logo", crate::image::Image::new_owned(vec![0; 4], 1, 1))
+			.build()
+			.unwrap();
+
+		assert_eq!(app.manager.menu.items.lock().unwrap().len(), 3);
+
+		let copy = menu.remove_at(0).unwrap().unwrap();
+
+		assert_eq!(copy.as_menuitem().unwrap().accelerator().as_deref(), Some("Ctrl+C"));
+
+		drop(copy);
+
+		assert_eq!(app.manager.menu.items.lock().unwrap().len(), 2);
+
+		drop(menu);
+
+		assert!(app.manager.menu.items.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn item_metadata_round_trips_through_the_config() {
+		let app = mock_app();
+
+		let file = SubmenuBuilder::with_id(&app, "file", "File")
+			.text_with_accelerator("save", "Save", "CmdOrCtrl+S")
+			.separator()
+			.copy()
+			.build()
+			.unwrap();
+
+		let menu = MenuBuilder::new(&app).item(&file).build().unwrap();
+
+		let value = menu.to_value().unwrap();
+
+		let rebuilt = crate::menu::Menu::from_value(&app, value.clone()).unwrap();
+
+		assert_eq!(rebuilt.to_value().unwrap(), value);
+	}
+
+	#[test]
+	fn conflicting_accelerators_are_rejected() {
+		let app = mock_app();
+
+		let result = MenuBuilder::new(&app)
+			.text_with_accelerator("copy", "Copy", "Ctrl+Shift+C")
+			.text_with_accelerator("clone", "Clone", "Shift+Ctrl+C")
+			.build();
+
+		assert!(matches!(
+			result,
+			Err(crate::Error::AcceleratorConflict { first_id, second_id, .. })
+				if first_id == "copy" && second_id == "clone"
+		));
+	}
+
+	#[test]
+	fn accelerators_of_submenus_conflict() {
+		let app = mock_app();
+
+		let file = SubmenuBuilder::new(&app, "File")
+			.text_with_accelerator("save", "Save", "Ctrl+S")
+			.build()
+			.unwrap();
+
+		let menu = MenuBuilder::new(&app).item(&file).build().unwrap();
+
+		let store = MenuItem::with_id(&app, "store", "Store", true, Some("Ctrl+S")).unwrap();
+
+		assert!(matches!(
+			menu.append(&store),
+			Err(crate::Error::AcceleratorConflict { first_id, .. }) if first_id == "save"
+		));
+
+		let open = MenuItem::with_id(&app, "open", "Open", true, Some("Ctrl+O")).unwrap();
+
+		assert!(menu.append(&open).is_ok());
+	}
+
+	#[test]
+	fn duplicate_accelerators_can_be_allowed() {
+		let app = mock_app();
+
+		let menu = MenuBuilder::new(&app)
+			.allow_duplicate_accelerators(true)
+			.text_with_accelerator("copy", "Copy", "Ctrl+C")
+			.text_with_accelerator("clone", "Clone", "Ctrl+C")
+			.build()
+			.unwrap();
+
+		assert_eq!(menu.items().unwrap().len(), 2);
+
+		drop(menu);
+
+		assert!(app.manager.menu.items.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn submenu_items_are_checked_against_the_whole_menu() {
+		let app = mock_app();
+
+		let edit = SubmenuBuilder::new(&app, "Edit").build().unwrap();
+
+		let menu = MenuBuilder::new(&app)
+			.text_with_accelerator("save", "Save", "Ctrl+S")
+			.item(&edit)
+			.build()
+			.unwrap();
+
+		let store = MenuItem::with_id(&app, "store", "Store", true, Some("Ctrl+S")).unwrap();
+
+		assert!(matches!(
+			edit.append(&store),
+			Err(crate::Error::AcceleratorConflict { first_id, .. }) if first_id == "save"
+		));
+
+		// the items of the submenu can be replaced by items with the same accelerators
+		let undo = MenuItem::with_id(&app, "undo", "Undo", true, Some("Ctrl+Z")).unwrap();
+
+		edit.append(&undo).unwrap();
+
+		let revert = MenuItem::with_id(&app, "revert", "Revert", true, Some("Ctrl+Z")).unwrap();
+
+		edit.set_items(&[&revert]).unwrap();
+
+		assert!(edit.set_items(&[&store]).is_err());
+
+		drop(menu);
+	}
+
+	#[test]
+	fn appended_items_are_checked_at_once() {
+		let app = mock_app();
+
+		let menu = MenuBuilder::new(&app).text("open", "Open").build().unwrap();
+
+		let copy = MenuItem::with_id(&app, "copy", "Copy", true, Some("Ctrl+C")).unwrap();
+
+		let clone = MenuItem::with_id(&app, "clone", "Clone", true, Some("Ctrl+C")).unwrap();
+
+		assert!(menu.append_items(&[&copy, &clone]).is_err());
+
+		assert_eq!(menu.items().unwrap().len(), 1);
+	}
 }
@@ -4,9 +4,11 @@
 
 use std::{
 	borrow::Cow,
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	fmt,
+	future::Future,
 	sync::{Arc, Mutex, MutexGuard},
+	time::Duration,
 };
 
 use serde::Serialize;
@@ -16,6 +18,8 @@ use tauri_utils::{
 	config::{Csp, CspDirectiveSources},
 	html::{SCRIPT_NONCE_TOKEN, STYLE_NONCE_TOKEN},
 };
+#[cfg(feature = "isolation")]
+use tauri_utils::pattern::isolation::KeyRing;
 use url::Url;
 
 use crate::{
@@ -33,7 +37,18 @@ use crate::{
 		GlobalWindowEventListener,
 		OnPageLoad,
 	},
-	event::{EmitArgs, Event, EventId, EventTarget, Listeners, assert_event_name_is_valid},
+	event::{
+		EmitArgs,
+		EmitCompletion,
+		EmitReport,
+		Event,
+		EventFilter,
+		EventId,
+		EventTarget,
+		Listeners,
+		assert_event_name_is_valid,
+		assert_event_pattern_is_valid,
+	},
 	ipc::{Invoke, InvokeHandler, RuntimeAuthority},
 	plugin::PluginStore,
 	resources::ResourceTable,
@@ -194,6 +209,9 @@ pub struct AppManager<R:Runtime> {
 	pub menu:menu::MenuManager<R>,
 
 	pub(crate) plugins:Mutex<PluginStore<R>>,
+	/// The names of the initialized plugins, kept out of the plugin store so
+	/// the plugins can read it while they are initialized.
+	pub(crate) initialized_plugins:Mutex<HashSet<&'static str>>,
 	pub listeners:Listeners,
 	pub state:Arc<StateManager>,
 	pub config:Config,
@@ -208,6 +226,12 @@ pub struct AppManager<R:Runtime> {
 	/// Application pattern.
 	pub pattern:Arc<Pattern>,
 
+	/// The keys of the isolation pattern, starting with the keys of
+	/// [`Pattern::Isolation`] and rotated on
+	/// `app > security > isolationKeyRotationIntervalSeconds`.
+	#[cfg(feature = "isolation")]
+	pub(crate) isolation_keys:Option<KeyRing>,
+
 	/// Global API scripts collected from plugins.
 	pub plugin_global_api_scripts:Arc<Option<&'static [&'static str]>>,
 
@@ -266,9 +290,26 @@ impl<R:Runtime> AppManager<R> {
 	) -> Self {
 		// generate a random isolation key at runtime
 		#[cfg(feature = "isolation")]
-		if let Pattern::Isolation { ref mut key, .. } = &mut context.pattern {
-			*key = uuid::Uuid::new_v4().to_string();
-		}
+		let isolation_keys = match &mut context.pattern {
+			Pattern::Isolation { key, crypto_keys, .. } => {
+				*key = uuid::Uuid::new_v4().to_string();
+
+				let mut keys = KeyRing::new((**crypto_keys).clone());
+
+				keys.set_rotation_interval(
+					context
+						.config
+						.app
+						.security
+						.isolation_key_rotation_interval_seconds
+						.map(|seconds| Duration::from_secs(seconds.get())),
+				);
+
+				Some(keys)
+			},
+			#[allow(unreachable_patterns)]
+			_ => None,
+		};
 
 		Self {
 			runtime_authority:Mutex::new(context.runtime_authority),
@@ -276,9 +317,18 @@ impl<R:Runtime> AppManager<R> {
 				windows:Mutex::default(),
 				default_icon:context.default_window_icon,
 				event_listeners:Arc::new(window_event_listeners),
+				#[cfg(desktop)]
+				parents:Default::default(),
+				#[cfg(desktop)]
+				restored_states:Default::default(),
+				linux_app_id:Default::default(),
+				state_dropped_hooks:Default::default(),
+				#[cfg(target_os = "macos")]
+				progress_animation:Default::default(),
 			},
 			webview:webview::WebviewManager {
 				webviews:Mutex::default(),
+				remote_webviews:Mutex::default(),
 				invoke_handler,
 				on_page_load,
 				uri_scheme_protocols:Mutex::new(uri_scheme_protocols),
@@ -292,15 +342,21 @@ impl<R:Runtime> AppManager<R> {
 				icons:Default::default(),
 				global_event_listeners:Mutex::new(tray_icon_event_listeners),
 				event_listeners:Default::default(),
+				menu_factories:Default::default(),
 			},
 			#[cfg(desktop)]
 			menu:menu::MenuManager {
 				menus:Default::default(),
 				menu:Default::default(),
 				global_event_listeners:Mutex::new(menu_event_listener),
+				items:Default::default(),
+				roots:Default::default(),
 				event_listeners:Mutex::new(window_menu_event_listeners),
+				#[cfg(linux_dbus_menu)]
+				dbus_exporter:Default::default(),
 			},
 			plugins:Mutex::new(plugins),
+			initialized_plugins:Default::default(),
 			listeners:Listeners::default(),
 			state:Arc::new(state),
 			config:context.config,
@@ -310,6 +366,8 @@ impl<R:Runtime> AppManager<R> {
 			app_icon:context.app_icon,
 			package_info:context.package_info,
 			pattern:Arc::new(context.pattern),
+			#[cfg(feature = "isolation")]
+			isolation_keys,
 			plugin_global_api_scripts:Arc::new(context.plugin_global_api_scripts),
 			resources_table:Arc::default(),
 			invoke_key,
@@ -494,6 +552,22 @@ impl<R:Runtime> AppManager<R> {
 
 	pub fn config(&self) -> &Config { &self.config }
 
+	/// The `.desktop` file name of the app, used by the Linux launcher APIs.
+	pub(crate) fn desktop_file_name(&self) -> String {
+		let app_id = self.window.linux_app_id.get().unwrap_or(&self.config.identifier);
+
+		format!("{app_id}.desktop")
+	}
+
+	/// The `.desktop` file name used by the launcher APIs that predate
+	/// [`crate::Builder::linux_app_id`], which is named after the package name
+	/// when no app id is set.
+	pub(crate) fn package_desktop_file_name(&self) -> String {
+		let app_id = self.window.linux_app_id.get().unwrap_or(&self.package_info.name);
+
+		format!("{app_id}.desktop")
+	}
+
 	#[cfg(dev)]
 	pub fn config_parent(&self) -> Option<&std::path::PathBuf> { self.config_parent.as_ref() }
 
@@ -505,9 +579,17 @@ impl<R:Runtime> AppManager<R> {
 		target:EventTarget,
 		handler:F,
 	) -> EventId {
-		assert_event_name_is_valid(&event);
+		assert_event_pattern_is_valid(&event);
+
+		let replay = self.listeners().is_replayed(&event).then(|| (event.clone(), target.clone()));
 
-		self.listeners().listen(event, target, handler)
+		let id = self.listeners().listen(event, target, handler);
+
+		if let Some((event, target)) = replay {
+			self.replay_events(&event, &target);
+		}
+
+		id
 	}
 
 	pub fn once<F:FnOnce(Event) + Send + 'static>(
@@ -516,9 +598,57 @@ impl<R:Runtime> AppManager<R> {
 		target:EventTarget,
 		handler:F,
 	) -> EventId {
-		assert_event_name_is_valid(&event);
+		assert_event_pattern_is_valid(&event);
+
+		let replay = self.listeners().is_replayed(&event).then(|| (event.clone(), target.clone()));
+
+		let id = self.listeners().once(event, target, handler);
+
+		if let Some((event, target)) = replay {
+			self.replay_events(&event, &target);
+		}
+
+		id
+	}
+
+	pub fn listen_filter<F:Fn(Event) + Send + 'static>(
+		&self,
+		event:String,
+		target:EventTarget,
+		filter:EventFilter,
+		handler:F,
+	) -> EventId {
+		assert_event_pattern_is_valid(&event);
+
+		let replay = self.listeners().is_replayed(&event).then(|| (event.clone(), target.clone()));
 
-		self.listeners().once(event, target, handler)
+		let id = self.listeners().listen_filter(event, target, Some(filter), handler);
+
+		if let Some((event, target)) = replay {
+			self.replay_events(&event, &target);
+		}
+
+		id
+	}
+
+	pub fn once_filter<F:FnOnce(Event) + Send + 'static>(
+		&self,
+		event:String,
+		target:EventTarget,
+		filter:EventFilter,
+		handler:F,
+	) -> EventId {
+		assert_event_pattern_is_valid(&event);
+
+		let replay = self.listeners().is_replayed(&event).then(|| (event.clone(), target.clone()));
+
+		let id = self.listeners().once_filter(event, target, Some(filter), handler);
+
+		if let Some((event, target)) = replay {
+			self.replay_events(&event, &target);
+		}
+
+		id
 	}
 
 	pub fn unlisten(&self, id:EventId) { self.listeners().unlisten(id) }
@@ -530,13 +660,24 @@ impl<R:Runtime> AppManager<R> {
 		#[cfg(feature = "tracing")]
 		let _span = tracing::debug_span!("emit::run").entered();
 
-		let emit_args = EmitArgs::new(event, payload)?;
+		self.emit_args_to(EventTarget::Any, EmitArgs::new(event, payload)?)
+	}
+
+	/// The webviews the events are sent to, including the remote frontends.
+	fn event_webviews(&self) -> Vec<Webview<R>> {
+		let mut webviews = self.webview.webviews_lock().values().cloned().collect::<Vec<_>>();
 
+		webviews.extend(self.webview.remote_webviews_lock().values().cloned());
+
+		webviews
+	}
+
+	fn emit_args(&self, emit_args:EmitArgs) -> crate::Result<()> {
 		let listeners = self.listeners();
 
-		let webviews = self.webview.webviews_lock().values().cloned().collect::<Vec<_>>();
+		let webviews = self.event_webviews();
 
-		listeners.emit_js(webviews.iter(), event, &emit_args)?;
+		listeners.emit_js(webviews.iter(), &emit_args.event_name, &emit_args)?;
 
 		listeners.emit(emit_args)?;
 
@@ -556,13 +697,17 @@ impl<R:Runtime> AppManager<R> {
 		#[cfg(feature = "tracing")]
 		let _span = tracing::debug_span!("emit::run").entered();
 
-		let emit_args = EmitArgs::new(event, payload)?;
+		self.emit_args_filter(EmitArgs::new(event, payload)?, filter)
+	}
 
+	fn emit_args_filter<F>(&self, emit_args:EmitArgs, filter:F) -> crate::Result<()>
+	where
+		F: Fn(&EventTarget) -> bool, {
 		let listeners = self.listeners();
 
 		listeners.emit_js_filter(
-			self.webview.webviews_lock().values(),
-			event,
+			self.event_webviews().iter(),
+			&emit_args.event_name,
 			&emit_args,
 			Some(&filter),
 		)?;
@@ -584,60 +729,193 @@ impl<R:Runtime> AppManager<R> {
 		#[cfg(feature = "tracing")]
 		tracing::Span::current().record("target", format!("{target:?}"));
 
+		assert_event_name_is_valid(event);
+
+		self.emit_args_to(target, EmitArgs::new(event, payload)?)
+	}
+
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument("app::emit::bytes", skip(self, target, bytes), fields(target))
+	)]
+	pub fn emit_bytes_to(
+		&self,
+		target:EventTarget,
+		event:&str,
+		bytes:bytes::Bytes,
+	) -> crate::Result<()> {
+		#[cfg(feature = "tracing")]
+		tracing::Span::current().record("target", format!("{target:?}"));
+
+		assert_event_name_is_valid(event);
+
+		self.emit_args_to(target, EmitArgs::binary(event, bytes)?)
+	}
+
+	fn emit_args_to(&self, target:EventTarget, mut emit_args:EmitArgs) -> crate::Result<()> {
+		emit_args.target = Some(target.clone());
+
+		let listeners = self.listeners();
+
+		// buffered until a listener of the target attaches
+		if listeners.is_replayed(&emit_args.event_name) {
+			let filter = |t:&EventTarget| *t == EventTarget::Any || is_target_matched(&target, t);
+
+			let event = emit_args.event_name.clone();
+
+			// a buffered event doesn't keep an `emit_and_wait` call waiting for its replay
+			let completion = emit_args.completion.take();
+
+			match listeners.buffer_replay_unless(target.clone(), emit_args, || {
+				listeners.has_listener(&event, filter) || listeners.has_js_listener(&event, filter)
+			}) {
+				Some(args) => emit_args = EmitArgs { completion, ..args },
+				None => return Ok(()),
+			}
+		}
+
 		match target {
 			// if targeting all, emit to all using emit without filter
-			EventTarget::Any => self.emit(event, payload),
-
-			// if targeting any label, emit using emit_filter and filter labels
-			EventTarget::AnyLabel { label: target_label } => {
-				self.emit_filter(event, payload, |t| {
-					match t {
-						EventTarget::Window { label }
-						| EventTarget::Webview { label }
-						| EventTarget::WebviewWindow { label }
-						| EventTarget::AnyLabel { label } => label == &target_label,
-						_ => false,
-					}
-				})
-			},
+			EventTarget::Any => self.emit_args(emit_args),
 
-			EventTarget::Window { label: target_label } => {
-				self.emit_filter(event, payload, |t| {
-					match t {
-						EventTarget::AnyLabel { label } | EventTarget::Window { label } => {
-							label == &target_label
-						},
-						_ => false,
-					}
-				})
-			},
+			// otherwise emit using emit_filter and filter targets
+			target => self.emit_args_filter(emit_args, |t| is_target_matched(&target, t)),
+		}
+	}
 
-			EventTarget::Webview { label: target_label } => {
-				self.emit_filter(event, payload, |t| {
-					match t {
-						EventTarget::AnyLabel { label } | EventTarget::Webview { label } => {
-							label == &target_label
-						},
-						_ => false,
-					}
-				})
-			},
+	/// Emits the buffered `event` events whose target is matched by a listener
+	/// of `listener_target` that just attached.
+	pub(crate) fn replay_events(&self, event:&str, listener_target:&EventTarget) {
+		let replayed = self.listeners().take_replay(event, |target| {
+			*listener_target == EventTarget::Any || is_target_matched(target, listener_target)
+		});
 
-			EventTarget::WebviewWindow { label: target_label } => {
-				self.emit_filter(event, payload, |t| {
-					match t {
-						EventTarget::AnyLabel { label } | EventTarget::WebviewWindow { label } => {
-							label == &target_label
-						},
+		for (target, emit_args) in replayed {
+			if let Err(e) = self.emit_args_filter(emit_args, |t| is_target_matched(&target, t)) {
+				log::error!("failed to replay the `{event}` event: {e}");
+			}
+		}
+	}
 
-						_ => false,
-					}
-				})
-			},
+	#[cfg_attr(
+		feature = "tracing",
+		tracing::instrument("app::emit::to_many", skip(self, targets, payload))
+	)]
+	pub fn emit_to_many<S>(
+		&self,
+		targets:Vec<EventTarget>,
+		event:&str,
+		payload:S,
+	) -> crate::Result<usize>
+	where
+		S: Serialize + Clone, {
+		assert_event_name_is_valid(event);
+
+		let listeners = self.listeners();
+
+		let has_listener = |filter:&dyn Fn(&EventTarget) -> bool| {
+			listeners.has_listener(event, filter) || listeners.has_js_listener(event, filter)
+		};
+
+		// the listeners of any target get the event too, but they aren't a target
+		let any_listener = has_listener(&|t| *t == EventTarget::Any);
+
+		let targets = targets
+			.into_iter()
+			.filter(|target| {
+				has_listener(&|t| *t != EventTarget::Any && is_target_matched(target, t))
+			})
+			.collect::<Vec<_>>();
+
+		if targets.is_empty() && !any_listener {
+			return Ok(0);
+		}
+
+		// a single dispatch, so the listeners matching several targets get the event once
+		self.emit_args_filter(EmitArgs::new(event, payload)?, |t| {
+			targets.iter().any(|target| is_target_matched(target, t))
+		})?;
+
+		Ok(targets.len())
+	}
+
+	/// Emits the event like [`Self::emit_to`], returning a future resolving once
+	/// its listeners handled it or once the timeout elapses.
+	///
+	/// The event is dispatched before the future is polled, so a listener of
+	/// the same event can call it without waiting for itself: its Rust
+	/// listeners then run after the calling listener returns.
+	pub(crate) fn emit_and_wait<S:Serialize>(
+		&self,
+		target:EventTarget,
+		event:&str,
+		payload:S,
+		timeout:Duration,
+	) -> crate::Result<impl Future<Output = EmitReport> + Send + 'static> {
+		assert_event_name_is_valid(event);
+
+		let mut emit_args = EmitArgs::new(event, payload)?;
+
+		let (completion, mut completed) = tokio::sync::mpsc::channel::<()>(1);
+
+		let acks = Arc::new(Mutex::new(Vec::new()));
+
+		emit_args.completion = Some(EmitCompletion { listeners:completion, acks:acks.clone() });
+
+		// the webviews are sent the event, and asked to acknowledge it, right away
+		self.emit_args_to(target, emit_args)?;
+
+		let acks = std::mem::take(&mut *acks.lock().unwrap());
+
+		let listeners = self.listeners().clone();
+
+		Ok(async move {
+			let deadline = tokio::time::Instant::now() + timeout;
+
+			let listeners_completed = tokio::time::timeout_at(deadline, async {
+				while completed.recv().await.is_some() {}
+			})
+			.await
+			.is_ok();
+
+			let mut report = EmitReport { listeners_completed, ..Default::default() };
+
+			let ids = acks.iter().map(|(id, ..)| *id).collect::<Vec<_>>();
+
+			for (_, label, ack) in acks {
+				match tokio::time::timeout_at(deadline, ack).await {
+					Ok(Ok(())) => report.acknowledged.push(label),
+					_ => report.unacknowledged.push(label),
+				}
+			}
+
+			listeners.remove_acks(&ids);
 
-			// otherwise match same target
-			_ => self.emit_filter(event, payload, |t| t == &target),
+			report
+		})
+	}
+
+	/// Emits an event from the `source` webview to the private listeners of the
+	/// `target` webview, skipping the broadcast listeners.
+	pub(crate) fn emit_private(
+		&self,
+		source:&str,
+		target:&str,
+		emit_args:EmitArgs,
+	) -> crate::Result<()> {
+		let ids = self.listeners().emit_private(target, source, &emit_args);
+
+		if !ids.is_empty() {
+			let webview = self
+				.get_webview(target)
+				.or_else(|| self.webview.remote_webviews_lock().get(target).cloned());
+
+			if let Some(webview) = webview {
+				webview.emit_js(&emit_args, &ids)?;
+			}
 		}
+
+		Ok(())
 	}
 
 	pub fn get_window(&self, label:&str) -> Option<Window<R>> {
@@ -658,13 +936,65 @@ impl<R:Runtime> AppManager<R> {
 		if let Some(window) = window {
 			for webview in window.webviews() {
 				self.webview.webviews_lock().remove(webview.label());
+
+				self.cancel_invokes(webview.label());
+
+				self.listeners().remove_private(webview.label());
+
+				self.listeners().remove_webview_acks(webview.label());
 			}
+
+			for hook in self.window.state_dropped_hooks.get().into_iter().flatten() {
+				hook(&window);
+			}
+
+			// the handles to the window may outlive it, and would keep its state alive
+			window.state.clear();
 		}
 	}
 
 	#[cfg(desktop)]
 	pub(crate) fn on_webview_close(&self, label:&str) {
 		self.webview.webviews_lock().remove(label);
+
+		self.cancel_invokes(label);
+
+		self.listeners().remove_private(label);
+
+		self.listeners().remove_webview_acks(label);
+	}
+
+	/// Drops the webview of a disconnected remote frontend.
+	pub(crate) fn on_remote_webview_close(&self, label:&str) {
+		self.webview.remote_webviews_lock().remove(label);
+
+		self.cancel_invokes(label);
+
+		self.listeners().remove_js(label);
+
+		self.listeners().remove_private(label);
+
+		self.listeners().remove_webview_acks(label);
+	}
+
+	/// Cancels the running invokes of a destroyed webview, errors the request
+	/// body streams they read and drops the response body streams sent to it.
+	fn cancel_invokes(&self, label:&str) {
+		if let Some(registry) = self.state.try_get::<crate::ipc::cancel::CancellationRegistry>() {
+			registry.cancel_webview(label);
+		}
+
+		if let Some(registry) =
+			self.state.try_get::<crate::ipc::body_stream::RequestStreamRegistry>()
+		{
+			registry.close_webview(label);
+		}
+
+		if let Some(registry) =
+			self.state.try_get::<crate::ipc::response_stream::ResponseStreamRegistry>()
+		{
+			registry.close_webview(label);
+		}
 	}
 
 	pub fn windows(&self) -> HashMap<String, Window<R>> { self.window.windows_lock().clone() }
@@ -695,6 +1025,40 @@ impl<R:Runtime> AppManager<R> {
 	}
 }
 
+/// Whether the listeners of the `listener` target get the events emitted to
+/// `target`.
+fn is_target_matched(target:&EventTarget, listener:&EventTarget) -> bool {
+	match (target, listener) {
+		(EventTarget::Any, _) => true,
+
+		(
+			EventTarget::AnyLabel { label: target_label },
+			EventTarget::Window { label }
+			| EventTarget::Webview { label }
+			| EventTarget::WebviewWindow { label }
+			| EventTarget::AnyLabel { label },
+		) => label == target_label,
+
+		(
+			EventTarget::Window { label: target_label },
+			EventTarget::AnyLabel { label } | EventTarget::Window { label },
+		) => label == target_label,
+
+		(
+			EventTarget::Webview { label: target_label },
+			EventTarget::AnyLabel { label } | EventTarget::Webview { label },
+		) => label == target_label,
+
+		(
+			EventTarget::WebviewWindow { label: target_label },
+			EventTarget::AnyLabel { label } | EventTarget::WebviewWindow { label },
+		) => label == target_label,
+
+		// otherwise match same target
+		_ => target == listener,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::replace_with_callback;
@@ -1025,4 +1389,279 @@ mod test {
 
 		assert_events("emit_to", &received, &[other_webview_listen_id]);
 	}
+
+	#[test]
+	fn emit_to_many_counts_the_targets_with_listeners() {
+		let EventSetup { app, window, webview, rx, .. } = setup_events(false);
+
+		let payload = "global-payload";
+
+		let emit_to_many = |targets:Vec<EventTarget>, expected:&[&str]| {
+			let count = app.emit_to_many(targets, TEST_EVENT_NAME, payload).unwrap();
+
+			let mut received = Vec::new();
+
+			while let Ok((source, p)) = rx.recv_timeout(Duration::from_secs(1)) {
+				assert_eq!(p, payload);
+
+				received.push(source);
+			}
+
+			assert_events("emit_to_many", &received, expected);
+
+			count
+		};
+
+		let targets = [window.label(), webview.label(), "missing"].map(EventTarget::labeled);
+
+		assert_eq!(emit_to_many(targets.to_vec(), &[WINDOW_LISTEN_ID, WEBVIEW_LISTEN_ID]), 2);
+
+		// a listener matching several targets gets the event once
+		let targets = vec![EventTarget::labeled(window.label()), EventTarget::window(window.label())];
+
+		assert_eq!(emit_to_many(targets, &[WINDOW_LISTEN_ID]), 2);
+
+		assert_eq!(emit_to_many(vec![EventTarget::labeled("missing")], &[]), 0);
+	}
+
+	#[test]
+	fn emit_to_many_does_not_count_the_listeners_of_any_target() {
+		let EventSetup { app, window, webview, rx, .. } = setup_events(true);
+
+		let payload = "global-payload";
+
+		let emit_to_many = |targets:Vec<EventTarget>, expected:&[&str]| {
+			let count = app.emit_to_many(targets, TEST_EVENT_NAME, payload).unwrap();
+
+			let mut received = Vec::new();
+
+			while let Ok((source, p)) = rx.recv_timeout(Duration::from_secs(1)) {
+				assert_eq!(p, payload);
+
+				received.push(source);
+			}
+
+			assert_events("emit_to_many", &received, expected);
+
+			count
+		};
+
+		let any_listeners = [
+			APP_LISTEN_ANY_ID,
+			WINDOW_LISTEN_ANY_ID,
+			WEBVIEW_LISTEN_ANY_ID,
+			WEBVIEW_WINDOW_LISTEN_ANY_ID,
+		];
+
+		// the listeners of any target get the event once
+		assert_eq!(emit_to_many(vec![EventTarget::labeled("ghost")], &any_listeners), 0);
+
+		let targets = [window.label(), webview.label(), "ghost"].map(EventTarget::labeled);
+
+		let expected = [&any_listeners[..], &[WINDOW_LISTEN_ID, WEBVIEW_LISTEN_ID]].concat();
+
+		assert_eq!(emit_to_many(targets.to_vec(), &expected), 2);
+	}
+
+	#[test]
+	fn replayed_events_reach_the_first_listener_of_their_target() {
+		let app = crate::test::mock_builder()
+			.event_replay(crate::EventReplayConfig { events:vec!["ready".into()], buffer:2 })
+			.build(crate::test::mock_context(crate::test::noop_assets()))
+			.unwrap();
+
+		let webview_window =
+			WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		for payload in ["first", "second", "third"] {
+			app.emit_to("main", "ready", payload).unwrap();
+		}
+
+		// buffered for another target
+		app.emit_to("other", "ready", "other").unwrap();
+
+		let (tx, rx) = channel();
+
+		let tx_ = tx.clone();
+
+		webview_window.listen("ready", move |event| {
+			tx_.send(serde_json::from_str::<String>(event.payload()).unwrap()).unwrap();
+		});
+
+		// the oldest event was dropped from the full buffer
+		assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["second", "third"]);
+
+		// the next events are emitted to the listener right away
+		app.emit_to("main", "ready", "fourth").unwrap();
+
+		assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["fourth"]);
+
+		// and the ones of the other target are still waiting for theirs
+		app.listen_any("ready", move |event| {
+			tx.send(serde_json::from_str::<String>(event.payload()).unwrap()).unwrap();
+		});
+
+		assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["other"]);
+	}
+
+	#[test]
+	fn events_buffered_while_a_listener_attaches_are_replayed_to_it() {
+		let app = crate::test::mock_builder()
+			.event_replay(crate::EventReplayConfig { events:vec!["ready".into()], buffer:1 })
+			.build(crate::test::mock_context(crate::test::noop_assets()))
+			.unwrap();
+
+		let listeners = app.handle().manager.listeners();
+
+		let (tx, rx) = channel();
+
+		let mut listener = None;
+
+		let emitted = listeners.buffer_replay_unless(
+			EventTarget::Any,
+			crate::event::EmitArgs::new("ready", "payload").unwrap(),
+			|| {
+				// the listener attaches right after the emitter found none
+				let app = app.handle().clone();
+
+				listener = Some(std::thread::spawn(move || {
+					app.listen_any("ready", move |event| {
+						tx.send(event.payload().to_string()).unwrap();
+					});
+				}));
+
+				while !listeners.has_listener("ready", |_| true) {
+					std::thread::yield_now();
+				}
+
+				false
+			},
+		);
+
+		assert!(emitted.is_none());
+
+		listener.unwrap().join().unwrap();
+
+		assert_eq!(rx.recv_timeout(Duration::from_secs(1)).unwrap(), "\"payload\"");
+	}
+
+	#[test]
+	fn emit_and_wait_from_a_listener_of_the_same_event() {
+		let app = mock_app();
+
+		let handle = app.handle().clone();
+
+		let (tx, rx) = channel();
+
+		let (payloads, received) = channel();
+
+		app.listen_any("flush", move |event| {
+			payloads.send(event.payload().to_string()).unwrap();
+
+			if event.payload() == "1" {
+				// the listener doesn't block on the future, which resolves once it returned
+				tx.send(handle.emit_and_wait("flush", 2)).unwrap();
+			}
+		});
+
+		let outer = crate::async_runtime::block_on(app.emit_and_wait("flush", 1)).unwrap();
+
+		let inner = rx.recv_timeout(Duration::from_secs(1)).unwrap();
+
+		let inner = crate::async_runtime::block_on(inner).unwrap();
+
+		assert!(outer.is_complete());
+		assert!(inner.is_complete());
+
+		assert_eq!(received.try_iter().collect::<Vec<_>>(), ["1", "2"]);
+	}
+
+	/// The ID the webview was asked to acknowledge the last `flush` event with.
+	fn flush_ack_id(webview_window:&WebviewWindow<MockRuntime>) -> u32 {
+		let scripts = webview_window.webview.webview.dispatcher.state().evaluated_scripts;
+
+		let script = scripts.iter().rev().find(|script| script.contains("\"flush\"")).unwrap();
+
+		let (_, ack) = script.rsplit_once("], ").unwrap();
+
+		ack.split(')').next().unwrap().parse().unwrap()
+	}
+
+	#[test]
+	fn emit_and_wait_waits_for_the_webviews_to_acknowledge_the_event() {
+		use crate::{
+			ipc::{CallbackFn, InvokeBody},
+			test::{INVOKE_KEY, get_ipc_response},
+			webview::InvokeRequest,
+		};
+
+		let app = mock_app();
+
+		let webview_window =
+			WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let listeners = app.handle().manager.listeners();
+
+		listeners.listen_js("flush", "main", EventTarget::labeled("main"), 1, None);
+
+		let report = app.emit_and_wait_timeout("flush", (), Duration::from_secs(5));
+
+		let ack = flush_ack_id(&webview_window);
+
+		get_ipc_response(
+			&webview_window,
+			InvokeRequest {
+				cmd:crate::ipc::channel::ACK_EVENT_COMMAND.into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::Json(serde_json::json!({ "id": ack })),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.unwrap();
+
+		let report = crate::async_runtime::block_on(report).unwrap();
+
+		assert!(report.is_complete());
+		assert_eq!(report.acknowledged, ["main"]);
+
+		// never acknowledged
+		let report = app.emit_and_wait_timeout("flush", (), Duration::from_millis(50));
+
+		let report = crate::async_runtime::block_on(report).unwrap();
+
+		assert!(report.listeners_completed);
+		assert_eq!(report.unacknowledged, ["main"]);
+
+		// the webview isn't the target of the event
+		let report = app.emit_to_and_wait("other", "flush", (), Duration::from_millis(50));
+
+		let report = crate::async_runtime::block_on(report).unwrap();
+
+		assert!(report.is_complete());
+		assert!(report.acknowledged.is_empty());
+	}
+
+	#[test]
+	fn emit_and_wait_does_not_wait_for_buffered_events() {
+		let app = crate::test::mock_builder()
+			.event_replay(crate::EventReplayConfig { events:vec!["flush".into()], buffer:1 })
+			.build(crate::test::mock_context(crate::test::noop_assets()))
+			.unwrap();
+
+		let report = app.emit_and_wait_timeout("flush", 1, Duration::from_secs(30));
+
+		let report = crate::async_runtime::block_on(report).unwrap();
+
+		assert!(report.is_complete());
+
+		// the buffered event is still replayed
+		let (tx, rx) = channel();
+
+		app.listen_any("flush", move |event| tx.send(event.payload().to_string()).unwrap());
+
+		assert_eq!(rx.try_iter().collect::<Vec<_>>(), ["1"]);
+	}
 }
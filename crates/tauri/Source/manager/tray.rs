@@ -7,7 +7,7 @@ use std::{collections::HashMap, fmt, sync::Mutex};
 use crate::{
 	AppHandle,
 	Runtime,
-	app::GlobalTrayIconEventListener,
+	app::{GlobalTrayIconEventListener, TrayMenuFactory},
 	image::Image,
 	tray::{TrayIcon, TrayIconEvent, TrayIconId},
 };
@@ -20,6 +20,8 @@ pub struct TrayManager<R:Runtime> {
 	pub(crate) global_event_listeners:Mutex<Vec<GlobalTrayIconEventListener<AppHandle<R>>>>,
 	/// Tray icon event listeners.
 	pub(crate) event_listeners:Mutex<HashMap<TrayIconId, GlobalTrayIconEventListener<TrayIcon<R>>>>,
+	/// Factories rebuilding the tray menus before they are shown.
+	pub(crate) menu_factories:Mutex<HashMap<TrayIconId, TrayMenuFactory<R>>>,
 }
 
 impl<R:Runtime> fmt::Debug for TrayManager<R> {
@@ -47,6 +49,8 @@ impl<R:Runtime> TrayManager<R> {
 	where
 		I: ?Sized,
 		TrayIconId: PartialEq<&'a I>, {
+		self.menu_factories.lock().unwrap().retain(|tray_id, _| tray_id != &id);
+
 		let mut icons = self.icons.lock().unwrap();
 
 		let idx = icons.iter().position(|t| t.id() == &id);
@@ -54,6 +54,18 @@ use crate::{
 // prepare_uri_scheme_protocol
 pub(crate) const PROXY_DEV_SERVER:bool = cfg!(all(dev, mobile));
 
+pub(crate) const PAGE_LOAD_EVENT:&str = "tauri://page-load";
+
+const WEBVIEW_ZOOM_CHANGED_EVENT:&str = "tauri://zoom-changed";
+
+const WEBVIEW_FIND_RESULT_CHANGED_EVENT:&str = "tauri://find-result-changed";
+
+const WEBVIEW_HISTORY_CHANGED_EVENT:&str = "tauri://history-changed";
+
+const WEBVIEW_DEVTOOLS_OPENED_EVENT:&str = "tauri://devtools-opened";
+
+const WEBVIEW_DEVTOOLS_CLOSED_EVENT:&str = "tauri://devtools-closed";
+
 pub(crate) const PROCESS_IPC_MESSAGE_FN:&str =
 	include_str!("../../scripts/process-ipc-message-fn.js");
 
@@ -82,6 +94,8 @@ pub struct UriSchemeProtocol<R:Runtime> {
 
 pub struct WebviewManager<R:Runtime> {
 	pub webviews:Mutex<HashMap<String, Webview<R>>>,
+	/// The webviews of the remote frontends connected with an invoke transport.
+	pub(crate) remote_webviews:Mutex<HashMap<String, Webview<R>>>,
 	/// The JS message handler.
 	pub invoke_handler:Box<InvokeHandler<R>>,
 	/// The page load hook, invoked when the webview performs a navigation.
@@ -123,6 +137,11 @@ impl<R:Runtime> WebviewManager<R> {
 		self.webviews.lock().expect("poisoned webview manager")
 	}
 
+	/// Get a locked handle to the webviews of the remote frontends.
+	pub(crate) fn remote_webviews_lock(&self) -> MutexGuard<'_, HashMap<String, Webview<R>>> {
+		self.remote_webviews.lock().expect("poisoned webview manager")
+	}
+
 	fn prepare_pending_webview<M:Manager<R>>(
 		&self,
 		mut pending:PendingWebview<EventLoopMessage, R>,
@@ -285,8 +304,8 @@ impl<R:Runtime> WebviewManager<R> {
 
 		let on_page_load_handler = pending.on_page_load_handler.take();
 
-		pending.on_page_load_handler.replace(Box::new(move |url, event| {
-			let payload = PageLoadPayload { url:&url, event };
+		pending.on_page_load_handler.replace(Box::new(move |url, event, error| {
+			let payload = PageLoadPayload { url:&url, event, error:error.as_ref() };
 
 			if let Some(w) = app_manager_.get_webview(&label) {
 				if let Some(on_page_load) = &app_manager_.webview.on_page_load {
@@ -294,10 +313,12 @@ impl<R:Runtime> WebviewManager<R> {
 				}
 
 				app_manager_.plugins.lock().unwrap().on_page_load(&w, &payload);
+
+				let _ = w.emit_to_webview(PAGE_LOAD_EVENT, &payload);
 			}
 
 			if let Some(handler) = &on_page_load_handler {
-				handler(url, event);
+				handler(url, event, error);
 			}
 		}));
 
@@ -311,14 +332,11 @@ impl<R:Runtime> WebviewManager<R> {
 		}
 
 		#[cfg(feature = "isolation")]
-		if let crate::Pattern::Isolation { assets, schema, key: _, crypto_keys } =
-			&*app_manager.pattern
-		{
+		if let crate::Pattern::Isolation { assets, schema, .. } = &*app_manager.pattern {
 			let protocol = crate::protocol::isolation::get(
 				manager.manager_owned(),
 				schema,
 				assets.clone(),
-				*crypto_keys.aes_gcm().raw(),
 				window_origin,
 				use_https_scheme,
 			);
@@ -403,7 +421,9 @@ impl<R:Runtime> WebviewManager<R> {
 		mut pending:PendingWebview<EventLoopMessage, R>,
 		window_label:&str,
 	) -> crate::Result<PendingWebview<EventLoopMessage, R>> {
-		if self.webviews_lock().contains_key(&pending.label) {
+		if self.webviews_lock().contains_key(&pending.label)
+			|| self.remote_webviews_lock().contains_key(&pending.label)
+		{
 			return Err(crate::Error::WebviewLabelAlreadyExists(pending.label));
 		}
 
@@ -532,8 +552,8 @@ impl<R:Runtime> WebviewManager<R> {
 				os_name:&'a str,
 			}
 
-			pending.webview_attributes.initialization_scripts.push(
-				HotkeyZoom { os_name:std::env::consts::OS }
+			pending.webview_attributes = pending.webview_attributes.initialization_script(
+				&HotkeyZoom { os_name:std::env::consts::OS }
 					.render_default(&Default::default())?
 					.into_string(),
 			)
@@ -666,13 +686,14 @@ fn on_webview_event<R:Runtime>(webview:&Webview<R>, event:&WebviewEvent) -> crat
 	match event {
 		WebviewEvent::DragDrop(event) => {
 			match event {
-				DragDropEvent::Enter { paths, position } => {
-					let payload = DragDropPayload { paths:Some(paths), position };
+				DragDropEvent::Enter { paths, types, position } => {
+					let payload =
+						DragDropPayload { paths:Some(paths), types:Some(types), position };
 
 					webview.emit_to_webview(DRAG_ENTER_EVENT, payload)?
 				},
 				DragDropEvent::Over { position } => {
-					let payload = DragDropPayload { position, paths:None };
+					let payload = DragDropPayload { position, paths:None, types:None };
 
 					webview.emit_to_webview(DRAG_OVER_EVENT, payload)?
 				},
@@ -687,7 +708,7 @@ fn on_webview_event<R:Runtime>(webview:&Webview<R>, event:&WebviewEvent) -> crat
 						}
 					}
 
-					let payload = DragDropPayload { paths:Some(paths), position };
+					let payload = DragDropPayload { paths:Some(paths), types:None, position };
 
 					webview.emit_to_webview(DRAG_DROP_EVENT, payload)?
 				},
@@ -695,6 +716,17 @@ fn on_webview_event<R:Runtime>(webview:&Webview<R>, event:&WebviewEvent) -> crat
 				_ => unimplemented!(),
 			}
 		},
+		WebviewEvent::ZoomChanged(factor) => {
+			webview.emit_to_webview(WEBVIEW_ZOOM_CHANGED_EVENT, factor)?
+		},
+		WebviewEvent::FindResultChanged(result) => {
+			webview.emit_to_webview(WEBVIEW_FIND_RESULT_CHANGED_EVENT, result)?
+		},
+		WebviewEvent::HistoryChanged(history) => {
+			webview.emit_to_webview(WEBVIEW_HISTORY_CHANGED_EVENT, history)?
+		},
+		WebviewEvent::DevtoolsOpened => webview.emit_to_webview(WEBVIEW_DEVTOOLS_OPENED_EVENT, ())?,
+		WebviewEvent::DevtoolsClosed => webview.emit_to_webview(WEBVIEW_DEVTOOLS_CLOSED_EVENT, ())?,
 	}
 
 	Ok(())
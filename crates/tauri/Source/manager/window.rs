@@ -6,7 +6,7 @@ use std::{
 	collections::{HashMap, HashSet},
 	fmt,
 	path::PathBuf,
-	sync::{Arc, Mutex, MutexGuard},
+	sync::{Arc, Mutex, MutexGuard, OnceLock},
 };
 
 use serde::Serialize;
@@ -25,7 +25,7 @@ use crate::{
 	Scopes,
 	Window,
 	WindowEvent,
-	app::GlobalWindowEventListener,
+	app::{GlobalWindowEventListener, OnWindowStateDropped},
 	image::Image,
 	sealed::ManagerBase,
 };
@@ -38,6 +38,8 @@ const WINDOW_FOCUS_EVENT:&str = "tauri://focus";
 const WINDOW_BLUR_EVENT:&str = "tauri://blur";
 const WINDOW_SCALE_FACTOR_CHANGED_EVENT:&str = "tauri://scale-change";
 const WINDOW_THEME_CHANGED:&str = "tauri://theme-changed";
+const WINDOW_TITLE_BAR_RECTS_CHANGED:&str = "tauri://title-bar-rects-changed";
+const WINDOW_FULLSCREEN_MONITOR_DISCONNECTED:&str = "tauri://fullscreen-monitor-disconnected";
 pub(crate) const DRAG_ENTER_EVENT:&str = "tauri://drag-enter";
 pub(crate) const DRAG_OVER_EVENT:&str = "tauri://drag-over";
 pub(crate) const DRAG_DROP_EVENT:&str = "tauri://drag-drop";
@@ -48,6 +50,23 @@ pub struct WindowManager<R:Runtime> {
 	pub default_icon:Option<Image<'static>>,
 	/// Window event listeners to all windows.
 	pub event_listeners:Arc<Vec<GlobalWindowEventListener<R>>>,
+	/// The parent window label and destroy policy of child windows, by child
+	/// window label.
+	#[cfg(desktop)]
+	pub(crate) parents:Mutex<HashMap<String, (String, crate::window::ParentDestroyPolicy)>>,
+	/// The saved properties of the windows created with
+	/// [`crate::window::WindowBuilder::restore_state`], by window label.
+	#[cfg(desktop)]
+	pub(crate) restored_states:Mutex<HashMap<String, crate::window::StateFlags>>,
+	/// The app id used by the Linux launcher APIs, see
+	/// [`crate::Builder::linux_app_id`].
+	pub(crate) linux_app_id:OnceLock<String>,
+	/// The hooks run before the state scoped to a window is released, see
+	/// [`crate::Builder::on_window_state_dropped`].
+	pub(crate) state_dropped_hooks:OnceLock<Vec<OnWindowStateDropped<R>>>,
+	/// Stops the indeterminate dock progress animation.
+	#[cfg(target_os = "macos")]
+	pub(crate) progress_animation:Mutex<Option<Arc<std::sync::atomic::AtomicBool>>>,
 }
 
 impl<R:Runtime> fmt::Debug for WindowManager<R> {
@@ -165,6 +184,8 @@ impl<R:Runtime> Window<R> {
 pub(crate) struct DragDropPayload<'a> {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub paths:Option<&'a Vec<PathBuf>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub types:Option<&'a Vec<String>>,
 	pub position:&'a PhysicalPosition<f64>,
 }
 
@@ -176,10 +197,28 @@ fn on_window_event<R:Runtime>(window:&Window<R>, event:&WindowEvent) -> crate::R
 			if window.has_js_listener(WINDOW_CLOSE_REQUESTED_EVENT) {
 				api.prevent_close();
 			}
+
+			#[cfg(desktop)]
+			crate::window::on_close_requested(window);
+
+			#[cfg(desktop)]
+			crate::window::state::on_close_requested(window);
+
 			window.emit_to_window(WINDOW_CLOSE_REQUESTED_EVENT, ())?;
 		},
 
 		WindowEvent::Destroyed => {
+			#[cfg(linux_dbus_menu)]
+			if let Some(exporter) = window.manager.menu.dbus_exporter.get() {
+				exporter.unexport::<R>(window.label());
+			}
+
+			#[cfg(desktop)]
+			crate::window::on_destroyed(window);
+
+			#[cfg(desktop)]
+			crate::window::state::on_destroyed(window);
+
 			window.emit_to_window(WINDOW_DESTROYED_EVENT, ())?;
 		},
 
@@ -195,8 +234,9 @@ fn on_window_event<R:Runtime>(window:&Window<R>, event:&WindowEvent) -> crate::R
 		},
 		WindowEvent::DragDrop(event) => {
 			match event {
-				DragDropEvent::Enter { paths, position } => {
-					let payload = DragDropPayload { paths:Some(paths), position };
+				DragDropEvent::Enter { paths, types, position } => {
+					let payload =
+						DragDropPayload { paths:Some(paths), types:Some(types), position };
 
 					if window.is_webview_window() {
 						window.emit_to(
@@ -209,7 +249,7 @@ fn on_window_event<R:Runtime>(window:&Window<R>, event:&WindowEvent) -> crate::R
 					}
 				},
 				DragDropEvent::Over { position } => {
-					let payload = DragDropPayload { position, paths:None };
+					let payload = DragDropPayload { position, paths:None, types:None };
 
 					if window.is_webview_window() {
 						window.emit_to(
@@ -232,7 +272,7 @@ fn on_window_event<R:Runtime>(window:&Window<R>, event:&WindowEvent) -> crate::R
 						}
 					}
 
-					let payload = DragDropPayload { paths:Some(paths), position };
+					let payload = DragDropPayload { paths:Some(paths), types:None, position };
 
 					if window.is_webview_window() {
 						window.emit_to(
@@ -261,6 +301,12 @@ fn on_window_event<R:Runtime>(window:&Window<R>, event:&WindowEvent) -> crate::R
 		WindowEvent::ThemeChanged(theme) => {
 			window.emit_to_window(WINDOW_THEME_CHANGED, theme.to_string())?
 		},
+		WindowEvent::TitleBarRectsChanged(rects) => {
+			window.emit_to_window(WINDOW_TITLE_BAR_RECTS_CHANGED, rects)?
+		},
+		WindowEvent::FullscreenMonitorDisconnected => {
+			window.emit_to_window(WINDOW_FULLSCREEN_MONITOR_DISCONNECTED, ())?
+		},
 	}
 	Ok(())
 }
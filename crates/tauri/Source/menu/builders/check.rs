@@ -15,6 +15,7 @@ pub struct CheckMenuItemBuilder {
 	enabled:bool,
 	checked:bool,
 	accelerator:Option<String>,
+	mnemonics:Option<bool>,
 }
 
 impl CheckMenuItemBuilder {
@@ -30,6 +31,7 @@ impl CheckMenuItemBuilder {
 			enabled:true,
 			checked:true,
 			accelerator:None,
+			mnemonics:None,
 		}
 	}
 
@@ -45,6 +47,7 @@ impl CheckMenuItemBuilder {
 			enabled:true,
 			checked:true,
 			accelerator:None,
+			mnemonics:None,
 		}
 	}
 
@@ -76,19 +79,29 @@ impl CheckMenuItemBuilder {
 		self
 	}
 
+	/// Whether `&` in the text assigns a mnemonic.
+	///
+	/// When `false`, `&` is displayed as is. This overrides
+	/// [`MenuBuilder::mnemonics`](crate::menu::MenuBuilder::mnemonics) of the
+	/// menus this item is added to.
+	pub fn mnemonics(mut self, enabled:bool) -> Self {
+		self.mnemonics.replace(enabled);
+
+		self
+	}
+
 	/// Build the menu item
 	pub fn build<R:Runtime, M:Manager<R>>(self, manager:&M) -> crate::Result<CheckMenuItem<R>> {
-		if let Some(id) = self.id {
-			CheckMenuItem::with_id(
-				manager,
-				id,
-				self.text,
-				self.enabled,
-				self.checked,
-				self.accelerator,
-			)
+		let text = super::mnemonics_text(self.text, self.mnemonics);
+
+		let item = if let Some(id) = self.id {
+			CheckMenuItem::with_id(manager, id, text, self.enabled, self.checked, self.accelerator)
 		} else {
-			CheckMenuItem::new(manager, self.text, self.enabled, self.checked, self.accelerator)
-		}
+			CheckMenuItem::new(manager, text, self.enabled, self.checked, self.accelerator)
+		}?;
+
+		super::record_mnemonics(manager.app_handle(), item.key(), self.mnemonics);
+
+		Ok(item)
 	}
 }
@@ -17,6 +17,7 @@ pub struct IconMenuItemBuilder<'a> {
 	icon:Option<Image<'a>>,
 	native_icon:Option<NativeIcon>,
 	accelerator:Option<String>,
+	mnemonics:Option<bool>,
 }
 
 impl<'a> IconMenuItemBuilder<'a> {
@@ -33,6 +34,7 @@ impl<'a> IconMenuItemBuilder<'a> {
 			icon:None,
 			native_icon:None,
 			accelerator:None,
+			mnemonics:None,
 		}
 	}
 
@@ -49,6 +51,7 @@ impl<'a> IconMenuItemBuilder<'a> {
 			icon:None,
 			native_icon:None,
 			accelerator:None,
+			mnemonics:None,
 		}
 	}
 
@@ -97,26 +100,32 @@ impl<'a> IconMenuItemBuilder<'a> {
 		self
 	}
 
+	/// Whether `&` in the text assigns a mnemonic.
+	///
+	/// When `false`, `&` is displayed as is. This overrides
+	/// [`MenuBuilder::mnemonics`](crate::menu::MenuBuilder::mnemonics) of the
+	/// menus this item is added to.
+	pub fn mnemonics(mut self, enabled:bool) -> Self {
+		self.mnemonics.replace(enabled);
+
+		self
+	}
+
 	/// Build the menu item
 	pub fn build<R:Runtime, M:Manager<R>>(self, manager:&M) -> crate::Result<IconMenuItem<R>> {
-		if self.icon.is_some() {
+		let text = super::mnemonics_text(self.text, self.mnemonics);
+
+		let item = if self.icon.is_some() {
 			if let Some(id) = self.id {
-				IconMenuItem::with_id(
-					manager,
-					id,
-					self.text,
-					self.enabled,
-					self.icon,
-					self.accelerator,
-				)
+				IconMenuItem::with_id(manager, id, text, self.enabled, self.icon, self.accelerator)
 			} else {
-				IconMenuItem::new(manager, self.text, self.enabled, self.icon, self.accelerator)
+				IconMenuItem::new(manager, text, self.enabled, self.icon, self.accelerator)
 			}
 		} else if let Some(id) = self.id {
 			IconMenuItem::with_id_and_native_icon(
 				manager,
 				id,
-				self.text,
+				text,
 				self.enabled,
 				self.native_icon,
 				self.accelerator,
@@ -124,11 +133,15 @@ impl<'a> IconMenuItemBuilder<'a> {
 		} else {
 			IconMenuItem::with_native_icon(
 				manager,
-				self.text,
+				text,
 				self.enabled,
 				self.native_icon,
 				self.accelerator,
 			)
-		}
+		}?;
+
+		super::record_mnemonics(manager.app_handle(), item.key(), self.mnemonics);
+
+		Ok(item)
 	}
 }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{Manager, Runtime, image::Image, menu::*};
+use crate::{AppHandle, Manager, Runtime, image::Image, menu::*};
 
 /// A builder type for [`Menu`]
 ///
@@ -30,6 +30,7 @@ use crate::{Manager, Runtime, image::Image, menu::*};
 /// 		.paste()
 /// 		.separator()
 /// 		.text("item2", "MenuItem 2")
+/// 		.text_with_accelerator("save", "Save", "CmdOrCtrl+S")
 /// 		.check("checkitem2", "CheckMenuItem 2")
 /// 		.icon("iconitem2", "IconMenuItem 2", app.default_window_icon().cloned().unwrap())
 /// 		.build()?;
@@ -41,34 +42,92 @@ pub struct MenuBuilder<'m, R:Runtime, M:Manager<R>> {
 	pub(crate) id:Option<MenuId>,
 	pub(crate) manager:&'m M,
 	pub(crate) items:Vec<crate::Result<MenuItemKind<R>>>,
+	pub(crate) mnemonics:bool,
+	pub(crate) duplicate_accelerators:bool,
 }
 
 impl<'m, R:Runtime, M:Manager<R>> MenuBuilder<'m, R, M> {
 	/// Create a new menu builder.
-	pub fn new(manager:&'m M) -> Self { Self { id:None, items:Vec::new(), manager } }
+	pub fn new(manager:&'m M) -> Self {
+		Self {
+			id:None,
+			items:Vec::new(),
+			mnemonics:true,
+			duplicate_accelerators:false,
+			manager,
+		}
+	}
 
 	/// Create a new menu builder with the specified id.
 	pub fn with_id<I:Into<MenuId>>(manager:&'m M, id:I) -> Self {
-		Self { id:Some(id.into()), items:Vec::new(), manager }
+		Self {
+			id:Some(id.into()),
+			items:Vec::new(),
+			mnemonics:true,
+			duplicate_accelerators:false,
+			manager,
+		}
 	}
 
 	/// Builds this menu
+	///
+	/// If some items failed to be created, e.g. because of an invalid
+	/// accelerator, returns [`crate::Error::MenuBuild`] listing all of them.
 	pub fn build(self) -> crate::Result<Menu<R>> {
+		let items = super::collect_items(self.items)?;
+
 		let menu = if let Some(id) = self.id {
 			Menu::with_id(self.manager, id)?
 		} else {
 			Menu::new(self.manager)?
 		};
 
-		for item in self.items {
-			let item = item?;
-			menu.append(&item)?;
+		if !self.mnemonics {
+			self.manager
+				.app_handle()
+				.manager
+				.menu
+				.update_item_state(menu.key(), |state| state.mnemonics_disabled = true);
+		}
+
+		if self.duplicate_accelerators {
+			self.manager
+				.app_handle()
+				.manager
+				.menu
+				.update_item_state(menu.key(), |state| state.duplicate_accelerators_allowed = true);
+		}
+
+		for (id, role) in [(WINDOW_SUBMENU_ID, "Window"), (HELP_SUBMENU_ID, "Help")] {
+			if count_submenus_with_id(&items, id)? > 1 {
+				return Err(crate::Error::MenuRoleConflict(format!(
+					"more than one submenu has the {role} menu role"
+				)));
+			}
 		}
 
+		menu.append_items(&items.iter().map(|i| i as &dyn IsMenuItem<R>).collect::<Vec<_>>())?;
+
 		Ok(menu)
 	}
 }
 
+/// Counts the submenus with the given id in `items` and in their nested
+/// submenus.
+fn count_submenus_with_id<R:Runtime>(items:&[MenuItemKind<R>], id:&str) -> crate::Result<usize> {
+	let mut count = 0;
+
+	for submenu in items.iter().filter_map(MenuItemKind::as_submenu) {
+		if *submenu.id() == id {
+			count += 1;
+		}
+
+		count += count_submenus_with_id(&submenu.items()?, id)?;
+	}
+
+	Ok(count)
+}
+
 /// A builder type for [`Submenu`]
 ///
 /// # Example
@@ -106,6 +165,9 @@ pub struct SubmenuBuilder<'m, R:Runtime, M:Manager<R>> {
 	pub(crate) text:String,
 	pub(crate) enabled:bool,
 	pub(crate) items:Vec<crate::Result<MenuItemKind<R>>>,
+	pub(crate) role:Option<&'static str>,
+	pub(crate) mnemonics:bool,
+	pub(crate) duplicate_accelerators:bool,
 }
 
 impl<'m, R:Runtime, M:Manager<R>> SubmenuBuilder<'m, R, M> {
@@ -115,7 +177,16 @@ impl<'m, R:Runtime, M:Manager<R>> SubmenuBuilder<'m, R, M> {
 	///   this character as the mnemonic for this menu item. To display a `&`
 	///   without assigning a mnemenonic, use `&&`.
 	pub fn new<S:AsRef<str>>(manager:&'m M, text:S) -> Self {
-		Self { id:None, items:Vec::new(), text:text.as_ref().to_string(), enabled:true, manager }
+		Self {
+			id:None,
+			items:Vec::new(),
+			text:text.as_ref().to_string(),
+			enabled:true,
+			role:None,
+			mnemonics:true,
+			duplicate_accelerators:false,
+			manager,
+		}
 	}
 
 	/// Create a new submenu builder with the specified id.
@@ -129,6 +200,9 @@ impl<'m, R:Runtime, M:Manager<R>> SubmenuBuilder<'m, R, M> {
 			text:text.as_ref().to_string(),
 			enabled:true,
 			items:Vec::new(),
+			role:None,
+			mnemonics:true,
+			duplicate_accelerators:false,
 			manager,
 		}
 	}
@@ -140,19 +214,76 @@ impl<'m, R:Runtime, M:Manager<R>> SubmenuBuilder<'m, R, M> {
 		self
 	}
 
+	/// Mark this submenu as the Window menu, where macOS lists the open windows.
+	///
+	/// The role replaces the submenu id with [`WINDOW_SUBMENU_ID`] and is applied
+	/// when the submenu is a top-level item of the app menu. See
+	/// [`Menu::default_window_submenu`] for the stock Window menu.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Windows / Linux**: Unsupported, the role has no effect.
+	pub fn window_menu_role(self) -> Self { self.role(WINDOW_SUBMENU_ID) }
+
+	/// Mark this submenu as the Help menu, where macOS adds a search field.
+	///
+	/// The role replaces the submenu id with [`HELP_SUBMENU_ID`] and is applied
+	/// when the submenu is a top-level item of the app menu. See
+	/// [`Menu::default_help_submenu`] for the stock Help menu.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Windows / Linux**: Unsupported, the role has no effect.
+	pub fn help_menu_role(self) -> Self { self.role(HELP_SUBMENU_ID) }
+
+	fn role(mut self, role:&'static str) -> Self {
+		if let Some(previous) = self.role.replace(role) {
+			if previous != role {
+				self.items.push(Err(crate::Error::MenuRoleConflict(format!(
+					"submenu `{}` can't have both the Window and Help menu roles",
+					self.text
+				))));
+			}
+		}
+
+		self
+	}
+
 	/// Builds this submenu
+	///
+	/// If some items failed to be created, e.g. because of an invalid
+	/// accelerator, returns [`crate::Error::MenuBuild`] listing all of them.
 	pub fn build(self) -> crate::Result<Submenu<R>> {
-		let submenu = if let Some(id) = self.id {
+		let id:Option<MenuId> = self.role.map(Into::into).or(self.id);
+
+		let items = super::collect_items(self.items)?;
+
+		let submenu = if let Some(id) = id {
 			Submenu::with_id(self.manager, id, self.text, self.enabled)?
 		} else {
 			Submenu::new(self.manager, self.text, self.enabled)?
 		};
 
-		for item in self.items {
-			let item = item?;
-			submenu.append(&item)?;
+		if !self.mnemonics {
+			self.manager
+				.app_handle()
+				.manager
+				.menu
+				.update_item_state(submenu.key(), |state| state.mnemonics_disabled = true);
+		}
+
+		if self.duplicate_accelerators {
+			self.manager
+				.app_handle()
+				.manager
+				.menu
+				.update_item_state(submenu.key(), |state| {
+					state.duplicate_accelerators_allowed = true
+				});
 		}
 
+		submenu.append_items(&items.iter().map(|i| i as &dyn IsMenuItem<R>).collect::<Vec<_>>())?;
+
 		Ok(submenu)
 	}
 }
@@ -167,6 +298,33 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Whether `&` in the item texts assigns a mnemonic, `true` by default.
+			///
+			/// When disabled, `&` is escaped before the text is given to the native
+			/// layer so it is displayed as is, e.g. `Save & Export`. This applies to
+			/// the items added with this builder, including the ones created by its
+			/// helpers like [`Self::text`], and to the items added to the built menu
+			/// later. Items with their own setting, e.g.
+			/// [`MenuItemBuilder::mnemonics`], keep it.
+			pub fn mnemonics(mut self, enabled:bool) -> Self {
+				self.mnemonics = enabled;
+
+				self
+			}
+
+			/// Whether items of the menu, including the items of its submenus, may
+			/// have the same accelerator, `false` by default.
+			///
+			/// When disallowed, building the menu or adding an item to it later
+			/// fails with [`crate::Error::AcceleratorConflict`] if an accelerator
+			/// is used twice. Allow it for menus that intentionally duplicate
+			/// accelerators, e.g. across platform-specific submenus.
+			pub fn allow_duplicate_accelerators(mut self, allow:bool) -> Self {
+				self.duplicate_accelerators = allow;
+
+				self
+			}
+
 			/// Add this item to the menu.
 			pub fn item(mut self, item:&dyn IsMenuItem<R>) -> Self {
 				self.items.push(Ok(item.kind()));
@@ -174,6 +332,18 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Insert this item at `position` in the menu, appending it if `position`
+			/// is past the end.
+			///
+			/// Positions are indices into the items added so far.
+			pub fn item_at(mut self, position:usize, item:&dyn IsMenuItem<R>) -> Self {
+				let position = position.min(self.items.len());
+
+				self.items.insert(position, Ok(item.kind()));
+
+				self
+			}
+
 			/// Add these items to the menu.
 			pub fn items(mut self, items:&[&dyn IsMenuItem<R>]) -> Self {
 				for item in items {
@@ -183,6 +353,41 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Add this item to the menu if `condition` is `true`.
+			pub fn item_if(self, condition:bool, item:&dyn IsMenuItem<R>) -> Self {
+				if condition { self.item(item) } else { self }
+			}
+
+			/// Add these items to the menu if `condition` is `true`.
+			pub fn items_if(self, condition:bool, items:&[&dyn IsMenuItem<R>]) -> Self {
+				if condition { self.items(items) } else { self }
+			}
+
+			/// Call `f` with this builder if `condition` is `true`, keeping
+			/// conditional sections inline.
+			///
+			/// The items added in `f` aren't created at all when `condition` is
+			/// `false`, unlike the ones given to [`Self::item_if`].
+			///
+			/// # Example
+			///
+			/// ```no_run
+			/// use tauri::menu::SubmenuBuilder;
+			///
+			/// tauri::Builder::default().setup(|app| {
+			/// 	let submenu = SubmenuBuilder::new(app, "App")
+			/// 		.about(None)
+			/// 		.apply_if(cfg!(target_os = "macos"), |b| b.separator().services())
+			/// 		.separator()
+			/// 		.quit()
+			/// 		.build()?;
+			/// 	Ok(())
+			/// });
+			/// ```
+			pub fn apply_if<F:FnOnce(Self) -> Self>(self, condition:bool, f:F) -> Self {
+				if condition { f(self) } else { self }
+			}
+
 			/// Add a [MenuItem] to the menu.
 			pub fn text<I:Into<MenuId>, S:AsRef<str>>(mut self, id:I, text:S) -> Self {
 				self.items.push(
@@ -192,10 +397,65 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
-			/// Add a [CheckMenuItem] to the menu.
-			pub fn check<I:Into<MenuId>, S:AsRef<str>>(mut self, id:I, text:S) -> Self {
+			/// Add a [MenuItem] with a click handler to the menu, see
+			/// [`MenuItemBuilder::on_click`].
+			pub fn text_with_handler<I, S, F>(mut self, id:I, text:S, handler:F) -> Self
+			where
+				I: Into<MenuId>,
+				S: AsRef<str>,
+				F: Fn(&AppHandle<R>) + Send + Sync + 'static, {
+				self.items.push(
+					MenuItemBuilder::with_id(id, text)
+						.on_click(handler)
+						.build(self.manager)
+						.map(|i| i.kind()),
+				);
+
+				self
+			}
+
+			/// Add a disabled [MenuItem] to the menu.
+			pub fn text_disabled<I:Into<MenuId>, S:AsRef<str>>(mut self, id:I, text:S) -> Self {
+				self.items.push(
+					MenuItem::with_id(self.manager, id, text, false, None::<&str>).map(|i| i.kind()),
+				);
+
+				self
+			}
+
+			/// Add a checked [CheckMenuItem] to the menu.
+			///
+			/// Use [`Self::check_with_state`] to add an unchecked item.
+			pub fn check<I:Into<MenuId>, S:AsRef<str>>(self, id:I, text:S) -> Self {
+				self.check_with_state(id, text, true)
+			}
+
+			/// Add a [CheckMenuItem] with the given initial `checked` state to the
+			/// menu.
+			pub fn check_with_state<I:Into<MenuId>, S:AsRef<str>>(
+				mut self,
+				id:I,
+				text:S,
+				checked:bool,
+			) -> Self {
+				self.items.push(
+					CheckMenuItem::with_id(self.manager, id, text, true, checked, None::<&str>)
+						.map(|i| i.kind()),
+				);
+
+				self
+			}
+
+			/// Add a disabled [CheckMenuItem] with the given `checked` state to the
+			/// menu.
+			pub fn check_disabled<I:Into<MenuId>, S:AsRef<str>>(
+				mut self,
+				id:I,
+				text:S,
+				checked:bool,
+			) -> Self {
 				self.items.push(
-					CheckMenuItem::with_id(self.manager, id, text, true, true, None::<&str>)
+					CheckMenuItem::with_id(self.manager, id, text, false, checked, None::<&str>)
 						.map(|i| i.kind()),
 				);
 
@@ -217,6 +477,84 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Add a [MenuItem] with a keyboard accelerator to the menu.
+			///
+			/// An invalid accelerator is reported as an error by `build`.
+			pub fn text_with_accelerator<I:Into<MenuId>, S:AsRef<str>, A:AsRef<str>>(
+				mut self,
+				id:I,
+				text:S,
+				accelerator:A,
+			) -> Self {
+				self.items.push(parse_accelerator(accelerator.as_ref()).and_then(|()| {
+					MenuItem::with_id(self.manager, id, text, true, Some(accelerator)).map(|i| i.kind())
+				}));
+
+				self
+			}
+
+			/// Add a [CheckMenuItem] with a keyboard accelerator to the menu.
+			///
+			/// An invalid accelerator is reported as an error by `build`.
+			pub fn check_with_accelerator<I:Into<MenuId>, S:AsRef<str>, A:AsRef<str>>(
+				mut self,
+				id:I,
+				text:S,
+				accelerator:A,
+			) -> Self {
+				self.items.push(parse_accelerator(accelerator.as_ref()).and_then(|()| {
+					CheckMenuItem::with_id(self.manager, id, text, true, true, Some(accelerator))
+						.map(|i| i.kind())
+				}));
+
+				self
+			}
+
+			/// Add an [IconMenuItem] with a keyboard accelerator to the menu.
+			///
+			/// An invalid accelerator is reported as an error by `build`.
+			pub fn icon_with_accelerator<I:Into<MenuId>, S:AsRef<str>, A:AsRef<str>>(
+				mut self,
+				id:I,
+				text:S,
+				icon:Image<'_>,
+				accelerator:A,
+			) -> Self {
+				self.items.push(parse_accelerator(accelerator.as_ref()).and_then(|()| {
+					IconMenuItem::with_id(self.manager, id, text, true, Some(icon), Some(accelerator))
+						.map(|i| i.kind())
+				}));
+
+				self
+			}
+
+			/// Add an [IconMenuItem] with an icon loaded from `path` to the menu, see
+			/// [`IconMenuItem::with_id_and_icon_path`].
+			///
+			/// Errors reading or decoding the icon are reported by `build`.
+			#[cfg(any(feature = "image-ico", feature = "image-png"))]
+			#[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+			pub fn icon_from_path<I:Into<MenuId>, S:AsRef<str>, P:AsRef<std::path::Path>>(
+				mut self,
+				id:I,
+				text:S,
+				path:P,
+			) -> Self {
+				self.items.push(
+					IconMenuItem::with_id_and_icon_path(
+						self.manager,
+						id,
+						text,
+						true,
+						path,
+						None::<&str>,
+					)
+					.map(|i| i.kind()),
+				);
+
+				self
+			}
+
 			/// Add an [IconMenuItem] with a native icon to the menu.
 			///
 			/// ## Platform-specific:
@@ -250,6 +588,17 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Insert a Separator menu item at `position` in the menu, appending it
+			/// if `position` is past the end.
+			pub fn separator_at(mut self, position:usize) -> Self {
+				let position = position.min(self.items.len());
+
+				self.items
+					.insert(position, PredefinedMenuItem::separator(self.manager).map(|i| i.kind()));
+
+				self
+			}
+
 			/// Add Copy menu item to the menu.
 			pub fn copy(mut self) -> Self {
 				self.items.push(PredefinedMenuItem::copy(self.manager, None).map(|i| i.kind()));
@@ -577,6 +926,18 @@ macro_rules! shared_menu_builder {
 				self
 			}
 
+			/// Add About app menu item to the menu, with the metadata of the running
+			/// app, see [`AboutMetadata::from_app`].
+			pub fn about_with_defaults(mut self) -> Self {
+				let metadata = AboutMetadata::from_app(self.manager.app_handle());
+
+				self.items.push(
+					PredefinedMenuItem::about(self.manager, None, Some(metadata)).map(|i| i.kind()),
+				);
+
+				self
+			}
+
 			/// Add About app menu item with specified text to the menu.
 			pub fn about_with_text<S:AsRef<str>>(
 				mut self,
@@ -616,9 +977,262 @@ macro_rules! shared_menu_builder {
 
 				self
 			}
+
+			/// Add a group of check menu items where only one item is checked at a
+			/// time, see [`CheckMenuItemGroup::new`].
+			pub fn radio_group(
+				mut self,
+				id_prefix:&str,
+				items:&[(&str, &str)],
+				selected:usize,
+			) -> Self {
+				match CheckMenuItemGroup::new(self.manager, id_prefix, items, selected) {
+					Ok(group) => self.items.extend(group.items().iter().map(|i| Ok(i.kind()))),
+					Err(e) => self.items.push(Err(e)),
+				}
+
+				self
+			}
+
+			/// Add Zoom window menu item to the menu.
+			///
+			/// ## Platform-specific:
+			///
+			/// - **Windows:** Behaves like [`Self::maximize`].
+			/// - **Linux:** Unsupported.
+			pub fn zoom(mut self) -> Self {
+				self.items.push(PredefinedMenuItem::zoom(self.manager, None).map(|i| i.kind()));
+
+				self
+			}
+
+			/// Add Zoom window menu item with specified text to the menu.
+			///
+			/// ## Platform-specific:
+			///
+			/// - **Windows:** Behaves like [`Self::maximize`].
+			/// - **Linux:** Unsupported.
+			pub fn zoom_with_text<S:AsRef<str>>(mut self, text:S) -> Self {
+				self.items.push(
+					PredefinedMenuItem::zoom(self.manager, Some(text.as_ref())).map(|i| i.kind()),
+				);
+
+				self
+			}
+
+			/// Add Bring All to Front menu item to the menu.
+			///
+			/// ## Platform-specific:
+			///
+			/// - **Windows / Linux:** Unsupported.
+			pub fn bring_all_to_front(mut self) -> Self {
+				self.items.push(
+					PredefinedMenuItem::bring_all_to_front(self.manager, None).map(|i| i.kind()),
+				);
+
+				self
+			}
+
+			/// Add Bring All to Front menu item with specified text to the menu.
+			///
+			/// ## Platform-specific:
+			///
+			/// - **Windows / Linux:** Unsupported.
+			pub fn bring_all_to_front_with_text<S:AsRef<str>>(mut self, text:S) -> Self {
+				self.items.push(
+					PredefinedMenuItem::bring_all_to_front(self.manager, Some(text.as_ref()))
+						.map(|i| i.kind()),
+				);
+
+				self
+			}
 		}
 	};
 }
 
 shared_menu_builder!(MenuBuilder<'m, R, M>);
 shared_menu_builder!(SubmenuBuilder<'m, R, M>);
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		image::Image,
+		menu::{MenuBuilder, MenuItem, MenuItemKind, SubmenuBuilder},
+		test::mock_app,
+	};
+
+	#[test]
+	fn nested_menu_roles_conflict() {
+		let app = mock_app();
+
+		let window = SubmenuBuilder::new(&app, "Window").window_menu_role().build().unwrap();
+
+		let nested = SubmenuBuilder::new(&app, "Windows").window_menu_role().build().unwrap();
+
+		let view = SubmenuBuilder::new(&app, "View").item(&nested).build().unwrap();
+
+		assert!(matches!(
+			MenuBuilder::new(&app).item(&window).item(&view).build(),
+			Err(crate::Error::MenuRoleConflict(_))
+		));
+
+		let help = SubmenuBuilder::new(&app, "Help").help_menu_role().build().unwrap();
+
+		assert!(MenuBuilder::new(&app).item(&view).item(&help).build().is_ok());
+	}
+
+	#[test]
+	fn items_are_inserted_at_their_position() {
+		let app = mock_app();
+
+		let save = MenuItem::with_id(&app, "save", "Save", true, None::<&str>).unwrap();
+
+		let close = MenuItem::with_id(&app, "close", "Close", true, None::<&str>).unwrap();
+
+		let submenu = SubmenuBuilder::new(&app, "File")
+			.text("open", "Open")
+			.text("quit", "Quit")
+			.item_at(1, &save)
+			.item_at(10, &close)
+			.separator_at(3)
+			.build()
+			.unwrap();
+
+		let items = submenu.items().unwrap();
+
+		assert!(matches!(items[3], MenuItemKind::Predefined(_)));
+
+		let ids = items
+			.iter()
+			.filter(|i| !matches!(i, MenuItemKind::Predefined(_)))
+			.map(|i| i.id().0.clone())
+			.collect::<Vec<_>>();
+
+		assert_eq!(ids, ["open", "save", "quit", "close"]);
+	}
+
+	#[test]
+	fn invalid_accelerators_fail_the_build() {
+		let app = mock_app();
+
+		let icon = || Image::new_owned(vec![0; 4], 1, 1);
+
+		let builders = [
+			MenuBuilder::new(&app).text("open", "Open").text_with_accelerator(
+				"save",
+				"Save",
+				"Ctrl+Nope",
+			),
+			MenuBuilder::new(&app).text("open", "Open").check_with_accelerator(
+				"autosave",
+				"Auto Save",
+				"Ctrl+Nope",
+			),
+			MenuBuilder::new(&app).text("open", "Open").icon_with_accelerator(
+				"export",
+				"Export",
+				icon(),
+				"Ctrl+Nope",
+			),
+		];
+
+		// the invalid accelerator is only reported once the menu is built
+		for builder in builders {
+			assert!(matches!(
+				builder.build(),
+				Err(crate::Error::MenuBuild(errors))
+					if matches!(errors[..], [(1, crate::Error::Menu(_))])
+			));
+		}
+
+		let menu = MenuBuilder::new(&app)
+			.text_with_accelerator("save", "Save", "CmdOrCtrl+S")
+			.check_with_accelerator("autosave", "Auto Save", "CmdOrCtrl+Shift+S")
+			.icon_with_accelerator("export", "Export", icon(), "CmdOrCtrl+E")
+			.build()
+			.unwrap();
+
+		assert_eq!(menu.items().unwrap().len(), 3);
+	}
+
+	#[test]
+	fn every_failed_item_is_reported() {
+		let app = mock_app();
+
+		let result = MenuBuilder::new(&app)
+			.text("open", "Open")
+			.text_with_accelerator("save", "Save", "Ctrl+Nope")
+			.separator()
+			.check_with_accelerator("autosave", "Auto Save", "Shift+Nope")
+			.build();
+
+		assert!(matches!(
+			result,
+			Err(crate::Error::MenuBuild(errors)) if matches!(
+				errors[..],
+				[(1, crate::Error::Menu(_)), (3, crate::Error::Menu(_))]
+			)
+		));
+	}
+
+	#[test]
+	fn items_are_built_with_their_state() {
+		let app = mock_app();
+
+		let menu = MenuBuilder::new(&app)
+			.text("open", "Open")
+			.text_disabled("save", "Save")
+			.check("autosave", "Auto Save")
+			.check_with_state("spellcheck", "Spell Check", false)
+			.check_disabled("sync", "Sync", true)
+			.check_disabled("backup", "Backup", false)
+			.build()
+			.unwrap();
+
+		let text = |id:&str| menu.get(id).unwrap().as_menuitem().unwrap().is_enabled().unwrap();
+
+		assert!(text("open"));
+		assert!(!text("save"));
+
+		let check = |id:&str| {
+			let item = menu.get(id).unwrap();
+
+			let item = item.as_check_menuitem().unwrap();
+
+			(item.is_enabled().unwrap(), item.is_checked().unwrap())
+		};
+
+		assert_eq!(check("autosave"), (true, true));
+		assert_eq!(check("spellcheck"), (true, false));
+		assert_eq!(check("sync"), (false, true));
+		assert_eq!(check("backup"), (false, false));
+	}
+
+	#[test]
+	fn apply_if_only_calls_the_closure_when_the_condition_holds() {
+		let app = mock_app();
+
+		let calls = std::cell::Cell::new(0);
+
+		let submenu = SubmenuBuilder::new(&app, "App")
+			.text("about", "About")
+			.apply_if(false, |b| {
+				calls.set(calls.get() + 1);
+
+				b.text("services", "Services")
+			})
+			.apply_if(true, |b| {
+				calls.set(calls.get() + 1);
+
+				b.text("quit", "Quit")
+			})
+			.build()
+			.unwrap();
+
+		assert_eq!(calls.get(), 1);
+
+		let ids = submenu.items().unwrap().iter().map(|i| i.id().0.clone()).collect::<Vec<_>>();
+
+		assert_eq!(ids, ["about", "quit"]);
+	}
+}
@@ -14,3 +14,51 @@ mod check;
 pub use check::CheckMenuItemBuilder;
 mod icon;
 pub use icon::IconMenuItemBuilder;
+
+/// Collects the deferred items of a builder, reporting all the items that
+/// failed in [`crate::Error::MenuBuild`].
+pub(crate) fn collect_items<R:crate::Runtime>(
+	items:Vec<crate::Result<crate::menu::MenuItemKind<R>>>,
+) -> crate::Result<Vec<crate::menu::MenuItemKind<R>>> {
+	let mut collected = Vec::with_capacity(items.len());
+
+	let mut errors = Vec::new();
+
+	for (index, item) in items.into_iter().enumerate() {
+		match item {
+			Ok(item) => collected.push(item),
+			Err(error) => errors.push((index, error)),
+		}
+	}
+
+	if errors.is_empty() { Ok(collected) } else { Err(crate::Error::MenuBuild(errors)) }
+}
+
+/// The text to create an item with, escaped if `mnemonics` is `Some(false)`.
+pub(crate) fn mnemonics_text(text:String, mnemonics:Option<bool>) -> String {
+	if mnemonics == Some(false) { crate::menu::escape_mnemonics(&text) } else { text }
+}
+
+/// Records the mnemonics setting of an item built with `mnemonics` set, so the
+/// menus it's added to keep it.
+pub(crate) fn record_mnemonics<R:crate::Runtime>(
+	app_handle:&crate::AppHandle<R>,
+	key:crate::menu::MenuItemKey,
+	mnemonics:Option<bool>,
+) {
+	if let Some(enabled) = mnemonics {
+		app_handle.manager.menu.update_item_metadata(key, |metadata| {
+			metadata.mnemonics = Some(enabled);
+
+			metadata.escaped_mnemonics = !enabled;
+		});
+	}
+}
+
+/// Validates an accelerator string, since the item constructors ignore invalid ones.
+pub(crate) fn parse_accelerator(accelerator:&str) -> crate::Result<()> {
+	accelerator
+		.parse::<muda::accelerator::Accelerator>()
+		.map(|_| ())
+		.map_err(|e| muda::Error::from(e).into())
+}
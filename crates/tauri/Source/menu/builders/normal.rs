@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
+use std::{any::Any, sync::Arc};
+
 use crate::{
+	AppHandle,
 	Manager,
 	Runtime,
+	app::MenuItemClickHandler,
 	menu::{MenuId, MenuItem},
 };
 
@@ -14,6 +18,10 @@ pub struct MenuItemBuilder {
 	text:String,
 	enabled:bool,
 	accelerator:Option<String>,
+	mnemonics:Option<bool>,
+	/// The [`MenuItemClickHandler`] of the runtime passed to `build`, the
+	/// builder itself isn't generic over the runtime.
+	on_click:Option<Box<dyn Any + Send + Sync>>,
 }
 
 impl MenuItemBuilder {
@@ -23,7 +31,14 @@ impl MenuItemBuilder {
 	///   this character as the mnemonic for this menu item. To display a `&`
 	///   without assigning a mnemenonic, use `&&`.
 	pub fn new<S:AsRef<str>>(text:S) -> Self {
-		Self { id:None, text:text.as_ref().to_string(), enabled:true, accelerator:None }
+		Self {
+			id:None,
+			text:text.as_ref().to_string(),
+			enabled:true,
+			accelerator:None,
+			mnemonics:None,
+			on_click:None,
+		}
 	}
 
 	/// Create a new menu item builder with the specified id.
@@ -37,6 +52,8 @@ impl MenuItemBuilder {
 			text:text.as_ref().to_string(),
 			enabled:true,
 			accelerator:None,
+			mnemonics:None,
+			on_click:None,
 		}
 	}
 
@@ -61,12 +78,60 @@ impl MenuItemBuilder {
 		self
 	}
 
+	/// Whether `&` in the text assigns a mnemonic.
+	///
+	/// When `false`, `&` is displayed as is. This overrides
+	/// [`MenuBuilder::mnemonics`](crate::menu::MenuBuilder::mnemonics) of the
+	/// menus this item is added to.
+	pub fn mnemonics(mut self, enabled:bool) -> Self {
+		self.mnemonics.replace(enabled);
+
+		self
+	}
+
+	/// Set a closure that is called when this menu item is clicked.
+	///
+	/// Clicks on this item are no longer delivered to the app-wide
+	/// [`on_menu_event`](crate::App::on_menu_event) listeners. The closure is
+	/// dropped with the item: once it isn't in a menu or submenu anymore and
+	/// all its [`MenuItem`]s are dropped.
+	///
+	/// Clicks are reported by item id, so when several items share an id the
+	/// closures of all of them are called.
+	///
+	/// The runtime `R` must be the one of the manager passed to
+	/// [`Self::build`], which otherwise fails.
+	pub fn on_click<R:Runtime, F:Fn(&AppHandle<R>) + Send + Sync + 'static>(
+		mut self,
+		handler:F,
+	) -> Self {
+		let handler:MenuItemClickHandler<R> = Arc::new(handler);
+
+		self.on_click.replace(Box::new(handler));
+
+		self
+	}
+
 	/// Build the menu item
 	pub fn build<R:Runtime, M:Manager<R>>(self, manager:&M) -> crate::Result<MenuItem<R>> {
-		if let Some(id) = self.id {
-			MenuItem::with_id(manager, id, self.text, self.enabled, self.accelerator)
+		let text = super::mnemonics_text(self.text, self.mnemonics);
+
+		let item = if let Some(id) = self.id {
+			MenuItem::with_id(manager, id, text, self.enabled, self.accelerator)
 		} else {
-			MenuItem::new(manager, self.text, self.enabled, self.accelerator)
+			MenuItem::new(manager, text, self.enabled, self.accelerator)
+		}?;
+
+		super::record_mnemonics(manager.app_handle(), item.key(), self.mnemonics);
+
+		if let Some(handler) = self.on_click {
+			let handler = handler
+				.downcast::<MenuItemClickHandler<R>>()
+				.map_err(|_| crate::Error::MenuItemClickHandlerRuntime(item.id().0.clone()))?;
+
+			manager.app_handle().manager.menu.on_menu_item_click(item.key(), *handler);
 		}
+
+		Ok(item)
 	}
 }
@@ -2,8 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::sync::Arc;
-
 use super::{CheckMenuItem, run_item_main_thread};
 use crate::{
 	AppHandle,
@@ -36,14 +34,18 @@ impl<R:Runtime> CheckMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
 
 		let item = run_main_thread!(handle, || {
 			let item = muda::CheckMenuItem::new(text, enabled, checked, accelerator);
 			CheckMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.accelerator = accelerator_text);
+
+		Ok(item)
 	}
 
 	/// Create a new menu item with the specified id.
@@ -72,7 +74,7 @@ impl<R:Runtime> CheckMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
 
 		let item = run_main_thread!(handle, || {
 			let item =
@@ -80,7 +82,11 @@ impl<R:Runtime> CheckMenuItem<R> {
 			CheckMenuItemInner { id, inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.accelerator = accelerator_text);
+
+		Ok(item)
 	}
 
 	/// The application handle associated with this type.
@@ -91,7 +97,9 @@ impl<R:Runtime> CheckMenuItem<R> {
 
 	/// Get the text for this menu item.
 	pub fn text(&self) -> crate::Result<String> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())
+		let text = run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())?;
+
+		Ok(self.0.app_handle.manager.menu.display_text(self.key(), text))
 	}
 
 	/// Set the text for this menu item. `text` could optionally contain
@@ -99,9 +107,13 @@ impl<R:Runtime> CheckMenuItem<R> {
 	/// for this menu item. To display a `&` without assigning a mnemenonic, use
 	/// `&&`.
 	pub fn set_text<S:AsRef<str>>(&self, text:S) -> crate::Result<()> {
-		let text = text.as_ref().to_string();
+		let text = self.0.app_handle.manager.menu.native_text(self.key(), text.as_ref());
+
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))?;
 
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Get whether this menu item is enabled or not.
@@ -111,15 +123,36 @@ impl<R:Runtime> CheckMenuItem<R> {
 
 	/// Enable or disable this menu item.
 	pub fn set_enabled(&self, enabled:bool) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
-	/// Set this menu item accelerator.
+	/// Set this menu item accelerator, or remove it with `None`.
+	///
+	/// Updates both the shortcut hint displayed next to the item and the key
+	/// handling. Returns an error if `accelerator` isn't a valid accelerator,
+	/// leaving the current one in place.
 	pub fn set_accelerator<S:AsRef<str>>(&self, accelerator:Option<S>) -> crate::Result<()> {
-		let accel = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accel, accelerator) = super::try_parse_item_accelerator(accelerator)?;
+
+		run_item_main_thread!(self, |self_:Self| { (*self_.0).as_ref().set_accelerator(accel) })??;
+
+		let menu = &self.0.app_handle.manager.menu;
 
-		run_item_main_thread!(self, |self_:Self| { (*self_.0).as_ref().set_accelerator(accel) })?
-			.map_err(Into::into)
+		menu.update_item_metadata(self.key(), |m| m.accelerator = accelerator);
+
+		menu.notify_changed();
+
+		Ok(())
+	}
+
+	/// Returns the accelerator of this menu item as it was given, e.g.
+	/// `CmdOrCtrl+Shift+E`, if it has one.
+	pub fn accelerator(&self) -> Option<String> {
+		self.0.app_handle.manager.menu.item_metadata(self.key()).accelerator
 	}
 
 	/// Get whether this check menu item is checked or not.
@@ -129,6 +162,29 @@ impl<R:Runtime> CheckMenuItem<R> {
 
 	/// Check or Uncheck this check menu item.
 	pub fn set_checked(&self, checked:bool) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_checked(checked))
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_checked(checked))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::mock_app;
+
+	#[test]
+	fn invalid_accelerators_keep_the_previous_one() {
+		let app = mock_app();
+
+		let item =
+			CheckMenuItem::with_id(&app, "autosave", "Auto Save", true, false, Some("CmdOrCtrl+A"))
+				.unwrap();
+
+		assert!(item.set_accelerator(Some("Ctrl+Nope")).is_err());
+
+		assert_eq!(item.accelerator().as_deref(), Some("CmdOrCtrl+A"));
 	}
 }
@@ -0,0 +1,431 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+	plugin::{Predefined, PredefinedMenuItemPayload},
+	*,
+};
+use crate::{AppHandle, Manager, Runtime, image::JsImage};
+
+/// A declarative menu definition, in the shape accepted by the JavaScript
+/// `Menu.new` API.
+///
+/// See [`Menu::from_value`] and [`Menu::from_config`], and [`Menu::to_value`]
+/// to describe an existing menu.
+///
+/// # Example
+///
+/// ```json
+/// {
+///   "items": [{
+///     "text": "File",
+///     "items": [
+///       { "id": "open", "text": "Open", "accelerator": "CmdOrCtrl+O" },
+///       { "id": "autosave", "text": "Auto Save", "checked": true },
+///       { "item": "Separator" },
+///       { "item": "Quit" }
+///     ]
+///   }]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuConfig {
+	/// The menu id.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub id:Option<MenuId>,
+	/// The menu items.
+	#[serde(default)]
+	pub items:Vec<MenuItemConfig>,
+}
+
+/// A menu item definition, see [`MenuConfig`].
+///
+/// The item kind is inferred from its fields like the JavaScript API does:
+/// `item` makes a [`PredefinedMenuItem`], `checked` a [`CheckMenuItem`],
+/// `icon` an [`IconMenuItem`], `items` a [`Submenu`] and anything else a
+/// [`MenuItem`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuItemConfig {
+	/// The item id.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub id:Option<MenuId>,
+	/// The item text, required for every kind except predefined items.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub text:Option<String>,
+	/// Whether the item is enabled, defaults to `true`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub enabled:Option<bool>,
+	/// Whether the check item is checked.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub checked:Option<bool>,
+	/// The keyboard accelerator, e.g. `CmdOrCtrl+S`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub accelerator:Option<String>,
+	/// The item icon.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub icon:Option<MenuIconConfig>,
+	/// The predefined item kind, e.g. `"Separator"` or
+	/// `{ "About": { "name": "My App" } }`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub item:Option<Value>,
+	/// The submenu items.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub items:Option<Vec<MenuItemConfig>>,
+}
+
+/// A menu item icon, see [`MenuItemConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MenuIconConfig {
+	/// A native icon.
+	Native(NativeIcon),
+	/// A path to an image file, or a `data:` URL with the base64 encoded image
+	/// bytes.
+	Image(String),
+}
+
+impl MenuConfig {
+	/// Builds the menu described by this definition.
+	pub fn build<R:Runtime, M:Manager<R>>(&self, manager:&M) -> crate::Result<Menu<R>> {
+		let menu = match &self.id {
+			Some(id) => Menu::with_id(manager, id.clone())?,
+			None => Menu::new(manager)?,
+		};
+
+		for (index, item) in self.items.iter().enumerate() {
+			menu.append(&item.build(manager, &format!("items[{index}]"))?)?;
+		}
+
+		Ok(menu)
+	}
+}
+
+impl MenuItemConfig {
+	/// Describes an existing item with its current state.
+	///
+	/// Image icons are only described if `icons` is `true`, as base64 encoded
+	/// PNG `data:` URLs.
+	pub(crate) fn from_item<R:Runtime>(
+		app_handle:&AppHandle<R>,
+		item:&MenuItemKind<R>,
+		icons:bool,
+	) -> crate::Result<Self> {
+		let metadata = app_handle.manager.menu.item_metadata(item.key());
+
+		let mut config = Self { id:Some(item.id().clone()), ..Default::default() };
+
+		match item {
+			MenuItemKind::MenuItem(i) => {
+				config.text = Some(i.text()?);
+
+				config.enabled = Some(i.is_enabled()?);
+
+				config.accelerator = metadata.accelerator;
+			},
+			MenuItemKind::Check(i) => {
+				config.text = Some(i.text()?);
+
+				config.enabled = Some(i.is_enabled()?);
+
+				config.checked = Some(i.is_checked()?);
+
+				config.accelerator = metadata.accelerator;
+			},
+			MenuItemKind::Icon(i) => {
+				config.text = Some(i.text()?);
+
+				config.enabled = Some(i.is_enabled()?);
+
+				config.accelerator = metadata.accelerator;
+
+				config.icon = match metadata.icon {
+					Some(MenuItemIcon::Native(icon)) => Some(MenuIconConfig::Native(icon)),
+					#[cfg(feature = "image-png")]
+					Some(MenuItemIcon::Image(image)) if icons => {
+						Some(MenuIconConfig::Image(icon_data_url(&image)?))
+					},
+					_ => None,
+				};
+			},
+			MenuItemKind::Submenu(i) => {
+				config.text = Some(i.text()?);
+
+				config.enabled = Some(i.is_enabled()?);
+
+				config.items = Some(
+					i.items()?
+						.iter()
+						.map(|item| Self::from_item(app_handle, item, icons))
+						.collect::<crate::Result<_>>()?,
+				);
+			},
+			MenuItemKind::Predefined(i) => {
+				// predefined items keep their default text unless it was overridden,
+				// so it's always safe to pass it back
+				config.text = Some(i.text()?).filter(|text| !text.is_empty());
+
+				config.item = metadata.predefined.map(Value::from);
+			},
+		}
+
+		#[cfg(not(feature = "image-png"))]
+		let _ = icons;
+
+		Ok(config)
+	}
+
+	fn build<R:Runtime, M:Manager<R>>(
+		&self,
+		manager:&M,
+		path:&str,
+	) -> crate::Result<MenuItemKind<R>> {
+		if let Some(item) = &self.item {
+			return self.build_predefined(manager, item, path).map(|i| i.kind());
+		}
+
+		let text = self.text.as_deref().ok_or_else(|| invalid(path, "missing `text`"))?;
+
+		let enabled = self.enabled.unwrap_or(true);
+
+		if let Some(accelerator) = &self.accelerator {
+			parse_accelerator(accelerator)
+				.map_err(|e| invalid(&format!("{path}.accelerator"), e.to_string()))?;
+		}
+
+		if let Some(checked) = self.checked {
+			let mut builder = CheckMenuItemBuilder::new(text).enabled(enabled).checked(checked);
+
+			if let Some(id) = &self.id {
+				builder = builder.id(id.clone());
+			}
+
+			if let Some(accelerator) = &self.accelerator {
+				builder = builder.accelerator(accelerator);
+			}
+
+			return builder.build(manager).map(|i| i.kind());
+		}
+
+		if let Some(icon) = &self.icon {
+			let mut builder = IconMenuItemBuilder::new(text).enabled(enabled);
+
+			builder = match icon {
+				MenuIconConfig::Native(icon) => builder.native_icon(*icon),
+				MenuIconConfig::Image(image) => {
+					let icon = load_icon(manager, image)
+						.map_err(|e| invalid(&format!("{path}.icon"), e.to_string()))?;
+
+					builder.icon(icon)
+				},
+			};
+
+			if let Some(id) = &self.id {
+				builder = builder.id(id.clone());
+			}
+
+			if let Some(accelerator) = &self.accelerator {
+				builder = builder.accelerator(accelerator);
+			}
+
+			return builder.build(manager).map(|i| i.kind());
+		}
+
+		if let Some(items) = &self.items {
+			let mut builder = SubmenuBuilder::new(manager, text).enabled(enabled);
+
+			if let Some(id) = &self.id {
+				builder = builder.id(id.clone());
+			}
+
+			for (index, item) in items.iter().enumerate() {
+				builder = builder.item(&item.build(manager, &format!("{path}.items[{index}]"))?);
+			}
+
+			return builder.build().map(|i| i.kind());
+		}
+
+		let mut builder = MenuItemBuilder::new(text).enabled(enabled);
+
+		if let Some(id) = &self.id {
+			builder = builder.id(id.clone());
+		}
+
+		if let Some(accelerator) = &self.accelerator {
+			builder = builder.accelerator(accelerator);
+		}
+
+		builder.build(manager).map(|i| i.kind())
+	}
+
+	fn build_predefined<R:Runtime, M:Manager<R>>(
+		&self,
+		manager:&M,
+		item:&Value,
+		path:&str,
+	) -> crate::Result<PredefinedMenuItem<R>> {
+		// bare names, as described by `Menu::to_value`, are also accepted for
+		// `About` which takes optional metadata
+		let item = match item {
+			Value::String(kind) => {
+				Value::Object([(kind.clone(), Value::Null)].into_iter().collect())
+			},
+			item => item.clone(),
+		};
+
+		let item = Predefined::deserialize(item)
+			.map_err(|e| invalid(&format!("{path}.item"), e.to_string()))?;
+
+		let resources_table = manager.resources_table();
+
+		PredefinedMenuItemPayload { item, text:self.text.clone() }
+			.create_item(manager, &resources_table)
+	}
+}
+
+/// Loads an icon from a file path or a base64 `data:` URL.
+fn load_icon<R:Runtime, M:Manager<R>>(manager:&M, icon:&str) -> crate::Result<Image<'static>> {
+	let image = match icon.strip_prefix("data:") {
+		Some(data) => {
+			let (_, base64) = data
+				.split_once(";base64,")
+				.ok_or_else(|| {
+					std::io::Error::new(
+						std::io::ErrorKind::InvalidInput,
+						"expected a base64 encoded `data:` URL",
+					)
+				})?;
+
+			let bytes = base64::engine::general_purpose::STANDARD
+				.decode(base64)
+				.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+			JsImage::Bytes(bytes)
+		},
+		None => JsImage::Path(icon.into()),
+	};
+
+	let resources_table = manager.resources_table();
+
+	let image = image.into_img(&resources_table)?;
+
+	Ok(image.as_ref().clone().to_owned())
+}
+
+/// Encodes an icon as a base64 PNG `data:` URL.
+#[cfg(feature = "image-png")]
+fn icon_data_url(image:&Image<'_>) -> crate::Result<String> {
+	let mut png = Vec::new();
+
+	::image::RgbaImage::from_raw(image.width(), image.height(), image.rgba().to_vec())
+		.ok_or_else(|| {
+			std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid icon dimensions")
+		})?
+		.write_to(&mut std::io::Cursor::new(&mut png), ::image::ImageFormat::Png)?;
+
+	Ok(format!("data:image/png;base64,{}", base64::engine::general_purpose::STANDARD.encode(png)))
+}
+
+fn invalid(path:&str, reason:impl Into<String>) -> crate::Error {
+	crate::Error::InvalidMenuConfig { path:path.into(), reason:reason.into() }
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use crate::{
+		menu::{Menu, MenuItemKind},
+		test::mock_app,
+	};
+
+	#[test]
+	fn item_kinds_are_inferred_from_their_fields() {
+		let app = mock_app();
+
+		let menu = Menu::from_value(
+			&app,
+			json!({
+				"items": [
+					{ "id": "save", "text": "Save" },
+					{ "id": "autosave", "text": "Auto Save", "checked": true, "enabled": false },
+					{ "id": "add", "text": "Add", "icon": "Add" },
+					{ "id": "file", "text": "File", "items": [{ "item": "Quit" }] },
+					{ "item": "Separator" },
+					{ "item": "About" },
+					{ "item": { "About": { "name": "My App" } }, "text": "About My App" }
+				]
+			}),
+		)
+		.unwrap();
+
+		let items = menu.items().unwrap();
+
+		assert!(matches!(&items[0], MenuItemKind::MenuItem(i) if i.text().unwrap() == "Save"));
+
+		assert!(matches!(
+			&items[1],
+			MenuItemKind::Check(i) if i.is_checked().unwrap() && !i.is_enabled().unwrap()
+		));
+
+		assert!(matches!(&items[2], MenuItemKind::Icon(_)));
+
+		let MenuItemKind::Submenu(file) = &items[3] else { panic!("expected a submenu") };
+
+		assert!(matches!(file.items().unwrap()[..], [MenuItemKind::Predefined(_)]));
+
+		assert!(matches!(&items[4], MenuItemKind::Predefined(_)));
+
+		assert!(matches!(&items[5], MenuItemKind::Predefined(_)));
+
+		assert!(matches!(
+			&items[6],
+			MenuItemKind::Predefined(i) if i.text().unwrap() == "About My App"
+		));
+	}
+
+	#[test]
+	fn invalid_items_name_their_path() {
+		let app = mock_app();
+
+		let error = |value| match Menu::from_value(&app, value) {
+			Err(crate::Error::InvalidMenuConfig { path, reason }) => (path, reason),
+			other => panic!("expected an invalid menu config error, got {:?}", other.err()),
+		};
+
+		assert_eq!(
+			error(json!({ "items": [{ "id": "save" }] })),
+			("items[0]".into(), "missing `text`".into())
+		);
+
+		let (path, _) = error(json!({
+			"items": [{
+				"text": "File",
+				"items": [{ "item": "Quit" }, { "text": "Save", "accelerator": "Ctrl+Nope" }]
+			}]
+		}));
+
+		assert_eq!(path, "items[0].items[1].accelerator");
+
+		let (path, reason) =
+			error(json!({ "items": [{ "item": "Separator" }, { "item": "Eject" }] }));
+
+		assert_eq!(path, "items[1].item");
+
+		assert!(reason.contains("Eject"), "{reason}");
+
+		let (path, _) = error(json!({ "items": [{ "item": { "Copy": null, "Cut": null } }] }));
+
+		assert_eq!(path, "items[0].item");
+
+		let (path, _) = error(json!({ "items": [{ "item": { "About": { "name": 1 } } }] }));
+
+		assert_eq!(path, "items[0].item");
+	}
+}
@@ -0,0 +1,406 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Export of window menus over DBus using the `com.canonical.dbusmenu`
+//! protocol, so they can be rendered by global menu bars (KDE Plasma, GNOME
+//! appmenu extensions) and HUDs.
+
+use std::{
+	collections::HashMap,
+	sync::{Arc, Mutex},
+};
+
+use tauri_runtime::{EventLoopProxy, RuntimeHandle};
+use zbus::{
+	blocking::Connection,
+	interface,
+	object_server::SignalContext,
+	zvariant::{ObjectPath, OwnedValue, StructureBuilder, Value},
+};
+
+use super::{Menu, MenuEvent, MenuId, MenuItemKind};
+use crate::{AppHandle, EventLoopMessage, Runtime};
+
+const REGISTRAR_NAME:&str = "com.canonical.AppMenu.Registrar";
+const REGISTRAR_PATH:&str = "/com/canonical/AppMenu/Registrar";
+
+/// Maps menu item ids to the integer ids used by the dbusmenu protocol.
+///
+/// Ids are never reused so the menu host can keep its caches.
+#[derive(Default)]
+struct IdMap {
+	by_menu_id:HashMap<MenuId, i32>,
+	by_dbus_id:HashMap<i32, MenuId>,
+	next:i32,
+}
+
+impl IdMap {
+	fn get_or_insert(&mut self, id:&MenuId) -> i32 {
+		if let Some(dbus_id) = self.by_menu_id.get(id) {
+			return *dbus_id;
+		}
+
+		// 0 is reserved for the root item
+		self.next += 1;
+		self.by_menu_id.insert(id.clone(), self.next);
+		self.by_dbus_id.insert(self.next, id.clone());
+		self.next
+	}
+}
+
+struct DbusMenu<R:Runtime> {
+	app_handle:AppHandle<R>,
+	menu:Menu<R>,
+	ids:Arc<Mutex<IdMap>>,
+	revision:Arc<Mutex<u32>>,
+}
+
+impl<R:Runtime> DbusMenu<R> {
+	fn item_properties(&self, item:&MenuItemKind<R>) -> HashMap<String, OwnedValue> {
+		let mut properties = HashMap::new();
+
+		let mut insert = |key:&str, value:Value<'_>| {
+			if let Ok(value) = OwnedValue::try_from(value) {
+				properties.insert(key.to_string(), value);
+			}
+		};
+
+		let (text, enabled) = match item {
+			MenuItemKind::MenuItem(i) => (i.text().ok(), i.is_enabled().ok()),
+			MenuItemKind::Submenu(i) => {
+				insert("children-display", Value::from("submenu"));
+				(i.text().ok(), i.is_enabled().ok())
+			},
+			MenuItemKind::Predefined(i) => (i.text().ok(), None),
+			MenuItemKind::Check(i) => {
+				insert("toggle-type", Value::from("checkmark"));
+				insert(
+					"toggle-state",
+					Value::from(if i.is_checked().unwrap_or_default() { 1i32 } else { 0i32 }),
+				);
+				(i.text().ok(), i.is_enabled().ok())
+			},
+			MenuItemKind::Icon(i) => (i.text().ok(), i.is_enabled().ok()),
+		};
+
+		match text.filter(|t| !t.is_empty()) {
+			Some(text) => insert("label", Value::from(to_dbus_label(&text))),
+			None if matches!(item, MenuItemKind::Predefined(_)) => {
+				insert("type", Value::from("separator"))
+			},
+			None => {},
+		}
+
+		if let Some(enabled) = enabled {
+			insert("enabled", Value::from(enabled));
+		}
+
+		properties
+	}
+
+	fn layout(&self, id:i32, items:Vec<MenuItemKind<R>>, depth:i32) -> Value<'static> {
+		let mut properties = HashMap::new();
+		if let Ok(value) = OwnedValue::try_from(Value::from("submenu")) {
+			properties.insert("children-display".to_string(), value);
+		}
+
+		self.layout_node(id, properties, items, depth)
+	}
+
+	fn layout_node(
+		&self,
+		id:i32,
+		properties:HashMap<String, OwnedValue>,
+		items:Vec<MenuItemKind<R>>,
+		depth:i32,
+	) -> Value<'static> {
+		let children = if depth == 0 {
+			Vec::new()
+		} else {
+			items
+				.into_iter()
+				.map(|item| {
+					let child_id = self.ids.lock().unwrap().get_or_insert(item.id());
+
+					let child_properties = self.item_properties(&item);
+
+					let child_items = match &item {
+						MenuItemKind::Submenu(submenu) => submenu.items().unwrap_or_default(),
+						_ => Vec::new(),
+					};
+
+					self.layout_node(child_id, child_properties, child_items, depth - 1)
+				})
+				.collect::<Vec<_>>()
+		};
+
+		StructureBuilder::new()
+			.add_field(id)
+			.add_field(properties)
+			.add_field(children)
+			.build()
+			.into()
+	}
+
+	fn find_item(&self, id:i32) -> Option<MenuItemKind<R>> {
+		let menu_id = self.ids.lock().unwrap().by_dbus_id.get(&id).cloned()?;
+
+		self.menu.get(menu_id.0.as_str()).or_else(|| {
+			self.menu.items().ok()?.into_iter().find_map(|item| {
+				item.as_submenu().and_then(|submenu| find_in_submenu(submenu, &menu_id))
+			})
+		})
+	}
+
+	fn activate(&self, id:i32) {
+		let Some(item) = self.find_item(id) else {
+			return;
+		};
+
+		// the native menu toggles check items on activation, mirror that behavior
+		if let MenuItemKind::Check(check) = &item {
+			if let Ok(checked) = check.is_checked() {
+				let _ = check.set_checked(!checked);
+			}
+		}
+
+		let _ = self
+			.app_handle
+			.runtime_handle
+			.create_proxy()
+			.send_event(EventLoopMessage::MenuEvent(MenuEvent { id:item.id().clone() }));
+	}
+}
+
+fn find_in_submenu<R:Runtime>(submenu:&super::Submenu<R>, id:&MenuId) -> Option<MenuItemKind<R>> {
+	submenu.get(id.0.as_str()).or_else(|| {
+		submenu.items().ok()?.into_iter().find_map(|item| {
+			item.as_submenu().and_then(|submenu| find_in_submenu(submenu, id))
+		})
+	})
+}
+
+/// Converts a `&` mnemonic label to the `_` mnemonic used by dbusmenu.
+fn to_dbus_label(text:&str) -> String {
+	let mut label = String::with_capacity(text.len());
+
+	let mut chars = text.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'_' => label.push_str("__"),
+			'&' if chars.peek() == Some(&'&') => {
+				chars.next();
+				label.push('&');
+			},
+			'&' => label.push('_'),
+			c => label.push(c),
+		}
+	}
+
+	label
+}
+
+#[interface(name = "com.canonical.dbusmenu")]
+impl<R:Runtime> DbusMenu<R> {
+	fn get_layout(
+		&self,
+		parent_id:i32,
+		recursion_depth:i32,
+		_property_names:Vec<String>,
+	) -> (u32, OwnedValue) {
+		let revision = *self.revision.lock().unwrap();
+
+		let depth = if recursion_depth < 0 { i32::MAX } else { recursion_depth };
+
+		let layout = if parent_id == 0 {
+			self.layout(0, self.menu.items().unwrap_or_default(), depth)
+		} else {
+			match self.find_item(parent_id) {
+				Some(MenuItemKind::Submenu(submenu)) => {
+					self.layout(parent_id, submenu.items().unwrap_or_default(), depth)
+				},
+				Some(item) => self.layout_node(parent_id, self.item_properties(&item), Vec::new(), 0),
+				None => self.layout(parent_id, Vec::new(), 0),
+			}
+		};
+
+		(revision, OwnedValue::try_from(layout).expect("layout is a valid dbus value"))
+	}
+
+	fn get_group_properties(
+		&self,
+		ids:Vec<i32>,
+		_property_names:Vec<String>,
+	) -> Vec<(i32, HashMap<String, OwnedValue>)> {
+		ids.into_iter()
+			.filter_map(|id| self.find_item(id).map(|item| (id, self.item_properties(&item))))
+			.collect()
+	}
+
+	fn get_property(&self, id:i32, name:String) -> zbus::fdo::Result<OwnedValue> {
+		self.find_item(id)
+			.and_then(|item| self.item_properties(&item).remove(&name))
+			.ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("unknown property {name}")))
+	}
+
+	fn event(&self, id:i32, event_id:String, _data:OwnedValue, _timestamp:u32) {
+		if event_id == "clicked" {
+			self.activate(id);
+		}
+	}
+
+	fn event_group(&self, events:Vec<(i32, String, OwnedValue, u32)>) -> Vec<i32> {
+		let mut not_found = Vec::new();
+
+		for (id, event_id, ..) in events {
+			if self.find_item(id).is_none() {
+				not_found.push(id);
+			} else if event_id == "clicked" {
+				self.activate(id);
+			}
+		}
+
+		not_found
+	}
+
+	fn about_to_show(&self, _id:i32) -> bool { false }
+
+	fn about_to_show_group(&self, _ids:Vec<i32>) -> (Vec<i32>, Vec<i32>) { (Vec::new(), Vec::new()) }
+
+	#[zbus(property)]
+	fn version(&self) -> u32 { 3 }
+
+	#[zbus(property)]
+	fn text_direction(&self) -> &str { "ltr" }
+
+	#[zbus(property)]
+	fn status(&self) -> &str { "normal" }
+
+	#[zbus(property)]
+	fn icon_theme_path(&self) -> Vec<String> { Vec::new() }
+
+	#[zbus(signal)]
+	async fn layout_updated(
+		ctxt:&SignalContext<'_>,
+		revision:u32,
+		parent:i32,
+	) -> zbus::Result<()>;
+}
+
+/// Exports window menus on the session bus.
+pub(crate) struct DbusMenuExporter {
+	connection:Connection,
+	/// Object path and revision for each exported window.
+	exported:Mutex<HashMap<String, (String, Arc<Mutex<u32>>)>>,
+	ids:Arc<Mutex<IdMap>>,
+}
+
+impl DbusMenuExporter {
+	/// Connects to the session bus. Returns `None` if the bus is not available.
+	pub(crate) fn new() -> Option<Self> {
+		Connection::session().ok().map(|connection| {
+			Self { connection, exported:Default::default(), ids:Default::default() }
+		})
+	}
+
+	/// Exports the menu for the given X11 window and registers it with the
+	/// AppMenu registrar.
+	///
+	/// Returns `false` if no registrar is running, in which case the menu
+	/// should stay in the window.
+	pub(crate) fn export<R:Runtime>(
+		&self,
+		app_handle:&AppHandle<R>,
+		window_label:&str,
+		xid:u32,
+		menu:&Menu<R>,
+	) -> bool {
+		let path = format!("/com/canonical/menu/{xid:X}");
+
+		let revision = Arc::new(Mutex::new(1));
+
+		let object_server = self.connection.object_server();
+
+		let _ = object_server.remove::<DbusMenu<R>, _>(path.as_str());
+
+		let iface = DbusMenu {
+			app_handle:app_handle.clone(),
+			menu:menu.clone(),
+			ids:self.ids.clone(),
+			revision:revision.clone(),
+		};
+
+		if object_server.at(path.as_str(), iface).is_err() {
+			return false;
+		}
+
+		let Ok(object_path) = ObjectPath::try_from(path.as_str()) else {
+			return false;
+		};
+
+		let registered = self
+			.connection
+			.call_method(
+				Some(REGISTRAR_NAME),
+				REGISTRAR_PATH,
+				Some(REGISTRAR_NAME),
+				"RegisterWindow",
+				&(xid, object_path),
+			)
+			.is_ok();
+
+		if registered {
+			self.exported.lock().unwrap().insert(window_label.to_string(), (path, revision));
+		} else {
+			let _ = object_server.remove::<DbusMenu<R>, _>(path.as_str());
+		}
+
+		registered
+	}
+
+	/// Stops exporting the menu of the given window.
+	pub(crate) fn unexport<R:Runtime>(&self, window_label:&str) {
+		if let Some((path, _)) = self.exported.lock().unwrap().remove(window_label) {
+			let _ = self.connection.object_server().remove::<DbusMenu<R>, _>(path.as_str());
+		}
+	}
+
+	/// Whether the menu of the given window is exported.
+	pub(crate) fn is_exported(&self, window_label:&str) -> bool {
+		self.exported.lock().unwrap().contains_key(window_label)
+	}
+
+	/// Notifies the menu hosts that the exported menus changed.
+	pub(crate) fn notify_changed<R:Runtime>(&self) {
+		for (path, revision) in self.exported.lock().unwrap().values() {
+			let revision = {
+				let mut revision = revision.lock().unwrap();
+				*revision += 1;
+				*revision
+			};
+
+			if let Ok(iface) = self.connection.object_server().interface::<_, DbusMenu<R>>(path.as_str())
+			{
+				let _ = zbus::block_on(DbusMenu::<R>::layout_updated(
+					iface.signal_context(),
+					revision,
+					0,
+				));
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::to_dbus_label;
+
+	#[test]
+	fn converts_mnemonics() {
+		assert_eq!(to_dbus_label("&File"), "_File");
+		assert_eq!(to_dbus_label("Save && Quit"), "Save & Quit");
+		assert_eq!(to_dbus_label("snake_case"), "snake__case");
+	}
+}
@@ -0,0 +1,266 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use super::{CheckMenuItem, MenuId, MenuItemKey, run_item_main_thread};
+use crate::{AppHandle, Manager, Runtime};
+
+/// A group of [`CheckMenuItem`]s where only one item is checked at a time,
+/// like radio buttons.
+///
+/// Clicking an item of the group checks it and unchecks the others before the
+/// menu event is emitted, so the menu event handlers only need to persist the
+/// choice.
+///
+/// # Example
+///
+/// ```no_run
+/// use tauri::menu::{CheckMenuItemGroup, MenuId, SubmenuBuilder};
+///
+/// tauri::Builder::default().setup(|app| {
+/// 	let theme = SubmenuBuilder::new(app, "Theme")
+/// 		.radio_group(
+/// 			"theme-",
+/// 			&[("light", "Light"), ("dark", "Dark"), ("system", "System")],
+/// 			2,
+/// 		)
+/// 		.build()?;
+///
+/// 	// later, e.g. when the system theme changes
+/// 	let group = CheckMenuItemGroup::get(app, &MenuId::new("theme-dark")).unwrap();
+/// 	group.select(&MenuId::new("theme-dark"))?;
+/// 	Ok(())
+/// });
+/// ```
+#[tauri_macros::default_runtime(crate::Wry, wry)]
+pub struct CheckMenuItemGroup<R:Runtime> {
+	items:Arc<Vec<CheckMenuItem<R>>>,
+	app_handle:AppHandle<R>,
+}
+
+impl<R:Runtime> Clone for CheckMenuItemGroup<R> {
+	fn clone(&self) -> Self {
+		Self { items:self.items.clone(), app_handle:self.app_handle.clone() }
+	}
+}
+
+impl<R:Runtime> CheckMenuItemGroup<R> {
+	/// Creates a group of check menu items, one per `(id, text)` pair, with
+	/// ids prefixed by `id_prefix`.
+	///
+	/// The item at `selected` is checked, none is if it's out of bounds.
+	pub fn new<M:Manager<R>>(
+		manager:&M,
+		id_prefix:&str,
+		items:&[(&str, &str)],
+		selected:usize,
+	) -> crate::Result<Self> {
+		let items = items
+			.iter()
+			.enumerate()
+			.map(|(index, (id, text))| {
+				CheckMenuItem::with_id(
+					manager,
+					format!("{id_prefix}{id}"),
+					text,
+					true,
+					index == selected,
+					None::<&str>,
+				)
+			})
+			.collect::<crate::Result<Vec<_>>>()?;
+
+		Ok(Self::from_items(manager, items))
+	}
+
+	/// Creates a group from existing check menu items.
+	///
+	/// Clicking an item only updates its last group: adding an item to another
+	/// group makes that group the one [`Self::get`] returns for it, while the
+	/// previous group still lists it in its [`Self::items`].
+	///
+	/// The group is kept as long as one of its items is alive.
+	pub fn from_items<M:Manager<R>>(manager:&M, items:Vec<CheckMenuItem<R>>) -> Self {
+		// the group has its own wrappers, which don't keep the items alive
+		let items = items.iter().map(|item| CheckMenuItem::from_inner((*item.0).clone())).collect();
+
+		let group = Self { items:Arc::new(items), app_handle:manager.app_handle().clone() };
+
+		group.app_handle.manager.menu.set_check_group(&group);
+
+		group
+	}
+
+	/// Returns the group containing the item matching the given identifier.
+	pub fn get<M:Manager<R>>(manager:&M, id:&MenuId) -> Option<Self> {
+		manager.app_handle().manager.menu.check_groups(id).into_iter().next()
+	}
+
+	/// The application handle associated with this type.
+	pub fn app_handle(&self) -> &AppHandle<R> { &self.app_handle }
+
+	/// The items of this group.
+	pub fn items(&self) -> &[CheckMenuItem<R>] { &self.items }
+
+	/// Checks the item matching the given identifier and unchecks the others,
+	/// in a single main thread task.
+	///
+	/// Returns [`crate::Error::MenuItemNotFound`] if the item isn't part of
+	/// this group.
+	pub fn select(&self, id:&MenuId) -> crate::Result<()> {
+		let index = self
+			.items
+			.iter()
+			.position(|item| item.id() == id)
+			.ok_or_else(|| crate::Error::MenuItemNotFound(id.0.clone()))?;
+
+		run_item_main_thread!(self, |self_:Self| {
+			for (i, item) in self_.items.iter().enumerate() {
+				(*item.0).as_ref().set_checked(i == index);
+			}
+		})?;
+
+		self.app_handle.manager.menu.notify_changed();
+
+		Ok(())
+	}
+
+	/// Identifies the group, shared by its clones.
+	pub(crate) fn key(&self) -> usize { Arc::as_ptr(&self.items) as usize }
+
+	/// The number of clones of this group, including this one.
+	pub(crate) fn clones(&self) -> usize { Arc::strong_count(&self.items) }
+
+	/// The number of wrappers of the item with the given key that only this
+	/// group holds.
+	pub(crate) fn own_wrappers(&self, key:MenuItemKey) -> usize {
+		self.items
+			.iter()
+			.filter(|item| item.key() == key && Arc::strong_count(&item.0) == 1)
+			.count()
+	}
+
+	/// Returns the id of the checked item, if any.
+	pub fn selected(&self) -> crate::Result<Option<MenuId>> {
+		run_item_main_thread!(self, |self_:Self| {
+			self_
+				.items
+				.iter()
+				.find(|item| (*item.0).as_ref().is_checked())
+				.map(|item| item.id().clone())
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		menu::SubmenuBuilder,
+		test::{MockRuntime, mock_app},
+	};
+
+	#[test]
+	fn selecting_an_item_unchecks_the_others() {
+		let app = mock_app();
+
+		let group =
+			CheckMenuItemGroup::new(&app, "theme-", &[("light", "Light"), ("dark", "Dark")], 1).unwrap();
+
+		assert_eq!(group.selected().unwrap(), Some(MenuId::new("theme-dark")));
+
+		group.select(&MenuId::new("theme-light")).unwrap();
+
+		assert!(group.items()[0].is_checked().unwrap());
+		assert!(!group.items()[1].is_checked().unwrap());
+
+		assert!(matches!(
+			group.select(&MenuId::new("light")),
+			Err(crate::Error::MenuItemNotFound(id)) if id == "light"
+		));
+
+		assert_eq!(group.selected().unwrap(), Some(MenuId::new("theme-light")));
+	}
+
+	#[test]
+	fn out_of_bounds_selection_checks_nothing() {
+		let app = mock_app();
+
+		let group = CheckMenuItemGroup::new(&app, "size-", &[("s", "Small")], 5).unwrap();
+
+		assert_eq!(group.selected().unwrap(), None);
+	}
+
+	#[test]
+	fn items_belong_to_their_last_group() {
+		let app = mock_app();
+
+		let submenu = SubmenuBuilder::new(&app, "Theme")
+			.radio_group("theme-", &[("light", "Light"), ("dark", "Dark")], 0)
+			.build()
+			.unwrap();
+
+		assert_eq!(submenu.items().unwrap().len(), 2);
+
+		let group = CheckMenuItemGroup::get(&app, &MenuId::new("theme-dark")).unwrap();
+
+		assert_eq!(group.items().len(), 2);
+
+		let dark = group.items()[1].clone();
+
+		CheckMenuItemGroup::from_items(&app, vec![dark]);
+
+		let moved = CheckMenuItemGroup::get(&app, &MenuId::new("theme-dark")).unwrap();
+
+		assert_eq!(moved.items().len(), 1);
+		assert!(CheckMenuItemGroup::get(&app, &MenuId::new("theme-other")).is_none());
+	}
+
+	#[test]
+	fn clicking_an_item_only_selects_it_in_its_group() {
+		let app = mock_app();
+
+		let theme = CheckMenuItemGroup::new(
+			&app,
+			"theme-",
+			&[("light", "Light"), ("dark", "Dark"), ("system", "System")],
+			0,
+		)
+		.unwrap();
+
+		let size =
+			CheckMenuItemGroup::new(&app, "size-", &[("s", "Small"), ("l", "Large")], 0).unwrap();
+
+		let groups = app.manager.menu.check_groups(&MenuId::new("theme-dark"));
+
+		assert_eq!(groups.len(), 1);
+
+		groups[0].select(&MenuId::new("theme-dark")).unwrap();
+
+		let checked = |group:&CheckMenuItemGroup<MockRuntime>| {
+			group.items().iter().map(|i| i.is_checked().unwrap()).collect::<Vec<_>>()
+		};
+
+		assert_eq!(checked(&theme), [false, true, false]);
+		assert_eq!(checked(&size), [true, false]);
+	}
+
+	#[test]
+	fn groups_are_dropped_with_their_items() {
+		let app = mock_app();
+
+		let submenu = SubmenuBuilder::new(&app, "Theme")
+			.radio_group("theme-", &[("light", "Light"), ("dark", "Dark")], 0)
+			.build()
+			.unwrap();
+
+		assert!(CheckMenuItemGroup::get(&app, &MenuId::new("theme-dark")).is_some());
+
+		drop(submenu);
+
+		assert!(CheckMenuItemGroup::get(&app, &MenuId::new("theme-dark")).is_none());
+		assert!(app.manager.menu.items.lock().unwrap().is_empty());
+	}
+}
@@ -2,8 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::sync::Arc;
-
 use super::{IconMenuItem, NativeIcon, run_item_main_thread};
 use crate::{
 	AppHandle,
@@ -37,7 +35,9 @@ impl<R:Runtime> IconMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
+
+		let icon_metadata = super::MenuItemIcon::from_image(icon.as_ref());
 
 		let icon = match icon {
 			Some(i) => Some(i.try_into()?),
@@ -49,7 +49,15 @@ impl<R:Runtime> IconMenuItem<R> {
 			IconMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| {
+			m.accelerator = accelerator_text;
+
+			m.icon = icon_metadata;
+		});
+
+		Ok(item)
 	}
 
 	/// Create a new menu item with the specified id.
@@ -78,7 +86,9 @@ impl<R:Runtime> IconMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
+
+		let icon_metadata = super::MenuItemIcon::from_image(icon.as_ref());
 
 		let icon = match icon {
 			Some(i) => Some(i.try_into()?),
@@ -90,7 +100,15 @@ impl<R:Runtime> IconMenuItem<R> {
 			IconMenuItemInner { id, inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| {
+			m.accelerator = accelerator_text;
+
+			m.icon = icon_metadata;
+		});
+
+		Ok(item)
 	}
 
 	/// Create a new icon menu item but with a native icon.
@@ -117,16 +135,65 @@ impl<R:Runtime> IconMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
+		let icon_metadata = native_icon.map(super::MenuItemIcon::Native);
+
 		let icon = native_icon.map(Into::into);
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
 
 		let item = run_main_thread!(handle, || {
 			let item = muda::IconMenuItem::with_native_icon(text, enabled, icon, accelerator);
 			IconMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| {
+			m.accelerator = accelerator_text;
+
+			m.icon = icon_metadata;
+		});
+
+		Ok(item)
+	}
+
+	/// Create a new icon menu item with the specified id and an icon loaded
+	/// from `path`.
+	///
+	/// The icon is decoded once and cached for the app until the file
+	/// changes, so rebuilding menus doesn't decode it again. Multi-resolution
+	/// sources are supported: `.ico` and `.icns` files use the entry that best
+	/// matches the menu icon size on the current display, and a PNG file uses
+	/// its `@2x` sibling (`icon@2x.png` for `icon.png`) on high density
+	/// displays when it exists.
+	///
+	/// See [`IconMenuItem::new`] for more info.
+	#[cfg(any(feature = "image-ico", feature = "image-png"))]
+	#[cfg_attr(docsrs, doc(cfg(any(feature = "image-ico", feature = "image-png"))))]
+	pub fn with_id_and_icon_path<M, I, T, P, A>(
+		manager:&M,
+		id:I,
+		text:T,
+		enabled:bool,
+		path:P,
+		accelerator:Option<A>,
+	) -> crate::Result<Self>
+	where
+		M: Manager<R>,
+		I: Into<MenuId>,
+		T: AsRef<str>,
+		P: AsRef<std::path::Path>,
+		A: AsRef<str>, {
+		let icon = super::icon_cache::load(manager, path.as_ref())?;
+
+		Self::with_id(
+			manager,
+			id,
+			text,
+			enabled,
+			Some(Image::new(icon.rgba(), icon.width(), icon.height())),
+			accelerator,
+		)
 	}
 
 	/// Create a new icon menu item with the specified id but with a native
@@ -158,9 +225,11 @@ impl<R:Runtime> IconMenuItem<R> {
 
 		let text = text.as_ref().to_owned();
 
+		let icon_metadata = native_icon.map(super::MenuItemIcon::Native);
+
 		let icon = native_icon.map(Into::into);
 
-		let accelerator = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accelerator, accelerator_text) = super::parse_item_accelerator(accelerator);
 
 		let item = run_main_thread!(handle, || {
 			let item = muda::IconMenuItem::with_id_and_native_icon(
@@ -173,7 +242,15 @@ impl<R:Runtime> IconMenuItem<R> {
 			IconMenuItemInner { id, inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| {
+			m.accelerator = accelerator_text;
+
+			m.icon = icon_metadata;
+		});
+
+		Ok(item)
 	}
 
 	/// The application handle associated with this type.
@@ -184,7 +261,9 @@ impl<R:Runtime> IconMenuItem<R> {
 
 	/// Get the text for this menu item.
 	pub fn text(&self) -> crate::Result<String> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())
+		let text = run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())?;
+
+		Ok(self.0.app_handle.manager.menu.display_text(self.key(), text))
 	}
 
 	/// Set the text for this menu item. `text` could optionally contain
@@ -192,9 +271,13 @@ impl<R:Runtime> IconMenuItem<R> {
 	/// for this menu item. To display a `&` without assigning a mnemenonic, use
 	/// `&&`.
 	pub fn set_text<S:AsRef<str>>(&self, text:S) -> crate::Result<()> {
-		let text = text.as_ref().to_string();
+		let text = self.0.app_handle.manager.menu.native_text(self.key(), text.as_ref());
+
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))?;
 
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Get whether this menu item is enabled or not.
@@ -204,25 +287,54 @@ impl<R:Runtime> IconMenuItem<R> {
 
 	/// Enable or disable this menu item.
 	pub fn set_enabled(&self, enabled:bool) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
-	/// Set this menu item accelerator.
+	/// Set this menu item accelerator, or remove it with `None`.
+	///
+	/// Updates both the shortcut hint displayed next to the item and the key
+	/// handling. Returns an error if `accelerator` isn't a valid accelerator,
+	/// leaving the current one in place.
 	pub fn set_accelerator<S:AsRef<str>>(&self, accelerator:Option<S>) -> crate::Result<()> {
-		let accel = accelerator.and_then(|s| s.as_ref().parse().ok());
+		let (accel, accelerator) = super::try_parse_item_accelerator(accelerator)?;
+
+		run_item_main_thread!(self, |self_:Self| { (*self_.0).as_ref().set_accelerator(accel) })??;
+
+		let menu = &self.0.app_handle.manager.menu;
 
-		run_item_main_thread!(self, |self_:Self| { (*self_.0).as_ref().set_accelerator(accel) })?
-			.map_err(Into::into)
+		menu.update_item_metadata(self.key(), |m| m.accelerator = accelerator);
+
+		menu.notify_changed();
+
+		Ok(())
+	}
+
+	/// Returns the accelerator of this menu item as it was given, e.g.
+	/// `CmdOrCtrl+Shift+E`, if it has one.
+	pub fn accelerator(&self) -> Option<String> {
+		self.0.app_handle.manager.menu.item_metadata(self.key()).accelerator
 	}
 
 	/// Change this menu item icon or remove it.
 	pub fn set_icon(&self, icon:Option<Image<'_>>) -> crate::Result<()> {
+		let icon_metadata = super::MenuItemIcon::from_image(icon.as_ref());
+
 		let icon = match icon {
 			Some(i) => Some(i.try_into()?),
 			None => None,
 		};
 
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_icon(icon))
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_icon(icon))?;
+
+		let menu = &self.0.app_handle.manager.menu;
+
+		menu.update_item_metadata(self.key(), |m| m.icon = icon_metadata);
+
+		Ok(())
 	}
 
 	/// Change this menu item icon to a native image or remove it.
@@ -231,6 +343,10 @@ impl<R:Runtime> IconMenuItem<R> {
 	///
 	/// - **Windows / Linux**: Unsupported.
 	pub fn set_native_icon(&self, _icon:Option<NativeIcon>) -> crate::Result<()> {
+		let menu = &self.0.app_handle.manager.menu;
+
+		menu.update_item_metadata(self.key(), |m| m.icon = _icon.map(super::MenuItemIcon::Native));
+
 		#[cfg(target_os = "macos")]
 		return run_item_main_thread!(self, |self_:Self| {
 			(*self_.0).as_ref().set_native_icon(_icon.map(Into::into))
@@ -239,3 +355,24 @@ impl<R:Runtime> IconMenuItem<R> {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test::mock_app;
+
+	#[test]
+	fn invalid_accelerators_keep_the_previous_one() {
+		let app = mock_app();
+
+		let icon = Image::new_owned(vec![0; 4], 1, 1);
+
+		let item =
+			IconMenuItem::with_id(&app, "export", "Export", true, Some(icon), Some("CmdOrCtrl+E"))
+				.unwrap();
+
+		assert!(item.set_accelerator(Some("Ctrl+Nope")).is_err());
+
+		assert_eq!(item.accelerator().as_deref(), Some("CmdOrCtrl+E"));
+	}
+}
@@ -0,0 +1,260 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#![cfg(any(feature = "image-ico", feature = "image-png"))]
+
+//! Decoded menu icons loaded from the filesystem, shared by all the menus of
+//! the app.
+
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+	time::SystemTime,
+};
+
+use crate::{Manager, Runtime, image::Image};
+
+/// The logical size of a menu icon.
+const MENU_ICON_SIZE:f64 = 16.0;
+
+const PNG_SIGNATURE:&[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Decoded icons keyed by their canonical path and the pixel size they were
+/// picked for, invalidated when the file modification time changes.
+#[derive(Default)]
+pub(crate) struct MenuIconCache(
+	Mutex<HashMap<(PathBuf, u32), (SystemTime, Arc<Image<'static>>)>>,
+);
+
+/// Loads the icon at `path` for a menu item, decoding it only if it isn't
+/// cached yet or the file changed since.
+///
+/// Multi-resolution sources are resolved to the representation closest to
+/// the menu icon size on the current display:
+/// - `.ico` and `.icns` files use their best matching entry.
+/// - PNG files use an `@2x` sibling (`icon@2x.png` for `icon.png`) on high
+///   density displays when it exists.
+pub(crate) fn load<R:Runtime, M:Manager<R>>(
+	manager:&M,
+	path:&Path,
+) -> crate::Result<Arc<Image<'static>>> {
+	let size = icon_pixel_size(manager);
+
+	let path = resolve_path(&dunce::canonicalize(path)?, size);
+
+	let modified = std::fs::metadata(&path)?.modified()?;
+
+	manager.manage(MenuIconCache::default());
+
+	let cache = manager.state::<MenuIconCache>();
+
+	let key = (path, size);
+
+	if let Some((cached_modified, image)) = cache.0.lock().unwrap().get(&key) {
+		if *cached_modified == modified {
+			return Ok(image.clone());
+		}
+	}
+
+	let image = Arc::new(decode(&std::fs::read(&key.0)?, size)?);
+
+	cache.0.lock().unwrap().insert(key, (modified, image.clone()));
+
+	Ok(image)
+}
+
+/// The pixel size of a menu icon on the primary display.
+#[cfg_attr(target_os = "macos", allow(unused_variables))]
+fn icon_pixel_size<R:Runtime, M:Manager<R>>(manager:&M) -> u32 {
+	// menus are rendered by AppKit, which picks the right representation when
+	// moving between displays, so always use the high density one
+	#[cfg(target_os = "macos")]
+	let scale_factor = 2.0;
+
+	#[cfg(not(target_os = "macos"))]
+	let scale_factor = manager
+		.app_handle()
+		.primary_monitor()
+		.ok()
+		.flatten()
+		.map(|monitor| monitor.scale_factor())
+		.unwrap_or(1.0);
+
+	(MENU_ICON_SIZE * scale_factor).ceil() as u32
+}
+
+/// Uses the `@2x` sibling of a PNG file on high density displays.
+fn resolve_path(path:&Path, size:u32) -> PathBuf {
+	if size as f64 > MENU_ICON_SIZE && path.extension().is_some_and(|ext| ext == "png") {
+		if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+			let hidpi = path.with_file_name(format!("{stem}@2x.png"));
+
+			if hidpi.is_file() {
+				return hidpi;
+			}
+		}
+	}
+
+	path.to_path_buf()
+}
+
+fn decode(bytes:&[u8], size:u32) -> crate::Result<Image<'static>> {
+	let entries = if bytes.starts_with(b"icns") {
+		icns_entries(bytes)
+	} else if bytes.starts_with(&[0, 0, 1, 0]) {
+		ico_entries(bytes)
+	} else {
+		Vec::new()
+	};
+
+	// the smallest entry that is at least as large as the icon, or the largest
+	let entry = entries
+		.iter()
+		.filter(|(entry_size, _)| *entry_size >= size)
+		.min_by_key(|(entry_size, _)| *entry_size)
+		.or_else(|| entries.iter().max_by_key(|(entry_size, _)| *entry_size));
+
+	match entry {
+		Some((_, entry)) => Image::from_bytes(entry),
+		None => Image::from_bytes(bytes),
+	}
+}
+
+/// The PNG entries of an `.icns` file with their pixel size.
+fn icns_entries(bytes:&[u8]) -> Vec<(u32, Vec<u8>)> {
+	let mut entries = Vec::new();
+
+	let mut offset = 8;
+
+	while let Some(header) = bytes.get(offset..offset + 8) {
+		let length = u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+		let Some(data) = bytes.get(offset + 8..offset + length.max(8)) else {
+			break;
+		};
+
+		if let Some(size) = png_width(data) {
+			entries.push((size, data.to_vec()));
+		}
+
+		offset += length.max(8);
+	}
+
+	entries
+}
+
+/// The entries of an `.ico` file with their pixel size, each as a standalone
+/// image file.
+fn ico_entries(bytes:&[u8]) -> Vec<(u32, Vec<u8>)> {
+	let count = bytes.get(4..6).map(|c| u16::from_le_bytes([c[0], c[1]])).unwrap_or(0) as usize;
+
+	let mut entries = Vec::new();
+
+	for index in 0..count {
+		let Some(entry) = bytes.get(6 + index * 16..6 + (index + 1) * 16) else {
+			break;
+		};
+
+		// a size of 0 means 256 pixels
+		let size = if entry[0] == 0 { 256 } else { entry[0] as u32 };
+
+		let length = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as usize;
+
+		let offset = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]) as usize;
+
+		let Some(data) = bytes.get(offset..offset + length) else {
+			continue;
+		};
+
+		if data.starts_with(PNG_SIGNATURE) {
+			entries.push((size, data.to_vec()));
+		} else {
+			// wrap the bitmap in a single entry icon so it can be decoded on its own
+			let mut ico = Vec::with_capacity(22 + length);
+
+			ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+
+			ico.extend_from_slice(&entry[..12]);
+
+			ico.extend_from_slice(&22u32.to_le_bytes());
+
+			ico.extend_from_slice(data);
+
+			entries.push((size, ico));
+		}
+	}
+
+	entries
+}
+
+/// The width of a PNG image, read from its header.
+fn png_width(data:&[u8]) -> Option<u32> {
+	if !data.starts_with(PNG_SIGNATURE) {
+		return None;
+	}
+
+	data.get(16..20).map(|w| u32::from_be_bytes([w[0], w[1], w[2], w[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn png_header(size:u32) -> Vec<u8> {
+		let mut png = PNG_SIGNATURE.to_vec();
+
+		png.extend_from_slice(&[0, 0, 0, 13]);
+
+		png.extend_from_slice(b"IHDR");
+
+		png.extend_from_slice(&size.to_be_bytes());
+
+		png.extend_from_slice(&size.to_be_bytes());
+
+		png
+	}
+
+	#[test]
+	fn reads_icns_entries() {
+		let mut icns = b"icns\0\0\0\0".to_vec();
+
+		for (kind, size) in [(b"ic11", 32u32), (b"ic12", 64)] {
+			let png = png_header(size);
+
+			icns.extend_from_slice(kind);
+
+			icns.extend_from_slice(&(png.len() as u32 + 8).to_be_bytes());
+
+			icns.extend_from_slice(&png);
+		}
+
+		let sizes = icns_entries(&icns).into_iter().map(|(size, _)| size).collect::<Vec<_>>();
+
+		assert_eq!(sizes, vec![32, 64]);
+	}
+
+	#[test]
+	fn reads_ico_entries() {
+		let png = png_header(48);
+
+		let mut ico = vec![0, 0, 1, 0, 1, 0];
+
+		ico.extend_from_slice(&[48, 48, 0, 0, 1, 0, 32, 0]);
+
+		ico.extend_from_slice(&(png.len() as u32).to_le_bytes());
+
+		ico.extend_from_slice(&22u32.to_le_bytes());
+
+		ico.extend_from_slice(&png);
+
+		let entries = ico_entries(&ico);
+
+		assert_eq!(entries.len(), 1);
+
+		assert_eq!(entries[0].0, 48);
+
+		assert_eq!(entries[0].1, png);
+	}
+}
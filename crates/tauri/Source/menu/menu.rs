@@ -10,8 +10,11 @@ use super::{
 	AboutMetadata,
 	IsMenuItem,
 	Menu,
+	MenuConfig,
 	MenuInner,
+	MenuItemConfig,
 	MenuItemKind,
+	MenuRoot,
 	PredefinedMenuItem,
 	Submenu,
 	run_item_main_thread,
@@ -31,7 +34,7 @@ impl<R:Runtime> super::ContextMenu for Menu<R> {
 	}
 
 	fn popup<T:Runtime>(&self, window:Window<T>) -> crate::Result<()> {
-		self.popup_inner(window, None::<Position>)
+		self.popup_inner(window, None::<Position>, None)
 	}
 
 	fn popup_at<T:Runtime, P:Into<Position>>(
@@ -39,7 +42,16 @@ impl<R:Runtime> super::ContextMenu for Menu<R> {
 		window:Window<T>,
 		position:P,
 	) -> crate::Result<()> {
-		self.popup_inner(window, Some(position))
+		self.popup_inner(window, Some(position), None)
+	}
+
+	fn popup_at_in_monitor<T:Runtime, P:Into<Position>>(
+		&self,
+		window:Window<T>,
+		position:P,
+		monitor:crate::Monitor,
+	) -> crate::Result<()> {
+		self.popup_inner(window, Some(position), Some(monitor))
 	}
 }
 
@@ -48,8 +60,18 @@ impl<R:Runtime> ContextMenuBase for Menu<R> {
 		&self,
 		window:crate::Window<T>,
 		position:Option<P>,
+		monitor:Option<crate::Monitor>,
 	) -> crate::Result<()> {
-		let position = position.map(Into::into);
+		let position = match position {
+			Some(position) => {
+				let size = run_item_main_thread!(self, |self_:Self| {
+					super::context_menu_size(self_.inner_context())
+				})?;
+
+				Some(super::resolve_popup_position(&window, position.into(), monitor, size)?)
+			},
+			None => None,
+		};
 
 		run_item_main_thread!(self, move |self_:Self| {
 			#[cfg(target_os = "macos")]
@@ -82,6 +104,12 @@ impl<R:Runtime> ContextMenuBase for Menu<R> {
 	fn inner_context_owned(&self) -> Box<dyn muda::ContextMenu> {
 		Box::new((*self.0).as_ref().clone())
 	}
+
+	fn inner_context_factory(&self) -> super::sealed::ContextMenuFactory {
+		let menu = self.clone();
+
+		Arc::new(move || menu.inner_context_owned())
+	}
 }
 
 impl<R:Runtime> Menu<R> {
@@ -96,7 +124,11 @@ impl<R:Runtime> Menu<R> {
 			MenuInner { id:menu.id().clone(), inner:Some(menu), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(menu)))
+		let menu = Self::from_inner(menu);
+
+		handle.manager.menu.register_root(MenuRoot::Menu(Arc::downgrade(&menu.0)));
+
+		Ok(menu)
 	}
 
 	/// Creates a new menu with the specified id.
@@ -112,7 +144,11 @@ impl<R:Runtime> Menu<R> {
 			MenuInner { id, inner:Some(menu), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(menu)))
+		let menu = Self::from_inner(menu);
+
+		handle.manager.menu.register_root(MenuRoot::Menu(Arc::downgrade(&menu.0)));
+
+		Ok(menu)
 	}
 
 	/// Creates a new menu with given `items`. It calls [`Menu::new`] and
@@ -142,44 +178,90 @@ impl<R:Runtime> Menu<R> {
 		Ok(menu)
 	}
 
+	/// Creates a menu from a declarative definition, see [`MenuConfig`].
+	///
+	/// The definition uses the shape accepted by the JavaScript `Menu.new` API,
+	/// so the same JSON can build the menu on either side. The menu can be used
+	/// as the app or window menu and as a context menu.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use tauri::{Manager, menu::Menu};
+	///
+	/// tauri::Builder::default().setup(|app| {
+	/// 	let menu = Menu::from_value(
+	/// 		app.handle(),
+	/// 		serde_json::json!({
+	/// 			"items": [
+	/// 				{ "id": "rename", "text": "Rename", "accelerator": "F2" },
+	/// 				{ "item": "Separator" },
+	/// 				{ "id": "delete", "text": "Delete" }
+	/// 			]
+	/// 		}),
+	/// 	)?;
+	/// 	let window = app.get_webview_window("main").unwrap();
+	/// 	window.popup_menu(&menu)?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	pub fn from_value<M:Manager<R>>(manager:&M, value:serde_json::Value) -> crate::Result<Self> {
+		Self::from_config(manager, &serde_json::from_value(value)?)
+	}
+
+	/// Creates a menu from a [`MenuConfig`].
+	///
+	/// Invalid items, like unknown predefined item kinds, fail with
+	/// [`crate::Error::InvalidMenuConfig`] naming the path of the item, e.g.
+	/// `items[0].items[2].item`.
+	pub fn from_config<M:Manager<R>>(manager:&M, config:&MenuConfig) -> crate::Result<Self> {
+		config.build(manager)
+	}
+
+	/// Describes this menu and its items, in the shape accepted by
+	/// [`Menu::from_value`].
+	///
+	/// The state of the items is read when this is called, so checked and
+	/// enabled states reflect user interaction. Predefined items are tagged by
+	/// their kind, and only native icons are described, see
+	/// [`Menu::to_value_with_icons`] to include image icons.
+	///
+	/// The metadata of predefined items like [`PredefinedMenuItem::about`] is
+	/// not described.
+	pub fn to_value(&self) -> crate::Result<serde_json::Value> {
+		serde_json::to_value(self.to_config(false)?).map_err(Into::into)
+	}
+
+	/// Like [`Menu::to_value`], also describing image icons as base64 encoded
+	/// PNG `data:` URLs.
+	#[cfg(feature = "image-png")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "image-png")))]
+	pub fn to_value_with_icons(&self) -> crate::Result<serde_json::Value> {
+		serde_json::to_value(self.to_config(true)?).map_err(Into::into)
+	}
+
+	fn to_config(&self, icons:bool) -> crate::Result<MenuConfig> {
+		Ok(MenuConfig {
+			id:Some(self.id().clone()),
+			items:self
+				.items()?
+				.iter()
+				.map(|item| MenuItemConfig::from_item(self.app_handle(), item, icons))
+				.collect::<crate::Result<_>>()?,
+		})
+	}
+
 	/// Creates a menu filled with default menu items and submenus.
 	pub fn default(app_handle:&AppHandle<R>) -> crate::Result<Self> {
+		#[cfg(target_os = "macos")]
 		let pkg_info = app_handle.package_info();
 
-		let config = app_handle.config();
-
-		let about_metadata = AboutMetadata {
-			name:Some(pkg_info.name.clone()),
-			version:Some(pkg_info.version.to_string()),
-			copyright:config.bundle.copyright.clone(),
-			authors:config.bundle.publisher.clone().map(|p| vec![p]),
-			..Default::default()
-		};
+		#[cfg(target_os = "macos")]
+		let about_metadata = AboutMetadata::from_app(app_handle);
 
-		let window_menu = Submenu::with_id_and_items(
-			app_handle,
-			WINDOW_SUBMENU_ID,
-			"Window",
-			true,
-			&[
-				&PredefinedMenuItem::minimize(app_handle, None)?,
-				&PredefinedMenuItem::maximize(app_handle, None)?,
-				#[cfg(target_os = "macos")]
-				&PredefinedMenuItem::separator(app_handle)?,
-				&PredefinedMenuItem::close_window(app_handle, None)?,
-			],
-		)?;
+		let window_menu = Self::default_window_submenu(app_handle)?;
 
-		let help_menu = Submenu::with_id_and_items(
-			app_handle,
-			HELP_SUBMENU_ID,
-			"Help",
-			true,
-			&[
-				#[cfg(not(target_os = "macos"))]
-				&PredefinedMenuItem::about(app_handle, None, Some(about_metadata))?,
-			],
-		)?;
+		let help_menu = Self::default_help_submenu(app_handle)?;
 
 		let menu = Menu::with_items(
 			app_handle,
@@ -246,6 +328,48 @@ impl<R:Runtime> Menu<R> {
 		Ok(menu)
 	}
 
+	/// Creates the "Window" submenu of [`Menu::default`], with the Window menu
+	/// role.
+	///
+	/// Use it to reuse the stock submenu in a custom menu, see
+	/// [`SubmenuBuilder::window_menu_role`](super::SubmenuBuilder::window_menu_role).
+	pub fn default_window_submenu(app_handle:&AppHandle<R>) -> crate::Result<Submenu<R>> {
+		Submenu::with_id_and_items(
+			app_handle,
+			WINDOW_SUBMENU_ID,
+			"Window",
+			true,
+			&[
+				&PredefinedMenuItem::minimize(app_handle, None)?,
+				&PredefinedMenuItem::maximize(app_handle, None)?,
+				#[cfg(target_os = "macos")]
+				&PredefinedMenuItem::separator(app_handle)?,
+				&PredefinedMenuItem::close_window(app_handle, None)?,
+			],
+		)
+	}
+
+	/// Creates the "Help" submenu of [`Menu::default`], with the Help menu role.
+	///
+	/// Use it to reuse the stock submenu in a custom menu, see
+	/// [`SubmenuBuilder::help_menu_role`](super::SubmenuBuilder::help_menu_role).
+	pub fn default_help_submenu(app_handle:&AppHandle<R>) -> crate::Result<Submenu<R>> {
+		Submenu::with_id_and_items(
+			app_handle,
+			HELP_SUBMENU_ID,
+			"Help",
+			true,
+			&[
+				#[cfg(not(target_os = "macos"))]
+				&PredefinedMenuItem::about(
+					app_handle,
+					None,
+					Some(AboutMetadata::from_app(app_handle)),
+				)?,
+			],
+		)
+	}
+
 	pub(crate) fn inner(&self) -> &muda::Menu { (*self.0).as_ref() }
 
 	/// The application handle associated with this type.
@@ -262,16 +386,10 @@ impl<R:Runtime> Menu<R> {
 	///
 	/// [`Submenu`]: super::Submenu
 	pub fn append(&self, item:&dyn IsMenuItem<R>) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().append(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], None)
 	}
 
-	/// Add menu items to the end of this menu. It calls [`Menu::append`] in a
-	/// loop internally.
+	/// Add menu items to the end of this menu.
 	///
 	/// ## Platform-specific:
 	///
@@ -279,11 +397,7 @@ impl<R:Runtime> Menu<R> {
 	///
 	/// [`Submenu`]: super::Submenu
 	pub fn append_items(&self, items:&[&dyn IsMenuItem<R>]) -> crate::Result<()> {
-		for item in items {
-			self.append(*item)?
-		}
-
-		Ok(())
+		self.add_items(items, None)
 	}
 
 	/// Add a menu item to the beginning of this menu.
@@ -294,12 +408,7 @@ impl<R:Runtime> Menu<R> {
 	///
 	/// [`Submenu`]: super::Submenu
 	pub fn prepend(&self, item:&dyn IsMenuItem<R>) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().prepend(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], Some(0))
 	}
 
 	/// Add menu items to the beginning of this menu. It calls
@@ -322,12 +431,7 @@ impl<R:Runtime> Menu<R> {
 	///
 	/// [`Submenu`]: super::Submenu
 	pub fn insert(&self, item:&dyn IsMenuItem<R>, position:usize) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().insert(kind.inner().inner_muda(), position)
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], Some(position))
 	}
 
 	/// Insert menu items at the specified `position` in the menu.
@@ -338,32 +442,123 @@ impl<R:Runtime> Menu<R> {
 	///
 	/// [`Submenu`]: super::Submenu
 	pub fn insert_items(&self, items:&[&dyn IsMenuItem<R>], position:usize) -> crate::Result<()> {
-		for (i, item) in items.iter().enumerate() {
-			self.insert(*item, position + i)?
+		self.add_items(items, Some(position))
+	}
+
+	/// Adds `items` at `position`, or at the end, checking their accelerators
+	/// once and adding them in a single main thread task.
+	fn add_items(&self, items:&[&dyn IsMenuItem<R>], position:Option<usize>) -> crate::Result<()> {
+		let items = items.iter().map(|i| i.kind()).collect::<Vec<_>>();
+
+		self.check_accelerators(&items, false)?;
+
+		for item in &items {
+			self.0.app_handle.manager.menu.apply_mnemonics(self.key(), item)?;
 		}
 
+		run_item_main_thread!(self, |self_:Self| {
+			let inner = (*self_.0).as_ref();
+
+			for (i, item) in items.iter().enumerate() {
+				match position {
+					Some(position) => inner.insert(item.inner().inner_muda(), position + i)?,
+					None => inner.append(item.inner().inner_muda())?,
+				}
+			}
+
+			Ok::<_, muda::Error>(())
+		})??;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
 		Ok(())
 	}
 
+	/// Returns [`crate::Error::AcceleratorConflict`] if `items` have
+	/// accelerators conflicting with each other or with the items of this
+	/// menu, unless they replace them.
+	fn check_accelerators(&self, items:&[MenuItemKind<R>], replace:bool) -> crate::Result<()> {
+		let existing = || if replace { Ok(Vec::new()) } else { self.items() };
+
+		self.0.app_handle.manager.menu.check_accelerators(self.key(), existing, None, items)
+	}
+
 	/// Remove a menu item from this menu.
 	pub fn remove(&self, item:&dyn IsMenuItem<R>) -> crate::Result<()> {
 		let kind = item.kind();
 
 		run_item_main_thread!(self, |self_:Self| {
 			(*self_.0).as_ref().remove(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		})??;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Remove the menu item at the specified position from this menu and
 	/// returns it.
 	pub fn remove_at(&self, position:usize) -> crate::Result<Option<MenuItemKind<R>>> {
-		run_item_main_thread!(self, |self_:Self| {
+		let item = run_item_main_thread!(self, |self_:Self| {
 			(*self_.0)
 				.as_ref()
 				.remove_at(position)
 				.map(|i| MenuItemKind::from_muda(self_.0.app_handle.clone(), i))
-		})
+		})?;
+
+		if item.is_some() {
+			self.0.app_handle.manager.menu.notify_changed();
+		}
+
+		Ok(item)
+	}
+
+	/// Replaces all the items of this menu with `items`.
+	///
+	/// The previous items are removed and the new ones added in a single main
+	/// thread task, so the menu is never observed half updated. If an item
+	/// fails to be added, the previous items are restored and
+	/// [`crate::Error::MenuSetItems`] reports which one failed.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **macOS:** Only [`Submenu`] can be added to the menu
+	///
+	/// [`Submenu`]: super::Submenu
+	pub fn set_items(&self, items:&[&dyn IsMenuItem<R>]) -> crate::Result<()> {
+		let items = items.iter().map(|i| i.kind()).collect::<Vec<_>>();
+
+		self.check_accelerators(&items, true)?;
+
+		for item in &items {
+			self.0.app_handle.manager.menu.apply_mnemonics(self.key(), item)?;
+		}
+
+		// wrapped, so that the state of the removed items is dropped with them
+		let previous = run_item_main_thread!(self, |self_:Self| {
+			let inner = (*self_.0).as_ref();
+
+			let previous = inner.items();
+
+			super::replace_items(
+				previous.clone(),
+				&items,
+				|item| inner.append(item),
+				|position| inner.remove_at(position),
+			)
+			.map(|()| {
+				previous
+					.into_iter()
+					.map(|i| MenuItemKind::from_muda(self_.0.app_handle.clone(), i))
+					.collect::<Vec<_>>()
+			})
+		})??;
+
+		drop(previous);
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Retrieves the menu item matching the given identifier.
@@ -374,6 +569,15 @@ impl<R:Runtime> Menu<R> {
 		self.items().unwrap_or_default().into_iter().find(|i| i.id() == &id)
 	}
 
+	/// Retrieves the menu item matching the given identifier, searching nested
+	/// submenus depth-first.
+	pub fn get_deep<'a, I>(&self, id:&'a I) -> Option<MenuItemKind<R>>
+	where
+		I: ?Sized,
+		MenuId: PartialEq<&'a I>, {
+		super::find_item_deep(self.items().unwrap_or_default(), id)
+	}
+
 	/// Returns a list of menu items that has been added to this menu.
 	pub fn items(&self) -> crate::Result<Vec<MenuItemKind<R>>> {
 		run_item_main_thread!(self, |self_:Self| {
@@ -386,6 +590,20 @@ impl<R:Runtime> Menu<R> {
 		})
 	}
 
+	/// Returns all the menu items of this menu and its nested submenus,
+	/// depth-first, each paired with the ids of the submenus containing it.
+	///
+	/// Items added directly to this menu have an empty path.
+	pub fn items_deep(
+		&self,
+	) -> crate::Result<impl Iterator<Item = (Vec<MenuId>, MenuItemKind<R>)>> {
+		let mut items = Vec::new();
+
+		super::collect_items_deep(self.items()?, &mut Vec::new(), &mut items)?;
+
+		Ok(items.into_iter())
+	}
+
 	/// Set this menu as the application menu.
 	///
 	/// This is an alias for [`AppHandle::set_menu`].
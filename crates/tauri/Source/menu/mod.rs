@@ -6,16 +6,26 @@
 
 mod builders;
 mod check;
+mod config;
+#[cfg(linux_dbus_menu)]
+pub(crate) mod dbus;
+mod group;
 mod icon;
+mod icon_cache;
 #[allow(clippy::module_inception)]
 mod menu;
 mod normal;
 pub(crate) mod plugin;
 mod predefined;
 mod submenu;
-use std::sync::Arc;
+use std::{
+	collections::HashSet,
+	sync::{Arc, Weak},
+};
 
 pub use builders::*;
+pub use config::{MenuConfig, MenuIconConfig, MenuItemConfig};
+pub use group::CheckMenuItemGroup;
 pub use menu::{HELP_SUBMENU_ID, WINDOW_SUBMENU_ID};
 pub use muda::MenuId;
 use serde::{Deserialize, Serialize};
@@ -82,7 +92,14 @@ macro_rules! gen_wrappers {
 
       impl<R: Runtime> $crate::Resource for $type<R> {}
 
+      impl<R: $crate::Runtime> $inner<R> {
+        fn key(&self) -> $crate::menu::MenuItemKey {
+          $crate::menu::MenuItemKey::of(self.as_ref().id())
+        }
+      }
+
       impl<R: $crate::Runtime> Clone for $inner<R> {
+        /// The clone must be wrapped with `from_inner` like the original.
         fn clone(&self) -> Self {
           Self {
             id: self.id.clone(),
@@ -94,11 +111,17 @@ macro_rules! gen_wrappers {
 
       impl<R: Runtime> Drop for $inner<R> {
         fn drop(&mut self) {
+          if self.inner.is_some() {
+            self.app_handle.manager.menu.release_item(self.key());
+          }
           let inner = self.inner.take();
+          let app_handle = self.app_handle.clone();
           // SAFETY: inner was created on main thread and is being dropped on main thread
           let inner = $crate::UnsafeSend(inner);
           let _ = self.app_handle.run_on_main_thread(move || {
             drop(inner.take());
+            // the native item may be gone now, and so the state kept for it
+            app_handle.manager.menu.drop_unreachable_item_state();
           });
         }
       }
@@ -119,6 +142,20 @@ macro_rules! gen_wrappers {
         }
       }
 
+      impl<R: $crate::Runtime> $type<R> {
+        /// Wraps `inner`, keeping the state of the native item while the
+        /// wrapper is alive.
+        pub(crate) fn from_inner(inner: $inner<R>) -> Self {
+          inner.app_handle.manager.menu.retain_item(inner.key(), &inner.id);
+          Self(::std::sync::Arc::new(inner))
+        }
+
+        /// The key of the native item, see [`MenuItemKey`].
+        pub(crate) fn key(&self) -> $crate::menu::MenuItemKey {
+          self.0.key()
+        }
+      }
+
       $(
         impl<R: $crate::Runtime> $crate::menu::sealed::IsMenuItemBase for $type<R> {
           fn inner_muda(&self) -> &dyn muda::IsMenuItem {
@@ -222,6 +259,55 @@ pub struct AboutMetadata<'a> {
 	pub icon:Option<Image<'a>>,
 }
 
+impl AboutMetadata<'static> {
+	/// Creates the about metadata of the running app, read from its
+	/// [`PackageInfo`](crate::PackageInfo) and bundle config.
+	///
+	/// - `name` and `version` are the product name and version.
+	/// - `authors` are the Cargo package authors, or the bundle publisher if
+	///   there are none.
+	/// - `comments` is the Cargo package description.
+	/// - `copyright`, `license` and `website` are the bundle copyright,
+	///   license and homepage.
+	/// - `icon` is the default window icon.
+	///
+	/// The fields can be overridden afterwards, see also
+	/// [`AboutMetadataBuilder::with_defaults`].
+	pub fn from_app<R:Runtime>(app_handle:&AppHandle<R>) -> Self {
+		let pkg_info = app_handle.package_info();
+
+		let config = app_handle.config();
+
+		let authors = pkg_info
+			.authors
+			.split(':')
+			.map(str::trim)
+			.filter(|author| !author.is_empty())
+			.map(String::from)
+			.collect::<Vec<_>>();
+
+		let authors = if authors.is_empty() {
+			config.bundle.publisher.clone().map(|publisher| vec![publisher])
+		} else {
+			Some(authors)
+		};
+
+		Self {
+			name:Some(pkg_info.name.clone()),
+			version:Some(pkg_info.version.to_string()),
+			authors,
+			comments:Some(pkg_info.description)
+				.filter(|description| !description.is_empty())
+				.map(String::from),
+			copyright:config.bundle.copyright.clone(),
+			license:config.bundle.license.clone(),
+			website:config.bundle.homepage.clone(),
+			icon:app_handle.default_window_icon().cloned().map(Image::to_owned),
+			..Default::default()
+		}
+	}
+}
+
 /// A builder type for [`AboutMetadata`].
 #[derive(Clone, Debug, Default)]
 pub struct AboutMetadataBuilder<'a>(AboutMetadata<'a>);
@@ -230,6 +316,12 @@ impl<'a> AboutMetadataBuilder<'a> {
 	/// Create a new about metadata builder.
 	pub fn new() -> Self { Default::default() }
 
+	/// Create an about metadata builder filled with the metadata of the
+	/// running app, see [`AboutMetadata::from_app`].
+	pub fn with_defaults<R:Runtime>(app_handle:&AppHandle<R>) -> AboutMetadataBuilder<'static> {
+		AboutMetadataBuilder(AboutMetadata::from_app(app_handle))
+	}
+
 	/// Sets the application name.
 	pub fn name<S:Into<String>>(mut self, name:Option<S>) -> Self {
 		self.0.name = name.map(|s| s.into());
@@ -373,7 +465,7 @@ impl TryFrom<AboutMetadata<'_>> for muda::AboutMetadata {
 /// ## Platform-specific:
 ///
 /// - **Windows / Linux**: Unsupported.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NativeIcon {
 	/// An add item template image.
 	Add,
@@ -579,6 +671,30 @@ impl<R:Runtime> MenuItemKind<R> {
 		}
 	}
 
+	/// The key of the native item, see [`MenuItemKey`].
+	pub(crate) fn key(&self) -> MenuItemKey { MenuItemKey::of(self.inner().inner_muda().id()) }
+
+	/// The item text, as returned by the `text` getter of the item.
+	pub(crate) fn text(&self) -> crate::Result<String> {
+		match self {
+			MenuItemKind::MenuItem(i) => i.text(),
+			MenuItemKind::Submenu(i) => i.text(),
+			MenuItemKind::Predefined(i) => i.text(),
+			MenuItemKind::Check(i) => i.text(),
+			MenuItemKind::Icon(i) => i.text(),
+		}
+	}
+
+	pub(crate) fn set_text(&self, text:&str) -> crate::Result<()> {
+		match self {
+			MenuItemKind::MenuItem(i) => i.set_text(text),
+			MenuItemKind::Submenu(i) => i.set_text(text),
+			MenuItemKind::Predefined(i) => i.set_text(text),
+			MenuItemKind::Check(i) => i.set_text(text),
+			MenuItemKind::Icon(i) => i.set_text(text),
+		}
+	}
+
 	pub(crate) fn inner(&self) -> &dyn IsMenuItem<R> {
 		match self {
 			MenuItemKind::MenuItem(i) => i,
@@ -592,39 +708,45 @@ impl<R:Runtime> MenuItemKind<R> {
 	pub(crate) fn from_muda(app_handle:AppHandle<R>, i:muda::MenuItemKind) -> Self {
 		match i {
 			muda::MenuItemKind::MenuItem(i) => {
-				Self::MenuItem(MenuItem(Arc::new(MenuItemInner {
+				Self::MenuItem(MenuItem::from_inner(MenuItemInner {
 					id:i.id().clone(),
 					inner:i.into(),
 					app_handle,
-				})))
+				}))
 			},
 			muda::MenuItemKind::Submenu(i) => {
-				Self::Submenu(Submenu(Arc::new(SubmenuInner {
+				let submenu = Submenu::from_inner(SubmenuInner {
 					id:i.id().clone(),
 					inner:i.into(),
 					app_handle,
-				})))
+				});
+
+				let root = MenuRoot::Submenu(Arc::downgrade(&submenu.0));
+
+				submenu.0.app_handle.manager.menu.register_root(root);
+
+				Self::Submenu(submenu)
 			},
 			muda::MenuItemKind::Predefined(i) => {
-				Self::Predefined(PredefinedMenuItem(Arc::new(PredefinedMenuItemInner {
+				Self::Predefined(PredefinedMenuItem::from_inner(PredefinedMenuItemInner {
 					id:i.id().clone(),
 					inner:i.into(),
 					app_handle,
-				})))
+				}))
 			},
 			muda::MenuItemKind::Check(i) => {
-				Self::Check(CheckMenuItem(Arc::new(CheckMenuItemInner {
+				Self::Check(CheckMenuItem::from_inner(CheckMenuItemInner {
 					id:i.id().clone(),
 					inner:i.into(),
 					app_handle,
-				})))
+				}))
 			},
 			muda::MenuItemKind::Icon(i) => {
-				Self::Icon(IconMenuItem(Arc::new(IconMenuItemInner {
+				Self::Icon(IconMenuItem::from_inner(IconMenuItemInner {
 					id:i.id().clone(),
 					inner:i.into(),
 					app_handle,
-				})))
+				}))
 			},
 		}
 	}
@@ -711,6 +833,335 @@ impl<R:Runtime> MenuItemKind<R> {
 	}
 }
 
+/// Depth-first search for the item matching `id` in `items` and their nested
+/// submenus.
+pub(crate) fn find_item_deep<'a, R:Runtime, I>(
+	items:Vec<MenuItemKind<R>>,
+	id:&'a I,
+) -> Option<MenuItemKind<R>>
+where
+	I: ?Sized,
+	MenuId: PartialEq<&'a I>, {
+	for item in items {
+		if item.id() == &id {
+			return Some(item);
+		}
+
+		if let Some(submenu) = item.as_submenu() {
+			if let Some(item) = find_item_deep(submenu.items().unwrap_or_default(), id) {
+				return Some(item);
+			}
+		}
+	}
+
+	None
+}
+
+/// Depth-first walk of `items` and their nested submenus, pairing each item
+/// with the ids of the submenus containing it.
+pub(crate) fn collect_items_deep<R:Runtime>(
+	items:Vec<MenuItemKind<R>>,
+	path:&mut Vec<MenuId>,
+	collected:&mut Vec<(Vec<MenuId>, MenuItemKind<R>)>,
+) -> crate::Result<()> {
+	for item in items {
+		let submenu = item.as_submenu().cloned();
+
+		collected.push((path.clone(), item));
+
+		if let Some(submenu) = submenu {
+			path.push(submenu.id().clone());
+
+			collect_items_deep(submenu.items()?, path, collected)?;
+
+			path.pop();
+		}
+	}
+
+	Ok(())
+}
+
+/// Identifies a native menu item.
+///
+/// Unlike [`MenuId`], which several items can share, the key is unique to an
+/// item and the same for all the [`MenuItem`]s, [`Submenu`]s, etc. wrapping
+/// it, including the ones returned by [`Menu::items`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct MenuItemKey(usize);
+
+impl MenuItemKey {
+	/// The key of the native item with the given `id`, which must be the one
+	/// returned by the `id` getter of the native item: it is stored once per
+	/// item and shared by its clones.
+	pub(crate) fn of(id:&MenuId) -> Self { Self(id as *const MenuId as usize) }
+}
+
+/// A menu or submenu, weakly referenced to find the items that are still in a
+/// menu, see [`MenuManager::drop_unreachable_item_state`].
+///
+/// [`MenuManager::drop_unreachable_item_state`]: crate::manager::menu::MenuManager::drop_unreachable_item_state
+pub(crate) enum MenuRoot<R:Runtime> {
+	Menu(Weak<MenuInner<R>>),
+	Submenu(Weak<SubmenuInner<R>>),
+}
+
+impl<R:Runtime> Clone for MenuRoot<R> {
+	fn clone(&self) -> Self {
+		match self {
+			Self::Menu(menu) => Self::Menu(menu.clone()),
+			Self::Submenu(submenu) => Self::Submenu(submenu.clone()),
+		}
+	}
+}
+
+impl<R:Runtime> MenuRoot<R> {
+	pub(crate) fn is_alive(&self) -> bool {
+		match self {
+			Self::Menu(menu) => menu.strong_count() > 0,
+			Self::Submenu(submenu) => submenu.strong_count() > 0,
+		}
+	}
+
+	/// The menu of this root, if it's a menu containing the item with the given
+	/// key. Must be called on the main thread.
+	pub(crate) fn menu_containing(&self, key:MenuItemKey) -> Option<Menu<R>> {
+		let Self::Menu(menu) = self else {
+			return None;
+		};
+
+		let menu = Menu(menu.upgrade()?);
+
+		let mut keys = HashSet::new();
+
+		self.collect_item_keys(&mut keys);
+
+		keys.contains(&key).then_some(menu)
+	}
+
+	/// Adds the keys of the items of this menu, and of their nested items, to
+	/// `keys`. Must be called on the main thread.
+	pub(crate) fn collect_item_keys(&self, keys:&mut HashSet<MenuItemKey>) {
+		let items = match self {
+			Self::Menu(menu) => menu.upgrade().map(|menu| (*menu).as_ref().items()),
+			Self::Submenu(submenu) => submenu.upgrade().map(|submenu| (*submenu).as_ref().items()),
+		};
+
+		collect_item_keys(items.unwrap_or_default(), keys);
+	}
+}
+
+fn collect_item_keys(items:Vec<muda::MenuItemKind>, keys:&mut HashSet<MenuItemKey>) {
+	for item in items {
+		if keys.insert(MenuItemKey::of(item.id())) {
+			if let Some(submenu) = item.as_submenu() {
+				collect_item_keys(submenu.items(), keys);
+			}
+		}
+	}
+}
+
+/// State of a menu item that can't be read back from the native item, used to
+/// describe existing menus with [`Menu::to_value`].
+#[derive(Default, Clone)]
+pub(crate) struct MenuItemMetadata {
+	/// The accelerator of the item, as it was given.
+	pub(crate) accelerator:Option<String>,
+	/// The kind of a predefined item, as named in [`MenuItemConfig::item`].
+	pub(crate) predefined:Option<&'static str>,
+	/// The icon of an icon item.
+	pub(crate) icon:Option<MenuItemIcon>,
+	/// Whether `&` in the item text assigns a mnemonic, when set on the item
+	/// itself instead of inherited from its menu.
+	pub(crate) mnemonics:Option<bool>,
+	/// Whether the native item text has its `&` escaped, see
+	/// [`escape_mnemonics`].
+	pub(crate) escaped_mnemonics:bool,
+}
+
+#[derive(Clone)]
+pub(crate) enum MenuItemIcon {
+	Native(NativeIcon),
+	Image(Arc<Image<'static>>),
+}
+
+impl MenuItemIcon {
+	fn from_image(image:Option<&Image<'_>>) -> Option<Self> {
+		image.map(|image| Self::Image(Arc::new(image.clone().to_owned())))
+	}
+}
+
+/// Escapes `&` so the native layer displays it instead of interpreting it as
+/// a mnemonic.
+///
+/// Windows and Linux underline the character following `&` and macOS strips
+/// it, while `&&` is displayed as `&` everywhere.
+pub(crate) fn escape_mnemonics(text:&str) -> String { text.replace('&', "&&") }
+
+/// Reverts [`escape_mnemonics`].
+pub(crate) fn unescape_mnemonics(text:&str) -> String { text.replace("&&", "&") }
+
+/// Parses an item accelerator, ignoring it if it's invalid like the item
+/// constructors do, and returns it with the text to keep in the item metadata.
+pub(crate) fn parse_item_accelerator<A:AsRef<str>>(
+	accelerator:Option<A>,
+) -> (Option<muda::accelerator::Accelerator>, Option<String>) {
+	let Some(text) = accelerator.map(|a| a.as_ref().to_string()) else {
+		return (None, None);
+	};
+
+	match text.parse() {
+		Ok(accelerator) => (Some(accelerator), Some(text)),
+		Err(_) => (None, None),
+	}
+}
+
+/// Parses an accelerator given to a setter, returning the error of an invalid
+/// one unlike [`parse_item_accelerator`].
+pub(crate) fn try_parse_item_accelerator<A:AsRef<str>>(
+	accelerator:Option<A>,
+) -> crate::Result<(Option<muda::accelerator::Accelerator>, Option<String>)> {
+	let Some(text) = accelerator.map(|a| a.as_ref().to_string()) else {
+		return Ok((None, None));
+	};
+
+	let accelerator = text.parse().map_err(muda::Error::from)?;
+
+	Ok((Some(accelerator), Some(text)))
+}
+
+/// Replaces the items returned by `current` with `items`, on the main thread.
+///
+/// If an item can't be added, the items added so far are removed and the
+/// previous ones restored before returning the error.
+pub(crate) fn replace_items<R:Runtime>(
+	current:Vec<muda::MenuItemKind>,
+	items:&[MenuItemKind<R>],
+	append:impl Fn(&dyn muda::IsMenuItem) -> muda::Result<()>,
+	remove_at:impl Fn(usize) -> Option<muda::MenuItemKind>,
+) -> crate::Result<()> {
+	for _ in 0..current.len() {
+		remove_at(0);
+	}
+
+	for (index, item) in items.iter().enumerate() {
+		if let Err(source) = append(item.inner().inner_muda()) {
+			for _ in 0..index {
+				remove_at(0);
+			}
+
+			for previous in &current {
+				let _ = append(previous);
+			}
+
+			return Err(crate::Error::MenuSetItems { index, id:item.id().0.clone(), source });
+		}
+	}
+
+	Ok(())
+}
+
+/// The size of `menu` when it pops up, when it can be known before showing it.
+///
+/// Only macOS measures the menu, the Windows and GTK menus are flipped and
+/// moved by the system when they would overflow the monitor. Must be called on
+/// the main thread.
+#[cfg(target_os = "macos")]
+pub(crate) fn context_menu_size(menu:&dyn muda::ContextMenu) -> Option<crate::LogicalSize<f64>> {
+	let menu = menu.ns_menu() as *const objc2_app_kit::NSMenu;
+
+	// SAFETY: muda returns a valid NSMenu, used on the main thread
+	let size = unsafe { (*menu).size() };
+
+	Some(crate::LogicalSize::new(size.width, size.height))
+}
+
+/// The size of `menu` when it pops up, when it can be known before showing it.
+///
+/// Only macOS measures the menu, the Windows and GTK menus are flipped and
+/// moved by the system when they would overflow the monitor. Must be called on
+/// the main thread.
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn context_menu_size(_menu:&dyn muda::ContextMenu) -> Option<crate::LogicalSize<f64>> {
+	None
+}
+
+/// Converts a popup `position` relative to `window` to physical pixels and
+/// moves the popup inside `monitor`, or the monitor containing it, see
+/// [`fit_popup_position`].
+///
+/// Logical positions and sizes are converted with the window scale factor,
+/// which is the one of the monitor the window is on, not the primary monitor
+/// one.
+pub(crate) fn resolve_popup_position<R:Runtime>(
+	window:&crate::Window<R>,
+	position:crate::Position,
+	monitor:Option<crate::Monitor>,
+	menu_size:Option<crate::LogicalSize<f64>>,
+) -> crate::Result<crate::Position> {
+	let scale_factor = window.scale_factor()?;
+
+	let position = position.to_physical::<f64>(scale_factor);
+
+	let origin = window.inner_position()?;
+
+	let x = origin.x as f64 + position.x;
+
+	let y = origin.y as f64 + position.y;
+
+	let monitor = match monitor {
+		Some(monitor) => Some(monitor),
+		None => window.monitor_from_point(x, y)?.or(window.current_monitor()?),
+	};
+
+	let (x, y) = match monitor {
+		Some(monitor) => {
+			let min = (monitor.position().x as f64, monitor.position().y as f64);
+
+			let max =
+				(min.0 + monitor.size().width as f64, min.1 + monitor.size().height as f64);
+
+			let size = menu_size.map(|size| size.to_physical::<f64>(scale_factor));
+
+			fit_popup_position((x, y), size.map(|size| (size.width, size.height)), min, max)
+		},
+		None => (x, y),
+	};
+
+	Ok(crate::PhysicalPosition::new(
+		(x - origin.x as f64).round() as i32,
+		(y - origin.y as f64).round() as i32,
+	)
+	.into())
+}
+
+/// Places a popup menu opening at `anchor` inside the monitor going from `min`
+/// to `max`, excluded.
+///
+/// When the menu `size` is known, a menu overflowing the monitor is flipped to
+/// the other side of the anchor, and then moved so it's fully visible.
+/// Otherwise only the anchor is moved inside the monitor.
+pub(crate) fn fit_popup_position(
+	anchor:(f64, f64),
+	size:Option<(f64, f64)>,
+	min:(f64, f64),
+	max:(f64, f64),
+) -> (f64, f64) {
+	fn fit(anchor:f64, len:Option<f64>, min:f64, max:f64) -> f64 {
+		let Some(len) = len else {
+			return anchor.clamp(min, (max - 1.).max(min));
+		};
+
+		let start = if anchor + len > max && anchor - len >= min { anchor - len } else { anchor };
+
+		start.clamp(min, (max - len).max(min))
+	}
+
+	(
+		fit(anchor.0, size.map(|s| s.0), min.0, max.0),
+		fit(anchor.1, size.map(|s| s.1), min.1, max.1),
+	)
+}
+
 impl<R:Runtime> Clone for MenuItemKind<R> {
 	fn clone(&self) -> Self {
 		match self {
@@ -769,15 +1220,37 @@ pub trait ContextMenu: sealed::ContextMenuBase + Send + Sync {
 	/// Popup this menu as a context menu on the specified window at the
 	/// specified position.
 	///
-	/// The position is relative to the window's top-left corner.
+	/// The position is relative to the window's top-left corner. A logical
+	/// position is converted with the scale factor of the window's monitor and
+	/// the menu is kept on the monitor containing the position, see
+	/// [`Self::popup_at_in_monitor`].
 	fn popup_at<R:crate::Runtime, P:Into<crate::Position>>(
 		&self,
 		window:crate::Window<R>,
 		position:P,
 	) -> crate::Result<()>;
+
+	/// Popup this menu as a context menu on the specified window at the
+	/// specified position, keeping it on `monitor`.
+	///
+	/// The position is relative to the window's top-left corner and is moved
+	/// inside the monitor bounds if it is outside of them. The system then
+	/// opens the menu on the other side of the position when it would overflow
+	/// the monitor.
+	fn popup_at_in_monitor<R:crate::Runtime, P:Into<crate::Position>>(
+		&self,
+		window:crate::Window<R>,
+		position:P,
+		monitor:crate::Monitor,
+	) -> crate::Result<()>;
 }
 
 pub(crate) mod sealed {
+	use std::sync::Arc;
+
+	/// Creates a new native handle to a context menu, e.g. to give it to a
+	/// tray icon that is recreated.
+	pub type ContextMenuFactory = Arc<dyn Fn() -> Box<dyn muda::ContextMenu> + Send + Sync>;
 
 	pub trait IsMenuItemBase {
 		fn inner_muda(&self) -> &dyn muda::IsMenuItem;
@@ -788,10 +1261,13 @@ pub(crate) mod sealed {
 
 		fn inner_context_owned(&self) -> Box<dyn muda::ContextMenu>;
 
+		fn inner_context_factory(&self) -> ContextMenuFactory;
+
 		fn popup_inner<R:crate::Runtime, P:Into<crate::Position>>(
 			&self,
 			window:crate::Window<R>,
 			position:Option<P>,
+			monitor:Option<crate::Monitor>,
 		) -> crate::Result<()>;
 	}
 }
@@ -804,3 +1280,260 @@ pub(crate) fn map_to_menu_theme(theme:tauri_utils::Theme) -> muda::MenuTheme {
 		_ => muda::MenuTheme::Auto,
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::cell::RefCell;
+
+	use super::*;
+	use crate::test::{MockRuntime, mock_app, mock_builder, mock_context, noop_assets};
+
+	fn noop(_:&AppHandle<MockRuntime>) {}
+
+	#[test]
+	fn set_items_keeps_the_state_of_the_remaining_items() {
+		let app = mock_app();
+
+		let open = MenuItemBuilder::with_id("open", "Open").on_click(noop).build(&app).unwrap();
+
+		let save = MenuItemBuilder::with_id("save", "Save").on_click(noop).build(&app).unwrap();
+
+		let menu = MenuBuilder::new(&app).item(&open).item(&save).build().unwrap();
+
+		let close = MenuItem::with_id(&app, "close", "Close", true, None::<&str>).unwrap();
+
+		menu.set_items(&[&close, &open]).unwrap();
+
+		drop(save);
+
+		let ids = menu.items().unwrap().iter().map(|i| i.id().0.clone()).collect::<Vec<_>>();
+
+		assert_eq!(ids, ["close", "open"]);
+
+		assert_eq!(app.manager.menu.item_click_handlers(&"open".into()).len(), 1);
+
+		assert!(app.manager.menu.item_click_handlers(&"save".into()).is_empty());
+	}
+
+	#[test]
+	fn nested_items_are_found_and_listed_depth_first() {
+		let app = mock_app();
+
+		let recent = SubmenuBuilder::with_id(&app, "recent", "Open Recent")
+			.text("clear", "Clear")
+			.build()
+			.unwrap();
+
+		let file = SubmenuBuilder::with_id(&app, "file", "File")
+			.text("open", "Open")
+			.item(&recent)
+			.text("close", "Close")
+			.build()
+			.unwrap();
+
+		let menu = MenuBuilder::new(&app).item(&file).text("about", "About").build().unwrap();
+
+		assert!(menu.get("clear").is_none());
+		assert_eq!(menu.get_deep("clear").unwrap().id().0, "clear");
+		assert_eq!(menu.get_deep("recent").unwrap().text().unwrap(), "Open Recent");
+		assert!(menu.get_deep("missing").is_none());
+
+		assert_eq!(file.get_deep("clear").unwrap().id().0, "clear");
+		assert!(file.get_deep("about").is_none());
+
+		let paths = |items:Vec<(Vec<MenuId>, MenuItemKind<MockRuntime>)>| {
+			items
+				.into_iter()
+				.map(|(path, item)| {
+					let ids = path.iter().chain([item.id()]).map(|id| id.0.as_str());
+
+					ids.collect::<Vec<_>>().join("/")
+				})
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(
+			paths(menu.items_deep().unwrap().collect()),
+			["file", "file/open", "file/recent", "file/recent/clear", "file/close", "about"]
+		);
+
+		assert_eq!(
+			paths(file.items_deep().unwrap().collect()),
+			["open", "recent", "recent/clear", "close"]
+		);
+
+		assert_eq!(paths(recent.items_deep().unwrap().collect()), ["clear"]);
+	}
+
+	#[test]
+	fn about_metadata_is_filled_from_the_app() {
+		let about = |authors:&'static str, description:&'static str, publisher:Option<&str>| {
+			let mut context = mock_context(noop_assets());
+
+			context.package_info_mut().authors = authors;
+
+			context.package_info_mut().description = description;
+
+			let bundle = &mut context.config_mut().bundle;
+
+			bundle.publisher = publisher.map(Into::into);
+
+			bundle.copyright = Some("Copyright Tauri".into());
+
+			bundle.license = Some("MIT".into());
+
+			bundle.homepage = Some("https://tauri.app".into());
+
+			context.set_default_window_icon(Some(Image::new_owned(vec![0; 4], 1, 1)));
+
+			let app = mock_builder().build(context).unwrap();
+
+			AboutMetadata::from_app(app.handle())
+		};
+
+		let metadata = about("Jane Doe <jane@tauri.app>: John Doe:", "A test app", Some("Tauri"));
+
+		assert_eq!(metadata.name.as_deref(), Some("test"));
+		assert_eq!(metadata.version.as_deref(), Some("0.1.0"));
+		assert_eq!(
+			metadata.authors,
+			Some(vec!["Jane Doe <jane@tauri.app>".to_string(), "John Doe".to_string()])
+		);
+		assert_eq!(metadata.comments.as_deref(), Some("A test app"));
+		assert_eq!(metadata.copyright.as_deref(), Some("Copyright Tauri"));
+		assert_eq!(metadata.license.as_deref(), Some("MIT"));
+		assert_eq!(metadata.website.as_deref(), Some("https://tauri.app"));
+		assert!(metadata.icon.is_some());
+
+		// the publisher stands in for missing authors
+		let metadata = about(" : ", "", Some("Tauri"));
+
+		assert_eq!(metadata.authors, Some(vec!["Tauri".to_string()]));
+		assert_eq!(metadata.comments, None);
+
+		assert_eq!(about("", "", None).authors, None);
+	}
+
+	#[test]
+	fn mnemonics_are_escaped_and_restored() {
+		assert_eq!(escape_mnemonics("Save & Quit"), "Save && Quit");
+		assert_eq!(unescape_mnemonics("Save && Quit"), "Save & Quit");
+
+		for text in ["Save", "&Open", "&&", "&&&", "R&&D & Q"] {
+			assert_eq!(unescape_mnemonics(&escape_mnemonics(text)), text);
+		}
+	}
+
+	#[test]
+	fn menus_without_mnemonics_escape_their_items() {
+		let app = mock_app();
+
+		let menu =
+			MenuBuilder::new(&app).mnemonics(false).text("save", "Save & Quit").build().unwrap();
+
+		let save = menu.get("save").unwrap();
+
+		assert_eq!(save.text().unwrap(), "Save & Quit");
+		assert_eq!((*save.as_menuitem().unwrap().0).as_ref().text(), "Save && Quit");
+
+		let open = MenuItem::with_id(&app, "open", "&Open", true, None::<&str>).unwrap();
+
+		menu.append(&open).unwrap();
+
+		assert_eq!((*open.0).as_ref().text(), "&&Open");
+
+		drop((save, open, menu));
+
+		assert!(app.manager.menu.items.lock().unwrap().is_empty());
+	}
+
+	#[test]
+	fn set_items_rejects_conflicting_accelerators() {
+		let app = mock_app();
+
+		let menu = MenuBuilder::new(&app).text("open", "Open").build().unwrap();
+
+		let copy = MenuItem::with_id(&app, "copy", "Copy", true, Some("Ctrl+C")).unwrap();
+
+		let clone = MenuItem::with_id(&app, "clone", "Clone", true, Some("Ctrl+C")).unwrap();
+
+		assert!(matches!(
+			menu.set_items(&[&copy, &clone]),
+			Err(crate::Error::AcceleratorConflict { .. })
+		));
+
+		assert_eq!(menu.items().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn popups_are_flipped_and_kept_inside_the_monitor() {
+		let min = (0., 0.);
+
+		let max = (1920., 1080.);
+
+		// fits
+		assert_eq!(fit_popup_position((100., 100.), Some((200., 300.)), min, max), (100., 100.));
+
+		// overflows the right and bottom edges, opens on the other side
+		assert_eq!(
+			fit_popup_position((1800., 1000.), Some((200., 300.)), min, max),
+			(1600., 700.)
+		);
+
+		// can't be flipped either, stays inside
+		assert_eq!(fit_popup_position((100., 1000.), Some((200., 1050.)), min, max), (100., 30.));
+
+		// bigger than the monitor
+		assert_eq!(fit_popup_position((100., 100.), Some((2000., 100.)), min, max), (0., 100.));
+
+		// unknown size, only the anchor is moved
+		assert_eq!(fit_popup_position((2500., -20.), None, min, max), (1919., 0.));
+
+		// secondary monitor on the left of the primary one
+		assert_eq!(
+			fit_popup_position((-100., 500.), Some((200., 100.)), (-1280., 0.), (0., 1024.)),
+			(-300., 500.)
+		);
+	}
+
+	#[test]
+	fn replace_items_restores_the_previous_items_on_failure() {
+		let app = mock_app();
+
+		let previous = vec![
+			muda::MenuItemKind::MenuItem(muda::MenuItem::with_id("a", "A", true, None)),
+			muda::MenuItemKind::MenuItem(muda::MenuItem::with_id("b", "B", true, None)),
+		];
+
+		let items = ["c", "bad", "d"]
+			.map(|id| MenuItem::with_id(&app, id, id, true, None::<&str>).unwrap().kind());
+
+		let menu = RefCell::new(vec![MenuId::new("a"), MenuId::new("b")]);
+
+		let result = replace_items::<MockRuntime>(
+			previous,
+			&items,
+			|item| {
+				if item.id() == "bad" {
+					return Err(muda::Error::NotAChildOfThisMenu);
+				}
+
+				menu.borrow_mut().push(item.id().clone());
+
+				Ok(())
+			},
+			|position| {
+				menu.borrow_mut().remove(position);
+
+				None
+			},
+		);
+
+		assert!(matches!(
+			result,
+			Err(crate::Error::MenuSetItems { index:1, ref id, .. }) if id == "bad"
+		));
+
+		assert_eq!(*menu.borrow(), [MenuId::new("a"), MenuId::new("b")]);
+	}
+}
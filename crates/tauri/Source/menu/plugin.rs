@@ -79,7 +79,7 @@ impl AboutMetadata {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Deserialize)]
-enum Predefined {
+pub(crate) enum Predefined {
 	Separator,
 	Copy,
 	Cut,
@@ -97,6 +97,8 @@ enum Predefined {
 	Quit,
 	About(Option<AboutMetadata>),
 	Services,
+	Zoom,
+	BringAllToFront,
 }
 
 #[derive(Deserialize)]
@@ -274,46 +276,50 @@ impl MenuItemPayload {
 }
 
 #[derive(Deserialize)]
-struct PredefinedMenuItemPayload {
-	item:Predefined,
-	text:Option<String>,
+pub(crate) struct PredefinedMenuItemPayload {
+	pub item:Predefined,
+	pub text:Option<String>,
 }
 
 impl PredefinedMenuItemPayload {
-	pub fn create_item<R:Runtime>(
+	pub fn create_item<R:Runtime, M:Manager<R>>(
 		self,
-		webview:&Webview<R>,
+		manager:&M,
 		resources_table:&ResourceTable,
 	) -> crate::Result<PredefinedMenuItem<R>> {
 		match self.item {
-			Predefined::Separator => PredefinedMenuItem::separator(webview),
-			Predefined::Copy => PredefinedMenuItem::copy(webview, self.text.as_deref()),
-			Predefined::Cut => PredefinedMenuItem::cut(webview, self.text.as_deref()),
-			Predefined::Paste => PredefinedMenuItem::paste(webview, self.text.as_deref()),
-			Predefined::SelectAll => PredefinedMenuItem::select_all(webview, self.text.as_deref()),
-			Predefined::Undo => PredefinedMenuItem::undo(webview, self.text.as_deref()),
-			Predefined::Redo => PredefinedMenuItem::redo(webview, self.text.as_deref()),
-			Predefined::Minimize => PredefinedMenuItem::minimize(webview, self.text.as_deref()),
-			Predefined::Maximize => PredefinedMenuItem::maximize(webview, self.text.as_deref()),
-			Predefined::Fullscreen => PredefinedMenuItem::fullscreen(webview, self.text.as_deref()),
-			Predefined::Hide => PredefinedMenuItem::hide(webview, self.text.as_deref()),
+			Predefined::Separator => PredefinedMenuItem::separator(manager),
+			Predefined::Copy => PredefinedMenuItem::copy(manager, self.text.as_deref()),
+			Predefined::Cut => PredefinedMenuItem::cut(manager, self.text.as_deref()),
+			Predefined::Paste => PredefinedMenuItem::paste(manager, self.text.as_deref()),
+			Predefined::SelectAll => PredefinedMenuItem::select_all(manager, self.text.as_deref()),
+			Predefined::Undo => PredefinedMenuItem::undo(manager, self.text.as_deref()),
+			Predefined::Redo => PredefinedMenuItem::redo(manager, self.text.as_deref()),
+			Predefined::Minimize => PredefinedMenuItem::minimize(manager, self.text.as_deref()),
+			Predefined::Maximize => PredefinedMenuItem::maximize(manager, self.text.as_deref()),
+			Predefined::Fullscreen => PredefinedMenuItem::fullscreen(manager, self.text.as_deref()),
+			Predefined::Hide => PredefinedMenuItem::hide(manager, self.text.as_deref()),
 			Predefined::HideOthers => {
-				PredefinedMenuItem::hide_others(webview, self.text.as_deref())
+				PredefinedMenuItem::hide_others(manager, self.text.as_deref())
 			},
-			Predefined::ShowAll => PredefinedMenuItem::show_all(webview, self.text.as_deref()),
+			Predefined::ShowAll => PredefinedMenuItem::show_all(manager, self.text.as_deref()),
 			Predefined::CloseWindow => {
-				PredefinedMenuItem::close_window(webview, self.text.as_deref())
+				PredefinedMenuItem::close_window(manager, self.text.as_deref())
 			},
-			Predefined::Quit => PredefinedMenuItem::quit(webview, self.text.as_deref()),
+			Predefined::Quit => PredefinedMenuItem::quit(manager, self.text.as_deref()),
 			Predefined::About(metadata) => {
 				let metadata = match metadata {
 					Some(m) => Some(m.into_metadata(resources_table)?),
 					None => None,
 				};
 
-				PredefinedMenuItem::about(webview, self.text.as_deref(), metadata)
+				PredefinedMenuItem::about(manager, self.text.as_deref(), metadata)
+			},
+			Predefined::Services => PredefinedMenuItem::services(manager, self.text.as_deref()),
+			Predefined::Zoom => PredefinedMenuItem::zoom(manager, self.text.as_deref()),
+			Predefined::BringAllToFront => {
+				PredefinedMenuItem::bring_all_to_front(manager, self.text.as_deref())
 			},
-			Predefined::Services => PredefinedMenuItem::services(webview, self.text.as_deref()),
 		}
 	}
 }
@@ -701,12 +707,12 @@ async fn popup<R:Runtime>(
 			ItemKind::Menu => {
 				let menu = resources_table.get::<Menu<R>>(rid)?;
 
-				menu.popup_inner(window, at)?;
+				menu.popup_inner(window, at, None)?;
 			},
 			ItemKind::Submenu => {
 				let submenu = resources_table.get::<Submenu<R>>(rid)?;
 
-				submenu.popup_inner(window, at)?;
+				submenu.popup_inner(window, at, None)?;
 			},
 			_ => return Err(anyhow::anyhow!("unexpected menu item kind").into()),
 		};
@@ -818,6 +824,16 @@ fn set_accelerator<R:Runtime>(
 	)
 }
 
+#[command(root = "crate")]
+fn accelerator<R:Runtime>(
+	webview:Webview<R>,
+	rid:ResourceId,
+	kind:ItemKind,
+) -> crate::Result<Option<String>> {
+	let resources_table = webview.resources_table();
+	do_menu_item!(resources_table, rid, kind, |i| Ok(i.accelerator()), !Predefined | !Submenu)
+}
+
 #[command(root = "crate")]
 fn set_as_windows_menu_for_nsapp<R:Runtime>(
 	webview:Webview<R>,
@@ -920,6 +936,7 @@ pub(crate) fn init<R:Runtime>() -> TauriPlugin<R> {
 			is_enabled,
 			set_enabled,
 			set_accelerator,
+			accelerator,
 			set_as_windows_menu_for_nsapp,
 			set_as_help_menu_for_nsapp,
 			is_checked,
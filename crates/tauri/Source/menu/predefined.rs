@@ -2,8 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::sync::Arc;
-
 use super::{AboutMetadata, PredefinedMenuItem, run_item_main_thread};
 use crate::{
 	AppHandle,
@@ -25,7 +23,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Separator"));
+
+		Ok(item)
 	}
 
 	/// Copy menu item
@@ -41,7 +43,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Copy"));
+
+		Ok(item)
 	}
 
 	/// Cut menu item
@@ -57,7 +63,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Cut"));
+
+		Ok(item)
 	}
 
 	/// Paste menu item
@@ -73,7 +83,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Paste"));
+
+		Ok(item)
 	}
 
 	/// SelectAll menu item
@@ -89,7 +103,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("SelectAll"));
+
+		Ok(item)
 	}
 
 	/// Undo menu item
@@ -109,7 +127,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Undo"));
+
+		Ok(item)
 	}
 
 	/// Redo menu item
@@ -129,7 +151,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Redo"));
+
+		Ok(item)
 	}
 
 	/// Minimize window menu item
@@ -149,7 +175,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Minimize"));
+
+		Ok(item)
 	}
 
 	/// Maximize window menu item
@@ -169,7 +199,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Maximize"));
+
+		Ok(item)
 	}
 
 	/// Fullscreen menu item
@@ -189,7 +223,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Fullscreen"));
+
+		Ok(item)
 	}
 
 	/// Hide window menu item
@@ -209,7 +247,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Hide"));
+
+		Ok(item)
 	}
 
 	/// Hide other windows menu item
@@ -229,7 +271,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("HideOthers"));
+
+		Ok(item)
 	}
 
 	/// Show all app windows menu item
@@ -249,7 +295,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("ShowAll"));
+
+		Ok(item)
 	}
 
 	/// Close window menu item
@@ -269,7 +319,14 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle
+			.manager
+			.menu
+			.update_item_metadata(item.key(), |m| m.predefined = Some("CloseWindow"));
+
+		Ok(item)
 	}
 
 	/// Quit app menu item
@@ -289,7 +346,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Quit"));
+
+		Ok(item)
 	}
 
 	/// About app menu item
@@ -314,7 +375,11 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("About"));
+
+		Ok(item)
 	}
 
 	/// Services menu item
@@ -334,7 +399,67 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(item)))
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Services"));
+
+		Ok(item)
+	}
+
+	/// Zoom window menu item, the native macOS Window menu item that toggles
+	/// between the standard and the user size of the window. Defaults to the
+	/// `Zoom` text.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Windows:** Behaves like [`PredefinedMenuItem::maximize`].
+	/// - **Linux:** Unsupported.
+	pub fn zoom<M:Manager<R>>(manager:&M, text:Option<&str>) -> crate::Result<Self> {
+		let handle = manager.app_handle();
+
+		let app_handle = handle.clone();
+
+		let text = text.unwrap_or("Zoom").to_owned();
+
+		// the maximize item performs `performZoom:` on macOS
+		let item = run_main_thread!(handle, || {
+			let item = muda::PredefinedMenuItem::maximize(Some(&text));
+			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
+		})?;
+
+		let item = Self::from_inner(item);
+
+		handle.manager.menu.update_item_metadata(item.key(), |m| m.predefined = Some("Zoom"));
+
+		Ok(item)
+	}
+
+	/// Bring All to Front menu item, the native macOS Window menu item that
+	/// arranges all the app windows in front of the windows of other apps.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Windows / Linux:** Unsupported.
+	pub fn bring_all_to_front<M:Manager<R>>(manager:&M, text:Option<&str>) -> crate::Result<Self> {
+		let handle = manager.app_handle();
+
+		let app_handle = handle.clone();
+
+		let text = text.map(|t| t.to_owned());
+
+		let item = run_main_thread!(handle, || {
+			let item = muda::PredefinedMenuItem::bring_all_to_front(text.as_deref());
+			PredefinedMenuItemInner { id:item.id().clone(), inner:Some(item), app_handle }
+		})?;
+
+		let item = Self::from_inner(item);
+
+		handle
+			.manager
+			.menu
+			.update_item_metadata(item.key(), |m| m.predefined = Some("BringAllToFront"));
+
+		Ok(item)
 	}
 
 	/// Returns a unique identifier associated with this menu item.
@@ -342,7 +467,9 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 
 	/// Get the text for this menu item.
 	pub fn text(&self) -> crate::Result<String> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())
+		let text = run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())?;
+
+		Ok(self.0.app_handle.manager.menu.display_text(self.key(), text))
 	}
 
 	/// Set the text for this menu item. `text` could optionally contain
@@ -350,9 +477,13 @@ impl<R:Runtime> PredefinedMenuItem<R> {
 	/// for this menu item. To display a `&` without assigning a mnemenonic, use
 	/// `&&`.
 	pub fn set_text<S:AsRef<str>>(&self, text:S) -> crate::Result<()> {
-		let text = text.as_ref().to_string();
+		let text = self.0.app_handle.manager.menu.native_text(self.key(), text.as_ref());
+
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
 
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))
+		Ok(())
 	}
 
 	/// The application handle associated with this type.
@@ -6,7 +6,14 @@ use std::sync::Arc;
 
 use muda::{ContextMenu, MenuId};
 
-use super::{IsMenuItem, MenuItemKind, Submenu, run_item_main_thread, sealed::ContextMenuBase};
+use super::{
+	IsMenuItem,
+	MenuItemKind,
+	MenuRoot,
+	Submenu,
+	run_item_main_thread,
+	sealed::ContextMenuBase,
+};
 use crate::{AppHandle, Manager, Position, Runtime, Window, menu::SubmenuInner, run_main_thread};
 
 impl<R:Runtime> super::ContextMenu for Submenu<R> {
@@ -16,7 +23,7 @@ impl<R:Runtime> super::ContextMenu for Submenu<R> {
 	}
 
 	fn popup<T:Runtime>(&self, window:Window<T>) -> crate::Result<()> {
-		self.popup_inner(window, None::<Position>)
+		self.popup_inner(window, None::<Position>, None)
 	}
 
 	fn popup_at<T:Runtime, P:Into<Position>>(
@@ -24,7 +31,16 @@ impl<R:Runtime> super::ContextMenu for Submenu<R> {
 		window:Window<T>,
 		position:P,
 	) -> crate::Result<()> {
-		self.popup_inner(window, Some(position))
+		self.popup_inner(window, Some(position), None)
+	}
+
+	fn popup_at_in_monitor<T:Runtime, P:Into<Position>>(
+		&self,
+		window:Window<T>,
+		position:P,
+		monitor:crate::Monitor,
+	) -> crate::Result<()> {
+		self.popup_inner(window, Some(position), Some(monitor))
 	}
 }
 
@@ -33,8 +49,18 @@ impl<R:Runtime> ContextMenuBase for Submenu<R> {
 		&self,
 		window:crate::Window<T>,
 		position:Option<P>,
+		monitor:Option<crate::Monitor>,
 	) -> crate::Result<()> {
-		let position = position.map(Into::into);
+		let position = match position {
+			Some(position) => {
+				let size = run_item_main_thread!(self, |self_:Self| {
+					super::context_menu_size(self_.inner_context())
+				})?;
+
+				Some(super::resolve_popup_position(&window, position.into(), monitor, size)?)
+			},
+			None => None,
+		};
 
 		run_item_main_thread!(self, move |self_:Self| {
 			#[cfg(target_os = "macos")]
@@ -67,6 +93,12 @@ impl<R:Runtime> ContextMenuBase for Submenu<R> {
 	fn inner_context_owned(&self) -> Box<dyn muda::ContextMenu> {
 		Box::new((*self.0).as_ref().clone())
 	}
+
+	fn inner_context_factory(&self) -> super::sealed::ContextMenuFactory {
+		let menu = self.clone();
+
+		Arc::new(move || menu.inner_context_owned())
+	}
 }
 
 impl<R:Runtime> Submenu<R> {
@@ -87,7 +119,11 @@ impl<R:Runtime> Submenu<R> {
 			SubmenuInner { id:submenu.id().clone(), inner:Some(submenu), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(submenu)))
+		let submenu = Self::from_inner(submenu);
+
+		handle.manager.menu.register_root(MenuRoot::Submenu(Arc::downgrade(&submenu.0)));
+
+		Ok(submenu)
 	}
 
 	/// Creates a new submenu with the specified id.
@@ -110,7 +146,11 @@ impl<R:Runtime> Submenu<R> {
 			SubmenuInner { id, inner:Some(submenu), app_handle }
 		})?;
 
-		Ok(Self(Arc::new(submenu)))
+		let submenu = Self::from_inner(submenu);
+
+		handle.manager.menu.register_root(MenuRoot::Submenu(Arc::downgrade(&submenu.0)));
+
+		Ok(submenu)
 	}
 
 	/// Creates a new menu with given `items`. It calls [`Submenu::new`] and
@@ -154,32 +194,17 @@ impl<R:Runtime> Submenu<R> {
 
 	/// Add a menu item to the end of this submenu.
 	pub fn append(&self, item:&dyn IsMenuItem<R>) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().append(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], None)
 	}
 
-	/// Add menu items to the end of this submenu. It calls [`Submenu::append`]
-	/// in a loop internally.
+	/// Add menu items to the end of this submenu.
 	pub fn append_items(&self, items:&[&dyn IsMenuItem<R>]) -> crate::Result<()> {
-		for item in items {
-			self.append(*item)?
-		}
-
-		Ok(())
+		self.add_items(items, None)
 	}
 
 	/// Add a menu item to the beginning of this submenu.
 	pub fn prepend(&self, item:&dyn IsMenuItem<R>) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().prepend(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], Some(0))
 	}
 
 	/// Add menu items to the beginning of this submenu. It calls
@@ -190,18 +215,72 @@ impl<R:Runtime> Submenu<R> {
 
 	/// Insert a menu item at the specified `position` in this submenu.
 	pub fn insert(&self, item:&dyn IsMenuItem<R>, position:usize) -> crate::Result<()> {
-		let kind = item.kind();
-
-		run_item_main_thread!(self, |self_:Self| {
-			(*self_.0).as_ref().insert(kind.inner().inner_muda(), position)
-		})?
-		.map_err(Into::into)
+		self.add_items(&[item], Some(position))
 	}
 
 	/// Insert menu items at the specified `position` in this submenu.
 	pub fn insert_items(&self, items:&[&dyn IsMenuItem<R>], position:usize) -> crate::Result<()> {
-		for (i, item) in items.iter().enumerate() {
-			self.insert(*item, position + i)?
+		self.add_items(items, Some(position))
+	}
+
+	/// Adds `items` at `position`, or at the end, checking their accelerators
+	/// once and adding them in a single main thread task.
+	fn add_items(&self, items:&[&dyn IsMenuItem<R>], position:Option<usize>) -> crate::Result<()> {
+		let items = items.iter().map(|i| i.kind()).collect::<Vec<_>>();
+
+		self.check_accelerators(&items, false)?;
+
+		for item in &items {
+			self.0.app_handle.manager.menu.apply_mnemonics(self.key(), item)?;
+		}
+
+		run_item_main_thread!(self, |self_:Self| {
+			let inner = (*self_.0).as_ref();
+
+			for (i, item) in items.iter().enumerate() {
+				match position {
+					Some(position) => inner.insert(item.inner().inner_muda(), position + i)?,
+					None => inner.append(item.inner().inner_muda())?,
+				}
+			}
+
+			Ok::<_, muda::Error>(())
+		})??;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
+	}
+
+	/// Returns [`crate::Error::AcceleratorConflict`] if `items` have
+	/// accelerators conflicting with each other or with the items of the menus
+	/// containing this submenu, or of this submenu if it isn't in a menu. The
+	/// current items of this submenu are ignored if `items` replace them.
+	fn check_accelerators(&self, items:&[MenuItemKind<R>], replace:bool) -> crate::Result<()> {
+		let manager = &self.0.app_handle.manager.menu;
+
+		let key = self.key();
+
+		if manager.duplicate_accelerators_allowed(key) {
+			return Ok(());
+		}
+
+		let handle = &self.0.app_handle;
+
+		let app_handle = handle.clone();
+
+		let menus = run_main_thread!(handle, || app_handle.manager.menu.menus_containing(key))?;
+
+		let replaced = replace.then_some(key);
+
+		if menus.is_empty() {
+			let existing = || if replace { Ok(Vec::new()) } else { self.items() };
+
+			return manager.check_accelerators(key, existing, None, items);
+		}
+
+		for menu in menus {
+			manager.check_accelerators(menu.key(), || menu.items(), replaced, items)?;
 		}
 
 		Ok(())
@@ -213,19 +292,90 @@ impl<R:Runtime> Submenu<R> {
 
 		run_item_main_thread!(self, |self_:Self| {
 			(*self_.0).as_ref().remove(kind.inner().inner_muda())
-		})?
-		.map_err(Into::into)
+		})??;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Remove the menu item at the specified position from this submenu and
 	/// returns it.
 	pub fn remove_at(&self, position:usize) -> crate::Result<Option<MenuItemKind<R>>> {
-		run_item_main_thread!(self, |self_:Self| {
+		let item = run_item_main_thread!(self, |self_:Self| {
 			(*self_.0)
 				.as_ref()
 				.remove_at(position)
 				.map(|i| MenuItemKind::from_muda(self_.0.app_handle.clone(), i))
-		})
+		})?;
+
+		if item.is_some() {
+			self.0.app_handle.manager.menu.notify_changed();
+		}
+
+		Ok(item)
+	}
+
+	/// Moves the item matching the given identifier to `position` in this
+	/// submenu, or to the end if `position` is past it.
+	///
+	/// Returns [`crate::Error::MenuItemNotFound`] if this submenu doesn't
+	/// contain the item.
+	pub fn move_item(&self, id:&MenuId, position:usize) -> crate::Result<()> {
+		let items = self.items()?;
+
+		let index = items
+			.iter()
+			.position(|i| i.id() == id)
+			.ok_or_else(|| crate::Error::MenuItemNotFound(id.0.clone()))?;
+
+		if let Some(item) = self.remove_at(index)? {
+			self.insert(&item, position.min(items.len() - 1))?;
+		}
+
+		Ok(())
+	}
+
+	/// Replaces all the items of this submenu with `items`.
+	///
+	/// The previous items are removed and the new ones added in a single main
+	/// thread task, so the submenu is never observed half updated. If an item
+	/// fails to be added, the previous items are restored and
+	/// [`crate::Error::MenuSetItems`] reports which one failed.
+	pub fn set_items(&self, items:&[&dyn IsMenuItem<R>]) -> crate::Result<()> {
+		let items = items.iter().map(|i| i.kind()).collect::<Vec<_>>();
+
+		self.check_accelerators(&items, true)?;
+
+		for item in &items {
+			self.0.app_handle.manager.menu.apply_mnemonics(self.key(), item)?;
+		}
+
+		// wrapped, so that the state of the removed items is dropped with them
+		let previous = run_item_main_thread!(self, |self_:Self| {
+			let inner = (*self_.0).as_ref();
+
+			let previous = inner.items();
+
+			super::replace_items(
+				previous.clone(),
+				&items,
+				|item| inner.append(item),
+				|position| inner.remove_at(position),
+			)
+			.map(|()| {
+				previous
+					.into_iter()
+					.map(|i| MenuItemKind::from_muda(self_.0.app_handle.clone(), i))
+					.collect::<Vec<_>>()
+			})
+		})??;
+
+		drop(previous);
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Retrieves the menu item matching the given identifier.
@@ -236,6 +386,15 @@ impl<R:Runtime> Submenu<R> {
 		self.items().unwrap_or_default().into_iter().find(|i| i.id() == &id)
 	}
 
+	/// Retrieves the menu item matching the given identifier, searching nested
+	/// submenus depth-first.
+	pub fn get_deep<'a, I>(&self, id:&'a I) -> Option<MenuItemKind<R>>
+	where
+		I: ?Sized,
+		MenuId: PartialEq<&'a I>, {
+		super::find_item_deep(self.items().unwrap_or_default(), id)
+	}
+
 	/// Returns a list of menu items that has been added to this submenu.
 	pub fn items(&self) -> crate::Result<Vec<MenuItemKind<R>>> {
 		run_item_main_thread!(self, |self_:Self| {
@@ -248,9 +407,44 @@ impl<R:Runtime> Submenu<R> {
 		})
 	}
 
+	/// Returns all the menu items of this submenu and its nested submenus,
+	/// depth-first, each paired with the ids of the submenus containing it.
+	///
+	/// Items added directly to this submenu have an empty path.
+	pub fn items_deep(
+		&self,
+	) -> crate::Result<impl Iterator<Item = (Vec<MenuId>, MenuItemKind<R>)>> {
+		let mut items = Vec::new();
+
+		super::collect_items_deep(self.items()?, &mut Vec::new(), &mut items)?;
+
+		Ok(items.into_iter())
+	}
+
+	/// Describes this submenu and its items as a submenu item of
+	/// [`Menu::from_value`](super::Menu::from_value), see
+	/// [`Menu::to_value`](super::Menu::to_value).
+	pub fn to_value(&self) -> crate::Result<serde_json::Value> {
+		let config = super::MenuItemConfig::from_item(self.app_handle(), &self.kind(), false)?;
+
+		serde_json::to_value(config).map_err(Into::into)
+	}
+
+	/// Like [`Submenu::to_value`], also describing image icons as base64
+	/// encoded PNG `data:` URLs.
+	#[cfg(feature = "image-png")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "image-png")))]
+	pub fn to_value_with_icons(&self) -> crate::Result<serde_json::Value> {
+		let config = super::MenuItemConfig::from_item(self.app_handle(), &self.kind(), true)?;
+
+		serde_json::to_value(config).map_err(Into::into)
+	}
+
 	/// Get the text for this submenu.
 	pub fn text(&self) -> crate::Result<String> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())
+		let text = run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().text())?;
+
+		Ok(self.0.app_handle.manager.menu.display_text(self.key(), text))
 	}
 
 	/// Set the text for this submenu. `text` could optionally contain
@@ -258,9 +452,13 @@ impl<R:Runtime> Submenu<R> {
 	/// for this submenu. To display a `&` without assigning a mnemonic, use
 	/// `&&`.
 	pub fn set_text<S:AsRef<str>>(&self, text:S) -> crate::Result<()> {
-		let text = text.as_ref().to_string();
+		let text = self.0.app_handle.manager.menu.native_text(self.key(), text.as_ref());
+
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
 
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_text(text))
+		Ok(())
 	}
 
 	/// Get whether this submenu is enabled or not.
@@ -270,7 +468,11 @@ impl<R:Runtime> Submenu<R> {
 
 	/// Enable or disable this submenu.
 	pub fn set_enabled(&self, enabled:bool) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))
+		run_item_main_thread!(self, |self_:Self| (*self_.0).as_ref().set_enabled(enabled))?;
+
+		self.0.app_handle.manager.menu.notify_changed();
+
+		Ok(())
 	}
 
 	/// Set this submenu as the Window menu for the application on macOS.
@@ -301,3 +503,38 @@ impl<R:Runtime> Submenu<R> {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		menu::{MenuId, SubmenuBuilder},
+		test::mock_app,
+	};
+
+	#[test]
+	fn items_are_moved_within_the_submenu() {
+		let app = mock_app();
+
+		let submenu =
+			SubmenuBuilder::new(&app, "Edit").text("a", "A").text("b", "B").text("c", "C").build().unwrap();
+
+		let ids = || submenu.items().unwrap().iter().map(|i| i.id().0.clone()).collect::<Vec<_>>();
+
+		submenu.move_item(&MenuId::new("a"), 2).unwrap();
+
+		assert_eq!(ids(), ["b", "c", "a"]);
+
+		submenu.move_item(&MenuId::new("a"), 0).unwrap();
+
+		assert_eq!(ids(), ["a", "b", "c"]);
+
+		submenu.move_item(&MenuId::new("a"), 10).unwrap();
+
+		assert_eq!(ids(), ["b", "c", "a"]);
+
+		assert!(matches!(
+			submenu.move_item(&MenuId::new("missing"), 0),
+			Err(crate::Error::MenuItemNotFound(id)) if id == "missing"
+		));
+	}
+}
@@ -0,0 +1,39 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::PathBuf;
+
+use super::{Error, OpenerScope};
+use crate::{
+	Runtime,
+	command,
+	ipc::CommandScope,
+	plugin::{Builder, TauriPlugin},
+};
+
+#[command(root = "crate")]
+pub fn open_url(scope:CommandScope<OpenerScope>, url:String) -> crate::Result<()> {
+	let url = super::parse_url(&url)?;
+
+	if !super::is_url_allowed(&url, scope.allows(), scope.denies()) {
+		return Err(Error::NotAllowed(url.into()).into());
+	}
+
+	super::open(url.as_str())
+}
+
+#[command(root = "crate")]
+pub fn open_path(scope:CommandScope<OpenerScope>, path:PathBuf) -> crate::Result<()> {
+	let path = super::resolve_path(&path)?;
+
+	if !super::is_path_allowed(&path, scope.allows(), scope.denies()) {
+		return Err(Error::NotAllowed(path.display().to_string()).into());
+	}
+
+	super::open_resolved_path(&path)
+}
+
+pub fn init<R:Runtime>() -> TauriPlugin<R> {
+	Builder::new("opener").invoke_handler(crate::generate_handler![open_url, open_path]).build()
+}
@@ -6,7 +6,7 @@
 
 use std::{
 	borrow::Cow,
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	fmt::{self, Debug},
 	sync::Arc,
 };
@@ -45,6 +45,9 @@ pub trait Plugin<R:Runtime>: Send {
 	/// The plugin name. Used as key on the plugin config object.
 	fn name(&self) -> &'static str;
 
+	/// The names of the plugins that must be initialized before this plugin.
+	fn requires(&self) -> &[&'static str] { &[] }
+
 	/// Initializes the plugin.
 	#[allow(unused_variables)]
 	fn initialize(
@@ -247,6 +250,7 @@ pub struct Builder<R:Runtime, C:DeserializeOwned = ()> {
 	on_event:Box<OnEvent<R>>,
 	on_drop:Option<Box<OnDrop<R>>>,
 	uri_scheme_protocols:HashMap<String, Arc<UriSchemeProtocol<R>>>,
+	requires:Vec<&'static str>,
 }
 
 impl<R:Runtime, C:DeserializeOwned> Builder<R, C> {
@@ -264,6 +268,7 @@ impl<R:Runtime, C:DeserializeOwned> Builder<R, C> {
 			on_event:Box::new(|_, _| ()),
 			on_drop:None,
 			uri_scheme_protocols:Default::default(),
+			requires:Vec::new(),
 		}
 	}
 
@@ -676,6 +681,40 @@ impl<R:Runtime, C:DeserializeOwned> Builder<R, C> {
 		self
 	}
 
+	/// Declares that the plugin must be initialized after the plugin with the
+	/// given name.
+	///
+	/// The app sorts the initialization of its plugins, and runs their
+	/// [`on_event`](Self::on_event) hooks, in the order of their dependencies,
+	/// failing to start if the required plugin is not registered or if the
+	/// plugins require each other.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use tauri::{
+	/// 	Manager,
+	/// 	Runtime,
+	/// 	plugin::{Builder, TauriPlugin},
+	/// };
+	///
+	/// fn init<R:Runtime>() -> TauriPlugin<R> {
+	/// 	Builder::new("telemetry")
+	/// 		.requires("config-store")
+	/// 		.setup(|app, _api| {
+	/// 			assert!(app.plugin_initialized("config-store"));
+	/// 			Ok(())
+	/// 		})
+	/// 		.build()
+	/// }
+	/// ```
+	#[must_use]
+	pub fn requires(mut self, plugin:&'static str) -> Self {
+		self.requires.push(plugin);
+
+		self
+	}
+
 	/// Builds the [`TauriPlugin`].
 	pub fn try_build(self) -> Result<TauriPlugin<R, C>, BuilderError> {
 		if let Some(&reserved) = RESERVED_PLUGIN_NAMES.iter().find(|&r| r == &self.name) {
@@ -695,6 +734,7 @@ impl<R:Runtime, C:DeserializeOwned> Builder<R, C> {
 			on_event:self.on_event,
 			on_drop:self.on_drop,
 			uri_scheme_protocols:self.uri_scheme_protocols,
+			requires:self.requires,
 		})
 	}
 
@@ -722,6 +762,7 @@ pub struct TauriPlugin<R:Runtime, C:DeserializeOwned = ()> {
 	on_event:Box<OnEvent<R>>,
 	on_drop:Option<Box<OnDrop<R>>>,
 	uri_scheme_protocols:HashMap<String, Arc<UriSchemeProtocol<R>>>,
+	requires:Vec<&'static str>,
 }
 
 impl<R:Runtime, C:DeserializeOwned> Drop for TauriPlugin<R, C> {
@@ -735,6 +776,8 @@ impl<R:Runtime, C:DeserializeOwned> Drop for TauriPlugin<R, C> {
 impl<R:Runtime, C:DeserializeOwned> Plugin<R> for TauriPlugin<R, C> {
 	fn name(&self) -> &'static str { self.name }
 
+	fn requires(&self) -> &[&'static str] { &self.requires }
+
 	fn initialize(
 		&mut self,
 		app:&AppHandle<R>,
@@ -821,7 +864,25 @@ impl<R:Runtime> PluginStore<R> {
 	}
 
 	/// Removes the plugin with the given name from the store.
+	///
+	/// The plugin is kept if another plugin in the store requires it.
 	pub fn unregister(&mut self, plugin:&'static str) -> bool {
+		let dependents = self
+			.store
+			.iter()
+			.filter(|p| p.name() != plugin && p.requires().contains(&plugin))
+			.map(|p| p.name())
+			.collect::<Vec<_>>();
+
+		if !dependents.is_empty() {
+			log::warn!(
+				"plugin `{plugin}` is required by {}, remove them first",
+				dependents.iter().map(|p| format!("`{p}`")).collect::<Vec<_>>().join(", ")
+			);
+
+			return false;
+		}
+
 		let len = self.store.len();
 
 		self.store.retain(|p| p.name() != plugin);
@@ -829,22 +890,49 @@ impl<R:Runtime> PluginStore<R> {
 		len != self.store.len()
 	}
 
-	/// Initializes the given plugin.
+	/// Initializes the given plugin, failing if the plugins it requires are not
+	/// initialized.
 	pub(crate) fn initialize(
 		&self,
 		plugin:&mut Box<dyn Plugin<R>>,
 		app:&AppHandle<R>,
 		config:&PluginConfig,
 	) -> crate::Result<()> {
+		let initialized = app.manager.initialized_plugins.lock().unwrap().clone();
+
+		if let Some(dependency) = plugin.requires().iter().find(|d| !initialized.contains(*d)) {
+			return Err(Error::MissingPluginDependency {
+				plugin:plugin.name().into(),
+				dependency:dependency.to_string(),
+			});
+		}
+
 		initialize(plugin, app, config)
 	}
 
-	/// Initializes all plugins in the store.
+	/// Initializes all plugins in the store, after the plugins they require.
+	///
+	/// The store is sorted in the initialization order, so the other hooks run
+	/// in the same order.
 	pub(crate) fn initialize_all(
 		&mut self,
 		app:&AppHandle<R>,
 		config:&PluginConfig,
 	) -> crate::Result<()> {
+		let plugins = self
+			.store
+			.iter()
+			.map(|plugin| (plugin.name(), plugin.requires().to_vec()))
+			.collect::<Vec<_>>();
+
+		let initialized = app.manager.initialized_plugins.lock().unwrap().clone();
+
+		let order = initialization_order(&plugins, &initialized)?;
+
+		let mut store = std::mem::take(&mut self.store).into_iter().map(Some).collect::<Vec<_>>();
+
+		self.store = order.into_iter().filter_map(|index| store[index].take()).collect();
+
 		self.store.iter_mut().try_for_each(|plugin| initialize(plugin, app, config))
 	}
 
@@ -926,7 +1014,88 @@ fn initialize<R:Runtime>(
 ) -> crate::Result<()> {
 	plugin
 		.initialize(app, config.0.get(plugin.name()).cloned().unwrap_or_default())
-		.map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e.to_string()))
+		.map_err(|e| Error::PluginInitialization(plugin.name().to_string(), e.to_string()))?;
+
+	app.manager.initialized_plugins.lock().unwrap().insert(plugin.name());
+
+	Ok(())
+}
+
+/// Sorts the plugins after the plugins they require, keeping their
+/// registration order otherwise, returning their indices.
+///
+/// Each plugin is placed as soon as its requirements are, so a plugin only
+/// moves after the plugins registered later that it requires, directly or not.
+/// The requirements can also be met by the `initialized` plugins.
+fn initialization_order(
+	plugins:&[(&'static str, Vec<&'static str>)],
+	initialized:&HashSet<&'static str>,
+) -> crate::Result<Vec<usize>> {
+	// the indices of the registered plugins each plugin requires
+	let mut requirements = Vec::with_capacity(plugins.len());
+
+	for (name, requires) in plugins {
+		let mut indices = Vec::new();
+
+		for dependency in requires {
+			match plugins.iter().position(|(p, _)| p == dependency) {
+				Some(index) => indices.push(index),
+				None if initialized.contains(dependency) => {},
+				None => {
+					return Err(Error::MissingPluginDependency {
+						plugin:name.to_string(),
+						dependency:dependency.to_string(),
+					});
+				},
+			}
+		}
+
+		requirements.push(indices);
+	}
+
+	let mut placed = vec![false; plugins.len()];
+
+	let mut order = Vec::with_capacity(plugins.len());
+
+	while order.len() < plugins.len() {
+		let ready = (0..plugins.len())
+			.find(|&index| !placed[index] && requirements[index].iter().all(|&r| placed[r]));
+
+		match ready {
+			Some(index) => {
+				placed[index] = true;
+
+				order.push(index);
+			},
+			None => {
+				// every plugin left requires another plugin left, so following their
+				// requirements leads to a cycle
+				let mut path = Vec::new();
+
+				let left = |index:&usize| !placed[*index];
+
+				let mut index = (0..plugins.len()).find(left).unwrap_or_default();
+
+				while !path.contains(&index) {
+					path.push(index);
+
+					index = requirements[index].iter().copied().find(left).unwrap_or(index);
+				}
+
+				let start = path.iter().position(|&p| p == index).unwrap_or_default();
+
+				let cycle = path[start..]
+					.iter()
+					.chain(std::iter::once(&index))
+					.map(|&p| plugins[p].0.to_string())
+					.collect();
+
+				return Err(Error::PluginDependencyCycle(cycle));
+			},
+		}
+	}
+
+	Ok(order)
 }
 
 /// Permission state.
@@ -979,3 +1148,92 @@ impl<'de> Deserialize<'de> for PermissionState {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashSet;
+
+	use super::{Builder, TauriPlugin, initialization_order};
+	use crate::{
+		Error,
+		test::{MockRuntime, mock_builder, mock_context, noop_assets},
+	};
+
+	fn plugin(name:&'static str, requires:&[&'static str]) -> TauriPlugin<MockRuntime> {
+		requires.iter().fold(Builder::new(name), |builder, r| builder.requires(*r)).build()
+	}
+
+	#[test]
+	fn plugins_are_initialized_after_their_requirements() {
+		let plugins = vec![
+			("telemetry", vec!["config-store"]),
+			("window-state", vec![]),
+			("config-store", vec!["fs"]),
+			("fs", vec![]),
+		];
+
+		let order = initialization_order(&plugins, &HashSet::new()).unwrap();
+
+		// window-state doesn't move after the plugins telemetry requires
+		assert_eq!(order, vec![1, 3, 2, 0]);
+
+		let independent = vec![("b", vec![]), ("a", vec![]), ("c", vec![])];
+
+		assert_eq!(initialization_order(&independent, &HashSet::new()).unwrap(), vec![0, 1, 2]);
+	}
+
+	#[test]
+	fn missing_requirements_and_cycles_are_rejected() {
+		let missing = initialization_order(&[("telemetry", vec!["config-store"])], &HashSet::new());
+
+		assert!(matches!(
+			missing,
+			Err(Error::MissingPluginDependency { plugin, dependency })
+				if plugin == "telemetry" && dependency == "config-store"
+		));
+
+		let initialized = HashSet::from(["config-store"]);
+
+		assert_eq!(
+			initialization_order(&[("telemetry", vec!["config-store"])], &initialized).unwrap(),
+			vec![0]
+		);
+
+		let cycle = initialization_order(
+			&[("a", vec!["b"]), ("b", vec!["c"]), ("c", vec!["a"])],
+			&HashSet::new(),
+		);
+
+		assert_eq!(cycle.unwrap_err().to_string(), "plugin dependency cycle: a -> b -> c -> a");
+
+		let cycle =
+			initialization_order(&[("fs", vec![]), ("a", vec!["fs", "a"])], &HashSet::new());
+
+		assert_eq!(cycle.unwrap_err().to_string(), "plugin dependency cycle: a -> a");
+	}
+
+	#[test]
+	fn required_plugins_are_only_removed_after_their_dependents() {
+		let app = mock_builder()
+			.plugin(plugin("telemetry", &["config-store"]))
+			.plugin(plugin("config-store", &[]))
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let app = app.handle();
+
+		assert!(app.plugin_initialized("telemetry"));
+
+		assert!(!app.remove_plugin("config-store"));
+
+		assert!(app.plugin_initialized("config-store"));
+
+		assert!(app.remove_plugin("telemetry"));
+
+		assert!(!app.plugin_initialized("telemetry"));
+
+		assert!(app.remove_plugin("config-store"));
+
+		assert!(!app.plugin_initialized("config-store"));
+	}
+}
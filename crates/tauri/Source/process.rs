@@ -4,7 +4,7 @@
 
 //! Types and functions related to child processes management.
 
-use std::path::PathBuf;
+use std::{ffi::OsString, path::PathBuf};
 
 use crate::Env;
 
@@ -57,6 +57,29 @@ pub fn current_binary(_env:&Env) -> std::io::Result<PathBuf> {
 	tauri_utils::platform::current_exe()
 }
 
+/// The configuration of the process replacing the app, see
+/// [`AppHandle::restart_with`](crate::AppHandle::restart_with).
+#[derive(Debug, Clone, Default)]
+pub struct RestartOptions {
+	/// The arguments of the new process, excluding the binary path.
+	///
+	/// Use `env.args_os.iter().skip(1).cloned().collect()` to keep the current
+	/// ones, [`Env::args_os`] starts with the binary path.
+	pub args:Vec<OsString>,
+	/// The environment variables set on the new process, in addition to the
+	/// ones of the current process.
+	pub env:Vec<(OsString, OsString)>,
+	/// Whether the new process runs with elevated privileges.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The user is prompted by UAC to run the app as an
+	///   administrator. The elevated process does not inherit the environment,
+	///   so `env` must be empty.
+	/// - **Linux / macOS / iOS / Android:** Unsupported.
+	pub elevate:bool,
+}
+
 /// Restarts the currently running binary.
 ///
 /// See [`current_binary`] for platform specific behavior, and
@@ -73,58 +96,228 @@ pub fn current_binary(_env:&Env) -> std::io::Result<PathBuf> {
 /// });
 /// ```
 pub fn restart(env:&Env) -> ! {
-	use std::process::{Command, exit};
-
-	if let Ok(path) = current_binary(env) {
-		// on macOS on updates the binary name might have changed
-		// so we'll read the Contents/Info.plist file to determine the binary path
-		#[cfg(target_os = "macos")]
-		restart_macos_app(&path, env);
-
-		if let Err(e) =
-			Command::new(path).args(env.args_os.iter().skip(1).collect::<Vec<_>>()).spawn()
-		{
-			log::error!("failed to restart app: {e}");
-		}
+	let options =
+		RestartOptions { args:env.args_os.iter().skip(1).cloned().collect(), ..Default::default() };
+
+	if let Err(e) = spawn_replacement(env, &options) {
+		log::error!("failed to restart app: {e}");
 	}
 
-	exit(0);
+	std::process::exit(0);
+}
+
+/// Spawns the process replacing the currently running binary.
+pub(crate) fn spawn_replacement(env:&Env, options:&RestartOptions) -> std::io::Result<()> {
+	let path = current_binary(env)?;
+
+	// on macOS on updates the binary name might have changed
+	// so we'll read the Contents/Info.plist file to determine the binary path
+	#[cfg(target_os = "macos")]
+	let path = macos_app_binary(&path).unwrap_or(path);
+
+	#[cfg(windows)]
+	if options.elevate {
+		return spawn_elevated(&path, options);
+	}
+
+	replacement_command(path, options).spawn().map(|_| ())
+}
+
+/// Builds the command spawning the binary at `path` with the given options,
+/// unless they require elevated privileges.
+fn replacement_command(path:PathBuf, options:&RestartOptions) -> std::process::Command {
+	let mut command = std::process::Command::new(path);
+
+	command.args(&options.args).envs(options.env.iter().cloned());
+
+	command
 }
 
 #[cfg(target_os = "macos")]
-fn restart_macos_app(current_binary:&std::path::Path, env:&Env) {
-	use std::process::{Command, exit};
-
-	if let Some(macos_directory) = current_binary.parent() {
-		if macos_directory.components().last()
-			!= Some(std::path::Component::Normal(std::ffi::OsStr::new("MacOS")))
-		{
-			return;
+fn macos_app_binary(current_binary:&std::path::Path) -> Option<PathBuf> {
+	let macos_directory = current_binary.parent()?;
+
+	if macos_directory.components().last()
+		!= Some(std::path::Component::Normal(std::ffi::OsStr::new("MacOS")))
+	{
+		return None;
+	}
+
+	let contents_directory = macos_directory.parent()?;
+
+	if contents_directory.components().last()
+		!= Some(std::path::Component::Normal(std::ffi::OsStr::new("Contents")))
+	{
+		return None;
+	}
+
+	let info_plist =
+		plist::from_file::<_, plist::Dictionary>(contents_directory.join("Info.plist")).ok()?;
+
+	let binary_name = info_plist.get("CFBundleExecutable").and_then(|v| v.as_string())?;
+
+	Some(macos_directory.join(binary_name))
+}
+
+#[cfg(windows)]
+fn spawn_elevated(path:&std::path::Path, options:&RestartOptions) -> std::io::Result<()> {
+	use windows::{
+		Win32::{
+			Foundation::HWND,
+			UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOWNORMAL},
+		},
+		core::{HSTRING, PCWSTR, w},
+	};
+
+	if !options.env.is_empty() {
+		return Err(std::io::Error::new(
+			std::io::ErrorKind::InvalidInput,
+			"environment variables cannot be passed to an elevated process",
+		));
+	}
+
+	let file = HSTRING::from(path.as_os_str());
+
+	let parameters = HSTRING::from(
+		options
+			.args
+			.iter()
+			.map(|arg| quote_argument(&arg.to_string_lossy()))
+			.collect::<Vec<_>>()
+			.join(" "),
+	);
+
+	let result = unsafe {
+		ShellExecuteW(
+			HWND::default(),
+			w!("runas"),
+			&file,
+			&parameters,
+			PCWSTR::null(),
+			SW_SHOWNORMAL,
+		)
+	};
+
+	// values greater than 32 indicate success
+	if result.0 as isize > 32 { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+}
+
+/// Quotes the argument so `CommandLineToArgvW` parses it back as is.
+#[cfg(windows)]
+fn quote_argument(argument:&str) -> String {
+	if !argument.is_empty() && !argument.contains([' ', '\t', '\n', '\u{b}', '"']) {
+		return argument.to_string();
+	}
+
+	let mut quoted = String::from('"');
+
+	let mut backslashes = 0;
+
+	for c in argument.chars() {
+		match c {
+			'\\' => backslashes += 1,
+			'"' => {
+				// the backslashes preceding a quote are escaped, as well as the quote
+				quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+				quoted.push('"');
+				backslashes = 0;
+			},
+			c => {
+				quoted.push_str(&"\\".repeat(backslashes));
+				quoted.push(c);
+				backslashes = 0;
+			},
 		}
+	}
+
+	// the closing quote must not be escaped
+	quoted.push_str(&"\\".repeat(backslashes * 2));
+
+	quoted.push('"');
+
+	quoted
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		ffi::OsStr,
+		sync::{
+			Arc,
+			atomic::{AtomicUsize, Ordering},
+		},
+	};
+
+	use super::{RestartOptions, replacement_command};
+	use crate::test::{mock_builder, mock_context, noop_assets};
+
+	#[test]
+	fn replacement_command_keeps_the_arguments_and_environment() {
+		let options = RestartOptions {
+			args:vec!["--migrated".into(), "two words".into()],
+			env:vec![("APP_MIGRATION".into(), "1".into())],
+			elevate:false,
+		};
+
+		let command = replacement_command("app".into(), &options);
+
+		assert_eq!(command.get_program(), "app");
+
+		assert_eq!(command.get_args().collect::<Vec<_>>(), ["--migrated", "two words"]);
+
+		assert_eq!(
+			command.get_envs().collect::<Vec<_>>(),
+			[(OsStr::new("APP_MIGRATION"), Some(OsStr::new("1")))]
+		);
+	}
 
-		if let Some(contents_directory) = macos_directory.parent() {
-			if contents_directory.components().last()
-				!= Some(std::path::Component::Normal(std::ffi::OsStr::new("Contents")))
-			{
-				return;
-			}
-
-			if let Ok(info_plist) =
-				plist::from_file::<_, plist::Dictionary>(contents_directory.join("Info.plist"))
-			{
-				if let Some(binary_name) =
-					info_plist.get("CFBundleExecutable").and_then(|v| v.as_string())
-				{
-					if let Err(e) = Command::new(macos_directory.join(binary_name))
-						.args(env.args_os.iter().skip(1).collect::<Vec<_>>())
-						.spawn()
-					{
-						log::error!("failed to restart app: {e}");
-					}
-
-					exit(0);
+	#[test]
+	fn failed_restarts_keep_the_app_running() {
+		let hooks_ran = Arc::new(AtomicUsize::new(0));
+
+		let hooks_ran_ = hooks_ran.clone();
+
+		let app = mock_builder()
+			.on_shutdown(move |_| {
+				let hooks_ran = hooks_ran_.clone();
+
+				async move {
+					hooks_ran.fetch_add(1, Ordering::SeqCst);
 				}
-			}
-		}
+			})
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		// the nul byte fails the spawn on every platform
+		let options = RestartOptions { args:vec!["--invalid\0".into()], ..Default::default() };
+
+		assert!(app.handle().restart_with(options).is_err());
+
+		#[cfg(not(windows))]
+		assert!(matches!(
+			app.handle().restart_with(RestartOptions { elevate:true, ..Default::default() }),
+			Err(crate::Error::ElevationUnsupported)
+		));
+
+		assert_eq!(hooks_ran.load(Ordering::SeqCst), 0);
+
+		// the hooks still run once the app exits
+		app.cleanup_before_exit();
+
+		assert_eq!(hooks_ran.load(Ordering::SeqCst), 1);
+	}
+
+	#[cfg(windows)]
+	#[test]
+	fn arguments_are_quoted_for_command_line_to_argv() {
+		use super::quote_argument;
+
+		assert_eq!(quote_argument("--migrated"), "--migrated");
+
+		assert_eq!(quote_argument(""), r#""""#);
+
+		assert_eq!(quote_argument(r"C:\Program Files\"), r#""C:\Program Files\\""#);
+
+		assert_eq!(quote_argument(r#"say "hi""#), r#""say \"hi\"""#);
 	}
 }
@@ -9,6 +9,7 @@ use serialize_to_javascript::Template;
 use tauri_utils::{
 	assets::EmbeddedAssets,
 	config::{Csp, HeaderAddition},
+	pattern::isolation::KEY_ROTATION_GRACE_PERIOD,
 };
 
 use crate::{
@@ -18,11 +19,13 @@ use crate::{
 	webview::UriSchemeProtocolHandler,
 };
 
+/// The path the isolation application fetches the rotated keys from.
+const KEY_PATH:&str = "/__tauri_isolation_key__";
+
 pub fn get<R:Runtime>(
 	manager:Arc<AppManager<R>>,
 	schema:&str,
 	assets:Arc<EmbeddedAssets>,
-	aes_gcm_key:[u8; 32],
 	window_origin:String,
 	use_https_scheme:bool,
 ) -> UriSchemeProtocolHandler {
@@ -36,7 +39,28 @@ pub fn get<R:Runtime>(
 
 	let assets = assets as Arc<dyn Assets<R>>;
 
+	let isolation_origin = crate::pattern::format_real_schema(schema, use_https_scheme);
+
+	// refresh often enough that a replaced key is never used past its grace period,
+	// the interval being at least a second
+	let key_refresh_interval_ms = manager
+		.isolation_keys
+		.as_ref()
+		.and_then(|keys| keys.rotation_interval())
+		.map(|interval| interval.min(KEY_ROTATION_GRACE_PERIOD).as_millis() as u64 / 2);
+
+	let default_csp = if key_refresh_interval_ms.is_some() {
+		format!("default-src 'none'; frame-src {frame_src}; connect-src {frame_src}")
+	} else {
+		format!("default-src 'none'; frame-src {frame_src}")
+	};
+
 	Box::new(move |_, request, responder| {
+		if request.uri().path() == KEY_PATH {
+			responder.respond(key_response(&manager, &request, &isolation_origin));
+			return;
+		}
+
 		let response = match request_to_path(&request).as_str() {
 			"index.html" => {
 				match assets.get(&"index.html".into()) {
@@ -47,14 +71,25 @@ pub fn get<R:Runtime>(
 							&assets,
 							&"index.html".into(),
 							&manager,
-							Csp::Policy(format!("default-src 'none'; frame-src {}", frame_src)),
+							Csp::Policy(default_csp.clone()),
 						);
 						let csp = Csp::DirectiveMap(csp_map).to_string();
 
+						let keys = manager.isolation_keys.as_ref().map(|keys| keys.current());
+
+						let keys = match keys {
+							Some(Ok(keys)) => keys,
+							_ => {
+								responder.respond(internal_error());
+								return;
+							},
+						};
+
 						let template =
 							tauri_utils::pattern::isolation::IsolationJavascriptRuntime {
-								runtime_aes_gcm_key:&aes_gcm_key,
+								runtime_aes_gcm_key:keys.aes_gcm().raw(),
 								origin:window_origin.clone(),
+								key_refresh_interval_ms,
 								process_ipc_message_fn:PROCESS_IPC_MESSAGE_FN,
 							};
 						match template.render(asset.as_ref(), &Default::default()) {
@@ -95,16 +130,56 @@ pub fn get<R:Runtime>(
 		if let Ok(r) = response {
 			responder.respond(r);
 		} else {
-			responder.respond(
-				http::Response::builder()
-					.status(http::StatusCode::INTERNAL_SERVER_ERROR)
-					.body("failed to get response".as_bytes().to_vec())
-					.unwrap(),
-			);
+			responder.respond(internal_error());
 		}
 	})
 }
 
+/// Serves the current keys to the isolation application.
+///
+/// The keys are only readable by the isolation origin, cross-origin requests
+/// (such as the ones made by the main frame) are rejected.
+fn key_response<R:Runtime>(
+	manager:&AppManager<R>,
+	request:&http::Request<Vec<u8>>,
+	isolation_origin:&str,
+) -> http::Response<Vec<u8>> {
+	if is_cross_origin(request, isolation_origin) {
+		return http::Response::builder()
+			.status(http::StatusCode::FORBIDDEN)
+			.header(CONTENT_TYPE, mime::TEXT_PLAIN.as_ref())
+			.body(Vec::new())
+			.unwrap();
+	}
+
+	match manager.isolation_keys.as_ref().map(|keys| keys.current()) {
+		Some(Ok(keys)) => {
+			http::Response::builder()
+				.header(CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())
+				.header(http::header::CACHE_CONTROL, "no-store")
+				.body(serde_json::to_vec(keys.aes_gcm().raw()).unwrap_or_default())
+				.unwrap()
+		},
+		_ => internal_error(),
+	}
+}
+
+/// Whether the request comes from another origin than the isolation
+/// application, the same-origin requests not always having an `Origin` header.
+fn is_cross_origin(request:&http::Request<Vec<u8>>, isolation_origin:&str) -> bool {
+	request
+		.headers()
+		.get(http::header::ORIGIN)
+		.is_some_and(|origin| origin.to_str().map_or(true, |origin| origin != isolation_origin))
+}
+
+fn internal_error() -> http::Response<Vec<u8>> {
+	http::Response::builder()
+		.status(http::StatusCode::INTERNAL_SERVER_ERROR)
+		.body("failed to get response".as_bytes().to_vec())
+		.unwrap()
+}
+
 fn request_to_path(request:&http::Request<Vec<u8>>) -> String {
 	let path = request.uri().path().trim_start_matches('/').trim_end_matches('/');
 
@@ -120,3 +195,32 @@ fn request_to_path(request:&http::Request<Vec<u8>>) -> String {
 		path.chars().skip(1).collect()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::is_cross_origin;
+
+	fn request(origin:Option<&str>) -> http::Request<Vec<u8>> {
+		let mut request =
+			http::Request::builder().uri("isolation://localhost/__tauri_isolation_key__");
+
+		if let Some(origin) = origin {
+			request = request.header(http::header::ORIGIN, origin);
+		}
+
+		request.body(Vec::new()).unwrap()
+	}
+
+	#[test]
+	fn keys_are_only_served_to_the_isolation_origin() {
+		let origin = "https://isolation.localhost";
+
+		assert!(!is_cross_origin(&request(None), origin));
+
+		assert!(!is_cross_origin(&request(Some(origin)), origin));
+
+		for other in ["https://isolation.localhost.evil", "http://isolation.localhost", "null"] {
+			assert!(is_cross_origin(&request(Some(other)), origin), "{other} was accepted");
+		}
+	}
+}
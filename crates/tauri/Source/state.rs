@@ -3,13 +3,26 @@
 // SPDX-License-Identifier: MIT
 
 use std::{
-	any::{Any, TypeId},
+	any::{Any, TypeId, type_name},
 	cell::UnsafeCell,
-	collections::HashMap,
+	collections::{HashMap, HashSet},
+	future::Future,
 	hash::BuildHasherDefault,
-	sync::Mutex,
+	marker::PhantomData,
+	sync::{
+		Arc,
+		Mutex,
+		Weak,
+		atomic::{AtomicBool, Ordering},
+	},
 };
 
+use futures_util::{
+	FutureExt,
+	future::{AbortHandle, BoxFuture, Shared},
+};
+use serde_json::Value as JsonValue;
+
 use crate::{
 	Runtime,
 	ipc::{CommandArg, CommandItem, InvokeError},
@@ -17,15 +30,28 @@ use crate::{
 
 /// A guard for a state value.
 ///
+/// The value stays alive as long as a guard holds it, even if it is
+/// [unmanaged](`crate::Manager::unmanage`).
+///
 /// See [`Manager::manage`](`crate::Manager::manage`) for usage examples.
-pub struct State<'r, T:Send + Sync + 'static>(&'r T);
+pub struct State<'r, T:Send + Sync + 'static>(&'r T, Arc<T>, &'r StateManager);
 
 impl<'r, T:Send + Sync + 'static> State<'r, T> {
 	/// Retrieve a borrow to the underlying value with a lifetime of `'r`.
 	/// Using this method is typically unnecessary as `State` implements
 	/// [`std::ops::Deref`] with a [`std::ops::Deref::Target`] of `T`.
+	///
+	/// The borrow can outlive the guard, so once it is taken the value is never
+	/// dropped before the state manager, even if it is
+	/// [replaced](`crate::Manager::replace_state`) or
+	/// [unmanaged](`crate::Manager::unmanage`). Every value is only kept once,
+	/// but prefer [`std::ops::Deref`] for the states replaced over and over.
 	#[inline(always)]
-	pub fn inner(&self) -> &'r T { self.0 }
+	pub fn inner(&self) -> &'r T {
+		self.2.pin(&self.1);
+
+		self.0
+	}
 }
 
 impl<T:Send + Sync + 'static> std::ops::Deref for State<'_, T> {
@@ -36,7 +62,7 @@ impl<T:Send + Sync + 'static> std::ops::Deref for State<'_, T> {
 }
 
 impl<T:Send + Sync + 'static> Clone for State<'_, T> {
-	fn clone(&self) -> Self { State(self.0) }
+	fn clone(&self) -> Self { State(self.0, Arc::clone(&self.1), self.2) }
 }
 
 impl<T:Send + Sync + 'static + PartialEq> PartialEq for State<'_, T> {
@@ -50,12 +76,82 @@ impl<T:Send + Sync + std::fmt::Debug> std::fmt::Debug for State<'_, T> {
 }
 
 impl<'r, 'de:'r, T:Send + Sync + 'static, R:Runtime> CommandArg<'de, R> for State<'r, T> {
-	/// Grabs the [`State`] from the [`CommandItem`]. This will never fail.
+	/// Grabs the [`State`] from the [`CommandItem`].
+	///
+	/// Fails if the state is not managed, or if it is managed with
+	/// [`Manager::manage_async`](`crate::Manager::manage_async`) and its
+	/// initialization failed or, on synchronous commands, is not finished.
 	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
-		command.message.state_ref().try_get().ok_or_else(|| {
+		let state = command.message.state_ref();
+
+		match state.initialization::<T>() {
+			Some(Some(Err(error))) => return Err(InvokeError(error)),
+			Some(None) => {
+				return Err(InvokeError::from_anyhow(anyhow::anyhow!(
+					"state for field `{}` on command `{}` is still initializing. Make the command \
+					 async to wait for it, or await `Manager::state_async` if its type uses the \
+					 generic parameters of the command",
+					command.key,
+					command.name
+				)));
+			},
+			_ => {},
+		}
+
+		state.try_get().ok_or_else(|| {
 			InvokeError::from_anyhow(anyhow::anyhow!(
-				"state not managed for field `{}` on command `{}`. You must call `.manage()` \
-				 before using this command",
+				"{} for field `{}` on command `{}`. You must call `.manage()` before using this \
+				 command",
+				state.missing_reason::<T>(),
+				command.key,
+				command.name
+			))
+		})
+	}
+}
+
+/// A guard for a state value scoped to the window invoking a command.
+///
+/// See [`Window::manage_scoped`](`crate::Window::manage_scoped`) for usage
+/// examples.
+pub struct WindowState<'r, T:Send + Sync + 'static>(Arc<T>, PhantomData<&'r T>);
+
+impl<T:Send + Sync + 'static> WindowState<'_, T> {
+	pub(crate) fn new(state:Arc<T>) -> Self { Self(state, PhantomData) }
+
+	/// Retrieve the shared pointer to the underlying value, which stays valid
+	/// after the window is destroyed.
+	pub fn arc(&self) -> Arc<T> { Arc::clone(&self.0) }
+}
+
+impl<T:Send + Sync + 'static> std::ops::Deref for WindowState<'_, T> {
+	type Target = T;
+
+	#[inline(always)]
+	fn deref(&self) -> &T { &self.0 }
+}
+
+impl<T:Send + Sync + 'static> Clone for WindowState<'_, T> {
+	fn clone(&self) -> Self { WindowState(Arc::clone(&self.0), PhantomData) }
+}
+
+impl<T:Send + Sync + std::fmt::Debug> std::fmt::Debug for WindowState<'_, T> {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_tuple("WindowState").field(&self.0).finish()
+	}
+}
+
+impl<'r, 'de:'r, T:Send + Sync + 'static, R:Runtime> CommandArg<'de, R> for WindowState<'r, T> {
+	/// Grabs the state scoped to the window of the webview invoking the
+	/// command, failing if the window doesn't manage it.
+	fn from_command(command:CommandItem<'de, R>) -> Result<Self, InvokeError> {
+		let window = command.message.webview_ref().window();
+
+		window.state.try_get_arc().map(WindowState::new).ok_or_else(|| {
+			InvokeError::from_anyhow(anyhow::anyhow!(
+				"window state not managed by window `{}` for field `{}` on command `{}`. You must \
+				 call `.manage_scoped()` on the window before using this command",
+				window.label(),
 				command.key,
 				command.name
 			))
@@ -87,10 +183,64 @@ impl std::hash::Hasher for IdentHash {
 
 type TypeIdMap = HashMap<TypeId, Box<dyn Any>, BuildHasherDefault<IdentHash>>;
 
+/// Identifies a managed value.
+fn address<T>(value:&Arc<T>) -> usize { Arc::as_ptr(value) as *const () as usize }
+
+/// The initialization of a state managed with
+/// [`Manager::manage_async`](`crate::Manager::manage_async`), resolving to the
+/// serialized error if it failed.
+#[derive(Clone)]
+struct Initialization {
+	future:Shared<BoxFuture<'static, Result<(), JsonValue>>>,
+	started:Arc<AtomicBool>,
+	/// Identifies the initialization, so a value is only managed while its
+	/// initialization is still registered.
+	token:Arc<()>,
+	abort:AbortHandle,
+}
+
+impl Initialization {
+	fn failed(&self) -> bool { matches!(self.future.peek(), Some(Err(_))) }
+
+	/// Spawns the initialization on the first access to the state, so it keeps
+	/// running even if the access doesn't wait for it.
+	fn start(&self) {
+		if !self.started.swap(true, Ordering::AcqRel) {
+			let future = self.future.clone();
+
+			crate::async_runtime::spawn(async move {
+				let _ = future.await;
+			});
+		}
+	}
+}
+
+impl std::fmt::Debug for Initialization {
+	fn fmt(&self, f:&mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Initialization")
+			.field("result", &self.future.peek())
+			.field("started", &self.started.load(Ordering::Acquire))
+			.finish()
+	}
+}
+
 /// The Tauri state manager.
+///
+/// Every value is stored in an [`Arc`], so the [`State`] guards borrowing it
+/// stay valid when it is replaced.
 #[derive(Debug)]
 pub struct StateManager {
 	map:Mutex<UnsafeCell<TypeIdMap>>,
+	/// The values borrowed with [`State::inner`] by address, kept until the
+	/// state manager is dropped since the borrows may outlive their guards, even
+	/// once the values are replaced or unmanaged.
+	pinned:Mutex<HashMap<usize, Box<dyn Any + Send + Sync>>>,
+	/// The asynchronously initialized values that are not managed yet, or whose
+	/// initialization failed.
+	initializations:Mutex<HashMap<TypeId, Initialization, BuildHasherDefault<IdentHash>>>,
+	/// The types removed with [`Self::unmanage`] and not managed again, to
+	/// explain why reading them fails.
+	unmanaged:Mutex<HashSet<TypeId, BuildHasherDefault<IdentHash>>>,
 }
 
 // SAFETY: data is accessed behind a lock
@@ -98,7 +248,14 @@ unsafe impl Sync for StateManager {}
 unsafe impl Send for StateManager {}
 
 impl StateManager {
-	pub(crate) fn new() -> Self { Self { map:Default::default() } }
+	pub(crate) fn new() -> Self {
+		Self {
+			map:Default::default(),
+			pinned:Default::default(),
+			initializations:Default::default(),
+			unmanaged:Default::default(),
+		}
+	}
 
 	fn with_map_ref<'a, F:FnOnce(&'a TypeIdMap) -> R, R>(&'a self, f:F) -> R {
 		let map = self.map.lock().unwrap();
@@ -116,35 +273,263 @@ impl StateManager {
 		f(map)
 	}
 
-	pub(crate) fn set<T:Send + Sync + 'static>(&self, state:T) -> bool {
+	pub(crate) fn set<T:Send + Sync + 'static>(&self, state:T) -> bool { self.try_set(state).is_ok() }
+
+	/// Manages the state, giving it back if the type is already managed.
+	pub(crate) fn try_set<T:Send + Sync + 'static>(&self, state:T) -> Result<(), T> {
+		let mut initializations = self.initializations.lock().unwrap();
+
+		if initializations.get(&TypeId::of::<T>()).is_some_and(|i| !i.failed()) {
+			return Err(state);
+		}
+
 		self.with_map_mut(|map| {
 			let type_id = TypeId::of::<T>();
-			let already_set = map.contains_key(&type_id);
-			if !already_set {
-				map.insert(type_id, Box::new(state) as Box<dyn Any>);
+			if map.contains_key(&type_id) {
+				return Err(state);
+			}
+			map.insert(type_id, Box::new(Arc::new(state)) as Box<dyn Any>);
+			Ok(())
+		})?;
+
+		// replaces a failed initialization
+		initializations.remove(&TypeId::of::<T>());
+
+		self.unmanaged.lock().unwrap().remove(&TypeId::of::<T>());
+
+		Ok(())
+	}
+
+	/// Manages the value the future resolves to, returning `false` if the type
+	/// is already managed.
+	///
+	/// The future is only spawned on the [`async_runtime`](crate::async_runtime)
+	/// by the first access to the state, see [`Initialization::start`]. When it
+	/// fails, its error is kept and returned by every access to the state until
+	/// the type is managed again.
+	pub(crate) fn set_async<T, E, F>(self:&Arc<Self>, init:F) -> bool
+	where
+		T: Send + Sync + 'static,
+		E: Into<InvokeError>,
+		F: Future<Output = Result<T, E>> + Send + 'static, {
+		let type_id = TypeId::of::<T>();
+
+		let mut initializations = self.initializations.lock().unwrap();
+
+		if initializations.get(&type_id).is_some_and(|i| !i.failed())
+			|| self.with_map_ref(|map| map.contains_key(&type_id))
+		{
+			return false;
+		}
+
+		let manager:Weak<Self> = Arc::downgrade(self);
+
+		let token = Arc::new(());
+
+		let token_ = Arc::downgrade(&token);
+
+		let initialization = async move {
+			let state = init.await.map_err(|e| e.into().0)?;
+
+			if let Some(manager) = manager.upgrade() {
+				let mut initializations = manager.initializations.lock().unwrap();
+
+				// unmanaged or managed again while initializing
+				let registered = initializations.get(&TypeId::of::<T>()).is_some_and(|i| {
+					token_.upgrade().is_some_and(|token| Arc::ptr_eq(&i.token, &token))
+				});
+
+				if !registered {
+					return Err(JsonValue::String(format!(
+						"state `{}` was unmanaged",
+						type_name::<T>()
+					)));
+				}
+
+				// the value must be managed before the initialization is removed,
+				// see `Self::initialization`
+				manager.with_map_mut(|map| {
+					map.insert(TypeId::of::<T>(), Box::new(Arc::new(state)) as Box<dyn Any>)
+				});
+
+				initializations.remove(&TypeId::of::<T>());
 			}
-			!already_set
+
+			Ok(())
+		};
+
+		let (initialization, abort) = futures_util::future::abortable(initialization);
+
+		let initialization = initialization.map(|result| {
+			result.unwrap_or_else(|_| {
+				Err(JsonValue::String(format!("state `{}` was unmanaged", type_name::<T>())))
+			})
+		});
+
+		initializations.insert(
+			type_id,
+			Initialization {
+				future:initialization.boxed().shared(),
+				started:Default::default(),
+				token,
+				abort,
+			},
+		);
+
+		self.unmanaged.lock().unwrap().remove(&type_id);
+
+		true
+	}
+
+	/// The status of the asynchronous initialization of the state, starting it
+	/// if needed: `None` if it isn't initialized asynchronously or is already
+	/// managed, `Some(None)` while it is running.
+	fn initialization<T:Send + Sync + 'static>(&self) -> Option<Option<Result<(), JsonValue>>> {
+		self.initializations.lock().unwrap().get(&TypeId::of::<T>()).map(|initialization| {
+			initialization.start();
+
+			initialization.future.peek().cloned()
 		})
 	}
 
+	/// Waits for the asynchronous initialization of the state, if any.
+	///
+	/// Resolves immediately for the states managed synchronously.
+	pub(crate) fn ready<T:Send + Sync + 'static>(
+		&self,
+	) -> impl Future<Output = Result<(), InvokeError>> + Send + 'static {
+		let initialization =
+			self.initializations.lock().unwrap().get(&TypeId::of::<T>()).cloned();
+
+		async move {
+			match initialization {
+				Some(initialization) => {
+					initialization.start();
+
+					initialization.future.await.map_err(InvokeError)
+				},
+				None => Ok(()),
+			}
+		}
+	}
+
+	/// Swaps the managed value of the type, returning the previous one.
+	///
+	/// The previous value is kept alive as long as the [`State`] guards obtained
+	/// before the swap, or as long as the state manager if it was borrowed with
+	/// [`State::inner`].
+	pub(crate) fn replace<T:Send + Sync + 'static>(&self, state:T) -> Option<Arc<T>> {
+		let previous = self.with_map_mut(|map| {
+			map.insert(TypeId::of::<T>(), Box::new(Arc::new(state)) as Box<dyn Any>)
+		})?;
+
+		// the guards hold their own reference, the last one drops the value
+		previous.downcast::<Arc<T>>().ok().map(|previous| *previous)
+	}
+
+	/// Keeps the value until the state manager is dropped.
+	fn pin<T:Send + Sync + 'static>(&self, value:&Arc<T>) {
+		self.pinned
+			.lock()
+			.unwrap()
+			.entry(address(value))
+			.or_insert_with(|| Box::new(Arc::clone(value)));
+	}
+
+	/// Removes the state of the type, returning it if no [`State`] guard holds
+	/// it anymore and it wasn't borrowed with [`State::inner`]. Otherwise it is
+	/// dropped with the last guard, or with the state manager if it was
+	/// borrowed.
 	pub(crate) fn unmanage<T:Send + Sync + 'static>(&self) -> Option<T> {
-		self.with_map_mut(|map| {
-			let type_id = TypeId::of::<T>();
-			map.remove(&type_id).and_then(|ptr| ptr.downcast().ok().map(|b| *b))
-		})
+		let type_id = TypeId::of::<T>();
+
+		let initialization = self.initializations.lock().unwrap().remove(&type_id);
+
+		// the waiting accesses fail instead of managing the value
+		if let Some(initialization) = &initialization {
+			initialization.abort.abort();
+		}
+
+		let state = self.with_map_mut(|map| map.remove(&type_id));
+
+		if initialization.is_some() || state.is_some() {
+			self.unmanaged.lock().unwrap().insert(type_id);
+		}
+
+		state
+			.and_then(|ptr| ptr.downcast::<Arc<T>>().ok())
+			.and_then(|arc| Arc::try_unwrap(*arc).ok())
+	}
+
+	/// Removes every state, each dropped with its last [`State`] guard, or with
+	/// the state manager if it was borrowed with [`State::inner`].
+	pub(crate) fn clear(&self) {
+		let states = self.with_map_mut(std::mem::take);
+
+		for (_, initialization) in self.initializations.lock().unwrap().drain() {
+			initialization.abort.abort();
+		}
+
+		// outside of the lock, the values may access the state on drop
+		drop(states);
+	}
+
+	/// Why the state of the type cannot be read.
+	pub(crate) fn missing_reason<T:Send + Sync + 'static>(&self) -> String {
+		match self.initialization::<T>() {
+			Some(None) => format!("state `{}` is still initializing", type_name::<T>()),
+			Some(Some(Err(error))) => {
+				format!("state `{}` failed to initialize: {error}", type_name::<T>())
+			},
+			_ if self.unmanaged.lock().unwrap().contains(&TypeId::of::<T>()) => {
+				format!("state `{}` was unmanaged", type_name::<T>())
+			},
+			_ => format!("state `{}` is not managed", type_name::<T>()),
+		}
 	}
 
 	/// Gets the state associated with the specified type.
+	///
+	/// # Panics
+	///
+	/// Panics if the type is not managed, was unmanaged, or is managed
+	/// asynchronously and not initialized yet.
 	pub fn get<T:Send + Sync + 'static>(&self) -> State<'_, T> {
-		self.try_get().expect("state: get() when given type is not managed")
+		self.try_get().unwrap_or_else(|| {
+			let hint = if matches!(self.initialization::<T>(), Some(None)) {
+				"use `state_async` to wait for it"
+			} else {
+				"use `try_state` if it may not be managed"
+			};
+
+			panic!("{}, {hint}", self.missing_reason::<T>())
+		})
 	}
 
-	/// Gets the state associated with the specified type.
-	pub fn try_get<T:Send + Sync + 'static>(&self) -> Option<State<'_, T>> {
+	/// Gets a shared pointer to the state associated with the specified type.
+	pub(crate) fn try_get_arc<T:Send + Sync + 'static>(&self) -> Option<Arc<T>> {
 		self.with_map_ref(|map| {
-			map.get(&TypeId::of::<T>()).and_then(|ptr| ptr.downcast_ref::<T>()).map(State)
+			map.get(&TypeId::of::<T>()).and_then(|ptr| ptr.downcast_ref::<Arc<T>>()).cloned()
 		})
 	}
+
+	/// Gets the state associated with the specified type.
+	///
+	/// Starts the initialization of a state managed asynchronously, returning
+	/// `None` until it is done.
+	pub fn try_get<T:Send + Sync + 'static>(&self) -> Option<State<'_, T>> {
+		let state = self.with_map_ref(|map| {
+			map.get(&TypeId::of::<T>())
+				.and_then(|ptr| ptr.downcast_ref::<Arc<T>>())
+				.map(|arc| State(&**arc, Arc::clone(arc), self))
+		});
+
+		if state.is_none() {
+			self.initialization::<T>();
+		}
+
+		state
+	}
 }
 
 // Ported from https://github.com/SergioBenitez/state/blob/556c1b94db8ce8427a0e72de7983ab5a9af4cc41/tests/main.rs
@@ -156,6 +541,7 @@ mod tests {
 	};
 
 	use super::StateManager;
+	use crate::ipc::InvokeError;
 
 	// Tiny structures to test that dropping works as expected.
 	struct DroppingStruct(Arc<RwLock<bool>>);
@@ -193,6 +579,40 @@ mod tests {
 		assert_eq!(*state.get::<u32>(), 2);
 	}
 
+	#[test]
+	fn unmanaged_state_lives_until_the_last_guard() {
+		let state = StateManager::new();
+
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+		let guard = state.get::<DroppingStruct>();
+
+		// the guard still holds it
+		assert!(state.unmanage::<DroppingStruct>().is_none());
+
+		assert!(state.try_get::<DroppingStruct>().is_none());
+
+		assert!(!*drop_flag.read().unwrap());
+
+		drop(guard);
+
+		assert!(*drop_flag.read().unwrap());
+	}
+
+	#[test]
+	#[should_panic(expected = "was unmanaged")]
+	fn get_unmanaged_state_panics() {
+		let state = StateManager::new();
+
+		assert!(state.set(1u32));
+
+		state.unmanage::<u32>();
+
+		state.get::<u32>();
+	}
+
 	#[test]
 	fn dst_set_get() {
 		let state = StateManager::new();
@@ -272,6 +692,387 @@ mod tests {
 		assert!(!*drop_flag.read().unwrap());
 	}
 
+	#[test]
+	fn try_set_gives_the_state_back() {
+		let state = StateManager::new();
+
+		assert_eq!(state.try_set(1u32), Ok(()));
+
+		assert_eq!(state.try_set(2u32), Err(2));
+
+		assert_eq!(*state.get::<u32>(), 1);
+	}
+
+	#[test]
+	fn replace_keeps_borrowed_state_alive() {
+		let state = StateManager::new();
+
+		assert!(state.replace("first".to_string()).is_none());
+
+		let first = state.get::<String>();
+
+		let previous = state.replace("second".to_string()).unwrap();
+
+		assert_eq!(*previous, "first");
+
+		// the guard obtained before the swap still reads the previous value
+		assert_eq!(*first, "first");
+
+		assert_eq!(*state.get::<String>(), "second");
+	}
+
+	#[test]
+	fn replaced_state_is_dropped_with_its_last_guard() {
+		let state = StateManager::new();
+
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+		let guard = state.get::<DroppingStruct>();
+
+		let other = guard.clone();
+
+		drop(state.replace(DroppingStruct(Arc::new(RwLock::new(false)))));
+
+		drop(guard);
+
+		assert!(!*drop_flag.read().unwrap());
+
+		drop(other);
+
+		assert!(*drop_flag.read().unwrap());
+	}
+
+	#[test]
+	fn replaced_state_is_dropped_with_guards_dropped_concurrently() {
+		let state = StateManager::new();
+
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+		let barrier = std::sync::Barrier::new(8);
+
+		thread::scope(|scope| {
+			let guards = (0..8).map(|_| state.get::<DroppingStruct>()).collect::<Vec<_>>();
+
+			drop(state.replace(DroppingStruct(Arc::new(RwLock::new(false)))));
+
+			for guard in guards {
+				let barrier = &barrier;
+
+				scope.spawn(move || {
+					// a clone made on another thread is dropped with the others
+					let clone = guard.clone();
+
+					barrier.wait();
+
+					drop(guard);
+
+					drop(clone);
+				});
+			}
+		});
+
+		assert!(*drop_flag.read().unwrap());
+	}
+
+	#[test]
+	fn replaced_state_borrowed_with_inner_is_kept() {
+		let state = StateManager::new();
+
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+		let borrowed = state.get::<DroppingStruct>().inner();
+
+		drop(state.replace(DroppingStruct(Arc::new(RwLock::new(false)))));
+
+		// the borrow outlived its guard, so the previous value can't be dropped
+		assert!(!*drop_flag.read().unwrap());
+
+		assert!(Arc::ptr_eq(&borrowed.0, &drop_flag));
+	}
+
+	#[test]
+	fn unmanaged_state_borrowed_with_inner_is_kept() {
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		{
+			let state = StateManager::new();
+
+			assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+			let borrowed = state.get::<DroppingStruct>().inner();
+
+			// the borrow outlived its guard, so the value can't be returned nor dropped
+			assert!(state.unmanage::<DroppingStruct>().is_none());
+
+			assert!(state.try_get::<DroppingStruct>().is_none());
+
+			assert!(!*borrowed.0.read().unwrap());
+
+			assert!(Arc::ptr_eq(&borrowed.0, &drop_flag));
+		}
+
+		// dropped with the state manager
+		assert!(*drop_flag.read().unwrap());
+	}
+
+	#[test]
+	fn inner_pins_each_value_once() {
+		let state = StateManager::new();
+
+		assert!(state.set(1u32));
+
+		for _ in 0..3 {
+			assert_eq!(*state.get::<u32>().inner(), 1);
+		}
+
+		assert_eq!(state.pinned.lock().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn cleared_state_lives_until_the_last_guard() {
+		let state = StateManager::new();
+
+		let drop_flag = Arc::new(RwLock::new(false));
+
+		assert!(state.set(DroppingStruct(drop_flag.clone())));
+
+		assert!(state.set(1u32));
+
+		let guard = state.get::<DroppingStruct>();
+
+		state.clear();
+
+		assert!(state.try_get::<DroppingStruct>().is_none());
+
+		assert!(state.try_get::<u32>().is_none());
+
+		assert!(!*drop_flag.read().unwrap());
+
+		drop(guard);
+
+		assert!(*drop_flag.read().unwrap());
+	}
+
+	#[test]
+	fn concurrent_get_during_replace() {
+		let state = Arc::new(StateManager::new());
+
+		state.set(0usize);
+
+		let readers = (0..8)
+			.map(|_| {
+				let state = Arc::clone(&state);
+
+				thread::spawn(move || {
+					let mut last = 0;
+
+					for _ in 0..1000 {
+						let value = state.get::<usize>();
+
+						// replacements are atomic and never go back in time
+						assert!(*value >= last);
+
+						last = *value;
+					}
+				})
+			})
+			.collect::<Vec<_>>();
+
+		for i in 1..=100usize {
+			assert_eq!(state.replace(i).as_deref(), Some(&(i - 1)));
+		}
+
+		for reader in readers {
+			reader.join().unwrap();
+		}
+
+		assert_eq!(*state.get::<usize>(), 100);
+	}
+
+	#[test]
+	fn async_state_is_initialized_when_awaited() {
+		let state = Arc::new(StateManager::new());
+
+		let (sender, receiver) = tokio::sync::oneshot::channel();
+
+		assert!(state.set_async(async move {
+			receiver.await.unwrap();
+
+			Ok::<_, InvokeError>(1u32)
+		}));
+
+		assert!(!state.set(2u32));
+
+		assert_eq!(state.try_get::<u32>(), None);
+
+		sender.send(()).unwrap();
+
+		crate::async_runtime::block_on(state.ready::<u32>()).unwrap();
+
+		assert_eq!(*state.get::<u32>(), 1);
+	}
+
+	#[test]
+	fn async_state_is_initialized_on_first_access() {
+		let state = Arc::new(StateManager::new());
+
+		let (sender, receiver) = std::sync::mpsc::channel();
+
+		assert!(state.set_async(async move {
+			let _ = sender.send(());
+
+			Ok::<_, InvokeError>(1u32)
+		}));
+
+		// nothing accessed the state yet
+		assert!(receiver.recv_timeout(std::time::Duration::from_millis(50)).is_err());
+
+		assert_eq!(state.try_get::<u32>(), None);
+
+		// started by the access
+		receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+
+		crate::async_runtime::block_on(state.ready::<u32>()).unwrap();
+
+		assert_eq!(*state.get::<u32>(), 1);
+	}
+
+	#[test]
+	#[should_panic(expected = "is still initializing, use `state_async` to wait for it")]
+	fn initializing_state_is_not_reported_as_missing() {
+		let state = Arc::new(StateManager::new());
+
+		assert!(state.set_async(std::future::pending::<Result<u32, InvokeError>>()));
+
+		state.get::<u32>();
+	}
+
+	#[test]
+	fn failed_async_state_keeps_its_error() {
+		let state = Arc::new(StateManager::new());
+
+		assert!(state.set_async(async { Err::<u32, _>("database unreachable") }));
+
+		for _ in 0..2 {
+			let error = crate::async_runtime::block_on(state.ready::<u32>()).unwrap_err();
+
+			assert_eq!(error.0, serde_json::json!("database unreachable"));
+		}
+
+		assert_eq!(state.try_get::<u32>(), None);
+	}
+
+	#[test]
+	fn failed_async_state_can_be_managed_again() {
+		let state = Arc::new(StateManager::new());
+
+		assert!(state.set_async(async { Err::<u32, _>("database unreachable") }));
+
+		assert!(crate::async_runtime::block_on(state.ready::<u32>()).is_err());
+
+		assert!(state.set_async(async { Ok::<_, InvokeError>(1u32) }));
+
+		crate::async_runtime::block_on(state.ready::<u32>()).unwrap();
+
+		assert_eq!(*state.get::<u32>(), 1);
+
+		let state = Arc::new(StateManager::new());
+
+		assert!(state.set_async(async { Err::<u32, _>("database unreachable") }));
+
+		assert!(crate::async_runtime::block_on(state.ready::<u32>()).is_err());
+
+		assert!(state.set(2u32));
+
+		crate::async_runtime::block_on(state.ready::<u32>()).unwrap();
+
+		assert_eq!(*state.get::<u32>(), 2);
+	}
+
+	#[test]
+	fn unmanaged_async_state_is_never_managed() {
+		let state = Arc::new(StateManager::new());
+
+		let (sender, receiver) = tokio::sync::oneshot::channel();
+
+		let (started, initializing) = std::sync::mpsc::channel();
+
+		assert!(state.set_async(async move {
+			started.send(()).unwrap();
+
+			receiver.await.unwrap();
+
+			Ok::<_, InvokeError>(1u32)
+		}));
+
+		let ready = state.ready::<u32>();
+
+		// the initialization is running
+		assert_eq!(state.try_get::<u32>(), None);
+
+		initializing.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+
+		assert!(state.unmanage::<u32>().is_none());
+
+		// resolved after the unmanage, the value is dropped
+		let _ = sender.send(());
+
+		let error = crate::async_runtime::block_on(ready).unwrap_err();
+
+		assert_eq!(error.0, serde_json::json!("state `u32` was unmanaged"));
+
+		assert_eq!(state.try_get::<u32>(), None);
+
+		assert_eq!(state.missing_reason::<u32>(), "state `u32` was unmanaged");
+	}
+
+	#[test]
+	fn async_state_resolved_after_its_unmanage_is_dropped() {
+		let state = Arc::new(StateManager::new());
+
+		let (sender, receiver) = std::sync::mpsc::channel::<()>();
+
+		assert!(state.set_async(async move {
+			// blocks the initialization until the state is unmanaged
+			receiver.recv().unwrap();
+
+			Ok::<_, InvokeError>(1u32)
+		}));
+
+		let initialization = state
+			.initializations
+			.lock()
+			.unwrap()
+			.get(&std::any::TypeId::of::<u32>())
+			.cloned()
+			.unwrap();
+
+		let unmanaged = {
+			let state = Arc::clone(&state);
+
+			thread::spawn(move || {
+				state.unmanage::<u32>();
+
+				let _ = sender.send(());
+			})
+		};
+
+		// polled past the abort
+		let error = crate::async_runtime::block_on(initialization.future).unwrap_err();
+
+		unmanaged.join().unwrap();
+
+		assert_eq!(error, serde_json::json!("state `u32` was unmanaged"));
+
+		assert_eq!(state.try_get::<u32>(), None);
+	}
+
 	// Ensure dropping a type_map drops its contents.
 	#[test]
 	fn drop_inners_on_drop() {
@@ -0,0 +1,1305 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(unused_variables)]
+
+use std::{
+	cell::RefCell,
+	collections::HashMap,
+	fmt,
+	sync::{
+		Arc,
+		Mutex,
+		atomic::{AtomicBool, AtomicU32, Ordering},
+		mpsc::{Receiver, SyncSender, channel, sync_channel},
+	},
+	time::Duration,
+};
+
+use tauri_runtime::{
+	DeviceEventFilter,
+	Error,
+	EventLoopProxy,
+	ExitRequestedEventAction,
+	Icon,
+	InitializationScriptId,
+	ProgressBarState,
+	Rect,
+	ResizeDirection,
+	Result,
+	RunEvent,
+	Runtime,
+	RuntimeHandle,
+	RuntimeInitArgs,
+	UserAttentionType,
+	UserEvent,
+	WebviewDispatch,
+	WebviewEventId,
+	WindowDispatch,
+	WindowEventId,
+	dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+	monitor::Monitor,
+	webview::{
+		Cookie,
+		DataTypes,
+		DetachedWebview,
+		FindOptions,
+		FindResult,
+		InitializationScript,
+		PdfOptions,
+		PendingWebview,
+		ProxyConfig,
+		WebviewLayout,
+	},
+	window::{
+		CursorIcon,
+		DetachedWindow,
+		DetachedWindowWebview,
+		FullscreenMonitor,
+		PendingWindow,
+		RawWindow,
+		WebviewEvent,
+		WindowBuilder,
+		WindowBuilderBase,
+		WindowEvent,
+		WindowId,
+		WindowSizeConstraints,
+	},
+};
+#[cfg(target_os = "macos")]
+use tauri_utils::TitleBarStyle;
+use tauri_utils::{
+	Theme,
+	config::{Color, WindowConfig},
+};
+use url::Url;
+#[cfg(windows)]
+use windows::Win32::Foundation::HWND;
+
+type WindowEventListener = Arc<Mutex<Box<dyn Fn(&WindowEvent) + Send>>>;
+type WebviewEventListener = Arc<Mutex<Box<dyn Fn(&WebviewEvent) + Send>>>;
+
+enum Message {
+	Task(Box<dyn FnOnce() + Send>),
+	CloseWindow(WindowId),
+	DestroyWindow(WindowId),
+	RequestExit(i32),
+}
+
+struct Window {
+	label:String,
+	webviews:Vec<u32>,
+	listeners:Arc<Mutex<HashMap<WindowEventId, WindowEventListener>>>,
+}
+
+#[derive(Clone)]
+pub struct RuntimeContext {
+	is_running:Arc<AtomicBool>,
+	windows:Arc<RefCell<HashMap<WindowId, Window>>>,
+	run_tx:SyncSender<Message>,
+	next_window_id:Arc<AtomicU32>,
+	next_webview_id:Arc<AtomicU32>,
+	next_window_event_id:Arc<AtomicU32>,
+	next_webview_event_id:Arc<AtomicU32>,
+	system_theme:Arc<Mutex<Theme>>,
+	theme:Arc<Mutex<Option<Theme>>>,
+}
+
+// SAFETY: the window map is only used on the main thread, every other access
+// goes through `run_tx`.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Send for RuntimeContext {}
+
+// SAFETY: the window map is only used on the main thread, every other access
+// goes through `run_tx`.
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl Sync for RuntimeContext {}
+
+impl RuntimeContext {
+	fn send_message(&self, message:Message) -> Result<()> {
+		if self.is_running.load(Ordering::Relaxed) {
+			self.run_tx.send(message).map_err(|_| Error::FailedToSendMessage)
+		} else {
+			match message {
+				Message::Task(task) => task(),
+				Message::CloseWindow(id) => {
+					self.close_window(id);
+				},
+				Message::DestroyWindow(id) => {
+					self.destroy_window(id);
+				},
+				Message::RequestExit(_) => {},
+			}
+
+			Ok(())
+		}
+	}
+
+	fn window_listeners(&self, id:WindowId) -> Vec<WindowEventListener> {
+		self.windows
+			.borrow()
+			.get(&id)
+			.map(|window| window.listeners.lock().unwrap().values().cloned().collect())
+			.unwrap_or_default()
+	}
+
+	fn emit_window_event(&self, id:WindowId, event:&WindowEvent) {
+		for listener in self.window_listeners(id) {
+			(listener.lock().unwrap())(event);
+		}
+	}
+
+	/// Emits [`WindowEvent::CloseRequested`] and destroys the window unless
+	/// the close was prevented, returning the label of the destroyed window.
+	fn close_window(&self, id:WindowId) -> Option<String> {
+		let (signal_tx, signal_rx) = channel();
+
+		self.emit_window_event(id, &WindowEvent::CloseRequested { signal_tx });
+
+		if signal_rx.try_iter().any(|prevent| prevent) { None } else { self.destroy_window(id) }
+	}
+
+	/// Removes the window and emits [`WindowEvent::Destroyed`], returning its
+	/// label.
+	fn destroy_window(&self, id:WindowId) -> Option<String> {
+		let listeners = self.window_listeners(id);
+
+		let window = self.windows.borrow_mut().remove(&id)?;
+
+		for listener in listeners {
+			(listener.lock().unwrap())(&WindowEvent::Destroyed);
+		}
+
+		Some(window.label)
+	}
+
+	fn next_window_id(&self) -> WindowId { self.next_window_id.fetch_add(1, Ordering::Relaxed).into() }
+
+	fn next_webview_id(&self) -> u32 { self.next_webview_id.fetch_add(1, Ordering::Relaxed) }
+
+	fn next_window_event_id(&self) -> WindowEventId {
+		self.next_window_event_id.fetch_add(1, Ordering::Relaxed)
+	}
+
+	fn next_webview_event_id(&self) -> WebviewEventId {
+		self.next_webview_event_id.fetch_add(1, Ordering::Relaxed)
+	}
+
+	fn create_window<T:UserEvent>(
+		&self,
+		pending:PendingWindow<T, MockRuntime>,
+	) -> Result<DetachedWindow<T, MockRuntime>> {
+		let id = self.next_window_id();
+
+		let webview = pending.webview.map(|webview| self.detached_webview(id, webview));
+
+		self.windows.borrow_mut().insert(
+			id,
+			Window {
+				label:pending.label.clone(),
+				webviews:webview.iter().map(|w| w.dispatcher.id).collect(),
+				listeners:Default::default(),
+			},
+		);
+
+		let state = MockWindowState { theme:pending.window_builder.theme, ..Default::default() };
+
+		Ok(DetachedWindow {
+			id,
+			label:pending.label,
+			dispatcher:MockWindowDispatcher {
+				id,
+				context:self.clone(),
+				state:Arc::new(Mutex::new(state)),
+			},
+			webview:webview.map(|webview| DetachedWindowWebview { webview, use_https_scheme:false }),
+		})
+	}
+
+	fn create_webview<T:UserEvent>(
+		&self,
+		window_id:WindowId,
+		pending:PendingWebview<T, MockRuntime>,
+	) -> Result<DetachedWebview<T, MockRuntime>> {
+		let webview = self.detached_webview(window_id, pending);
+
+		self.windows
+			.borrow_mut()
+			.get_mut(&window_id)
+			.ok_or(Error::WindowNotFound)?
+			.webviews
+			.push(webview.dispatcher.id);
+
+		Ok(webview)
+	}
+
+	fn detached_webview<T:UserEvent>(
+		&self,
+		window_id:WindowId,
+		pending:PendingWebview<T, MockRuntime>,
+	) -> DetachedWebview<T, MockRuntime> {
+		let attributes = pending.webview_attributes;
+
+		let state = MockWebviewState {
+			url:pending.url,
+			zoom:attributes.zoom.unwrap_or(1.),
+			user_agent:attributes.user_agent.unwrap_or_else(|| "tauri-mock".into()),
+			initialization_scripts:attributes
+				.initialization_scripts
+				.into_iter()
+				.enumerate()
+				.map(|(id, script)| (id as InitializationScriptId, script))
+				.collect(),
+			cookies:attributes.cookies,
+			proxy:attributes.proxy,
+			layout:attributes.layout,
+			bounds:attributes.bounds.unwrap_or_default(),
+			..Default::default()
+		};
+
+		DetachedWebview {
+			label:pending.label,
+			dispatcher:MockWebviewDispatcher {
+				id:self.next_webview_id(),
+				window_id:Arc::new(Mutex::new(window_id)),
+				context:self.clone(),
+				state:Arc::new(Mutex::new(state)),
+				listeners:Default::default(),
+			},
+		}
+	}
+}
+
+impl fmt::Debug for RuntimeContext {
+	fn fmt(&self, f:&mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("RuntimeContext").finish()
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct MockRuntimeHandle {
+	context:RuntimeContext,
+}
+
+impl<T:UserEvent> RuntimeHandle<T> for MockRuntimeHandle {
+	type Runtime = MockRuntime;
+
+	fn create_proxy(&self) -> EventProxy { EventProxy {} }
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn set_activation_policy(
+		&self,
+		activation_policy:tauri_runtime::ActivationPolicy,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	fn request_exit(&self, code:i32) -> Result<()> { self.context.send_message(Message::RequestExit(code)) }
+
+	fn create_window<F:Fn(RawWindow) + Send + 'static>(
+		&self,
+		pending:PendingWindow<T, Self::Runtime>,
+		_after_window_creation:Option<F>,
+	) -> Result<DetachedWindow<T, Self::Runtime>> {
+		self.context.create_window(pending)
+	}
+
+	fn create_webview(
+		&self,
+		window_id:WindowId,
+		pending:PendingWebview<T, Self::Runtime>,
+	) -> Result<DetachedWebview<T, Self::Runtime>> {
+		self.context.create_webview(window_id, pending)
+	}
+
+	fn run_on_main_thread<F:FnOnce() + Send + 'static>(&self, f:F) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(f)))
+	}
+
+	fn display_handle(
+		&self,
+	) -> std::result::Result<raw_window_handle::DisplayHandle, raw_window_handle::HandleError> {
+		Err(raw_window_handle::HandleError::NotSupported)
+	}
+
+	fn primary_monitor(&self) -> Option<Monitor> { None }
+
+	fn monitor_from_point(&self, x:f64, y:f64) -> Option<Monitor> { None }
+
+	fn available_monitors(&self) -> Vec<Monitor> { Vec::new() }
+
+	fn cursor_position(&self) -> Result<PhysicalPosition<f64>> { Ok(PhysicalPosition::new(0.0, 0.0)) }
+
+	fn set_theme(&self, theme:Option<Theme>) { *self.context.theme.lock().unwrap() = theme; }
+
+	fn system_theme(&self) -> Theme { *self.context.system_theme.lock().unwrap() }
+
+	fn set_badge_count(&self, count:Option<u64>, desktop_filename:Option<String>) -> Result<()> { Ok(()) }
+
+	fn set_badge_label(&self, label:Option<String>) -> Result<()> { Ok(()) }
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn show(&self) -> Result<()> { Ok(()) }
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn hide(&self) -> Result<()> { Ok(()) }
+
+	#[cfg(target_os = "android")]
+	fn find_class<'a>(
+		&self,
+		env:&mut jni::JNIEnv<'a>,
+		activity:&jni::objects::JObject<'_>,
+		name:impl Into<String>,
+	) -> std::result::Result<jni::objects::JClass<'a>, jni::errors::Error> {
+		// the mock runtime has no Android activity to load the class with
+		Err(jni::errors::Error::NullPtr("activity"))
+	}
+
+	#[cfg(target_os = "android")]
+	fn run_on_android_context<F>(&self, f:F)
+	where
+		F: FnOnce(&mut jni::JNIEnv, &jni::objects::JObject, &jni::objects::JObject) + Send + 'static, {
+		// there is no Android context to run `f` on
+	}
+}
+
+/// The state of a mock window, changed by its dispatcher.
+#[derive(Debug, Clone)]
+pub struct MockWindowState {
+	pub title:String,
+	pub position:PhysicalPosition<i32>,
+	pub size:PhysicalSize<u32>,
+	pub scale_factor:f64,
+	pub size_constraints:WindowSizeConstraints,
+	pub aspect_ratio:Option<f64>,
+	pub visible:bool,
+	pub focused:bool,
+	pub fullscreen:bool,
+	pub maximized:bool,
+	pub minimized:bool,
+	pub decorated:bool,
+	pub resizable:bool,
+	pub enabled:bool,
+	pub theme:Option<Theme>,
+	pub ignore_cursor_events:bool,
+	pub ignore_cursor_events_region:Option<Vec<Rect>>,
+}
+
+impl Default for MockWindowState {
+	fn default() -> Self {
+		Self {
+			title:String::new(),
+			position:PhysicalPosition::new(0, 0),
+			size:PhysicalSize::new(800, 600),
+			scale_factor:1.0,
+			size_constraints:Default::default(),
+			aspect_ratio:None,
+			visible:true,
+			focused:false,
+			fullscreen:false,
+			maximized:false,
+			minimized:false,
+			decorated:true,
+			resizable:true,
+			enabled:true,
+			theme:None,
+			ignore_cursor_events:false,
+			ignore_cursor_events_region:None,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct MockWindowDispatcher {
+	id:WindowId,
+	context:RuntimeContext,
+	state:Arc<Mutex<MockWindowState>>,
+}
+
+impl MockWindowDispatcher {
+	/// A snapshot of the window state.
+	pub fn state(&self) -> MockWindowState { self.state.lock().unwrap().clone() }
+
+	/// Emits `event` to the listeners of the window, as if the platform
+	/// reported it.
+	pub fn emit(&self, event:WindowEvent) { self.context.emit_window_event(self.id, &event); }
+
+	fn update(&self, f:impl FnOnce(&mut MockWindowState)) -> Result<()> {
+		f(&mut self.state.lock().unwrap());
+
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct MockWindowBuilder {
+	theme:Option<Theme>,
+	has_icon:bool,
+}
+
+impl WindowBuilderBase for MockWindowBuilder {}
+
+impl WindowBuilder for MockWindowBuilder {
+	fn new() -> Self { Self { theme:None, has_icon:false } }
+
+	fn with_config(config:&WindowConfig) -> Self { Self { theme:config.theme, has_icon:false } }
+
+	fn center(self) -> Self { self }
+
+	fn position(self, x:f64, y:f64) -> Self { self }
+
+	fn inner_size(self, min_width:f64, min_height:f64) -> Self { self }
+
+	fn min_inner_size(self, min_width:f64, min_height:f64) -> Self { self }
+
+	fn max_inner_size(self, max_width:f64, max_height:f64) -> Self { self }
+
+	fn inner_size_constraints(self, constraints:WindowSizeConstraints) -> Self { self }
+
+	fn aspect_ratio(self, ratio:f64) -> Self { self }
+
+	fn resizable(self, resizable:bool) -> Self { self }
+
+	fn maximizable(self, resizable:bool) -> Self { self }
+
+	fn minimizable(self, resizable:bool) -> Self { self }
+
+	fn closable(self, resizable:bool) -> Self { self }
+
+	fn title<S:Into<String>>(self, title:S) -> Self { self }
+
+	fn fullscreen(self, fullscreen:bool) -> Self { self }
+
+	fn fullscreen_monitor(self, monitor:FullscreenMonitor) -> Self { self }
+
+	fn focused(self, focused:bool) -> Self { self }
+
+	fn maximized(self, maximized:bool) -> Self { self }
+
+	fn visible(self, visible:bool) -> Self { self }
+
+	#[cfg(any(not(target_os = "macos"), feature = "macos-private-api"))]
+	#[cfg_attr(docsrs, doc(cfg(any(not(target_os = "macos"), feature = "macos-private-api"))))]
+	fn transparent(self, transparent:bool) -> Self { self }
+
+	fn decorations(self, decorations:bool) -> Self { self }
+
+	fn always_on_bottom(self, always_on_bottom:bool) -> Self { self }
+
+	fn always_on_top(self, always_on_top:bool) -> Self { self }
+
+	fn visible_on_all_workspaces(self, visible_on_all_workspaces:bool) -> Self { self }
+
+	fn content_protected(self, protected:bool) -> Self { self }
+
+	fn icon(mut self, icon:Icon) -> Result<Self> {
+		self.has_icon = true;
+
+		Ok(self)
+	}
+
+	fn skip_taskbar(self, skip:bool) -> Self { self }
+
+	fn background_color(self, color:Color) -> Self { self }
+
+	fn shadow(self, enable:bool) -> Self { self }
+
+	#[cfg(windows)]
+	fn owner(self, owner:HWND) -> Self { self }
+
+	#[cfg(windows)]
+	fn parent(self, parent:HWND) -> Self { self }
+
+	#[cfg(target_os = "macos")]
+	fn parent(self, parent:*mut std::ffi::c_void) -> Self { self }
+
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	fn transient_for(self, parent:&impl gtk::glib::IsA<gtk::Window>) -> Self { self }
+
+	#[cfg(windows)]
+	fn drag_and_drop(self, enabled:bool) -> Self { self }
+
+	#[cfg(target_os = "macos")]
+	fn title_bar_style(self, style:TitleBarStyle) -> Self { self }
+
+	#[cfg(target_os = "macos")]
+	fn hidden_title(self, transparent:bool) -> Self { self }
+
+	#[cfg(target_os = "macos")]
+	fn traffic_light_position<P:Into<tauri_runtime::dpi::Position>>(self, position:P) -> Self { self }
+
+	#[cfg(windows)]
+	fn titlebar_overlay(self, enabled:bool) -> Self { self }
+
+	#[cfg(target_os = "macos")]
+	fn tabbing_identifier(self, identifier:&str) -> Self { self }
+
+	fn theme(mut self, theme:Option<Theme>) -> Self {
+		self.theme = theme;
+
+		self
+	}
+
+	fn has_icon(&self) -> bool { self.has_icon }
+
+	fn get_theme(&self) -> Option<Theme> { self.theme }
+
+	fn window_classname<S:Into<String>>(self, classname:S) -> Self { self }
+}
+
+impl<T:UserEvent> WindowDispatch<T> for MockWindowDispatcher {
+	type Runtime = MockRuntime;
+
+	type WindowBuilder = MockWindowBuilder;
+
+	fn run_on_main_thread<F:FnOnce() + Send + 'static>(&self, f:F) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(f)))
+	}
+
+	fn on_window_event<F:Fn(&WindowEvent) + Send + 'static>(&self, f:F) -> WindowEventId {
+		let id = self.context.next_window_event_id();
+
+		if let Some(window) = self.context.windows.borrow().get(&self.id) {
+			window.listeners.lock().unwrap().insert(id, Arc::new(Mutex::new(Box::new(f))));
+		}
+
+		id
+	}
+
+	fn scale_factor(&self) -> Result<f64> { Ok(self.state.lock().unwrap().scale_factor) }
+
+	fn inner_position(&self) -> Result<PhysicalPosition<i32>> { Ok(self.state.lock().unwrap().position) }
+
+	fn outer_position(&self) -> Result<PhysicalPosition<i32>> { Ok(self.state.lock().unwrap().position) }
+
+	fn inner_size(&self) -> Result<PhysicalSize<u32>> { Ok(self.state.lock().unwrap().size) }
+
+	fn outer_size(&self) -> Result<PhysicalSize<u32>> { Ok(self.state.lock().unwrap().size) }
+
+	fn size_constraints(&self) -> Result<WindowSizeConstraints> {
+		Ok(self.state.lock().unwrap().size_constraints)
+	}
+
+	fn is_fullscreen(&self) -> Result<bool> { Ok(self.state.lock().unwrap().fullscreen) }
+
+	fn is_minimized(&self) -> Result<bool> { Ok(self.state.lock().unwrap().minimized) }
+
+	fn is_maximized(&self) -> Result<bool> { Ok(self.state.lock().unwrap().maximized) }
+
+	fn is_focused(&self) -> Result<bool> { Ok(self.state.lock().unwrap().focused) }
+
+	fn is_decorated(&self) -> Result<bool> { Ok(self.state.lock().unwrap().decorated) }
+
+	fn is_resizable(&self) -> Result<bool> { Ok(self.state.lock().unwrap().resizable) }
+
+	fn is_maximizable(&self) -> Result<bool> { Ok(true) }
+
+	fn is_minimizable(&self) -> Result<bool> { Ok(true) }
+
+	fn is_closable(&self) -> Result<bool> { Ok(true) }
+
+	fn is_visible(&self) -> Result<bool> { Ok(self.state.lock().unwrap().visible) }
+
+	fn is_enabled(&self) -> Result<bool> { Ok(self.state.lock().unwrap().enabled) }
+
+	fn title(&self) -> Result<String> { Ok(self.state.lock().unwrap().title.clone()) }
+
+	fn current_monitor(&self) -> Result<Option<Monitor>> { Ok(None) }
+
+	fn primary_monitor(&self) -> Result<Option<Monitor>> { Ok(None) }
+
+	fn monitor_from_point(&self, x:f64, y:f64) -> Result<Option<Monitor>> { Ok(None) }
+
+	fn available_monitors(&self) -> Result<Vec<Monitor>> { Ok(Vec::new()) }
+
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	fn gtk_window(&self) -> Result<gtk::ApplicationWindow> {
+		// the mock windows have no native window
+		Err(Error::WindowNotFound)
+	}
+
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	fn default_vbox(&self) -> Result<gtk::Box> { Err(Error::WindowNotFound) }
+
+	fn window_handle(
+		&self,
+	) -> std::result::Result<raw_window_handle::WindowHandle<'_>, raw_window_handle::HandleError> {
+		Err(raw_window_handle::HandleError::NotSupported)
+	}
+
+	fn theme(&self) -> Result<Theme> {
+		let theme = self.state.lock().unwrap().theme;
+
+		Ok(theme
+			.or(*self.context.theme.lock().unwrap())
+			.unwrap_or(*self.context.system_theme.lock().unwrap()))
+	}
+
+	fn title_bar_rects(&self) -> Result<Vec<Rect>> { Ok(Vec::new()) }
+
+	fn confirm(&self, title:String, message:String) -> Result<bool> { Ok(true) }
+
+	fn center(&self) -> Result<()> { Ok(()) }
+
+	fn request_user_attention(&self, request_type:Option<UserAttentionType>) -> Result<()> { Ok(()) }
+
+	fn create_window<F:Fn(RawWindow) + Send + 'static>(
+		&mut self,
+		pending:PendingWindow<T, Self::Runtime>,
+		_after_window_creation:Option<F>,
+	) -> Result<DetachedWindow<T, Self::Runtime>> {
+		self.context.create_window(pending)
+	}
+
+	fn create_webview(
+		&mut self,
+		pending:PendingWebview<T, Self::Runtime>,
+	) -> Result<DetachedWebview<T, Self::Runtime>> {
+		self.context.create_webview(self.id, pending)
+	}
+
+	fn set_resizable(&self, resizable:bool) -> Result<()> { self.update(|s| s.resizable = resizable) }
+
+	fn set_enabled(&self, enabled:bool) -> Result<()> { self.update(|s| s.enabled = enabled) }
+
+	fn set_maximizable(&self, maximizable:bool) -> Result<()> { Ok(()) }
+
+	fn set_minimizable(&self, minimizable:bool) -> Result<()> { Ok(()) }
+
+	fn set_closable(&self, closable:bool) -> Result<()> { Ok(()) }
+
+	fn set_title<S:Into<String>>(&self, title:S) -> Result<()> {
+		let title = title.into();
+
+		self.update(|s| s.title = title)
+	}
+
+	fn maximize(&self) -> Result<()> { self.update(|s| s.maximized = true) }
+
+	fn unmaximize(&self) -> Result<()> { self.update(|s| s.maximized = false) }
+
+	fn minimize(&self) -> Result<()> { self.update(|s| s.minimized = true) }
+
+	fn unminimize(&self) -> Result<()> { self.update(|s| s.minimized = false) }
+
+	fn show(&self) -> Result<()> { self.update(|s| s.visible = true) }
+
+	fn hide(&self) -> Result<()> { self.update(|s| s.visible = false) }
+
+	fn close(&self) -> Result<()> { self.context.send_message(Message::CloseWindow(self.id)) }
+
+	fn destroy(&self) -> Result<()> { self.context.send_message(Message::DestroyWindow(self.id)) }
+
+	fn set_decorations(&self, decorations:bool) -> Result<()> { self.update(|s| s.decorated = decorations) }
+
+	fn set_shadow(&self, shadow:bool) -> Result<()> { Ok(()) }
+
+	fn set_always_on_bottom(&self, always_on_bottom:bool) -> Result<()> { Ok(()) }
+
+	fn set_always_on_top(&self, always_on_top:bool) -> Result<()> { Ok(()) }
+
+	fn set_visible_on_all_workspaces(&self, visible_on_all_workspaces:bool) -> Result<()> { Ok(()) }
+
+	fn set_background_color(&self, color:Option<Color>) -> Result<()> { Ok(()) }
+
+	fn set_content_protected(&self, protected:bool) -> Result<()> { Ok(()) }
+
+	fn set_size(&self, size:Size) -> Result<()> {
+		self.update(|s| s.size = size.to_physical(s.scale_factor))
+	}
+
+	fn set_min_size(&self, size:Option<Size>) -> Result<()> { Ok(()) }
+
+	fn set_max_size(&self, size:Option<Size>) -> Result<()> { Ok(()) }
+
+	fn set_size_constraints(&self, constraints:WindowSizeConstraints) -> Result<()> {
+		self.update(|s| s.size_constraints = constraints)
+	}
+
+	fn set_aspect_ratio(&self, ratio:Option<f64>) -> Result<()> {
+		self.update(|s| s.aspect_ratio = ratio)
+	}
+
+	fn set_position(&self, position:Position) -> Result<()> {
+		self.update(|s| s.position = position.to_physical(s.scale_factor))
+	}
+
+	fn set_fullscreen(&self, fullscreen:bool) -> Result<()> { self.update(|s| s.fullscreen = fullscreen) }
+
+	fn set_fullscreen_on_monitor(&self, monitor:Monitor) -> Result<()> {
+		self.update(|s| {
+			s.fullscreen = true;
+			s.position = monitor.position;
+		})
+	}
+
+	fn set_focus(&self) -> Result<()> { self.update(|s| s.focused = true) }
+
+	fn set_icon(&self, icon:Icon<'_>) -> Result<()> { Ok(()) }
+
+	fn set_skip_taskbar(&self, skip:bool) -> Result<()> { Ok(()) }
+
+	fn set_cursor_grab(&self, grab:bool) -> Result<()> { Ok(()) }
+
+	fn set_cursor_visible(&self, visible:bool) -> Result<()> { Ok(()) }
+
+	fn set_cursor_icon(&self, icon:CursorIcon) -> Result<()> { Ok(()) }
+
+	fn set_cursor_position<Pos:Into<Position>>(&self, position:Pos) -> Result<()> { Ok(()) }
+
+	fn set_ignore_cursor_events(&self, ignore:bool) -> Result<()> {
+		self.update(|s| s.ignore_cursor_events = ignore)
+	}
+
+	fn set_ignore_cursor_events_region(&self, regions:Option<Vec<Rect>>) -> Result<()> {
+		self.update(|s| s.ignore_cursor_events_region = regions)
+	}
+
+	fn start_dragging(&self) -> Result<()> { Ok(()) }
+
+	fn start_resize_dragging(&self, direction:ResizeDirection) -> Result<()> { Ok(()) }
+
+	fn set_badge_count(&self, count:Option<i64>, desktop_filename:Option<String>) -> Result<()> { Ok(()) }
+
+	fn set_badge_label(&self, label:Option<String>) -> Result<()> { Ok(()) }
+
+	fn set_overlay_icon(&self, icon:Option<Icon<'_>>) -> Result<()> { Ok(()) }
+
+	fn set_progress_bar(&self, progress_state:ProgressBarState) -> Result<()> { Ok(()) }
+
+	fn set_title_bar_style(&self, style:tauri_utils::TitleBarStyle) -> Result<()> { Ok(()) }
+
+	fn set_theme(&self, theme:Option<Theme>) -> Result<()> { self.update(|s| s.theme = theme) }
+}
+
+/// The state of a mock webview, changed by its dispatcher.
+#[derive(Debug, Clone)]
+pub struct MockWebviewState {
+	pub url:String,
+	pub bounds:Rect,
+	pub zoom:f64,
+	pub user_agent:String,
+	pub visible:bool,
+	pub devtools_open:bool,
+	pub auto_resize:bool,
+	pub proxy:Option<ProxyConfig>,
+	pub layout:Option<WebviewLayout>,
+	pub cookies:Vec<Cookie<'static>>,
+	pub initialization_scripts:Vec<(InitializationScriptId, InitializationScript)>,
+	/// Every script evaluated on the webview, in order.
+	pub evaluated_scripts:Vec<String>,
+	pub next_initialization_script_id:InitializationScriptId,
+}
+
+impl Default for MockWebviewState {
+	fn default() -> Self {
+		Self {
+			url:"tauri://localhost".into(),
+			bounds:Rect::default(),
+			zoom:1.,
+			user_agent:String::new(),
+			visible:true,
+			devtools_open:false,
+			auto_resize:false,
+			proxy:None,
+			layout:None,
+			cookies:Vec::new(),
+			initialization_scripts:Vec::new(),
+			evaluated_scripts:Vec::new(),
+			next_initialization_script_id:0,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct MockWebviewDispatcher {
+	id:u32,
+	window_id:Arc<Mutex<WindowId>>,
+	context:RuntimeContext,
+	state:Arc<Mutex<MockWebviewState>>,
+	listeners:Arc<Mutex<HashMap<WebviewEventId, WebviewEventListener>>>,
+}
+
+impl MockWebviewDispatcher {
+	/// A snapshot of the webview state.
+	pub fn state(&self) -> MockWebviewState { self.state.lock().unwrap().clone() }
+
+	/// The last script evaluated on the webview.
+	pub fn last_evaluated_script(&self) -> Option<String> {
+		self.state.lock().unwrap().evaluated_scripts.last().cloned()
+	}
+
+	/// Emits `event` to the listeners of the webview, as if the platform
+	/// reported it.
+	pub fn emit(&self, event:WebviewEvent) {
+		let listeners = self.listeners.lock().unwrap().values().cloned().collect::<Vec<_>>();
+
+		for listener in listeners {
+			(listener.lock().unwrap())(&event);
+		}
+	}
+
+	fn update(&self, f:impl FnOnce(&mut MockWebviewState)) -> Result<()> {
+		f(&mut self.state.lock().unwrap());
+
+		Ok(())
+	}
+}
+
+impl<T:UserEvent> WebviewDispatch<T> for MockWebviewDispatcher {
+	type Runtime = MockRuntime;
+
+	fn run_on_main_thread<F:FnOnce() + Send + 'static>(&self, f:F) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(f)))
+	}
+
+	fn on_webview_event<F:Fn(&WebviewEvent) + Send + 'static>(&self, f:F) -> WebviewEventId {
+		let id = self.context.next_webview_event_id();
+
+		self.listeners.lock().unwrap().insert(id, Arc::new(Mutex::new(Box::new(f))));
+
+		id
+	}
+
+	fn with_webview<F:FnOnce(Box<dyn std::any::Any>) + Send + 'static>(&self, f:F) -> Result<()> {
+		Ok(())
+	}
+
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools(&self) {
+		let _ = self.update(|s| s.devtools_open = true);
+	}
+
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn open_devtools_with(&self, options:tauri_runtime::webview::DevtoolsOptions) -> Result<()> {
+		self.update(|s| s.devtools_open = true)
+	}
+
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn close_devtools(&self) {
+		let _ = self.update(|s| s.devtools_open = false);
+	}
+
+	#[cfg(any(debug_assertions, feature = "devtools"))]
+	fn is_devtools_open(&self) -> Result<bool> { Ok(self.state.lock().unwrap().devtools_open) }
+
+	fn url(&self) -> Result<String> { Ok(self.state.lock().unwrap().url.clone()) }
+
+	fn bounds(&self) -> Result<Rect> { Ok(self.state.lock().unwrap().bounds) }
+
+	fn position(&self) -> Result<PhysicalPosition<i32>> {
+		Ok(self.state.lock().unwrap().bounds.position.to_physical(1.))
+	}
+
+	fn size(&self) -> Result<PhysicalSize<u32>> { Ok(self.state.lock().unwrap().bounds.size.to_physical(1.)) }
+
+	fn zoom(&self) -> Result<f64> { Ok(self.state.lock().unwrap().zoom) }
+
+	fn user_agent(&self) -> Result<String> { Ok(self.state.lock().unwrap().user_agent.clone()) }
+
+	fn memory_usage(&self) -> Result<Option<u64>> { Ok(None) }
+
+	fn can_go_back(&self) -> Result<bool> { Ok(false) }
+
+	fn can_go_forward(&self) -> Result<bool> { Ok(false) }
+
+	fn navigate(&self, url:Url) -> Result<()> { self.update(|s| s.url = url.to_string()) }
+
+	fn go_back(&self) -> Result<()> { Ok(()) }
+
+	fn go_forward(&self) -> Result<()> { Ok(()) }
+
+	fn reload(&self, ignore_cache:bool) -> Result<()> { Ok(()) }
+
+	fn print(&self) -> Result<()> { Ok(()) }
+
+	fn capture<F:FnOnce(Result<Icon<'static>>) + Send + 'static>(
+		&self,
+		region:Option<Rect>,
+		f:F,
+	) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(move || {
+			f(Ok(Icon { rgba:vec![0; 4].into(), width:1, height:1 }))
+		})))
+	}
+
+	fn print_to_pdf<F:FnOnce(Result<Vec<u8>>) + Send + 'static>(
+		&self,
+		options:PdfOptions,
+		f:F,
+	) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(move || f(Ok(b"%PDF-1.4".to_vec())))))
+	}
+
+	fn find_in_page<F:FnOnce(Result<FindResult>) + Send + 'static>(
+		&self,
+		query:String,
+		options:FindOptions,
+		f:F,
+	) -> Result<()> {
+		self.context.send_message(Message::Task(Box::new(move || {
+			f(Ok(FindResult { found:false, match_count:Some(0), active_match_index:None }))
+		})))
+	}
+
+	fn stop_find(&self, clear_highlights:bool) -> Result<()> { Ok(()) }
+
+	fn add_initialization_script(
+		&self,
+		script:InitializationScript,
+	) -> Result<InitializationScriptId> {
+		let mut state = self.state.lock().unwrap();
+
+		let id = state
+			.initialization_scripts
+			.iter()
+			.map(|(id, _)| id + 1)
+			.max()
+			.unwrap_or_default()
+			.max(state.next_initialization_script_id);
+
+		state.next_initialization_script_id = id + 1;
+
+		state.initialization_scripts.push((id, script));
+
+		Ok(id)
+	}
+
+	fn remove_initialization_script(&self, id:InitializationScriptId) -> Result<()> {
+		self.update(|s| s.initialization_scripts.retain(|(script_id, _)| *script_id != id))
+	}
+
+	fn close(&self) -> Result<()> {
+		let window_id = *self.window_id.lock().unwrap();
+
+		if let Some(window) = self.context.windows.borrow_mut().get_mut(&window_id) {
+			window.webviews.retain(|id| *id != self.id);
+		}
+
+		Ok(())
+	}
+
+	fn set_bounds(&self, bounds:Rect) -> Result<()> { self.update(|s| s.bounds = bounds) }
+
+	fn set_size(&self, size:Size) -> Result<()> { self.update(|s| s.bounds.size = size) }
+
+	fn set_position(&self, position:Position) -> Result<()> { self.update(|s| s.bounds.position = position) }
+
+	fn set_focus(&self) -> Result<()> { Ok(()) }
+
+	fn hide(&self) -> Result<()> { self.update(|s| s.visible = false) }
+
+	fn show(&self) -> Result<()> { self.update(|s| s.visible = true) }
+
+	fn eval_script<S:Into<String>>(&self, script:S) -> Result<()> {
+		let script = script.into();
+
+		self.update(|s| s.evaluated_scripts.push(script))
+	}
+
+	fn reparent(&self, window_id:WindowId, bounds:Option<Rect>) -> Result<()> {
+		let mut windows = self.context.windows.borrow_mut();
+
+		if !windows.contains_key(&window_id) {
+			return Err(Error::WindowNotFound);
+		}
+
+		let mut current = self.window_id.lock().unwrap();
+
+		if let Some(window) = windows.get_mut(&current) {
+			window.webviews.retain(|id| *id != self.id);
+		}
+
+		windows.get_mut(&window_id).unwrap().webviews.push(self.id);
+
+		*current = window_id;
+
+		if let Some(bounds) = bounds {
+			self.state.lock().unwrap().bounds = bounds;
+		}
+
+		Ok(())
+	}
+
+	fn set_auto_resize(&self, auto_resize:bool) -> Result<()> { self.update(|s| s.auto_resize = auto_resize) }
+
+	fn set_proxy(&self, proxy:Option<ProxyConfig>) -> Result<()> { self.update(|s| s.proxy = proxy) }
+
+	fn set_layout(&self, layout:Option<WebviewLayout>) -> Result<()> { self.update(|s| s.layout = layout) }
+
+	fn set_zoom(&self, scale_factor:f64) -> Result<()> { self.update(|s| s.zoom = scale_factor) }
+
+	fn set_user_agent(&self, user_agent:String) -> Result<()> { self.update(|s| s.user_agent = user_agent) }
+
+	fn set_background_color(&self, color:Option<Color>) -> Result<()> { Ok(()) }
+
+	fn clear_all_browsing_data(&self) -> Result<()> { self.update(|s| s.cookies.clear()) }
+
+	fn clear_browsing_data<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		types:DataTypes,
+		f:F,
+	) -> Result<()> {
+		if types.contains(DataTypes::COOKIES) {
+			self.state.lock().unwrap().cookies.clear();
+		}
+
+		self.context.send_message(Message::Task(Box::new(move || f(Ok(())))))
+	}
+
+	fn cookies_for_url(&self, url:Url) -> Result<Vec<Cookie<'static>>> {
+		let host = url.host_str().unwrap_or_default().to_string();
+
+		Ok(self
+			.state
+			.lock()
+			.unwrap()
+			.cookies
+			.iter()
+			.filter(|cookie| cookie.domain().map_or(true, |domain| domain.trim_start_matches('.') == host))
+			.cloned()
+			.collect())
+	}
+
+	fn cookies(&self) -> Result<Vec<Cookie<'static>>> { Ok(self.state.lock().unwrap().cookies.clone()) }
+
+	fn set_cookie<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		cookie:Cookie<'static>,
+		f:F,
+	) -> Result<()> {
+		{
+			let mut state = self.state.lock().unwrap();
+
+			state.cookies.retain(|c| {
+				c.name() != cookie.name() || c.domain() != cookie.domain() || c.path() != cookie.path()
+			});
+
+			state.cookies.push(cookie);
+		}
+
+		self.context.send_message(Message::Task(Box::new(move || f(Ok(())))))
+	}
+
+	fn delete_cookie<F:FnOnce(Result<()>) + Send + 'static>(
+		&self,
+		name:String,
+		url:Url,
+		f:F,
+	) -> Result<()> {
+		let host = url.host_str().unwrap_or_default().to_string();
+
+		self.update(|s| {
+			s.cookies.retain(|cookie| {
+				cookie.name() != name
+					|| cookie.domain().is_some_and(|domain| domain.trim_start_matches('.') != host)
+			})
+		})?;
+
+		self.context.send_message(Message::Task(Box::new(move || f(Ok(())))))
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct EventProxy {}
+
+impl<T:UserEvent> EventLoopProxy<T> for EventProxy {
+	fn send_event(&self, event:T) -> Result<()> { Ok(()) }
+}
+
+#[derive(Debug)]
+pub struct MockRuntime {
+	is_running:Arc<AtomicBool>,
+	pub context:RuntimeContext,
+	run_rx:Receiver<Message>,
+}
+
+impl MockRuntime {
+	fn init() -> Self {
+		let is_running = Arc::new(AtomicBool::new(false));
+
+		let (tx, rx) = sync_channel(256);
+
+		let context = RuntimeContext {
+			is_running:is_running.clone(),
+			windows:Default::default(),
+			run_tx:tx,
+			next_window_id:Default::default(),
+			next_webview_id:Default::default(),
+			next_window_event_id:Default::default(),
+			next_webview_event_id:Default::default(),
+			system_theme:Arc::new(Mutex::new(Theme::Light)),
+			theme:Default::default(),
+		};
+
+		Self { is_running, context, run_rx:rx }
+	}
+
+	/// Handles the pending messages, returning `false` once the event loop must
+	/// exit.
+	fn handle_messages<T:UserEvent, F:FnMut(RunEvent<T>)>(&self, callback:&mut F) -> bool {
+		while let Ok(message) = self.run_rx.try_recv() {
+			match message {
+				Message::Task(task) => task(),
+				Message::CloseWindow(id) => {
+					if !self.window_destroyed(self.context.close_window(id), callback) {
+						return false;
+					}
+				},
+				Message::DestroyWindow(id) => {
+					if !self.window_destroyed(self.context.destroy_window(id), callback) {
+						return false;
+					}
+				},
+				Message::RequestExit(code) => {
+					if !self.exit_requested(Some(code), callback) {
+						return false;
+					}
+				},
+			}
+		}
+
+		true
+	}
+
+	/// Emits the destruction of the window with the given label, if any,
+	/// returning `false` if it was the last window and the exit wasn't
+	/// prevented.
+	fn window_destroyed<T:UserEvent, F:FnMut(RunEvent<T>)>(
+		&self,
+		label:Option<String>,
+		callback:&mut F,
+	) -> bool {
+		let Some(label) = label else {
+			return true;
+		};
+
+		callback(RunEvent::WindowEvent { label, event:WindowEvent::Destroyed });
+
+		!self.context.windows.borrow().is_empty() || self.exit_requested(None, callback)
+	}
+
+	/// Emits [`RunEvent::ExitRequested`], returning whether the exit was
+	/// prevented.
+	fn exit_requested<T:UserEvent, F:FnMut(RunEvent<T>)>(&self, code:Option<i32>, callback:&mut F) -> bool {
+		let (tx, rx) = channel();
+
+		callback(RunEvent::ExitRequested { code, tx });
+
+		matches!(rx.try_recv(), Ok(ExitRequestedEventAction::Prevent))
+	}
+}
+
+impl<T:UserEvent> Runtime<T> for MockRuntime {
+	type WindowDispatcher = MockWindowDispatcher;
+	type WebviewDispatcher = MockWebviewDispatcher;
+	type Handle = MockRuntimeHandle;
+	type EventLoopProxy = EventProxy;
+
+	fn new(_args:RuntimeInitArgs) -> Result<Self> { Ok(Self::init()) }
+
+	#[cfg(any(windows, target_os = "linux"))]
+	fn new_any_thread(_args:RuntimeInitArgs) -> Result<Self> { Ok(Self::init()) }
+
+	fn create_proxy(&self) -> EventProxy { EventProxy {} }
+
+	fn handle(&self) -> Self::Handle { MockRuntimeHandle { context:self.context.clone() } }
+
+	fn create_window<F:Fn(RawWindow<'_>) + Send + 'static>(
+		&self,
+		pending:PendingWindow<T, Self>,
+		_after_window_creation:Option<F>,
+	) -> Result<DetachedWindow<T, Self>> {
+		self.context.create_window(pending)
+	}
+
+	fn create_webview(
+		&self,
+		window_id:WindowId,
+		pending:PendingWebview<T, Self>,
+	) -> Result<DetachedWebview<T, Self>> {
+		self.context.create_webview(window_id, pending)
+	}
+
+	fn primary_monitor(&self) -> Option<Monitor> { None }
+
+	fn monitor_from_point(&self, x:f64, y:f64) -> Option<Monitor> { None }
+
+	fn available_monitors(&self) -> Vec<Monitor> { Vec::new() }
+
+	fn cursor_position(&self) -> Result<PhysicalPosition<f64>> { Ok(PhysicalPosition::new(0.0, 0.0)) }
+
+	fn set_theme(&self, theme:Option<Theme>) { *self.context.theme.lock().unwrap() = theme; }
+
+	fn system_theme(&self) -> Theme { *self.context.system_theme.lock().unwrap() }
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn set_activation_policy(&mut self, activation_policy:tauri_runtime::ActivationPolicy) {}
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn show(&self) {}
+
+	#[cfg(target_os = "macos")]
+	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	fn hide(&self) {}
+
+	fn set_device_event_filter(&mut self, filter:DeviceEventFilter) {}
+
+	#[cfg(desktop)]
+	fn run_iteration<F:FnMut(RunEvent<T>)>(&mut self, mut callback:F) {
+		self.is_running.store(true, Ordering::Relaxed);
+
+		self.handle_messages(&mut callback);
+
+		callback(RunEvent::MainEventsCleared);
+
+		self.is_running.store(false, Ordering::Relaxed);
+	}
+
+	fn run<F:FnMut(RunEvent<T>) + 'static>(self, mut callback:F) {
+		self.is_running.store(true, Ordering::Relaxed);
+
+		callback(RunEvent::Ready);
+
+		while self.handle_messages(&mut callback) {
+			callback(RunEvent::MainEventsCleared);
+
+			std::thread::sleep(Duration::from_millis(10));
+		}
+
+		self.is_running.store(false, Ordering::Relaxed);
+
+		callback(RunEvent::Exit);
+	}
+}
+
+impl MockRuntimeHandle {
+	/// Changes the system theme reported by the runtime.
+	pub fn set_system_theme(&self, theme:Theme) { *self.context.system_theme.lock().unwrap() = theme; }
+}
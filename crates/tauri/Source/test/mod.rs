@@ -0,0 +1,290 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Utilities for unit testing on Tauri applications.
+//!
+//! # Stability
+//!
+//! This module is unstable.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tauri::test::{mock_builder, mock_context, noop_assets};
+//!
+//! #[tauri::command]
+//! fn ping() -> &'static str {
+//!     "pong"
+//! }
+//!
+//! fn create_app<R: tauri::Runtime>(builder: tauri::Builder<R>) -> tauri::App<R> {
+//!     builder
+//!         .invoke_handler(tauri::generate_handler![ping])
+//!         // remove the string argument to use your app's config file
+//!         .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+//!         .expect("failed to build app")
+//! }
+//!
+//! fn main() {
+//!     // Use `tauri::Builder::default()` to use the default runtime rather than the `MockRuntime`;
+//!     // let app = create_app(tauri::Builder::default());
+//!     let app = create_app(mock_builder());
+//!     let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+//!
+//!     // run the `ping` command and assert it returns `pong`
+//!     let res = tauri::test::get_ipc_response(
+//!         &webview,
+//!         tauri::webview::InvokeRequest {
+//!             cmd: "ping".into(),
+//!             callback: tauri::ipc::CallbackFn(0),
+//!             error: tauri::ipc::CallbackFn(1),
+//!             // alternatively use "tauri://localhost"
+//!             url: "http://tauri.localhost".parse().unwrap(),
+//!             body: tauri::ipc::InvokeBody::default(),
+//!             headers: Default::default(),
+//!             invoke_key: tauri::test::INVOKE_KEY.to_string(),
+//!         },
+//!     ).map(|b| b.deserialize::<String>().unwrap());
+//! }
+//! ```
+
+#![allow(unused_variables)]
+
+mod mock_runtime;
+
+use std::{borrow::Cow, collections::HashMap, fmt::Debug};
+
+pub use mock_runtime::*;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use tauri_utils::{
+	acl::resolved::Resolved,
+	assets::{AssetKey, AssetsIter, CspHash},
+	config::Config,
+};
+
+use crate::{
+	App,
+	Assets,
+	Builder,
+	Context,
+	Pattern,
+	Runtime,
+	Webview,
+	ipc::{InvokeError, InvokeResponse, InvokeResponseBody, RuntimeAuthority},
+	webview::InvokeRequest,
+};
+
+/// The invoke key used for tests.
+pub const INVOKE_KEY:&str = "__invoke-key__";
+
+/// An empty [`Assets`] implementation.
+pub struct NoopAsset {
+	assets:HashMap<String, Vec<u8>>,
+	csp_hashes:Vec<CspHash<'static>>,
+}
+
+impl<R:Runtime> Assets<R> for NoopAsset {
+	fn get(&self, key:&AssetKey) -> Option<Cow<'_, [u8]>> { None }
+
+	fn csp_hashes(&self, html_path:&AssetKey) -> Box<dyn Iterator<Item = CspHash<'_>> + '_> {
+		Box::new(self.csp_hashes.iter().copied())
+	}
+
+	fn iter(&self) -> Box<AssetsIter<'_>> {
+		Box::new(
+			self.assets
+				.iter()
+				.map(|(k, b)| (Cow::Borrowed(k.as_str()), Cow::Borrowed(b.as_slice()))),
+		)
+	}
+}
+
+/// Creates a new empty [`Assets`] implementation.
+pub fn noop_assets() -> NoopAsset { NoopAsset { assets:Default::default(), csp_hashes:Default::default() } }
+
+/// Creates a new [`crate::Context`] for testing.
+pub fn mock_context<R:Runtime, A:Assets<R>>(assets:A) -> crate::Context<R> {
+	Context {
+		config:Config { identifier:"app.tauri.test".into(), ..Default::default() },
+		assets:Box::new(assets),
+		default_window_icon:None,
+		app_icon:None,
+		#[cfg(all(desktop, feature = "tray-icon"))]
+		tray_icon:None,
+		package_info:crate::PackageInfo {
+			name:"test".into(),
+			version:"0.1.0".parse().unwrap(),
+			authors:"Tauri",
+			description:"Tauri test",
+			crate_name:"test",
+		},
+		pattern:Pattern::Brownfield,
+		runtime_authority:RuntimeAuthority::new(Default::default(), Resolved::default()),
+		plugin_global_api_scripts:None,
+		#[cfg(dev)]
+		config_parent:None,
+	}
+}
+
+/// Creates a new [`Builder`] using the [`MockRuntime`].
+///
+/// To use a dummy [`Context`], see [`mock_app`].
+///
+/// # Examples
+///
+/// ```rust
+/// #[cfg(test)]
+/// fn do_something() {
+///   let app = tauri::test::mock_builder()
+///     // remove the string argument to use your app's config file
+///     .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+///     .unwrap();
+/// }
+/// ```
+pub fn mock_builder() -> Builder<MockRuntime> {
+	let mut builder = Builder::<MockRuntime>::new().enable_macos_default_menu(false);
+
+	builder.invoke_initialization_script = "".into();
+
+	builder.invoke_key = INVOKE_KEY.to_string();
+
+	builder
+}
+
+/// Creates a new [`App`] for testing using the [`mock_context`] with a
+/// [`noop_assets`].
+pub fn mock_app() -> App<MockRuntime> { mock_builder().build(mock_context(noop_assets())).unwrap() }
+
+/// A plugin manifest with one permission allowing a command for each
+/// `(identifier, command)` pair.
+#[cfg(test)]
+pub(crate) fn mock_manifest(permissions:&[(&str, &str)]) -> tauri_utils::acl::manifest::Manifest {
+	use tauri_utils::acl::{Commands, Permission, manifest::Manifest};
+
+	Manifest {
+		default_permission:None,
+		permissions:permissions
+			.iter()
+			.map(|(identifier, command)| {
+				let permission = Permission {
+					version:None,
+					identifier:identifier.to_string(),
+					description:None,
+					commands:Commands { allow:vec![command.to_string()], deny:Vec::new() },
+					scope:Default::default(),
+					platforms:None,
+				};
+
+				(identifier.to_string(), permission)
+			})
+			.collect(),
+		permission_sets:Default::default(),
+		global_scope_schema:None,
+	}
+}
+
+/// A local capability granting the `permissions` to the `windows`.
+#[cfg(test)]
+pub(crate) fn mock_capability(
+	identifier:&str,
+	windows:&[&str],
+	permissions:&[&str],
+) -> tauri_utils::acl::capability::Capability {
+	use tauri_utils::acl::capability::{Capability, PermissionEntry};
+
+	Capability {
+		identifier:identifier.to_string(),
+		description:"".into(),
+		remote:None,
+		local:true,
+		windows:windows.iter().map(ToString::to_string).collect(),
+		webviews:Vec::new(),
+		permissions:permissions
+			.iter()
+			.map(|p| PermissionEntry::PermissionRef(p.to_string().try_into().unwrap()))
+			.collect(),
+		platforms:None,
+	}
+}
+
+/// Executes the given IPC message and assert the response matches the
+/// expected value.
+///
+/// # Examples
+///
+/// ```rust
+/// use tauri::test::{mock_builder, mock_context, noop_assets};
+///
+/// #[tauri::command]
+/// fn ping() -> &'static str {
+///     "pong"
+/// }
+///
+/// fn create_app<R: tauri::Runtime>(builder: tauri::Builder<R>) -> tauri::App<R> {
+///     builder
+///         .invoke_handler(tauri::generate_handler![ping])
+///         // remove the string argument to use your app's config file
+///         .build(tauri::generate_context!("test/fixture/src-tauri/tauri.conf.json"))
+///         .expect("failed to build app")
+/// }
+///
+/// fn main() {
+///     let app = create_app(mock_builder());
+///     let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+///
+///     // run the `ping` command and assert it returns `pong`
+///     tauri::test::assert_ipc_response(
+///         &webview,
+///         tauri::webview::InvokeRequest {
+///             cmd: "ping".into(),
+///             callback: tauri::ipc::CallbackFn(0),
+///             error: tauri::ipc::CallbackFn(1),
+///             url: "http://tauri.localhost".parse().unwrap(),
+///             body: tauri::ipc::InvokeBody::default(),
+///             headers: Default::default(),
+///             invoke_key: tauri::test::INVOKE_KEY.to_string(),
+///         },
+///       Ok("pong")
+///     );
+/// }
+/// ```
+pub fn assert_ipc_response<T:Serialize + Debug + Send + Sync + 'static, W:AsRef<Webview<MockRuntime>>>(
+	webview:&W,
+	request:InvokeRequest,
+	expected:Result<T, T>,
+) {
+	let response = get_ipc_response(webview, request).map(|b| b.deserialize::<JsonValue>().unwrap());
+
+	assert_eq!(
+		response,
+		expected
+			.map(|e| serde_json::to_value(e).unwrap())
+			.map_err(|e| serde_json::to_value(e).unwrap())
+	);
+}
+
+/// Executes the given IPC message and get the return value.
+///
+/// See [`assert_ipc_response`] for an example.
+pub fn get_ipc_response<W:AsRef<Webview<MockRuntime>>>(
+	webview:&W,
+	request:InvokeRequest,
+) -> Result<InvokeResponseBody, JsonValue> {
+	let (tx, rx) = std::sync::mpsc::sync_channel(1);
+
+	webview.as_ref().clone().on_message(
+		request,
+		Box::new(move |_window, _cmd, response, _callback, _error| {
+			tx.send(response).unwrap();
+		}),
+	);
+
+	let res = rx.recv().expect("Failed to receive result from command");
+
+	match res {
+		InvokeResponse::Ok(body) => Ok(body),
+		InvokeResponse::Err(InvokeError(value)) => Err(value),
+	}
+}
@@ -6,21 +6,40 @@
 
 pub(crate) mod plugin;
 
-use std::path::Path;
+use std::{
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		Mutex,
+		Weak,
+		atomic::{AtomicBool, Ordering},
+	},
+	time::Duration,
+};
 
 use serde::Serialize;
+use tauri_runtime::{EventLoopProxy, RuntimeHandle};
 pub use tray_icon::TrayIconId;
 
 use crate::{
 	AppHandle,
+	EventLoopMessage,
 	Manager,
 	PhysicalPosition,
 	Rect,
 	Runtime,
+	Theme,
 	UnsafeSend,
-	app::{GlobalMenuEventListener, GlobalTrayIconEventListener},
+	app::{GlobalMenuEventListener, GlobalTrayIconEventListener, TrayMenuFactory},
 	image::Image,
-	menu::{ContextMenu, MenuEvent, run_item_main_thread},
+	menu::{
+		ContextMenu,
+		Menu,
+		MenuBuilder,
+		MenuEvent,
+		run_item_main_thread,
+		sealed::{ContextMenuBase, ContextMenuFactory},
+	},
 	resources::Resource,
 };
 
@@ -133,6 +152,14 @@ pub enum TrayIconEvent {
 		/// Position and size of the tray icon.
 		rect:Rect,
 	},
+	/// The tray icon was shown again by [`TrayIcon::attach`].
+	///
+	/// The native tray icon is recreated with the same id, menu and
+	/// attributes, use this event to refresh any other state related to it.
+	Attached {
+		/// Id of the tray icon which triggered this event.
+		id:TrayIconId,
+	},
 }
 
 impl TrayIconEvent {
@@ -144,6 +171,22 @@ impl TrayIconEvent {
 			TrayIconEvent::Enter { id, .. } => id,
 			TrayIconEvent::Move { id, .. } => id,
 			TrayIconEvent::Leave { id, .. } => id,
+			TrayIconEvent::Attached { id } => id,
+		}
+	}
+
+	/// Whether this event is the press of the mouse button opening the tray
+	/// icon menu, the right one or also the left one when `menu_on_left_click`.
+	pub(crate) fn opens_menu(&self, menu_on_left_click:bool) -> bool {
+		match self {
+			TrayIconEvent::Click { button, button_state:MouseButtonState::Down, .. } => {
+				match button {
+					MouseButton::Right => true,
+					MouseButton::Left => menu_on_left_click,
+					MouseButton::Middle => false,
+				}
+			},
+			_ => false,
 		}
 	}
 }
@@ -194,12 +237,105 @@ impl From<tray_icon::TrayIconEvent> for TrayIconEvent {
 	}
 }
 
+/// The attributes of a native tray icon, kept to recreate it after
+/// [`TrayIcon::detach`].
+struct TrayIconAttributes {
+	icon:Option<tray_icon::Icon>,
+	/// The icons for the light and dark system themes, see
+	/// [`TrayIconBuilder::theme_icons`].
+	theme_icons:Option<(tray_icon::Icon, tray_icon::Icon)>,
+	/// The theme of the icon picked from [`Self::theme_icons`].
+	icon_theme:Option<Theme>,
+	menu:Option<ContextMenuFactory>,
+	tooltip:Option<String>,
+	title:Option<String>,
+	temp_dir_path:Option<PathBuf>,
+	icon_as_template:bool,
+	menu_on_left_click:bool,
+	visible:bool,
+}
+
+impl Default for TrayIconAttributes {
+	fn default() -> Self {
+		Self {
+			icon:None,
+			theme_icons:None,
+			icon_theme:None,
+			menu:None,
+			tooltip:None,
+			title:None,
+			temp_dir_path:None,
+			icon_as_template:false,
+			menu_on_left_click:true,
+			visible:true,
+		}
+	}
+}
+
+impl TrayIconAttributes {
+	/// Picks the icon for `theme` if the tray icon follows the system theme,
+	/// returning whether it changed.
+	fn apply_theme(&mut self, theme:Theme) -> bool {
+		let Some((light, dark)) = &self.theme_icons else {
+			return false;
+		};
+
+		if self.icon_theme == Some(theme) {
+			return false;
+		}
+
+		let icon = if theme == Theme::Dark { dark } else { light };
+
+		self.icon = Some(icon.clone());
+
+		self.icon_theme = Some(theme);
+
+		true
+	}
+
+	fn native_builder(&self, id:&TrayIconId) -> tray_icon::TrayIconBuilder {
+		let mut builder = tray_icon::TrayIconBuilder::new()
+			.with_id(id.clone())
+			.with_icon_as_template(self.icon_as_template)
+			.with_menu_on_left_click(self.menu_on_left_click);
+
+		if let Some(icon) = &self.icon {
+			builder = builder.with_icon(icon.clone());
+		}
+
+		if let Some(menu) = &self.menu {
+			builder = builder.with_menu(menu());
+		}
+
+		if let Some(tooltip) = &self.tooltip {
+			builder = builder.with_tooltip(tooltip);
+		}
+
+		if let Some(title) = &self.title {
+			builder = builder.with_title(title);
+		}
+
+		if let Some(path) = &self.temp_dir_path {
+			builder = builder.with_temp_dir_path(path);
+		}
+
+		builder
+	}
+}
+
 /// [`TrayIcon`] builder struct and associated methods.
-#[derive(Default)]
 pub struct TrayIconBuilder<R:Runtime> {
 	on_menu_event:Option<GlobalMenuEventListener<AppHandle<R>>>,
 	on_tray_icon_event:Option<GlobalTrayIconEventListener<TrayIcon<R>>>,
-	inner:tray_icon::TrayIconBuilder,
+	menu_factory:Option<TrayMenuFactory<R>>,
+	id:TrayIconId,
+	attributes:TrayIconAttributes,
+	/// Error from [`Self::theme_icons`], returned by [`Self::build`].
+	theme_icons_error:Option<crate::Error>,
+}
+
+impl<R:Runtime> Default for TrayIconBuilder<R> {
+	fn default() -> Self { Self::new() }
 }
 
 impl<R:Runtime> TrayIconBuilder<R> {
@@ -211,9 +347,12 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	///   Setting an empty [`Menu`](crate::menu::Menu) is enough.
 	pub fn new() -> Self {
 		Self {
-			inner:tray_icon::TrayIconBuilder::new(),
+			id:tray_icon::TrayIconBuilder::new().id().clone(),
+			attributes:Default::default(),
 			on_menu_event:None,
 			on_tray_icon_event:None,
+			menu_factory:None,
+			theme_icons_error:None,
 		}
 	}
 
@@ -226,7 +365,7 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	pub fn with_id<I:Into<TrayIconId>>(id:I) -> Self {
 		let mut builder = Self::new();
 
-		builder.inner = builder.inner.with_id(id);
+		builder.id = id.into();
 
 		builder
 	}
@@ -238,7 +377,29 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	/// - **Linux**: once a menu is set, it cannot be removed or replaced but
 	///   you can change its content.
 	pub fn menu<M:ContextMenu>(mut self, menu:&M) -> Self {
-		self.inner = self.inner.with_menu(menu.inner_context_owned());
+		self.attributes.menu.replace(menu.inner_context_factory());
+
+		self
+	}
+
+	/// Set a closure that builds the menu of this tray icon, replacing
+	/// [`Self::menu`].
+	///
+	/// The menu is built when the tray icon is created and rebuilt with
+	/// [`TrayIcon::rebuild_menu`] when the mouse button that opens it is
+	/// pressed, see [`Self::show_menu_on_left_click`], so it reflects the app
+	/// state when it's opened.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **macOS**: The menu opens as soon as the button is pressed, so the
+	///   rebuilt menu is the one shown the next time it's opened.
+	/// - **Linux**: The menu is only built when the tray icon is created since
+	///   tray icon events are not emitted.
+	pub fn menu_factory<F>(mut self, factory:F) -> Self
+	where
+		F: Fn(MenuBuilder<'_, R, AppHandle<R>>) -> crate::Result<Menu<R>> + Send + Sync + 'static, {
+		self.menu_factory.replace(std::sync::Arc::new(factory));
 
 		self
 	}
@@ -250,10 +411,44 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	/// - **Linux:** Sometimes the icon won't be visible unless a menu is set.
 	///   Setting an empty [`Menu`](crate::menu::Menu) is enough.
 	pub fn icon(mut self, icon:Image<'_>) -> Self {
-		let icon = icon.try_into().ok();
+		if let Ok(icon) = icon.try_into() {
+			self.attributes.icon.replace(icon);
+		}
 
-		if let Some(icon) = icon {
-			self.inner = self.inner.with_icon(icon);
+		self
+	}
+
+	/// Sets the icons shown for the light and dark system themes, switched when
+	/// the system theme changes. Overrides [`Self::icon`].
+	///
+	/// [`Self::build`] fails if either image can't be converted to an icon.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use tauri::{image::Image, tray::TrayIconBuilder};
+	/// # fn tray_icon(_dark:bool) -> Image<'static> {
+	/// # 	Image::new_owned(vec![0; 32 * 32 * 4], 32, 32)
+	/// # }
+	///
+	/// tauri::Builder::default().setup(|app| {
+	/// 	// dark glyphs on light themes and the other way around
+	/// 	TrayIconBuilder::new().theme_icons(tray_icon(false), tray_icon(true)).build(app)?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	pub fn theme_icons(mut self, light:Image<'_>, dark:Image<'_>) -> Self {
+		match tray_icon::Icon::try_from(light)
+			.and_then(|light| Ok((light, tray_icon::Icon::try_from(dark)?)))
+		{
+			Ok(icons) => {
+				self.attributes.theme_icons.replace(icons);
+
+				self.theme_icons_error = None;
+			},
+			Err(error) => {
+				self.theme_icons_error.replace(error);
+			},
 		}
 
 		self
@@ -265,7 +460,7 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	///
 	/// - **Linux:** Unsupported.
 	pub fn tooltip<S:AsRef<str>>(mut self, s:S) -> Self {
-		self.inner = self.inner.with_tooltip(s);
+		self.attributes.tooltip.replace(s.as_ref().to_string());
 
 		self
 	}
@@ -281,7 +476,7 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	///   panel.  This may not be shown in all visualizations.
 	/// - **Windows:** Unsupported.
 	pub fn title<S:AsRef<str>>(mut self, title:S) -> Self {
-		self.inner = self.inner.with_title(title);
+		self.attributes.title.replace(title.as_ref().to_string());
 
 		self
 	}
@@ -291,14 +486,14 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	/// On Linux, we need to write the icon to the disk and usually it will
 	/// be `$XDG_RUNTIME_DIR/tray-icon` or `$TEMP/tray-icon`.
 	pub fn temp_dir_path<P:AsRef<Path>>(mut self, s:P) -> Self {
-		self.inner = self.inner.with_temp_dir_path(s);
+		self.attributes.temp_dir_path.replace(s.as_ref().to_path_buf());
 
 		self
 	}
 
 	/// Use the icon as a [template](https://developer.apple.com/documentation/appkit/nsimage/1520017-template?language=objc). **macOS only**.
 	pub fn icon_as_template(mut self, is_template:bool) -> Self {
-		self.inner = self.inner.with_icon_as_template(is_template);
+		self.attributes.icon_as_template = is_template;
 
 		self
 	}
@@ -310,7 +505,7 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	/// - **Linux:** Unsupported.
 	#[deprecated(since = "2.2.0", note = "Use `TrayIconBuiler::show_menu_on_left_click` instead.")]
 	pub fn menu_on_left_click(mut self, enable:bool) -> Self {
-		self.inner = self.inner.with_menu_on_left_click(enable);
+		self.attributes.menu_on_left_click = enable;
 
 		self
 	}
@@ -321,7 +516,7 @@ impl<R:Runtime> TrayIconBuilder<R> {
 	///
 	/// - **Linux:** Unsupported.
 	pub fn show_menu_on_left_click(mut self, enable:bool) -> Self {
-		self.inner = self.inner.with_menu_on_left_click(enable);
+		self.attributes.menu_on_left_click = enable;
 
 		self
 	}
@@ -352,16 +547,31 @@ impl<R:Runtime> TrayIconBuilder<R> {
 
 	/// Access the unique id that will be assigned to the tray icon
 	/// this builder will create.
-	pub fn id(&self) -> &TrayIconId { self.inner.id() }
+	pub fn id(&self) -> &TrayIconId { &self.id }
 
 	/// Builds and adds a new [`TrayIcon`] to the system tray.
-	pub fn build<M:Manager<R>>(self, manager:&M) -> crate::Result<TrayIcon<R>> {
+	pub fn build<M:Manager<R>>(mut self, manager:&M) -> crate::Result<TrayIcon<R>> {
+		if let Some(error) = self.theme_icons_error {
+			return Err(error);
+		}
+
 		let id = self.id().clone();
 
+		if let Some(factory) = &self.menu_factory {
+			let menu = factory(MenuBuilder::new(manager.app_handle()))?;
+
+			self.attributes.menu.replace(menu.inner_context_factory());
+		}
+
+		// the system theme is only read for the tray icons following it
+		if self.attributes.theme_icons.is_some() {
+			self.attributes.apply_theme(manager.app_handle().system_theme());
+		}
+
 		// SAFETY:
 		// the menu within this builder was created on main thread
 		// and will be accessed on the main thread
-		let unsafe_builder = UnsafeSend(self.inner);
+		let unsafe_builder = UnsafeSend(self.attributes.native_builder(&id));
 
 		let (tx, rx) = std::sync::mpsc::channel();
 
@@ -373,15 +583,79 @@ impl<R:Runtime> TrayIconBuilder<R> {
 			})
 			.and_then(|_| rx.recv().map_err(|_| crate::Error::FailedToReceiveMessage))??;
 
-		let icon =
-			TrayIcon { id, inner:unsafe_tray.take(), app_handle:manager.app_handle().clone() };
+		let icon = TrayIcon {
+			id,
+			inner:Arc::new(Mutex::new(TrayIconInner {
+				native:Some(unsafe_tray.take()),
+				attributes:self.attributes,
+				animation:None,
+			})),
+			app_handle:manager.app_handle().clone(),
+		};
 
 		icon.register(&icon.app_handle, self.on_menu_event, self.on_tray_icon_event);
 
+		if let Some(factory) = self.menu_factory {
+			icon.app_handle
+				.manager
+				.tray
+				.menu_factories
+				.lock()
+				.unwrap()
+				.insert(icon.id.clone(), factory);
+		}
+
 		Ok(icon)
 	}
 }
 
+/// Requests the next frame of an icon animation every `interval` until the
+/// animation is stopped or the tray icon is dropped, with at most one request
+/// waiting on the event loop at a time.
+async fn run_icon_animation<R:Runtime>(
+	app_handle:AppHandle<R>,
+	inner:Weak<Mutex<TrayIconInner>>,
+	running:Arc<AtomicBool>,
+	interval:Duration,
+) {
+	let pending = Arc::new(AtomicBool::new(false));
+
+	let mut ticks = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+
+	// frames are dropped rather than sent in a burst when the event loop is slow
+	ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+	loop {
+		ticks.tick().await;
+
+		if !running.load(Ordering::Relaxed) || inner.strong_count() == 0 {
+			break;
+		}
+
+		if pending.swap(true, Ordering::AcqRel) {
+			continue;
+		}
+
+		let inner = inner.clone();
+
+		let running = running.clone();
+
+		let pending = pending.clone();
+
+		let sent = app_handle.run_on_main_thread(move || {
+			pending.store(false, Ordering::Release);
+
+			if let Some(inner) = inner.upgrade() {
+				inner.lock().unwrap().next_animation_frame(&running);
+			}
+		});
+
+		if sent.is_err() {
+			break;
+		}
+	}
+}
+
 /// Tray icon struct and associated methods.
 ///
 /// This type is reference-counted and the icon is removed when the last
@@ -391,10 +665,63 @@ impl<R:Runtime> TrayIconBuilder<R> {
 #[tauri_macros::default_runtime(crate::Wry, wry)]
 pub struct TrayIcon<R:Runtime> {
 	id:TrayIconId,
-	inner:tray_icon::TrayIcon,
+	inner:Arc<Mutex<TrayIconInner>>,
 	app_handle:AppHandle<R>,
 }
 
+struct TrayIconInner {
+	/// The native tray icon, `None` while detached.
+	native:Option<tray_icon::TrayIcon>,
+	attributes:TrayIconAttributes,
+	/// The running icon animation, see [`TrayIcon::set_icon_animation`].
+	animation:Option<TrayIconAnimation>,
+}
+
+// SAFETY: the native tray icon is only accessed on the main thread
+unsafe impl Send for TrayIconInner {}
+
+impl TrayIconInner {
+	/// Shows the icon of the theme if the tray icon follows the system theme,
+	/// unless an animation is running.
+	fn show_theme_icon(&mut self, theme:Theme) -> Result<(), tray_icon::Error> {
+		if !self.attributes.apply_theme(theme) {
+			return Ok(());
+		}
+
+		match (&self.native, &self.animation) {
+			(Some(native), None) => native.set_icon(self.attributes.icon.clone()),
+			_ => Ok(()),
+		}
+	}
+
+	/// Shows the next frame of the animation owning `running`, ignoring ticks
+	/// of an animation that was replaced.
+	fn next_animation_frame(&mut self, running:&Arc<AtomicBool>) {
+		let Some(animation) = self.animation.as_mut() else {
+			return;
+		};
+
+		if !Arc::ptr_eq(&animation.running, running) {
+			return;
+		}
+
+		animation.current = (animation.current + 1) % animation.frames.len();
+
+		if let Some(native) = &self.native {
+			if let Err(e) = native.set_icon(Some(animation.frames[animation.current].clone())) {
+				log::error!("failed to set tray icon animation frame: {e}");
+			}
+		}
+	}
+}
+
+struct TrayIconAnimation {
+	frames:Vec<tray_icon::Icon>,
+	current:usize,
+	/// Cleared to stop the timer task.
+	running:Arc<AtomicBool>,
+}
+
 impl<R:Runtime> Clone for TrayIcon<R> {
 	fn clone(&self) -> Self {
 		Self { id:self.id.clone(), inner:self.inner.clone(), app_handle:self.app_handle.clone() }
@@ -466,14 +793,207 @@ impl<R:Runtime> TrayIcon<R> {
 	/// Returns the id associated with this tray icon.
 	pub fn id(&self) -> &TrayIconId { &self.id }
 
+	/// Records an attribute change with `update` so it survives
+	/// [`Self::detach`], then applies it with `f` if the native tray icon
+	/// currently exists.
+	fn with_native<T, U, F>(&self, update:U, f:F) -> crate::Result<Option<T>>
+	where
+		T: Send + 'static,
+		U: FnOnce(&mut TrayIconAttributes) + Send + 'static,
+		F: FnOnce(&tray_icon::TrayIcon) -> T + Send + 'static, {
+		run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			update(&mut inner.attributes);
+
+			inner.native.as_ref().map(f)
+		})
+	}
+
 	/// Sets a new tray icon. If `None` is provided, it will remove the icon.
+	///
+	/// While an animation is running, see [`Self::set_icon_animation`], the
+	/// icon is shown once the animation is stopped.
+	///
+	/// The icon stops following the system theme, see
+	/// [`Self::set_theme_icons`].
 	pub fn set_icon(&self, icon:Option<Image<'_>>) -> crate::Result<()> {
-		let icon = match icon {
+		let icon:Option<tray_icon::Icon> = match icon {
 			Some(i) => Some(i.try_into()?),
 			None => None,
 		};
 
-		run_item_main_thread!(self, |self_:Self| self_.inner.set_icon(icon))?.map_err(Into::into)
+		run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			inner.attributes.icon = icon.clone();
+
+			inner.attributes.theme_icons = None;
+
+			inner.attributes.icon_theme = None;
+
+			match (&inner.native, &inner.animation) {
+				(Some(native), None) => native.set_icon(icon),
+				_ => Ok(()),
+			}
+		})?
+		.map_err(Into::into)
+	}
+
+	/// Sets the icons shown for the light and dark system themes, switched when
+	/// the system theme changes. `None` keeps the current icon and stops
+	/// following the system theme.
+	pub fn set_theme_icons(&self, icons:Option<(Image<'_>, Image<'_>)>) -> crate::Result<()> {
+		let icons:Option<(tray_icon::Icon, tray_icon::Icon)> = match icons {
+			Some((light, dark)) => Some((light.try_into()?, dark.try_into()?)),
+			None => None,
+		};
+
+		let theme = icons.is_some().then(|| self.app_handle.system_theme());
+
+		run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			inner.attributes.theme_icons = icons;
+
+			inner.attributes.icon_theme = None;
+
+			match theme {
+				Some(theme) => inner.show_theme_icon(theme),
+				None => Ok(()),
+			}
+		})?
+		.map_err(Into::into)
+	}
+
+	/// Shows the icon for the new system theme, if the tray icon follows it.
+	pub(crate) fn on_system_theme_changed(&self, theme:Theme) {
+		let result = run_item_main_thread!(self, |self_:Self| {
+			self_.inner.lock().unwrap().show_theme_icon(theme)
+		});
+
+		if let Err(e) = result.and_then(|result| result.map_err(Into::into)) {
+			log::error!("failed to switch the tray icon to the {theme:?} theme: {e}");
+		}
+	}
+
+	/// Cycles the tray icon through `frames`, showing each frame for
+	/// `interval`, until [`Self::stop_icon_animation`] is called.
+	///
+	/// The frames are swapped on the event loop. A frame that's due while the
+	/// previous one is still waiting to be shown is skipped rather than queued,
+	/// so a busy event loop slows the animation down instead of being flooded.
+	/// Replaces the running animation, if any.
+	///
+	/// All frames must have the same dimensions, an empty `frames` or a zero
+	/// `interval` is rejected too.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// use std::time::Duration;
+	///
+	/// use tauri::image::Image;
+	/// # fn sync_frame(_index:usize) -> Image<'static> {
+	/// # 	Image::new_owned(vec![0; 32 * 32 * 4], 32, 32)
+	/// # }
+	///
+	/// tauri::Builder::default().setup(|app| {
+	/// 	let tray = app.tray_by_id("main").unwrap();
+	///
+	/// 	let frames = (0..8).map(sync_frame).collect();
+	///
+	/// 	tray.set_icon_animation(frames, Duration::from_millis(100))?;
+	///
+	/// 	// once synced
+	/// 	tray.stop_icon_animation()?;
+	/// 	Ok(())
+	/// });
+	/// ```
+	pub fn set_icon_animation(
+		&self,
+		frames:Vec<Image<'_>>,
+		interval:Duration,
+	) -> crate::Result<()> {
+		let Some(first) = frames.first() else {
+			return Err(crate::Error::InvalidTrayIconAnimation("no frames".into()));
+		};
+
+		if interval.is_zero() {
+			return Err(crate::Error::InvalidTrayIconAnimation("zero interval".into()));
+		}
+
+		let size = (first.width(), first.height());
+
+		if let Some((index, frame)) = frames
+			.iter()
+			.enumerate()
+			.find(|(_, frame)| (frame.width(), frame.height()) != size)
+		{
+			return Err(crate::Error::InvalidTrayIconAnimation(format!(
+				"frame {index} is {}x{} but the first frame is {}x{}",
+				frame.width(),
+				frame.height(),
+				size.0,
+				size.1
+			)));
+		}
+
+		let frames = frames
+			.into_iter()
+			.map(tray_icon::Icon::try_from)
+			.collect::<crate::Result<Vec<_>>>()?;
+
+		let running = Arc::new(AtomicBool::new(true));
+
+		let timer_running = running.clone();
+
+		run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			if let Some(native) = &inner.native {
+				if let Err(e) = native.set_icon(Some(frames[0].clone())) {
+					log::error!("failed to set tray icon animation frame: {e}");
+				}
+			}
+
+			let previous =
+				inner.animation.replace(TrayIconAnimation { frames, current:0, running });
+
+			if let Some(previous) = previous {
+				previous.running.store(false, Ordering::Relaxed);
+			}
+		})?;
+
+		let inner = Arc::downgrade(&self.inner);
+
+		let app_handle = self.app_handle.clone();
+
+		crate::async_runtime::spawn(run_icon_animation(app_handle, inner, timer_running, interval));
+
+		Ok(())
+	}
+
+	/// Stops the animation started with [`Self::set_icon_animation`] and
+	/// restores the icon set with [`Self::set_icon`] or on the builder.
+	///
+	/// Does nothing if no animation is running.
+	pub fn stop_icon_animation(&self) -> crate::Result<()> {
+		run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			let Some(animation) = inner.animation.take() else {
+				return Ok(());
+			};
+
+			animation.running.store(false, Ordering::Relaxed);
+
+			match &inner.native {
+				Some(native) => native.set_icon(inner.attributes.icon.clone()),
+				None => Ok(()),
+			}
+		})?
+		.map_err(Into::into)
 	}
 
 	/// Sets a new tray menu.
@@ -483,9 +1003,39 @@ impl<R:Runtime> TrayIcon<R> {
 	/// - **Linux**: once a menu is set it cannot be removed so `None` has no
 	///   effect
 	pub fn set_menu<M:ContextMenu + 'static>(&self, menu:Option<M>) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| {
-			self_.inner.set_menu(menu.map(|m| m.inner_context_owned()))
-		})
+		let menu = menu.map(|m| m.inner_context_factory());
+
+		let native_menu = menu.clone();
+
+		self.with_native(
+			move |a| a.menu = menu,
+			move |t| t.set_menu(native_menu.map(|m| m())),
+		)?;
+
+		Ok(())
+	}
+
+	/// Whether the menu is also shown on left click, see
+	/// [`Self::set_show_menu_on_left_click`].
+	pub(crate) fn shows_menu_on_left_click(&self) -> bool {
+		self.inner.lock().unwrap().attributes.menu_on_left_click
+	}
+
+	/// Builds a new tray menu with `f` and replaces the current one with it.
+	///
+	/// The new menu is fully built before the tray icon is touched and then
+	/// swapped in a single main thread task, so a click goes either to the
+	/// previous menu or to the new one, never to a partially built menu.
+	///
+	/// ## Platform-specific:
+	///
+	/// - **Linux**: See [`Self::set_menu`].
+	pub fn rebuild_menu<F>(&self, f:F) -> crate::Result<()>
+	where
+		F: FnOnce(MenuBuilder<'_, R, AppHandle<R>>) -> crate::Result<Menu<R>>, {
+		let menu = f(MenuBuilder::new(&self.app_handle))?;
+
+		self.set_menu(Some(menu))
 	}
 
 	/// Sets the tooltip for this tray icon.
@@ -496,7 +1046,11 @@ impl<R:Runtime> TrayIcon<R> {
 	pub fn set_tooltip<S:AsRef<str>>(&self, tooltip:Option<S>) -> crate::Result<()> {
 		let s = tooltip.map(|s| s.as_ref().to_string());
 
-		run_item_main_thread!(self, |self_:Self| self_.inner.set_tooltip(s))?.map_err(Into::into)
+		let native_s = s.clone();
+
+		self.with_native(move |a| a.tooltip = s, move |t| t.set_tooltip(native_s))?
+			.unwrap_or(Ok(()))
+			.map_err(Into::into)
 	}
 
 	/// Sets the title for this tray icon.
@@ -512,15 +1066,74 @@ impl<R:Runtime> TrayIcon<R> {
 	pub fn set_title<S:AsRef<str>>(&self, title:Option<S>) -> crate::Result<()> {
 		let s = title.map(|s| s.as_ref().to_string());
 
-		run_item_main_thread!(self, |self_:Self| self_.inner.set_title(s))
+		let native_s = s.clone();
+
+		self.with_native(move |a| a.title = s, move |t| t.set_title(native_s))?;
+
+		Ok(())
 	}
 
 	/// Show or hide this tray icon.
+	///
+	/// Some desktop environments keep a slot for hidden tray icons, use
+	/// [`Self::detach`] to remove the native tray icon entirely.
 	pub fn set_visible(&self, visible:bool) -> crate::Result<()> {
-		run_item_main_thread!(self, |self_:Self| self_.inner.set_visible(visible))?
+		self.with_native(move |a| a.visible = visible, move |t| t.set_visible(visible))?
+			.unwrap_or(Ok(()))
 			.map_err(Into::into)
 	}
 
+	/// Removes the native tray icon from the system tray, keeping this
+	/// [`TrayIcon`] with its id, menu, attributes and event handlers so it
+	/// can be shown again with [`Self::attach`].
+	///
+	/// Setters called while detached are applied when the tray icon is
+	/// attached again.
+	pub fn detach(&self) -> crate::Result<()> {
+		run_item_main_thread!(self, |self_:Self| {
+			self_.inner.lock().unwrap().native.take();
+		})
+	}
+
+	/// Recreates the native tray icon removed by [`Self::detach`], emitting
+	/// [`TrayIconEvent::Attached`] once it is back in the system tray.
+	///
+	/// Does nothing if the tray icon is already attached.
+	pub fn attach(&self) -> crate::Result<()> {
+		let attached = run_item_main_thread!(self, |self_:Self| {
+			let mut inner = self_.inner.lock().unwrap();
+
+			if inner.native.is_some() {
+				return Ok(false);
+			}
+
+			let tray = inner.attributes.native_builder(&self_.id).build()?;
+
+			if let Some(animation) = &inner.animation {
+				tray.set_icon(Some(animation.frames[animation.current].clone()))?;
+			}
+
+			if !inner.attributes.visible {
+				tray.set_visible(false)?;
+			}
+
+			inner.native.replace(tray);
+
+			Ok::<_, tray_icon::Error>(true)
+		})??;
+
+		if attached {
+			self.app_handle.runtime_handle.create_proxy().send_event(
+				EventLoopMessage::TrayIconEvent(TrayIconEvent::Attached { id:self.id.clone() }),
+			)?;
+		}
+
+		Ok(())
+	}
+
+	/// Whether the native tray icon currently exists, see [`Self::detach`].
+	pub fn is_attached(&self) -> bool { self.inner.lock().unwrap().native.is_some() }
+
 	/// Sets the tray icon temp dir path. **Linux only**.
 	///
 	/// On Linux, we need to write the icon to the disk and usually it will
@@ -529,7 +1142,14 @@ impl<R:Runtime> TrayIcon<R> {
 		#[allow(unused)]
 		let p = path.map(|p| p.as_ref().to_path_buf());
 		#[cfg(target_os = "linux")]
-		run_item_main_thread!(self, |self_:Self| self_.inner.set_temp_dir_path(p))?;
+		{
+			let native_p = p.clone();
+
+			self.with_native(
+				move |a| a.temp_dir_path = p,
+				move |t| t.set_temp_dir_path(native_p),
+			)?;
+		}
 
 		Ok(())
 	}
@@ -537,9 +1157,10 @@ impl<R:Runtime> TrayIcon<R> {
 	/// Sets the current icon as a [template](https://developer.apple.com/documentation/appkit/nsimage/1520017-template?language=objc). **macOS only**.
 	pub fn set_icon_as_template(&self, #[allow(unused)] is_template:bool) -> crate::Result<()> {
 		#[cfg(target_os = "macos")]
-		run_item_main_thread!(self, |self_:Self| {
-			self_.inner.set_icon_as_template(is_template)
-		})?;
+		self.with_native(
+			move |a| a.icon_as_template = is_template,
+			move |t| t.set_icon_as_template(is_template),
+		)?;
 
 		Ok(())
 	}
@@ -552,9 +1173,10 @@ impl<R:Runtime> TrayIcon<R> {
 	/// - **Linux**: Unsupported.
 	pub fn set_show_menu_on_left_click(&self, #[allow(unused)] enable:bool) -> crate::Result<()> {
 		#[cfg(any(target_os = "macos", windows))]
-		run_item_main_thread!(self, |self_:Self| {
-			self_.inner.set_show_menu_on_left_click(enable)
-		})?;
+		self.with_native(
+			move |a| a.menu_on_left_click = enable,
+			move |t| t.set_show_menu_on_left_click(enable),
+		)?;
 
 		Ok(())
 	}
@@ -564,13 +1186,15 @@ impl<R:Runtime> TrayIcon<R> {
 	/// ## Platform-specific:
 	///
 	/// - **Linux**: Unsupported, always returns `None`.
+	///
+	/// Returns `None` while the tray icon is detached.
 	pub fn rect(&self) -> crate::Result<Option<crate::Rect>> {
-		run_item_main_thread!(self, |self_:Self| {
-			self_
-				.inner
-				.rect()
-				.map(|rect| Rect { position:rect.position.into(), size:rect.size.into() })
-		})
+		let rect = self.with_native(
+			|_| {},
+			|t| t.rect().map(|rect| Rect { position:rect.position.into(), size:rect.size.into() }),
+		)?;
+
+		Ok(rect.flatten())
 	}
 }
 
@@ -628,4 +1252,195 @@ mod tests {
 			})
 		);
 	}
+
+	#[test]
+	fn menu_button_presses_open_the_menu() {
+		use super::*;
+
+		let rect = crate::Rect {
+			position:tray_icon::Rect::default().position.into(),
+			size:tray_icon::Rect::default().size.into(),
+		};
+
+		let click = |button, button_state| {
+			TrayIconEvent::Click {
+				button,
+				button_state,
+				id:TrayIconId::new("id"),
+				position:crate::PhysicalPosition::default(),
+				rect,
+			}
+		};
+
+		assert!(click(MouseButton::Right, MouseButtonState::Down).opens_menu(false));
+
+		assert!(!click(MouseButton::Right, MouseButtonState::Up).opens_menu(true));
+
+		assert!(click(MouseButton::Left, MouseButtonState::Down).opens_menu(true));
+
+		assert!(!click(MouseButton::Left, MouseButtonState::Down).opens_menu(false));
+
+		assert!(!click(MouseButton::Middle, MouseButtonState::Down).opens_menu(true));
+
+		let enter = TrayIconEvent::Enter {
+			id:TrayIconId::new("id"),
+			position:crate::PhysicalPosition::default(),
+			rect,
+		};
+
+		assert!(!enter.opens_menu(true));
+	}
+
+	#[test]
+	fn invalid_theme_icons_fail_the_build() {
+		use super::*;
+		use crate::{image::Image, test::mock_app};
+
+		let app = mock_app();
+
+		// the pixels don't match the size
+		let invalid = Image::new_owned(vec![0; 4], 32, 32);
+
+		let valid = Image::new_owned(vec![0; 32 * 32 * 4], 32, 32);
+
+		assert!(TrayIconBuilder::new().theme_icons(valid, invalid).build(&app).is_err());
+	}
+
+	#[test]
+	fn theme_icons_follow_the_system_theme() {
+		use super::*;
+
+		let running = Arc::new(AtomicBool::new(true));
+
+		let inner = animated_tray(&running);
+
+		let mut inner = inner.lock().unwrap();
+
+		let icon = || tray_icon::Icon::from_rgba(vec![0; 4], 1, 1).unwrap();
+
+		// without theme icons the icon is left alone
+		assert!(!inner.attributes.apply_theme(Theme::Dark));
+
+		assert!(inner.attributes.icon.is_none());
+
+		inner.attributes.theme_icons = Some((icon(), icon()));
+
+		// the icon of the theme is kept for when the animation stops
+		inner.show_theme_icon(Theme::Dark).unwrap();
+
+		assert_eq!(inner.attributes.icon_theme, Some(Theme::Dark));
+
+		assert!(inner.attributes.icon.is_some());
+
+		assert!(inner.animation.is_some());
+
+		// the same theme reported again doesn't set the icon again
+		assert!(!inner.attributes.apply_theme(Theme::Dark));
+
+		assert!(inner.attributes.apply_theme(Theme::Light));
+
+		assert_eq!(inner.attributes.icon_theme, Some(Theme::Light));
+
+		inner.attributes.theme_icons = None;
+
+		assert!(!inner.attributes.apply_theme(Theme::Dark));
+
+		assert_eq!(inner.attributes.icon_theme, Some(Theme::Light));
+	}
+
+	fn animated_tray(
+		running:&std::sync::Arc<std::sync::atomic::AtomicBool>,
+	) -> std::sync::Arc<std::sync::Mutex<super::TrayIconInner>> {
+		use super::*;
+
+		let frame = || tray_icon::Icon::from_rgba(vec![0; 4], 1, 1).unwrap();
+
+		Arc::new(Mutex::new(TrayIconInner {
+			native:None,
+			attributes:Default::default(),
+			animation:Some(TrayIconAnimation {
+				frames:vec![frame(), frame(), frame()],
+				current:0,
+				running:running.clone(),
+			}),
+		}))
+	}
+
+	#[test]
+	fn icon_animations_loop_over_their_frames() {
+		use super::*;
+
+		let running = Arc::new(AtomicBool::new(true));
+
+		let inner = animated_tray(&running);
+
+		let mut inner = inner.lock().unwrap();
+
+		for expected in [1, 2, 0, 1] {
+			inner.next_animation_frame(&running);
+
+			assert_eq!(inner.animation.as_ref().unwrap().current, expected);
+		}
+
+		// a timer of a replaced animation doesn't move the new one
+		inner.next_animation_frame(&Arc::new(AtomicBool::new(true)));
+
+		assert_eq!(inner.animation.as_ref().unwrap().current, 1);
+	}
+
+	#[test]
+	fn icon_animation_timers_stop_with_the_animation() {
+		use super::*;
+		use crate::test::mock_app;
+
+		let app = mock_app();
+
+		let running = Arc::new(AtomicBool::new(true));
+
+		let inner = animated_tray(&running);
+
+		let timer = crate::async_runtime::spawn(run_icon_animation(
+			app.handle().clone(),
+			Arc::downgrade(&inner),
+			running.clone(),
+			Duration::from_millis(1),
+		));
+
+		crate::async_runtime::block_on(async {
+			let advanced = async {
+				while inner.lock().unwrap().animation.as_ref().unwrap().current == 0 {
+					tokio::time::sleep(Duration::from_millis(1)).await;
+				}
+			};
+
+			tokio::time::timeout(Duration::from_secs(5), advanced)
+				.await
+				.expect("the animation didn't advance");
+
+			running.store(false, Ordering::Relaxed);
+
+			tokio::time::timeout(Duration::from_secs(5), timer)
+				.await
+				.expect("the timer didn't stop")
+				.unwrap();
+		});
+
+		let running = Arc::new(AtomicBool::new(true));
+
+		let inner = animated_tray(&running);
+
+		let timer = crate::async_runtime::spawn(run_icon_animation(
+			app.handle().clone(),
+			Arc::downgrade(&inner),
+			running,
+			Duration::from_millis(1),
+		));
+
+		// dropping the tray icon stops the timer too
+		drop(inner);
+
+		crate::async_runtime::block_on(tokio::time::timeout(Duration::from_secs(5), timer))
+			.expect("the timer didn't stop")
+			.unwrap();
+	}
 }
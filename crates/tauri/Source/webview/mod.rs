@@ -12,7 +12,12 @@ pub use webview_window::{WebviewWindow, WebviewWindowBuilder};
 use http::HeaderMap;
 use serde::Serialize;
 use tauri_macros::default_runtime;
-pub use tauri_runtime::webview::PageLoadEvent;
+pub use tauri_runtime::webview::{
+  Anchors, Cookie, DataTypes, DevtoolsDock, DevtoolsOptions, DockSide, FindOptions, FindResult,
+  NavigationHistory, PageLoadError, PageLoadErrorKind, PageLoadEvent, PdfMargins, PdfOptions,
+  PdfPageSize, ProxyAuthenticationChallenge, ProxyConfig, ProxyCredentials, SameSite,
+  WebviewLayout,
+};
 #[cfg(desktop)]
 use tauri_runtime::{
   dpi::{PhysicalPosition, PhysicalSize, Position, Size},
@@ -28,7 +33,7 @@ pub use url::Url;
 
 use crate::{
   app::{UriSchemeResponder, WebviewEvent},
-  event::{EmitArgs, EventTarget},
+  event::{EmitArgs, EventPayloadRef, EventTarget},
   ipc::{
     CallbackFn, CommandArg, CommandItem, CommandScope, GlobalScope, Invoke, InvokeBody,
     InvokeError, InvokeMessage, InvokeResolver, Origin, OwnedInvokeResponder, ScopeObject,
@@ -55,6 +60,11 @@ pub(crate) type OnPageLoad<R> = dyn Fn(Webview<R>, PageLoadPayload<'_>) + Send +
 
 pub(crate) type DownloadHandler<R> = dyn Fn(Webview<R>, DownloadEvent<'_>) -> bool + Send + Sync;
 
+pub(crate) type DragDropFilter = dyn Fn(&crate::DragDropEvent) -> bool + Send;
+
+pub(crate) type ProxyAuthenticationHandler<R> =
+  dyn Fn(Webview<R>, ProxyAuthenticationChallenge) -> Option<ProxyCredentials> + Send + Sync;
+
 #[derive(Clone, Serialize)]
 pub(crate) struct CreatedEvent {
   pub(crate) label: String,
@@ -93,10 +103,11 @@ pub enum DownloadEvent<'a> {
 }
 
 /// The payload for the [`WebviewBuilder::on_page_load`] hook.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PageLoadPayload<'a> {
   pub(crate) url: &'a Url,
   pub(crate) event: PageLoadEvent,
+  pub(crate) error: Option<&'a PageLoadError>,
 }
 
 impl<'a> PageLoadPayload<'a> {
@@ -109,8 +120,18 @@ impl<'a> PageLoadPayload<'a> {
   pub fn event(&self) -> PageLoadEvent {
     self.event
   }
+
+  /// The page load failure details, set when the event is [`PageLoadEvent::Failed`].
+  pub fn error(&self) -> Option<&'a PageLoadError> {
+    self.error
+  }
 }
 
+/// A handle to an initialization script added with
+/// [`Webview::add_initialization_script`], used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScriptHandle(tauri_runtime::InitializationScriptId);
+
 /// The IPC invoke request.
 ///
 /// # Stability
@@ -237,6 +258,8 @@ unstable_struct!(
     pub(crate) navigation_handler: Option<Box<NavigationHandler>>,
     pub(crate) on_page_load_handler: Option<Box<OnPageLoad<R>>>,
     pub(crate) download_handler: Option<Arc<DownloadHandler<R>>>,
+    pub(crate) drag_drop_filter: Option<Box<DragDropFilter>>,
+    pub(crate) proxy_authentication_handler: Option<Box<ProxyAuthenticationHandler<R>>>,
   }
 );
 
@@ -313,6 +336,8 @@ async fn create_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      drag_drop_filter: None,
+      proxy_authentication_handler: None,
     }
   }
 
@@ -353,6 +378,8 @@ async fn reopen_window(app: tauri::AppHandle) {
       navigation_handler: None,
       on_page_load_handler: None,
       download_handler: None,
+      drag_drop_filter: None,
+      proxy_authentication_handler: None,
     }
   }
 
@@ -499,8 +526,10 @@ tauri::Builder::default()
   }
 
   /// Defines a closure to be executed when a page load event is triggered.
-  /// The event can be either [`PageLoadEvent::Started`] if the page has started loading
-  /// or [`PageLoadEvent::Finished`] when the page finishes loading.
+  /// The event can be either [`PageLoadEvent::Started`] if the page has started loading,
+  /// [`PageLoadEvent::Finished`] when the page finishes loading
+  /// or [`PageLoadEvent::Failed`] when the navigation failed, in which case
+  /// [`PageLoadPayload::error`] describes the failure.
   ///
   /// # Examples
   ///
@@ -527,6 +556,9 @@ tauri::Builder::default()
           PageLoadEvent::Finished => {
             println!("{} finished loading", payload.url());
           }
+          PageLoadEvent::Failed => {
+            println!("{} failed to load: {:?}", payload.url(), payload.error());
+          }
         }
       });
     let webview = window.add_child(webview_builder, tauri::LogicalPosition::new(0, 0), window.inner_size().unwrap())?;
@@ -550,8 +582,21 @@ tauri::Builder::default()
   ) -> crate::Result<PendingWebview<EventLoopMessage, R>> {
     let mut pending = PendingWebview::new(self.webview_attributes, self.label.clone())?;
     pending.navigation_handler = self.navigation_handler.take();
+    pending.drag_drop_filter = self.drag_drop_filter.take();
     pending.web_resource_request_handler = self.web_resource_request_handler.take();
 
+    if let Some(proxy_authentication_handler) = self.proxy_authentication_handler.take() {
+      let label = pending.label.clone();
+      let manager = manager.manager_owned();
+      pending
+        .proxy_authentication_handler
+        .replace(Box::new(move |challenge| {
+          manager
+            .get_webview(&label)
+            .and_then(|w| proxy_authentication_handler(w, challenge))
+        }));
+    }
+
     if let Some(download_handler) = self.download_handler.take() {
       let label = pending.label.clone();
       let manager = manager.manager_owned();
@@ -578,10 +623,17 @@ tauri::Builder::default()
     let manager_ = manager.manager_owned();
     pending
       .on_page_load_handler
-      .replace(Box::new(move |url, event| {
+      .replace(Box::new(move |url, event, error| {
         if let Some(w) = manager_.get_webview(&label_) {
           if let Some(handler) = self.on_page_load_handler.as_ref() {
-            handler(w, PageLoadPayload { url: &url, event });
+            handler(
+              w,
+              PageLoadPayload {
+                url: &url,
+                event,
+                error: error.as_ref(),
+              },
+            );
           }
         }
       }));
@@ -637,6 +689,8 @@ impl<R: Runtime> WebviewBuilder<R> {
   /// Since it runs on all top-level document and child frame page navigations,
   /// it's recommended to check the `window.location` to guard your script from running on unexpected origins.
   ///
+  /// See [`Self::initialization_script_for_main_frame_only`] to only run it on the main frame.
+  ///
   /// # Examples
   ///
   #[cfg_attr(
@@ -668,10 +722,19 @@ fn main() {
   )]
   #[must_use]
   pub fn initialization_script(mut self, script: &str) -> Self {
+    self.webview_attributes = self.webview_attributes.initialization_script(script);
     self
+  }
+
+  /// Adds the provided JavaScript to a list of scripts that should be run after the global object has been created,
+  /// but before the HTML document has been parsed and before any other script included by the HTML document is run.
+  ///
+  /// The script only runs on the main frame.
+  #[must_use]
+  pub fn initialization_script_for_main_frame_only(mut self, script: &str) -> Self {
+    self.webview_attributes = self
       .webview_attributes
-      .initialization_scripts
-      .push(script.to_string());
+      .initialization_script_for_main_frame_only(script);
     self
   }
 
@@ -682,6 +745,20 @@ fn main() {
     self
   }
 
+  /// Appends `suffix`, separated by a space, to the default user agent of the platform webview
+  /// instead of replacing it, or to the one set with [`Self::user_agent`].
+  ///
+  /// The initial page is loaded once the user agent is updated.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn append_user_agent(mut self, suffix: &str) -> Self {
+    self.webview_attributes.append_user_agent = Some(suffix.to_string());
+    self
+  }
+
   /// Set additional arguments for the webview.
   ///
   /// ## Platform-specific
@@ -715,6 +792,26 @@ fn main() {
     self
   }
 
+  /// Defines a closure deciding whether a drag operation entering the webview is accepted,
+  /// called with the [`DragDropEvent::Enter`](crate::DragDropEvent::Enter) event.
+  ///
+  /// A rejected drag does not emit any drag and drop event, shows the "no drop" cursor and
+  /// cannot be dropped.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: The drag is filtered once its data is received, so the "no drop" cursor
+  ///   is shown from the next cursor move.
+  /// - **Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn drag_drop_filter<F: Fn(&crate::DragDropEvent) -> bool + Send + 'static>(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.drag_drop_filter.replace(Box::new(f));
+    self
+  }
+
   /// Enables clipboard access for the page rendered on **Linux** and **Windows**.
   ///
   /// **macOS** doesn't provide such method and is always enabled by default,
@@ -745,7 +842,64 @@ fn main() {
   /// - **macOS**: Requires the `macos-proxy` feature flag and only compiles for macOS 14+.
   #[must_use]
   pub fn proxy_url(mut self, url: Url) -> Self {
-    self.webview_attributes.proxy_url = Some(url);
+    self.webview_attributes = self.webview_attributes.proxy_url(url);
+    self
+  }
+
+  /// Set the proxy for the WebView for all network requests, with the hosts that bypass it
+  /// and the credentials it requires.
+  ///
+  /// The proxy URL must be either a `http://` or a `socks5://` URL.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: The bypass list requires [`Self::additional_browser_args`], as they
+  ///   replace the default arguments. The credentials are unsupported, WebView2 prompts for them.
+  /// - **Linux**: The proxy is shared by the webviews using the same data directory.
+  /// - **macOS**: Requires the `macos-proxy` feature flag and only compiles for macOS 14+.
+  ///   The bypass list and the credentials are unsupported.
+  /// - **Android / iOS**: Unsupported.
+  ///
+  /// The webview creation fails when an unsupported setting is used.
+  #[must_use]
+  pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+    self.webview_attributes = self.webview_attributes.proxy(proxy);
+    self
+  }
+
+  /// Adds a cookie to the webview data store before the initial page load, so it is sent with
+  /// the first request.
+  ///
+  /// A cookie without a domain is a host-only cookie of the initial URL. The cookie is a session
+  /// cookie unless it has an expiration date or a max age.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android**: Unsupported.
+  #[must_use]
+  pub fn cookie(mut self, cookie: Cookie<'static>) -> Self {
+    self.webview_attributes = self.webview_attributes.cookie(cookie);
+    self
+  }
+
+  /// Defines a closure answering the proxy authentication challenges of the webview,
+  /// `None` cancelling the request.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux**: Called for the challenges the [`ProxyConfig::credentials`] do not answer.
+  /// - **Windows / macOS / Android / iOS**: Unsupported, the webview creation fails.
+  #[must_use]
+  pub fn on_proxy_authentication<
+    F: Fn(Webview<R>, ProxyAuthenticationChallenge) -> Option<ProxyCredentials>
+      + Send
+      + Sync
+      + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.proxy_authentication_handler.replace(Box::new(f));
     self
   }
 
@@ -775,6 +929,47 @@ fn main() {
     self
   }
 
+  /// Sets how the webview is moved and resized when the parent window resizes, taking precedence
+  /// over [`Self::auto_resize`].
+  ///
+  /// The layout is applied on the event loop as soon as the window is resized, before it is
+  /// painted, so the webview does not lag behind the window.
+  #[must_use]
+  pub fn layout(mut self, layout: WebviewLayout) -> Self {
+    self.webview_attributes.layout = Some(layout);
+    self
+  }
+
+  /// Keeps the distances of the webview to the `anchors` window edges when the parent window
+  /// resizes, see [`Self::layout`].
+  ///
+  /// # Examples
+  ///
+  #[cfg_attr(
+    feature = "unstable",
+    doc = r####"
+```rust,no_run
+use tauri::webview::{Anchors, WebviewBuilder};
+
+// a sidebar keeping its width and stretching with the window height
+let sidebar = WebviewBuilder::<tauri::Wry>::new("sidebar", tauri::WebviewUrl::App("sidebar.html".into()))
+  .anchors(Anchors { left: true, top: true, bottom: true, right: false });
+```
+  "####
+  )]
+  #[must_use]
+  pub fn anchors(self, anchors: Anchors) -> Self {
+    self.layout(WebviewLayout::Anchored(anchors))
+  }
+
+  /// Attaches the webview to the `side` of the parent window, `size` being its width for the
+  /// left and right sides or its height for the top and bottom sides in logical pixels. The
+  /// webview fills the other dimension of the window, see [`Self::layout`].
+  #[must_use]
+  pub fn dock(self, side: DockSide, size: f64) -> Self {
+    self.layout(WebviewLayout::Docked { side, size })
+  }
+
   /// Whether page zooming by hotkeys is enabled
   ///
   /// ## Platform-specific:
@@ -790,6 +985,19 @@ fn main() {
     self
   }
 
+  /// Sets the initial zoom factor of the webview, `1.0` being the default size.
+  ///
+  /// The zoom is kept across the navigations of the webview.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn zoom(mut self, scale_factor: f64) -> Self {
+    self.webview_attributes.zoom = Some(scale_factor);
+    self
+  }
+
   /// Whether browser extensions can be installed for the webview process
   ///
   /// ## Platform-specific:
@@ -879,7 +1087,11 @@ pub struct Webview<R: Runtime> {
   pub(crate) manager: Arc<AppManager<R>>,
   pub(crate) app_handle: AppHandle<R>,
   pub(crate) resources_table: Arc<Mutex<ResourceTable>>,
+  /// The script setting the label of the window in the page metadata after a reparent.
+  window_label_script: Arc<Mutex<Option<ScriptHandle>>>,
   use_https_scheme: bool,
+  /// Sends the scripts of a remote frontend connected with an invoke transport.
+  remote: Option<Arc<crate::ipc::transport::RemoteSender>>,
 }
 
 impl<R: Runtime> std::fmt::Debug for Webview<R> {
@@ -900,7 +1112,9 @@ impl<R: Runtime> Clone for Webview<R> {
       manager: self.manager.clone(),
       app_handle: self.app_handle.clone(),
       resources_table: self.resources_table.clone(),
+      window_label_script: self.window_label_script.clone(),
       use_https_scheme: self.use_https_scheme,
+      remote: self.remote.clone(),
     }
   }
 }
@@ -934,10 +1148,39 @@ impl<R: Runtime> Webview<R> {
       window: Arc::new(Mutex::new(window)),
       webview,
       resources_table: Default::default(),
+      window_label_script: Default::default(),
       use_https_scheme,
+      remote: None,
     }
   }
 
+  /// The webview of a remote frontend hosted by the given window, evaluating its scripts with
+  /// `send`.
+  ///
+  /// The runtime webview is the first webview of the window.
+  pub(crate) fn remote(
+    window: Window<R>,
+    label: String,
+    send: Arc<crate::ipc::transport::RemoteSender>,
+  ) -> crate::Result<Self> {
+    let host = window
+      .webviews()
+      .into_iter()
+      .next()
+      .ok_or(crate::Error::WebviewNotFound)?;
+
+    let webview = DetachedWebview {
+      label,
+      dispatcher: host.webview.dispatcher.clone(),
+    };
+
+    let mut remote = Self::new(window, webview, host.use_https_scheme);
+
+    remote.remote = Some(send);
+
+    Ok(remote)
+  }
+
   /// Initializes a webview builder with the given window label and URL to load on the webview.
   ///
   /// Data URLs are only supported with the `webview-data-url` feature flag.
@@ -1017,21 +1260,10 @@ impl<R: Runtime> Webview<R> {
     plugin: &str,
     command: &str,
   ) -> crate::Result<Option<ResolvedScope<T>>> {
-    let current_url = self.url()?;
-    let is_local = self.is_local_url(&current_url);
-    let origin = if is_local {
-      Origin::Local
-    } else {
-      Origin::Remote { url: current_url }
-    };
+    let origin = self.acl_origin()?;
 
     let cmd_name = format!("plugin:{plugin}|{command}");
-    let resolved_access = self
-      .manager()
-      .runtime_authority
-      .lock()
-      .unwrap()
-      .resolve_access(&cmd_name, self.window().label(), self.label(), &origin);
+    let resolved_access = self.resolve_access(&cmd_name, &origin);
 
     if let Some(access) = resolved_access {
       let scope_ids = access
@@ -1062,6 +1294,150 @@ impl<R: Runtime> Webview<R> {
     self.webview.dispatcher.print().map_err(Into::into)
   }
 
+  /// Captures the contents of the webview as an RGBA image, limited to
+  /// `region` if given, relative to the top-left corner of the webview.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: The webview is captured even if the window is occluded, but not when it
+  ///   is minimized.
+  /// - **Linux**: The webview is captured even if the window is occluded.
+  pub async fn capture(
+    &self,
+    region: Option<tauri_runtime::Rect>,
+  ) -> crate::Result<crate::image::Image<'static>> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self.webview.dispatcher.capture(region, move |bitmap| {
+      let _ = tx.send(bitmap);
+    })?;
+
+    let bitmap = rx
+      .await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))??;
+
+    Ok(crate::image::Image::new_owned(
+      bitmap.rgba.into_owned(),
+      bitmap.width,
+      bitmap.height,
+    ))
+  }
+
+  /// Prints the contents of the webview to a PDF document, returning its bytes.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: Requires WebView2 Runtime 1.0.1518 or later.
+  /// - **Linux**: [`PdfOptions::print_background`] is ignored, the backgrounds follow the
+  ///   `print-color-adjust` CSS property of the page.
+  /// - **macOS**: The whole page is rendered on a single PDF page, the page size, margins and
+  ///   orientation are ignored and the backgrounds are always printed.
+  pub async fn print_to_pdf(&self, options: PdfOptions) -> crate::Result<Vec<u8>> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self.webview.dispatcher.print_to_pdf(options, move |pdf| {
+      let _ = tx.send(pdf);
+    })?;
+
+    rx.await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))?
+      .map_err(Into::into)
+  }
+
+  /// Finds `query` in the page, highlighting the matches and returning the search result.
+  ///
+  /// Searching the same query again moves the active match to the next one, in the direction
+  /// given by [`FindOptions::backwards`]. [`crate::WebviewEvent::FindResultChanged`] is emitted
+  /// when the matches change, for instance to show the active match among the match count.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: Requires WebView2 Runtime 1.0.2849 or later. The search always wraps around
+  ///   and a new search always starts at the first match.
+  /// - **Linux**: Up to 1000 matches are counted and the active match index is not reported.
+  /// - **macOS**: The matches are counted in the page text, the active match index is not
+  ///   reported.
+  /// - **iOS / Android**: Unsupported.
+  pub async fn find_in_page(&self, query: &str, options: FindOptions) -> crate::Result<FindResult> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self
+      .webview
+      .dispatcher
+      .find_in_page(query.into(), options, move |result| {
+        let _ = tx.send(result);
+      })?;
+
+    rx.await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))?
+      .map_err(Into::into)
+  }
+
+  /// Ends the search started with [`Self::find_in_page`], removing the highlights if
+  /// `clear_highlights` is true.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS**: The highlight is the page selection, which is cleared.
+  /// - **iOS / Android**: Unsupported.
+  pub fn stop_find(&self, clear_highlights: bool) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .stop_find(clear_highlights)
+      .map_err(Into::into)
+  }
+
+  /// Adds a script that runs on the main frame and every child frame before any other script of
+  /// the page, on every navigation that starts after this call, returning a handle to remove it.
+  ///
+  /// Unlike [`WebviewBuilder::initialization_script`], the current page is not affected.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android**: Unsupported.
+  pub fn add_initialization_script(&self, script: &str) -> crate::Result<ScriptHandle> {
+    self.add_script(script, false)
+  }
+
+  /// Adds a script that runs on the main frame before any other script of the page, on every
+  /// navigation that starts after this call, returning a handle to remove it.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: WebView2 runs the script on every frame, so it is wrapped in a
+  ///   `window.self === window.top` check and its top-level `let`, `const` and `class`
+  ///   declarations are not global.
+  /// - **iOS / Android**: Unsupported.
+  pub fn add_initialization_script_for_main_frame_only(
+    &self,
+    script: &str,
+  ) -> crate::Result<ScriptHandle> {
+    self.add_script(script, true)
+  }
+
+  fn add_script(&self, script: &str, for_main_frame_only: bool) -> crate::Result<ScriptHandle> {
+    self
+      .webview
+      .dispatcher
+      .add_initialization_script(tauri_runtime::webview::InitializationScript {
+        script: script.to_string(),
+        for_main_frame_only,
+      })
+      .map(ScriptHandle)
+      .map_err(Into::into)
+  }
+
+  /// Removes a script added with [`Self::add_initialization_script`] or
+  /// [`Self::add_initialization_script_for_main_frame_only`], from the next navigation on.
+  pub fn remove_initialization_script(&self, handle: ScriptHandle) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .remove_initialization_script(handle.0)
+      .map_err(Into::into)
+  }
+
   /// Get the cursor position relative to the top-left hand corner of the desktop.
   ///
   /// Note that the top-left hand corner of the desktop is not necessarily the same as the screen.
@@ -1081,7 +1457,8 @@ impl<R: Runtime> Webview<R> {
     Ok(())
   }
 
-  /// Resizes this webview.
+  /// Moves and resizes this webview at once, without the intermediate layout of separate
+  /// [`Self::set_position`] and [`Self::set_size`] calls.
   pub fn set_bounds(&self, bounds: tauri_runtime::Rect) -> crate::Result<()> {
     self
       .webview
@@ -1123,8 +1500,32 @@ impl<R: Runtime> Webview<R> {
     self.webview.dispatcher.show().map_err(Into::into)
   }
 
-  /// Move the webview to the given window.
+  /// Moves the webview to the given window, keeping its bounds relative to the window.
+  ///
+  /// Events, IPC origin checks and capabilities are resolved against the new window from then on:
+  /// the access of the webview is resolved again on its next invoke, so the commands it can call
+  /// and their scopes follow the capabilities of that window.
+  /// The webview is left in its window if it could not be moved.
   pub fn reparent(&self, window: &Window<R>) -> crate::Result<()> {
+    self.reparent_inner(window, None)
+  }
+
+  /// Moves the webview to the given window at the given bounds relative to it.
+  ///
+  /// See [`Self::reparent`] for more information.
+  pub fn reparent_with_bounds(
+    &self,
+    window: &Window<R>,
+    bounds: tauri_runtime::Rect,
+  ) -> crate::Result<()> {
+    self.reparent_inner(window, Some(bounds))
+  }
+
+  fn reparent_inner(
+    &self,
+    window: &Window<R>,
+    bounds: Option<tauri_runtime::Rect>,
+  ) -> crate::Result<()> {
     #[cfg(not(feature = "unstable"))]
     {
       if self.window_ref().is_webview_window() || window.is_webview_window() {
@@ -1132,8 +1533,31 @@ impl<R: Runtime> Webview<R> {
       }
     }
 
+    self.webview.dispatcher.reparent(window.window.id, bounds)?;
     *self.window.lock().unwrap() = window.clone();
-    self.webview.dispatcher.reparent(window.window.id)?;
+
+    if let Err(e) = self.sync_window_label(window.label()) {
+      log::error!("failed to update the window label of webview `{}`: {e}", self.label());
+    }
+
+    Ok(())
+  }
+
+  /// Updates the label of the window in the page metadata, for the current and next pages.
+  fn sync_window_label(&self, label: &str) -> crate::Result<()> {
+    let script = format!(
+      "window.__TAURI_INTERNALS__.metadata.currentWindow.label = {}",
+      serde_json::to_string(label)?
+    );
+
+    self.eval(&script)?;
+
+    let mut window_label_script = self.window_label_script.lock().unwrap();
+    if let Some(handle) = window_label_script.take() {
+      self.remove_initialization_script(handle)?;
+    }
+    *window_label_script = Some(self.add_initialization_script(&script)?);
+
     Ok(())
   }
 
@@ -1146,6 +1570,18 @@ impl<R: Runtime> Webview<R> {
       .map_err(Into::into)
   }
 
+  /// Sets how the webview is moved and resized when the parent window resizes, resolved against
+  /// its current bounds. `None` keeps the webview bounds.
+  ///
+  /// See [`WebviewBuilder::layout`] for more information.
+  pub fn set_layout(&self, layout: Option<WebviewLayout>) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .set_layout(layout)
+      .map_err(Into::into)
+  }
+
   /// Returns the bounds of the webviews's client area.
   pub fn bounds(&self) -> crate::Result<tauri_runtime::Rect> {
     self.webview.dispatcher.bounds().map_err(Into::into)
@@ -1268,6 +1704,82 @@ fn main() {
     self.webview.dispatcher.navigate(url).map_err(Into::into)
   }
 
+  /// Navigates back in the webview history, using the native history of the platform webview
+  /// instead of the `history` object of the page.
+  ///
+  /// [`crate::WebviewEvent::HistoryChanged`] is emitted when [`Self::can_go_back`] or
+  /// [`Self::can_go_forward`] change.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS**: The availability changes are only checked when a page load starts or finishes,
+  ///   so the same-document navigations are reported with the next page load.
+  /// - **Android / iOS**: Unsupported.
+  pub fn go_back(&self) -> crate::Result<()> {
+    self.webview.dispatcher.go_back().map_err(Into::into)
+  }
+
+  /// Navigates forward in the webview history.
+  ///
+  /// See [`Self::go_back`] for the platform specific details.
+  pub fn go_forward(&self) -> crate::Result<()> {
+    self.webview.dispatcher.go_forward().map_err(Into::into)
+  }
+
+  /// Whether the webview can navigate back in its history.
+  ///
+  /// See [`Self::go_back`] for the platform specific details.
+  pub fn can_go_back(&self) -> crate::Result<bool> {
+    self.webview.dispatcher.can_go_back().map_err(Into::into)
+  }
+
+  /// Whether the webview can navigate forward in its history.
+  ///
+  /// See [`Self::go_back`] for the platform specific details.
+  pub fn can_go_forward(&self) -> crate::Result<bool> {
+    self.webview.dispatcher.can_go_forward().map_err(Into::into)
+  }
+
+  /// Reloads the current page, bypassing the cache if `ignore_cache` is true.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: Bypassing the cache uses the `Page.reload` DevTools protocol method.
+  /// - **Android / iOS**: Unsupported.
+  pub fn reload(&self, ignore_cache: bool) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .reload(ignore_cache)
+      .map_err(Into::into)
+  }
+
+  /// The ACL origin of the webview's current URL.
+  pub(crate) fn acl_origin(&self) -> crate::Result<Origin> {
+    let current_url = self.url()?;
+    if self.is_local_url(&current_url) {
+      Ok(Origin::Local)
+    } else {
+      Ok(Origin::Remote { url: current_url })
+    }
+  }
+
+  /// Resolves the access of the webview to the given command against the capabilities of the
+  /// window currently hosting it, so the access follows the webview when it is reparented.
+  pub(crate) fn resolve_access(
+    &self,
+    command: &str,
+    origin: &Origin,
+  ) -> Option<Vec<tauri_utils::acl::resolved::ResolvedCommand>> {
+    let window_label = self.window_label();
+    self
+      .manager()
+      .runtime_authority
+      .lock()
+      .unwrap()
+      .resolve_access(command, &window_label, self.label(), origin)
+  }
+
   fn is_local_url(&self, current_url: &Url) -> bool {
     let uses_https = current_url.scheme() == "https";
 
@@ -1334,11 +1846,27 @@ fn main() {
       return;
     }
 
+    // the request body stream is removed once the invoke settles or is dropped
+    let (request_stream, request_stream_error) = match self
+      .state::<crate::ipc::body_stream::RequestStreamRegistry>()
+      .open(self.label(), &request.headers)
+    {
+      Ok(request_stream) => (request_stream, None),
+      Err(error) => (None, Some(error)),
+    };
+
+    // counted until the invoke is answered or dropped
+    let pending_invoke = self
+      .state::<crate::ipc::cancel::CancellationRegistry>()
+      .start(self.label(), &request.cmd);
+
     let resolver = InvokeResolver::new(
       self.clone(),
       Arc::new(Mutex::new(Some(Box::new(
         #[allow(unused_variables)]
         move |webview: Webview<R>, cmd, response, callback, error| {
+          drop(request_stream);
+          drop(pending_invoke);
           responder(webview, cmd, response, callback, error);
         },
       )))),
@@ -1347,9 +1875,20 @@ fn main() {
       request.error,
     );
 
-    #[cfg(mobile)]
+    if let Some(error) = request_stream_error {
+      resolver.reject(error);
+      return;
+    }
+
+    self
+      .state::<crate::ipc::response_stream::ResponseStreamRegistry>()
+      .request(self.label(), request.callback.0, &request.headers);
+
+    #[cfg(mobile)]
     let app_handle = self.app_handle.clone();
 
+    let invoke_id = crate::ipc::cancel::invoke_id(&request.headers);
+
     let message = InvokeMessage::new(
       self,
       manager.state(),
@@ -1358,6 +1897,11 @@ fn main() {
       request.headers,
     );
 
+    message
+      .webview
+      .state::<crate::ipc::cancel::CancellationRegistry>()
+      .register(message.webview.label(), invoke_id, &message.cancellation);
+
     let acl_origin = if is_local {
       Origin::Local
     } else {
@@ -1365,16 +1909,8 @@ fn main() {
         url: request.url.clone(),
       }
     };
-    let (resolved_acl, has_app_acl_manifest) = {
-      let runtime_authority = manager.runtime_authority.lock().unwrap();
-      let acl = runtime_authority.resolve_access(
-        &request.cmd,
-        message.webview.window_ref().label(),
-        message.webview.label(),
-        &acl_origin,
-      );
-      (acl, runtime_authority.has_app_manifest())
-    };
+    let resolved_acl = message.webview.resolve_access(&request.cmd, &acl_origin);
+    let has_app_acl_manifest = manager.runtime_authority.lock().unwrap().has_app_manifest();
 
     let mut invoke = Invoke {
       message,
@@ -1393,6 +1929,13 @@ fn main() {
     // we only check ACL on plugin commands or if the app defined its ACL manifest
     if (plugin_command.is_some() || has_app_acl_manifest)
       && request.cmd != crate::ipc::channel::FETCH_CHANNEL_DATA_COMMAND
+      && request.cmd != crate::ipc::stream::CANCEL_STREAM_COMMAND
+      && request.cmd != crate::ipc::cancel::CANCEL_INVOKE_COMMAND
+      && request.cmd != crate::ipc::response_stream::PULL_RESPONSE_CHUNK_COMMAND
+      && request.cmd != crate::ipc::response_stream::CANCEL_RESPONSE_STREAM_COMMAND
+      && request.cmd != crate::ipc::channel::ACK_CHANNEL_MESSAGE_COMMAND
+      && request.cmd != crate::ipc::channel::CLOSE_CHANNEL_COMMAND
+      && request.cmd != crate::ipc::channel::ACK_EVENT_COMMAND
       && invoke.acl.is_none()
     {
       #[cfg(debug_assertions)]
@@ -1408,7 +1951,7 @@ fn main() {
             .resolve_access_message(
               key,
               &command_name,
-              invoke.message.webview.window().label(),
+              &invoke.message.webview.window_label(),
               invoke.message.webview.label(),
               &acl_origin,
             ),
@@ -1485,6 +2028,12 @@ fn main() {
 
   /// Evaluates JavaScript on this window.
   pub fn eval(&self, js: &str) -> crate::Result<()> {
+    if let Some(send) = &self.remote {
+      send(js.to_string());
+
+      return Ok(());
+    }
+
     self.webview.dispatcher.eval_script(js).map_err(Into::into)
   }
 
@@ -1494,6 +2043,7 @@ fn main() {
     event: &str,
     target: EventTarget,
     handler: CallbackFn,
+    filter: Option<crate::event::PayloadFilter>,
   ) -> crate::Result<EventId> {
     let listeners = self.manager().listeners();
 
@@ -1507,7 +2057,13 @@ fn main() {
       &format!("window['_{}']", handler.0),
     ))?;
 
-    listeners.listen_js(event, self.label(), target, id);
+    let replay = listeners.is_replayed(event).then(|| target.clone());
+
+    listeners.listen_js(event, self.label(), target, id, filter);
+
+    if let Some(target) = replay {
+      self.manager().replay_events(event, &target);
+    }
 
     Ok(id)
   }
@@ -1528,11 +2084,120 @@ fn main() {
   }
 
   pub(crate) fn emit_js(&self, emit_args: &EmitArgs, ids: &[u32]) -> crate::Result<()> {
-    self.eval(&crate::event::emit_js_script(
-      self.manager().listeners().function_name(),
-      emit_args,
-      &serde_json::to_string(ids)?,
-    )?)?;
+    let listeners = self.manager().listeners();
+    // acknowledged once the listeners handled an `emit_and_wait` event
+    let ack = emit_args
+      .completion
+      .as_ref()
+      .map(|completion| completion.wait_for_ack(listeners, self.label()));
+
+    let function_name = listeners.function_name();
+    let ids = serde_json::to_string(ids)?;
+
+    match &emit_args.bytes {
+      // fetched by the webview as a raw IPC response, like the channel data
+      Some(bytes) => {
+        let data_id = crate::ipc::channel::queue_data(self, bytes.clone());
+        self.eval(&crate::event::emit_binary_js_script(
+          function_name,
+          emit_args,
+          data_id,
+          &ids,
+          ack,
+        ))?;
+      }
+      None => {
+        self.eval(&crate::event::emit_js_script(
+          function_name,
+          emit_args,
+          &ids,
+          ack,
+        )?)?;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Emits an event to the webview with the given label, only delivered to the
+  /// listeners it registered for this webview with [`Self::listen_private`] or
+  /// `listenPrivate` on the JavaScript side.
+  ///
+  /// Private events never reach the broadcast listeners, including
+  /// [`Listener::listen_any`] and the `event` plugin listeners.
+  ///
+  /// # Examples
+  /// ```
+  /// #[tauri::command]
+  /// fn share_token(webview: tauri::Webview, token: String) -> tauri::Result<()> {
+  ///   webview.emit_private("main", "token", token)
+  /// }
+  /// ```
+  pub fn emit_private<S: Serialize>(
+    &self,
+    target: &str,
+    event: &str,
+    payload: S,
+  ) -> crate::Result<()> {
+    crate::event::assert_event_name_is_valid(event);
+
+    let mut emit_args = EmitArgs::new(event, payload)?;
+    emit_args.target = Some(EventTarget::webview(target));
+
+    self.manager().emit_private(self.label(), target, emit_args)
+  }
+
+  /// Listens to the `event` events the webview with the `source` label emits to
+  /// this webview with [`Self::emit_private`].
+  pub fn listen_private<F>(&self, source: &str, event: impl Into<String>, handler: F) -> EventId
+  where
+    F: Fn(Event) + Send + Sync + 'static,
+  {
+    let event = event.into();
+    crate::event::assert_event_name_is_valid(&event);
+
+    self
+      .manager()
+      .listeners()
+      .listen_private(self.label(), source, event, handler)
+  }
+
+  /// Register a JS private event listener and return its identifier.
+  pub(crate) fn listen_private_js(
+    &self,
+    source: &str,
+    event: &str,
+    handler: CallbackFn,
+  ) -> crate::Result<EventId> {
+    let listeners = self.manager().listeners();
+
+    let id = listeners.next_event_id();
+
+    self.eval(&crate::event::listen_js_script(
+      listeners.listeners_object_name(),
+      &serde_json::to_string(&EventTarget::webview(self.label()))?,
+      event,
+      id,
+      &format!("window['_{}']", handler.0),
+    ))?;
+
+    listeners.listen_private_js(self.label(), source, event, id);
+
+    Ok(id)
+  }
+
+  /// Unregister a JS private event listener.
+  pub(crate) fn unlisten_private_js(&self, event: &str, id: EventId) -> crate::Result<()> {
+    let listeners = self.manager().listeners();
+
+    self.eval(&crate::event::unlisten_js_script(
+      listeners.listeners_object_name(),
+      event,
+      id,
+    ))?;
+
+    listeners.unlisten_private_js(self.label(), id);
+
     Ok(())
   }
 
@@ -1566,6 +2231,30 @@ tauri::Builder::default()
     self.webview.dispatcher.open_devtools();
   }
 
+  /// Opens the developer tools window (Web Inspector) docked as set in `options`.
+  /// The devtools is only enabled on debug builds or with the `devtools` feature flag.
+  ///
+  /// [`crate::WebviewEvent::DevtoolsOpened`] and [`crate::WebviewEvent::DevtoolsClosed`] are
+  /// emitted when the devtools are opened or closed, from the API or by the user.
+  ///
+  /// Returns an error if the platform can't dock the devtools as set in `options`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** Only supported on macOS 10.15+.
+  ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
+  /// - **macOS / Linux:** [`DevtoolsDock::Right`] is unsupported.
+  /// - **Windows:** Only [`DevtoolsDock::Detached`] is supported.
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  #[cfg_attr(docsrs, doc(cfg(any(debug_assertions, feature = "devtools"))))]
+  pub fn open_devtools_with(&self, options: DevtoolsOptions) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .open_devtools_with(options)
+      .map_err(Into::into)
+  }
+
   /// Closes the developer tools window (Web Inspector).
   /// The devtools is only enabled on debug builds or with the `devtools` feature flag.
   ///
@@ -1573,7 +2262,8 @@ tauri::Builder::default()
   ///
   /// - **macOS:** Only supported on macOS 10.15+.
   ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
-  /// - **Windows:** Unsupported.
+  /// - **Windows:** The devtools window opened by the user is given to the webview of the
+  ///   foreground window, or to the only webview of its browser process.
   ///
   /// # Examples
   ///
@@ -1611,7 +2301,8 @@ tauri::Builder::default()
   ///
   /// - **macOS:** Only supported on macOS 10.15+.
   ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
-  /// - **Windows:** Unsupported.
+  /// - **Windows:** The devtools window opened by the user is given to the webview of the
+  ///   foreground window, or to the only webview of its browser process.
   ///
   /// # Examples
   ///
@@ -1644,6 +2335,49 @@ tauri::Builder::default()
       .unwrap_or_default()
   }
 
+  /// Returns the webview zoom level.
+  ///
+  /// The zoom set with [`Self::set_zoom`] or changed by the user is kept across navigations,
+  /// and [`crate::WebviewEvent::ZoomChanged`] is emitted when it changes.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS**: Pinch magnification is not a zoom change.
+  /// - **Android / iOS**: Unsupported.
+  pub fn zoom(&self) -> crate::Result<f64> {
+    self.webview.dispatcher.zoom().map_err(Into::into)
+  }
+
+  /// Returns the user agent of the webview, the platform default unless it was set.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **macOS**: WebKit cannot report its default user agent, which is then the frozen Safari
+  ///   one followed by the application name of the webview configuration.
+  /// - **Android / iOS**: Unsupported.
+  pub fn user_agent(&self) -> crate::Result<String> {
+    self.webview.dispatcher.user_agent().map_err(Into::into)
+  }
+
+  /// Returns the memory used by the process rendering the webview, in bytes, or `None` if the
+  /// platform does not expose it.
+  ///
+  /// Webviews can share their rendering process, in which case they report the same usage.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows**: reports the private bytes of the renderer process. The process is looked up
+  ///   asynchronously, so the usage read by the previous call is returned, `None` the first time.
+  ///   Requires WebView2 Runtime 1.0.2210 or newer.
+  /// - **macOS**: reports the physical footprint of the web content process. Requires the
+  ///   `macos-private-api` feature, always `None` otherwise.
+  /// - **Linux**: Unsupported, always `None`. WebKitGTK does not expose the web process of a
+  ///   webview.
+  /// - **Android / iOS**: Unsupported, always `None`.
+  pub fn memory_usage(&self) -> crate::Result<Option<u64>> {
+    self.webview.dispatcher.memory_usage().map_err(Into::into)
+  }
+
   /// Set the webview zoom level
   ///
   /// ## Platform-specific:
@@ -1659,6 +2393,40 @@ tauri::Builder::default()
       .map_err(Into::into)
   }
 
+  /// Sets the user agent of the webview, sent with the requests made after this call.
+  ///
+  /// Append to [`Self::user_agent`] to keep the platform default. Fails if the user agent has
+  /// control characters, which can't be sent in the `User-Agent` header.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Windows / macOS**: The `navigator.userAgent` of the current page is only updated on the
+  ///   next navigation.
+  /// - **Linux**: The `navigator.userAgent` of the current page is updated right away.
+  /// - **Android / iOS**: Unsupported.
+  pub fn set_user_agent(&self, user_agent: &str) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .set_user_agent(user_agent.to_string())
+      .map_err(Into::into)
+  }
+
+  /// Changes the webview proxy, `None` for a direct connection.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Linux**: The proxy is shared by the webviews using the same data directory.
+  /// - **Windows / macOS / Android / iOS**: Unsupported, returns an error. The proxy can only
+  ///   be set when the webview is created with [`WebviewBuilder::proxy`].
+  pub fn set_proxy(&self, proxy: Option<ProxyConfig>) -> crate::Result<()> {
+    self
+      .webview
+      .dispatcher
+      .set_proxy(proxy)
+      .map_err(Into::into)
+  }
+
   /// Specify the webview background color.
   ///
   /// ## Platfrom-specific:
@@ -1683,6 +2451,104 @@ tauri::Builder::default()
       .clear_all_browsing_data()
       .map_err(Into::into)
   }
+
+  /// Removes the browsing data of the given kinds from the webview data store, returning once
+  /// the platform webview removed it.
+  ///
+  /// The data store is shared by the webviews using the same data directory.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: Requires WebView2 Runtime 1.0.1245 or later. [`DataTypes::LOCAL_STORAGE`]
+  ///   does not include the `sessionStorage` data.
+  /// - **iOS / Android**: Unsupported.
+  pub async fn clear_browsing_data(&self, types: DataTypes) -> crate::Result<()> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self
+      .webview
+      .dispatcher
+      .clear_browsing_data(types, move |result| {
+        let _ = tx.send(result);
+      })?;
+
+    rx.await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))?
+      .map_err(Into::into)
+  }
+
+  /// Returns the cookies of the webview data store sent to `url`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: The cookies are read with a nested message loop, so this method must not be
+  ///   called from a synchronous command or an event handler, use an async command instead.
+  /// - **iOS / Android**: Unsupported.
+  pub fn cookies_for_url(&self, url: Url) -> crate::Result<Vec<Cookie<'static>>> {
+    self
+      .webview
+      .dispatcher
+      .cookies_for_url(url)
+      .map_err(Into::into)
+  }
+
+  /// Returns every cookie of the webview data store.
+  ///
+  /// Use [`Webview::cookies_for_url`] to only get the cookies sent to a URL.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows**: The cookies are read with a nested message loop, so this method must not be
+  ///   called from a synchronous command or an event handler, use an async command instead.
+  /// - **iOS / Android**: Unsupported.
+  pub fn cookies(&self) -> crate::Result<Vec<Cookie<'static>>> {
+    self.webview.dispatcher.cookies().map_err(Into::into)
+  }
+
+  /// Adds `cookie` to the webview data store, replacing the cookie with the same name, domain
+  /// and path, returning once the platform webview stored it.
+  ///
+  /// A cookie without a domain is a host-only cookie of the current URL of the webview. The
+  /// cookie is a session cookie unless it has an expiration date or a max age.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android**: Unsupported.
+  pub async fn set_cookie(&self, cookie: Cookie<'_>) -> crate::Result<()> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self
+      .webview
+      .dispatcher
+      .set_cookie(cookie.into_owned(), move |result| {
+        let _ = tx.send(result);
+      })?;
+
+    rx.await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))?
+      .map_err(Into::into)
+  }
+
+  /// Deletes the cookie named `name` sent to `url` from the webview data store, returning once
+  /// the platform webview deleted it.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **iOS / Android**: Unsupported.
+  pub async fn delete_cookie(&self, name: &str, url: Url) -> crate::Result<()> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    self
+      .webview
+      .dispatcher
+      .delete_cookie(name.into(), url, move |result| {
+        let _ = tx.send(result);
+      })?;
+
+    rx.await
+      .map_err(|_| crate::Error::Runtime(tauri_runtime::Error::FailedToReceiveMessage))?
+      .map_err(Into::into)
+  }
 }
 
 impl<R: Runtime> Listener<R> for Webview<R> {
@@ -1736,6 +2602,42 @@ tauri::Builder::default()
     )
   }
 
+  /// Listen to the events accepted by `filter` on this webview.
+  ///
+  /// See [`Listener::listen_filter`] for more information.
+  fn listen_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: Fn(Event) + Send + 'static,
+  {
+    self.manager.listen_filter(
+      event.into(),
+      EventTarget::Webview {
+        label: self.label().to_string(),
+      },
+      Box::new(filter),
+      handler,
+    )
+  }
+
+  /// Listen to the first event accepted by `filter` on this webview.
+  ///
+  /// See [`Listener::listen_filter`] for more information.
+  fn once_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: FnOnce(Event) + Send + 'static,
+  {
+    self.manager.once_filter(
+      event.into(),
+      EventTarget::Webview {
+        label: self.label().to_string(),
+      },
+      Box::new(filter),
+      handler,
+    )
+  }
+
   /// Unlisten to an event on this webview.
   ///
   /// # Examples
@@ -1918,4 +2820,425 @@ mod tests {
     crate::test_utils::assert_send::<super::Webview>();
     crate::test_utils::assert_sync::<super::Webview>();
   }
+
+  #[cfg(feature = "unstable")]
+  #[test]
+  fn reparented_webviews_follow_the_capabilities_of_their_window() {
+    use tauri_utils::platform::Target;
+
+    use crate::{
+      ipc::{Origin, RuntimeAuthority},
+      test::{mock_capability, mock_manifest},
+    };
+
+    let app = crate::test::mock_app();
+
+    let manifest = mock_manifest(&[("allow-read", "read")]);
+
+    // only the `main` window can read the tabs
+    let capability = mock_capability("main", &["main"], &["tabs:allow-read"]);
+
+    *app.handle().manager.runtime_authority.lock().unwrap() = RuntimeAuthority::resolve(
+      [("tabs".to_string(), manifest)].into_iter().collect(),
+      [("main".to_string(), capability)].into_iter().collect(),
+      Target::current(),
+    )
+    .unwrap();
+
+    let main = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let torn_off = crate::WebviewWindowBuilder::new(&app, "torn-off", Default::default())
+      .build()
+      .unwrap();
+
+    let webview = &torn_off.webview;
+
+    let can_read = || {
+      webview
+        .resolve_access("plugin:tabs|read", &Origin::Local)
+        .is_some()
+    };
+
+    assert!(!can_read());
+
+    webview.reparent(&main.window).unwrap();
+
+    assert_eq!(webview.window().label(), "main");
+
+    assert!(can_read());
+
+    // and the access is lost again when the webview leaves the window
+    webview.reparent(&torn_off.window).unwrap();
+
+    assert!(!can_read());
+  }
+
+  #[test]
+  fn binary_events_share_their_bytes_and_keep_their_order() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+      ipc::channel::{ChannelDataIpcQueue, QueuedData},
+      Emitter, EventPayload, EventTarget, Listener, Manager,
+    };
+
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    // a JavaScript listener of the webview
+    app
+      .handle()
+      .manager
+      .listeners()
+      .listen_js("waveform", "main", EventTarget::Any, 1, None);
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let received_ = received.clone();
+
+    app.listen_any("waveform", move |event| {
+      if let EventPayload::Binary(bytes) = event.data() {
+        received_.lock().unwrap().push(bytes.as_ptr() as usize);
+      }
+    });
+
+    let chunk = vec![1u8, 2, 3];
+
+    let address = chunk.as_ptr() as usize;
+
+    app.emit_bytes("waveform", EventTarget::Any, chunk).unwrap();
+
+    // the Rust listener and the webview get the emitted bytes, not copies
+    assert_eq!(*received.lock().unwrap(), [address]);
+
+    {
+      let queue = app.state::<ChannelDataIpcQueue>();
+
+      let queue = queue.0.lock().unwrap();
+
+      let shared = queue
+        .values()
+        .filter_map(|data| match data {
+          QueuedData::Shared(bytes) => Some(bytes.as_ptr() as usize),
+          QueuedData::Body(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+      assert_eq!(shared, [address]);
+    }
+
+    app.emit("waveform", "done").unwrap();
+
+    let scripts = window.webview.webview.dispatcher.state().evaluated_scripts;
+
+    let emits = scripts
+      .iter()
+      .filter(|script| script.contains("waveform"))
+      .collect::<Vec<_>>();
+
+    assert_eq!(emits.len(), 2);
+
+    // the fetch of the binary payload is handed to the emit function, which queues the JSON events
+    // emitted after it until it is delivered
+    assert!(emits[0].contains(r#"fn({event: "waveform"}, [1], null, window.__TAURI_INTERNALS__"#));
+
+    assert!(emits[1].contains(r#"payload: "done""#));
+  }
+
+  #[test]
+  fn initialization_scripts_run_on_every_frame_by_default() {
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .initialization_script("window.everyFrame = true")
+      .initialization_script_for_main_frame_only("window.mainFrame = true")
+      .build()
+      .unwrap();
+
+    let scripts = window.webview.webview.dispatcher.state().initialization_scripts;
+
+    let (main_frame_only, every_frame) = scripts
+      .iter()
+      .map(|(_, script)| script)
+      .partition::<Vec<_>, _>(|script| script.for_main_frame_only);
+
+    // the internal scripts must reach the child frames too
+    assert!(every_frame
+      .iter()
+      .any(|script| script.script.contains("__TAURI_INTERNALS__")));
+
+    assert!(every_frame
+      .iter()
+      .any(|script| script.script == "window.everyFrame = true"));
+
+    assert_eq!(main_frame_only.len(), 1);
+
+    assert_eq!(main_frame_only[0].script, "window.mainFrame = true");
+  }
+
+  #[test]
+  fn initialization_scripts_can_be_added_and_removed() {
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let added = |script: &str| {
+      window
+        .webview
+        .webview
+        .dispatcher
+        .state()
+        .initialization_scripts
+        .into_iter()
+        .find(|(_, added)| added.script == script)
+        .map(|(_, added)| added.for_main_frame_only)
+    };
+
+    let every_frame = window.add_initialization_script("window.a = 1").unwrap();
+
+    let main_frame = window
+      .add_initialization_script_for_main_frame_only("window.b = 2")
+      .unwrap();
+
+    assert_ne!(every_frame, main_frame);
+
+    assert_eq!(added("window.a = 1"), Some(false));
+
+    assert_eq!(added("window.b = 2"), Some(true));
+
+    window.remove_initialization_script(every_frame).unwrap();
+
+    assert_eq!(added("window.a = 1"), None);
+
+    assert_eq!(added("window.b = 2"), Some(true));
+  }
+
+  #[test]
+  fn zoom_changes_reach_the_webview_listeners() {
+    use std::sync::{Arc, Mutex};
+
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .zoom(1.5)
+      .build()
+      .unwrap();
+
+    assert_eq!(window.zoom().unwrap(), 1.5);
+
+    window.set_zoom(2.0).unwrap();
+
+    assert_eq!(window.zoom().unwrap(), 2.0);
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+
+    let reported_ = reported.clone();
+
+    window.webview.on_webview_event(move |event| {
+      if let crate::WebviewEvent::ZoomChanged(factor) = event {
+        reported_.lock().unwrap().push(*factor);
+      }
+    });
+
+    // the platform reports a zoom changed by the user
+    window
+      .webview
+      .webview
+      .dispatcher
+      .emit(tauri_runtime::window::WebviewEvent::ZoomChanged(1.25));
+
+    assert_eq!(*reported.lock().unwrap(), [1.25]);
+  }
+
+  #[test]
+  fn history_changes_are_emitted_to_the_webview() {
+    use crate::Listener;
+    use std::sync::{Arc, Mutex};
+
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let payloads = Arc::new(Mutex::new(Vec::new()));
+
+    let payloads_ = payloads.clone();
+
+    app.listen_any("tauri://history-changed", move |event| {
+      payloads_.lock().unwrap().push(event.payload().to_string());
+    });
+
+    // the platform reports that the webview can now navigate back
+    window
+      .webview
+      .webview
+      .dispatcher
+      .emit(tauri_runtime::window::WebviewEvent::HistoryChanged(
+        super::NavigationHistory {
+          can_go_back: true,
+          can_go_forward: false,
+        },
+      ));
+
+    assert_eq!(
+      *payloads.lock().unwrap(),
+      [r#"{"canGoBack":true,"canGoForward":false}"#]
+    );
+  }
+
+  #[test]
+  fn pdf_options_deserialize_from_the_js_options() {
+    use super::{PdfMargins, PdfOptions, PdfPageSize};
+
+    let options = |value| serde_json::from_value::<PdfOptions>(value).unwrap();
+
+    assert_eq!(options(serde_json::json!({})), PdfOptions::default());
+
+    let options = options(serde_json::json!({
+      "pageSize": { "width": 8.27, "height": 11.69 },
+      "margins": { "top": 1.0 },
+      "landscape": true,
+      "printBackground": true
+    }));
+
+    assert_eq!(options.page_size, PdfPageSize::A4);
+
+    // the margins that are not given keep their default
+    assert_eq!(
+      options.margins,
+      PdfMargins {
+        top: 1.0,
+        ..Default::default()
+      }
+    );
+
+    assert!(options.landscape);
+
+    assert!(options.print_background);
+  }
+
+  #[test]
+  fn webviews_print_to_pdf_bytes() {
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let pdf = crate::async_runtime::block_on(window.print_to_pdf(Default::default())).unwrap();
+
+    assert!(pdf.starts_with(b"%PDF"));
+  }
+
+  #[test]
+  fn proxies_are_set_on_creation_and_changed() {
+    use super::{ProxyConfig, ProxyCredentials};
+
+    let app = crate::test::mock_app();
+
+    let proxy = ProxyConfig {
+      bypass: vec!["localhost".into()],
+      credentials: Some(ProxyCredentials {
+        username: "user".into(),
+        password: "secret".into(),
+      }),
+      ..ProxyConfig::from("http://localhost:8080".parse::<url::Url>().unwrap())
+    };
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .proxy(proxy.clone())
+      .build()
+      .unwrap();
+
+    assert_eq!(window.webview.webview.dispatcher.state().proxy, Some(proxy));
+
+    window.webview.set_proxy(None).unwrap();
+
+    assert_eq!(window.webview.webview.dispatcher.state().proxy, None);
+  }
+
+  #[test]
+  fn proxy_urls_are_proxies_without_bypass_list() {
+    let app = crate::test::mock_app();
+
+    let url = "socks5://localhost:1080".parse::<url::Url>().unwrap();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .proxy_url(url.clone())
+      .build()
+      .unwrap();
+
+    assert_eq!(
+      window.webview.webview.dispatcher.state().proxy,
+      Some(super::ProxyConfig::from(url))
+    );
+  }
+
+  #[test]
+  fn proxy_credentials_are_not_debug_printed() {
+    let credentials = super::ProxyCredentials {
+      username: "user".into(),
+      password: "secret".into(),
+    };
+
+    assert!(!format!("{credentials:?}").contains("secret"));
+  }
+
+  #[test]
+  fn builder_cookies_are_stored_before_the_first_load() {
+    use tauri_runtime::webview::Cookie;
+
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .cookie(Cookie::build(("session", "abc")).domain("tauri.app").build())
+      .build()
+      .unwrap();
+
+    let cookies = window.cookies().unwrap();
+
+    assert_eq!(cookies.len(), 1);
+
+    assert_eq!(cookies[0].name_value(), ("session", "abc"));
+  }
+
+  #[test]
+  fn cookies_are_replaced_and_read_by_url() {
+    use tauri_runtime::webview::Cookie;
+
+    let app = crate::test::mock_app();
+
+    let window = crate::WebviewWindowBuilder::new(&app, "main", Default::default())
+      .build()
+      .unwrap();
+
+    let set = |name: &'static str, value: &'static str, domain: &'static str| {
+      crate::async_runtime::block_on(
+        window.set_cookie(Cookie::build((name, value)).domain(domain).build()),
+      )
+      .unwrap()
+    };
+
+    set("session", "old", "tauri.app");
+    set("session", "new", "tauri.app");
+    set("session", "other", "example.com");
+
+    assert_eq!(window.cookies().unwrap().len(), 2);
+
+    let cookies = window
+      .cookies_for_url("https://tauri.app".parse().unwrap())
+      .unwrap();
+
+    assert_eq!(cookies.len(), 1);
+
+    assert_eq!(cookies[0].value(), "new");
+  }
 }
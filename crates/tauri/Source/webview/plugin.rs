@@ -13,7 +13,10 @@ use crate::{
 mod desktop_commands {
 
 	use serde::{Deserialize, Serialize};
-	use tauri_runtime::dpi::{Position, Size};
+	use tauri_runtime::{
+		dpi::{Position, Size},
+		webview::{Cookie, SameSite},
+	};
 	use tauri_utils::config::{WebviewUrl, WindowConfig};
 
 	use super::*;
@@ -22,10 +25,12 @@ mod desktop_commands {
 		Webview,
 		WebviewWindowBuilder,
 		command,
+		ipc::Response,
 		sealed::ManagerBase,
 		utils::config::WindowEffectsConfig,
-		webview::Color,
+		webview::{Color, DataTypes, FindOptions, FindResult, PdfOptions},
 	};
+	use url::Url;
 
 	fn default_true() -> bool { true }
 
@@ -51,6 +56,7 @@ mod desktop_commands {
 		incognito:bool,
 		#[serde(default)]
 		zoom_hotkeys_enabled:bool,
+		zoom:Option<f64>,
 	}
 
 	#[cfg(feature = "unstable")]
@@ -66,6 +72,7 @@ mod desktop_commands {
 			builder.webview_attributes.window_effects = config.window_effects;
 			builder.webview_attributes.incognito = config.incognito;
 			builder.webview_attributes.zoom_hotkeys_enabled = config.zoom_hotkeys_enabled;
+			builder.webview_attributes.zoom = config.zoom;
 			builder
 		}
 	}
@@ -189,6 +196,9 @@ mod desktop_commands {
 	// TODO
 	getter!(webview_position, position, tauri_runtime::dpi::PhysicalPosition<i32>);
 	getter!(webview_size, size, tauri_runtime::dpi::PhysicalSize<u32>);
+	getter!(webview_zoom, zoom, f64);
+	getter!(can_go_back, bool);
+	getter!(can_go_forward, bool);
 	// getter!(is_focused, bool);
 
 	setter!(print);
@@ -201,17 +211,147 @@ mod desktop_commands {
 	setter!(set_webview_zoom, set_zoom, f64);
 	setter!(set_webview_background_color, set_background_color, Option<Color>);
 	setter!(clear_all_browsing_data, clear_all_browsing_data);
+	setter!(go_back);
+	setter!(go_forward);
+	setter!(reload, reload, bool);
+
+	/// Prints the webview to PDF, returning the document bytes. Writing them to a
+	/// file is left to the fs plugin and its scope.
+	#[command(root = "crate")]
+	pub async fn print_to_pdf<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		options:Option<PdfOptions>,
+	) -> crate::Result<Response> {
+		let pdf = get_webview(webview, label)?.print_to_pdf(options.unwrap_or_default()).await?;
+
+		Ok(Response::new(pdf))
+	}
+
+	#[command(root = "crate")]
+	pub async fn find_in_page<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		query:String,
+		options:Option<FindOptions>,
+	) -> crate::Result<FindResult> {
+		get_webview(webview, label)?.find_in_page(&query, options.unwrap_or_default()).await
+	}
+
+	#[command(root = "crate")]
+	pub async fn stop_find<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		clear_highlights:bool,
+	) -> crate::Result<()> {
+		get_webview(webview, label)?.stop_find(clear_highlights)
+	}
+
+	/// A kind of browsing data, mapped to [`DataTypes`].
+	#[derive(Debug, Clone, Copy, Deserialize)]
+	#[serde(rename_all = "camelCase")]
+	pub enum BrowsingDataKind {
+		Cookies,
+		LocalStorage,
+		IndexedDb,
+		Cache,
+	}
+
+	impl From<BrowsingDataKind> for DataTypes {
+		fn from(kind:BrowsingDataKind) -> Self {
+			match kind {
+				BrowsingDataKind::Cookies => DataTypes::COOKIES,
+				BrowsingDataKind::LocalStorage => DataTypes::LOCAL_STORAGE,
+				BrowsingDataKind::IndexedDb => DataTypes::INDEXED_DB,
+				BrowsingDataKind::Cache => DataTypes::CACHE,
+			}
+		}
+	}
+
+	/// The cookie fields sent to JS.
+	#[derive(Debug, Serialize)]
+	#[serde(rename_all = "camelCase")]
+	pub struct CookieInfo {
+		name:String,
+		value:String,
+		domain:Option<String>,
+		path:Option<String>,
+		secure:bool,
+		http_only:bool,
+		/// `strict`, `lax` or `none`, `None` when the browser default applies.
+		same_site:Option<&'static str>,
+		/// The expiration date in milliseconds since the Unix epoch, `None` for
+		/// session cookies.
+		expires:Option<i64>,
+	}
+
+	impl From<Cookie<'_>> for CookieInfo {
+		fn from(cookie:Cookie<'_>) -> Self {
+			Self {
+				name:cookie.name().into(),
+				value:cookie.value().into(),
+				domain:cookie.domain().map(Into::into),
+				path:cookie.path().map(Into::into),
+				secure:cookie.secure().unwrap_or_default(),
+				http_only:cookie.http_only().unwrap_or_default(),
+				same_site:cookie.same_site().map(|same_site| {
+					match same_site {
+						SameSite::Strict => "strict",
+						SameSite::Lax => "lax",
+						SameSite::None => "none",
+					}
+				}),
+				expires:cookie.expires_datetime().map(|expires| expires.unix_timestamp() * 1000),
+			}
+		}
+	}
+
+	#[command(root = "crate")]
+	pub async fn clear_browsing_data<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		types:Vec<BrowsingDataKind>,
+	) -> crate::Result<()> {
+		let types = types.into_iter().fold(DataTypes::empty(), |types, kind| types | kind.into());
+
+		get_webview(webview, label)?.clear_browsing_data(types).await
+	}
+
+	#[command(root = "crate")]
+	pub async fn cookies_for_url<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		url:Url,
+	) -> crate::Result<Vec<CookieInfo>> {
+		let cookies = get_webview(webview, label)?.cookies_for_url(url)?;
+
+		Ok(cookies.into_iter().map(Into::into).collect())
+	}
+
+	#[command(root = "crate")]
+	pub async fn delete_cookie<R:Runtime>(
+		webview:Webview<R>,
+		label:Option<String>,
+		name:String,
+		url:Url,
+	) -> crate::Result<()> {
+		get_webview(webview, label)?.delete_cookie(&name, url).await
+	}
 
 	#[command(root = "crate")]
 	pub async fn reparent<R:Runtime>(
 		webview:crate::Webview<R>,
 		label:Option<String>,
 		window:String,
+		bounds:Option<crate::Rect>,
 	) -> crate::Result<()> {
 		let webview = get_webview(webview, label)?;
 
 		if let Some(window) = webview.manager.get_window(&window) {
-			webview.reparent(&window)?;
+			match bounds {
+				Some(bounds) => webview.reparent_with_bounds(&window, bounds)?,
+				None => webview.reparent(&window)?,
+			}
 		}
 
 		Ok(())
@@ -280,6 +420,9 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 						desktop_commands::get_all_webviews,
 						desktop_commands::webview_position,
 						desktop_commands::webview_size,
+						desktop_commands::webview_zoom,
+						desktop_commands::can_go_back,
+						desktop_commands::can_go_forward,
 						// setters
 						desktop_commands::webview_close,
 						desktop_commands::set_webview_size,
@@ -290,8 +433,17 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 						desktop_commands::webview_hide,
 						desktop_commands::webview_show,
 						desktop_commands::print,
+						desktop_commands::go_back,
+						desktop_commands::go_forward,
+						desktop_commands::reload,
+						desktop_commands::print_to_pdf,
+						desktop_commands::find_in_page,
+						desktop_commands::stop_find,
 						desktop_commands::reparent,
 						desktop_commands::clear_all_browsing_data,
+						desktop_commands::clear_browsing_data,
+						desktop_commands::cookies_for_url,
+						desktop_commands::delete_cookie,
 						#[cfg(any(debug_assertions, feature = "devtools"))]
 						desktop_commands::internal_toggle_devtools,
 					]);
@@ -307,3 +459,68 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 		})
 		.build()
 }
+
+#[cfg(all(test, desktop))]
+mod tests {
+	use serde_json::json;
+	use tauri_runtime::webview::Cookie;
+
+	use super::desktop_commands::{BrowsingDataKind, CookieInfo};
+	use crate::webview::DataTypes;
+
+	#[test]
+	fn browsing_data_kinds_map_to_data_types() {
+		let kinds:Vec<BrowsingDataKind> =
+			serde_json::from_value(json!(["cookies", "localStorage", "indexedDb", "cache"]))
+				.unwrap();
+
+		let types = kinds.into_iter().map(DataTypes::from).collect::<Vec<_>>();
+
+		assert_eq!(
+			types,
+			[DataTypes::COOKIES, DataTypes::LOCAL_STORAGE, DataTypes::INDEXED_DB, DataTypes::CACHE]
+		);
+	}
+
+	#[test]
+	fn cookies_are_sent_with_every_attribute() {
+		let cookie = Cookie::parse(
+			"session=abc; Domain=tauri.app; Path=/docs; Secure; HttpOnly; SameSite=Lax; \
+			 Expires=Wed, 21 Oct 2015 07:28:00 GMT",
+		)
+		.unwrap();
+
+		assert_eq!(
+			serde_json::to_value(CookieInfo::from(cookie)).unwrap(),
+			json!({
+				"name": "session",
+				"value": "abc",
+				"domain": "tauri.app",
+				"path": "/docs",
+				"secure": true,
+				"httpOnly": true,
+				"sameSite": "lax",
+				"expires": 1_445_412_480_000_i64,
+			})
+		);
+	}
+
+	#[test]
+	fn session_cookies_have_no_expiration() {
+		let cookie = Cookie::parse("session=abc").unwrap();
+
+		assert_eq!(
+			serde_json::to_value(CookieInfo::from(cookie)).unwrap(),
+			json!({
+				"name": "session",
+				"value": "abc",
+				"domain": null,
+				"path": null,
+				"secure": false,
+				"httpOnly": false,
+				"sameSite": null,
+				"expires": null,
+			})
+		);
+	}
+}
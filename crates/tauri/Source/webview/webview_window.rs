@@ -11,11 +11,11 @@ use std::{
 };
 
 use crate::{
-  event::EventTarget,
+  event::{EventPayloadRef, EventTarget},
   ipc::ScopeObject,
   runtime::dpi::{PhysicalPosition, PhysicalSize},
   window::Monitor,
-  Emitter, Listener, ResourceTable, Window,
+  Emitter, Listener, ResourceTable, Window, WindowState,
 };
 #[cfg(desktop)]
 use crate::{
@@ -26,6 +26,11 @@ use crate::{
     window::CursorIcon,
     UserAttentionType,
   },
+  webview::{
+    Cookie, DataTypes, FindOptions, FindResult, PdfOptions, ProxyAuthenticationChallenge,
+    ProxyConfig, ProxyCredentials, ScriptHandle,
+  },
+  window::{ParentDestroyPolicy, StateFlags},
 };
 use serde::Serialize;
 use tauri_utils::{
@@ -55,6 +60,7 @@ use super::{DownloadEvent, ResolvedScope};
 pub struct WebviewWindowBuilder<'a, R: Runtime, M: Manager<R>> {
   window_builder: WindowBuilder<'a, R, M>,
   webview_builder: WebviewBuilder<R>,
+  preset_error: Option<crate::Error>,
 }
 
 impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
@@ -110,6 +116,7 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     Self {
       window_builder: WindowBuilder::new(manager, &label),
       webview_builder: WebviewBuilder::new(&label, url),
+      preset_error: None,
     }
   }
 
@@ -117,6 +124,9 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
   /// Keep in mind that you can't create 2 windows with the same `label` so make sure
   /// that the initial window was closed or change the label of the new [`WebviewWindowBuilder`].
   ///
+  /// A [`WindowConfig::preset`] is resolved from `tauri.conf.json > app > windowPresets`,
+  /// see [`WindowConfig::with_preset`].
+  ///
   /// # Known issues
   ///
   /// On Windows, this function deadlocks when used in a synchronous command, see [the Webview2 issue].
@@ -138,9 +148,61 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
   ///
   /// [the Webview2 issue]: https://github.com/tauri-apps/wry/issues/583
   pub fn from_config(manager: &'a M, config: &WindowConfig) -> crate::Result<Self> {
+    let config = crate::window::resolve_preset(manager.config(), config)?;
     Ok(Self {
-      window_builder: WindowBuilder::from_config(manager, config)?,
-      webview_builder: WebviewBuilder::from_config(config),
+      window_builder: WindowBuilder::from_config(manager, &config)?,
+      webview_builder: WebviewBuilder::from_config(&config),
+      preset_error: None,
+    })
+  }
+
+  /// Initializes a webview window builder from a named preset defined in `tauri.conf.json > app > windowPresets`.
+  ///
+  /// The preset [`WindowConfig`] is used with the given `label` and `url`,
+  /// and any further builder call overrides the preset values.
+  ///
+  /// If the preset does not exist, [`Self::build`] returns [`crate::Error::WindowPresetNotFound`]
+  /// listing the available preset names.
+  ///
+  /// # Known issues
+  ///
+  /// On Windows, this function deadlocks when used in a synchronous command, see [the Webview2 issue].
+  /// You should use `async` commands when creating windows.
+  ///
+  /// # Examples
+  ///
+  /// - Create a window from the `palette` preset in a command:
+  ///
+  /// ```
+  /// #[tauri::command]
+  /// async fn open_palette(app: tauri::AppHandle) {
+  ///   let webview_window = tauri::WebviewWindowBuilder::from_preset(&app, "palette", "colors", tauri::WebviewUrl::App("colors.html".into()))
+  ///     .title("Colors")
+  ///     .build()
+  ///     .unwrap();
+  /// }
+  /// ```
+  ///
+  /// [the Webview2 issue]: https://github.com/tauri-apps/wry/issues/583
+  pub fn from_preset<L: Into<String>>(
+    manager: &'a M,
+    preset: &str,
+    label: L,
+    url: WebviewUrl,
+  ) -> Self {
+    let label = label.into();
+
+    let config = WindowConfig {
+      label: label.clone(),
+      url: url.clone(),
+      preset: Some(preset.into()),
+      ..Default::default()
+    };
+
+    Self::from_config(manager, &config).unwrap_or_else(|error| {
+      let mut builder = Self::new(manager, label, url);
+      builder.preset_error.replace(error);
+      builder
     })
   }
 
@@ -307,8 +369,10 @@ tauri::Builder::default()
   }
 
   /// Defines a closure to be executed when a page load event is triggered.
-  /// The event can be either [`tauri_runtime::webview::PageLoadEvent::Started`] if the page has started loading
-  /// or [`tauri_runtime::webview::PageLoadEvent::Finished`] when the page finishes loading.
+  /// The event can be either [`tauri_runtime::webview::PageLoadEvent::Started`] if the page has started loading,
+  /// [`tauri_runtime::webview::PageLoadEvent::Finished`] when the page finishes loading
+  /// or [`tauri_runtime::webview::PageLoadEvent::Failed`] when the navigation failed, in which case
+  /// [`PageLoadPayload::error`] describes the failure.
   ///
   /// # Examples
   /// ```rust,no_run
@@ -329,6 +393,9 @@ tauri::Builder::default()
   ///           PageLoadEvent::Finished => {
   ///             println!("{} finished loading", payload.url());
   ///           }
+  ///           PageLoadEvent::Failed => {
+  ///             println!("{} failed to load: {:?}", payload.url(), payload.error());
+  ///           }
   ///         }
   ///       })
   ///       .build()?;
@@ -353,6 +420,10 @@ tauri::Builder::default()
 
   /// Creates a new window.
   pub fn build(self) -> crate::Result<WebviewWindow<R>> {
+    if let Some(error) = self.preset_error {
+      return Err(error);
+    }
+
     let (window, webview) = self.window_builder.with_webview(self.webview_builder)?;
     Ok(WebviewWindow { window, webview })
   }
@@ -413,6 +484,18 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Locks the inner size of the window to the given width to height ratio when it is resized.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** the aspect ratio is only kept when the window is resized by the user.
+  /// - **iOS / Android:** Unsupported.
+  #[must_use]
+  pub fn aspect_ratio(mut self, ratio: f64) -> Self {
+    self.window_builder = self.window_builder.aspect_ratio(ratio);
+    self
+  }
+
   /// Whether the window is resizable or not.
   /// When resizable is set to false, native window's maximize button is automatically disabled.
   #[must_use]
@@ -472,6 +555,17 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Starts the window in fullscreen on the given monitor, either its index in
+  /// [`WebviewWindow::available_monitors`] or its name.
+  ///
+  /// See [`WindowBuilder::fullscreen_monitor`](crate::window::WindowBuilder::fullscreen_monitor)
+  /// for more information.
+  #[must_use]
+  pub fn fullscreen_monitor(mut self, monitor: crate::utils::config::FullscreenMonitor) -> Self {
+    self.window_builder = self.window_builder.fullscreen_monitor(monitor);
+    self
+  }
+
   /// Sets the window to be initially focused.
   #[must_use]
   #[deprecated(
@@ -531,6 +625,29 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Turns the window into a desktop widget: it stays below all normal
+  /// windows, is not shown in the taskbar or the window switcher and does not
+  /// take the focus when shown or clicked. Mouse events still reach the webview.
+  ///
+  /// See [`WindowBuilder::desktop_widget`] for the platform specific details.
+  #[cfg(desktop)]
+  #[must_use]
+  pub fn desktop_widget(mut self, desktop_widget: bool) -> Self {
+    self.window_builder = self.window_builder.desktop_widget(desktop_widget);
+    self
+  }
+
+  /// Restores the `flags` properties of the window saved by a previous run,
+  /// and saves them when the window is requested to close and when the app is requested to exit.
+  ///
+  /// See [`WindowBuilder::restore_state`] for more information.
+  #[cfg(desktop)]
+  #[must_use]
+  pub fn restore_state(mut self, flags: StateFlags) -> Self {
+    self.window_builder = self.window_builder.restore_state(flags);
+    self
+  }
+
   /// Whether the window should always be on top of other windows.
   #[must_use]
   pub fn always_on_top(mut self, always_on_top: bool) -> Self {
@@ -586,7 +703,8 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
   ///   - `false` has no effect on decorated window, shadows are always ON.
   ///   - `true` will make undecorated window have a 1px white border,
   ///     and on Windows 11, it will have a rounded corners.
-  /// - **Linux:** Unsupported.
+  /// - **Linux:** `true` creates the window with the GTK client-side decorations,
+  ///   which draw the shadow and the resize borders around it when undecorated.
   #[must_use]
   pub fn shadow(mut self, enable: bool) -> Self {
     self.window_builder = self.window_builder.shadow(enable);
@@ -604,11 +722,38 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
   ///     - An owned window is hidden when its owner is minimized.
   /// - **Linux**: This makes the new window transient for parent, see <https://docs.gtk.org/gtk3/method.Window.set_transient_for.html>
   /// - **macOS**: This adds the window as a child of parent, see <https://developer.apple.com/documentation/appkit/nswindow/1419152-addchildwindow?language=objc>
+  ///
+  /// See [`Self::parent_destroy_policy`] for what happens to the window when its parent is destroyed,
+  /// and [`WebviewWindow::set_parent`] to change the parent later.
   pub fn parent(mut self, parent: &WebviewWindow<R>) -> crate::Result<Self> {
     self.window_builder = self.window_builder.parent(&parent.window)?;
     Ok(self)
   }
 
+  /// What happens to the window when its parent, see [`Self::parent`], is closed or destroyed.
+  /// Defaults to [`ParentDestroyPolicy::Platform`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::{window::ParentDestroyPolicy, Manager, WebviewUrl, WebviewWindowBuilder};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let main = app.get_webview_window("main").unwrap();
+  ///     WebviewWindowBuilder::new(app, "inspector", WebviewUrl::App("inspector.html".into()))
+  ///       .parent(&main)?
+  ///       .parent_destroy_policy(ParentDestroyPolicy::Orphan)
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn parent_destroy_policy(mut self, policy: ParentDestroyPolicy) -> Self {
+    self.window_builder = self.window_builder.parent_destroy_policy(policy);
+    self
+  }
+
   /// Set an owner to the window to be created.
   ///
   /// From MSDN:
@@ -715,6 +860,42 @@ impl<'a, R: Runtime, M: Manager<R>> WebviewWindowBuilder<'a, R, M> {
     self
   }
 
+  /// Sets the position of the window controls (traffic lights) from the top-left corner of the
+  /// window, kept when the window is resized.
+  ///
+  /// See [`crate::window::WindowBuilder::traffic_light_position`] for more information.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let webview_window = tauri::WebviewWindowBuilder::new(app, "main", Default::default())
+  ///       .title_bar_style(tauri::TitleBarStyle::Overlay)
+  ///       .hidden_title(true)
+  ///       .traffic_light_position(tauri::LogicalPosition::new(16.0, 20.0))
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(target_os = "macos")]
+  #[must_use]
+  pub fn traffic_light_position<P: Into<Position>>(mut self, position: P) -> Self {
+    self.window_builder = self.window_builder.traffic_light_position(position);
+    self
+  }
+
+  /// Extends the web content into the title bar, the caption buttons being drawn by the system
+  /// over it. Use [`WebviewWindow::title_bar_rects`] to lay out the content around them.
+  ///
+  /// See [`crate::window::WindowBuilder::titlebar_overlay`] for more information.
+  #[cfg(windows)]
+  #[must_use]
+  pub fn titlebar_overlay(mut self, enabled: bool) -> Self {
+    self.window_builder = self.window_builder.titlebar_overlay(enabled);
+    self
+  }
+
   /// Defines the window [tabbing identifier] for macOS.
   ///
   /// Windows with matching tabbing identifiers will be grouped together.
@@ -757,6 +938,8 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
   /// Since it runs on all top-level document and child frame page navigations,
   /// it's recommended to check the `window.location` to guard your script from running on unexpected origins.
   ///
+  /// See [`Self::initialization_script_for_main_frame_only`] to only run it on the main frame.
+  ///
   /// # Examples
   ///
   /// ```rust
@@ -786,6 +969,18 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
     self
   }
 
+  /// Adds the provided JavaScript to a list of scripts that should be run after the global object has been created,
+  /// but before the HTML document has been parsed and before any other script included by the HTML document is run.
+  ///
+  /// The script only runs on the main frame.
+  #[must_use]
+  pub fn initialization_script_for_main_frame_only(mut self, script: &str) -> Self {
+    self.webview_builder = self
+      .webview_builder
+      .initialization_script_for_main_frame_only(script);
+    self
+  }
+
   /// Set the user agent for the webview
   #[must_use]
   pub fn user_agent(mut self, user_agent: &str) -> Self {
@@ -793,6 +988,16 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
     self
   }
 
+  /// Appends `suffix`, separated by a space, to the default user agent of the platform webview
+  /// instead of replacing it, or to the one set with [`Self::user_agent`].
+  ///
+  /// See [`WebviewBuilder::append_user_agent`] for the platform specific details.
+  #[must_use]
+  pub fn append_user_agent(mut self, suffix: &str) -> Self {
+    self.webview_builder = self.webview_builder.append_user_agent(suffix);
+    self
+  }
+
   /// Set additional arguments for the webview.
   ///
   /// ## Platform-specific
@@ -825,6 +1030,38 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
     self
   }
 
+  /// Defines a closure deciding whether a drag operation entering the webview is accepted.
+  ///
+  /// See [`WebviewBuilder::drag_drop_filter`] for more information.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{DragDropEvent, WebviewUrl, WebviewWindowBuilder};
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     // only accept markdown files
+  ///     WebviewWindowBuilder::new(app, "editor", WebviewUrl::default())
+  ///       .drag_drop_filter(|event| match event {
+  ///         DragDropEvent::Enter { paths, .. } => paths
+  ///           .iter()
+  ///           .all(|path| path.extension().is_some_and(|extension| extension == "md")),
+  ///         _ => true,
+  ///       })
+  ///       .build()?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn drag_drop_filter<F: Fn(&crate::DragDropEvent) -> bool + Send + 'static>(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.webview_builder = self.webview_builder.drag_drop_filter(f);
+    self
+  }
+
   /// Enables clipboard access for the page rendered on **Linux** and **Windows**.
   ///
   /// **macOS** doesn't provide such method and is always enabled by default,
@@ -862,6 +1099,54 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
     self
   }
 
+  /// Set the proxy for the WebView for all network requests, with the hosts that bypass it
+  /// and the credentials it requires.
+  ///
+  /// See [`WebviewBuilder::proxy`] for more information.
+  #[must_use]
+  pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+    self.webview_builder = self.webview_builder.proxy(proxy);
+    self
+  }
+
+  /// Adds a cookie to the webview data store before the initial page load, so it is sent with
+  /// the first request.
+  ///
+  /// See [`WebviewBuilder::cookie`] for more information.
+  #[must_use]
+  pub fn cookie(mut self, cookie: Cookie<'static>) -> Self {
+    self.webview_builder = self.webview_builder.cookie(cookie);
+    self
+  }
+
+  /// Defines a closure answering the proxy authentication challenges of the webview,
+  /// `None` cancelling the request.
+  ///
+  /// See [`WebviewBuilder::on_proxy_authentication`] for more information.
+  #[must_use]
+  pub fn on_proxy_authentication<
+    F: Fn(WebviewWindow<R>, ProxyAuthenticationChallenge) -> Option<ProxyCredentials>
+      + Send
+      + Sync
+      + 'static,
+  >(
+    mut self,
+    f: F,
+  ) -> Self {
+    self.webview_builder = self
+      .webview_builder
+      .on_proxy_authentication(move |webview, challenge| {
+        f(
+          WebviewWindow {
+            window: webview.window(),
+            webview,
+          },
+          challenge,
+        )
+      });
+    self
+  }
+
   /// Whether the window should be transparent. If this is true, writing colors
   /// with alpha values different than `1.0` will produce a transparent window.
   #[cfg(any(not(target_os = "macos"), feature = "macos-private-api"))]
@@ -894,6 +1179,19 @@ impl<R: Runtime, M: Manager<R>> WebviewWindowBuilder<'_, R, M> {
     self
   }
 
+  /// Sets the initial zoom factor of the webview, `1.0` being the default size.
+  ///
+  /// The zoom is kept across the navigations of the webview.
+  ///
+  /// ## Platform-specific:
+  ///
+  /// - **Android / iOS**: Unsupported.
+  #[must_use]
+  pub fn zoom(mut self, scale_factor: f64) -> Self {
+    self.webview_builder = self.webview_builder.zoom(scale_factor);
+    self
+  }
+
   /// Whether browser extensions can be installed for the webview process
   ///
   /// ## Platform-specific:
@@ -1067,11 +1365,73 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.label()
   }
 
+  /// Adds `state` to the state scoped to this window, returning `false` if a state of the same
+  /// type is already managed by the window.
+  ///
+  /// See [`Window::manage_scoped`] for more information.
+  pub fn manage_scoped<T: Send + Sync + 'static>(&self, state: T) -> bool {
+    self.window.manage_scoped(state)
+  }
+
+  /// Removes the state of type `T` scoped to this window, returning it if it is not borrowed
+  /// anymore.
+  pub fn unmanage_scoped<T: Send + Sync + 'static>(&self) -> Option<T> {
+    self.window.unmanage_scoped()
+  }
+
+  /// Retrieves the state of type `T` scoped to this window.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the state has not been [managed](Self::manage_scoped) by the window.
+  /// Use [`Self::try_scoped_state`] for a non-panicking version.
+  pub fn scoped_state<T: Send + Sync + 'static>(&self) -> WindowState<'_, T> {
+    self.window.scoped_state()
+  }
+
+  /// Attempts to retrieve the state of type `T` scoped to this window.
+  pub fn try_scoped_state<T: Send + Sync + 'static>(&self) -> Option<WindowState<'_, T>> {
+    self.window.try_scoped_state()
+  }
+
   /// Registers a window event listener.
   pub fn on_window_event<F: Fn(&WindowEvent) + Send + 'static>(&self, f: F) {
     self.window.on_window_event(f);
   }
 
+  /// Registers an asynchronous close request handler.
+  ///
+  /// The window is only closed once the future returned by `handler` resolves to
+  /// [`CloseDecision::Close`](crate::window::CloseDecision::Close). The close requests made while a
+  /// decision is pending are ignored.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use tauri::{Manager, window::CloseDecision};
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let window = app.get_webview_window("main").unwrap();
+  ///
+  ///     window.on_close_requested(|request| async move {
+  ///       request
+  ///         .confirm("Unsaved changes", "Close without saving?")
+  ///         .await
+  ///         .unwrap_or(CloseDecision::Cancel)
+  ///     });
+  ///
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[cfg(desktop)]
+  pub fn on_close_requested<F, Fut>(&self, handler: F)
+  where
+    F: Fn(crate::window::CloseRequest<R>) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = crate::window::CloseDecision> + Send + 'static,
+  {
+    self.window.on_close_requested(handler)
+  }
+
   /// Resolves the given command scope for this webview on the currently loaded URL.
   ///
   /// If the command is not allowed, returns None.
@@ -1255,6 +1615,14 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.outer_size()
   }
 
+  /// Returns the physical minimum and maximum inner size of the window.
+  ///
+  /// A size is `None` when neither of its dimensions is constrained, and a dimension that is not
+  /// constrained is `0` for the minimum size and `u32::MAX` for the maximum size.
+  pub fn size_constraints(&self) -> crate::Result<(Option<Size>, Option<Size>)> {
+    self.window.size_constraints()
+  }
+
   /// Gets the window's current fullscreen state.
   pub fn is_fullscreen(&self) -> crate::Result<bool> {
     self.window.is_fullscreen()
@@ -1405,6 +1773,14 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn theme(&self) -> crate::Result<crate::Theme> {
     self.window.theme()
   }
+
+  /// Returns the regions of the client area covered by the title bar controls, in physical
+  /// pixels from its top-left corner.
+  ///
+  /// See [`Window::title_bar_rects`] for more information.
+  pub fn title_bar_rects(&self) -> crate::Result<Vec<crate::Rect>> {
+    self.window.title_bar_rects()
+  }
 }
 
 /// Desktop window getters.
@@ -1536,6 +1912,23 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.destroy()
   }
 
+  /// Makes this window a child of `parent`, or a top-level window if `parent` is `None`.
+  ///
+  /// A child window stays above its parent and is minimized with it, see
+  /// [`WebviewWindowBuilder::parent`] for the platform details.
+  /// The [`ParentDestroyPolicy`] set when the window was created is kept.
+  pub fn set_parent(&self, parent: Option<&WebviewWindow<R>>) -> crate::Result<()> {
+    self.window.set_parent(parent.map(|parent| &parent.window))
+  }
+
+  /// Returns the parent of this window, see [`Self::set_parent`].
+  pub fn parent(&self) -> Option<WebviewWindow<R>> {
+    self
+      .window
+      .parent()
+      .and_then(|parent| self.get_webview_window(parent.label()))
+  }
+
   /// Determines if this window should be [decorated].
   ///
   /// [decorated]: https://en.wikipedia.org/wiki/Window_(computing)#Window_decoration
@@ -1551,7 +1944,8 @@ impl<R: Runtime> WebviewWindow<R> {
   ///   - `false` has no effect on decorated window, shadow are always ON.
   ///   - `true` will make undecorated window have a 1px white border,
   ///     and on Windows 11, it will have a rounded corners.
-  /// - **Linux:** Unsupported.
+  /// - **Linux:** Only has an effect on the windows created with a shadow, which
+  ///   keep the GTK client-side decorations drawing it when undecorated.
   pub fn set_shadow(&self, enable: bool) -> crate::Result<()> {
     self.window.set_shadow(enable)
   }
@@ -1621,17 +2015,17 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_size(size.into())
   }
 
-  /// Sets this window's minimum inner size.
+  /// Sets this window's minimum inner size, resizing the window if it is smaller.
   pub fn set_min_size<S: Into<Size>>(&self, size: Option<S>) -> crate::Result<()> {
     self.window.set_min_size(size.map(|s| s.into()))
   }
 
-  /// Sets this window's maximum inner size.
+  /// Sets this window's maximum inner size, resizing the window if it is larger.
   pub fn set_max_size<S: Into<Size>>(&self, size: Option<S>) -> crate::Result<()> {
     self.window.set_max_size(size.map(|s| s.into()))
   }
 
-  /// Sets this window's minimum inner width.
+  /// Sets this window's minimum and maximum inner size, resizing the window to satisfy them.
   pub fn set_size_constraints(
     &self,
     constriants: tauri_runtime::window::WindowSizeConstraints,
@@ -1639,6 +2033,17 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_size_constraints(constriants)
   }
 
+  /// Locks this window's inner size to the given width to height ratio, resizing the window to
+  /// it, or unlocks it when `None`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Windows:** the aspect ratio is only kept when the window is resized by the user.
+  /// - **iOS / Android:** Unsupported.
+  pub fn set_aspect_ratio(&self, ratio: Option<f64>) -> crate::Result<()> {
+    self.window.set_aspect_ratio(ratio)
+  }
+
   /// Sets this window's position.
   pub fn set_position<Pos: Into<Position>>(&self, position: Pos) -> crate::Result<()> {
     self.window.set_position(position)
@@ -1649,6 +2054,13 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_fullscreen(fullscreen)
   }
 
+  /// Moves the window to `monitor` and makes it fullscreen there in a single step.
+  ///
+  /// See [`Window::set_fullscreen_on_monitor`] for the platform specific details.
+  pub fn set_fullscreen_on_monitor(&self, monitor: &Monitor) -> crate::Result<()> {
+    self.window.set_fullscreen_on_monitor(monitor)
+  }
+
   /// Bring the window to front and focus.
   pub fn set_focus(&self) -> crate::Result<()> {
     self.window.set_focus()
@@ -1724,6 +2136,18 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_ignore_cursor_events(ignore)
   }
 
+  /// Ignores the window cursor events outside of the interactive `regions`, in window
+  /// coordinates, so the clicks pass through to the windows behind. `None` handles the cursor
+  /// events of the whole window again.
+  ///
+  /// See [`Window::set_ignore_cursor_events_region`] for the platform specific details.
+  pub fn set_ignore_cursor_events_region(
+    &self,
+    regions: Option<Vec<crate::Rect>>,
+  ) -> crate::Result<()> {
+    self.window.set_ignore_cursor_events_region(regions)
+  }
+
   /// Starts dragging the window.
   pub fn start_dragging(&self) -> crate::Result<()> {
     self.window.start_dragging()
@@ -1740,17 +2164,14 @@ impl<R: Runtime> WebviewWindow<R> {
 
   /// Sets the taskbar badge count. Using `0` or `None` will remove the badge
   ///
-  /// ## Platform-specific
-  /// - **Windows:** Unsupported, use [`WebviewWindow::set_overlay_icon`] instead.
-  /// - **iOS:** iOS expects i32, the value will be clamped to i32::MIN, i32::MAX.
-  /// - **Android:** Unsupported.
+  /// See [`Window::set_badge_count`] for the platform specific details.
   pub fn set_badge_count(&self, count: Option<i64>) -> crate::Result<()> {
     self.window.set_badge_count(count)
   }
 
-  /// Sets the taskbar badge label **macOS only**. Using `None` will remove the badge
-  #[cfg(target_os = "macos")]
-  #[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+  /// Sets the taskbar badge label, for non-numeric badges. Using `None` will remove the badge
+  ///
+  /// See [`Window::set_badge_label`] for the platform specific details.
   pub fn set_badge_label(&self, label: Option<String>) -> crate::Result<()> {
     self.window.set_badge_label(label)
   }
@@ -1769,6 +2190,13 @@ impl<R: Runtime> WebviewWindow<R> {
     self.window.set_progress_bar(progress_state)
   }
 
+  /// Sets the taskbar or dock progress indicator.
+  ///
+  /// See [`Window::set_progress`] for the platform specific details.
+  pub fn set_progress(&self, state: crate::window::ProgressState) -> crate::Result<()> {
+    self.window.set_progress(state)
+  }
+
   /// Sets the title bar style. **macOS only**.
   pub fn set_title_bar_style(&self, style: tauri_utils::TitleBarStyle) -> crate::Result<()> {
     self.window.set_title_bar_style(style)
@@ -1789,6 +2217,67 @@ impl<R: Runtime> WebviewWindow<R> {
   pub fn print(&self) -> crate::Result<()> {
     self.webview.print()
   }
+
+  /// Captures the contents of the webview as an RGBA image, limited to
+  /// `region` if given, relative to the top-left corner of the webview.
+  ///
+  /// See [`Webview::capture`] for the platform specific details.
+  pub async fn capture(
+    &self,
+    region: Option<tauri_runtime::Rect>,
+  ) -> crate::Result<crate::image::Image<'static>> {
+    self.webview.capture(region).await
+  }
+
+  /// Prints the contents of the webview to a PDF document, returning its bytes.
+  ///
+  /// See [`Webview::print_to_pdf`] for the platform specific details.
+  pub async fn print_to_pdf(&self, options: PdfOptions) -> crate::Result<Vec<u8>> {
+    self.webview.print_to_pdf(options).await
+  }
+
+  /// Finds `query` in the page, highlighting the matches and returning the search result.
+  ///
+  /// See [`Webview::find_in_page`] for the platform specific details.
+  pub async fn find_in_page(&self, query: &str, options: FindOptions) -> crate::Result<FindResult> {
+    self.webview.find_in_page(query, options).await
+  }
+
+  /// Ends the search started with [`Self::find_in_page`], removing the highlights if
+  /// `clear_highlights` is true.
+  ///
+  /// See [`Webview::stop_find`] for the platform specific details.
+  pub fn stop_find(&self, clear_highlights: bool) -> crate::Result<()> {
+    self.webview.stop_find(clear_highlights)
+  }
+
+  /// Adds a script that runs on the main frame and every child frame before any other script of
+  /// the page, on every navigation that starts after this call, returning a handle to remove it.
+  ///
+  /// See [`Webview::add_initialization_script`] for the platform specific details.
+  pub fn add_initialization_script(&self, script: &str) -> crate::Result<ScriptHandle> {
+    self.webview.add_initialization_script(script)
+  }
+
+  /// Adds a script that runs on the main frame before any other script of the page, on every
+  /// navigation that starts after this call, returning a handle to remove it.
+  ///
+  /// See [`Webview::add_initialization_script_for_main_frame_only`] for the platform specific
+  /// details.
+  pub fn add_initialization_script_for_main_frame_only(
+    &self,
+    script: &str,
+  ) -> crate::Result<ScriptHandle> {
+    self
+      .webview
+      .add_initialization_script_for_main_frame_only(script)
+  }
+
+  /// Removes a script added with [`Self::add_initialization_script`] or
+  /// [`Self::add_initialization_script_for_main_frame_only`], from the next navigation on.
+  pub fn remove_initialization_script(&self, handle: ScriptHandle) -> crate::Result<()> {
+    self.webview.remove_initialization_script(handle)
+  }
 }
 
 /// Webview APIs.
@@ -1868,6 +2357,41 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.navigate(url)
   }
 
+  /// Navigates back in the webview history.
+  ///
+  /// See [`Webview::go_back`] for the platform specific details.
+  pub fn go_back(&self) -> crate::Result<()> {
+    self.webview.go_back()
+  }
+
+  /// Navigates forward in the webview history.
+  ///
+  /// See [`Webview::go_back`] for the platform specific details.
+  pub fn go_forward(&self) -> crate::Result<()> {
+    self.webview.go_forward()
+  }
+
+  /// Whether the webview can navigate back in its history.
+  ///
+  /// See [`Webview::go_back`] for the platform specific details.
+  pub fn can_go_back(&self) -> crate::Result<bool> {
+    self.webview.can_go_back()
+  }
+
+  /// Whether the webview can navigate forward in its history.
+  ///
+  /// See [`Webview::go_back`] for the platform specific details.
+  pub fn can_go_forward(&self) -> crate::Result<bool> {
+    self.webview.can_go_forward()
+  }
+
+  /// Reloads the current page, bypassing the cache if `ignore_cache` is true.
+  ///
+  /// See [`Webview::reload`] for the platform specific details.
+  pub fn reload(&self, ignore_cache: bool) -> crate::Result<()> {
+    self.webview.reload(ignore_cache)
+  }
+
   /// Handles this window receiving an [`crate::webview::InvokeRequest`].
   pub fn on_message(
     self,
@@ -1907,6 +2431,29 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.open_devtools();
   }
 
+  /// Opens the developer tools window (Web Inspector) docked as set in `options`.
+  /// The devtools is only enabled on debug builds or with the `devtools` feature flag.
+  ///
+  /// [`crate::WebviewEvent::DevtoolsOpened`] and [`crate::WebviewEvent::DevtoolsClosed`] are
+  /// emitted when the devtools are opened or closed, from the API or by the user.
+  ///
+  /// Returns an error if the platform can't dock the devtools as set in `options`.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **macOS:** Only supported on macOS 10.15+.
+  ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
+  /// - **macOS / Linux:** [`DevtoolsDock::Right`] is unsupported.
+  /// - **Windows:** Only [`DevtoolsDock::Detached`] is supported.
+  ///
+  /// [`DevtoolsDock::Right`]: crate::webview::DevtoolsDock::Right
+  /// [`DevtoolsDock::Detached`]: crate::webview::DevtoolsDock::Detached
+  #[cfg(any(debug_assertions, feature = "devtools"))]
+  #[cfg_attr(docsrs, doc(cfg(any(debug_assertions, feature = "devtools"))))]
+  pub fn open_devtools_with(&self, options: crate::webview::DevtoolsOptions) -> crate::Result<()> {
+    self.webview.open_devtools_with(options)
+  }
+
   /// Closes the developer tools window (Web Inspector).
   /// The devtools is only enabled on debug builds or with the `devtools` feature flag.
   ///
@@ -1914,7 +2461,8 @@ impl<R: Runtime> WebviewWindow<R> {
   ///
   /// - **macOS:** Only supported on macOS 10.15+.
   ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
-  /// - **Windows:** Unsupported.
+  /// - **Windows:** The devtools window opened by the user is given to the webview of the
+  ///   foreground window, or to the only webview of its browser process.
   ///
   /// # Examples
   ///
@@ -1947,7 +2495,8 @@ impl<R: Runtime> WebviewWindow<R> {
   ///
   /// - **macOS:** Only supported on macOS 10.15+.
   ///   This is a private API on macOS, so you cannot use this if your application will be published on the App Store.
-  /// - **Windows:** Unsupported.
+  /// - **Windows:** The devtools window opened by the user is given to the webview of the
+  ///   foreground window, or to the only webview of its browser process.
   ///
   /// # Examples
   ///
@@ -1971,6 +2520,20 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.is_devtools_open()
   }
 
+  /// Returns the webview zoom level.
+  ///
+  /// See [`Webview::zoom`] for the platform specific details.
+  pub fn zoom(&self) -> crate::Result<f64> {
+    self.webview.zoom()
+  }
+
+  /// Returns the user agent of the webview, the platform default unless it was set.
+  ///
+  /// See [`Webview::user_agent`] for the platform specific details.
+  pub fn user_agent(&self) -> crate::Result<String> {
+    self.webview.user_agent()
+  }
+
   /// Set the webview zoom level
   ///
   /// ## Platform-specific:
@@ -1982,10 +2545,62 @@ impl<R: Runtime> WebviewWindow<R> {
     self.webview.set_zoom(scale_factor)
   }
 
+  /// Sets the user agent of the webview, sent with the requests made after this call.
+  ///
+  /// See [`Webview::set_user_agent`] for the platform specific details.
+  pub fn set_user_agent(&self, user_agent: &str) -> crate::Result<()> {
+    self.webview.set_user_agent(user_agent)
+  }
+
+  /// Changes the webview proxy, `None` for a direct connection.
+  ///
+  /// See [`Webview::set_proxy`] for the platform specific details.
+  pub fn set_proxy(&self, proxy: Option<ProxyConfig>) -> crate::Result<()> {
+    self.webview.set_proxy(proxy)
+  }
+
   /// Clear all browsing data for this webview window.
   pub fn clear_all_browsing_data(&self) -> crate::Result<()> {
     self.webview.clear_all_browsing_data()
   }
+
+  /// Removes the browsing data of the given kinds from the webview data store, returning once
+  /// the platform webview removed it.
+  ///
+  /// See [`Webview::clear_browsing_data`] for the platform specific details.
+  pub async fn clear_browsing_data(&self, types: DataTypes) -> crate::Result<()> {
+    self.webview.clear_browsing_data(types).await
+  }
+
+  /// Returns the cookies of the webview data store sent to `url`.
+  ///
+  /// See [`Webview::cookies_for_url`] for the platform specific details.
+  pub fn cookies_for_url(&self, url: Url) -> crate::Result<Vec<Cookie<'static>>> {
+    self.webview.cookies_for_url(url)
+  }
+
+  /// Returns every cookie of the webview data store.
+  ///
+  /// See [`Webview::cookies`] for the platform specific details.
+  pub fn cookies(&self) -> crate::Result<Vec<Cookie<'static>>> {
+    self.webview.cookies()
+  }
+
+  /// Adds `cookie` to the webview data store, replacing the cookie with the same name, domain
+  /// and path, returning once the platform webview stored it.
+  ///
+  /// See [`Webview::set_cookie`] for the platform specific details.
+  pub async fn set_cookie(&self, cookie: Cookie<'_>) -> crate::Result<()> {
+    self.webview.set_cookie(cookie).await
+  }
+
+  /// Deletes the cookie named `name` sent to `url` from the webview data store, returning once
+  /// the platform webview deleted it.
+  ///
+  /// See [`Webview::delete_cookie`] for the platform specific details.
+  pub async fn delete_cookie(&self, name: &str, url: Url) -> crate::Result<()> {
+    self.webview.delete_cookie(name, url).await
+  }
 }
 
 impl<R: Runtime> Listener<R> for WebviewWindow<R> {
@@ -2035,6 +2650,42 @@ impl<R: Runtime> Listener<R> for WebviewWindow<R> {
     )
   }
 
+  /// Listen to the events accepted by `filter` on this webview window.
+  ///
+  /// See [`Listener::listen_filter`] for more information.
+  fn listen_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: Fn(Event) + Send + 'static,
+  {
+    self.manager().listen_filter(
+      event.into(),
+      EventTarget::WebviewWindow {
+        label: self.label().to_string(),
+      },
+      Box::new(filter),
+      handler,
+    )
+  }
+
+  /// Listen to the first event accepted by `filter` on this webview window.
+  ///
+  /// See [`Listener::listen_filter`] for more information.
+  fn once_filter<P, F>(&self, event: impl Into<String>, filter: P, handler: F) -> EventId
+  where
+    P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+    F: FnOnce(Event) + Send + 'static,
+  {
+    self.manager().once_filter(
+      event.into(),
+      EventTarget::WebviewWindow {
+        label: self.label().to_string(),
+      },
+      Box::new(filter),
+      handler,
+    )
+  }
+
   /// Unlisten to an event on this webview window.
   ///
   /// # Examples
@@ -2165,3 +2816,106 @@ impl<R: Runtime> ManagerBase<R> for WebviewWindow<R> {
     self.webview.managed_app_handle()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use tauri_utils::config::WindowConfig;
+
+  use crate::{
+    test::{mock_builder, mock_context, noop_assets, MockRuntime},
+    App, Manager, Theme, WebviewUrl, WebviewWindowBuilder,
+  };
+
+  fn app_with_palette_preset() -> App<MockRuntime> {
+    let mut context = mock_context(noop_assets());
+    let config = &mut context.config_mut().app;
+
+    config.window_presets.insert(
+      "palette".into(),
+      WindowConfig {
+        label: "palette".into(),
+        theme: Some(Theme::Dark),
+        always_on_top: true,
+        ..Default::default()
+      },
+    );
+
+    config.windows.push(WindowConfig {
+      label: "colors".into(),
+      preset: Some("palette".into()),
+      ..Default::default()
+    });
+
+    config.windows.push(WindowConfig {
+      label: "light-colors".into(),
+      preset: Some("palette".into()),
+      theme: Some(Theme::Light),
+      ..Default::default()
+    });
+
+    mock_builder().build(context).unwrap()
+  }
+
+  #[test]
+  fn config_windows_use_their_preset() {
+    let app = app_with_palette_preset();
+
+    for config in &app.config().app.windows {
+      WebviewWindowBuilder::from_config(&app, config)
+        .unwrap()
+        .build()
+        .unwrap();
+    }
+
+    let colors = app.get_webview_window("colors").unwrap();
+    assert_eq!(colors.theme().unwrap(), Theme::Dark);
+
+    // the fields set on the window override the preset
+    let light_colors = app.get_webview_window("light-colors").unwrap();
+    assert_eq!(light_colors.theme().unwrap(), Theme::Light);
+
+    assert!(app.get_webview_window("palette").is_none());
+  }
+
+  #[test]
+  fn builder_calls_override_the_preset() {
+    let app = app_with_palette_preset();
+
+    let window =
+      WebviewWindowBuilder::from_preset(&app, "palette", "colors", WebviewUrl::default())
+        .theme(Some(Theme::Light))
+        .build()
+        .unwrap();
+
+    assert_eq!(window.label(), "colors");
+    assert_eq!(window.theme().unwrap(), Theme::Light);
+  }
+
+  #[test]
+  fn unknown_presets_fail_when_building() {
+    let app = app_with_palette_preset();
+
+    // the error is deferred so the builder calls can be chained
+    let builder =
+      WebviewWindowBuilder::from_preset(&app, "toolbar", "tools", WebviewUrl::default())
+        .title("Tools");
+
+    assert!(matches!(
+      builder.build(),
+      Err(crate::Error::WindowPresetNotFound(name, available))
+        if name == "toolbar" && available == "`palette`"
+    ));
+    assert!(app.get_webview_window("tools").is_none());
+
+    let config = WindowConfig {
+      label: "tools".into(),
+      preset: Some("toolbar".into()),
+      ..Default::default()
+    };
+
+    assert!(matches!(
+      WebviewWindowBuilder::from_config(&app, &config),
+      Err(crate::Error::WindowPresetNotFound(..))
+    ));
+  }
+}
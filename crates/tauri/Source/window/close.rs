@@ -0,0 +1,238 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Asynchronous close request handling, see [`Window::on_close_requested`].
+
+use std::{
+	future::Future,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+
+use crate::{Runtime, Window, WindowEvent, runtime::WindowDispatch};
+
+/// The decision taken on a request to close a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseDecision {
+	/// Closes the window.
+	Close,
+	/// Keeps the window open.
+	Cancel,
+}
+
+/// A request to close a window, see [`Window::on_close_requested`].
+#[derive(Debug)]
+pub struct CloseRequest<R:Runtime> {
+	window:Window<R>,
+}
+
+impl<R:Runtime> CloseRequest<R> {
+	/// The window requested to close.
+	pub fn window(&self) -> &Window<R> { &self.window }
+
+	/// Shows a modal dialog over the window asking to confirm `message`,
+	/// resolving to [`CloseDecision::Close`] if it was confirmed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS:** The dialog is modal to the whole application.
+	/// - **iOS / Android:** Unsupported.
+	pub fn confirm(
+		&self,
+		title:impl Into<String>,
+		message:impl Into<String>,
+	) -> impl Future<Output = crate::Result<CloseDecision>> + Send + 'static {
+		let dispatcher = self.window.window.dispatcher.clone();
+
+		let (title, message) = (title.into(), message.into());
+
+		async move {
+			// the dialog blocks until it is answered
+			let confirmed =
+				crate::async_runtime::spawn_blocking(move || dispatcher.confirm(title, message))
+					.await??;
+
+			Ok(if confirmed { CloseDecision::Close } else { CloseDecision::Cancel })
+		}
+	}
+}
+
+/// Clears the pending decision when dropped, so the window can be closed again
+/// even if the handler panics.
+struct PendingGuard(Arc<AtomicBool>);
+
+impl Drop for PendingGuard {
+	fn drop(&mut self) { self.0.store(false, Ordering::Release); }
+}
+
+/// Prevents the close requests of `window`, closing it if `handler` decides
+/// to. The requests made while a decision is pending are ignored.
+pub(crate) fn listen<R, F, Fut>(window:&Window<R>, handler:F)
+where
+	R: Runtime,
+	F: Fn(CloseRequest<R>) -> Fut + Send + Sync + 'static,
+	Fut: Future<Output = CloseDecision> + Send + 'static, {
+	let handler = Arc::new(handler);
+
+	let pending = Arc::new(AtomicBool::new(false));
+
+	let window_ = window.clone();
+
+	window.on_window_event(move |event| {
+		let WindowEvent::CloseRequested { api, .. } = event else {
+			return;
+		};
+
+		api.prevent_close();
+
+		if pending.swap(true, Ordering::AcqRel) {
+			return;
+		}
+
+		let window = window_.clone();
+
+		let handler = handler.clone();
+
+		let pending = PendingGuard(pending.clone());
+
+		crate::async_runtime::spawn(async move {
+			let decision = handler(CloseRequest { window:window.clone() }).await;
+
+			drop(pending);
+
+			// destroying does not request the close again
+			if decision == CloseDecision::Close {
+				if let Err(e) = window.destroy() {
+					log::error!("failed to close window `{}`: {e}", window.label());
+				}
+			}
+		});
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{
+		sync::{
+			Arc,
+			Mutex,
+			atomic::{AtomicUsize, Ordering},
+			mpsc::{Receiver, channel},
+		},
+		time::Duration,
+	};
+
+	use super::CloseDecision;
+	use crate::{Manager, WebviewWindow, WindowEvent, test::MockRuntime};
+
+	/// Requests to close `window` until the close handler is called, which
+	/// only happens once the previous decision was taken.
+	fn close_until_handled(window:&WebviewWindow<MockRuntime>, requested:&Receiver<()>) -> bool {
+		(0..500).any(|_| {
+			window.close().unwrap();
+
+			requested.recv_timeout(Duration::from_millis(10)).is_ok()
+		})
+	}
+
+	#[test]
+	fn requests_are_ignored_while_a_decision_is_pending() {
+		let app = crate::test::mock_app();
+
+		let window =
+			crate::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let (requested_tx, requested_rx) = channel();
+
+		let (decision_tx, decision_rx) = channel();
+
+		let decisions = Arc::new(Mutex::new(decision_rx));
+
+		window.on_close_requested(move |_| {
+			requested_tx.send(()).unwrap();
+
+			let decisions = decisions.clone();
+
+			let decision = move || decisions.lock().unwrap().recv().unwrap();
+
+			async move { crate::async_runtime::spawn_blocking(decision).await.unwrap() }
+		});
+
+		let (destroyed_tx, destroyed_rx) = channel();
+
+		window.on_window_event(move |event| {
+			if let WindowEvent::Destroyed = event {
+				let _ = destroyed_tx.send(());
+			}
+		});
+
+		window.close().unwrap();
+
+		window.close().unwrap();
+
+		requested_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+		assert!(app.get_webview_window("main").is_some());
+
+		decision_tx.send(CloseDecision::Close).unwrap();
+
+		destroyed_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+		assert!(requested_rx.try_recv().is_err());
+
+		assert!(app.get_webview_window("main").is_none());
+	}
+
+	#[test]
+	fn cancelled_requests_keep_the_window_open() {
+		let app = crate::test::mock_app();
+
+		let window =
+			crate::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let (requested_tx, requested_rx) = channel();
+
+		window.on_close_requested(move |_| {
+			requested_tx.send(()).unwrap();
+
+			async { CloseDecision::Cancel }
+		});
+
+		assert!(close_until_handled(&window, &requested_rx));
+
+		assert!(close_until_handled(&window, &requested_rx));
+
+		assert!(app.get_webview_window("main").is_some());
+	}
+
+	#[test]
+	fn a_panicking_handler_does_not_block_the_next_requests() {
+		let app = crate::test::mock_app();
+
+		let window =
+			crate::WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let (requested_tx, requested_rx) = channel();
+
+		let calls = Arc::new(AtomicUsize::new(0));
+
+		window.on_close_requested(move |_| {
+			requested_tx.send(()).unwrap();
+
+			let first = calls.fetch_add(1, Ordering::Relaxed) == 0;
+
+			async move {
+				assert!(!first, "the first close request panics");
+
+				CloseDecision::Cancel
+			}
+		});
+
+		assert!(close_until_handled(&window, &requested_rx));
+
+		assert!(close_until_handled(&window, &requested_rx));
+	}
+}
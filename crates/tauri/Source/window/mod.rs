@@ -4,9 +4,20 @@
 
 //! The Tauri window types and functions.
 
+#[cfg(desktop)]
+mod close;
+#[cfg(desktop)]
+mod parent;
 pub(crate) mod plugin;
+#[cfg(desktop)]
+mod progress;
+#[cfg(desktop)]
+pub(crate) mod state;
+#[cfg(desktop)]
+mod widget;
 
 use std::{
+	borrow::Cow,
 	fmt,
 	hash::{Hash, Hasher},
 	sync::{Arc, Mutex, MutexGuard},
@@ -15,13 +26,23 @@ use std::{
 use serde::Serialize;
 use tauri_macros::default_runtime;
 use tauri_runtime::{
-	dpi::{PhysicalPosition, PhysicalSize},
+	dpi::{PhysicalPosition, PhysicalSize, PixelUnit},
 	webview::PendingWebview,
 };
 pub use tauri_utils::{WindowEffect as Effect, WindowEffectState as EffectState, config::Color};
 #[cfg(windows)]
 use windows::Win32::Foundation::HWND;
 
+#[cfg(desktop)]
+pub use self::close::{CloseDecision, CloseRequest};
+#[cfg(desktop)]
+pub use self::parent::ParentDestroyPolicy;
+#[cfg(desktop)]
+pub use self::progress::ProgressState;
+#[cfg(desktop)]
+pub use self::state::StateFlags;
+#[cfg(desktop)]
+pub(crate) use self::parent::{on_close_requested, on_destroyed};
 #[cfg(desktop)]
 pub use crate::runtime::ProgressBarStatus;
 #[cfg(desktop)]
@@ -41,11 +62,13 @@ use crate::{
 	Manager,
 	ResourceTable,
 	Runtime,
+	StateManager,
 	Theme,
 	Webview,
 	WindowEvent,
+	WindowState,
 	app::AppHandle,
-	event::{Event, EventId, EventTarget},
+	event::{Event, EventId, EventPayloadRef, EventTarget},
 	ipc::{CommandArg, CommandItem, InvokeError},
 	manager::AppManager,
 	runtime::{
@@ -55,7 +78,7 @@ use crate::{
 		window::{DetachedWindow, PendingWindow, WindowBuilder as _},
 	},
 	sealed::{ManagerBase, RuntimeOrDispatch},
-	utils::config::{WindowConfig, WindowEffectsConfig},
+	utils::config::{Config, WindowConfig, WindowEffectsConfig},
 	webview::WebviewBuilder,
 };
 
@@ -121,6 +144,21 @@ unstable_struct!(
 		#[cfg(desktop)]
 		on_menu_event:Option<crate::app::GlobalMenuEventListener<Window<R>>>,
 		window_effects:Option<WindowEffectsConfig>,
+		/// The label of the parent window, see [`Self::parent`].
+		#[cfg(desktop)]
+		parent:Option<String>,
+		#[cfg(desktop)]
+		parent_destroy_policy:ParentDestroyPolicy,
+		/// See [`Self::desktop_widget`].
+		#[cfg(desktop)]
+		desktop_widget:bool,
+		/// Whether the window should be visible once created, tracked for
+		/// [`Self::desktop_widget`] which needs to hide it until it is set up.
+		#[cfg(desktop)]
+		visible:bool,
+		/// See [`Self::restore_state`].
+		#[cfg(desktop)]
+		restore_state:Option<StateFlags>,
 	}
 );
 
@@ -206,6 +244,16 @@ async fn create_window(app: tauri::AppHandle) {
 			#[cfg(desktop)]
 			on_menu_event:None,
 			window_effects:None,
+			#[cfg(desktop)]
+			parent:None,
+			#[cfg(desktop)]
+			parent_destroy_policy:Default::default(),
+			#[cfg(desktop)]
+			desktop_widget:false,
+			#[cfg(desktop)]
+			visible:true,
+			#[cfg(desktop)]
+			restore_state:None,
 		}
 	}
 
@@ -214,6 +262,9 @@ async fn create_window(app: tauri::AppHandle) {
 	/// same `label` so make sure that the initial window was closed or change
 	/// the label of the new [`WindowBuilder`].
 	///
+	/// A [`WindowConfig::preset`] is resolved from `tauri.conf.json > app >
+	/// windowPresets`, see [`WindowConfig::with_preset`].
+	///
 	/// # Known issues
 	///
 	/// On Windows, this function deadlocks when used in a synchronous command,
@@ -239,6 +290,10 @@ async fn reopen_window(app: tauri::AppHandle) {
 	)]
 	/// [the Webview2 issue]: https://github.com/tauri-apps/wry/issues/583
 	pub fn from_config(manager:&'a M, config:&WindowConfig) -> crate::Result<Self> {
+		let config = resolve_preset(manager.config(), config)?;
+
+		let config = &*config;
+
 		#[cfg_attr(not(windows), allow(unused_mut))]
     let mut builder = Self {
       manager,
@@ -252,6 +307,16 @@ async fn reopen_window(app: tauri::AppHandle) {
       menu: None,
       #[cfg(desktop)]
       on_menu_event: None,
+      #[cfg(desktop)]
+      parent: None,
+      #[cfg(desktop)]
+      parent_destroy_policy: Default::default(),
+      #[cfg(desktop)]
+      desktop_widget: false,
+      #[cfg(desktop)]
+      visible: config.visible,
+      #[cfg(desktop)]
+      restore_state: None,
     };
 
 		#[cfg(desktop)]
@@ -338,7 +403,37 @@ tauri::Builder::default()
 		self,
 		webview:Option<PendingWebview<EventLoopMessage, R>>,
 	) -> crate::Result<Window<R>> {
-		let mut pending = PendingWindow::new(self.window_builder.clone(), self.label.clone())?;
+		#[cfg_attr(not(desktop), allow(unused_mut))]
+		let mut window_builder = self.window_builder.clone();
+
+		#[cfg(desktop)]
+		let mut visible = self.visible;
+
+		#[cfg(desktop)]
+		if let Some(flags) = self.restore_state {
+			if let Some(saved) = state::saved_state(self.manager, &self.label) {
+				let app_handle = self.manager.app_handle();
+
+				let monitors = app_handle.available_monitors().unwrap_or_default();
+
+				let primary_monitor = app_handle.primary_monitor().ok().flatten();
+
+				window_builder =
+					saved.apply(flags, window_builder, &monitors, primary_monitor.as_ref());
+
+				if flags.contains(StateFlags::VISIBLE) {
+					visible = saved.visible;
+				}
+			}
+		}
+
+		// desktop widgets are shown once set up, see `widget::make_desktop_widget`
+		#[cfg(desktop)]
+		if self.desktop_widget {
+			window_builder = window_builder.visible(false);
+		}
+
+		let mut pending = PendingWindow::new(window_builder, self.label.clone())?;
 
 		if let Some(webview) = webview {
 			pending.set_webview(webview);
@@ -394,10 +489,44 @@ tauri::Builder::default()
 			window.on_menu_event(handler);
 		}
 
+		#[cfg(linux_dbus_menu)]
+		window.export_dbus_menu();
+
+		#[cfg(desktop)]
+		if let Some(parent) = self.parent {
+			app_manager
+				.window
+				.parents
+				.lock()
+				.unwrap()
+				.insert(window.label().to_string(), (parent, self.parent_destroy_policy));
+		}
+
+		#[cfg(desktop)]
+		if let Some(flags) = self.restore_state {
+			app_manager
+				.window
+				.restored_states
+				.lock()
+				.unwrap()
+				.insert(window.label().to_string(), flags);
+		}
+
 		if let Some(effects) = self.window_effects {
 			crate::vibrancy::set_window_effects(&window, Some(effects))?;
 		}
 
+		#[cfg(desktop)]
+		if self.desktop_widget {
+			let window_ = window.clone();
+
+			crate::run_main_thread!(window, move || widget::make_desktop_widget(&window_))??;
+
+			if visible {
+				window.show()?;
+			}
+		}
+
 		let app_manager = self.manager.manager_owned();
 
 		let window_label = window.label().to_string();
@@ -477,6 +606,21 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		self
 	}
 
+	/// Locks the inner size of the window to the given width to height ratio
+	/// when it is resized.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** the aspect ratio is only kept when the window is resized
+	///   by the user.
+	/// - **iOS / Android:** Unsupported.
+	#[must_use]
+	pub fn aspect_ratio(mut self, ratio:f64) -> Self {
+		self.window_builder = self.window_builder.aspect_ratio(ratio);
+
+		self
+	}
+
 	/// Whether the window is resizable or not.
 	/// When resizable is set to false, native window's maximize button is
 	/// automatically disabled.
@@ -545,6 +689,22 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		self
 	}
 
+	/// Starts the window in fullscreen on the given monitor, either its index
+	/// in [`Window::available_monitors`] or its name.
+	///
+	/// The window falls back to the monitor it would be created on when the
+	/// monitor is not found.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Android / iOS**: Unsupported.
+	#[must_use]
+	pub fn fullscreen_monitor(mut self, monitor:crate::utils::config::FullscreenMonitor) -> Self {
+		self.window_builder = self.window_builder.fullscreen_monitor(monitor);
+
+		self
+	}
+
 	/// Sets the window to be initially focused.
 	#[must_use]
 	#[deprecated(
@@ -579,6 +739,11 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 	pub fn visible(mut self, visible:bool) -> Self {
 		self.window_builder = self.window_builder.visible(visible);
 
+		#[cfg(desktop)]
+		{
+			self.visible = visible;
+		}
+
 		self
 	}
 
@@ -614,6 +779,26 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		self
 	}
 
+	/// Restores the `flags` properties of the window saved by a previous run,
+	/// and saves them when the window is requested to close and when the app is
+	/// requested to exit.
+	///
+	/// The state is read synchronously before the native window is created, so
+	/// it opens with its saved size and position. A saved position that is no
+	/// longer on any monitor is moved onto the primary monitor.
+	///
+	/// The states are saved by window label in the `window-states.json` file of
+	/// the app config directory, see also [`AppHandle::save_window_state`].
+	///
+	/// [`AppHandle::save_window_state`]: crate::AppHandle::save_window_state
+	#[cfg(desktop)]
+	#[must_use]
+	pub fn restore_state(mut self, flags:StateFlags) -> Self {
+		self.restore_state = Some(flags);
+
+		self
+	}
+
 	/// Whether the window should always be below other windows.
 	#[must_use]
 	pub fn always_on_bottom(mut self, always_on_bottom:bool) -> Self {
@@ -622,6 +807,35 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		self
 	}
 
+	/// Turns the window into a desktop widget: it stays below all normal
+	/// windows, is not shown in the taskbar or the window switcher and does not
+	/// take the focus when shown or clicked. Mouse events still reach its
+	/// contents.
+	///
+	/// Enabling it sets [`Self::always_on_bottom`], [`Self::skip_taskbar`] and
+	/// [`Self::focused`], plus the following:
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** Adds the `WS_EX_NOACTIVATE` and `WS_EX_TOOLWINDOW`
+	///   extended styles and pins the window to `HWND_BOTTOM`.
+	/// - **Linux:** Sets the `_NET_WM_WINDOW_TYPE_DESKTOP` type hint, the skip
+	///   pager and keep below hints. The window manager decides how these are
+	///   honored, most Wayland compositors ignore them.
+	/// - **macOS:** The window joins all spaces, is stationary and is ignored by
+	///   the window cycling. Clicking it still activates the app.
+	#[cfg(desktop)]
+	#[must_use]
+	pub fn desktop_widget(mut self, desktop_widget:bool) -> Self {
+		self.desktop_widget = desktop_widget;
+
+		if desktop_widget {
+			self = self.always_on_bottom(true).skip_taskbar(true).focused(false);
+		}
+
+		self
+	}
+
 	/// Whether the window should always be on top of other windows.
 	#[must_use]
 	pub fn always_on_top(mut self, always_on_top:bool) -> Self {
@@ -687,7 +901,8 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 	///   - `false` has no effect on decorated window, shadows are always ON.
 	///   - `true` will make undecorated window have a 1px white border, and on
 	///     Windows 11, it will have a rounded corners.
-	/// - **Linux:** Unsupported.
+	/// - **Linux:** `true` creates the window with the GTK client-side decorations,
+	///   which draw the shadow and the resize borders around it when undecorated.
 	#[must_use]
 	pub fn shadow(mut self, enable:bool) -> Self {
 		self.window_builder = self.window_builder.shadow(enable);
@@ -707,7 +922,13 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 	///     - An owned window is hidden when its owner is minimized.
 	/// - **Linux**: This makes the new window transient for parent, see <https://docs.gtk.org/gtk3/method.Window.set_transient_for.html>
 	/// - **macOS**: This adds the window as a child of parent, see <https://developer.apple.com/documentation/appkit/nswindow/1419152-addchildwindow?language=objc>
+	///
+	/// See [`Self::parent_destroy_policy`] for what happens to the window when
+	/// its parent is destroyed, and [`Window::set_parent`] to change the parent
+	/// later.
 	pub fn parent(mut self, parent:&Window<R>) -> crate::Result<Self> {
+		self.parent.replace(parent.label().to_string());
+
 		#[cfg(windows)]
 		{
 			self.window_builder = self.window_builder.owner(parent.hwnd()?);
@@ -732,6 +953,15 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		Ok(self)
 	}
 
+	/// What happens to the window when its parent, see [`Self::parent`], is
+	/// closed or destroyed. Defaults to [`ParentDestroyPolicy::Platform`].
+	#[must_use]
+	pub fn parent_destroy_policy(mut self, policy:ParentDestroyPolicy) -> Self {
+		self.parent_destroy_policy = policy;
+
+		self
+	}
+
 	/// Set an owner to the window to be created.
 	///
 	/// From MSDN:
@@ -865,6 +1095,36 @@ impl<'a, R:Runtime, M:Manager<R>> WindowBuilder<'a, R, M> {
 		self
 	}
 
+	/// Sets the position of the window controls (traffic lights) from the
+	/// top-left corner of the window, kept when the window is resized.
+	///
+	/// The window needs a title bar, usually with the
+	/// [`crate::TitleBarStyle::Overlay`] style and a hidden title, there are no
+	/// traffic lights without the window decorations.
+	#[cfg(target_os = "macos")]
+	#[must_use]
+	pub fn traffic_light_position<P:Into<Position>>(mut self, position:P) -> Self {
+		self.window_builder = self.window_builder.traffic_light_position(position);
+
+		self
+	}
+
+	/// Extends the web content into the title bar, the caption buttons being
+	/// drawn by the system over it. Use [`Window::title_bar_rects`] to lay out
+	/// the content around them.
+	///
+	/// The title bar is not draggable anymore, use a `data-tauri-drag-region`
+	/// element instead.
+	///
+	/// Requires the window decorations.
+	#[cfg(windows)]
+	#[must_use]
+	pub fn titlebar_overlay(mut self, enabled:bool) -> Self {
+		self.window_builder = self.window_builder.titlebar_overlay(enabled);
+
+		self
+	}
+
 	/// Defines the window [tabbing identifier] for macOS.
 	///
 	/// Windows with matching tabbing identifiers will be grouped together.
@@ -909,6 +1169,33 @@ impl<R:Runtime, M:Manager<R>> WindowBuilder<'_, R, M> {
 		self
 	}
 }
+
+/// Bases a window configuration on its `preset` from `app > windowPresets`.
+pub(crate) fn resolve_preset<'c>(
+	config:&Config,
+	window:&'c WindowConfig,
+) -> crate::Result<Cow<'c, WindowConfig>> {
+	let Some(name) = &window.preset else {
+		return Ok(Cow::Borrowed(window));
+	};
+
+	match config.app.window_presets.get(name) {
+		Some(preset) => Ok(Cow::Owned(window.with_preset(preset))),
+		None => {
+			let mut available = config
+				.app
+				.window_presets
+				.keys()
+				.map(|name| format!("`{name}`"))
+				.collect::<Vec<_>>();
+
+			available.sort();
+
+			Err(crate::Error::WindowPresetNotFound(name.clone(), available.join(", ")))
+		},
+	}
+}
+
 /// A wrapper struct to hold the window menu state
 /// and whether it is global per-app or specific to this window.
 #[cfg(desktop)]
@@ -933,6 +1220,8 @@ pub struct Window<R:Runtime> {
 	#[cfg(desktop)]
 	pub(crate) menu:Arc<Mutex<Option<WindowMenu<R>>>>,
 	pub(crate) resources_table:Arc<Mutex<ResourceTable>>,
+	/// The state scoped to this window, see [`Window::manage_scoped`].
+	pub(crate) state:Arc<StateManager>,
 }
 
 impl<R:Runtime> std::fmt::Debug for Window<R> {
@@ -971,6 +1260,7 @@ impl<R:Runtime> Clone for Window<R> {
 			#[cfg(desktop)]
 			menu:self.menu.clone(),
 			resources_table:self.resources_table.clone(),
+			state:self.state.clone(),
 		}
 	}
 }
@@ -1027,6 +1317,7 @@ impl<R:Runtime> Window<R> {
 			#[cfg(desktop)]
 			menu:Arc::new(std::sync::Mutex::new(menu)),
 			resources_table:Default::default(),
+			state:Arc::new(StateManager::new()),
 		}
 	}
 
@@ -1089,10 +1380,104 @@ impl<R:Runtime> Window<R> {
 	/// The label of this window.
 	pub fn label(&self) -> &str { &self.window.label }
 
+	/// Adds `state` to the state scoped to this window, returning `false` if a
+	/// state of the same type is already managed by the window.
+	///
+	/// Unlike [`Manager::manage`], which manages state for the whole app, the
+	/// window state is only available from this window, its webviews and the
+	/// commands they invoke with the [`WindowState`](crate::WindowState)
+	/// extractor. It is released when the window is destroyed, after the
+	/// [`Builder::on_window_state_dropped`](crate::Builder::on_window_state_dropped)
+	/// hooks ran, even if handles to the window are still alive. The values are
+	/// then dropped with their last [`WindowState`](crate::WindowState) guard.
+	///
+	/// # Examples
+	/// ```
+	/// use std::sync::Mutex;
+	///
+	/// use tauri::WindowState;
+	///
+	/// #[derive(Default)]
+	/// struct UndoStack(Mutex<Vec<String>>);
+	///
+	/// #[tauri::command]
+	/// fn undo(stack: WindowState<'_, UndoStack>) -> Option<String> {
+	///   stack.0.lock().unwrap().pop()
+	/// }
+	///
+	/// tauri::Builder::default()
+	///   .on_page_load(|webview, _| {
+	///     webview.window().manage_scoped(UndoStack::default());
+	///   })
+	///   .invoke_handler(tauri::generate_handler![undo]);
+	/// ```
+	pub fn manage_scoped<T:Send + Sync + 'static>(&self, state:T) -> bool {
+		self.state.set(state)
+	}
+
+	/// Removes the state of type `T` scoped to this window, returning it if it
+	/// is not borrowed anymore.
+	pub fn unmanage_scoped<T:Send + Sync + 'static>(&self) -> Option<T> {
+		self.state.unmanage()
+	}
+
+	/// Retrieves the state of type `T` scoped to this window.
+	///
+	/// The guard only borrows the state for its own lifetime, so the state is
+	/// released with the window.
+	///
+	/// # Panics
+	///
+	/// Panics if the state has not been [managed](Self::manage_scoped) by the
+	/// window. Use [`Self::try_scoped_state`] for a non-panicking version.
+	pub fn scoped_state<T:Send + Sync + 'static>(&self) -> WindowState<'_, T> {
+		self.try_scoped_state()
+			.expect("scoped_state() called before manage_scoped() for given type")
+	}
+
+	/// Attempts to retrieve the state of type `T` scoped to this window.
+	pub fn try_scoped_state<T:Send + Sync + 'static>(&self) -> Option<WindowState<'_, T>> {
+		self.state.try_get_arc().map(WindowState::new)
+	}
+
 	/// Registers a window event listener.
 	pub fn on_window_event<F:Fn(&WindowEvent) + Send + 'static>(&self, f:F) {
 		self.window.dispatcher.on_window_event(move |event| f(&event.clone().into()));
 	}
+
+	/// Registers an asynchronous close request handler.
+	///
+	/// The window is only closed once the future returned by `handler`
+	/// resolves to [`CloseDecision::Close`]. The close requests made while a
+	/// decision is pending are ignored.
+	#[cfg_attr(
+		feature = "unstable",
+		doc = r####"
+```rust,no_run
+use tauri::{Manager, window::CloseDecision};
+tauri::Builder::default()
+  .setup(|app| {
+    let window = app.get_window("main").unwrap();
+
+    window.on_close_requested(|request| async move {
+      request
+        .confirm("Unsaved changes", "Close without saving?")
+        .await
+        .unwrap_or(CloseDecision::Cancel)
+    });
+
+    Ok(())
+  });
+```
+  "####
+	)]
+	#[cfg(desktop)]
+	pub fn on_close_requested<F, Fut>(&self, handler:F)
+	where
+		F: Fn(CloseRequest<R>) -> Fut + Send + Sync + 'static,
+		Fut: std::future::Future<Output = CloseDecision> + Send + 'static, {
+		close::listen(self, handler)
+	}
 }
 
 /// Menu APIs
@@ -1211,6 +1596,9 @@ tauri::Builder::default()
 
 		self.menu_lock().replace(WindowMenu { is_app_wide:false, menu });
 
+		#[cfg(linux_dbus_menu)]
+		self.export_dbus_menu();
+
 		Ok(prev_menu)
 	}
 
@@ -1224,6 +1612,11 @@ tauri::Builder::default()
 	pub fn remove_menu(&self) -> crate::Result<Option<Menu<R>>> {
 		let prev_menu = self.menu_lock().take().map(|m| m.menu);
 
+		#[cfg(linux_dbus_menu)]
+		if let Some(exporter) = self.manager.menu.dbus_exporter.get() {
+			exporter.unexport::<R>(self.label());
+		}
+
 		// remove from the window
 		#[cfg_attr(target_os = "macos", allow(unused_variables))]
 		if let Some(menu) = &prev_menu {
@@ -1252,6 +1645,32 @@ tauri::Builder::default()
 		Ok(prev_menu)
 	}
 
+	/// Exports the window menu over DBus if enabled, hiding the in-window menu
+	/// when a global menu registrar accepted it.
+	#[cfg(linux_dbus_menu)]
+	pub(crate) fn export_dbus_menu(&self) {
+		use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+
+		let Some(exporter) = self.manager.menu.dbus_exporter.get() else {
+			return;
+		};
+
+		let Some(menu) = self.menu_lock().as_ref().map(|m| m.menu.clone()) else {
+			return;
+		};
+
+		// the AppMenu registrar identifies windows by their X11 window id
+		let xid = match self.window_handle().map(|handle| handle.as_raw()) {
+			Ok(RawWindowHandle::Xlib(handle)) => handle.window as u32,
+			Ok(RawWindowHandle::Xcb(handle)) => handle.window.get(),
+			_ => return,
+		};
+
+		if exporter.export(self.app_handle(), self.label(), xid, &menu) {
+			let _ = self.hide_menu();
+		}
+	}
+
 	/// Hides the window menu.
 	pub fn hide_menu(&self) -> crate::Result<()> {
 		// remove from the window
@@ -1392,6 +1811,31 @@ impl<R:Runtime> Window<R> {
 		self.window.dispatcher.outer_size().map_err(Into::into)
 	}
 
+	/// Returns the physical minimum and maximum inner size of the window.
+	///
+	/// A size is `None` when neither of its dimensions is constrained, and a
+	/// dimension that is not constrained is `0` for the minimum size and
+	/// `u32::MAX` for the maximum size.
+	pub fn size_constraints(&self) -> crate::Result<(Option<Size>, Option<Size>)> {
+		let constraints = self.window.dispatcher.size_constraints()?;
+
+		let scale_factor = self.window.dispatcher.scale_factor()?;
+
+		let size = |width:Option<PixelUnit>, height:Option<PixelUnit>, default:u32| {
+			let physical = |unit:Option<PixelUnit>| {
+				unit.map_or(default, |unit| unit.to_physical::<u32>(scale_factor).0)
+			};
+
+			(width.is_some() || height.is_some())
+				.then(|| PhysicalSize::new(physical(width), physical(height)).into())
+		};
+
+		Ok((
+			size(constraints.min_width, constraints.min_height, 0),
+			size(constraints.max_width, constraints.max_height, u32::MAX),
+		))
+	}
+
 	/// Gets the window's current fullscreen state.
 	pub fn is_fullscreen(&self) -> crate::Result<bool> {
 		self.window.dispatcher.is_fullscreen().map_err(Into::into)
@@ -1580,6 +2024,22 @@ impl<R:Runtime> Window<R> {
 	pub fn theme(&self) -> crate::Result<Theme> {
 		self.window.dispatcher.theme().map_err(Into::into)
 	}
+
+	/// Returns the regions of the client area covered by the title bar
+	/// controls, in physical pixels from its top-left corner.
+	///
+	/// [`WindowEvent::TitleBarRectsChanged`] is emitted when they change.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS**: The traffic lights, when the content extends under the title
+	///   bar.
+	/// - **Windows**: The caption buttons, when the title bar overlay is enabled
+	///   with [`WindowBuilder::titlebar_overlay`].
+	/// - **Linux / iOS / Android**: Unsupported, returns an empty list.
+	pub fn title_bar_rects(&self) -> crate::Result<Vec<crate::Rect>> {
+		self.window.dispatcher.title_bar_rects().map_err(Into::into)
+	}
 }
 
 /// Desktop window getters.
@@ -1711,9 +2171,54 @@ impl<R:Runtime> Window<R> {
 	/// Destroys this window. Similar to [`Self::close`] but does not emit any
 	/// events and force close the window instead.
 	pub fn destroy(&self) -> crate::Result<()> {
+		let window = self.clone();
+
+		// the window may already be gone, e.g. when the app exits
+		let _ = crate::run_main_thread!(self, move || parent::detach_orphans(&window));
+
 		self.window.dispatcher.destroy().map_err(Into::into)
 	}
 
+	/// Makes this window a child of `parent`, or a top-level window if `parent`
+	/// is `None`.
+	///
+	/// A child window stays above its parent and is minimized with it, see
+	/// [`WebviewWindowBuilder::parent`](crate::webview::WebviewWindowBuilder::parent)
+	/// for the platform details. The
+	/// [`ParentDestroyPolicy`] set when the window was created is kept.
+	pub fn set_parent(&self, parent:Option<&Window<R>>) -> crate::Result<()> {
+		let window = self.clone();
+
+		let parent_ = parent.cloned();
+
+		crate::run_main_thread!(self, move || {
+			parent::set_native_parent(&window, parent_.as_ref())
+		})??;
+
+		let mut parents = self.manager.window.parents.lock().unwrap();
+
+		match parent {
+			Some(parent) => {
+				let policy =
+					parents.get(self.label()).map(|(_, policy)| *policy).unwrap_or_default();
+
+				parents.insert(self.label().to_string(), (parent.label().to_string(), policy));
+			},
+			None => {
+				parents.remove(self.label());
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Returns the parent of this window, see [`Self::set_parent`].
+	pub fn parent(&self) -> Option<Window<R>> {
+		let parent = self.manager.window.parents.lock().unwrap().get(self.label()).cloned();
+
+		parent.and_then(|(label, _)| self.manager.get_window(&label))
+	}
+
 	/// Determines if this window should be [decorated].
 	///
 	/// [decorated]: https://en.wikipedia.org/wiki/Window_(computing)#Window_decoration
@@ -1729,7 +2234,8 @@ impl<R:Runtime> Window<R> {
 	///   - `false` has no effect on decorated window, shadow are always ON.
 	///   - `true` will make undecorated window have a 1px white border, and on
 	///     Windows 11, it will have a rounded corners.
-	/// - **Linux:** Unsupported.
+	/// - **Linux:** Only has an effect on the windows created with a shadow, which
+	///   keep the GTK client-side decorations drawing it when undecorated.
 	pub fn set_shadow(&self, enable:bool) -> crate::Result<()> {
 		self.window.dispatcher.set_shadow(enable).map_err(Into::into)
 	}
@@ -1828,17 +2334,20 @@ tauri::Builder::default()
 		self.window.dispatcher.set_size(size.into()).map_err(Into::into)
 	}
 
-	/// Sets this window's minimum inner size.
+	/// Sets this window's minimum inner size, resizing the window if it is
+	/// smaller.
 	pub fn set_min_size<S:Into<Size>>(&self, size:Option<S>) -> crate::Result<()> {
 		self.window.dispatcher.set_min_size(size.map(|s| s.into())).map_err(Into::into)
 	}
 
-	/// Sets this window's maximum inner size.
+	/// Sets this window's maximum inner size, resizing the window if it is
+	/// larger.
 	pub fn set_max_size<S:Into<Size>>(&self, size:Option<S>) -> crate::Result<()> {
 		self.window.dispatcher.set_max_size(size.map(|s| s.into())).map_err(Into::into)
 	}
 
-	/// Sets this window's minimum inner width.
+	/// Sets this window's minimum and maximum inner size, resizing the window
+	/// to satisfy them.
 	pub fn set_size_constraints(
 		&self,
 		constriants:tauri_runtime::window::WindowSizeConstraints,
@@ -1846,6 +2355,18 @@ tauri::Builder::default()
 		self.window.dispatcher.set_size_constraints(constriants).map_err(Into::into)
 	}
 
+	/// Locks this window's inner size to the given width to height ratio,
+	/// resizing the window to it, or unlocks it when `None`.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** the aspect ratio is only kept when the window is resized
+	///   by the user.
+	/// - **iOS / Android:** Unsupported.
+	pub fn set_aspect_ratio(&self, ratio:Option<f64>) -> crate::Result<()> {
+		self.window.dispatcher.set_aspect_ratio(ratio).map_err(Into::into)
+	}
+
 	/// Sets this window's position.
 	pub fn set_position<Pos:Into<Position>>(&self, position:Pos) -> crate::Result<()> {
 		self.window.dispatcher.set_position(position.into()).map_err(Into::into)
@@ -1856,6 +2377,33 @@ tauri::Builder::default()
 		self.window.dispatcher.set_fullscreen(fullscreen).map_err(Into::into)
 	}
 
+	/// Moves the window to `monitor` and makes it fullscreen there in a single
+	/// step, without showing the window on its way.
+	///
+	/// If the monitor is disconnected while the window is fullscreen, the window
+	/// is made fullscreen on the primary monitor and
+	/// [`WindowEvent::FullscreenMonitorDisconnected`] is emitted. Once the
+	/// window leaves the fullscreen, the monitor is no longer tracked.
+	///
+	/// Fails when `monitor` is not connected.
+	///
+	/// ## Platform-specific
+	///
+	/// - **macOS**: The window uses a borderless fullscreen instead of a
+	///   separate space.
+	/// - **Android / iOS**: Unsupported.
+	pub fn set_fullscreen_on_monitor(&self, monitor:&Monitor) -> crate::Result<()> {
+		self.window
+			.dispatcher
+			.set_fullscreen_on_monitor(RuntimeMonitor {
+				name:monitor.name.clone(),
+				size:monitor.size,
+				position:monitor.position,
+				scale_factor:monitor.scale_factor,
+			})
+			.map_err(Into::into)
+	}
+
 	/// Bring the window to front and focus.
 	pub fn set_focus(&self) -> crate::Result<()> {
 		self.window.dispatcher.set_focus().map_err(Into::into)
@@ -1919,6 +2467,30 @@ tauri::Builder::default()
 		self.window.dispatcher.set_ignore_cursor_events(ignore).map_err(Into::into)
 	}
 
+	/// Ignores the window cursor events outside of the interactive `regions`,
+	/// in window coordinates, so the clicks pass through to the windows
+	/// behind. `None` handles the cursor events of the whole window again.
+	///
+	/// The regions can be updated on every frame to follow the page layout.
+	/// Ignoring the cursor events with [`Window::set_ignore_cursor_events`]
+	/// takes precedence over the regions.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows / macOS**: Every mouse event is hit tested against the
+	///   regions before it reaches a window, and the whole window ignores the
+	///   cursor events while the cursor is outside of them. The mouse events
+	///   are read with a low-level mouse hook on Windows.
+	/// - **Linux**: The regions are the window input shape, which requires a
+	///   compositor supporting it on Wayland.
+	/// - **iOS / Android**: Unsupported.
+	pub fn set_ignore_cursor_events_region(
+		&self,
+		regions:Option<Vec<crate::Rect>>,
+	) -> crate::Result<()> {
+		self.window.dispatcher.set_ignore_cursor_events_region(regions).map_err(Into::into)
+	}
+
 	/// Starts dragging the window.
 	pub fn start_dragging(&self) -> crate::Result<()> {
 		self.window.dispatcher.start_dragging().map_err(Into::into)
@@ -1935,7 +2507,9 @@ tauri::Builder::default()
 	/// Sets the overlay icon on the taskbar **Windows only**. Using `None` to
 	/// remove the overlay icon
 	///
-	/// The overlay icon can be unique for each window.
+	/// The overlay icon can be unique for each window. It is hidden while the
+	/// window or the app has a badge, and shown again once the badge is
+	/// removed.
 	#[cfg(target_os = "windows")]
 	#[cfg_attr(docsrs, doc(cfg(target_os = "windows")))]
 	pub fn set_overlay_icon(&self, icon:Option<Image<'_>>) -> crate::Result<()> {
@@ -1947,44 +2521,82 @@ tauri::Builder::default()
 
 	/// Sets the taskbar badge count. Using `0` or `None` will remove the badge
 	///
+	/// See [`AppHandle::set_badge_count`](crate::AppHandle::set_badge_count) to
+	/// set the badge of the whole app.
+	///
 	/// ## Platform-specific
-	/// - **Windows:** Unsupported, use [`Window::set_overlay_icon`] instead.
+	/// - **Windows:** The count is drawn as the taskbar overlay icon of this
+	///   window, replacing the one set with [`Window::set_overlay_icon`] until
+	///   the badge is removed. Counts above 99 are shown as `99+`, negative
+	///   counts remove the badge.
+	/// - **Linux / macOS:** The badge is app-wide and not specific to this
+	///   window.
+	/// - **Linux:** Shown for the `.desktop` file named after the package
+	///   name, or [`Builder::linux_app_id`](crate::Builder::linux_app_id) when
+	///   set.
 	/// - **iOS:** iOS expects i32, the value will be clamped to i32::MIN,
 	///   i32::MAX.
 	/// - **Android:** Unsupported.
 	pub fn set_badge_count(&self, count:Option<i64>) -> crate::Result<()> {
 		self.window
 			.dispatcher
-			.set_badge_count(count, Some(format!("{}.desktop", self.package_info().name)))
+			.set_badge_count(count, Some(self.manager.package_desktop_file_name()))
 			.map_err(Into::into)
 	}
 
-	/// Sets the taskbar badge label **macOS only**. Using `None` will remove
-	/// the badge
-	#[cfg(target_os = "macos")]
-	#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
+	/// Sets the taskbar badge label, for non-numeric badges. Using `None` will
+	/// remove the badge
+	///
+	/// ## Platform-specific
+	/// - **Windows:** The label is drawn as the taskbar overlay icon of this
+	///   window, replacing the one set with [`Window::set_overlay_icon`] until
+	///   the badge is removed, so it should be a few characters at most.
+	/// - **macOS:** The badge is app-wide and not specific to this window.
+	/// - **Linux / iOS / Android:** Unsupported.
 	pub fn set_badge_label(&self, label:Option<String>) -> crate::Result<()> {
 		self.window.dispatcher.set_badge_label(label).map_err(Into::into)
 	}
 
+	/// Sets the taskbar or dock progress indicator.
+	///
+	/// Setting [`ProgressState::None`] hides the indicator and resets its
+	/// progress.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux / macOS**: The progress is app-wide and not specific to this
+	///   window.
+	/// - **Linux**: Shown by desktop environments implementing the `libunity`
+	///   launcher API, e.g. KDE or GNOME with the Dash to Dock extension, for the
+	///   `.desktop` file named after [`Builder::linux_app_id`]. The request is
+	///   ignored when the D-Bus session bus can't be connected to.
+	/// - **macOS**: The indeterminate state is animated on the dock tile.
+	///
+	/// [`Builder::linux_app_id`]: crate::Builder::linux_app_id
+	pub fn set_progress(&self, state:ProgressState) -> crate::Result<()> {
+		progress::set_progress(self, state)
+	}
+
 	/// Sets the taskbar progress state.
 	///
+	/// Prefer [`Self::set_progress`], which sets the status and progress
+	/// together.
+	///
 	/// ## Platform-specific
 	///
 	/// - **Linux / macOS**: Progress bar is app-wide and not specific to this
 	///   window.
 	/// - **Linux**: Only supported desktop environments with `libunity` (e.g.
-	///   GNOME).
+	///   GNOME), for the `.desktop` file named after the package name, or
+	///   [`Builder::linux_app_id`](crate::Builder::linux_app_id) when set.
 	/// - **iOS / Android:** Unsupported.
 	pub fn set_progress_bar(&self, progress_state:ProgressBarState) -> crate::Result<()> {
-		self.window
-			.dispatcher
-			.set_progress_bar(crate::runtime::ProgressBarState {
-				status:progress_state.status,
-				progress:progress_state.progress,
-				desktop_filename:Some(format!("{}.desktop", self.package_info().name)),
-			})
-			.map_err(Into::into)
+		progress::set_progress_bar(
+			self,
+			progress_state.status,
+			progress_state.progress,
+			self.manager.package_desktop_file_name(),
+		)
 	}
 
 	/// Sets the title bar style. **macOS only**.
@@ -2074,6 +2686,36 @@ tauri::Builder::default()
 		)
 	}
 
+	/// Listen to the events accepted by `filter` on this window.
+	///
+	/// See [`Listener::listen_filter`] for more information.
+	fn listen_filter<P, F>(&self, event:impl Into<String>, filter:P, handler:F) -> EventId
+	where
+		P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+		F: Fn(Event) + Send + 'static, {
+		self.manager.listen_filter(
+			event.into(),
+			EventTarget::Window { label:self.label().to_string() },
+			Box::new(filter),
+			handler,
+		)
+	}
+
+	/// Listen to the first event accepted by `filter` on this window.
+	///
+	/// See [`Listener::listen_filter`] for more information.
+	fn once_filter<P, F>(&self, event:impl Into<String>, filter:P, handler:F) -> EventId
+	where
+		P: Fn(&EventPayloadRef<'_>) -> bool + Send + 'static,
+		F: FnOnce(Event) + Send + 'static, {
+		self.manager.once_filter(
+			event.into(),
+			EventTarget::Window { label:self.label().to_string() },
+			Box::new(filter),
+			handler,
+		)
+	}
+
 	/// Unlisten to an event on this window.
 	///
 	/// # Examples
@@ -2254,10 +2896,132 @@ impl From<WindowEffectsConfig> for EffectsBuilder {
 
 #[cfg(test)]
 mod tests {
+	use std::sync::{
+		Arc,
+		Mutex,
+		atomic::{AtomicBool, Ordering},
+	};
+
+	use crate::{
+		Manager,
+		WebviewWindowBuilder,
+		WindowState,
+		ipc::{CallbackFn, InvokeBody},
+		test::{INVOKE_KEY, MockRuntime, get_ipc_response, mock_builder, mock_context, noop_assets},
+		webview::InvokeRequest,
+	};
+
+	struct UndoStack(Mutex<Vec<String>>);
+
+	/// Sets its flag once dropped.
+	struct Document(Arc<AtomicBool>);
+
+	impl Drop for Document {
+		fn drop(&mut self) { self.0.store(true, Ordering::SeqCst); }
+	}
+
+	#[crate::command(root = "crate")]
+	fn undo(stack:WindowState<'_, UndoStack>) -> Option<String> { stack.0.lock().unwrap().pop() }
+
+	fn invoke_undo(window:&crate::WebviewWindow<MockRuntime>) -> Result<Option<String>, String> {
+		get_ipc_response(
+			window,
+			InvokeRequest {
+				cmd:"undo".into(),
+				callback:CallbackFn(0),
+				error:CallbackFn(1),
+				url:"tauri://localhost".parse().unwrap(),
+				body:InvokeBody::default(),
+				headers:Default::default(),
+				invoke_key:INVOKE_KEY.to_string(),
+			},
+		)
+		.map(|body| body.deserialize().unwrap())
+		.map_err(|e| e.as_str().unwrap().to_string())
+	}
+
 	#[test]
 	fn window_is_send_sync() {
 		crate::test_utils::assert_send::<super::Window>();
 
 		crate::test_utils::assert_sync::<super::Window>();
 	}
+
+	#[test]
+	fn commands_extract_the_state_of_their_window() {
+		let app = mock_builder()
+			.invoke_handler(crate::generate_handler![undo])
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let main = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let other = WebviewWindowBuilder::new(&app, "other", Default::default()).build().unwrap();
+
+		assert!(main.as_ref().window().manage_scoped(UndoStack(Mutex::new(vec!["typed".into()]))));
+
+		assert_eq!(invoke_undo(&main), Ok(Some("typed".into())));
+
+		assert_eq!(invoke_undo(&main), Ok(None));
+
+		let error = invoke_undo(&other).unwrap_err();
+
+		assert!(error.contains("window state not managed by window `other`"), "{error}");
+
+		// the app state isn't scoped to any window
+		app.manage(UndoStack(Mutex::new(Vec::new())));
+
+		assert!(invoke_undo(&other).is_err());
+	}
+
+	#[test]
+	fn window_state_is_released_when_the_window_is_destroyed() {
+		let saved = Arc::new(Mutex::new(Vec::new()));
+
+		let saved_ = saved.clone();
+
+		let app = mock_builder()
+			.on_window_state_dropped(move |window| {
+				let dropped = window.try_scoped_state::<Document>().map(|document| {
+					document.0.load(Ordering::SeqCst)
+				});
+
+				saved_.lock().unwrap().push((window.label().to_string(), dropped));
+			})
+			.build(mock_context(noop_assets()))
+			.unwrap();
+
+		let main = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		let window = main.as_ref().window();
+
+		let dropped = Arc::new(AtomicBool::new(false));
+
+		assert!(window.manage_scoped(Document(dropped.clone())));
+
+		assert!(!window.manage_scoped(Document(Arc::new(AtomicBool::new(false)))));
+
+		let guard = window.scoped_state::<Document>().arc();
+
+		app.handle().manager.on_window_close("main");
+
+		// the hooks still see the state, which is released afterwards even though
+		// the window handles are alive
+		assert_eq!(*saved.lock().unwrap(), [("main".to_string(), Some(false))]);
+
+		assert!(app.get_window("main").is_none());
+
+		assert!(window.try_scoped_state::<Document>().is_none());
+
+		assert!(!dropped.load(Ordering::SeqCst));
+
+		drop(guard);
+
+		assert!(dropped.load(Ordering::SeqCst));
+
+		// the hooks only run for the windows that were still open
+		app.handle().manager.on_window_close("main");
+
+		assert_eq!(saved.lock().unwrap().len(), 1);
+	}
 }
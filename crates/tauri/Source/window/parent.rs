@@ -0,0 +1,240 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Parent and child windows, see [`Window::set_parent`].
+
+use crate::{Runtime, Window};
+
+/// What happens to a child window when its parent is closed or destroyed,
+/// see [`WebviewWindowBuilder::parent_destroy_policy`].
+///
+/// [`WebviewWindowBuilder::parent_destroy_policy`]: crate::webview::WebviewWindowBuilder::parent_destroy_policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParentDestroyPolicy {
+	/// The platform decides, like for windows created without a policy.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Windows:** The child window is destroyed with its parent.
+	/// - **Linux / macOS:** The child window stays open as a top-level window.
+	#[default]
+	Platform,
+	/// The child window is destroyed with its parent.
+	Close,
+	/// The child window is detached from its parent before the parent is
+	/// destroyed and stays open as a top-level window.
+	///
+	/// Only close requests and [`Window::destroy`] detach the child windows,
+	/// if the parent is destroyed by other means, e.g. when the app exits, the
+	/// platform may destroy them too.
+	Orphan,
+}
+
+/// Makes `child` a child of `parent` in the native windowing system, or a
+/// top-level window if `parent` is `None`.
+///
+/// Must be called on the main thread.
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(unused_variables))]
+pub(crate) fn set_native_parent<R:Runtime>(
+	child:&Window<R>,
+	parent:Option<&Window<R>>,
+) -> crate::Result<()> {
+	#[cfg(windows)]
+	{
+		use windows::Win32::UI::WindowsAndMessaging::GWLP_HWNDPARENT;
+
+		let owner = match parent {
+			Some(parent) => parent.hwnd()?.0 as isize,
+			None => 0,
+		};
+
+		// the owner of a window is stored in its GWLP_HWNDPARENT slot
+		#[cfg(target_pointer_width = "64")]
+		unsafe {
+			windows::Win32::UI::WindowsAndMessaging::SetWindowLongPtrW(
+				child.hwnd()?,
+				GWLP_HWNDPARENT,
+				owner,
+			)
+		};
+
+		#[cfg(target_pointer_width = "32")]
+		unsafe {
+			windows::Win32::UI::WindowsAndMessaging::SetWindowLongW(
+				child.hwnd()?,
+				GWLP_HWNDPARENT,
+				owner as i32,
+			)
+		};
+	}
+
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	{
+		use gtk::prelude::GtkWindowExt;
+
+		let parent = parent.map(|parent| parent.gtk_window()).transpose()?;
+
+		child.gtk_window()?.set_transient_for(parent.as_ref());
+	}
+
+	#[cfg(target_os = "macos")]
+	{
+		use objc2_app_kit::{NSWindow, NSWindowOrderingMode};
+
+		let child:&NSWindow = unsafe { &*child.ns_window()?.cast() };
+
+		if let Some(current) = unsafe { child.parentWindow() } {
+			unsafe { current.removeChildWindow(child) };
+		}
+
+		if let Some(parent) = parent {
+			let parent:&NSWindow = unsafe { &*parent.ns_window()?.cast() };
+
+			unsafe { parent.addChildWindow_ordered(child, NSWindowOrderingMode::NSWindowAbove) };
+		}
+	}
+
+	Ok(())
+}
+
+/// The child windows of `parent` with the given policy.
+fn children<R:Runtime>(parent:&Window<R>, policy:ParentDestroyPolicy) -> Vec<Window<R>> {
+	let labels = parent
+		.manager
+		.window
+		.parents
+		.lock()
+		.unwrap()
+		.iter()
+		.filter(|(_, (label, p))| label == parent.label() && *p == policy)
+		.map(|(child, _)| child.clone())
+		.collect::<Vec<_>>();
+
+	labels.iter().filter_map(|label| parent.manager.get_window(label)).collect()
+}
+
+/// Detaches the [`ParentDestroyPolicy::Orphan`] children of `parent` so they
+/// survive its destruction, returning them.
+///
+/// Must be called on the main thread.
+pub(crate) fn detach_orphans<R:Runtime>(parent:&Window<R>) -> Vec<Window<R>> {
+	let orphans = children(parent, ParentDestroyPolicy::Orphan);
+
+	for orphan in &orphans {
+		if let Err(e) = set_native_parent(orphan, None) {
+			log::error!("failed to detach window `{}` from its parent: {e}", orphan.label());
+		}
+	}
+
+	orphans
+}
+
+/// Detaches the orphan children of `parent` when its close is requested, and
+/// attaches them back if the close was prevented.
+pub(crate) fn on_close_requested<R:Runtime>(parent:&Window<R>) {
+	let orphans = detach_orphans(parent);
+
+	if orphans.is_empty() {
+		return;
+	}
+
+	let parent_ = parent.clone();
+
+	// runs after the event loop handled the close request, once the parent is
+	// either destroyed or kept open
+	let _ = parent.run_on_main_thread(move || {
+		if parent_.is_visible().is_err() {
+			return;
+		}
+
+		for orphan in &orphans {
+			let _ = set_native_parent(orphan, Some(&parent_));
+		}
+	});
+}
+
+/// Applies the [`ParentDestroyPolicy`] of the children of a destroyed window
+/// and forgets its relationships.
+pub(crate) fn on_destroyed<R:Runtime>(window:&Window<R>) {
+	let children = children(window, ParentDestroyPolicy::Close);
+
+	window
+		.manager
+		.window
+		.parents
+		.lock()
+		.unwrap()
+		.retain(|child, (parent, _)| child != window.label() && parent != window.label());
+
+	for child in children {
+		let _ = child.destroy();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+	};
+
+	use super::*;
+	use crate::{WebviewWindowBuilder, WindowEvent, test::mock_app};
+
+	#[test]
+	fn children_follow_their_destroy_policy() {
+		let app = mock_app();
+
+		let window = |label:&str| {
+			let window =
+				WebviewWindowBuilder::new(&app, label, Default::default()).build().unwrap();
+
+			let destroyed = Arc::new(AtomicBool::new(false));
+
+			let destroyed_ = destroyed.clone();
+
+			window.on_window_event(move |event| {
+				if matches!(event, WindowEvent::Destroyed) {
+					destroyed_.store(true, Ordering::Relaxed);
+				}
+			});
+
+			destroyed
+		};
+
+		let parent = WebviewWindowBuilder::new(&app, "parent", Default::default()).build().unwrap();
+
+		let platform = window("platform");
+
+		let close = window("close");
+
+		let orphan = window("orphan");
+
+		let mut parents = app.manager.window.parents.lock().unwrap();
+
+		for (child, policy) in [
+			("platform", ParentDestroyPolicy::default()),
+			("close", ParentDestroyPolicy::Close),
+			("orphan", ParentDestroyPolicy::Orphan),
+		] {
+			parents.insert(child.into(), ("parent".into(), policy));
+		}
+
+		drop(parents);
+
+		parent.destroy().unwrap();
+
+		assert!(close.load(Ordering::Relaxed));
+		assert!(!platform.load(Ordering::Relaxed));
+		assert!(!orphan.load(Ordering::Relaxed));
+
+		assert!(app.manager.window.parents.lock().unwrap().is_empty());
+	}
+}
@@ -11,7 +11,7 @@ use crate::{
 
 #[cfg(desktop)]
 mod desktop_commands {
-	use tauri_runtime::{ResizeDirection, window::WindowSizeConstraints};
+	use tauri_runtime::{Rect, ResizeDirection, window::WindowSizeConstraints};
 	use tauri_utils::TitleBarStyle;
 
 	use super::*;
@@ -23,6 +23,7 @@ mod desktop_commands {
 		PhysicalPosition,
 		PhysicalSize,
 		Position,
+		ResourceId,
 		Size,
 		Theme,
 		UserAttentionType,
@@ -31,7 +32,7 @@ mod desktop_commands {
 		command,
 		sealed::ManagerBase,
 		utils::config::{WindowConfig, WindowEffectsConfig},
-		window::{Color, ProgressBarState, WindowBuilder},
+		window::{Color, ProgressBarState, ProgressState, WindowBuilder},
 	};
 
 	#[command(root = "crate")]
@@ -112,6 +113,7 @@ mod desktop_commands {
 	getter!(available_monitors, Vec<Monitor>);
 	getter!(cursor_position, PhysicalPosition<f64>);
 	getter!(theme, Theme);
+	getter!(title_bar_rects, Vec<Rect>);
 
 	setter!(center);
 	setter!(request_user_attention, Option<UserAttentionType>);
@@ -147,15 +149,17 @@ mod desktop_commands {
 	setter!(set_cursor_icon, CursorIcon);
 	setter!(set_cursor_position, Position);
 	setter!(set_ignore_cursor_events, bool);
+	setter!(set_ignore_cursor_events_region, Option<Vec<Rect>>);
 	setter!(start_dragging);
 	setter!(start_resize_dragging, ResizeDirection);
 	setter!(set_progress_bar, ProgressBarState);
+	setter!(set_progress, ProgressState);
 	setter!(set_badge_count, Option<i64>);
-	#[cfg(target_os = "macos")]
 	setter!(set_badge_label, Option<String>);
 	setter!(set_visible_on_all_workspaces, bool);
 	setter!(set_title_bar_style, TitleBarStyle);
 	setter!(set_size_constraints, WindowSizeConstraints);
+	setter!(set_aspect_ratio, Option<f64>);
 	setter!(set_theme, Option<Theme>);
 	setter!(set_enabled, bool);
 
@@ -195,6 +199,25 @@ mod desktop_commands {
 			.map_err(Into::into)
 	}
 
+	#[command(root = "crate")]
+	pub async fn capture<R:Runtime>(
+		webview:Webview<R>,
+		window:Window<R>,
+		label:Option<String>,
+		region:Option<Rect>,
+	) -> crate::Result<ResourceId> {
+		let window = get_window(window, label)?;
+
+		let image = window
+			.manager()
+			.get_webview(window.label())
+			.ok_or(crate::Error::WebviewNotFound)?
+			.capture(region)
+			.await?;
+
+		Ok(webview.resources_table().add(image))
+	}
+
 	#[command(root = "crate")]
 	pub async fn toggle_maximize<R:Runtime>(
 		window:Window<R>,
@@ -227,6 +250,24 @@ mod desktop_commands {
 		Ok(())
 	}
 
+	#[command(root = "crate")]
+	pub async fn set_fullscreen_on_monitor<R:Runtime>(
+		window:Window<R>,
+		label:Option<String>,
+		name:Option<String>,
+		position:PhysicalPosition<i32>,
+	) -> crate::Result<()> {
+		let window = get_window(window, label)?;
+
+		let monitor = window
+			.available_monitors()?
+			.into_iter()
+			.find(|m| m.name() == name.as_ref() && *m.position() == position)
+			.ok_or_else(|| anyhow::anyhow!("monitor not found"))?;
+
+		window.set_fullscreen_on_monitor(&monitor)
+	}
+
 	#[command(root = "crate")]
 	pub async fn monitor_from_point<R:Runtime>(
 		window:Window<R>,
@@ -292,6 +333,8 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 						desktop_commands::available_monitors,
 						desktop_commands::cursor_position,
 						desktop_commands::theme,
+						desktop_commands::title_bar_rects,
+						desktop_commands::capture,
 						// setters
 						desktop_commands::center,
 						desktop_commands::request_user_attention,
@@ -318,8 +361,10 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 						desktop_commands::set_min_size,
 						desktop_commands::set_max_size,
 						desktop_commands::set_size_constraints,
+						desktop_commands::set_aspect_ratio,
 						desktop_commands::set_position,
 						desktop_commands::set_fullscreen,
+						desktop_commands::set_fullscreen_on_monitor,
 						desktop_commands::set_focus,
 						desktop_commands::set_enabled,
 						desktop_commands::set_skip_taskbar,
@@ -328,12 +373,13 @@ pub fn init<R:Runtime>() -> TauriPlugin<R> {
 						desktop_commands::set_cursor_icon,
 						desktop_commands::set_cursor_position,
 						desktop_commands::set_ignore_cursor_events,
+						desktop_commands::set_ignore_cursor_events_region,
 						desktop_commands::start_dragging,
 						desktop_commands::start_resize_dragging,
 						desktop_commands::set_badge_count,
-						#[cfg(target_os = "macos")]
 						desktop_commands::set_badge_label,
 						desktop_commands::set_progress_bar,
+						desktop_commands::set_progress,
 						#[cfg(target_os = "windows")]
 						desktop_commands::set_overlay_icon,
 						desktop_commands::set_icon,
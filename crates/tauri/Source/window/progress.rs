@@ -0,0 +1,320 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Taskbar and dock progress indicator, see [`Window::set_progress`].
+
+use serde::Deserialize;
+
+use crate::{
+	Runtime,
+	Window,
+	runtime::{ProgressBarState, ProgressBarStatus},
+};
+
+/// The state of the taskbar or dock progress indicator, see
+/// [`Window::set_progress`].
+///
+/// The progress values range from `0` to `100`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status", content = "progress")]
+pub enum ProgressState {
+	/// Hides the progress indicator.
+	None,
+	/// Shows an indeterminate progress indicator.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux**: Shown as an empty progress.
+	Indeterminate,
+	/// Shows the progress.
+	Normal(f64),
+	/// Shows the progress as paused.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux / macOS**: Shown as a normal progress.
+	Paused(f64),
+	/// Shows the progress as failed.
+	///
+	/// ## Platform-specific
+	///
+	/// - **Linux / macOS**: Shown as a normal progress.
+	Error(f64),
+}
+
+impl ProgressState {
+	fn status(self) -> ProgressBarStatus {
+		match self {
+			Self::None => ProgressBarStatus::None,
+			Self::Indeterminate => ProgressBarStatus::Indeterminate,
+			Self::Normal(_) => ProgressBarStatus::Normal,
+			Self::Paused(_) => ProgressBarStatus::Paused,
+			Self::Error(_) => ProgressBarStatus::Error,
+		}
+	}
+
+	fn progress(self) -> Option<u64> {
+		match self {
+			Self::None => Some(0),
+			Self::Indeterminate => None,
+			Self::Normal(progress) | Self::Paused(progress) | Self::Error(progress) => {
+				Some(progress.clamp(0.0, 100.0).round() as u64)
+			},
+		}
+	}
+}
+
+/// Sets the progress indicator of `window` to `state`.
+pub(crate) fn set_progress<R:Runtime>(window:&Window<R>, state:ProgressState) -> crate::Result<()> {
+	let desktop_filename = window.manager.desktop_file_name();
+
+	set_progress_bar(window, Some(state.status()), state.progress(), desktop_filename)
+}
+
+/// Sets the progress indicator status and progress of `window`, ignoring the
+/// request on desktop environments that cannot show it.
+pub(crate) fn set_progress_bar<R:Runtime>(
+	window:&Window<R>,
+	status:Option<ProgressBarStatus>,
+	progress:Option<u64>,
+	desktop_filename:String,
+) -> crate::Result<()> {
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	if !has_session_bus() {
+		log::debug!("no D-Bus session bus to show the taskbar progress, ignoring it");
+
+		return Ok(());
+	}
+
+	#[cfg(target_os = "macos")]
+	{
+		if let Some(running) = window.manager.window.progress_animation.lock().unwrap().take() {
+			running.store(false, std::sync::atomic::Ordering::Relaxed);
+		}
+
+		if matches!(status, Some(ProgressBarStatus::Indeterminate)) {
+			let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+
+			window.manager.window.progress_animation.lock().unwrap().replace(running.clone());
+
+			crate::async_runtime::spawn(run_indeterminate_animation(
+				window.clone(),
+				running,
+				desktop_filename,
+			));
+
+			return Ok(());
+		}
+	}
+
+	send(window, status, progress, desktop_filename)
+}
+
+fn send<R:Runtime>(
+	window:&Window<R>,
+	status:Option<ProgressBarStatus>,
+	progress:Option<u64>,
+	desktop_filename:String,
+) -> crate::Result<()> {
+	window
+		.window
+		.dispatcher
+		.set_progress_bar(ProgressBarState {
+			status,
+			progress,
+			desktop_filename:Some(desktop_filename),
+		})
+		.map_err(Into::into)
+}
+
+/// Whether the session bus of the libunity launcher API, which shows the
+/// progress, can be connected to.
+#[cfg(any(
+	target_os = "linux",
+	target_os = "dragonfly",
+	target_os = "freebsd",
+	target_os = "netbsd",
+	target_os = "openbsd"
+))]
+fn has_session_bus() -> bool {
+	use gtk::gio;
+
+	static CONNECTED:std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+
+	*CONNECTED
+		.get_or_init(|| gio::bus_get_sync(gio::BusType::Session, gio::Cancellable::NONE).is_ok())
+}
+
+/// The number of frames of a sweep of the indeterminate dock progress.
+#[cfg(target_os = "macos")]
+const INDETERMINATE_STEPS:u64 = 20;
+
+/// The progress shown by the frame `step` of the indeterminate dock progress,
+/// sweeping from `0` to `100` and back.
+#[cfg(target_os = "macos")]
+fn indeterminate_progress(step:u64) -> u64 {
+	let position = step % (INDETERMINATE_STEPS * 2);
+
+	let position =
+		if position < INDETERMINATE_STEPS { position } else { INDETERMINATE_STEPS * 2 - position };
+
+	position * 100 / INDETERMINATE_STEPS
+}
+
+/// The dock tile progress has no indeterminate style, so it is animated by
+/// sweeping the progress back and forth until `running` is cleared by another
+/// state or the window is closed.
+#[cfg(target_os = "macos")]
+async fn run_indeterminate_animation<R:Runtime>(
+	window:Window<R>,
+	running:std::sync::Arc<std::sync::atomic::AtomicBool>,
+	desktop_filename:String,
+) {
+	use std::sync::atomic::Ordering;
+
+	const FRAME_INTERVAL:std::time::Duration = std::time::Duration::from_millis(50);
+
+	let mut ticks = tokio::time::interval(FRAME_INTERVAL);
+
+	// frames are dropped rather than sent in a burst when the event loop is slow
+	ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+	for step in 0.. {
+		ticks.tick().await;
+
+		// the progress is app-wide but sent through the window, which may be closed
+		if !running.load(Ordering::Relaxed) || window.manager.get_window(window.label()).is_none() {
+			break;
+		}
+
+		let progress = indeterminate_progress(step);
+
+		let running = running.clone();
+
+		let window_ = window.clone();
+
+		let desktop_filename = desktop_filename.clone();
+
+		// checked on the main thread so a frame can't override a state set after
+		// the animation stopped
+		let _ = window.run_on_main_thread(move || {
+			if running.load(Ordering::Relaxed) {
+				let status = Some(ProgressBarStatus::Normal);
+
+				let _ = send(&window_, status, Some(progress), desktop_filename);
+			}
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::ProgressState;
+	use crate::{
+		WebviewWindowBuilder,
+		runtime::ProgressBarStatus,
+		test::{mock_app, mock_builder, mock_context, noop_assets},
+	};
+
+	#[test]
+	fn progress_states_map_to_the_runtime_state() {
+		assert!(matches!(ProgressState::None.status(), ProgressBarStatus::None));
+
+		assert!(matches!(ProgressState::Indeterminate.status(), ProgressBarStatus::Indeterminate));
+
+		assert!(matches!(ProgressState::Normal(0.0).status(), ProgressBarStatus::Normal));
+
+		assert!(matches!(ProgressState::Paused(0.0).status(), ProgressBarStatus::Paused));
+
+		assert!(matches!(ProgressState::Error(0.0).status(), ProgressBarStatus::Error));
+
+		// hiding the indicator resets its progress
+		assert_eq!(ProgressState::None.progress(), Some(0));
+
+		assert_eq!(ProgressState::Indeterminate.progress(), None);
+
+		assert_eq!(ProgressState::Normal(42.4).progress(), Some(42));
+
+		assert_eq!(ProgressState::Paused(150.0).progress(), Some(100));
+
+		assert_eq!(ProgressState::Error(-5.0).progress(), Some(0));
+	}
+
+	#[test]
+	fn progress_states_deserialize_from_the_js_state() {
+		let state = |value| serde_json::from_value::<ProgressState>(value).unwrap();
+
+		assert_eq!(state(serde_json::json!({ "status": "none" })), ProgressState::None);
+
+		assert_eq!(
+			state(serde_json::json!({ "status": "indeterminate" })),
+			ProgressState::Indeterminate
+		);
+
+		assert_eq!(
+			state(serde_json::json!({ "status": "paused", "progress": 50 })),
+			ProgressState::Paused(50.0)
+		);
+
+		assert!(serde_json::from_value::<ProgressState>(serde_json::json!({ "status": "normal" }))
+			.is_err());
+	}
+
+	#[test]
+	fn desktop_file_names_follow_the_linux_app_id() {
+		let app = mock_app();
+
+		assert_eq!(app.manager.desktop_file_name(), "app.tauri.test.desktop");
+
+		// the launcher APIs that predate the app id keep using the package name
+		assert_eq!(app.manager.package_desktop_file_name(), "test.desktop");
+
+		let app = mock_builder().linux_app_id("my-app").build(mock_context(noop_assets())).unwrap();
+
+		assert_eq!(app.manager.desktop_file_name(), "my-app.desktop");
+
+		assert_eq!(app.manager.package_desktop_file_name(), "my-app.desktop");
+	}
+
+	#[test]
+	fn every_progress_state_can_be_set() {
+		let app = mock_app();
+
+		let window = WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap();
+
+		for state in [
+			ProgressState::Indeterminate,
+			ProgressState::Normal(10.0),
+			ProgressState::Paused(20.0),
+			ProgressState::Error(30.0),
+			ProgressState::None,
+		] {
+			window.set_progress(state).unwrap();
+		}
+	}
+
+	#[cfg(target_os = "macos")]
+	#[test]
+	fn indeterminate_progress_sweeps_back_and_forth() {
+		use super::indeterminate_progress;
+
+		let frames = (0..=40).map(indeterminate_progress).collect::<Vec<_>>();
+
+		assert_eq!(frames[0], 0);
+
+		assert_eq!(frames[10], 50);
+
+		assert_eq!(frames[20], 100);
+
+		assert_eq!(frames[30], 50);
+
+		assert_eq!(frames[40], 0);
+	}
+}
@@ -0,0 +1,499 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Window state persistence, see [`WindowBuilder::restore_state`].
+//!
+//! The states are saved by window label in the app config directory.
+//!
+//! [`WindowBuilder::restore_state`]: crate::window::WindowBuilder::restore_state
+
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	sync::{Mutex, PoisonError},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri_runtime::window::WindowBuilder;
+
+use crate::{
+	AppHandle,
+	Manager,
+	Runtime,
+	Window,
+	runtime::dpi::{LogicalPosition, LogicalSize},
+	window::Monitor,
+};
+
+/// Named apart from the file of the window state plugin, which has another
+/// format.
+const STATE_FILENAME:&str = "window-states.json";
+
+/// Held while the state file is read, updated and written back, so windows
+/// saved at the same time don't drop each other's state.
+static STATE_FILE_LOCK:Mutex<()> = Mutex::new(());
+
+/// The minimum width of a restored window, in logical pixels, that must stay
+/// on a monitor for its position to be kept.
+const MIN_VISIBLE_WIDTH:f64 = 50.0;
+
+bitflags::bitflags! {
+	/// The window properties saved and restored, see
+	/// [`WindowBuilder::restore_state`](crate::window::WindowBuilder::restore_state).
+	#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+	pub struct StateFlags: u32 {
+		/// The inner size.
+		const SIZE = 1 << 0;
+		/// The outer position.
+		const POSITION = 1 << 1;
+		/// Whether the window is maximized.
+		const MAXIMIZED = 1 << 2;
+		/// Whether the window is visible.
+		const VISIBLE = 1 << 3;
+		/// Whether the window is decorated.
+		const DECORATIONS = 1 << 4;
+		/// Whether the window is fullscreen.
+		const FULLSCREEN = 1 << 5;
+	}
+}
+
+impl Default for StateFlags {
+	fn default() -> Self { Self::all() }
+}
+
+/// The saved state of a window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct WindowState {
+	size:Option<LogicalSize<f64>>,
+	position:Option<LogicalPosition<f64>>,
+	maximized:bool,
+	pub(crate) visible:bool,
+	decorated:bool,
+	fullscreen:bool,
+}
+
+impl Default for WindowState {
+	fn default() -> Self {
+		Self {
+			size:None,
+			position:None,
+			maximized:false,
+			visible:true,
+			decorated:true,
+			fullscreen:false,
+		}
+	}
+}
+
+impl WindowState {
+	/// Sets the `flags` properties of this state on `builder`, moving the window
+	/// onto one of the `monitors` if it is no longer on any of them.
+	pub(crate) fn apply<B:WindowBuilder>(
+		&self,
+		flags:StateFlags,
+		mut builder:B,
+		monitors:&[Monitor],
+		primary_monitor:Option<&Monitor>,
+	) -> B {
+		if flags.contains(StateFlags::SIZE) {
+			if let Some(size) = self.size {
+				builder = builder.inner_size(size.width, size.height);
+			}
+		}
+
+		if flags.contains(StateFlags::POSITION) {
+			let position = self.position.and_then(|position| {
+				clamp_to_monitors(
+					position,
+					self.size.unwrap_or_default(),
+					monitors,
+					primary_monitor,
+				)
+			});
+
+			if let Some(position) = position {
+				builder = builder.position(position.x, position.y);
+			}
+		}
+
+		if flags.contains(StateFlags::MAXIMIZED) {
+			builder = builder.maximized(self.maximized);
+		}
+
+		if flags.contains(StateFlags::VISIBLE) {
+			builder = builder.visible(self.visible);
+		}
+
+		if flags.contains(StateFlags::DECORATIONS) {
+			builder = builder.decorations(self.decorated);
+		}
+
+		if flags.contains(StateFlags::FULLSCREEN) {
+			builder = builder.fullscreen(self.fullscreen);
+		}
+
+		builder
+	}
+
+	/// Updates the `flags` properties of this state from the `observed` ones.
+	fn update(&mut self, observed:&Observed, flags:StateFlags) {
+		if flags.contains(StateFlags::SIZE) {
+			self.size = observed.size.or(self.size);
+		}
+
+		if flags.contains(StateFlags::POSITION) {
+			self.position = observed.position.or(self.position);
+		}
+
+		if flags.contains(StateFlags::MAXIMIZED) {
+			self.maximized = observed.maximized;
+		}
+
+		if flags.contains(StateFlags::VISIBLE) {
+			self.visible = observed.visible;
+		}
+
+		if flags.contains(StateFlags::DECORATIONS) {
+			self.decorated = observed.decorated;
+		}
+
+		if flags.contains(StateFlags::FULLSCREEN) {
+			self.fullscreen = observed.fullscreen;
+		}
+	}
+}
+
+/// The properties of a window, read before the state file is locked since
+/// the window getters may wait on the main thread.
+struct Observed {
+	/// `None` when the window is maximized, fullscreen or minimized, since its
+	/// size and position are not the ones to restore it to.
+	size:Option<LogicalSize<f64>>,
+	position:Option<LogicalPosition<f64>>,
+	maximized:bool,
+	visible:bool,
+	decorated:bool,
+	fullscreen:bool,
+}
+
+impl Observed {
+	fn read<R:Runtime>(window:&Window<R>) -> crate::Result<Self> {
+		let maximized = window.is_maximized()?;
+
+		let fullscreen = window.is_fullscreen()?;
+
+		let (size, position) = if maximized || fullscreen || window.is_minimized()? {
+			(None, None)
+		} else {
+			let scale_factor = window.scale_factor()?;
+
+			(
+				Some(window.inner_size()?.to_logical(scale_factor)),
+				Some(window.outer_position()?.to_logical(scale_factor)),
+			)
+		};
+
+		Ok(Self {
+			size,
+			position,
+			maximized,
+			visible:window.is_visible()?,
+			decorated:window.is_decorated()?,
+			fullscreen,
+		})
+	}
+}
+
+/// Keeps `position` if the window top edge is on one of the `monitors`,
+/// otherwise moves the window inside the primary monitor.
+fn clamp_to_monitors(
+	position:LogicalPosition<f64>,
+	size:LogicalSize<f64>,
+	monitors:&[Monitor],
+	primary_monitor:Option<&Monitor>,
+) -> Option<LogicalPosition<f64>> {
+	let bounds = |monitor:&Monitor| {
+		(
+			monitor.position().to_logical::<f64>(monitor.scale_factor()),
+			monitor.size().to_logical::<f64>(monitor.scale_factor()),
+		)
+	};
+
+	let is_visible = monitors.iter().any(|monitor| {
+		let (origin, extent) = bounds(monitor);
+
+		let visible_width = (position.x + size.width).min(origin.x + extent.width)
+			- position.x.max(origin.x);
+
+		position.y >= origin.y
+			&& position.y < origin.y + extent.height
+			&& visible_width >= MIN_VISIBLE_WIDTH.min(size.width.max(1.0))
+	});
+
+	if is_visible {
+		return Some(position);
+	}
+
+	let (origin, extent) = bounds(primary_monitor.or(monitors.first())?);
+
+	Some(LogicalPosition::new(
+		position.x.clamp(origin.x, origin.x + (extent.width - size.width).max(0.0)),
+		position.y.clamp(origin.y, origin.y + (extent.height - size.height).max(0.0)),
+	))
+}
+
+fn state_path<R:Runtime, M:Manager<R>>(manager:&M) -> crate::Result<PathBuf> {
+	Ok(manager.path().app_config_dir()?.join(STATE_FILENAME))
+}
+
+/// Reads the saved states, a missing or invalid file having none.
+fn load(path:&Path) -> HashMap<String, WindowState> {
+	std::fs::read(path)
+		.ok()
+		.and_then(|bytes| serde_json::from_slice(&bytes).ok())
+		.unwrap_or_default()
+}
+
+fn store(path:&Path, states:&HashMap<String, WindowState>) -> crate::Result<()> {
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir)?;
+	}
+
+	// written to a temporary file first so an interrupted save keeps the
+	// previous states
+	let temp_path = path.with_extension("json.tmp");
+
+	std::fs::write(&temp_path, serde_json::to_vec_pretty(states)?)?;
+
+	std::fs::rename(temp_path, path)?;
+
+	Ok(())
+}
+
+/// Reads the saved state of the `label` window.
+pub(crate) fn saved_state<R:Runtime, M:Manager<R>>(manager:&M, label:&str) -> Option<WindowState> {
+	load(&state_path(manager).ok()?).remove(label)
+}
+
+/// Saves the `observed` properties of the windows, by label.
+fn save_observed(
+	path:&Path,
+	observed:impl IntoIterator<Item = (String, Observed, StateFlags)>,
+) -> crate::Result<()> {
+	let _guard = STATE_FILE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+	let mut states = load(path);
+
+	for (label, observed, flags) in observed {
+		states.entry(label).or_default().update(&observed, flags);
+	}
+
+	store(path, &states)
+}
+
+/// Saves the state of `windows`, skipping the ones that can no longer be read.
+fn save<R:Runtime>(
+	app:&AppHandle<R>,
+	windows:impl IntoIterator<Item = (Window<R>, StateFlags)>,
+) -> crate::Result<()> {
+	let observed = windows
+		.into_iter()
+		.filter_map(|(window, flags)| {
+			match Observed::read(&window) {
+				Ok(observed) => Some((window.label().to_string(), observed, flags)),
+				Err(e) => {
+					log::debug!("failed to read the state of window `{}`: {e}", window.label());
+
+					None
+				},
+			}
+		})
+		.collect::<Vec<_>>();
+
+	save_observed(&state_path(app)?, observed)
+}
+
+/// Saves the `flags` properties of every window.
+pub(crate) fn save_all<R:Runtime>(app:&AppHandle<R>, flags:StateFlags) -> crate::Result<()> {
+	let windows = app.manager.window.windows_lock().values().cloned().collect::<Vec<_>>();
+
+	save(app, windows.into_iter().map(|window| (window, flags)))
+}
+
+/// Saves the state of the windows created with
+/// [`WindowBuilder::restore_state`](crate::window::WindowBuilder::restore_state),
+/// when the app is requested to exit and the windows still exist.
+pub(crate) fn save_restored<R:Runtime>(app:&AppHandle<R>) {
+	let windows = app
+		.manager
+		.window
+		.restored_states
+		.lock()
+		.unwrap()
+		.iter()
+		.filter_map(|(label, flags)| app.get_window(label).map(|window| (window, *flags)))
+		.collect::<Vec<_>>();
+
+	if windows.is_empty() {
+		return;
+	}
+
+	if let Err(e) = save(app, windows) {
+		log::error!("failed to save the window state: {e}");
+	}
+}
+
+/// Saves the state of `window` if it was created with
+/// [`WindowBuilder::restore_state`](crate::window::WindowBuilder::restore_state).
+pub(crate) fn on_close_requested<R:Runtime>(window:&Window<R>) {
+	let flags =
+		window.manager.window.restored_states.lock().unwrap().get(window.label()).copied();
+
+	if let Some(flags) = flags {
+		if let Err(e) = save(window.app_handle(), [(window.clone(), flags)]) {
+			log::error!("failed to save the state of window `{}`: {e}", window.label());
+		}
+	}
+}
+
+/// Stops saving the state of a destroyed window.
+pub(crate) fn on_destroyed<R:Runtime>(window:&Window<R>) {
+	window.manager.window.restored_states.lock().unwrap().remove(window.label());
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		WebviewWindowBuilder,
+		runtime::dpi::{PhysicalPosition, PhysicalSize, Position, Size},
+		test::mock_app,
+	};
+
+	fn monitor(x:i32, y:i32, width:u32, height:u32) -> Monitor {
+		Monitor {
+			name:None,
+			size:PhysicalSize::new(width, height),
+			position:PhysicalPosition::new(x, y),
+			scale_factor:1.0,
+		}
+	}
+
+	#[test]
+	fn positions_on_a_monitor_are_kept() {
+		let monitors = [monitor(0, 0, 1920, 1080), monitor(1920, 0, 1280, 1024)];
+
+		let size = LogicalSize::new(800.0, 600.0);
+
+		for position in [
+			LogicalPosition::new(100.0, 100.0),
+			LogicalPosition::new(2000.0, 50.0),
+			// partly off the left edge, with enough of it left to drag it back
+			LogicalPosition::new(-700.0, 0.0),
+		] {
+			assert_eq!(
+				clamp_to_monitors(position, size, &monitors, Some(&monitors[0])),
+				Some(position)
+			);
+		}
+	}
+
+	#[test]
+	fn positions_off_every_monitor_are_moved_onto_the_primary_one() {
+		// the monitor on the right was disconnected
+		let monitors = [monitor(0, 0, 1920, 1080)];
+
+		let size = LogicalSize::new(800.0, 600.0);
+
+		assert_eq!(
+			clamp_to_monitors(LogicalPosition::new(2500.0, 100.0), size, &monitors, None),
+			Some(LogicalPosition::new(1120.0, 100.0))
+		);
+
+		assert_eq!(
+			clamp_to_monitors(LogicalPosition::new(100.0, -300.0), size, &monitors, None),
+			Some(LogicalPosition::new(100.0, 0.0))
+		);
+
+		assert_eq!(clamp_to_monitors(LogicalPosition::new(100.0, 100.0), size, &[], None), None);
+	}
+
+	#[test]
+	fn states_are_updated_from_the_window() {
+		let app = mock_app();
+
+		let window =
+			WebviewWindowBuilder::new(&app, "main", Default::default()).build().unwrap().window;
+
+		window.set_size(Size::Physical(PhysicalSize::new(1024, 768))).unwrap();
+
+		window.set_position(Position::Physical(PhysicalPosition::new(10, 20))).unwrap();
+
+		let mut state = WindowState::default();
+
+		state.update(&Observed::read(&window).unwrap(), StateFlags::all());
+
+		assert_eq!(state.size, Some(LogicalSize::new(1024.0, 768.0)));
+
+		assert_eq!(state.position, Some(LogicalPosition::new(10.0, 20.0)));
+
+		assert!(!state.maximized);
+
+		// a maximized window keeps the size it is restored to
+		window.maximize().unwrap();
+
+		window.set_size(Size::Physical(PhysicalSize::new(1920, 1080))).unwrap();
+
+		state.update(&Observed::read(&window).unwrap(), StateFlags::all());
+
+		assert!(state.maximized);
+
+		assert_eq!(state.size, Some(LogicalSize::new(1024.0, 768.0)));
+
+		// only the flagged properties are updated
+		window.set_decorations(false).unwrap();
+
+		state.update(&Observed::read(&window).unwrap(), StateFlags::SIZE);
+
+		assert!(state.decorated);
+	}
+
+	#[test]
+	fn states_are_saved_by_window_label() {
+		let dir = std::env::temp_dir().join(format!("tauri-window-state-{}", std::process::id()));
+
+		let path = dir.join(STATE_FILENAME);
+
+		let observed = |width:f64| Observed {
+			size:Some(LogicalSize::new(width, 600.0)),
+			position:None,
+			maximized:false,
+			visible:true,
+			decorated:true,
+			fullscreen:false,
+		};
+
+		assert!(load(&path).is_empty());
+
+		save_observed(&path, [("main".to_string(), observed(800.0), StateFlags::all())]).unwrap();
+
+		save_observed(&path, [("settings".to_string(), observed(400.0), StateFlags::all())])
+			.unwrap();
+
+		let states = load(&path);
+
+		// saving a window keeps the state of the others
+		assert_eq!(states["main"].size, Some(LogicalSize::new(800.0, 600.0)));
+
+		assert_eq!(states["settings"].size, Some(LogicalSize::new(400.0, 600.0)));
+
+		// an invalid file has no states
+		std::fs::write(&path, "{").unwrap();
+
+		assert!(load(&path).is_empty());
+
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -0,0 +1,108 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Desktop widget windows, see [`WindowBuilder::desktop_widget`].
+//!
+//! [`WindowBuilder::desktop_widget`]: crate::window::WindowBuilder::desktop_widget
+
+use crate::{Runtime, Window};
+
+/// Applies the platform specific desktop widget configuration to `window`,
+/// on top of the always on bottom, skip taskbar and not focused flags already
+/// set on its builder.
+///
+/// Must be called on the main thread.
+pub(crate) fn make_desktop_widget<R:Runtime>(window:&Window<R>) -> crate::Result<()> {
+	#[cfg(windows)]
+	{
+		use windows::Win32::UI::WindowsAndMessaging::{
+			GWL_EXSTYLE,
+			HWND_BOTTOM,
+			SWP_NOACTIVATE,
+			SWP_NOMOVE,
+			SWP_NOSIZE,
+			SetWindowPos,
+			WS_EX_APPWINDOW,
+			WS_EX_NOACTIVATE,
+			WS_EX_TOOLWINDOW,
+		};
+
+		let hwnd = window.hwnd()?;
+
+		// WS_EX_NOACTIVATE keeps clicks from activating the window and bringing
+		// it to the front, WS_EX_TOOLWINDOW hides it from Alt-Tab
+		#[cfg(target_pointer_width = "64")]
+		unsafe {
+			use windows::Win32::UI::WindowsAndMessaging::{GetWindowLongPtrW, SetWindowLongPtrW};
+
+			let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+
+			SetWindowLongPtrW(
+				hwnd,
+				GWL_EXSTYLE,
+				(style | (WS_EX_NOACTIVATE.0 | WS_EX_TOOLWINDOW.0) as isize)
+					& !(WS_EX_APPWINDOW.0 as isize),
+			)
+		};
+
+		#[cfg(target_pointer_width = "32")]
+		unsafe {
+			use windows::Win32::UI::WindowsAndMessaging::{GetWindowLongW, SetWindowLongW};
+
+			let style = GetWindowLongW(hwnd, GWL_EXSTYLE);
+
+			SetWindowLongW(
+				hwnd,
+				GWL_EXSTYLE,
+				(style | (WS_EX_NOACTIVATE.0 | WS_EX_TOOLWINDOW.0) as i32)
+					& !(WS_EX_APPWINDOW.0 as i32),
+			)
+		};
+
+		unsafe {
+			SetWindowPos(hwnd, HWND_BOTTOM, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE)
+		}
+		.map_err(|e| crate::Error::Anyhow(e.into()))?;
+	}
+
+	#[cfg(any(
+		target_os = "linux",
+		target_os = "dragonfly",
+		target_os = "freebsd",
+		target_os = "netbsd",
+		target_os = "openbsd"
+	))]
+	{
+		use gtk::prelude::GtkWindowExt;
+
+		let gtk_window = window.gtk_window()?;
+
+		// the type hint is only read by the window manager when the window is
+		// mapped, the builder keeps the window hidden until then
+		gtk_window.set_type_hint(gtk::gdk::WindowTypeHint::Desktop);
+		gtk_window.set_skip_taskbar_hint(true);
+		gtk_window.set_skip_pager_hint(true);
+		gtk_window.set_keep_below(true);
+		gtk_window.set_focus_on_map(false);
+	}
+
+	#[cfg(target_os = "macos")]
+	{
+		use objc2_app_kit::{NSWindow, NSWindowCollectionBehavior};
+
+		let ns_window:&NSWindow = unsafe { &*window.ns_window()?.cast() };
+
+		// the always on bottom level keeps the window below normal windows, these
+		// keep it on the desktop of every space and out of the window cycling
+		unsafe {
+			ns_window.setCollectionBehavior(
+				NSWindowCollectionBehavior::CanJoinAllSpaces
+					| NSWindowCollectionBehavior::Stationary
+					| NSWindowCollectionBehavior::IgnoresCycle,
+			)
+		};
+	}
+
+	Ok(())
+}
@@ -30,7 +30,16 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 	),
 	(
 		"core:event",
-		&[("listen", true), ("unlisten", true), ("emit", true), ("emit_to", true)],
+		&[
+			("listen", true),
+			("unlisten", true),
+			("emit", true),
+			("emit_to", true),
+			("emit_to_many", true),
+			("listen_private", true),
+			("unlisten_private", true),
+			("emit_private", false),
+		],
 	),
 	(
 		"core:window",
@@ -61,6 +70,8 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("available_monitors", true),
 			("cursor_position", true),
 			("theme", true),
+			("title_bar_rects", true),
+			("capture", true),
 			// setters
 			("center", false),
 			("request_user_attention", false),
@@ -88,9 +99,11 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("set_size", false),
 			("set_min_size", false),
 			("set_size_constraints", false),
+			("set_aspect_ratio", false),
 			("set_max_size", false),
 			("set_position", false),
 			("set_fullscreen", false),
+			("set_fullscreen_on_monitor", false),
 			("set_focus", false),
 			("set_skip_taskbar", false),
 			("set_cursor_grab", false),
@@ -98,9 +111,11 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("set_cursor_icon", false),
 			("set_cursor_position", false),
 			("set_ignore_cursor_events", false),
+			("set_ignore_cursor_events_region", false),
 			("start_dragging", false),
 			("start_resize_dragging", false),
 			("set_progress_bar", false),
+			("set_progress", false),
 			("set_badge_count", false),
 			("set_overlay_icon", false),
 			("set_badge_label", false),
@@ -122,6 +137,9 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("get_all_webviews", true),
 			("webview_position", true),
 			("webview_size", true),
+			("webview_zoom", true),
+			("can_go_back", true),
+			("can_go_forward", true),
 			// setters
 			("webview_close", false),
 			("set_webview_size", false),
@@ -131,8 +149,17 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("webview_hide", false),
 			("webview_show", false),
 			("print", false),
+			("go_back", false),
+			("go_forward", false),
+			("reload", false),
+			("print_to_pdf", false),
+			("find_in_page", false),
+			("stop_find", false),
 			("reparent", false),
 			("clear_all_browsing_data", false),
+			("clear_browsing_data", false),
+			("cookies_for_url", false),
+			("delete_cookie", false),
 			("set_webview_background_color", false),
 			// internal
 			("internal_toggle_devtools", true),
@@ -148,6 +175,8 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("app_hide", false),
 			("default_window_icon", false),
 			("set_app_theme", false),
+			("set_app_badge_count", false),
+			("set_app_badge_label", false),
 		],
 	),
 	(
@@ -161,6 +190,8 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 		],
 	),
 	("core:resources", &[("close", true)]),
+	("core:permissions", &[("check", true), ("check_all", true)]),
+	("core:request-stream", &[("push_request_chunk", true), ("end_request_stream", true)]),
 	(
 		"core:menu",
 		&[
@@ -181,6 +212,7 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("is_enabled", true),
 			("set_enabled", true),
 			("set_accelerator", true),
+			("accelerator", true),
 			("set_as_windows_menu_for_nsapp", true),
 			("set_as_help_menu_for_nsapp", true),
 			("is_checked", true),
@@ -188,6 +220,8 @@ const PLUGINS:&[(&str, &[(&str, bool)])] = &[
 			("set_icon", true),
 		],
 	),
+	// disabled by default, opening URLs and paths must be explicitly allowed
+	("core:opener", &[("open_url", false), ("open_path", false)]),
 	(
 		"core:tray",
 		&[
@@ -243,6 +277,13 @@ fn main() {
 	alias("desktop", !mobile);
 	alias("mobile", mobile);
 
+	let linux_like = matches!(
+		target_os.as_str(),
+		"linux" | "dragonfly" | "freebsd" | "netbsd" | "openbsd"
+	);
+	alias("linux_dbus_menu", linux_like && has_feature("linux-dbus-menu"));
+	alias("single_instance", !mobile && has_feature("single-instance"));
+
 	let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
 
 	let checked_features_out_path = out_dir.join("checked_features");
@@ -41,17 +41,25 @@
         .then((response) => {
           const cb =
             response.headers.get('Tauri-Response') === 'ok' ? callback : error
+          const headers = Object.fromEntries(response.headers.entries())
           // we need to split here because on Android the content-type gets duplicated
           switch ((response.headers.get('content-type') || '').split(',')[0]) {
             case 'application/json':
-              return response.json().then((r) => [cb, r])
+              return response.json().then((r) => [cb, r, headers])
             case 'text/plain':
-              return response.text().then((r) => [cb, r])
+              return response.text().then((r) => [cb, r, headers])
             default:
-              return response.arrayBuffer().then((r) => [cb, r])
+              return response.arrayBuffer().then((r) => [cb, r, headers])
           }
         })
-        .then(([cb, data]) => {
+        .then(([cb, data, headers]) => {
+          if (
+            cb === callback &&
+            options &&
+            options.responseType === 'detailed'
+          ) {
+            data = { data, headers }
+          }
           if (window[`_${cb}`]) {
             window[`_${cb}`](data)
           } else {
@@ -0,0 +1,103 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+;(function () {
+  /**
+   * Sends `{ cmd, payload, headers }` to the backend and resolves to `{ ok, data, headers }`.
+   */
+  const send = __RAW_send_fn__
+
+  /**
+   * Whether the script runs outside of a webview, where nothing else defines `__TAURI_INTERNALS__`.
+   */
+  const standalone = __TEMPLATE_standalone__
+
+  function sendIpcMessage(message) {
+    const { cmd, callback, error, payload, options } = message
+
+    Promise.resolve(
+      send({ cmd, payload, headers: (options && options.headers) || {} })
+    )
+      .then(({ ok, data, headers }) => {
+        const cb = ok ? callback : error
+        if (ok && options && options.responseType === 'detailed') {
+          data = { data, headers }
+        }
+        if (window[`_${cb}`]) {
+          window[`_${cb}`](data)
+        }
+      })
+      .catch((e) => {
+        if (window[`_${error}`]) {
+          window[`_${error}`](String(e))
+        }
+      })
+  }
+
+  if (standalone) {
+    if (!window.__TAURI_INTERNALS__) {
+      Object.defineProperty(window, '__TAURI_INTERNALS__', {
+        value: {
+          plugins: {},
+          metadata: {
+            currentWindow: { label: __TEMPLATE_window_label__ },
+            currentWebview: { label: __TEMPLATE_webview_label__ }
+          }
+        }
+      })
+    }
+
+    Object.defineProperty(window.__TAURI_INTERNALS__, 'transformCallback', {
+      value: function transformCallback(callback, once) {
+        const identifier = window.crypto.getRandomValues(new Uint32Array(1))[0]
+        const prop = `_${identifier}`
+
+        Object.defineProperty(window, prop, {
+          value: (result) => {
+            if (once) {
+              Reflect.deleteProperty(window, prop)
+            }
+
+            return callback && callback(result)
+          },
+          writable: false,
+          configurable: true
+        })
+
+        return identifier
+      }
+    })
+
+    Object.defineProperty(window.__TAURI_INTERNALS__, 'invoke', {
+      value: function (cmd, payload = {}, options) {
+        return new Promise(function (resolve, reject) {
+          const callback = window.__TAURI_INTERNALS__.transformCallback(
+            function (r) {
+              resolve(r)
+              delete window[`_${error}`]
+            },
+            true
+          )
+          const error = window.__TAURI_INTERNALS__.transformCallback(
+            function (e) {
+              reject(e)
+              delete window[`_${callback}`]
+            },
+            true
+          )
+
+          sendIpcMessage({ cmd, callback, error, payload, options })
+        })
+      }
+    })
+
+    Object.defineProperty(window.__TAURI_INTERNALS__, 'ipc', {
+      value: sendIpcMessage
+    })
+  }
+
+  Object.defineProperty(window.__TAURI_INTERNALS__, 'postMessage', {
+    value: sendIpcMessage
+  })
+})()
@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[test]
+fn command_error() {
+	let cases = trybuild::TestCases::new();
+
+	cases.pass("tests/command_error/*.rs");
+
+	cases.compile_fail("tests/command_error/fail/*.rs");
+}
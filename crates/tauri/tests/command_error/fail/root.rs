@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[derive(Debug, thiserror::Error, tauri::CommandError)]
+#[command_error(root = "my-app")]
+enum Error {
+	#[error("cancelled")]
+	Cancelled,
+}
+
+fn main() {}
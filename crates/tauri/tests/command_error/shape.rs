@@ -0,0 +1,62 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The serialized shape of `CommandError` enums, which frontends rely on.
+
+mod reexports {
+	pub use tauri;
+}
+
+#[derive(Debug, thiserror::Error, tauri::CommandError)]
+#[command_error(rename_all = "snake_case")]
+enum Error {
+	#[error(transparent)]
+	Io(#[command_error(from, skip)] std::io::Error),
+	#[error("{path} was not found")]
+	NotFound { path:String },
+	#[error("cancelled")]
+	#[command_error(kind = "aborted")]
+	Cancelled,
+}
+
+/// The crate is only reachable through a nested path.
+#[derive(Debug, thiserror::Error, tauri::CommandError)]
+#[command_error(root = "crate::reexports::tauri")]
+enum NestedError {
+	#[error("denied {0}")]
+	Denied(String),
+}
+
+#[tauri::command]
+fn read(path:String) -> Result<String, Error> { Err(Error::NotFound { path }) }
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![read]
+}
+
+fn main() {
+	let io:Error = std::io::Error::other("disk full").into();
+
+	assert_eq!(
+		serde_json::to_value(io).unwrap(),
+		serde_json::json!({ "kind": "io", "message": "disk full", "data": null })
+	);
+
+	assert_eq!(
+		serde_json::to_value(Error::NotFound { path:"a.txt".into() }).unwrap(),
+		serde_json::json!({ "kind": "not_found", "message": "a.txt was not found", "data": { "path": "a.txt" } })
+	);
+
+	assert_eq!(
+		tauri::ipc::InvokeError::from(Error::Cancelled).0,
+		serde_json::json!({ "kind": "aborted", "message": "cancelled", "data": null })
+	);
+
+	assert_eq!(
+		serde_json::to_value(NestedError::Denied("write".into())).unwrap(),
+		serde_json::json!({ "kind": "Denied", "message": "denied write", "data": "write" })
+	);
+
+	let _ = handler::<tauri::Wry>;
+}
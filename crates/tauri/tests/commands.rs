@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[test]
+fn commands() {
+	let cases = trybuild::TestCases::new();
+
+	cases.pass("tests/commands/*.rs");
+
+	cases.compile_fail("tests/commands/fail/*.rs");
+}
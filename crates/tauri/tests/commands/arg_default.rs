@@ -0,0 +1,19 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Commands with default argument values.
+
+#[tauri::command]
+fn search(query:String, #[arg(default = 20)] limit:u32, #[arg(default)] offset:u32) -> String {
+	format!("{query}:{limit}:{offset}")
+}
+
+#[tauri::command]
+async fn tags(#[arg(default = vec!["all".to_string()])] tags:Vec<String>) -> Vec<String> { tags }
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![search, tags]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
@@ -0,0 +1,34 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Commands with custom extractors.
+
+use tauri::ipc::{CommandItem, FromCommandRequest};
+
+struct Origin(String);
+
+impl<'a, R:tauri::Runtime> FromCommandRequest<'a, R> for Origin {
+	type Error = String;
+
+	fn from_request(item:CommandItem<'a, R>) -> Result<Self, Self::Error> {
+		item.message
+			.headers()
+			.get("origin")
+			.and_then(|origin| origin.to_str().ok())
+			.map(|origin| Origin(origin.into()))
+			.ok_or_else(|| format!("{} needs an origin", item.name))
+	}
+}
+
+#[tauri::command]
+fn origin(#[arg(from_request)] origin:Origin) -> String { origin.0 }
+
+#[tauri::command]
+async fn echo(#[arg(from_request)] origin:Origin, message:String) -> String { format!("{}: {message}", origin.0) }
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![origin, echo]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
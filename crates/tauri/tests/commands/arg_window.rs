@@ -0,0 +1,28 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Commands receiving another window by label.
+
+#[tauri::command]
+fn reload_main<R:tauri::Runtime>(
+	caller:tauri::WebviewWindow<R>,
+	#[arg(window = "main")] main:tauri::WebviewWindow<R>,
+) -> tauri::Result<()> {
+	let _ = caller;
+
+	main.eval("location.reload()")
+}
+
+#[tauri::command]
+async fn focus_settings<R:tauri::Runtime>(
+	#[arg(window = "settings")] settings:tauri::WebviewWindow<R>,
+) -> tauri::Result<()> {
+	settings.set_focus()
+}
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![reload_main, focus_settings]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
@@ -0,0 +1,32 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Commands converting their error type with `#[command(error = "...")]`.
+
+/// An error type that does not implement `Serialize`.
+#[derive(Debug)]
+enum StorageError {
+	NotFound(String),
+}
+
+fn to_invoke_error(error:StorageError) -> tauri::ipc::InvokeError {
+	match error {
+		StorageError::NotFound(key) => tauri::ipc::InvokeError::from(format!("{key} not found")),
+	}
+}
+
+#[tauri::command(error = "crate::to_invoke_error")]
+fn read(key:String) -> Result<String, StorageError> { Err(StorageError::NotFound(key)) }
+
+#[tauri::command(error = "crate::to_invoke_error")]
+async fn read_async(key:String) -> Result<String, StorageError> { Err(StorageError::NotFound(key)) }
+
+#[tauri::command(error = "crate::to_invoke_error", raw_response)]
+async fn read_bytes(key:String) -> Result<Vec<u8>, StorageError> { Err(StorageError::NotFound(key)) }
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![read, read_async, read_bytes]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
@@ -0,0 +1,12 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[tauri::command]
+fn search(#[arg(default = "twenty")] limit:u32) -> u32 { limit }
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![search]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
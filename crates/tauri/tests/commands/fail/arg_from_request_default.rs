@@ -0,0 +1,8 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[tauri::command]
+fn user(#[arg(from_request, default)] user:String) -> String { user }
+
+fn main() {}
@@ -0,0 +1,11 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#[tauri::command(exec = "worker")]
+fn ping() {}
+
+#[tauri::command(exec = "main")]
+async fn pong() {}
+
+fn main() {}
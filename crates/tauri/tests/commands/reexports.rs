@@ -0,0 +1,33 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Commands listed in `generate_handler!` at the paths of their re-exports.
+
+mod features {
+	pub mod files {
+		#[tauri::command]
+		pub fn save() {}
+
+		#[tauri::command]
+		pub fn open() {}
+
+		#[tauri::command]
+		pub fn close() {}
+	}
+}
+
+mod api {
+	pub use crate::features::files::save;
+	pub use crate::features::files::open as open_file;
+}
+
+mod glob {
+	pub use crate::features::files::*;
+}
+
+fn handler<R:tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+	tauri::generate_handler![api::save, api::open_file, glob::close]
+}
+
+fn main() { let _ = handler::<tauri::Wry>; }
@@ -0,0 +1,27 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+use tauri::WebviewWindowBuilder;
+
+fn main() {
+	tauri::Builder::default()
+		.setup(|app| {
+			// the widget stays on the desktop, below the other windows, clicks on
+			// the counter still reach the webview without focusing the window
+			WebviewWindowBuilder::new(app, "widget", tauri::WebviewUrl::default())
+				.title("Tauri - Desktop Widget")
+				.inner_size(240.0, 160.0)
+				.position(40.0, 40.0)
+				.decorations(false)
+				.resizable(false)
+				.desktop_widget(true)
+				.build()?;
+
+			Ok(())
+		})
+		.run(tauri::generate_context!("../../examples/desktop-widget/tauri.conf.json"))
+		.expect("failed to run tauri application");
+}
@@ -121,6 +121,44 @@ async function setTheme(theme?: Theme | null): Promise<void> {
 	return invoke("plugin:app|set_app_theme", { theme });
 }
 
+/**
+ * Sets the app badge count. Pass `0`, `null` or `undefined` to remove the badge.
+ * Negative counts are rejected.
+ *
+ * @example
+ * ```typescript
+ * import { setBadgeCount } from '@tauri-apps/api/app';
+ * await setBadgeCount(3);
+ * ```
+ *
+ * #### Platform-specific
+ *
+ * - **Windows:** The count is drawn as the taskbar overlay icon of every window without a badge of its own, counts above 99 are shown as `99+`.
+ * - **Linux:** Shown by desktop environments implementing the `libunity` launcher API, e.g. KDE or GNOME with the Dash to Dock extension.
+ * - **Android:** Unsupported.
+ */
+async function setBadgeCount(count?: number | null): Promise<void> {
+	return invoke("plugin:app|set_app_badge_count", { count });
+}
+
+/**
+ * Sets the app badge label, for non-numeric badges. Pass `null` or `undefined` to remove the badge.
+ *
+ * @example
+ * ```typescript
+ * import { setBadgeLabel } from '@tauri-apps/api/app';
+ * await setBadgeLabel('!');
+ * ```
+ *
+ * #### Platform-specific
+ *
+ * - **Windows:** The label is drawn as the taskbar overlay icon of every window without a badge of its own, so it should be a few characters at most.
+ * - **Linux / iOS / Android:** Unsupported.
+ */
+async function setBadgeLabel(label?: string | null): Promise<void> {
+	return invoke("plugin:app|set_app_badge_label", { label });
+}
+
 export {
 	getName,
 	getVersion,
@@ -129,4 +167,6 @@ export {
 	hide,
 	defaultWindowIcon,
 	setTheme,
+	setBadgeCount,
+	setBadgeLabel,
 };
@@ -74,71 +74,85 @@ function transformCallback<T = unknown>(
 	return window.__TAURI_INTERNALS__.transformCallback(callback, once);
 }
 
+/**
+ * A channel receiving the messages sent by the Rust side, in order.
+ *
+ * The channel stays registered until {@link Channel.close} is called or the
+ * webview is closed, dropping every reference to it is not enough since its
+ * message callback keeps it alive. Call `close()` once the channel is no longer
+ * needed, otherwise the Rust side keeps waiting for the acknowledgements of the
+ * messages it sent.
+ *
+ * @since 2.0.0
+ */
 class Channel<T = unknown> {
-  id: number
-  // @ts-expect-error field used by the IPC serializer
-  private readonly __TAURI_CHANNEL_MARKER__ = true
-  #onmessage: (response: T) => void = () => {
-    // no-op
-  }
-  // the id is used as a mechanism to preserve message order
-  #nextMessageId = 0
-  #pendingMessages: T[] = []
-
-  constructor() {
-    this.id = transformCallback(
-      ({ message, id }: { message: T; id: number }) => {
-        // Process the message if we're at the right order
-        if (id == this.#nextMessageId) {
-          this.#onmessage(message)
-          this.#nextMessageId += 1
-
-          // process pending messages
-          while (this.#nextMessageId in this.#pendingMessages) {
-            const message = this.#pendingMessages[this.#nextMessageId]
-            this.#onmessage(message)
-            // eslint-disable-next-line @typescript-eslint/no-array-delete
-            delete this.#pendingMessages[this.#nextMessageId]
-            this.#nextMessageId += 1
-          }
-        }
-        // Queue the message if we're not
-        else {
-          // eslint-disable-next-line security/detect-object-injection
-          this.#pendingMessages[id] = message
-        }
-      }
-    )
-  }
-
-						for (const pendingId of pendingMessageIds.sort()) {
-							// if we have the next message, process it
-							if (parseInt(pendingId) === nextId) {
-								// eslint-disable-next-line security/detect-object-injection
-								const message =
-									this.#pendingMessages[pendingId];
-								// eslint-disable-next-line security/detect-object-injection
-								delete this.#pendingMessages[pendingId];
-
-								this.#onmessage(message);
-
-								// move the id counter to the next message to check
-								nextId += 1;
-							} else {
-								// we do not have the next message, let's wait
-								break;
-							}
-						}
-
-						this.#nextMessageId = nextId;
-					}
-				} else {
-					this.#pendingMessages[id.toString()] = message;
+	id: number;
+	// @ts-expect-error field used by the IPC serializer
+	private readonly __TAURI_CHANNEL_MARKER__ = true;
+	#onmessage: (response: T) => void = () => {
+		// no-op
+	};
+	// the id is used as a mechanism to preserve message order
+	#nextMessageId = 0;
+	#pendingMessages: Record<string, { message: T; ack: boolean }> = {};
+	#closed = false;
+
+	constructor() {
+		this.id = transformCallback(
+			({
+				message,
+				id,
+				ack,
+			}: {
+				message: T;
+				id: number;
+				ack?: boolean;
+			}) => {
+				if (this.#closed) {
+					return;
+				}
+
+				// Queue the message if we're not at the right order
+				if (id !== this.#nextMessageId) {
+					// eslint-disable-next-line security/detect-object-injection
+					this.#pendingMessages[id.toString()] = {
+						message,
+						ack: ack ?? false,
+					};
+
+					return;
+				}
+
+				this.#handle(message, id, ack ?? false);
+
+				// process pending messages
+				while (this.#nextMessageId.toString() in this.#pendingMessages) {
+					const pendingId = this.#nextMessageId.toString();
+					// eslint-disable-next-line security/detect-object-injection
+					const pending = this.#pendingMessages[pendingId];
+					// eslint-disable-next-line security/detect-object-injection
+					delete this.#pendingMessages[pendingId];
+
+					this.#handle(pending.message, this.#nextMessageId, pending.ack);
 				}
 			},
 		);
 	}
 
+	#handle(message: T, id: number, ack: boolean): void {
+		this.#onmessage(message);
+
+		this.#nextMessageId = id + 1;
+
+		// lets the Rust side know the message was handled, see `Channel::send_ack`
+		if (ack) {
+			invoke("plugin:__TAURI_CHANNEL__|ack", {
+				id: this.id,
+				index: id,
+			}).catch(console.error);
+		}
+	}
+
 	set onmessage(handler: (response: T) => void) {
 		this.#onmessage = handler;
 	}
@@ -147,6 +161,27 @@ class Channel<T = unknown> {
 		return this.#onmessage;
 	}
 
+	/**
+	 * Stops receiving messages.
+	 *
+	 * The Rust side can no longer send messages to the channel,
+	 * and the messages it is waiting to be acknowledged fail.
+	 * This is the only way to release a channel before its webview is closed.
+	 *
+	 * @since 2.3.0
+	 */
+	async close(): Promise<void> {
+		if (this.#closed) {
+			return;
+		}
+
+		this.#closed = true;
+
+		this.#pendingMessages = {};
+
+		await invoke("plugin:__TAURI_CHANNEL__|close", { id: this.id });
+	}
+
 	[SERIALIZE_TO_IPC_FN]() {
 		return `__CHANNEL__:${this.id}`;
 	}
@@ -225,18 +260,213 @@ async function requestPermissions<T>(plugin: string): Promise<T> {
 	return invoke(`plugin:${plugin}|request_permissions`);
 }
 
+/**
+ * Checks whether the given permission or command is granted for the current webview.
+ *
+ * The identifier can either be a permission identifier such as `fs:allow-write-file`
+ * or a command name such as `plugin:fs|write_file`.
+ * @example
+ * ```typescript
+ * import { hasPermission } from '@tauri-apps/api/core';
+ * const canWrite = await hasPermission('fs:allow-write-file');
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function hasPermission(identifier: string): Promise<boolean> {
+	return invoke("plugin:permissions|check", { identifier });
+}
+
+/**
+ * Checks whether each of the given permissions or commands is granted for the current webview.
+ * @example
+ * ```typescript
+ * import { hasPermissions } from '@tauri-apps/api/core';
+ * const granted = await hasPermissions(['fs:allow-write-file', 'plugin:fs|read_file']);
+ * if (!granted['fs:allow-write-file']) {
+ *   // hide the save button
+ * }
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function hasPermissions(
+	identifiers: string[],
+): Promise<Record<string, boolean>> {
+	return invoke("plugin:permissions|check_all", { identifiers });
+}
+
 /**
  * Command arguments.
  *
  * @since 1.0.0
  */
-type InvokeArgs = Record<string, unknown> | number[] | ArrayBuffer | Uint8Array;
+type InvokeArgs =
+	| Record<string, unknown>
+	| number[]
+	| ArrayBuffer
+	| Uint8Array
+	| ReadableStream<Uint8Array>
+	| Blob;
 
 /**
  * @since 2.0.0
  */
 interface InvokeOptions {
-	headers: Headers | Record<string, string>;
+	headers?: Headers | Record<string, string>;
+	/**
+	 * Set to `detailed` to resolve to a {@link DetailedResponse} that includes the response headers.
+	 *
+	 * Set to `stream` to resolve to a `ReadableStream` of the response body, read chunk by chunk
+	 * from a command returning a `tauri::ipc::Response::from_stream` response.
+	 * The stream errors if the command fails to produce the whole body or if the `signal` fires,
+	 * and the backend drops a body that is not read for 30 seconds.
+	 *
+	 * Set to `iterator` to resolve to an async iterator over the items of a command returning
+	 * a `tauri::ipc::CommandStream` or an `impl Stream`. The backend stops polling the stream
+	 * when the loop exits early or when the page navigates away.
+	 *
+	 * @since 2.3.0
+	 */
+	responseType?: "data" | "detailed" | "stream" | "iterator";
+	/**
+	 * Aborts the invoke when the signal fires.
+	 *
+	 * The promise rejects with an {@link InvokeError} whose `code` is `E_CANCELLED` and the
+	 * command is notified through its `tauri::ipc::CancellationToken` argument.
+	 *
+	 * @since 2.3.0
+	 */
+	signal?: AbortSignal;
+	/**
+	 * Aborts the invoke after the given number of milliseconds, see {@link InvokeOptions.signal}.
+	 *
+	 * @since 2.3.0
+	 */
+	timeout?: number;
+	/**
+	 * Receives the progress reports of the command, sent with its `tauri::ipc::Progress` argument.
+	 *
+	 * The reports are no longer received once the invoke settles.
+	 *
+	 * @since 2.3.0
+	 */
+	// eslint-disable-next-line @typescript-eslint/no-explicit-any
+	onProgress?: (progress: any) => void;
+}
+
+/**
+ * The serialized form of a structured `tauri::ipc::InvokeError`,
+ * marked with a `__TAURI_INVOKE_ERROR__` key.
+ *
+ * @since 2.3.0
+ */
+interface InvokeErrorEnvelope {
+	message: string;
+	code: string | null;
+	data: unknown;
+	sources: string[];
+}
+
+/**
+ * The rejection of an {@link invoke} call whose command returned a structured error,
+ * created with `tauri::ipc::InvokeError::with_code` for instance, or a string error,
+ * which only has a message.
+ *
+ * The invokes aborted with the `signal` or `timeout` options, or cancelled by the `timeout`
+ * of the command, reject with the `E_CANCELLED` code.
+ *
+ * @example
+ * ```typescript
+ * import { invoke, InvokeError } from '@tauri-apps/api/core';
+ * try {
+ *   await invoke('read_config');
+ * } catch (e) {
+ *   if (e instanceof InvokeError && e.code === 'E_NOT_FOUND') {
+ *     console.log(e.message, e.sources);
+ *   }
+ * }
+ * ```
+ *
+ * @since 2.3.0
+ */
+class InvokeError extends Error {
+	/** The machine-readable error code, if any. */
+	readonly code: string | null;
+	/** The data attached to the error, if any. */
+	readonly data: unknown;
+	/** The messages of the error sources, from the closest to the root cause. */
+	readonly sources: string[];
+
+	constructor(envelope: InvokeErrorEnvelope) {
+		super(envelope.message);
+
+		this.name = "InvokeError";
+		this.code = envelope.code;
+		this.data = envelope.data;
+		this.sources = envelope.sources;
+	}
+
+	/**
+	 * The error message, as the string errors were rejected with before.
+	 */
+	toString(): string {
+		return this.message;
+	}
+
+	/**
+	 * Whether the value is the serialized form of a structured error.
+	 */
+	static isEnvelope(value: unknown): value is InvokeErrorEnvelope {
+		return (
+			typeof value === "object" &&
+			value !== null &&
+			(value as Record<string, unknown>).__TAURI_INVOKE_ERROR__ === true
+		);
+	}
+
+	/**
+	 * Converts any {@link invoke} rejection to an {@link InvokeError},
+	 * a plain string error only having a message.
+	 */
+	static from(error: unknown): InvokeError {
+		if (error instanceof InvokeError) {
+			return error;
+		}
+
+		if (InvokeError.isEnvelope(error)) {
+			return new InvokeError(error);
+		}
+
+		return new InvokeError({
+			message: typeof error === "string" ? error : JSON.stringify(error),
+			code: null,
+			data: null,
+			sources: [],
+		});
+	}
+}
+
+/**
+ * Maps the structured and string rejections of an invoke to an {@link InvokeError},
+ * keeping the other values as they are serialized.
+ */
+function toInvokeError(error: unknown): unknown {
+	return typeof error === "string" || InvokeError.isEnvelope(error)
+		? InvokeError.from(error)
+		: error;
+}
+
+/**
+ * The result of an {@link invoke} call made with `responseType: 'detailed'`.
+ *
+ * @since 2.3.0
+ */
+interface DetailedResponse<T> {
+	/** The command response. */
+	data: T;
+	/** The headers attached to the response by the command. */
+	headers: Record<string, string>;
 }
 
 /**
@@ -247,6 +477,19 @@ interface InvokeOptions {
  * await invoke('login', { user: 'tauri', password: 'poiwe3h4r5ip3yrhtew9ty' });
  * ```
  *
+ * A `ReadableStream` or a `Blob` argument is sent in chunks to a command reading
+ * a `tauri::ipc::RequestBodyStream`, the next chunk is only read once the command has room for it.
+ *
+ * The items of a command returning a stream are iterated with the `responseType: 'iterator'` option:
+ * ```typescript
+ * for await (const entry of await invoke<string>('scan_dir', { path: '/tmp' }, { responseType: 'iterator' })) {
+ *   console.log(entry);
+ * }
+ * ```
+ *
+ * The promise rejects with an {@link InvokeError} when the command returns a structured or a
+ * string error, other errors are rejected as they are serialized.
+ *
  * @param cmd The command name.
  * @param args The optional arguments to pass to the command.
  * @param options The request options.
@@ -254,14 +497,446 @@ interface InvokeOptions {
  *
  * @since 1.0.0
  */
+async function invoke<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions & { responseType: "detailed" },
+): Promise<DetailedResponse<T>>;
+async function invoke(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions & { responseType: "stream" },
+): Promise<ReadableStream<Uint8Array>>;
+async function invoke<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions & { responseType: "iterator" },
+): Promise<AsyncIterableIterator<T>>;
+async function invoke<T>(
+	cmd: string,
+	args?: InvokeArgs,
+	options?: InvokeOptions,
+): Promise<T>;
 async function invoke<T>(
 	cmd: string,
 	args: InvokeArgs = {},
 	options?: InvokeOptions,
 ): Promise<T> {
+	try {
+		return await dispatchInvoke<T>(cmd, args, options);
+	} catch (error) {
+		throw toInvokeError(error);
+	}
+}
+
+function dispatchInvoke<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options?: InvokeOptions,
+): Promise<T> {
+	if (options?.onProgress) {
+		return progressInvoke(cmd, args, options);
+	}
+
+	if (options?.responseType === "stream") {
+		return streamResponse(cmd, args, options) as Promise<T>;
+	}
+
+	if (options?.responseType === "iterator") {
+		return iteratorResponse(cmd, args, options) as Promise<T>;
+	}
+
+	if (
+		args instanceof Blob ||
+		(typeof ReadableStream !== "undefined" && args instanceof ReadableStream)
+	) {
+		return streamingInvoke(cmd, args, options);
+	}
+
+	if (options?.signal || options?.timeout !== undefined) {
+		return cancellableInvoke(cmd, args, options);
+	}
+
 	return window.__TAURI_INTERNALS__.invoke(cmd, args, options);
 }
 
+/**
+ * Sends the invoke along with the ID of a channel receiving its progress reports,
+ * closed once the invoke settles.
+ */
+async function progressInvoke<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions,
+): Promise<T> {
+	const { onProgress, ...rest } = options;
+
+	const channel = new Channel<unknown>();
+
+	if (onProgress) {
+		channel.onmessage = onProgress;
+	}
+
+	const headers = new Headers(rest.headers);
+
+	headers.set("Tauri-Progress-Channel", channel.id.toString());
+
+	try {
+		return await invoke<T>(cmd, args, {
+			...rest,
+			headers: Object.fromEntries(headers.entries()),
+		});
+	} finally {
+		void channel.close().catch(() => {
+			// the webview navigated away
+		});
+	}
+}
+
+/**
+ * Sends the invoke and pulls the response body stream chunk by chunk as it is read.
+ */
+async function streamResponse(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions,
+): Promise<ReadableStream<Uint8Array>> {
+	const requestHeaders = new Headers(options.headers);
+
+	// the backend drops the body stream of the invokes that don't accept it
+	requestHeaders.set("Tauri-Accept-Response-Stream", "true");
+
+	const { data, headers } = await invoke<unknown>(cmd, args, {
+		...options,
+		headers: Object.fromEntries(requestHeaders.entries()),
+		responseType: "detailed",
+	});
+
+	const streamId = headers["tauri-response-stream"];
+
+	// the command returned a regular response, streamed as a single chunk
+	if (streamId === undefined) {
+		return new ReadableStream<Uint8Array>({
+			start(controller) {
+				controller.enqueue(
+					data instanceof ArrayBuffer
+						? new Uint8Array(data)
+						: new TextEncoder().encode(
+								typeof data === "string" ? data : JSON.stringify(data),
+							),
+				);
+
+				controller.close();
+			},
+		});
+	}
+
+	const id = Number(streamId);
+
+	const drop = () =>
+		invoke("plugin:__TAURI_CHANNEL__|cancel_response_stream", {
+			id,
+		}).catch(() => {
+			// the stream already ended
+		});
+
+	let abort: (() => void) | undefined;
+
+	return new ReadableStream<Uint8Array>({
+		start(controller) {
+			// the signal keeps cancelling the invoke while its body is read
+			abort = () => {
+				controller.error(
+					new InvokeError({
+						message: "the invoke was cancelled",
+						code: "E_CANCELLED",
+						data: null,
+						sources: [],
+					}),
+				);
+
+				void drop();
+			};
+
+			if (options.signal?.aborted) {
+				abort();
+			} else {
+				options.signal?.addEventListener("abort", abort, { once: true });
+			}
+		},
+		// a rejection errors the stream, so a partial body is never mistaken for a complete one
+		async pull(controller) {
+			try {
+				const chunk = new Uint8Array(
+					await invoke<ArrayBuffer | number[]>(
+						"plugin:__TAURI_CHANNEL__|pull_response_chunk",
+						{ id },
+					),
+				);
+
+				if (chunk.byteLength === 0) {
+					options.signal?.removeEventListener("abort", abort!);
+
+					controller.close();
+				} else {
+					controller.enqueue(chunk);
+				}
+			} catch (e) {
+				options.signal?.removeEventListener("abort", abort!);
+
+				throw e;
+			}
+		},
+		async cancel() {
+			options.signal?.removeEventListener("abort", abort!);
+
+			await drop();
+		},
+	});
+}
+
+type StreamEvent<T> =
+	| { event: "item"; data: T }
+	| { event: "error"; data: unknown }
+	| { event: "end" };
+
+/**
+ * Sends the invoke along with the ID of a channel receiving the items of the stream
+ * returned by the command, and iterates over them once the command resolves.
+ */
+async function iteratorResponse<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions,
+): Promise<AsyncIterableIterator<T>> {
+	const { responseType: _, ...rest } = options;
+
+	const channel = new Channel<StreamEvent<T>>();
+
+	const queue: StreamEvent<T>[] = [];
+
+	let wake: (() => void) | null = null;
+
+	channel.onmessage = (event) => {
+		queue.push(event);
+
+		wake?.();
+	};
+
+	const headers = new Headers(rest.headers);
+
+	headers.set("Tauri-Stream-Channel", channel.id.toString());
+
+	try {
+		await invoke(cmd, args, {
+			...rest,
+			headers: Object.fromEntries(headers.entries()),
+		});
+	} catch (error) {
+		void channel.close().catch(() => {
+			// the webview navigated away
+		});
+
+		throw error;
+	}
+
+	return (async function* () {
+		let done = false;
+
+		try {
+			while (true) {
+				const event = queue.shift();
+
+				if (!event) {
+					await new Promise<void>((resolve) => {
+						wake = resolve;
+					});
+
+					wake = null;
+
+					continue;
+				}
+
+				if (event.event === "item") {
+					yield event.data;
+				} else {
+					done = true;
+
+					if (event.event === "error") {
+						throw event.data;
+					}
+
+					return;
+				}
+			}
+		} finally {
+			if (!done) {
+				await invoke("plugin:__TAURI_CHANNEL__|cancel_stream", {
+					id: channel.id,
+				}).catch(() => {
+					// the stream already finished
+				});
+			}
+		}
+	})();
+}
+
+/**
+ * Sends the invoke along with an ID the backend cancels it with when it is aborted.
+ */
+function cancellableInvoke<T>(
+	cmd: string,
+	args: InvokeArgs,
+	options: InvokeOptions,
+): Promise<T> {
+	const { signal, timeout, ...rest } = options;
+
+	const id = window.crypto.getRandomValues(new Uint32Array(1))[0];
+
+	const headers = new Headers(rest.headers);
+
+	headers.set("Tauri-Invoke-Id", id.toString());
+
+	return new Promise<T>((resolve, reject) => {
+		let timer: ReturnType<typeof setTimeout> | undefined;
+
+		const cleanup = () => {
+			clearTimeout(timer);
+
+			signal?.removeEventListener("abort", cancel);
+		};
+
+		const cancel = () => {
+			cleanup();
+
+			reject(
+				new InvokeError({
+					message: "the invoke was cancelled",
+					code: "E_CANCELLED",
+					data: null,
+					sources: [],
+				}),
+			);
+
+			void window.__TAURI_INTERNALS__
+				.invoke("plugin:__TAURI_CHANNEL__|cancel_invoke", { id })
+				.catch(() => {
+					// the invoke already settled
+				});
+		};
+
+		if (signal?.aborted) {
+			cancel();
+
+			return;
+		}
+
+		signal?.addEventListener("abort", cancel, { once: true });
+
+		if (timeout !== undefined) {
+			timer = setTimeout(cancel, timeout);
+		}
+
+		window.__TAURI_INTERNALS__
+			.invoke<T>(cmd, args, {
+				...rest,
+				headers: Object.fromEntries(headers.entries()),
+			})
+			.then(
+				(response) => {
+					cleanup();
+
+					resolve(response);
+				},
+				(error) => {
+					cleanup();
+
+					reject(error);
+				},
+			);
+	});
+}
+
+/**
+ * Sends the invoke with an empty body and pushes the stream to the backend chunk by chunk.
+ */
+async function streamingInvoke<T>(
+	cmd: string,
+	body: ReadableStream<Uint8Array> | Blob,
+	options?: InvokeOptions,
+): Promise<T> {
+	const id = window.crypto.getRandomValues(new Uint32Array(1))[0];
+
+	const headers = new Headers(options?.headers);
+
+	headers.set("Tauri-Request-Stream", id.toString());
+
+	const reader = (body instanceof Blob ? body.stream() : body).getReader();
+
+	let finished = false;
+
+	const end = async (error: string | null) => {
+		if (finished) {
+			return;
+		}
+
+		finished = true;
+
+		void reader.cancel().catch(() => {
+			// the stream already errored
+		});
+
+		await window.__TAURI_INTERNALS__
+			.invoke("plugin:request-stream|end_request_stream", { id, error })
+			.catch(() => {
+				// the command already finished
+			});
+	};
+
+	const pump = async () => {
+		try {
+			while (!finished) {
+				const { done, value } = await reader.read();
+
+				if (done) {
+					await end(null);
+
+					return;
+				}
+
+				// resolves once the command has room for the chunk
+				await window.__TAURI_INTERNALS__.invoke(
+					"plugin:request-stream|push_request_chunk",
+					value,
+					{ headers: { "Tauri-Request-Stream": id.toString() } },
+				);
+			}
+		} catch (e) {
+			await end(String(e));
+		}
+	};
+
+	const abort = () => void end("the invoke was cancelled");
+
+	options?.signal?.addEventListener("abort", abort, { once: true });
+
+	// the invoke opens the stream on the backend, so it's sent before the first chunk
+	const result = invoke<T>(cmd, new Uint8Array(), {
+		...options,
+		headers: Object.fromEntries(headers.entries()),
+	});
+
+	void pump();
+
+	try {
+		return await result;
+	} finally {
+		options?.signal?.removeEventListener("abort", abort);
+
+		void end("the invoke settled before the request body was sent");
+	}
+}
+
 /**
  * Convert a device file path to an URL that can be loaded by the webview.
  * Note that `asset:` and `http://asset.localhost` must be added to [`app.security.csp`](https://v2.tauri.app/reference/config/#csp-1) in `tauri.conf.json`.
@@ -344,7 +1019,12 @@ function isTauri(): boolean {
 	return "isTauri" in window && !!window.isTauri;
 }
 
-export type { InvokeArgs, InvokeOptions };
+export type {
+	InvokeArgs,
+	InvokeOptions,
+	DetailedResponse,
+	InvokeErrorEnvelope,
+};
 
 export {
 	transformCallback,
@@ -354,7 +1034,10 @@ export {
 	PermissionState,
 	checkPermissions,
 	requestPermissions,
+	hasPermission,
+	hasPermissions,
 	invoke,
+	InvokeError,
 	convertFileSrc,
 	isTauri,
 };
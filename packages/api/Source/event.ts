@@ -24,11 +24,13 @@ interface Event<T> {
 	event: EventName;
 	/** Event identifier used to unlisten */
 	id: number;
-	/** Event payload */
+	/**
+	 * Event payload, a `Uint8Array` if the event was emitted with `Emitter::emit_bytes` on the Rust side.
+	 */
 	payload: T;
 }
 
-type EventCallback<T> = (event: Event<T>) => void;
+type EventCallback<T> = (event: Event<T>) => void | Promise<void>;
 
 type UnlistenFn = () => void;
 
@@ -41,6 +43,21 @@ interface Options {
 	 * If a string is provided, {@link EventTarget.AnyLabel} is used.
 	 */
 	target?: string | EventTarget;
+	/**
+	 * Only receive the events whose payload matches all the entries of the filter,
+	 * mapping [JSON pointers](https://datatracker.ietf.org/doc/html/rfc6901) to the values they must point to.
+	 *
+	 * The events are filtered before they are sent to the webview.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { listen } from '@tauri-apps/api/event';
+	 * await listen('download://progress', (event) => {}, { filter: { '/id': 1 } });
+	 * ```
+	 *
+	 * @since 2.3.0
+	 */
+	filter?: Record<string, unknown>;
 }
 
 /**
@@ -55,12 +72,20 @@ enum TauriEvent {
 	WINDOW_BLUR = "tauri://blur",
 	WINDOW_SCALE_FACTOR_CHANGED = "tauri://scale-change",
 	WINDOW_THEME_CHANGED = "tauri://theme-changed",
+	WINDOW_TITLE_BAR_RECTS_CHANGED = "tauri://title-bar-rects-changed",
+	WINDOW_FULLSCREEN_MONITOR_DISCONNECTED = "tauri://fullscreen-monitor-disconnected",
 	WINDOW_CREATED = "tauri://window-created",
 	WEBVIEW_CREATED = "tauri://webview-created",
 	DRAG_ENTER = "tauri://drag-enter",
 	DRAG_OVER = "tauri://drag-over",
 	DRAG_DROP = "tauri://drag-drop",
 	DRAG_LEAVE = "tauri://drag-leave",
+	PAGE_LOAD = "tauri://page-load",
+	WEBVIEW_ZOOM_CHANGED = "tauri://zoom-changed",
+	WEBVIEW_FIND_RESULT_CHANGED = "tauri://find-result-changed",
+	WEBVIEW_HISTORY_CHANGED = "tauri://history-changed",
+	WEBVIEW_DEVTOOLS_OPENED = "tauri://devtools-opened",
+	WEBVIEW_DEVTOOLS_CLOSED = "tauri://devtools-closed",
 }
 
 /**
@@ -92,7 +117,18 @@ async function _unlisten(event: string, eventId: number): Promise<void> {
  * unlisten();
  * ```
  *
- * @param event Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`.
+ * Listening to a `prefix:*` pattern receives every event namespaced under the prefix,
+ * `event.event` being the full name of the emitted event:
+ * ```typescript
+ * import { listen } from '@tauri-apps/api/event';
+ * // receives `sync:error` and `sync:file:progress`
+ * const unlisten = await listen('sync:*', (event) => {
+ *   console.log(`${event.event}: ${event.payload}`);
+ * });
+ * ```
+ *
+ * @param event Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`,
+ * optionally followed by a trailing `:*` wildcard.
  * @param handler Event handler callback.
  * @param options Event listening options.
  * @returns A promise resolving to a function to unlisten to the event.
@@ -114,6 +150,7 @@ async function listen<T>(
 		event,
 		target,
 		handler: transformCallback(handler),
+		filter: options?.filter ?? null,
 	}).then((eventId) => {
 		return async () => _unlisten(event, eventId);
 	});
@@ -156,7 +193,8 @@ async function once<T>(
 			// eslint-disable-next-line @typescript-eslint/no-floating-promises
 			_unlisten(event, eventData.id);
 
-			handler(eventData);
+			// an `emit_and_wait` call waits for the returned promise
+			return handler(eventData);
 		},
 		options,
 	);
@@ -184,32 +222,130 @@ async function emit(event: string, payload?: unknown): Promise<void> {
 }
 
 /**
- * Emits an event to all {@link EventTarget|targets} matching the given target.
+ * Emits an event to all {@link EventTarget|targets} matching the given target,
+ * or any of the given targets if an array is provided.
  *
  * @example
  * ```typescript
  * import { emitTo } from '@tauri-apps/api/event';
  * await emitTo('main', 'frontend-loaded', { loggedIn: true, token: 'authToken' });
+ * await emitTo(['main', 'settings'], 'theme-changed', 'dark');
  * ```
  *
- * @param target Label of the target Window/Webview/WebviewWindow or raw {@link EventTarget} object.
+ * @param target Label of the target Window/Webview/WebviewWindow or raw {@link EventTarget} object,
+ * or an array of them since 2.3.0. The listeners matching several targets get the event once.
  * @param event Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`.
  * @param payload Event payload.
+ * @returns For an array of targets, the number of targets that have a listener for the event.
+ * The listeners of any target get the event too but aren't counted.
  *
  * @since 2.0.0
  */
-async function emitTo(
+function emitTo(
 	target: EventTarget | string,
 	event: string,
 	payload?: unknown,
-): Promise<void> {
-	const eventTarget: EventTarget =
+): Promise<void>;
+function emitTo(
+	target: Array<EventTarget | string>,
+	event: string,
+	payload?: unknown,
+): Promise<number>;
+async function emitTo(
+	target: EventTarget | string | Array<EventTarget | string>,
+	event: string,
+	payload?: unknown,
+): Promise<void | number> {
+	const toEventTarget = (target: EventTarget | string): EventTarget =>
 		typeof target === "string"
 			? { kind: "AnyLabel", label: target }
 			: target;
 
+	if (Array.isArray(target)) {
+		return invoke<number>("plugin:event|emit_to_many", {
+			targets: target.map(toEventTarget),
+			event,
+			payload,
+		});
+	}
+
 	await invoke("plugin:event|emit_to", {
-		target: eventTarget,
+		target: toEventTarget(target),
+		event,
+		payload,
+	});
+}
+
+/**
+ * Listens to the private events the webview with the given label emits to the current webview
+ * with {@link emitPrivate}.
+ *
+ * Private events are only delivered to the listeners of the receiving webview, which is always
+ * the current webview as verified by the runtime, and never reach the {@link listen} listeners.
+ *
+ * @example
+ * ```typescript
+ * import { listenPrivate } from '@tauri-apps/api/event';
+ * const unlisten = await listenPrivate<string>('auth', 'token', (event) => {
+ *   console.log(`received the token ${event.payload}`);
+ * });
+ *
+ * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+ * unlisten();
+ * ```
+ *
+ * @param source Label of the webview emitting the events.
+ * @param event Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`.
+ * @param handler Event handler callback.
+ * @returns A promise resolving to a function to unlisten to the event.
+ *
+ * @since 2.3.0
+ */
+async function listenPrivate<T>(
+	source: string,
+	event: EventName,
+	handler: EventCallback<T>,
+): Promise<UnlistenFn> {
+	return invoke<number>("plugin:event|listen_private", {
+		source,
+		event,
+		handler: transformCallback(handler),
+	}).then((eventId) => {
+		return async () => {
+			await invoke("plugin:event|unlisten_private", {
+				event,
+				eventId,
+			});
+		};
+	});
+}
+
+/**
+ * Emits an event to the private listeners the webview with the given label registered for the
+ * current webview with {@link listenPrivate}.
+ *
+ * Requires the `core:event:allow-emit-private` permission, which is not part of the default
+ * permissions.
+ *
+ * @example
+ * ```typescript
+ * import { emitPrivate } from '@tauri-apps/api/event';
+ * await emitPrivate('main', 'token', 'authToken');
+ * ```
+ *
+ * @param target Label of the receiving webview.
+ * @param event Event name. Must include only alphanumeric characters, `-`, `/`, `:` and `_`.
+ * @param payload Event payload.
+ *
+ * @since 2.3.0
+ */
+async function emitPrivate(
+	target: string,
+	event: string,
+	payload?: unknown,
+): Promise<void> {
+	await invoke("plugin:event|emit_private", {
+		target,
 		event,
 		payload,
 	});
@@ -224,4 +360,12 @@ export type {
 	Options,
 };
 
-export { listen, once, emit, emitTo, TauriEvent };
+export {
+	listen,
+	once,
+	emit,
+	emitTo,
+	listenPrivate,
+	emitPrivate,
+	TauriEvent,
+};
@@ -30,6 +30,7 @@ import * as event from "./event";
 import * as image from "./image";
 import * as menu from "./menu";
 import * as mocks from "./mocks";
+import * as opener from "./opener";
 import * as path from "./path";
 import * as tray from "./tray";
 import * as webview from "./webview";
@@ -44,6 +45,7 @@ export {
 	image,
 	menu,
 	mocks,
+	opener,
 	path,
 	tray,
 	webview,
@@ -70,6 +70,14 @@ export class CheckMenuItem extends MenuItemBase {
 		});
 	}
 
+	/** Returns the accelerator of this check menu item, if it has one. */
+	async accelerator(): Promise<string | null> {
+		return invoke("plugin:menu|accelerator", {
+			rid: this.rid,
+			kind: this.kind,
+		});
+	}
+
 	/** Returns whether this check menu item is checked or not. */
 	async isChecked(): Promise<boolean> {
 		return invoke("plugin:menu|is_checked", { rid: this.rid });
@@ -201,6 +201,14 @@ export class IconMenuItem extends MenuItemBase {
 		});
 	}
 
+	/** Returns the accelerator of this icon menu item, if it has one. */
+	async accelerator(): Promise<string | null> {
+		return invoke("plugin:menu|accelerator", {
+			rid: this.rid,
+			kind: this.kind,
+		});
+	}
+
 	/** Sets an icon for this icon menu item */
 	async setIcon(
 		icon:
@@ -72,4 +72,12 @@ export class MenuItem extends MenuItemBase {
 			accelerator,
 		});
 	}
+
+	/** Returns the accelerator of this menu item, if it has one. */
+	async accelerator(): Promise<string | null> {
+		return invoke("plugin:menu|accelerator", {
+			rid: this.rid,
+			kind: this.kind,
+		});
+	}
 }
@@ -102,6 +102,8 @@ export interface PredefinedMenuItemOptions {
 		| "CloseWindow"
 		| "Quit"
 		| "Services"
+		| "Zoom"
+		| "BringAllToFront"
 		| {
 				About: AboutMetadata | null;
 		  };
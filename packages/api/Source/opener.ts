@@ -0,0 +1,55 @@
+// Copyright 2019-2024 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+import { invoke } from "./core";
+
+/**
+ * Open URLs and paths with the default system handler.
+ *
+ * Requires the `opener` Cargo feature of the `tauri` crate and the `core:opener:allow-open-url`
+ * or `core:opener:allow-open-path` permissions, optionally scoped with `{ "url": "<pattern>" }`
+ * and `{ "path": "<glob>" }` entries.
+ *
+ * @module
+ */
+
+/**
+ * Opens the given URL with the default handler for its scheme.
+ *
+ * Only `http`, `https` and `mailto` URLs are allowed unless another scheme
+ * matches an allowed `url` scope entry.
+ *
+ * @example
+ * ```typescript
+ * import { openUrl } from '@tauri-apps/api/opener';
+ * await openUrl('https://tauri.app');
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function openUrl(url: string | URL): Promise<void> {
+	return invoke("plugin:opener|open_url", {
+		url: url.toString(),
+	});
+}
+
+/**
+ * Opens the given absolute path with the default application for its type.
+ *
+ * The path must match an allowed `path` scope entry.
+ *
+ * @example
+ * ```typescript
+ * import { openPath } from '@tauri-apps/api/opener';
+ * import { documentDir, join } from '@tauri-apps/api/path';
+ * await openPath(await join(await documentDir(), 'report.pdf'));
+ * ```
+ *
+ * @since 2.3.0
+ */
+async function openPath(path: string): Promise<void> {
+	return invoke("plugin:opener|open_path", { path });
+}
+
+export { openUrl, openPath };
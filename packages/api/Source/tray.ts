@@ -16,9 +16,10 @@ export type TrayIconEventType =
 	| "DoubleClick"
 	| "Enter"
 	| "Move"
-	| "Leave";
+	| "Leave"
+	| "Attached";
 
-export type TrayIconEventBase<T extends TrayIconEventType> = {
+export type TrayIconEventBase<T extends Exclude<TrayIconEventType, "Attached">> = {
 	/** The tray icon event type */
 	type: T;
 	/** Id of the tray icon which triggered this event. */
@@ -40,6 +41,14 @@ export type TrayIconClickEvent = {
 	buttonState: MouseButtonState;
 };
 
+/** Emitted when a detached tray icon is shown again by `TrayIcon::attach` in Rust. */
+export type TrayIconAttachedEvent = {
+	/** The tray icon event type */
+	type: "Attached";
+	/** Id of the tray icon which triggered this event. */
+	id: string;
+};
+
 /**
  * Describes a tray icon event.
  *
@@ -54,19 +63,22 @@ export type TrayIconEvent =
 			Omit<TrayIconClickEvent, "buttonState">)
 	| TrayIconEventBase<"Enter">
 	| TrayIconEventBase<"Move">
-	| TrayIconEventBase<"Leave">;
-
-type RustTrayIconEvent = Omit<TrayIconEvent, "rect"> & {
-	rect: {
-		position: {
-			Physical: { x: number; y: number };
-		};
-
-		size: {
-			Physical: { width: number; height: number };
-		};
-	};
-};
+	| TrayIconEventBase<"Leave">
+	| TrayIconAttachedEvent;
+
+type RustTrayIconEvent =
+	| (Omit<Exclude<TrayIconEvent, TrayIconAttachedEvent>, "rect"> & {
+			rect: {
+				position: {
+					Physical: { x: number; y: number };
+				};
+
+				size: {
+					Physical: { width: number; height: number };
+				};
+			};
+	  })
+	| TrayIconAttachedEvent;
 
 /**
  * Tray icon types and utilities.
@@ -346,6 +358,10 @@ export class TrayIcon extends Resource {
 }
 
 function mapEvent(e: RustTrayIconEvent): TrayIconEvent {
+	if (e.type === "Attached") {
+		return e;
+	}
+
 	const out = e as unknown as TrayIconEvent;
 
 	out.position = new PhysicalPosition(e.position);
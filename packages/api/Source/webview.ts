@@ -42,7 +42,13 @@ import { Color, getCurrentWindow, Window } from "./window";
 
 /** The drag and drop event types. */
 type DragDropEvent =
-	| { type: "enter"; paths: string[]; position: PhysicalPosition }
+	| {
+			type: "enter";
+			paths: string[];
+			/** The MIME types of the dragged paths, `inode/directory` for directories. */
+			types: string[];
+			position: PhysicalPosition;
+	  }
 	| { type: "over"; position: PhysicalPosition }
 	| { type: "drop"; paths: string[]; position: PhysicalPosition }
 	| { type: "leave" };
@@ -395,6 +401,22 @@ class Webview {
 		).then((s) => new PhysicalSize(s));
 	}
 
+	/**
+	 * The webview zoom level, kept across navigations.
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
+	 * const zoom = await getCurrentWebview().zoom();
+	 * ```
+	 *
+	 * @returns The webview zoom level.
+	 */
+	async zoom(): Promise<number> {
+		return invoke<number>("plugin:webview|webview_zoom", {
+			label: this.label,
+		});
+	}
+
 	// Setters
 
 	/**
@@ -502,6 +524,33 @@ class Webview {
 		});
 	}
 
+	/**
+	 * Prints the webview contents to a PDF document.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:webview:allow-print-to-pdf`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows:** Requires WebView2 Runtime 1.0.1518 or later.
+	 * - **Linux:** `printBackground` is ignored, the backgrounds follow the `print-color-adjust` CSS property.
+	 * - **macOS:** The whole page is rendered on a single PDF page, the page size, margins and orientation are ignored.
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
+	 * const pdf = await getCurrentWebview().printToPdf({ landscape: true });
+	 * ```
+	 *
+	 * @returns The PDF document bytes.
+	 */
+	async printToPdf(options: PdfOptions = {}): Promise<Uint8Array> {
+		return invoke<ArrayBuffer>("plugin:webview|print_to_pdf", {
+			label: this.label,
+			options,
+		}).then((pdf) => new Uint8Array(pdf));
+	}
+
 	/**
 	 * Set webview zoom level.
 	 * @example
@@ -520,19 +569,45 @@ class Webview {
 	}
 
 	/**
-	 * Moves this webview to the given label.
+	 * Moves this webview to the given window, at the given bounds relative to it
+	 * or keeping its bounds if they are not set.
+	 *
+	 * Events and capabilities are resolved against the new window from then on.
 	 * @example
 	 * ```typescript
-	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
-	 * await getCurrentWebview().reparent('other-window');
+	 * import { getCurrentWebview, LogicalPosition, LogicalSize } from '@tauri-apps/api/webview';
+	 * await getCurrentWebview().reparent('other-window', {
+	 *   position: new LogicalPosition(0, 0),
+	 *   size: new LogicalSize(400, 300),
+	 * });
 	 * ```
 	 *
+	 * @param bounds Since 2.3.0
+	 *
 	 * @returns A promise indicating the success or failure of the operation.
 	 */
-	async reparent(window: Window | WebviewWindow | string): Promise<void> {
+	async reparent(
+		window: Window | WebviewWindow | string,
+		bounds?: {
+			position: LogicalPosition | PhysicalPosition | Position;
+			size: LogicalSize | PhysicalSize | Size;
+		},
+	): Promise<void> {
 		return invoke("plugin:webview|reparent", {
 			label: this.label,
 			window: typeof window === "string" ? window : window.label,
+			bounds: bounds
+				? {
+						position:
+							bounds.position instanceof Position
+								? bounds.position
+								: new Position(bounds.position),
+						size:
+							bounds.size instanceof Size
+								? bounds.size
+								: new Size(bounds.size),
+					}
+				: null,
 		});
 	}
 
@@ -550,6 +625,82 @@ class Webview {
 		return invoke("plugin:webview|clear_all_browsing_data");
 	}
 
+	/**
+	 * Removes the browsing data of the given kinds from the webview data store.
+	 *
+	 * The data store is shared by the webviews using the same data directory.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:webview:allow-clear-browsing-data`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows:** Requires WebView2 Runtime 1.0.1245 or later. `localStorage` does not include the `sessionStorage` data.
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
+	 * await getCurrentWebview().clearBrowsingData(['cookies', 'cache']);
+	 * ```
+	 *
+	 * @returns A promise resolved once the platform webview removed the data.
+	 */
+	async clearBrowsingData(types: BrowsingDataKind[]): Promise<void> {
+		return invoke("plugin:webview|clear_browsing_data", {
+			label: this.label,
+			types,
+		});
+	}
+
+	/**
+	 * Returns the cookies of the webview data store sent to `url`.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:webview:allow-cookies-for-url`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
+	 * const cookies = await getCurrentWebview().cookiesForUrl('https://tauri.app');
+	 * ```
+	 *
+	 * @returns The cookies sent to `url`.
+	 */
+	async cookiesForUrl(url: string): Promise<Cookie[]> {
+		return invoke<Cookie[]>("plugin:webview|cookies_for_url", {
+			label: this.label,
+			url,
+		});
+	}
+
+	/**
+	 * Deletes the cookie named `name` sent to `url` from the webview data store.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:webview:allow-delete-cookie`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from '@tauri-apps/api/webview';
+	 * await getCurrentWebview().deleteCookie('session', 'https://tauri.app');
+	 * ```
+	 *
+	 * @returns A promise resolved once the platform webview deleted the cookie.
+	 */
+	async deleteCookie(name: string, url: string): Promise<void> {
+		return invoke("plugin:webview|delete_cookie", {
+			label: this.label,
+			name,
+			url,
+		});
+	}
+
 	/**
 	 * Specify the webview background color.
 	 *
@@ -601,7 +752,11 @@ class Webview {
 	async onDragDropEvent(
 		handler: EventCallback<DragDropEvent>,
 	): Promise<UnlistenFn> {
-		type DragPayload = { paths: string[]; position: PhysicalPosition };
+		type DragPayload = {
+			paths: string[];
+			types: string[];
+			position: PhysicalPosition;
+		};
 
 		const unlistenDragEnter = await this.listen<DragPayload>(
 			TauriEvent.DRAG_ENTER,
@@ -611,6 +766,7 @@ class Webview {
 					payload: {
 						type: "enter",
 						paths: event.payload.paths,
+						types: event.payload.types,
 						position: new PhysicalPosition(event.payload.position),
 					},
 				});
@@ -658,6 +814,289 @@ class Webview {
 			unlistenDragLeave();
 		};
 	}
+
+	/**
+	 * Listen to page load events on the webview.
+	 *
+	 * Since a navigation replaces the current document, this is mostly useful to
+	 * observe other webviews, or failures of navigations that keep the current page.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { Webview } from "@tauri-apps/api/webview";
+	 * const webview = await Webview.getByLabel('content');
+	 * const unlisten = await webview?.onPageLoad((event) => {
+	 *   if (event.payload.event === 'failed') {
+	 *     console.log('Failed to load', event.payload.url, event.payload.error);
+	 *   }
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 *
+	 * @since 2.3.0
+	 */
+	async onPageLoad(
+		handler: EventCallback<PageLoadPayload>,
+	): Promise<UnlistenFn> {
+		return this.listen<PageLoadPayload>(TauriEvent.PAGE_LOAD, handler);
+	}
+
+	/**
+	 * Listen to the webview zoom level changes, made with {@link Webview.setZoom}
+	 * or by the user.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from "@tauri-apps/api/webview";
+	 * const unlisten = await getCurrentWebview().onZoomChanged(({ payload: zoom }) => {
+	 *   console.log('Zoom level changed to', zoom);
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * #### Platform-specific:
+	 *
+	 * - **macOS**: Pinch magnification is not a zoom change.
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onZoomChanged(handler: EventCallback<number>): Promise<UnlistenFn> {
+		return this.listen<number>(TauriEvent.WEBVIEW_ZOOM_CHANGED, handler);
+	}
+
+	/**
+	 * Listen to the match changes of the search started with {@link Webview.findInPage}.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from "@tauri-apps/api/webview";
+	 * const unlisten = await getCurrentWebview().onFindResultChanged(({ payload }) => {
+	 *   if (payload.activeMatchIndex !== null && payload.matchCount !== null) {
+	 *     console.log(`${payload.activeMatchIndex + 1} of ${payload.matchCount}`);
+	 *   }
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onFindResultChanged(
+		handler: EventCallback<FindResult>,
+	): Promise<UnlistenFn> {
+		return this.listen<FindResult>(
+			TauriEvent.WEBVIEW_FIND_RESULT_CHANGED,
+			handler,
+		);
+	}
+
+	/**
+	 * Listen to the back and forward navigation availability changes, to update the history navigation controls.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **macOS:** The changes are only checked when a page load starts or finishes, so the same-document navigations are reported with the next page load.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from "@tauri-apps/api/webview";
+	 * const unlisten = await getCurrentWebview().onHistoryChanged(({ payload }) => {
+	 *   backButton.disabled = !payload.canGoBack;
+	 *   forwardButton.disabled = !payload.canGoForward;
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onHistoryChanged(
+		handler: EventCallback<NavigationHistory>,
+	): Promise<UnlistenFn> {
+		return this.listen<NavigationHistory>(
+			TauriEvent.WEBVIEW_HISTORY_CHANGED,
+			handler,
+		);
+	}
+
+	/**
+	 * Listen to the devtools being opened, from the API or by the user, to update the devtools menu items.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from "@tauri-apps/api/webview";
+	 * const unlisten = await getCurrentWebview().onDevtoolsOpened(() => {
+	 *   console.log('devtools opened');
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onDevtoolsOpened(handler: EventCallback<null>): Promise<UnlistenFn> {
+		return this.listen<null>(TauriEvent.WEBVIEW_DEVTOOLS_OPENED, handler);
+	}
+
+	/**
+	 * Listen to the devtools being closed, from the API or by the user.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWebview } from "@tauri-apps/api/webview";
+	 * const unlisten = await getCurrentWebview().onDevtoolsClosed(() => {
+	 *   console.log('devtools closed');
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onDevtoolsClosed(handler: EventCallback<null>): Promise<UnlistenFn> {
+		return this.listen<null>(TauriEvent.WEBVIEW_DEVTOOLS_CLOSED, handler);
+	}
+}
+
+/**
+ * The kind of a page load failure.
+ *
+ * @since 2.3.0
+ */
+type PageLoadErrorKind = "http" | "tls" | "network" | "cancelled" | "other";
+
+/**
+ * Details of a failed page load.
+ *
+ * @since 2.3.0
+ */
+interface PageLoadError {
+	/** The kind of failure. */
+	kind: PageLoadErrorKind;
+	/** The platform description of the failure. */
+	description: string;
+	/** The HTTP status code of the response, if one was received. */
+	httpStatus: number | null;
+}
+
+/**
+ * The page load event payload.
+ *
+ * @since 2.3.0
+ */
+interface PageLoadPayload {
+	/** The page URL. */
+	url: string;
+	/** The page load event. */
+	event: "started" | "finished" | "failed";
+	/** The failure details, set when `event` is `failed`. */
+	error: PageLoadError | null;
+}
+
+/**
+ * The options used to print a webview to PDF. The sizes are in inches.
+ *
+ * @since 2.3.0
+ */
+interface PdfOptions {
+	/**
+	 * The page size, in portrait orientation. Defaults to US Letter, 8.5 x 11 inches.
+	 * Ignored on macOS.
+	 */
+	pageSize?: { width: number; height: number };
+	/** The page margins. Defaults to 0.4 inches on every side. Ignored on macOS. */
+	margins?: { top?: number; right?: number; bottom?: number; left?: number };
+	/** Whether the pages are in landscape orientation. Defaults to `false`. Ignored on macOS. */
+	landscape?: boolean;
+	/**
+	 * Whether the background colors and images are printed. Defaults to `false`.
+	 * Ignored on Linux, and always printed on macOS.
+	 */
+	printBackground?: boolean;
+}
+
+/**
+ * The options used to find text in a webview.
+ *
+ * @since 2.3.0
+ */
+interface FindOptions {
+	/** Whether the matches must have the same case as the query. Defaults to `false`. */
+	caseSensitive?: boolean;
+	/** Whether the search continues from the other end of the page. Defaults to `true`. */
+	wrapAround?: boolean;
+	/** Whether the search moves towards the beginning of the page. Defaults to `false`. */
+	backwards?: boolean;
+}
+
+/**
+ * The result of a search in a webview.
+ *
+ * @since 2.3.0
+ */
+interface FindResult {
+	/** Whether the query was found in the page. */
+	found: boolean;
+	/** The number of matches in the page, `null` if not reported by the platform webview. */
+	matchCount: number | null;
+	/** The index of the active match, starting at 0, `null` if not reported by the platform webview. */
+	activeMatchIndex: number | null;
+}
+
+/**
+ * The back and forward navigation availability of a webview.
+ *
+ * @since 2.3.0
+ */
+interface NavigationHistory {
+	/** Whether the webview can navigate back. */
+	canGoBack: boolean;
+	/** Whether the webview can navigate forward. */
+	canGoForward: boolean;
+}
+
+/**
+ * A kind of browsing data removed by {@link Webview.clearBrowsingData}.
+ *
+ * @since 2.3.0
+ */
+type BrowsingDataKind = "cookies" | "localStorage" | "indexedDb" | "cache";
+
+/**
+ * A cookie of the webview data store.
+ *
+ * @since 2.3.0
+ */
+interface Cookie {
+	name: string;
+	value: string;
+	/** The domain of the cookie, `null` for a host-only cookie. */
+	domain: string | null;
+	path: string | null;
+	/** Whether the cookie is only sent over secure connections. */
+	secure: boolean;
+	/** Whether the cookie is hidden from `document.cookie`. */
+	httpOnly: boolean;
+	/** The `SameSite` attribute of the cookie, `null` when the browser default applies. */
+	sameSite: "strict" | "lax" | "none" | null;
+	/** The expiration date in milliseconds since the Unix epoch, `null` for a session cookie. */
+	expires: number | null;
 }
 
 /**
@@ -738,6 +1177,15 @@ interface WebviewOptions {
 	 * - **Android / iOS**: Unsupported.
 	 */
 	zoomHotkeysEnabled?: boolean;
+	/**
+	 * The initial zoom factor of the webview, `1.0` being the default size.
+	 * The zoom is kept across the navigations of the webview.
+	 *
+	 * #### Platform-specific:
+	 *
+	 * - **Android / iOS**: Unsupported.
+	 */
+	zoom?: number;
 
 	/**
 	 * Sets whether the custom protocols should use `https://<scheme>.localhost` instead of the default `http://<scheme>.localhost` on Windows and Android. Defaults to `false`.
@@ -784,4 +1232,17 @@ interface WebviewOptions {
 
 export { Webview, getCurrentWebview, getAllWebviews };
 
-export type { DragDropEvent, WebviewOptions, Color };
+export type {
+	BrowsingDataKind,
+	Cookie,
+	DragDropEvent,
+	WebviewOptions,
+	Color,
+	FindOptions,
+	FindResult,
+	NavigationHistory,
+	PageLoadError,
+	PageLoadErrorKind,
+	PageLoadPayload,
+	PdfOptions,
+};
@@ -83,6 +83,34 @@ interface ScaleFactorChanged {
 	size: PhysicalSize;
 }
 
+/**
+ * A region of the window client area covered by the title bar controls, see {@link Window.titleBarRects}.
+ */
+interface TitleBarRect {
+	/** The top-left corner of the region, relative to the top-left corner of the client area. */
+	position: PhysicalPosition;
+	/** The region size. */
+	size: PhysicalSize;
+}
+
+/**
+ * An interactive region of the window, see {@link Window.setIgnoreCursorEventsRegion}.
+ *
+ * @since 2.3.0
+ */
+interface CursorRegion {
+	/** The top-left corner of the region, relative to the top-left corner of the client area. */
+	position: LogicalPosition | PhysicalPosition | Position;
+	/** The region size. */
+	size: LogicalSize | PhysicalSize | Size;
+}
+
+/** A title bar rect as serialized by the Rust side. */
+interface RawTitleBarRect {
+	position: { Physical: { x: number; y: number } };
+	size: { Physical: { width: number; height: number } };
+}
+
 /**
  * Attention type to request on a window.
  *
@@ -177,7 +205,7 @@ export enum ProgressBarStatus {
 	 */
 	Normal = "normal",
 	/**
-	 * Indeterminate state. **Treated as Normal on Linux and macOS**
+	 * Indeterminate state. **Treated as Normal on Linux**
 	 */
 	Indeterminate = "indeterminate",
 	/**
@@ -211,6 +239,23 @@ export interface ProgressBarState {
 	progress?: number;
 }
 
+/**
+ * The state of the taskbar or dock progress indicator, see {@link Window.setProgress}.
+ *
+ * The progress values range from `0` to `100`.
+ */
+export type ProgressState =
+	| {
+			status: ProgressBarStatus.None | ProgressBarStatus.Indeterminate;
+	  }
+	| {
+			status:
+				| ProgressBarStatus.Normal
+				| ProgressBarStatus.Paused
+				| ProgressBarStatus.Error;
+			progress: number;
+	  };
+
 /**
  * Get an instance of `Window` for the current window.
  *
@@ -824,6 +869,75 @@ class Window {
 		});
 	}
 
+	/**
+	 * Gets the regions of the window client area covered by the title bar controls,
+	 * so the content can be laid out around them.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **macOS:** The traffic lights, when the content extends under the title bar.
+	 * - **Windows:** The caption buttons, when the window is created with the title bar overlay.
+	 * - **Linux:** Unsupported, returns an empty list.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow } from '@tauri-apps/api/window';
+	 * const rects = await getCurrentWindow().titleBarRects();
+	 * ```
+	 *
+	 * @returns The regions covered by the title bar controls.
+	 */
+	async titleBarRects(): Promise<TitleBarRect[]> {
+		return invoke<RawTitleBarRect[]>("plugin:window|title_bar_rects", {
+			label: this.label,
+		}).then((rects) => rects.map(mapTitleBarRect));
+	}
+
+	/**
+	 * Captures the contents of the window's webview as an RGBA image.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:window:allow-capture`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows:** The webview is captured even if the window is occluded, but not when it is minimized.
+	 * - **Linux:** The webview is captured even if the window is occluded.
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow, LogicalPosition, LogicalSize } from '@tauri-apps/api/window';
+	 * const screenshot = await getCurrentWindow().capture();
+	 * const header = await getCurrentWindow().capture({
+	 *   position: new LogicalPosition(0, 0),
+	 *   size: new LogicalSize(800, 64),
+	 * });
+	 * ```
+	 *
+	 * @param region The region to capture, relative to the top-left corner of the webview. Defaults to the whole webview.
+	 * @returns The captured image.
+	 */
+	async capture(region?: {
+		position: LogicalPosition | PhysicalPosition | Position;
+		size: LogicalSize | PhysicalSize | Size;
+	}): Promise<Image> {
+		return invoke<number>("plugin:window|capture", {
+			label: this.label,
+			region: region
+				? {
+						position:
+							region.position instanceof Position
+								? region.position
+								: new Position(region.position),
+						size:
+							region.size instanceof Size
+								? region.size
+								: new Size(region.size),
+					}
+				: null,
+		}).then((rid) => new Image(rid));
+	}
+
 	// Setters
 
 	/**
@@ -1195,7 +1309,8 @@ class Window {
 	 *   - `false` has no effect on decorated window, shadows are always ON.
 	 *   - `true` will make undecorated window have a 1px white border,
 	 * and on Windows 11, it will have a rounded corners.
-	 * - **Linux:** Unsupported.
+	 * - **Linux:** Only has an effect on the windows created with a shadow, which
+	 *   keep the GTK client-side decorations drawing it when undecorated.
 	 *
 	 * @example
 	 * ```typescript
@@ -1344,7 +1459,7 @@ class Window {
 	}
 
 	/**
-	 * Sets the window inner size constraints.
+	 * Sets the window inner size constraints, resizing the window to satisfy them.
 	 * @example
 	 * ```typescript
 	 * import { getCurrentWindow } from '@tauri-apps/api/window';
@@ -1372,6 +1487,30 @@ class Window {
 		});
 	}
 
+	/**
+	 * Locks the window inner size to a width to height ratio, resizing the window to it.
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow } from '@tauri-apps/api/window';
+	 * await getCurrentWindow().setAspectRatio(16 / 9);
+	 * ```
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows:** the aspect ratio is only kept when the window is resized by the user.
+	 *
+	 * @param ratio The width to height ratio, or `null` to unlock the inner size.
+	 * @returns A promise indicating the success or failure of the operation.
+	 *
+	 * @since 2.3.0
+	 */
+	async setAspectRatio(ratio: number | null): Promise<void> {
+		return invoke("plugin:window|set_aspect_ratio", {
+			label: this.label,
+			value: ratio,
+		});
+	}
+
 	/**
 	 * Sets the window outer position.
 	 * @example
@@ -1413,6 +1552,28 @@ class Window {
 		});
 	}
 
+	/**
+	 * Puts the window in fullscreen on the given monitor.
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow, availableMonitors } from '@tauri-apps/api/window';
+	 * const [, secondary] = await availableMonitors();
+	 * await getCurrentWindow().setFullscreenOnMonitor(secondary);
+	 * ```
+	 *
+	 * This command is not part of the default permissions, enable it with `core:window:allow-set-fullscreen-on-monitor`.
+	 *
+	 * @param monitor One of the monitors returned by {@linkcode availableMonitors}.
+	 * @returns A promise indicating the success or failure of the operation, rejected when the monitor is not connected.
+	 */
+	async setFullscreenOnMonitor(monitor: Monitor): Promise<void> {
+		return invoke("plugin:window|set_fullscreen_on_monitor", {
+			label: this.label,
+			name: monitor.name,
+			position: monitor.position,
+		});
+	}
+
 	/**
 	 * Bring the window to front and focus.
 	 * @example
@@ -1604,6 +1765,48 @@ class Window {
 		});
 	}
 
+	/**
+	 * Ignores the window cursor events outside of the interactive `regions`, so the clicks pass through to the windows behind.
+	 *
+	 * The regions can be updated on every frame to follow the page layout. Ignoring the cursor events with {@link Window.setIgnoreCursorEvents} takes precedence over the regions.
+	 *
+	 * This command is not part of the default permissions, enable it with `core:window:allow-set-ignore-cursor-events-region`.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows / macOS:** Every mouse event is hit tested against the regions before it reaches a window, and the whole window ignores the cursor events while the cursor is outside of them. The mouse events are read with a low-level mouse hook on Windows.
+	 * - **Linux:** The regions are the window input shape, which requires a compositor supporting it on Wayland.
+	 * - **iOS / Android:** Unsupported.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow } from '@tauri-apps/api/window';
+	 * import { LogicalPosition, LogicalSize } from '@tauri-apps/api/dpi';
+	 * const { x, y, width, height } = document.querySelector('#toolbar').getBoundingClientRect();
+	 * await getCurrentWindow().setIgnoreCursorEventsRegion([
+	 *   { position: new LogicalPosition(x, y), size: new LogicalSize(width, height) },
+	 * ]);
+	 * ```
+	 *
+	 * @param regions The interactive regions, `null` to handle the cursor events of the whole window again.
+	 * @returns A promise indicating the success or failure of the operation.
+	 */
+	async setIgnoreCursorEventsRegion(
+		regions: CursorRegion[] | null,
+	): Promise<void> {
+		return invoke("plugin:window|set_ignore_cursor_events_region", {
+			label: this.label,
+			value:
+				regions?.map(({ position, size }) => ({
+					position:
+						position instanceof Position
+							? position
+							: new Position(position),
+					size: size instanceof Size ? size : new Size(size),
+				})) ?? null,
+		});
+	}
+
 	/**
 	 * Starts dragging the window.
 	 * @example
@@ -1642,7 +1845,7 @@ class Window {
 	 *
 	 * #### Platform-specific
 	 *
-	 * - **Windows**: Unsupported. Use @{linkcode Window.setOverlayIcon} instead.
+	 * - **Windows**: The count is drawn as the taskbar overlay icon of this window, counts above 99 are shown as `99+`.
 	 *
 	 * @example
 	 * ```typescript
@@ -1661,7 +1864,12 @@ class Window {
 	}
 
 	/**
-	 * Sets the badge cont **macOS only**.
+	 * Sets the badge label, for non-numeric badges.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Windows**: The label is drawn as the taskbar overlay icon of this window, so it should be a few characters at most.
+	 * - **Linux / iOS / Android**: Unsupported.
 	 *
 	 * @example
 	 * ```typescript
@@ -1736,6 +1944,36 @@ class Window {
 		});
 	}
 
+	/**
+	 * Sets the taskbar or dock progress indicator.
+	 *
+	 * Setting the `none` status hides the indicator and resets its progress.
+	 *
+	 * #### Platform-specific
+	 *
+	 * - **Linux / macOS**: The progress is app-wide and not specific to this window.
+	 * - **Linux**: Shown by desktop environments implementing the `libunity` launcher API. The request is ignored when there is no D-Bus session bus.
+	 * - **macOS**: The indeterminate state is animated on the dock tile.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow, ProgressBarStatus } from '@tauri-apps/api/window';
+	 * await getCurrentWindow().setProgress({
+	 *   status: ProgressBarStatus.Paused,
+	 *   progress: 50,
+	 * });
+	 * await getCurrentWindow().setProgress({ status: ProgressBarStatus.None });
+	 * ```
+	 *
+	 * @return A promise indicating the success or failure of the operation.
+	 */
+	async setProgress(state: ProgressState): Promise<void> {
+		return invoke("plugin:window|set_progress", {
+			label: this.label,
+			value: state,
+		});
+	}
+
 	/**
 	 * Sets whether the window should be visible on all workspaces or virtual desktops.
 	 *
@@ -1900,7 +2138,11 @@ class Window {
 	async onDragDropEvent(
 		handler: EventCallback<DragDropEvent>,
 	): Promise<UnlistenFn> {
-		type DragPayload = { paths: string[]; position: PhysicalPosition };
+		type DragPayload = {
+			paths: string[];
+			types: string[];
+			position: PhysicalPosition;
+		};
 
 		const unlistenDrag = await this.listen<DragPayload>(
 			TauriEvent.DRAG_ENTER,
@@ -1910,6 +2152,7 @@ class Window {
 					payload: {
 						type: "enter",
 						paths: event.payload.paths,
+						types: event.payload.types,
 						position: new PhysicalPosition(event.payload.position),
 					},
 				});
@@ -2050,6 +2293,62 @@ class Window {
 	async onThemeChanged(handler: EventCallback<Theme>): Promise<UnlistenFn> {
 		return this.listen<Theme>(TauriEvent.WINDOW_THEME_CHANGED, handler);
 	}
+
+	/**
+	 * Listen to the disconnection of the monitor the window is fullscreen on,
+	 * the window being moved to the primary monitor.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow } from "@tauri-apps/api/window";
+	 * const unlisten = await getCurrentWindow().onFullscreenMonitorDisconnected(() => {
+	 *  console.log('Fullscreen monitor disconnected');
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onFullscreenMonitorDisconnected(
+		handler: EventCallback<null>,
+	): Promise<UnlistenFn> {
+		return this.listen<null>(
+			TauriEvent.WINDOW_FULLSCREEN_MONITOR_DISCONNECTED,
+			handler,
+		);
+	}
+
+	/**
+	 * Listen to the changes of the regions covered by the title bar controls,
+	 * after a resize or a scale factor or theme change.
+	 *
+	 * @example
+	 * ```typescript
+	 * import { getCurrentWindow } from "@tauri-apps/api/window";
+	 * const unlisten = await getCurrentWindow().onTitleBarRectsChanged(({ payload: rects }) => {
+	 *  console.log('Title bar controls', rects);
+	 * });
+	 *
+	 * // you need to call unlisten if your handler goes out of scope e.g. the component is unmounted
+	 * unlisten();
+	 * ```
+	 *
+	 * @returns A promise resolving to a function to unlisten to the event.
+	 * Note that removing the listener is required if your listener goes out of scope e.g. the component is unmounted.
+	 */
+	async onTitleBarRectsChanged(
+		handler: EventCallback<TitleBarRect[]>,
+	): Promise<UnlistenFn> {
+		return this.listen<RawTitleBarRect[]>(
+			TauriEvent.WINDOW_TITLE_BAR_RECTS_CHANGED,
+			(e) => {
+				handler({ ...e, payload: e.payload.map(mapTitleBarRect) });
+			},
+		);
+	}
 }
 
 /**
@@ -2268,6 +2567,12 @@ interface WindowOptions {
 	title?: string;
 	/** Whether the window is in fullscreen mode or not. */
 	fullscreen?: boolean;
+	/**
+	 * The monitor to go fullscreen on, by index in {@linkcode availableMonitors} or by name.
+	 *
+	 * @since 2.3.0
+	 */
+	fullscreenMonitor?: number | string;
 	/** Whether the window will be initially focused or not. */
 	focus?: boolean;
 	/**
@@ -2299,7 +2604,8 @@ interface WindowOptions {
 	 *   - `false` has no effect on decorated window, shadows are always ON.
 	 *   - `true` will make undecorated window have a 1px white border,
 	 * and on Windows 11, it will have a rounded corners.
-	 * - **Linux:** Unsupported.
+	 * - **Linux:** `true` creates the window with the GTK client-side decorations,
+	 *   which draw the shadow and the resize borders around it when undecorated.
 	 *
 	 * @since 2.0.0
 	 */
@@ -2384,6 +2690,13 @@ interface WindowOptions {
 	backgroundColor?: Color;
 }
 
+function mapTitleBarRect(rect: RawTitleBarRect): TitleBarRect {
+	return {
+		position: new PhysicalPosition(rect.position),
+		size: new PhysicalSize(rect.size),
+	};
+}
+
 function mapMonitor(m: Monitor | null): Monitor | null {
 	return m === null
 		? null
@@ -2502,6 +2815,8 @@ export type {
 	Theme,
 	TitleBarStyle,
 	ScaleFactorChanged,
+	TitleBarRect,
+	CursorRegion,
 	WindowOptions,
 	Color,
 	DragDropEvent,